@@ -0,0 +1,20 @@
+use std::path::Path;
+use std::process::Command;
+
+/// Embeds `git describe` in the run metadata of JSON reports; empty when
+/// building outside a git checkout, e.g. from a crates.io tarball
+fn main() {
+    let describe = Command::new("git")
+        .args(["describe", "--tags", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .unwrap_or_default();
+    println!("cargo:rustc-env=POSTGREAT_GIT_DESCRIBE={}", describe.trim());
+    for path in [".git/HEAD", ".git/index"] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={path}");
+        }
+    }
+}
@@ -0,0 +1,628 @@
+use crate::models::{
+    unit_bytes, ByteSize, LoggedPlanFinding, PlanNodeStat, PlanRegression, PlanRegressionKind,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Outer rows above which a nested loop usually loses to a hash or merge join
+const NESTED_LOOP_OUTER_ROWS: f64 = 10_000.0;
+
+/// Plan nodes kept per statement in the report
+const MAX_NODES: usize = 5;
+
+/// A statement whose sort and hash nodes could together claim this much
+/// memory under the current work_mem is reported
+const WORK_MEM_EXPOSURE_BYTES: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Nodes that may each use up to work_mem
+const WORK_MEM_NODES: &[&str] = &[
+    "Sort",
+    "Incremental Sort",
+    "Materialize",
+    "WindowAgg",
+    "Recursive Union",
+];
+
+/// work_mem and hash_mem_multiplier the logged plans ran with, unless a plan
+/// lists its own under `Settings` (`auto_explain.log_settings`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlanMemory {
+    pub work_mem: u64,
+    pub hash_mem_multiplier: f64,
+}
+
+impl Default for PlanMemory {
+    /// PostgreSQL's defaults
+    fn default() -> Self {
+        Self {
+            work_mem: 4 * 1024 * 1024,
+            hash_mem_multiplier: 2.0,
+        }
+    }
+}
+
+impl PlanMemory {
+    fn with_settings(self, settings: Option<&Value>) -> Self {
+        let setting = |name: &str| settings?.get(name)?.as_str();
+        Self {
+            work_mem: setting("work_mem")
+                .and_then(|value| parse_memory_setting(value).ok())
+                .unwrap_or(self.work_mem),
+            hash_mem_multiplier: setting("hash_mem_multiplier")
+                .and_then(|value| value.trim().parse().ok())
+                .unwrap_or(self.hash_mem_multiplier),
+        }
+    }
+}
+
+/// `64MB`, `1GB` or a bare number of kilobytes, as work_mem is written in
+/// postgresql.conf
+pub fn parse_memory_setting(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let number: f64 = value[..split]
+        .parse()
+        .map_err(|_| format!("invalid memory size '{value}'"))?;
+    let unit = match value[split..].trim() {
+        "" => "kB",
+        unit => unit,
+    };
+    let bytes = unit_bytes(unit)
+        .ok_or_else(|| format!("invalid memory unit in '{value}'; expected B, kB, MB, GB or TB"))?;
+    Ok((number * bytes) as u64)
+}
+
+/// Root node, query text and non-default settings of one
+/// `auto_explain.log_format = json` entry
+pub(crate) struct LoggedPlan {
+    pub query_text: Option<String>,
+    pub root: Value,
+    pub settings: Option<Value>,
+}
+
+/// Aggregates auto_explain plans per statement, keyed by the same ID as the
+/// logged statements so plans and slow queries line up in the report
+#[derive(Default)]
+pub(crate) struct PlanCollector {
+    statements: HashMap<i64, PlanStatement>,
+    memory: PlanMemory,
+}
+
+/// Memory the work_mem-limited nodes of one plan may claim at once
+#[derive(Default)]
+struct MemoryExposure {
+    bytes: f64,
+    nodes: usize,
+    hash_nodes: usize,
+    /// Most processes running any one node: the leader plus parallel workers
+    processes: f64,
+}
+
+struct PlanStatement {
+    finding: LoggedPlanFinding,
+    nodes: HashMap<String, PlanNodeStat>,
+    /// Size of the occurrence each regression's `detail` describes
+    worst: HashMap<PlanRegressionKind, f64>,
+}
+
+/// What one plan contributed, before it is merged into its statement
+#[derive(Default)]
+struct PlanVisit {
+    nodes: Vec<(String, f64)>,
+    regressions: Vec<(PlanRegressionKind, f64, String)>,
+    analyzed: bool,
+}
+
+/// Parses the JSON after `plan:`; `None` for text-format plans. auto_explain
+/// writes a single object, older releases an array holding it.
+pub(crate) fn parse_plan(json: &str) -> Option<LoggedPlan> {
+    let value: Value = serde_json::from_str(json).ok()?;
+    let mut value = match value {
+        Value::Array(mut items) if !items.is_empty() => items.swap_remove(0),
+        value => value,
+    };
+    let object = value.as_object_mut()?;
+    let root = object.remove("Plan")?;
+    let query_text = object
+        .get("Query Text")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let settings = object.remove("Settings");
+    Some(LoggedPlan {
+        query_text,
+        root,
+        settings,
+    })
+}
+
+impl PlanCollector {
+    pub(crate) fn new(memory: PlanMemory) -> Self {
+        Self {
+            statements: HashMap::new(),
+            memory,
+        }
+    }
+
+    pub(crate) fn add(
+        &mut self,
+        queryid: i64,
+        query_text: &str,
+        duration_ms: f64,
+        plan: &LoggedPlan,
+    ) {
+        let mut visit = PlanVisit::default();
+        visit_node(&plan.root, &mut visit);
+
+        let memory = self.memory.with_settings(plan.settings.as_ref());
+        let mut exposure = MemoryExposure::default();
+        measure_memory(&plan.root, &memory, 1.0, &mut exposure);
+        if exposure.bytes >= WORK_MEM_EXPOSURE_BYTES {
+            visit.regressions.push((
+                PlanRegressionKind::WorkMemExposure,
+                exposure.bytes,
+                format!(
+                    "{} memory nodes ({} hash) in up to {:.0} processes could use {} with work_mem {} and hash_mem_multiplier {}",
+                    exposure.nodes,
+                    exposure.hash_nodes,
+                    exposure.processes,
+                    ByteSize(exposure.bytes as i64),
+                    ByteSize(memory.work_mem as i64),
+                    memory.hash_mem_multiplier
+                ),
+            ));
+        }
+
+        let statement = self
+            .statements
+            .entry(queryid)
+            .or_insert_with(|| PlanStatement {
+                finding: LoggedPlanFinding {
+                    queryid,
+                    query_text: query_text.to_string(),
+                    plans: 0,
+                    total_time_ms: 0.0,
+                    max_time_ms: 0.0,
+                    analyzed: false,
+                    nodes: Vec::new(),
+                    regressions: Vec::new(),
+                },
+                nodes: HashMap::new(),
+                worst: HashMap::new(),
+            });
+        let finding = &mut statement.finding;
+        finding.plans += 1;
+        finding.total_time_ms += duration_ms;
+        finding.max_time_ms = finding.max_time_ms.max(duration_ms);
+        finding.analyzed |= visit.analyzed;
+
+        for (node, rows) in visit.nodes {
+            let stat = statement
+                .nodes
+                .entry(node)
+                .or_insert_with_key(|node| PlanNodeStat {
+                    node: node.clone(),
+                    count: 0,
+                    rows: 0.0,
+                });
+            stat.count += 1;
+            stat.rows += rows;
+        }
+
+        // Each kind counts once per plan, described by its worst occurrence
+        visit.regressions.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let mut seen = Vec::new();
+        for (kind, size, detail) in visit.regressions {
+            if seen.contains(&kind) {
+                continue;
+            }
+            seen.push(kind);
+            let index = match finding
+                .regressions
+                .iter()
+                .position(|regression| regression.kind == kind)
+            {
+                Some(index) => index,
+                None => {
+                    finding.regressions.push(PlanRegression {
+                        kind,
+                        plans: 0,
+                        detail: String::new(),
+                        suggestion: regression_suggestion(kind).to_string(),
+                    });
+                    finding.regressions.len() - 1
+                }
+            };
+            let regression = &mut finding.regressions[index];
+            regression.plans += 1;
+            let worst = statement.worst.entry(kind).or_insert(f64::MIN);
+            if size > *worst {
+                *worst = size;
+                regression.detail = detail;
+            }
+        }
+    }
+
+    /// Statements with regressions first, then by time spent in logged plans
+    pub(crate) fn finish(self, limit: usize) -> Vec<LoggedPlanFinding> {
+        let mut findings: Vec<LoggedPlanFinding> = self
+            .statements
+            .into_values()
+            .map(|statement| {
+                let mut finding = statement.finding;
+                finding.nodes = statement.nodes.into_values().collect();
+                finding
+                    .nodes
+                    .sort_by(|a, b| b.rows.total_cmp(&a.rows).then(a.node.cmp(&b.node)));
+                finding.nodes.truncate(MAX_NODES);
+                finding
+                    .regressions
+                    .sort_by_key(|a| std::cmp::Reverse(a.plans));
+                finding
+            })
+            .collect();
+        findings.sort_by(|a, b| {
+            a.regressions
+                .is_empty()
+                .cmp(&b.regressions.is_empty())
+                .then(b.total_time_ms.total_cmp(&a.total_time_ms))
+        });
+        findings.truncate(limit);
+        findings
+    }
+}
+
+fn visit_node(node: &Value, visit: &mut PlanVisit) {
+    visit.analyzed |= node.get("Actual Rows").is_some();
+    visit.nodes.push((node_label(node), node_rows(node)));
+
+    let children = node
+        .get("Plans")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let number = |field: &str| node.get(field).and_then(Value::as_f64);
+    match node.get("Node Type").and_then(Value::as_str) {
+        Some("Nested Loop") => {
+            if let [outer, inner, ..] = children {
+                let rows = node_rows(outer);
+                if rows >= NESTED_LOOP_OUTER_ROWS {
+                    let estimated = if outer.get("Actual Rows").is_some() {
+                        ""
+                    } else {
+                        " (estimated)"
+                    };
+                    visit.regressions.push((
+                        PlanRegressionKind::NestedLoopLargeOuter,
+                        rows,
+                        format!(
+                            "{rows:.0} outer rows{estimated} from {} each probe {}",
+                            node_label(outer),
+                            node_label(inner)
+                        ),
+                    ));
+                }
+            }
+        }
+        Some("Sort") => {
+            let method = node
+                .get("Sort Method")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let on_disk = node.get("Sort Space Type").and_then(Value::as_str) == Some("Disk")
+                || method.starts_with("external");
+            if on_disk {
+                let kb = number("Sort Space Used").unwrap_or(0.0);
+                let keys = node
+                    .get("Sort Key")
+                    .and_then(Value::as_array)
+                    .map(|keys| {
+                        keys.iter()
+                            .filter_map(Value::as_str)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .unwrap_or_default();
+                visit.regressions.push((
+                    PlanRegressionKind::SortSpill,
+                    kb,
+                    format!(
+                        "{method} sort by {keys} wrote {} to disk",
+                        ByteSize(kb as i64 * 1024)
+                    ),
+                ));
+            }
+        }
+        Some("Hash") => {
+            let batches = number("Hash Batches").unwrap_or(1.0);
+            if batches > 1.0 {
+                let peak_kb = number("Peak Memory Usage").unwrap_or(0.0);
+                visit.regressions.push((
+                    PlanRegressionKind::HashSpill,
+                    batches,
+                    format!(
+                        "{batches:.0} batches with {} in memory",
+                        ByteSize(peak_kb as i64 * 1024)
+                    ),
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    for child in children {
+        visit_node(child, visit);
+    }
+}
+
+/// Adds up the work_mem budget of every sort, hash and materializing node,
+/// times the processes running it: nodes below a Gather run in each planned
+/// worker and, by default, the leader too
+fn measure_memory(
+    node: &Value,
+    memory: &PlanMemory,
+    processes: f64,
+    exposure: &mut MemoryExposure,
+) {
+    let node_type = node
+        .get("Node Type")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let hashed = matches!(node_type, "Hash" | "Memoize")
+        || (matches!(node_type, "Aggregate" | "SetOp")
+            && matches!(
+                node.get("Strategy").and_then(Value::as_str),
+                Some("Hashed" | "Mixed")
+            ));
+    let budget = if hashed {
+        Some(memory.work_mem as f64 * memory.hash_mem_multiplier)
+    } else if WORK_MEM_NODES.contains(&node_type) {
+        Some(memory.work_mem as f64)
+    } else {
+        None
+    };
+    if let Some(budget) = budget {
+        exposure.bytes += budget * processes;
+        exposure.nodes += 1;
+        exposure.hash_nodes += usize::from(hashed);
+        exposure.processes = exposure.processes.max(processes);
+    }
+
+    let below = match node_type {
+        "Gather" | "Gather Merge" => {
+            node.get("Workers Planned")
+                .and_then(Value::as_f64)
+                .unwrap_or(0.0)
+                + 1.0
+        }
+        _ => processes,
+    };
+    for child in node
+        .get("Plans")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+    {
+        measure_memory(child, memory, below, exposure);
+    }
+}
+
+/// `Seq Scan on orders`, or just the node type for nodes without a relation
+fn node_label(node: &Value) -> String {
+    let node_type = node
+        .get("Node Type")
+        .and_then(Value::as_str)
+        .unwrap_or("Unknown");
+    match node.get("Relation Name").and_then(Value::as_str) {
+        Some(relation) => format!("{node_type} on {relation}"),
+        None => node_type.to_string(),
+    }
+}
+
+/// Rows a node produced over all its loops, or the planner's estimate for
+/// plans logged without `auto_explain.log_analyze`
+fn node_rows(node: &Value) -> f64 {
+    let number = |field: &str| node.get(field).and_then(Value::as_f64);
+    match number("Actual Rows") {
+        Some(rows) => rows * number("Actual Loops").unwrap_or(1.0),
+        None => number("Plan Rows").unwrap_or(0.0),
+    }
+}
+
+fn regression_suggestion(kind: PlanRegressionKind) -> &'static str {
+    match kind {
+        PlanRegressionKind::NestedLoopLargeOuter => {
+            "Check the row estimate of the outer side (ANALYZE, extended statistics); with a realistic estimate the planner picks a hash or merge join"
+        }
+        PlanRegressionKind::SortSpill => {
+            "Raise work_mem for this statement's role, or add an index that returns rows in the sort order"
+        }
+        PlanRegressionKind::HashSpill => {
+            "Raise work_mem or hash_mem_multiplier for this statement's role so the hash table fits in memory"
+        }
+        PlanRegressionKind::WorkMemExposure => {
+            "work_mem limits each sort and hash node in each process, not the query: lower work_mem or max_parallel_workers_per_gather for this statement's role, or give it only to the reports that need it"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const ANALYZED_PLAN: &str = r#"{
+  "Query Text": "SELECT * FROM orders o JOIN items i ON i.order_id = o.id ORDER BY o.created_at",
+  "Plan": {
+    "Node Type": "Sort",
+    "Plan Rows": 1000,
+    "Actual Rows": 250000,
+    "Actual Loops": 1,
+    "Sort Key": ["o.created_at"],
+    "Sort Method": "external merge",
+    "Sort Space Used": 49152,
+    "Sort Space Type": "Disk",
+    "Plans": [
+      {
+        "Node Type": "Nested Loop",
+        "Plan Rows": 1000,
+        "Actual Rows": 250000,
+        "Actual Loops": 1,
+        "Plans": [
+          {
+            "Node Type": "Seq Scan",
+            "Relation Name": "orders",
+            "Plan Rows": 100,
+            "Actual Rows": 50000,
+            "Actual Loops": 1
+          },
+          {
+            "Node Type": "Index Scan",
+            "Relation Name": "items",
+            "Plan Rows": 10,
+            "Actual Rows": 5,
+            "Actual Loops": 50000
+          }
+        ]
+      }
+    ]
+  }
+}"#;
+
+    #[test]
+    fn parses_single_object_and_array_plans() {
+        let plan = parse_plan(ANALYZED_PLAN).unwrap();
+        assert!(plan.query_text.unwrap().starts_with("SELECT * FROM orders"));
+        assert_eq!(plan.root["Node Type"], "Sort");
+
+        let array = format!("[{ANALYZED_PLAN}]");
+        assert!(parse_plan(&array).is_some());
+        assert!(
+            parse_plan("Query Text: SELECT 1\nResult  (cost=0.00..0.01 rows=1 width=4)").is_none()
+        );
+    }
+
+    #[test]
+    fn flags_nested_loops_over_large_outer_sides_and_sort_spills() {
+        let plan = parse_plan(ANALYZED_PLAN).unwrap();
+        let mut collector = PlanCollector::default();
+        collector.add(7, "SELECT ...", 1500.0, &plan);
+        collector.add(7, "SELECT ...", 500.0, &plan);
+        let findings = collector.finish(10);
+
+        let finding = &findings[0];
+        assert_eq!(finding.plans, 2);
+        assert_eq!(finding.total_time_ms, 2000.0);
+        assert!(finding.analyzed);
+        assert_eq!(finding.nodes[0].node, "Index Scan on items");
+        assert_eq!(finding.nodes[0].rows, 500_000.0);
+
+        let regression = |kind| {
+            finding
+                .regressions
+                .iter()
+                .find(|regression| regression.kind == kind)
+                .unwrap()
+        };
+        let sort = regression(PlanRegressionKind::SortSpill);
+        assert_eq!(sort.plans, 2);
+        assert_eq!(
+            sort.detail,
+            "external merge sort by o.created_at wrote 48 MB to disk"
+        );
+        let nested_loop = regression(PlanRegressionKind::NestedLoopLargeOuter);
+        assert_eq!(nested_loop.plans, 2);
+        assert_eq!(
+            nested_loop.detail,
+            "50000 outer rows from Seq Scan on orders each probe Index Scan on items"
+        );
+        assert_eq!(finding.regressions.len(), 2);
+    }
+
+    #[test]
+    fn estimated_plans_use_planner_rows() {
+        let plan = parse_plan(
+            r#"{"Query Text": "SELECT 1", "Plan": {"Node Type": "Hash Join", "Plan Rows": 20,
+                "Plans": [{"Node Type": "Seq Scan", "Relation Name": "events", "Plan Rows": 20},
+                          {"Node Type": "Hash", "Plan Rows": 5}]}}"#,
+        )
+        .unwrap();
+        let mut collector = PlanCollector::default();
+        collector.add(1, "SELECT 1", 10.0, &plan);
+        let findings = collector.finish(10);
+        assert!(!findings[0].analyzed);
+        assert!(findings[0].regressions.is_empty());
+        assert_eq!(findings[0].nodes[0].rows, 20.0);
+    }
+
+    const PARALLEL_PLAN: &str = r#"{
+  "Query Text": "SELECT customer_id, count(*) FROM orders JOIN items USING (order_id) GROUP BY 1",
+  "Settings": {"work_mem": "256MB"},
+  "Plan": {
+    "Node Type": "Aggregate",
+    "Strategy": "Sorted",
+    "Plan Rows": 1000,
+    "Plans": [
+      {
+        "Node Type": "Gather Merge",
+        "Workers Planned": 3,
+        "Plan Rows": 1000,
+        "Plans": [
+          {
+            "Node Type": "Sort",
+            "Plan Rows": 1000,
+            "Plans": [
+              {
+                "Node Type": "Hash Join",
+                "Plan Rows": 1000,
+                "Plans": [
+                  {"Node Type": "Seq Scan", "Relation Name": "items", "Plan Rows": 1000},
+                  {"Node Type": "Hash", "Plan Rows": 100,
+                   "Plans": [{"Node Type": "Seq Scan", "Relation Name": "orders", "Plan Rows": 100}]}
+                ]
+              }
+            ]
+          }
+        ]
+      }
+    ]
+  }
+}"#;
+
+    #[test]
+    fn flags_plans_whose_work_mem_budget_reaches_gigabytes() {
+        let plan = parse_plan(PARALLEL_PLAN).unwrap();
+        let mut collector = PlanCollector::default();
+        collector.add(3, "SELECT ...", 900.0, &plan);
+        let findings = collector.finish(10);
+
+        let regression = &findings[0].regressions[0];
+        assert_eq!(regression.kind, PlanRegressionKind::WorkMemExposure);
+        assert_eq!(
+            regression.detail,
+            "2 memory nodes (1 hash) in up to 4 processes could use 3 GB with work_mem 256 MB and hash_mem_multiplier 2"
+        );
+
+        // Without its Settings the plan is measured against the given work_mem
+        let mut plan = plan;
+        plan.settings = None;
+        let mut collector = PlanCollector::new(PlanMemory::default());
+        collector.add(3, "SELECT ...", 900.0, &plan);
+        assert!(collector.finish(10)[0].regressions.is_empty());
+    }
+
+    #[rstest]
+    #[case("4MB", Ok(4 * 1024 * 1024))]
+    #[case("65536", Ok(64 * 1024 * 1024))]
+    #[case(" 1GB ", Ok(1024 * 1024 * 1024))]
+    #[case("1.5MB", Ok(1536 * 1024))]
+    #[case("64mb", Err(()))]
+    #[case("lots", Err(()))]
+    fn memory_settings_parse_like_postgresql_conf(
+        #[case] value: &str,
+        #[case] expected: Result<u64, ()>,
+    ) {
+        assert_eq!(parse_memory_setting(value).map_err(|_| ()), expected);
+    }
+}
@@ -1,6 +1,9 @@
-use crate::analysis::{get_param, param_value_as_megabytes, param_value_as_seconds};
+use crate::analysis::{get_param, param_value, param_value_as_megabytes, param_value_as_seconds};
 use crate::checker::CheckerError;
-use crate::models::{AnalysisResults, ConfigCategory, ConfigSuggestion, SuggestionLevel};
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ConfigCategory, ConfigSuggestion, ParamValue, SuggestionLevel,
+};
 use std::collections::HashMap;
 
 type Result<T> = std::result::Result<T, CheckerError>;
@@ -26,7 +29,9 @@ fn analyze_autovacuum_max_workers(
     results: &mut AnalysisResults,
 ) -> Result<()> {
     let current_value = get_param_value(params, "autovacuum_max_workers");
-    let current = current_value.parse::<usize>().unwrap_or(3);
+    let current = param_value(params, "autovacuum_max_workers")
+        .and_then(ParamValue::as_i64)
+        .unwrap_or(3);
 
     // Default is 3, recommended to increase to 5 for better responsiveness
     if current < 5 {
@@ -37,9 +42,7 @@ fn analyze_autovacuum_max_workers(
             &current_value,
             "5",
             SuggestionLevel::Important,
-            "autovacuum_max_workers is too low. Default of 3 is often insufficient for \
-             servers with many active databases and tables. Increasing to 5 allows more \
-             parallel autovacuum processes to keep up with write-heavy workloads.",
+            Message::new("autovacuum.max_workers"),
         );
     }
 
@@ -63,9 +66,7 @@ fn analyze_autovacuum_naptime(
                 &current_value,
                 "30s",
                 SuggestionLevel::Recommended,
-                "autovacuum_naptime controls how often the launcher checks for work. \
-                 Lowering it to 30s makes autovacuum more responsive on high-churn systems, \
-                 especially those with many databases and tables.",
+                Message::new("autovacuum.naptime"),
             );
         }
     }
@@ -78,7 +79,14 @@ fn analyze_autovacuum_vacuum_cost_limit(
     results: &mut AnalysisResults,
 ) -> Result<()> {
     let current_value = get_param_value(params, "autovacuum_vacuum_cost_limit");
-    let current = current_value.parse::<u64>().unwrap_or(200);
+    let current =
+        match param_value(params, "autovacuum_vacuum_cost_limit").and_then(ParamValue::as_i64) {
+            Some(limit) if limit >= 0 => limit,
+            // -1 inherits vacuum_cost_limit
+            _ => param_value(params, "vacuum_cost_limit")
+                .and_then(ParamValue::as_i64)
+                .unwrap_or(200),
+        };
 
     // Default is 200 (inherited from vacuum_cost_limit),
     // recommended to increase to 2000 for better throughput
@@ -96,10 +104,7 @@ fn analyze_autovacuum_vacuum_cost_limit(
             &current_value,
             "2000",
             level,
-            "autovacuum_vacuum_cost_limit is too low. Default 200 is so aggressive that \
-             autovacuum sleeps after processing only 10 dirty pages. This prevents autovacuum \
-             from keeping up with write-intensive workloads. Increase to 2000 (10x default) \
-             to allow workers to do more work before sleeping.",
+            Message::new("autovacuum.vacuum_cost_limit"),
         );
     }
 
@@ -124,11 +129,7 @@ fn analyze_autovacuum_work_mem(
                         &current_value,
                         &format!("{}MB", recommended_mb),
                         SuggestionLevel::Critical,
-                        "autovacuum_work_mem is -1 (inheriting maintenance_work_mem). \
-                         If maintenance_work_mem is set to a large value (e.g., 2GB), \
-                         and autovacuum_max_workers is 3 or more, the system could suddenly \
-                         allocate several GB of RAM for routine autovacuum. Always set autovacuum_work_mem \
-                         explicitly to decouple it from manual maintenance values.",
+                        Message::new("autovacuum.work_mem.inherited"),
                     );
                 }
             }
@@ -143,9 +144,7 @@ fn analyze_autovacuum_work_mem(
                     &current_value,
                     &format!("{}MB", recommended_mb),
                     SuggestionLevel::Recommended,
-                    "autovacuum_work_mem can be increased to 512MB per worker for better \
-                     vacuum performance. This is safe because autovacuum operations are infrequent \
-                     compared to normal queries.",
+                    Message::new("autovacuum.work_mem.low"),
                 );
             }
         }
@@ -159,7 +158,9 @@ fn analyze_autovacuum_scale_factor(
     results: &mut AnalysisResults,
 ) -> Result<()> {
     let current_value = get_param_value(params, "autovacuum_vacuum_scale_factor");
-    let current = current_value.parse::<f64>().unwrap_or(0.2);
+    let current = param_value(params, "autovacuum_vacuum_scale_factor")
+        .and_then(ParamValue::as_f64)
+        .unwrap_or(0.2);
 
     // Default is 0.2 (20%), which is catastrophic for large tables
     // Should be overridden per-table for large tables
@@ -177,15 +178,7 @@ fn analyze_autovacuum_scale_factor(
             &current_value,
             "0.1 or per-table override",
             level,
-            &format!(
-                "autovacuum_vacuum_scale_factor is too high at {} (20% default). \
-                 This causes autovacuum to wait for 200 million dead tuples on a 1-billion row table \
-                 before starting. For large tables, autovacuum_vacuum_scale_factor should be 0 \
-                 and autovacuum_vacuum_threshold should be set to a fixed value (e.g., 10,000). \
-                 This can be done per-table: \
-                 ALTER TABLE my_large_table SET (autovacuum_vacuum_scale_factor = 0, autovacuum_vacuum_threshold = 10000);",
-                current
-            ),
+            Message::new("autovacuum.vacuum_scale_factor").arg("current", current),
         );
     }
 
@@ -208,14 +201,16 @@ fn add_suggestion(
     current_value: &str,
     suggested_value: &str,
     level: SuggestionLevel,
-    rationale: &str,
+    rationale: Message,
 ) {
     let suggestion = ConfigSuggestion {
         parameter: parameter.to_string(),
         current_value: current_value.to_string(),
         suggested_value: suggested_value.to_string(),
         level,
-        rationale: rationale.to_string(),
+        rationale: rationale.render(Lang::En),
+        messages: vec![rationale],
+        fingerprint: String::new(),
     };
 
     results
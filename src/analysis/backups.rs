@@ -0,0 +1,393 @@
+use crate::i18n::{Lang, Message};
+use crate::models::{AnalysisResults, ConfigCategory, ConfigSuggestion, SuggestionLevel};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres, Row};
+use std::time::Duration;
+use tokio::process::Command;
+use tracing::{debug, warn};
+
+/// How long `--backup-check-command` may run before it counts as failed
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(120);
+/// RPO when only `--backup-check-command` is given
+const DEFAULT_RPO: Duration = Duration::from_secs(24 * 3600);
+
+/// Options for the backup recency check, enabled by `--backup-rpo` or
+/// `--backup-check-command`
+#[derive(Debug, Clone)]
+pub struct BackupCheck {
+    /// Newest backup must be younger than this
+    pub rpo: Duration,
+    /// Shell command printing the time of the last successful backup
+    pub command: Option<String>,
+}
+
+impl BackupCheck {
+    /// `None` unless either flag is given
+    pub fn from_flags(rpo: Option<Duration>, command: Option<String>) -> Option<Self> {
+        if rpo.is_none() && command.is_none() {
+            return None;
+        }
+        Some(Self {
+            rpo: rpo.unwrap_or(DEFAULT_RPO),
+            command,
+        })
+    }
+}
+
+/// Where a successful backup was seen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupSource {
+    /// `pg_stat_archiver.last_archived_time`; only extends a base backup
+    WalArchive,
+    /// A `*.backup` history file left by pg_basebackup or pg_backup_stop
+    BackupHistory,
+    /// `--backup-check-command`
+    Command,
+}
+
+impl BackupSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::WalArchive => "WAL archive",
+            Self::BackupHistory => "base backup history file",
+            Self::Command => "backup check command",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupEvidence {
+    pub source: BackupSource,
+    /// RFC 3339, UTC
+    pub at: String,
+}
+
+pub(crate) const ARCHIVER_QUERY: &str = r#"
+    SELECT
+        to_char(last_archived_time AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS"Z"') AS archived_at,
+        last_archived_wal,
+        CASE WHEN NOT pg_is_in_recovery() THEN pg_walfile_name(pg_current_wal_lsn()) END AS current_wal,
+        (SELECT setting::bigint FROM pg_settings WHERE name = 'wal_segment_size') AS segment_size
+    FROM pg_stat_archiver
+    WHERE last_archived_time IS NOT NULL
+"#;
+
+pub(crate) const BACKUP_HISTORY_QUERY: &str = r#"
+    SELECT to_char(max(modification) AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS"Z"')
+    FROM pg_ls_waldir()
+    WHERE name LIKE '%.backup'
+"#;
+
+/// Reads the archiver's last success, the newest backup history file in
+/// pg_wal, and the check command's output. Sources that cannot be read are
+/// left out; failures of the command are returned as warnings.
+pub async fn collect_evidence(
+    pool: &Pool<Postgres>,
+    check: &BackupCheck,
+) -> (Vec<BackupEvidence>, Vec<String>) {
+    let mut evidence = Vec::new();
+    let mut warnings = Vec::new();
+    match sqlx::query(ARCHIVER_QUERY).fetch_optional(pool).await {
+        Ok(Some(row)) => {
+            // An idle server has nothing left to archive, so its data is covered up to now
+            let caught_up = match (
+                row.get::<Option<String>, _>("last_archived_wal"),
+                row.get::<Option<String>, _>("current_wal"),
+                row.get::<Option<i64>, _>("segment_size"),
+            ) {
+                (Some(archived), Some(current), Some(size)) => {
+                    archive_caught_up(&archived, &current, size as u64)
+                }
+                _ => false,
+            };
+            let at = if caught_up {
+                Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+            } else {
+                row.get("archived_at")
+            };
+            evidence.push(BackupEvidence {
+                source: BackupSource::WalArchive,
+                at,
+            });
+        }
+        Ok(None) => {}
+        Err(err) => warn!("Failed to read pg_stat_archiver: {err}"),
+    }
+    match sqlx::query_scalar::<_, Option<String>>(BACKUP_HISTORY_QUERY)
+        .fetch_one(pool)
+        .await
+    {
+        Ok(at) => evidence.extend(at.map(|at| BackupEvidence {
+            source: BackupSource::BackupHistory,
+            at,
+        })),
+        // pg_ls_waldir() needs pg_monitor
+        Err(err) => debug!("Backup history files not read: {err}"),
+    }
+    if let Some(command) = &check.command {
+        match run_command(command).await {
+            Ok(at) => evidence.push(BackupEvidence {
+                source: BackupSource::Command,
+                at,
+            }),
+            Err(reason) => warnings.push(format!("Backup check command failed: {reason}.")),
+        }
+    }
+    (evidence, warnings)
+}
+
+/// Whether `archived` is the WAL segment just before `current`, the one being
+/// written, so everything that can be archived has been
+fn archive_caught_up(archived: &str, current: &str, segment_size: u64) -> bool {
+    // Timeline, log and segment as 8 hex digits each; the segment number
+    // counts across logs
+    let parse = |name: &str| -> Option<u64> {
+        let field = |range: std::ops::Range<usize>| {
+            name.get(range)
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        };
+        if name.len() != 24 {
+            return None;
+        }
+        let segments_per_log = 0x1_0000_0000 / segment_size.max(1);
+        field(0..8)?;
+        Some(u64::from(field(8..16)?) * segments_per_log + u64::from(field(16..24)?))
+    };
+    match (parse(archived), parse(current)) {
+        (Some(archived), Some(current)) => archived + 1 >= current,
+        _ => false,
+    }
+}
+
+/// Runs `command` through `sh -c` and reads the backup time from the last
+/// line it prints
+async fn run_command(command: &str) -> Result<String, String> {
+    let output = tokio::time::timeout(
+        COMMAND_TIMEOUT,
+        Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .map_err(|_| format!("timed out after {}s", COMMAND_TIMEOUT.as_secs()))?
+    .map_err(|err| err.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{} ({})", output.status, stderr.trim()));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let last = stdout
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .unwrap_or_default();
+    parse_backup_time(last)
+        .ok_or_else(|| format!("expected an RFC 3339 time or Unix seconds, got '{last}'"))
+}
+
+/// Parses an RFC 3339 timestamp or Unix seconds into RFC 3339, UTC
+pub fn parse_backup_time(value: &str) -> Option<String> {
+    let at = match value.parse::<i64>() {
+        Ok(secs) => DateTime::from_timestamp(secs, 0)?,
+        Err(_) => DateTime::parse_from_rfc3339(value)
+            .ok()?
+            .with_timezone(&Utc),
+    };
+    Some(at.to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+/// Flags the database as Critical when no base backup, extended by the WAL
+/// archived since, covers the RPO. Archived WAL cannot be restored without a
+/// base backup, so archiver progress alone never counts.
+pub fn analyze_backups(
+    database: &str,
+    evidence: &[BackupEvidence],
+    rpo: Duration,
+    now: DateTime<Utc>,
+    results: &mut AnalysisResults,
+) {
+    let rpo_text = format_age(rpo.as_secs() as i64);
+    let newest = |wal_archive: bool| {
+        evidence
+            .iter()
+            .filter(|evidence| (evidence.source == BackupSource::WalArchive) == wal_archive)
+            .filter_map(|evidence| {
+                let at = DateTime::parse_from_rfc3339(&evidence.at).ok()?;
+                Some((evidence, at.with_timezone(&Utc)))
+            })
+            .max_by_key(|(_, at)| *at)
+    };
+    let newest = match (newest(false), newest(true)) {
+        (Some(base), Some(archive)) if archive.1 > base.1 => Some(archive),
+        (base, _) => base,
+    };
+
+    let (current, rationale) = match newest {
+        None => (
+            "none found".to_string(),
+            Message::new("backup.recency.none").arg("rpo", &rpo_text),
+        ),
+        Some((evidence, at)) => {
+            let age = (now - at).num_seconds().max(0);
+            if age as u64 <= rpo.as_secs() {
+                return;
+            }
+            (
+                evidence.at.clone(),
+                Message::new("backup.recency.stale")
+                    .arg("age", format_age(age))
+                    .arg("source", evidence.source.as_str())
+                    .arg("at", &evidence.at)
+                    .arg("rpo", &rpo_text),
+            )
+        }
+    };
+
+    results
+        .suggestions_by_category
+        .entry(ConfigCategory::Backup)
+        .or_default()
+        .push(ConfigSuggestion {
+            parameter: format!("backup {database}"),
+            current_value: current,
+            suggested_value: format!("within {rpo_text}"),
+            level: SuggestionLevel::Critical,
+            rationale: rationale.render(Lang::En),
+            messages: vec![rationale],
+            fingerprint: String::new(),
+        });
+}
+
+/// `90` → `1m`, `93600` → `1d 2h`
+fn format_age(secs: i64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60);
+    match (days, hours, minutes) {
+        (0, 0, minutes) => format!("{minutes}m"),
+        (0, hours, 0) => format!("{hours}h"),
+        (0, hours, minutes) => format!("{hours}h {minutes}m"),
+        (days, 0, _) => format!("{days}d"),
+        (days, hours, _) => format!("{days}d {hours}h"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-10-16T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn findings(evidence: &[(BackupSource, &str)], rpo_hours: u64) -> Vec<ConfigSuggestion> {
+        let evidence: Vec<_> = evidence
+            .iter()
+            .map(|(source, at)| BackupEvidence {
+                source: *source,
+                at: at.to_string(),
+            })
+            .collect();
+        let mut results = AnalysisResults::default();
+        analyze_backups(
+            "orders",
+            &evidence,
+            Duration::from_secs(rpo_hours * 3600),
+            now(),
+            &mut results,
+        );
+        results
+            .suggestions_by_category
+            .remove(&ConfigCategory::Backup)
+            .unwrap_or_default()
+    }
+
+    #[rstest]
+    #[case("1760616000", Some("2025-10-16T12:00:00Z"))]
+    #[case("2026-10-16T09:30:00+02:00", Some("2026-10-16T07:30:00Z"))]
+    #[case("yesterday", None)]
+    fn backup_times_are_read_as_rfc3339_or_unix_seconds(
+        #[case] value: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(parse_backup_time(value).as_deref(), expected);
+    }
+
+    #[test]
+    fn no_backup_within_the_rpo_is_critical() {
+        let suggestions = findings(
+            &[
+                (BackupSource::WalArchive, "2026-10-15T06:00:00Z"),
+                (BackupSource::BackupHistory, "2026-10-14T01:00:00Z"),
+            ],
+            24,
+        );
+        assert_eq!(suggestions.len(), 1);
+        let suggestion = &suggestions[0];
+        assert_eq!(suggestion.parameter, "backup orders");
+        assert_eq!(suggestion.level, SuggestionLevel::Critical);
+        assert_eq!(suggestion.current_value, "2026-10-15T06:00:00Z");
+        assert_eq!(suggestion.suggested_value, "within 1d");
+        assert!(suggestion.rationale.contains("1d 6h old (WAL archive"));
+
+        let none = findings(&[], 4);
+        assert_eq!(none[0].current_value, "none found");
+        assert_eq!(none[0].suggested_value, "within 4h");
+    }
+
+    #[test]
+    fn a_recent_backup_from_any_source_satisfies_the_rpo() {
+        let evidence = [
+            (BackupSource::WalArchive, "2026-10-01T00:00:00Z"),
+            (BackupSource::Command, "2026-10-16T03:00:00Z"),
+        ];
+        assert!(findings(&evidence, 24).is_empty());
+        assert_eq!(findings(&evidence, 6).len(), 1);
+    }
+
+    #[test]
+    fn archived_wal_only_extends_a_base_backup() {
+        let archive_only = findings(&[(BackupSource::WalArchive, "2026-10-16T11:00:00Z")], 24);
+        assert_eq!(archive_only[0].current_value, "none found");
+
+        let evidence = [
+            (BackupSource::BackupHistory, "2026-10-10T00:00:00Z"),
+            (BackupSource::WalArchive, "2026-10-16T11:00:00Z"),
+        ];
+        assert!(findings(&evidence, 24).is_empty());
+    }
+
+    #[rstest]
+    #[case("000000010000000300000041", "000000010000000300000042", true)]
+    #[case("0000000100000003000000FF", "000000010000000400000000", true)]
+    #[case("000000010000000300000040", "000000010000000300000042", false)]
+    #[case(
+        "000000010000000300000041.00000028.backup",
+        "000000010000000300000042",
+        false
+    )]
+    fn an_archiver_one_segment_behind_is_caught_up(
+        #[case] archived: &str,
+        #[case] current: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(
+            archive_caught_up(archived, current, 16 * 1024 * 1024),
+            expected
+        );
+    }
+
+    #[tokio::test]
+    async fn the_check_command_reports_the_last_line() {
+        assert_eq!(
+            run_command("echo listing; echo 2026-10-16T03:00:00Z").await,
+            Ok("2026-10-16T03:00:00Z".to_string())
+        );
+        assert!(run_command("exit 3").await.is_err());
+    }
+}
@@ -0,0 +1,225 @@
+use crate::checker::CheckerError;
+use crate::fixture::Fixture;
+use futures_util::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::{Pool, Postgres, Row};
+
+/// Usage counters of an index and its table from `pg_stat_user_indexes` and
+/// `pg_stat_user_tables`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct IndexUsage {
+    pub idx_scan: i64,
+    pub idx_tup_read: i64,
+    pub idx_tup_fetch: i64,
+    pub table_live_tup: Option<i64>,
+    pub table_index_writes: Option<i64>,
+    pub table_scans: Option<i64>,
+}
+
+/// One index with its key columns, definition and size
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedIndex {
+    pub schema: String,
+    pub table: String,
+    pub index_name: String,
+    pub access_method: String,
+    pub key_columns: Vec<String>,
+    /// Whether each key column is stored `DESC`, aligned with `key_columns`
+    pub key_descending: Vec<bool>,
+    /// First key as `pg_get_indexdef` renders it, the expression for expression indexes
+    pub leading_key: Option<String>,
+    /// `pg_get_expr` of the index predicate for partial indexes
+    pub predicate: Option<String>,
+    pub definition: Option<String>,
+    pub size_bytes: i64,
+    pub size_pretty: String,
+    pub is_unique: bool,
+    pub is_partial: bool,
+    pub is_expression: bool,
+    pub is_valid: bool,
+    pub enforces_constraint: bool,
+    /// `None` for indexes without user statistics, e.g. those of TOAST tables
+    pub usage: Option<IndexUsage>,
+}
+
+/// Catalog metadata read once per run and shared by the table/index and the
+/// workload analyses, which would otherwise each walk every index's columns
+#[derive(Debug, Default)]
+pub struct CatalogCache {
+    /// `None` when not read, e.g. with `--gentle`; a failed read keeps its
+    /// message so each analysis reports it as before
+    indexes: Option<Result<Vec<CachedIndex>, String>>,
+}
+
+pub(crate) const INDEX_CATALOG_QUERY: &str = r#"
+    SELECT
+        n.nspname AS schema_name,
+        c.relname AS table_name,
+        idx.relname AS index_name,
+        am.amname AS access_method,
+        pg_get_indexdef(i.indexrelid, 1, false) AS leading_key,
+        pg_get_expr(i.indpred, i.indrelid) AS predicate,
+        pg_get_indexdef(i.indexrelid) AS index_definition,
+        pg_relation_size(i.indexrelid) AS size_bytes,
+        pg_size_pretty(pg_relation_size(i.indexrelid)) AS size_pretty,
+        i.indisunique AS is_unique,
+        (i.indpred IS NOT NULL) AS is_partial,
+        (i.indexprs IS NOT NULL) AS is_expression,
+        i.indisvalid AS is_valid,
+        EXISTS (
+            SELECT 1 FROM pg_constraint con WHERE con.conindid = i.indexrelid
+        ) AS enforces_constraint,
+        COALESCE(
+            array_agg(a.attname ORDER BY arr.ord) FILTER (WHERE a.attname IS NOT NULL),
+            ARRAY[]::text[]
+        ) AS key_columns,
+        COALESCE(
+            array_agg((i.indoption[arr.ord::int - 1] & 1) = 1 ORDER BY arr.ord)
+                FILTER (WHERE a.attname IS NOT NULL),
+            ARRAY[]::boolean[]
+        ) AS key_descending,
+        (s.indexrelid IS NOT NULL) AS has_usage,
+        s.idx_scan,
+        s.idx_tup_read,
+        s.idx_tup_fetch,
+        t.n_live_tup,
+        (t.n_tup_ins + t.n_tup_upd - t.n_tup_hot_upd) AS table_index_writes,
+        (t.seq_scan + COALESCE(t.idx_scan, 0)) AS table_scans
+    FROM pg_index i
+    JOIN pg_class c ON c.oid = i.indrelid
+    JOIN pg_class idx ON idx.oid = i.indexrelid
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    JOIN pg_am am ON am.oid = idx.relam
+    LEFT JOIN pg_stat_user_indexes s ON s.indexrelid = i.indexrelid
+    LEFT JOIN pg_stat_user_tables t ON t.relid = i.indrelid
+    LEFT JOIN LATERAL unnest(i.indkey) WITH ORDINALITY AS arr(attnum, ord)
+        ON arr.ord <= i.indnkeyatts
+    LEFT JOIN pg_attribute a
+        ON a.attrelid = c.oid
+       AND a.attnum = arr.attnum
+       AND arr.attnum > 0
+    WHERE n.nspname NOT IN ('pg_catalog', 'information_schema')
+    GROUP BY
+        i.indexrelid,
+        i.indrelid,
+        n.nspname,
+        c.relname,
+        idx.relname,
+        am.amname,
+        i.indpred,
+        i.indexprs,
+        i.indisunique,
+        i.indisvalid,
+        s.indexrelid,
+        s.idx_scan,
+        s.idx_tup_read,
+        s.idx_tup_fetch,
+        t.n_live_tup,
+        t.n_tup_ins,
+        t.n_tup_upd,
+        t.n_tup_hot_upd,
+        t.seq_scan,
+        t.idx_scan
+"#;
+
+impl CatalogCache {
+    /// Reads the index catalog, served as `indexes` by fixtures
+    pub(crate) async fn fetch(pool: &Pool<Postgres>, fixture: &Fixture) -> Self {
+        let indexes = fixture
+            .try_serve("indexes", fetch_indexes(pool))
+            .await
+            .map_err(|err| err.to_string());
+        Self {
+            indexes: Some(indexes),
+        }
+    }
+
+    /// A cache nothing was read into, as `--gentle` leaves it
+    pub(crate) const fn empty() -> Self {
+        Self { indexes: None }
+    }
+
+    /// Every index outside the system schemas, TOAST indexes included; empty
+    /// when the catalog was not read
+    pub(crate) fn indexes(&self) -> Result<&[CachedIndex], CheckerError> {
+        match &self.indexes {
+            Some(Ok(indexes)) => Ok(indexes),
+            Some(Err(message)) => Err(CheckerError::Other {
+                message: message.clone(),
+            }),
+            None => Ok(&[]),
+        }
+    }
+
+    pub(crate) fn is_read(&self) -> bool {
+        self.indexes.is_some()
+    }
+}
+
+/// Streams the catalog so only the converted rows are held, not the raw ones
+async fn fetch_indexes(pool: &Pool<Postgres>) -> Result<Vec<CachedIndex>, CheckerError> {
+    sqlx::query(INDEX_CATALOG_QUERY)
+        .fetch(pool)
+        .map_ok(|row| cached_index(&row))
+        .try_collect()
+        .await
+        .map_err(|source| CheckerError::QueryError {
+            query: INDEX_CATALOG_QUERY.into(),
+            source,
+        })
+}
+
+fn cached_index(row: &PgRow) -> CachedIndex {
+    let has_usage: bool = row.get("has_usage");
+    CachedIndex {
+        schema: row.get("schema_name"),
+        table: row.get("table_name"),
+        index_name: row.get("index_name"),
+        access_method: row.get("access_method"),
+        key_columns: row.get("key_columns"),
+        key_descending: row.get("key_descending"),
+        leading_key: row.get("leading_key"),
+        predicate: row.get("predicate"),
+        definition: row.get("index_definition"),
+        size_bytes: row.get("size_bytes"),
+        size_pretty: row.get("size_pretty"),
+        is_unique: row.get("is_unique"),
+        is_partial: row.get("is_partial"),
+        is_expression: row.get("is_expression"),
+        is_valid: row.get("is_valid"),
+        enforces_constraint: row.get("enforces_constraint"),
+        usage: has_usage.then(|| IndexUsage {
+            idx_scan: row.get("idx_scan"),
+            idx_tup_read: row.get("idx_tup_read"),
+            idx_tup_fetch: row.get("idx_tup_fetch"),
+            table_live_tup: row.get("n_live_tup"),
+            table_index_writes: row.get("table_index_writes"),
+            table_scans: row.get("table_scans"),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_catalog_query_limits_to_key_columns() {
+        assert!(INDEX_CATALOG_QUERY.contains("arr.ord <= i.indnkeyatts"));
+        assert!(INDEX_CATALOG_QUERY.contains("(i.indpred IS NOT NULL) AS is_partial"));
+    }
+
+    #[test]
+    fn unread_cache_has_no_indexes_and_failed_reads_keep_their_error() {
+        let empty = CatalogCache::empty();
+        assert!(!empty.is_read());
+        assert!(empty.indexes().unwrap().is_empty());
+
+        let failed = CatalogCache {
+            indexes: Some(Err("permission denied for pg_index".to_string())),
+        };
+        let err = failed.indexes().unwrap_err();
+        assert_eq!(err.to_string(), "permission denied for pg_index");
+    }
+}
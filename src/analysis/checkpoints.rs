@@ -0,0 +1,302 @@
+use crate::analysis::sampling::format_window;
+use crate::analysis::validation::in_param_unit;
+use crate::i18n::Message;
+use crate::models::{
+    unit_bytes, unit_seconds, AnalysisResults, ByteSize, ConfigCategory, PgConfigParam, SystemStats,
+};
+use std::collections::HashMap;
+
+/// Suggestions that get the before/after checkpoint interval appended
+const FORECAST_PARAMETERS: &[&str] = &["max_wal_size", "checkpoint_timeout"];
+
+/// The settings that decide when a checkpoint starts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CheckpointSettings {
+    pub max_wal_size_bytes: f64,
+    pub timeout_secs: f64,
+    pub completion_target: f64,
+}
+
+/// WAL volume per second over the statistics window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WalRate {
+    pub bytes_per_sec: f64,
+    /// Share of those bytes that are full-page images, 0.0 to 1.0
+    pub fpi_share: Option<f64>,
+}
+
+impl CheckpointSettings {
+    /// Current settings, or `None` when pg_settings lacks one of them
+    pub fn from_params(params: &HashMap<String, PgConfigParam>) -> Option<Self> {
+        Some(Self {
+            max_wal_size_bytes: params.get("max_wal_size")?.value.as_bytes()? as f64,
+            timeout_secs: params
+                .get("checkpoint_timeout")?
+                .value
+                .as_duration()?
+                .as_secs_f64(),
+            completion_target: params
+                .get("checkpoint_completion_target")
+                .and_then(|param| param.value.as_f64())
+                .unwrap_or(0.9),
+        })
+    }
+
+    /// The settings once the WAL suggestions in `results` are applied
+    pub fn with_suggestions(
+        mut self,
+        params: &HashMap<String, PgConfigParam>,
+        results: &AnalysisResults,
+    ) -> Self {
+        let suggestions = results
+            .suggestions_by_category
+            .get(&ConfigCategory::Wal)
+            .into_iter()
+            .flatten();
+        for suggestion in suggestions {
+            let Some(param) = params.get(&suggestion.parameter) else {
+                continue;
+            };
+            let Some(value) = in_param_unit(&suggestion.suggested_value, param) else {
+                continue;
+            };
+            let unit = param.unit.as_deref();
+            match suggestion.parameter.as_str() {
+                "max_wal_size" => {
+                    self.max_wal_size_bytes = value * unit.and_then(unit_bytes).unwrap_or(1.0)
+                }
+                "checkpoint_timeout" => {
+                    self.timeout_secs = value * unit.and_then(unit_seconds).unwrap_or(1.0)
+                }
+                "checkpoint_completion_target" => self.completion_target = value,
+                _ => {}
+            }
+        }
+        self
+    }
+
+    /// Seconds between checkpoints at `wal_rate` bytes per second.
+    ///
+    /// The server requests a checkpoint once WAL since the last one reaches
+    /// `max_wal_size / (1 + checkpoint_completion_target)`, so the next one can
+    /// finish before `max_wal_size` is exceeded.
+    pub fn interval_secs(&self, bytes_per_sec: f64) -> f64 {
+        if bytes_per_sec <= 0.0 {
+            return self.timeout_secs;
+        }
+        let distance = self.max_wal_size_bytes / (1.0 + self.completion_target);
+        (distance / bytes_per_sec).min(self.timeout_secs)
+    }
+}
+
+impl WalRate {
+    /// Rate over the WAL counters' window; `None` before PG14 or when no WAL was written
+    pub fn from_stats(stats: &SystemStats, block_size: f64) -> Option<Self> {
+        let bytes = stats.wal_bytes.filter(|bytes| *bytes > 0)? as f64;
+        let secs = stats.wal_stats_age_secs.filter(|secs| *secs > 0)? as f64;
+        Some(Self {
+            bytes_per_sec: bytes / secs,
+            fpi_share: stats
+                .wal_fpi
+                .map(|fpi| (fpi as f64 * block_size / bytes).min(1.0)),
+        })
+    }
+
+    /// Fraction of WAL volume saved by checkpointing every `after` instead of
+    /// every `before` seconds. A page's first change after each checkpoint is
+    /// logged in full, so full-page images shrink at most in proportion to the
+    /// longer interval.
+    pub fn fpi_savings(&self, before: f64, after: f64) -> Option<f64> {
+        let share = self.fpi_share?;
+        (after > before).then(|| share * (1.0 - before / after))
+    }
+}
+
+/// Appends the predicted checkpoint interval, today and with the WAL
+/// suggestions applied, to the `max_wal_size` and `checkpoint_timeout`
+/// suggestions
+pub fn annotate_checkpoint_intervals(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    results: &mut AnalysisResults,
+) {
+    let Some(current) = CheckpointSettings::from_params(params) else {
+        return;
+    };
+    let block_size = params
+        .get("block_size")
+        .and_then(|param| param.value.as_f64())
+        .unwrap_or(8192.0);
+    let Some(rate) = WalRate::from_stats(stats, block_size) else {
+        return;
+    };
+
+    let suggested = current.with_suggestions(params, results);
+    let before = current.interval_secs(rate.bytes_per_sec);
+    let after = suggested.interval_secs(rate.bytes_per_sec);
+    let wal_rate = format!("{}/s", ByteSize(rate.bytes_per_sec as i64));
+
+    let mut notes = vec![if suggested == current {
+        Message::new("note.checkpoint_interval")
+            .arg("wal_rate", &wal_rate)
+            .arg("before", format_window(before as u64))
+    } else {
+        Message::new("note.checkpoint_interval_change")
+            .arg("wal_rate", &wal_rate)
+            .arg("before", format_window(before as u64))
+            .arg("after", format_window(after as u64))
+    }];
+    if let (Some(share), Some(savings)) = (rate.fpi_share, rate.fpi_savings(before, after)) {
+        notes.push(
+            Message::new("note.full_page_writes")
+                .arg("share", format!("{:.0}", share * 100.0))
+                .arg("savings", format!("{:.0}", savings * 100.0)),
+        );
+    }
+
+    let suggestions = results
+        .suggestions_by_category
+        .get_mut(&ConfigCategory::Wal)
+        .into_iter()
+        .flatten()
+        .filter(|suggestion| FORECAST_PARAMETERS.contains(&suggestion.parameter.as_str()));
+    for suggestion in suggestions {
+        for note in &notes {
+            suggestion.push_note(note.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConfigSuggestion, ParamValue, SuggestionLevel};
+    use rstest::rstest;
+
+    const MB: f64 = 1024.0 * 1024.0;
+
+    fn param(name: &str, value: &str, unit: Option<&str>) -> PgConfigParam {
+        PgConfigParam {
+            name: name.to_string(),
+            current_value: value.to_string(),
+            default_value: None,
+            unit: unit.map(str::to_string),
+            context: "sighup".to_string(),
+            value: ParamValue::parse(value, "", unit),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        }
+    }
+
+    fn params() -> HashMap<String, PgConfigParam> {
+        [
+            param("max_wal_size", "1024", Some("MB")),
+            param("checkpoint_timeout", "1800", Some("s")),
+            param("checkpoint_completion_target", "0.9", None),
+        ]
+        .into_iter()
+        .map(|param| (param.name.clone(), param))
+        .collect()
+    }
+
+    fn suggestion(parameter: &str, suggested: &str) -> ConfigSuggestion {
+        ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: String::new(),
+            suggested_value: suggested.to_string(),
+            level: SuggestionLevel::Important,
+            rationale: "Raise it.".to_string(),
+            messages: Vec::new(),
+            fingerprint: String::new(),
+        }
+    }
+
+    /// An hour of WAL at 4MB/s, half of it full-page images
+    fn stats() -> SystemStats {
+        let wal_bytes = 4.0 * MB * 3600.0;
+        SystemStats {
+            wal_bytes: Some(wal_bytes as i64),
+            wal_fpi: Some((wal_bytes * 0.5 / 8192.0) as i64),
+            wal_stats_age_secs: Some(3600),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    #[case(1024.0, 1800.0, 4.0 * MB, 1024.0 / 1.9 / 4.0)]
+    #[case(65536.0, 1800.0, 4.0 * MB, 1800.0)]
+    #[case(1024.0, 300.0, 0.0, 300.0)]
+    fn interval_is_the_sooner_of_wal_distance_and_timeout(
+        #[case] max_wal_size_mb: f64,
+        #[case] timeout_secs: f64,
+        #[case] bytes_per_sec: f64,
+        #[case] expected: f64,
+    ) {
+        let settings = CheckpointSettings {
+            max_wal_size_bytes: max_wal_size_mb * MB,
+            timeout_secs,
+            completion_target: 0.9,
+        };
+        assert!((settings.interval_secs(bytes_per_sec) - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn wal_suggestions_get_before_and_after_intervals() {
+        let params = params();
+        let mut results = AnalysisResults::default();
+        results.suggestions_by_category.insert(
+            ConfigCategory::Wal,
+            vec![
+                suggestion("max_wal_size", "8GB"),
+                suggestion("checkpoint_completion_target", "0.9"),
+            ],
+        );
+
+        annotate_checkpoint_intervals(&params, &stats(), &mut results);
+
+        let wal = &results.suggestions_by_category[&ConfigCategory::Wal];
+        assert_eq!(
+            wal[0].rationale,
+            "Raise it. At the measured WAL rate of 4 MB/s, checkpoints run every ~2m today \
+             → ~17m with the suggested WAL settings. Full-page images are ~50% of WAL; \
+             the longer interval cuts WAL volume by up to ~44%."
+        );
+        assert_eq!(wal[1].rationale, "Raise it.");
+    }
+
+    #[test]
+    fn unchanged_settings_report_only_todays_interval() {
+        let params = params();
+        let mut results = AnalysisResults::default();
+        results.suggestions_by_category.insert(
+            ConfigCategory::Wal,
+            vec![suggestion("max_wal_size", "Increase value")],
+        );
+
+        annotate_checkpoint_intervals(&params, &stats(), &mut results);
+
+        assert_eq!(
+            results.suggestions_by_category[&ConfigCategory::Wal][0].rationale,
+            "Raise it. At the measured WAL rate of 4 MB/s, a checkpoint runs every ~2m."
+        );
+    }
+
+    #[test]
+    fn nothing_is_added_without_wal_statistics() {
+        let params = params();
+        let mut results = AnalysisResults::default();
+        results
+            .suggestions_by_category
+            .insert(ConfigCategory::Wal, vec![suggestion("max_wal_size", "8GB")]);
+
+        annotate_checkpoint_intervals(&params, &SystemStats::default(), &mut results);
+
+        assert_eq!(
+            results.suggestions_by_category[&ConfigCategory::Wal][0].rationale,
+            "Raise it."
+        );
+    }
+}
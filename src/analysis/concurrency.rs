@@ -1,9 +1,26 @@
+use crate::analysis::connection_history::busy_hours;
+use crate::analysis::param_value;
 use crate::checker::CheckerError;
-use crate::models::{AnalysisResults, ConfigCategory, ConfigSuggestion, SuggestionLevel};
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ConfigCategory, ConfigSuggestion, ParamValue, SuggestionLevel,
+};
 use std::collections::HashMap;
 
 type Result<T> = std::result::Result<T, CheckerError>;
 
+/// Servers above this many vCPUs usually span several sockets and NUMA nodes
+pub(crate) const LARGE_SERVER_VCPU: usize = 32;
+/// Per-query and per-maintenance-command worker cap on large servers; beyond
+/// it, workers on remote NUMA nodes add more memory traffic than speed
+const LARGE_SERVER_WORKERS_PER_OPERATION: usize = 8;
+/// Sampled peaks at this share of max_connections leave no room for a burst
+const SATURATED_UTILIZATION: f64 = 0.8;
+/// At this share, new connections are about to be refused
+const EXHAUSTED_UTILIZATION: f64 = 0.95;
+/// max_connections is kept this far above the sampled peak
+const PEAK_HEADROOM: f64 = 1.25;
+
 /// Analyzes concurrency and parallelism configuration
 pub fn analyze_concurrency(
     params: &HashMap<String, crate::models::PgConfigParam>,
@@ -25,37 +42,83 @@ fn analyze_max_connections(
     results: &mut AnalysisResults,
 ) -> Result<()> {
     let current_value = get_param_value(params, "max_connections");
+    let current_conns = param_value(params, "max_connections")
+        .and_then(ParamValue::as_count)
+        .unwrap_or(0);
+    let observed = results.connection_saturation.clone();
+    // Never suggest fewer connections than the samples show are needed
+    let observed_floor = observed
+        .as_ref()
+        .map(|observed| round_up_to_ten(observed.peak_connections as f64 * PEAK_HEADROOM));
+
+    if let (Some(observed), Some(floor)) = (&observed, observed_floor) {
+        if observed.peak_utilization >= SATURATED_UTILIZATION {
+            let level = if observed.peak_utilization >= EXHAUSTED_UTILIZATION {
+                SuggestionLevel::Critical
+            } else {
+                SuggestionLevel::Important
+            };
+            let suggestion = add_suggestion(
+                results,
+                ConfigCategory::Concurrency,
+                "max_connections",
+                &current_value,
+                &floor.max(current_conns).to_string(),
+                level,
+                Message::new("concurrency.max_connections.saturated")
+                    .arg("peak", observed.peak_connections)
+                    .arg("current", current_conns)
+                    .arg("pct", (observed.peak_utilization * 100.0).round())
+                    .arg("at", &observed.peak_at)
+                    .arg("samples", observed.samples),
+            );
+            let hours = busy_hours(observed);
+            if !hours.is_empty() {
+                suggestion
+                    .push_note(Message::new("note.connection_peak_hours").arg("hours", hours));
+            }
+            return Ok(());
+        }
+    }
 
     if let Some(cpu) = stats.cpu_count {
-        let recommended = (4 * cpu).max(100); // GREATEST(4 * vCPU, 100)
-        let current_conns = current_value.parse::<usize>().unwrap_or(0);
+        let per_vcpu = stats.profile().thresholds().max_connections_per_vcpu;
+        let formula = (per_vcpu * cpu).max(100); // GREATEST(4 * vCPU, 100)
+        let recommended = formula.max(observed_floor.unwrap_or(0));
 
-        if current_conns > recommended * 2 {
-            add_suggestion(
+        let suggestion = if current_conns > recommended * 2 {
+            Some(add_suggestion(
                 results,
                 ConfigCategory::Concurrency,
                 "max_connections",
                 &current_value,
                 &recommended.to_string(),
                 SuggestionLevel::Critical,
-                &format!(
-                    "max_connections is dangerously high at {}. Each connection consumes \
-                     memory and CPU. Best practice is to use a connection pooler (PgBouncer) \
-                     and set max_connections to ~4 * vCPU ({} for your system). \
-                     This is a common trap parameter.",
-                    current_conns, recommended
-                ),
-            );
+                Message::new("concurrency.max_connections.too_high")
+                    .arg("current", current_conns)
+                    .arg("recommended", recommended),
+            ))
         } else if current_conns > recommended {
-            add_suggestion(
+            Some(add_suggestion(
                 results,
                 ConfigCategory::Concurrency,
                 "max_connections",
                 &current_value,
                 &recommended.to_string(),
                 SuggestionLevel::Important,
-                "max_connections should be kept relatively low. Consider using a connection pooler \
-                 for better connection management.",
+                Message::new("concurrency.max_connections.high"),
+            ))
+        } else {
+            None
+        };
+        if let (Some(suggestion), Some(observed)) = (suggestion, &observed) {
+            suggestion.push_note(
+                Message::new("note.observed_connection_peak")
+                    .arg("peak", observed.peak_connections)
+                    .arg("pct", (observed.peak_utilization * 100.0).round())
+                    .arg("samples", observed.samples)
+                    .arg("first", &observed.first_at)
+                    .arg("last", &observed.last_at),
             );
         }
     }
@@ -63,6 +126,10 @@ fn analyze_max_connections(
     Ok(())
 }
 
+fn round_up_to_ten(value: f64) -> usize {
+    (value / 10.0).ceil() as usize * 10
+}
+
 fn analyze_max_worker_processes(
     params: &HashMap<String, crate::models::PgConfigParam>,
     stats: &crate::models::SystemStats,
@@ -72,7 +139,9 @@ fn analyze_max_worker_processes(
         let current_value = get_param_value(params, "max_worker_processes");
         let recommended = cpu;
 
-        if let Ok(current_workers) = current_value.parse::<usize>() {
+        if let Some(current_workers) =
+            param_value(params, "max_worker_processes").and_then(ParamValue::as_count)
+        {
             if current_workers != recommended {
                 add_suggestion(
                     results,
@@ -81,11 +150,8 @@ fn analyze_max_worker_processes(
                     &current_value,
                     &recommended.to_string(),
                     SuggestionLevel::Recommended,
-                    &format!(
-                        "max_worker_processes should match your vCPU count ({}). \
-                         This is the master limit for all background worker processes.",
-                        recommended
-                    ),
+                    Message::new("concurrency.max_worker_processes")
+                        .arg("recommended", recommended),
                 );
             }
         }
@@ -101,9 +167,11 @@ fn analyze_max_parallel_workers(
 ) -> Result<()> {
     if let Some(cpu) = stats.cpu_count {
         let current_value = get_param_value(params, "max_parallel_workers");
-        let recommended = cpu;
+        let recommended = parallel_workers_target(cpu);
 
-        if let Ok(current_workers) = current_value.parse::<usize>() {
+        if let Some(current_workers) =
+            param_value(params, "max_parallel_workers").and_then(ParamValue::as_count)
+        {
             if current_workers > recommended {
                 add_suggestion(
                     results,
@@ -112,13 +180,11 @@ fn analyze_max_parallel_workers(
                     &current_value,
                     &recommended.to_string(),
                     SuggestionLevel::Important,
-                    &format!(
-                        "max_parallel_workers (total across all queries) should not exceed vCPU count ({}). \
-                         Setting it higher can cause CPU contention.",
-                        recommended
-                    ),
+                    Message::new("concurrency.max_parallel_workers.too_high")
+                        .arg("recommended", recommended),
                 );
-            } else if current_workers < (recommended as f64 * 0.5) as usize {
+                note_large_server(results, cpu, recommended);
+            } else if current_workers < underutilized_below(stats, recommended) {
                 add_suggestion(
                     results,
                     ConfigCategory::Concurrency,
@@ -126,9 +192,9 @@ fn analyze_max_parallel_workers(
                     &current_value,
                     &recommended.to_string(),
                     SuggestionLevel::Recommended,
-                    "max_parallel_workers is underutilized. Consider increasing it to match \
-                     your vCPU count for better parallel query performance.",
+                    Message::new("concurrency.max_parallel_workers.underutilized"),
                 );
+                note_large_server(results, cpu, recommended);
             }
         }
     }
@@ -143,9 +209,17 @@ fn analyze_max_parallel_workers_per_gather(
 ) -> Result<()> {
     if let Some(cpu) = stats.cpu_count {
         let current_value = get_param_value(params, "max_parallel_workers_per_gather");
-        let recommended = (cpu / 2).max(1); // Half vCPU, but at least 1
+        let profile = stats.profile();
+        let is_olap = profile.workload_type() == crate::config::WorkloadType::Olap;
+        // Half vCPU for OLTP, 3/4 for warehouses, but at least 1
+        let recommended = per_operation_target(
+            cpu,
+            ((cpu as f64 * profile.thresholds().parallel_gather_ratio) as usize).max(1),
+        );
 
-        if let Ok(current_workers) = current_value.parse::<usize>() {
+        if let Some(current_workers) =
+            param_value(params, "max_parallel_workers_per_gather").and_then(ParamValue::as_count)
+        {
             if current_workers > cpu {
                 add_suggestion(
                     results,
@@ -154,14 +228,12 @@ fn analyze_max_parallel_workers_per_gather(
                     &current_value,
                     &recommended.to_string(),
                     SuggestionLevel::Critical,
-                    &format!(
-                        "max_parallel_workers_per_gather (per query) should not exceed vCPU count ({}). \
-                         Setting it to {} would allow a single query to consume all CPU resources, \
-                         starving other concurrent queries.",
-                        cpu, current_workers
-                    ),
+                    Message::new("concurrency.max_parallel_workers_per_gather.above_vcpu")
+                        .arg("vcpu", cpu)
+                        .arg("current", current_workers),
                 );
-            } else if current_workers == cpu {
+                note_large_server(results, cpu, recommended);
+            } else if current_workers == cpu && !is_olap {
                 add_suggestion(
                     results,
                     ConfigCategory::Concurrency,
@@ -169,11 +241,14 @@ fn analyze_max_parallel_workers_per_gather(
                     &current_value,
                     &recommended.to_string(),
                     SuggestionLevel::Important,
-                    "Setting max_parallel_workers_per_gather equal to vCPU count is dangerous. \
-                     It allows a single complex query to consume all parallel workers, starving \
-                     other queries. Set it to half of vCPUs to limit the blast radius of a runaway query.",
+                    Message::new("concurrency.max_parallel_workers_per_gather.equals_vcpu"),
                 );
-            } else if current_workers < (recommended as f64 * 0.5) as usize {
+                note_large_server(results, cpu, recommended);
+            } else if profile.thresholds().parallel_gather_ratio < 0.5
+                && current_workers > recommended
+            {
+                // Profiles that share the CPUs between tenants hold each query
+                // below the usual half of vCPUs
                 add_suggestion(
                     results,
                     ConfigCategory::Concurrency,
@@ -181,13 +256,32 @@ fn analyze_max_parallel_workers_per_gather(
                     &current_value,
                     &recommended.to_string(),
                     SuggestionLevel::Recommended,
-                    &format!(
-                        "max_parallel_workers_per_gather is underutilized. For mixed workloads, \
-                         setting it to half of vCPUs (e.g., {}) allows at least two complex queries \
-                         to run in parallel fully.",
-                        recommended
-                    ),
+                    Message::new("concurrency.max_parallel_workers_per_gather.above_profile")
+                        .arg("profile", profile.as_str())
+                        .arg("current", current_workers)
+                        .arg("recommended", recommended),
                 );
+                note_large_server(results, cpu, recommended);
+            } else if current_workers < underutilized_below(stats, recommended) {
+                let rationale = if is_olap {
+                    Message::new(
+                        "concurrency.max_parallel_workers_per_gather.underutilized_analytics",
+                    )
+                    .arg("recommended", recommended)
+                } else {
+                    Message::new("concurrency.max_parallel_workers_per_gather.underutilized")
+                        .arg("recommended", recommended)
+                };
+                add_suggestion(
+                    results,
+                    ConfigCategory::Concurrency,
+                    "max_parallel_workers_per_gather",
+                    &current_value,
+                    &recommended.to_string(),
+                    SuggestionLevel::Recommended,
+                    rationale,
+                );
+                note_large_server(results, cpu, recommended);
             }
         }
     }
@@ -202,9 +296,11 @@ fn analyze_max_parallel_maintenance_workers(
 ) -> Result<()> {
     if let Some(cpu) = stats.cpu_count {
         let current_value = get_param_value(params, "max_parallel_maintenance_workers");
-        let recommended = (cpu / 2).max(1); // Half vCPU, but at least 1
+        let recommended = per_operation_target(cpu, (cpu / 2).max(1)); // Half vCPU, but at least 1
 
-        if let Ok(current_workers) = current_value.parse::<usize>() {
+        if let Some(current_workers) =
+            param_value(params, "max_parallel_maintenance_workers").and_then(ParamValue::as_count)
+        {
             if current_workers < recommended {
                 add_suggestion(
                     results,
@@ -213,13 +309,10 @@ fn analyze_max_parallel_maintenance_workers(
                     &current_value,
                     &recommended.to_string(),
                     SuggestionLevel::Recommended,
-                    &format!(
-                        "max_parallel_maintenance_workers controls parallelism for manual \
-                         VACUUM and CREATE INDEX commands. Setting it to {} (half of vCPUs) \
-                         can significantly speed up maintenance operations.",
-                        recommended
-                    ),
+                    Message::new("concurrency.max_parallel_maintenance_workers")
+                        .arg("recommended", recommended),
                 );
+                note_large_server(results, cpu, recommended);
             }
         }
     }
@@ -229,6 +322,48 @@ fn analyze_max_parallel_maintenance_workers(
 
 // Helper functions
 
+/// Every vCPU, or half of them on large servers so parallel workers leave
+/// cores to the backends on each NUMA node
+fn parallel_workers_target(cpu: usize) -> usize {
+    if cpu > LARGE_SERVER_VCPU {
+        cpu / 2
+    } else {
+        cpu
+    }
+}
+
+/// `target`, capped on large servers
+fn per_operation_target(cpu: usize, target: usize) -> usize {
+    if cpu > LARGE_SERVER_VCPU {
+        target.min(LARGE_SERVER_WORKERS_PER_OPERATION)
+    } else {
+        target
+    }
+}
+
+/// Explains the lower target on the suggestion just added
+fn note_large_server(results: &mut AnalysisResults, cpu: usize, target: usize) {
+    if cpu <= LARGE_SERVER_VCPU {
+        return;
+    }
+    if let Some(suggestion) = results
+        .suggestions_by_category
+        .get_mut(&ConfigCategory::Concurrency)
+        .and_then(|suggestions| suggestions.last_mut())
+    {
+        suggestion.push_note(
+            Message::new("note.large_server_parallel")
+                .arg("vcpu", cpu)
+                .arg("target", target),
+        );
+    }
+}
+
+/// Parallelism settings below this value are reported as underutilized
+fn underutilized_below(stats: &crate::models::SystemStats, recommended: usize) -> usize {
+    (recommended as f64 * stats.profile().thresholds().parallel_underutilized_ratio) as usize
+}
+
 fn get_param_value(params: &HashMap<String, crate::models::PgConfigParam>, name: &str) -> String {
     params
         .get(name)
@@ -236,26 +371,211 @@ fn get_param_value(params: &HashMap<String, crate::models::PgConfigParam>, name:
         .unwrap_or_else(|| "unknown".to_string())
 }
 
-fn add_suggestion(
-    results: &mut AnalysisResults,
+fn add_suggestion<'a>(
+    results: &'a mut AnalysisResults,
     category: ConfigCategory,
     parameter: &str,
     current_value: &str,
     suggested_value: &str,
     level: SuggestionLevel,
-    rationale: &str,
-) {
+    rationale: Message,
+) -> &'a mut ConfigSuggestion {
     let suggestion = ConfigSuggestion {
         parameter: parameter.to_string(),
         current_value: current_value.to_string(),
         suggested_value: suggested_value.to_string(),
         level,
-        rationale: rationale.to_string(),
+        rationale: rationale.render(Lang::En),
+        messages: vec![rationale],
+        fingerprint: String::new(),
     };
 
-    results
-        .suggestions_by_category
-        .entry(category)
-        .or_default()
-        .push(suggestion);
+    let suggestions = results.suggestions_by_category.entry(category).or_default();
+    suggestions.push(suggestion);
+    suggestions.last_mut().expect("just pushed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::WorkloadType;
+    use crate::models::{ConnectionSaturation, HourlyPeak, PgConfigParam, SystemStats};
+    use crate::profile::Profile;
+    use rstest::rstest;
+
+    fn create_param(value: &str) -> PgConfigParam {
+        PgConfigParam {
+            name: "test".to_string(),
+            current_value: value.to_string(),
+            default_value: None,
+            unit: None,
+            context: "postmaster".to_string(),
+            value: ParamValue::parse(value, "", None),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        }
+    }
+
+    #[test]
+    fn test_parallel_workers_per_gather_multi_tenant() {
+        let mut params = HashMap::new();
+        params.insert(
+            "max_parallel_workers_per_gather".to_string(),
+            create_param("8"),
+        );
+
+        let stats = SystemStats {
+            cpu_count: Some(16),
+            profile: Some(Profile::MultiTenant),
+            ..Default::default()
+        };
+
+        let mut results = AnalysisResults::default();
+        analyze_max_parallel_workers_per_gather(&params, &stats, &mut results).unwrap();
+
+        // 8 is half of vCPUs: fine for a web app, but twice the multi-tenant target
+        let suggestion = &results.suggestions_by_category[&ConfigCategory::Concurrency][0];
+        assert_eq!(suggestion.suggested_value, "4");
+        assert_eq!(suggestion.level, SuggestionLevel::Recommended);
+
+        params.insert(
+            "max_parallel_workers_per_gather".to_string(),
+            create_param("4"),
+        );
+        let mut results = AnalysisResults::default();
+        analyze_max_parallel_workers_per_gather(&params, &stats, &mut results).unwrap();
+        assert!(results.suggestions_by_category.is_empty());
+    }
+
+    #[test]
+    fn test_parallel_workers_per_gather_olap() {
+        let mut params = HashMap::new();
+        params.insert(
+            "max_parallel_workers_per_gather".to_string(),
+            create_param("4"),
+        );
+
+        let stats = SystemStats {
+            cpu_count: Some(16),
+            workload_type: WorkloadType::Olap,
+            ..Default::default()
+        };
+
+        let mut results = AnalysisResults::default();
+        analyze_max_parallel_workers_per_gather(&params, &stats, &mut results).unwrap();
+
+        let suggestion = &results.suggestions_by_category[&ConfigCategory::Concurrency][0];
+        assert_eq!(suggestion.suggested_value, "12");
+        assert_eq!(suggestion.level, SuggestionLevel::Recommended);
+    }
+
+    fn observed(peak: usize, max_connections: usize) -> ConnectionSaturation {
+        ConnectionSaturation {
+            samples: 3,
+            first_at: "2026-10-15T09:00:00Z".to_string(),
+            last_at: "2026-10-16T14:00:00Z".to_string(),
+            peak_connections: peak,
+            peak_at: "2026-10-16T09:30:00Z".to_string(),
+            max_connections,
+            peak_utilization: peak as f64 / max_connections as f64,
+            hourly_peaks: vec![
+                HourlyPeak {
+                    hour: 9,
+                    peak_connections: peak,
+                    samples: 2,
+                },
+                HourlyPeak {
+                    hour: 14,
+                    peak_connections: peak / 4,
+                    samples: 1,
+                },
+            ],
+        }
+    }
+
+    #[rstest]
+    // Formula alone: 4 * 16 vCPU = 64, floored at 100
+    #[case("400", None, Some(("100", SuggestionLevel::Critical)))]
+    // The sampled peak of 150 needs 190 with headroom
+    #[case("300", Some(150), Some(("190", SuggestionLevel::Important)))]
+    #[case("200", Some(155), None)]
+    #[case("200", Some(170), Some(("220", SuggestionLevel::Important)))]
+    #[case("200", Some(195), Some(("250", SuggestionLevel::Critical)))]
+    fn max_connections_follows_the_observed_peak(
+        #[case] max_connections: &str,
+        #[case] peak: Option<usize>,
+        #[case] expected: Option<(&str, SuggestionLevel)>,
+    ) {
+        let params =
+            HashMap::from([("max_connections".to_string(), create_param(max_connections))]);
+        let stats = SystemStats {
+            cpu_count: Some(16),
+            ..Default::default()
+        };
+        let mut results = AnalysisResults {
+            connection_saturation: peak
+                .map(|peak| observed(peak, max_connections.parse().unwrap())),
+            ..Default::default()
+        };
+
+        analyze_max_connections(&params, &stats, &mut results).unwrap();
+
+        let suggestion = results
+            .suggestions_by_category
+            .get(&ConfigCategory::Concurrency)
+            .and_then(|suggestions| suggestions.first());
+        assert_eq!(
+            suggestion.map(|s| (s.suggested_value.as_str(), s.level)),
+            expected
+        );
+        if let (Some(suggestion), Some(peak)) = (suggestion, peak) {
+            assert!(
+                suggestion
+                    .rationale
+                    .contains(&format!("{peak} connections")),
+                "{}",
+                suggestion.rationale
+            );
+        }
+    }
+
+    #[test]
+    fn large_servers_cap_parallel_workers() {
+        let params: HashMap<_, _> = [
+            ("max_parallel_workers", "96"),
+            ("max_parallel_workers_per_gather", "2"),
+            ("max_parallel_maintenance_workers", "2"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), create_param(value)))
+        .collect();
+        let stats = SystemStats {
+            cpu_count: Some(96),
+            ..Default::default()
+        };
+
+        let mut results = AnalysisResults::default();
+        analyze_concurrency(&params, &stats, &mut results).unwrap();
+
+        let suggested: Vec<_> = results.suggestions_by_category[&ConfigCategory::Concurrency]
+            .iter()
+            .map(|s| (s.parameter.as_str(), s.suggested_value.as_str()))
+            .collect();
+        assert_eq!(
+            suggested,
+            [
+                ("max_parallel_workers", "48"),
+                ("max_parallel_workers_per_gather", "8"),
+                ("max_parallel_maintenance_workers", "8"),
+            ]
+        );
+        assert!(
+            results.suggestions_by_category[&ConfigCategory::Concurrency][0]
+                .rationale
+                .ends_with("caps the target at 48.")
+        );
+    }
 }
@@ -0,0 +1,169 @@
+use crate::models::{ConnectionSample, ConnectionSaturation, HourlyPeak, PgConfigParam};
+use chrono::{DateTime, Timelike};
+use sqlx::{Pool, Postgres, Row};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::warn;
+
+/// Connection counts taken across a `--sample-window`
+const SAMPLES_PER_WINDOW: u32 = 20;
+const MIN_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+const MAX_SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+
+pub(crate) const CONNECTION_SAMPLE_QUERY: &str = r#"
+    SELECT
+        to_char(now() AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS"Z"') AS at,
+        coalesce(sum(numbackends), 0)::bigint AS connections
+    FROM pg_stat_database
+"#;
+
+/// Counts client backends across all databases, the connections that count
+/// against max_connections
+pub async fn fetch_connection_sample(pool: &Pool<Postgres>) -> Option<ConnectionSample> {
+    match sqlx::query(CONNECTION_SAMPLE_QUERY).fetch_one(pool).await {
+        Ok(row) => Some(ConnectionSample {
+            at: row.get("at"),
+            connections: row.get::<i64, _>("connections").max(0) as usize,
+        }),
+        Err(err) => {
+            warn!("Failed to read pg_stat_database for connection samples: {err}");
+            None
+        }
+    }
+}
+
+/// Samples the connection count at regular intervals until `window` has passed,
+/// in place of sleeping through it
+pub async fn sample_over(pool: &Pool<Postgres>, window: Duration) -> Vec<ConnectionSample> {
+    let interval = (window / SAMPLES_PER_WINDOW).clamp(MIN_SAMPLE_INTERVAL, MAX_SAMPLE_INTERVAL);
+    let deadline = Instant::now() + window;
+    let mut samples = Vec::new();
+    while Instant::now() < deadline {
+        samples.extend(fetch_connection_sample(pool).await);
+        tokio::time::sleep_until((Instant::now() + interval).min(deadline)).await;
+    }
+    samples
+}
+
+/// Peak, its utilization of max_connections and the peak per hour of day.
+/// `None` without samples or a readable max_connections.
+pub fn summarize(
+    samples: &[ConnectionSample],
+    params: &HashMap<String, PgConfigParam>,
+) -> Option<ConnectionSaturation> {
+    let max_connections = params.get("max_connections")?.value.as_count()?;
+    let peak = samples.iter().max_by_key(|sample| sample.connections)?;
+    let mut ordered: Vec<&str> = samples.iter().map(|sample| sample.at.as_str()).collect();
+    ordered.sort_unstable();
+
+    let mut hours: BTreeMap<u32, HourlyPeak> = BTreeMap::new();
+    for sample in samples {
+        let Ok(at) = DateTime::parse_from_rfc3339(&sample.at) else {
+            continue;
+        };
+        let hour = hours.entry(at.hour()).or_insert(HourlyPeak {
+            hour: at.hour(),
+            peak_connections: 0,
+            samples: 0,
+        });
+        hour.peak_connections = hour.peak_connections.max(sample.connections);
+        hour.samples += 1;
+    }
+
+    Some(ConnectionSaturation {
+        samples: samples.len(),
+        first_at: ordered.first()?.to_string(),
+        last_at: ordered.last()?.to_string(),
+        peak_connections: peak.connections,
+        peak_at: peak.at.clone(),
+        max_connections,
+        peak_utilization: peak.connections as f64 / max_connections.max(1) as f64,
+        hourly_peaks: hours.into_values().collect(),
+    })
+}
+
+/// Hours whose peak comes within 80% of the overall peak, e.g.
+/// `09:00-10:00 UTC, 14:00-15:00 UTC`; empty when samples span a single hour
+pub fn busy_hours(saturation: &ConnectionSaturation) -> String {
+    if saturation.hourly_peaks.len() < 2 {
+        return String::new();
+    }
+    saturation
+        .hourly_peaks
+        .iter()
+        .filter(|hour| hour.peak_connections * 5 >= saturation.peak_connections * 4)
+        .map(|hour| format!("{:02}:00-{:02}:00 UTC", hour.hour, (hour.hour + 1) % 24))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ParamValue;
+
+    fn samples(counts: &[(&str, usize)]) -> Vec<ConnectionSample> {
+        counts
+            .iter()
+            .map(|(at, connections)| ConnectionSample {
+                at: at.to_string(),
+                connections: *connections,
+            })
+            .collect()
+    }
+
+    fn max_connections(value: &str) -> HashMap<String, PgConfigParam> {
+        let param = PgConfigParam {
+            name: "max_connections".to_string(),
+            current_value: value.to_string(),
+            default_value: None,
+            unit: None,
+            context: "postmaster".to_string(),
+            value: ParamValue::parse(value, "integer", None),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        };
+        HashMap::from([(param.name.clone(), param)])
+    }
+
+    #[test]
+    fn samples_are_summarized_by_peak_and_hour() {
+        let samples = samples(&[
+            ("2026-10-15T09:10:00Z", 150),
+            ("2026-10-15T09:40:00Z", 180),
+            ("2026-10-15T03:00:00Z", 20),
+            ("2026-10-16T14:05:00Z", 160),
+        ]);
+
+        let saturation = summarize(&samples, &max_connections("200")).unwrap();
+
+        assert_eq!(saturation.samples, 4);
+        assert_eq!(saturation.first_at, "2026-10-15T03:00:00Z");
+        assert_eq!(saturation.last_at, "2026-10-16T14:05:00Z");
+        assert_eq!(saturation.peak_connections, 180);
+        assert_eq!(saturation.peak_at, "2026-10-15T09:40:00Z");
+        assert!((saturation.peak_utilization - 0.9).abs() < 1e-9);
+        let hours: Vec<_> = saturation
+            .hourly_peaks
+            .iter()
+            .map(|hour| (hour.hour, hour.peak_connections, hour.samples))
+            .collect();
+        assert_eq!(hours, [(3, 20, 1), (9, 180, 2), (14, 160, 1)]);
+        assert_eq!(busy_hours(&saturation), "09:00-10:00 UTC, 14:00-15:00 UTC");
+    }
+
+    #[test]
+    fn nothing_to_summarize_without_samples() {
+        assert!(summarize(&[], &max_connections("100")).is_none());
+        let one = samples(&[("2026-10-16T14:05:00Z", 10)]);
+        assert!(summarize(&one, &HashMap::new()).is_none());
+        assert_eq!(
+            busy_hours(&summarize(&one, &max_connections("100")).unwrap()),
+            ""
+        );
+    }
+}
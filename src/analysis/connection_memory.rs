@@ -0,0 +1,151 @@
+use crate::config::WorkloadType;
+use crate::models::{ByteSize, ConnectionMemory, MemoryHeadroom, PgConfigParam, SystemStats};
+use std::collections::HashMap;
+
+/// Private memory of an idle backend. Catalog and plan caches usually put a
+/// busy one at 5-15MB, before any work_mem is allocated.
+pub const BACKEND_OVERHEAD_BYTES: u64 = 10 * 1024 * 1024;
+/// PostgreSQL's default temp_buffers, used when pg_settings does not report it
+const DEFAULT_TEMP_BUFFERS_BYTES: u64 = 8 * 1024 * 1024;
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Estimates per-connection memory and compares it with the RAM left after
+/// shared_buffers. Returns `None` without `work_mem`, `max_connections` or a
+/// connection count to scale by.
+pub fn estimate_connection_memory(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+) -> Option<ConnectionMemory> {
+    let work_mem = params.get("work_mem")?.value.as_bytes()?;
+    let max_connections = params.get("max_connections")?.value.as_count()?;
+    let peak_connections = stats.peak_connection_count.or(stats.connection_count)?;
+    let temp_buffers = params
+        .get("temp_buffers")
+        .and_then(|param| param.value.as_bytes())
+        .unwrap_or(DEFAULT_TEMP_BUFFERS_BYTES);
+
+    // Analytics plans stack several sorts and hashes; OLTP queries rarely need more than one
+    let work_mem_nodes = match stats.profile().workload_type() {
+        WorkloadType::Oltp => 1,
+        WorkloadType::Olap => 3,
+    };
+    let active_share = match (stats.active_connection_count, stats.connection_count) {
+        (Some(active), Some(total)) if total > 0 => (active as f64 / total as f64).min(1.0),
+        _ => 1.0,
+    };
+
+    let all_work_mem = work_mem as f64 * work_mem_nodes as f64;
+    let expected_per_connection = BACKEND_OVERHEAD_BYTES as f64 + all_work_mem * active_share;
+    let worst_per_connection = BACKEND_OVERHEAD_BYTES as f64 + all_work_mem + temp_buffers as f64;
+    let expected_at_peak = expected_per_connection * peak_connections as f64;
+    let worst_at_max_connections = worst_per_connection * max_connections as f64;
+
+    let shared_buffers = params
+        .get("shared_buffers")
+        .and_then(|param| param.value.as_bytes())
+        .unwrap_or(0);
+    let available = stats
+        .total_memory_gb
+        .map(|gb| (gb * GIB - shared_buffers as f64).max(0.0));
+    let headroom = match available {
+        None => MemoryHeadroom::Unknown,
+        Some(available) if expected_at_peak > available => MemoryHeadroom::Overcommitted,
+        Some(available) if worst_at_max_connections > available => MemoryHeadroom::Tight,
+        Some(_) => MemoryHeadroom::Comfortable,
+    };
+
+    Some(ConnectionMemory {
+        backend_overhead: ByteSize(BACKEND_OVERHEAD_BYTES as i64),
+        work_mem: ByteSize(work_mem as i64),
+        work_mem_nodes,
+        active_share,
+        temp_buffers: ByteSize(temp_buffers as i64),
+        expected_per_connection: ByteSize(expected_per_connection as i64),
+        worst_per_connection: ByteSize(worst_per_connection as i64),
+        peak_connections,
+        max_connections,
+        expected_at_peak: ByteSize(expected_at_peak as i64),
+        worst_at_max_connections: ByteSize(worst_at_max_connections as i64),
+        available: available.map(|bytes| ByteSize(bytes as i64)),
+        headroom,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ParamValue;
+    use rstest::rstest;
+
+    const MB: i64 = 1024 * 1024;
+
+    fn param(name: &str, value: &str, unit: Option<&str>) -> PgConfigParam {
+        PgConfigParam {
+            name: name.to_string(),
+            current_value: value.to_string(),
+            default_value: None,
+            unit: unit.map(str::to_string),
+            context: "user".to_string(),
+            value: ParamValue::parse(value, "", unit),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        }
+    }
+
+    fn params(work_mem_kb: &str, max_connections: &str) -> HashMap<String, PgConfigParam> {
+        [
+            param("work_mem", work_mem_kb, Some("kB")),
+            param("max_connections", max_connections, None),
+            param("temp_buffers", "1024", Some("8kB")),
+            param("shared_buffers", "262144", Some("8kB")),
+        ]
+        .into_iter()
+        .map(|param| (param.name.clone(), param))
+        .collect()
+    }
+
+    fn stats(total_memory_gb: Option<f64>) -> SystemStats {
+        SystemStats {
+            total_memory_gb,
+            connection_count: Some(40),
+            active_connection_count: Some(10),
+            peak_connection_count: Some(80),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn scales_per_connection_estimates_by_peak_and_max_connections() {
+        let estimate = estimate_connection_memory(&params("65536", "200"), &stats(Some(8.0)))
+            .expect("estimate");
+
+        assert_eq!(estimate.active_share, 0.25);
+        assert_eq!(estimate.expected_per_connection, ByteSize(26 * MB));
+        assert_eq!(estimate.worst_per_connection, ByteSize(82 * MB));
+        assert_eq!(estimate.expected_at_peak, ByteSize(80 * 26 * MB));
+        assert_eq!(estimate.worst_at_max_connections, ByteSize(200 * 82 * MB));
+        assert_eq!(estimate.available, Some(ByteSize(6 * 1024 * MB)));
+    }
+
+    #[rstest]
+    #[case::fits_everything("4096", "100", Some(8.0), MemoryHeadroom::Comfortable)]
+    #[case::max_connections_would_not_fit("65536", "200", Some(8.0), MemoryHeadroom::Tight)]
+    #[case::peak_does_not_fit("1048576", "200", Some(8.0), MemoryHeadroom::Overcommitted)]
+    #[case::no_compute_size("4096", "100", None, MemoryHeadroom::Unknown)]
+    fn headroom_compares_against_ram_after_shared_buffers(
+        #[case] work_mem_kb: &str,
+        #[case] max_connections: &str,
+        #[case] total_memory_gb: Option<f64>,
+        #[case] expected: MemoryHeadroom,
+    ) {
+        let estimate = estimate_connection_memory(
+            &params(work_mem_kb, max_connections),
+            &stats(total_memory_gb),
+        )
+        .expect("estimate");
+        assert_eq!(estimate.headroom, expected);
+    }
+}
@@ -0,0 +1,301 @@
+use crate::analysis::query_parser::parse_query_columns;
+use crate::analysis::resilience::format_secs;
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ConfigCategory, ConfigSuggestion, CursorSession, SuggestionLevel,
+};
+use sqlx::{Pool, Postgres, Row};
+use tracing::warn;
+
+/// A transaction left idle this long with a cursor open is reported
+const IDLE_TRANSACTION_SECS: i64 = 300;
+/// A WITH HOLD cursor left open this long after its transaction is reported
+const HELD_CURSOR_SECS: i64 = 3600;
+/// Bloated tables named in a finding
+const BLOATED_TABLES_SHOWN: usize = 5;
+
+pub(crate) const CURSOR_SESSIONS_QUERY: &str = r#"
+    SELECT
+        pid,
+        usename::text AS usename,
+        coalesce(application_name, '') AS application_name,
+        state,
+        query,
+        extract(epoch FROM now() - state_change)::bigint AS idle_secs,
+        age(backend_xmin)::bigint AS xmin_age
+    FROM pg_stat_activity
+    WHERE datname = current_database()
+      AND pid <> pg_backend_pid()
+      AND state IN ('idle', 'idle in transaction', 'idle in transaction (aborted)')
+      AND query ~* '^\s*(declare|fetch|move)\M'
+    ORDER BY idle_secs DESC
+"#;
+
+/// Idle sessions of this database whose last statement was DECLARE, FETCH
+/// or MOVE. `pg_cursors` only lists the connecting session's own cursors, so
+/// other sessions' cursors are inferred from `pg_stat_activity`.
+pub async fn fetch_cursor_sessions(pool: &Pool<Postgres>) -> Vec<CursorSession> {
+    match sqlx::query(CURSOR_SESSIONS_QUERY).fetch_all(pool).await {
+        Ok(rows) => rows
+            .into_iter()
+            .map(|row| CursorSession {
+                pid: row.get("pid"),
+                usename: row.get("usename"),
+                application_name: row.get("application_name"),
+                state: row.get("state"),
+                query: row.get("query"),
+                idle_secs: row.get("idle_secs"),
+                xmin_age: row.get("xmin_age"),
+            })
+            .collect(),
+        Err(err) => {
+            warn!("Failed to read cursor sessions from pg_stat_activity: {err}");
+            Vec::new()
+        }
+    }
+}
+
+/// Flags transactions left idle with a cursor open, whose snapshot keeps
+/// VACUUM from removing dead tuples in every table, and WITH HOLD cursors
+/// left open after their transaction. Runs after the table health analysis
+/// so the finding can name the tables whose dead tuples are piling up.
+pub fn analyze_open_cursors(results: &mut AnalysisResults) {
+    let bloated: Vec<String> = results
+        .table_health
+        .bloat_info
+        .iter()
+        .take(BLOATED_TABLES_SHOWN)
+        .map(|table| format!("{}.{}", table.schema, table.table_name))
+        .collect();
+    let sessions = results.system_stats.cursor_sessions.clone();
+
+    for session in &sessions {
+        let declared = DeclaredCursor::parse(&session.query);
+        let tables = declared
+            .as_ref()
+            .and_then(|cursor| parse_query_columns(cursor.query).ok())
+            .map(|usage| {
+                usage
+                    .tables
+                    .iter()
+                    .map(|table| table.full_name())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let who = describe_session(session);
+        let idle = format_secs(session.idle_secs.max(0) as u64);
+
+        let (mut suggestion, tables_key) = if session.state.starts_with("idle in transaction") {
+            if session.idle_secs < IDLE_TRANSACTION_SECS {
+                continue;
+            }
+            let mut suggestion = cursor_suggestion(
+                session,
+                format!("idle in transaction for {idle}"),
+                "COMMIT or ROLLBACK",
+                SuggestionLevel::Important,
+                Message::new("autovacuum.idle_cursor_transaction")
+                    .arg("session", &who)
+                    .arg("idle", &idle)
+                    .arg(
+                        "xmin_age",
+                        session
+                            .xmin_age
+                            .map_or_else(|| "?".to_string(), |age| age.to_string()),
+                    ),
+            );
+            if !bloated.is_empty() {
+                suggestion.push_note(
+                    Message::new("autovacuum.idle_cursor_transaction.bloat")
+                        .arg("tables", bloated.join(", ")),
+                );
+            }
+            (suggestion, "autovacuum.idle_cursor_transaction.tables")
+        } else {
+            // Outside a transaction only a WITH HOLD cursor survives, so a FETCH
+            // or MOVE here reads one; a plain DECLARE was closed at commit
+            if declared.is_some_and(|cursor| !cursor.with_hold)
+                || session.idle_secs < HELD_CURSOR_SECS
+            {
+                continue;
+            }
+            let suggestion = cursor_suggestion(
+                session,
+                format!("held cursor open for {idle}"),
+                "CLOSE the cursor",
+                SuggestionLevel::Recommended,
+                Message::new("autovacuum.held_cursor")
+                    .arg("session", &who)
+                    .arg("idle", &idle),
+            );
+            (suggestion, "autovacuum.held_cursor.tables")
+        };
+        if !tables.is_empty() {
+            suggestion.push_note(Message::new(tables_key).arg("tables", tables.join(", ")));
+        }
+        results
+            .suggestions_by_category
+            .entry(ConfigCategory::Autovacuum)
+            .or_default()
+            .push(suggestion);
+    }
+}
+
+/// `DECLARE name [options] CURSOR [WITH HOLD] FOR query`
+struct DeclaredCursor<'a> {
+    with_hold: bool,
+    query: &'a str,
+}
+
+impl<'a> DeclaredCursor<'a> {
+    fn parse(statement: &'a str) -> Option<Self> {
+        let statement = statement.trim();
+        let mut words = Vec::new();
+        let mut offset = 0;
+        for word in statement.split_ascii_whitespace() {
+            offset += statement[offset..].find(word)? + word.len();
+            let word = word.to_ascii_lowercase();
+            if words.is_empty() && word != "declare" {
+                return None;
+            }
+            if word == "for" && words.iter().any(|w| w == "cursor") {
+                let with_hold = words.windows(2).any(|pair| pair == ["with", "hold"]);
+                let query = statement[offset..].trim().trim_end_matches(';');
+                return Some(Self { with_hold, query });
+            }
+            words.push(word);
+        }
+        None
+    }
+}
+
+fn describe_session(session: &CursorSession) -> String {
+    let mut who = format!("pid {}", session.pid);
+    if let Some(user) = &session.usename {
+        who.push_str(&format!(" ({user}"));
+        if !session.application_name.is_empty() {
+            who.push_str(&format!(", {}", session.application_name));
+        }
+        who.push(')');
+    }
+    who
+}
+
+fn cursor_suggestion(
+    session: &CursorSession,
+    current_value: String,
+    suggested_value: &str,
+    level: SuggestionLevel,
+    rationale: Message,
+) -> ConfigSuggestion {
+    ConfigSuggestion {
+        parameter: format!("session {}", session.pid),
+        current_value,
+        suggested_value: suggested_value.to_string(),
+        level,
+        rationale: rationale.render(Lang::En),
+        messages: vec![rationale],
+        fingerprint: String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TableBloatInfo;
+    use rstest::rstest;
+
+    fn session(state: &str, query: &str, idle_secs: i64) -> CursorSession {
+        CursorSession {
+            pid: 4242,
+            usename: Some("app".to_string()),
+            application_name: "exporter".to_string(),
+            state: state.to_string(),
+            query: query.to_string(),
+            idle_secs,
+            xmin_age: Some(1_500_000),
+        }
+    }
+
+    fn analyzed(sessions: Vec<CursorSession>, bloated: &[&str]) -> Vec<ConfigSuggestion> {
+        let mut results = AnalysisResults::default();
+        results.system_stats.cursor_sessions = sessions;
+        results.table_health.bloat_info = bloated
+            .iter()
+            .map(|name| TableBloatInfo {
+                schema: "public".to_string(),
+                table_name: name.to_string(),
+                live_tuples: 1000,
+                dead_tuples: 900,
+                dead_tup_ratio: 0.9,
+                seq_scan: 0,
+                idx_scan: 0,
+                table_size_bytes: Default::default(),
+                table_size_pretty: String::new(),
+                last_autovacuum: None,
+                last_autoanalyze: None,
+                seconds_since_last_autovacuum: None,
+                seconds_since_last_autoanalyze: None,
+                fingerprint: String::new(),
+            })
+            .collect();
+        analyze_open_cursors(&mut results);
+        results
+            .suggestions_by_category
+            .remove(&ConfigCategory::Autovacuum)
+            .unwrap_or_default()
+    }
+
+    #[rstest]
+    #[case::plain(
+        "DECLARE c CURSOR FOR SELECT * FROM orders",
+        false,
+        "SELECT * FROM orders"
+    )]
+    #[case::with_hold(
+        "declare c no scroll cursor with hold for\n  select id from public.events;",
+        true,
+        "select id from public.events"
+    )]
+    fn declare_statements_are_parsed(
+        #[case] statement: &str,
+        #[case] with_hold: bool,
+        #[case] query: &str,
+    ) {
+        let cursor = DeclaredCursor::parse(statement).unwrap();
+        assert_eq!(cursor.with_hold, with_hold);
+        assert_eq!(cursor.query, query);
+    }
+
+    #[test]
+    fn idle_transaction_with_cursor_names_its_and_bloated_tables() {
+        let suggestions = analyzed(
+            vec![session(
+                "idle in transaction",
+                "DECLARE c CURSOR FOR SELECT * FROM orders o JOIN customers c ON c.id = o.customer_id",
+                1800,
+            )],
+            &["events"],
+        );
+        assert_eq!(suggestions.len(), 1);
+        let suggestion = &suggestions[0];
+        assert_eq!(suggestion.parameter, "session 4242");
+        assert_eq!(suggestion.level, SuggestionLevel::Important);
+        assert_eq!(suggestion.current_value, "idle in transaction for 30min");
+        assert!(suggestion.rationale.contains("public.events"));
+        assert!(suggestion.rationale.contains("orders, customers"));
+    }
+
+    #[rstest]
+    #[case::recent_transaction(session("idle in transaction", "FETCH 100 FROM c", 60), false)]
+    #[case::held_fetch(session("idle", "FETCH 100 FROM c", 7200), true)]
+    #[case::held_declare(session("idle", "DECLARE c CURSOR WITH HOLD FOR SELECT 1", 7200), true)]
+    #[case::closed_at_commit(session("idle", "DECLARE c CURSOR FOR SELECT 1", 7200), false)]
+    #[case::recent_held(session("idle", "FETCH 100 FROM c", 600), false)]
+    fn sessions_are_reported_after_their_threshold(
+        #[case] session: CursorSession,
+        #[case] reported: bool,
+    ) {
+        assert_eq!(analyzed(vec![session], &[]).len(), usize::from(reported));
+    }
+}
@@ -0,0 +1,339 @@
+use crate::analysis::table_index::quote_ident;
+use crate::catalog::Catalog;
+use crate::checker::CheckerError;
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ConfigCategory, ConfigSuggestion, ExtendedStatistics, SuggestionLevel,
+    SystemStats,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres, Row};
+use tracing::warn;
+
+type Result<T> = std::result::Result<T, CheckerError>;
+
+/// Rows read from a table to compare distinct counts
+const SAMPLE_ROWS: i64 = 30_000;
+/// Tables smaller than this are left to the single-column estimates
+const MIN_TABLE_ROWS: i64 = 10_000;
+/// A combination with at most this many times the distinct values of its most
+/// distinct column adds nothing the planner could not derive from that column
+const DEPENDENT_RATIO: f64 = 1.2;
+/// Independent columns must be expected to yield at least this many times the
+/// most distinct column's values for the comparison to tell them apart
+const MIN_INDEPENDENT_RATIO: f64 = 2.0;
+
+/// Statements filtering one table by equality on the same set of columns
+#[derive(Debug, Clone)]
+pub(crate) struct EqualityGroup {
+    pub schema: String,
+    pub table: String,
+    /// Sorted, without duplicates
+    pub columns: Vec<String>,
+    /// Calls of every statement with this filter
+    pub calls: i64,
+    /// The most called statement with this filter
+    pub queryid: i64,
+    pub statement_calls: i64,
+    pub mean_time_ms: f64,
+    pub query: String,
+}
+
+/// Distinct values of each column and of their combination in a table sample
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct SampledDistinct {
+    pub sampled: i64,
+    pub per_column: Vec<i64>,
+    pub combined: i64,
+}
+
+impl SampledDistinct {
+    /// Whether the columns determine each other: their combination has barely
+    /// more distinct values than the most distinct column alone, where
+    /// independent columns would multiply them. The planner multiplies the
+    /// columns' selectivities, so it underestimates rows matching all of them.
+    pub(crate) fn is_correlated(&self) -> bool {
+        let Some(&most) = self.per_column.iter().max() else {
+            return false;
+        };
+        if most < 2 || self.per_column.len() < 2 {
+            return false;
+        }
+        let independent = self
+            .per_column
+            .iter()
+            .fold(1.0_f64, |product, &distinct| {
+                product * distinct.max(1) as f64
+            })
+            .min(self.sampled as f64);
+        independent >= MIN_INDEPENDENT_RATIO * most as f64
+            && self.combined as f64 <= DEPENDENT_RATIO * most as f64
+    }
+}
+
+pub(crate) const RELTUPLES_QUERY: &str = r#"
+    SELECT c.reltuples::bigint
+    FROM pg_class c
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    WHERE n.nspname = $1 AND c.relname = $2
+"#;
+
+/// Counts distinct values of the group's columns, separately and combined,
+/// in a block sample of about `SAMPLE_ROWS` rows. Returns `None` for tables
+/// under `MIN_TABLE_ROWS`.
+pub(crate) async fn sample_distinct(
+    pool: &Pool<Postgres>,
+    group: &EqualityGroup,
+) -> Result<Option<SampledDistinct>> {
+    let reltuples: Option<i64> = sqlx::query_scalar(RELTUPLES_QUERY)
+        .bind(&group.schema)
+        .bind(&group.table)
+        .fetch_optional(pool)
+        .await
+        .map_err(|source| CheckerError::QueryError {
+            query: RELTUPLES_QUERY.into(),
+            source,
+        })?;
+    let Some(reltuples) = reltuples.filter(|&rows| rows >= MIN_TABLE_ROWS) else {
+        return Ok(None);
+    };
+
+    let percent = (SAMPLE_ROWS as f64 / reltuples as f64 * 100.0).clamp(0.01, 100.0);
+    let columns: Vec<String> = group.columns.iter().map(|c| quote_ident(c)).collect();
+    let table = format!(
+        "{}.{}",
+        quote_ident(&group.schema),
+        quote_ident(&group.table)
+    );
+    let query = sample_distinct_query(&columns, &table, &format!("{percent:.2}"));
+    let row = sqlx::query(&query)
+        .fetch_one(pool)
+        .await
+        .map_err(|source| CheckerError::QueryError {
+            query: query.clone(),
+            source,
+        })?;
+    Ok(Some(SampledDistinct {
+        sampled: row.get("sampled"),
+        per_column: (0..columns.len())
+            .map(|i| row.get(format!("d{i}").as_str()))
+            .collect(),
+        combined: row.get("combined"),
+    }))
+}
+
+pub(crate) const COVERED_COLUMNS_QUERY: &str = r#"
+    SELECT
+        n.nspname::text AS schema,
+        c.relname::text AS table_name,
+        ARRAY(
+            SELECT a.attname::text
+            FROM pg_attribute a
+            WHERE a.attrelid = s.stxrelid AND a.attnum = ANY(s.stxkeys)
+        ) AS columns
+    FROM pg_statistic_ext s
+    JOIN pg_class c ON c.oid = s.stxrelid
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    JOIN unnest($1::text[], $2::text[]) AS t(schema, table_name)
+        ON t.schema = n.nspname AND t.table_name = c.relname
+"#;
+
+/// Distinct counts of quoted `columns` in a `percent` block sample of `table`
+pub(crate) fn sample_distinct_query(columns: &[String], table: &str, percent: &str) -> String {
+    let per_column: Vec<String> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| format!("count(DISTINCT {column}) AS d{i}"))
+        .collect();
+    format!(
+        "SELECT count(*) AS sampled, {}, count(DISTINCT ({})) AS combined \
+         FROM (SELECT {} FROM {table} TABLESAMPLE SYSTEM ({percent}) LIMIT {SAMPLE_ROWS}) sample",
+        per_column.join(", "),
+        columns.join(", "),
+        columns.join(", "),
+    )
+}
+
+/// Column sets of the extended statistics on the given `(schema, table)` pairs
+pub(crate) async fn fetch_covered_columns(
+    pool: &Pool<Postgres>,
+    tables: &[(String, String)],
+) -> Result<Vec<(String, String, Vec<String>)>> {
+    let schemas: Vec<&str> = tables.iter().map(|(schema, _)| schema.as_str()).collect();
+    let names: Vec<&str> = tables.iter().map(|(_, table)| table.as_str()).collect();
+    let rows = sqlx::query(COVERED_COLUMNS_QUERY)
+        .bind(&schemas)
+        .bind(&names)
+        .fetch_all(pool)
+        .await
+        .map_err(|source| CheckerError::QueryError {
+            query: COVERED_COLUMNS_QUERY.into(),
+            source,
+        })?;
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.get("schema"), row.get("table_name"), row.get("columns")))
+        .collect())
+}
+
+/// The statistics object to create for a correlated group; multi-column MCV
+/// lists need PostgreSQL 12+
+pub(crate) fn create_statistics_ddl(group: &EqualityGroup, with_mcv: bool) -> String {
+    let columns: Vec<String> = group.columns.iter().map(|c| quote_ident(c)).collect();
+    let kinds = if with_mcv {
+        "dependencies, ndistinct, mcv"
+    } else {
+        "dependencies, ndistinct"
+    };
+    format!(
+        "CREATE STATISTICS {} ({kinds}) ON {} FROM {}.{}; ANALYZE {}.{};",
+        quote_ident(&format!(
+            "{}_{}_stats",
+            group.table,
+            group.columns.join("_")
+        )),
+        columns.join(", "),
+        quote_ident(&group.schema),
+        quote_ident(&group.table),
+        quote_ident(&group.schema),
+        quote_ident(&group.table),
+    )
+}
+
+pub(crate) const UNANALYZED_STATISTICS_QUERY: &str = r#"
+    SELECT
+        sn.nspname::text AS schema,
+        s.stxname::text AS name,
+        format('%s.%s', n.nspname, c.relname) AS table_name
+    FROM pg_statistic_ext s
+    JOIN pg_namespace sn ON sn.oid = s.stxnamespace
+    JOIN pg_class c ON c.oid = s.stxrelid
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    WHERE has_table_privilege(c.oid, 'SELECT')
+      AND ('d' = ANY(s.stxkind) OR 'f' = ANY(s.stxkind) OR 'm' = ANY(s.stxkind))
+      AND NOT EXISTS (
+          SELECT 1 FROM pg_stats_ext e
+          WHERE e.statistics_schemaname = sn.nspname
+            AND e.statistics_name = s.stxname
+            AND (e.n_distinct IS NOT NULL
+                 OR e.dependencies IS NOT NULL
+                 OR e.most_common_vals IS NOT NULL)
+      )
+    ORDER BY 1, 2
+"#;
+
+/// Extended statistics objects ANALYZE has not yet built, on tables the
+/// connecting role can read; empty before PostgreSQL 12, which lacks `pg_stats_ext`
+pub async fn fetch_unanalyzed_statistics(
+    pool: &Pool<Postgres>,
+    catalog: &Catalog,
+) -> Vec<ExtendedStatistics> {
+    if !catalog.has_pg_stats_ext() {
+        return Vec::new();
+    }
+
+    match sqlx::query(UNANALYZED_STATISTICS_QUERY)
+        .fetch_all(pool)
+        .await
+    {
+        Ok(rows) => rows
+            .into_iter()
+            .map(|row| ExtendedStatistics {
+                schema: row.get("schema"),
+                name: row.get("name"),
+                table_name: row.get("table_name"),
+            })
+            .collect(),
+        Err(err) => {
+            warn!("Failed to read extended statistics: {err}");
+            Vec::new()
+        }
+    }
+}
+
+/// Flags extended statistics that were created but never analyzed, so the
+/// planner still treats their columns as independent
+pub fn analyze_extended_statistics(
+    stats: &SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    for statistics in &stats.unanalyzed_extended_statistics {
+        let name = format!("{}.{}", statistics.schema, statistics.name);
+        let rationale = Message::new("planner.extended_statistics_not_analyzed")
+            .arg("statistics", &name)
+            .arg("table", &statistics.table_name);
+        results
+            .suggestions_by_category
+            .entry(ConfigCategory::Planner)
+            .or_default()
+            .push(ConfigSuggestion {
+                parameter: format!("statistics {name}"),
+                current_value: "never analyzed".to_string(),
+                suggested_value: format!("ANALYZE {}", statistics.table_name),
+                level: SuggestionLevel::Recommended,
+                rationale: rationale.render(Lang::En),
+                messages: vec![rationale],
+                fingerprint: String::new(),
+            });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn sampled(per_column: &[i64], combined: i64) -> SampledDistinct {
+        SampledDistinct {
+            sampled: SAMPLE_ROWS,
+            per_column: per_column.to_vec(),
+            combined,
+        }
+    }
+
+    #[rstest]
+    #[case::city_determines_country(sampled(&[50, 5000], 5020), true)]
+    #[case::independent(sampled(&[50, 5000], 25_000), false)]
+    #[case::too_few_combinations_to_tell(sampled(&[1, 40], 40), false)]
+    #[case::constant_columns(sampled(&[1, 1], 1), false)]
+    fn dependent_columns_are_detected(#[case] distinct: SampledDistinct, #[case] correlated: bool) {
+        assert_eq!(distinct.is_correlated(), correlated);
+    }
+
+    #[test]
+    fn ddl_creates_and_analyzes_the_statistics() {
+        let group = EqualityGroup {
+            schema: "public".to_string(),
+            table: "addresses".to_string(),
+            columns: vec!["city".to_string(), "country".to_string()],
+            calls: 10,
+            queryid: 1,
+            statement_calls: 10,
+            mean_time_ms: 1.0,
+            query: String::new(),
+        };
+        assert_eq!(
+            create_statistics_ddl(&group, true),
+            "CREATE STATISTICS addresses_city_country_stats (dependencies, ndistinct, mcv) \
+             ON city, country FROM public.addresses; ANALYZE public.addresses;"
+        );
+    }
+
+    #[test]
+    fn unanalyzed_statistics_are_flagged() {
+        let stats = SystemStats {
+            unanalyzed_extended_statistics: vec![ExtendedStatistics {
+                schema: "public".to_string(),
+                name: "addresses_stats".to_string(),
+                table_name: "public.addresses".to_string(),
+            }],
+            ..Default::default()
+        };
+        let mut results = AnalysisResults::default();
+        analyze_extended_statistics(&stats, &mut results).unwrap();
+        let suggestion = &results.suggestions_by_category[&ConfigCategory::Planner][0];
+        assert_eq!(suggestion.parameter, "statistics public.addresses_stats");
+        assert_eq!(suggestion.suggested_value, "ANALYZE public.addresses");
+    }
+}
@@ -0,0 +1,447 @@
+use crate::analysis::{param_value, pgvector};
+use crate::checker::CheckerError;
+use crate::fixture::Fixture;
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ConfigCategory, ConfigSuggestion, ParamValue, PgConfigParam, SuggestionLevel,
+    SystemStats,
+};
+use sqlx::{Pool, Postgres};
+use std::collections::HashMap;
+use tracing::warn;
+
+type Result<T> = std::result::Result<T, CheckerError>;
+
+pub const TIMESCALEDB: &str = "timescaledb";
+pub const CITUS: &str = "citus";
+pub const POSTGIS: &str = "postgis";
+/// pgvector's extension name
+pub const PGVECTOR: &str = "vector";
+
+/// Schemas holding TimescaleDB and Citus columnar catalogs. Their tables are
+/// maintained by the extension, so bloat and scan findings on them are noise.
+pub const EXTENSION_CATALOG_SCHEMAS: &[&str] = &[
+    "_timescaledb_cache",
+    "_timescaledb_catalog",
+    "_timescaledb_config",
+    "columnar",
+    "columnar_internal",
+];
+
+/// Worker slots TimescaleDB asks for on top of its background workers and
+/// parallel query workers: the launcher plus headroom for other extensions
+const TIMESCALEDB_EXTRA_WORKERS: usize = 3;
+/// Smallest `timescaledb.max_background_workers` worth suggesting
+const TIMESCALEDB_MIN_BACKGROUND_WORKERS: usize = 8;
+/// Shards per worker for new distributed tables when queries mostly hit one tenant
+const CITUS_OLTP_SHARDS_PER_WORKER: usize = 2;
+
+pub(crate) const INSTALLED_EXTENSIONS_QUERY: &str = "SELECT extname::text FROM pg_extension";
+pub(crate) const CITUS_WORKERS_QUERY: &str =
+    "SELECT count(*) FROM pg_dist_node WHERE noderole = 'primary' AND isactive AND groupid <> 0";
+pub(crate) const TIMESCALE_JOBS_QUERY: &str =
+    "SELECT count(*) FROM timescaledb_information.jobs WHERE scheduled";
+
+/// Names of the extensions installed in the analyzed database, empty when
+/// pg_extension cannot be read
+pub async fn fetch_installed(pool: &Pool<Postgres>) -> Vec<String> {
+    match sqlx::query_scalar::<_, String>(INSTALLED_EXTENSIONS_QUERY)
+        .fetch_all(pool)
+        .await
+    {
+        Ok(names) => names,
+        Err(err) => {
+            warn!("Failed to read installed extensions: {err}");
+            Vec::new()
+        }
+    }
+}
+
+/// Reads the installed extensions, plus the Citus worker count, the number of
+/// scheduled TimescaleDB jobs and the pgvector columns when those extensions
+/// are present
+pub async fn fetch_extension_stats(
+    pool: &Pool<Postgres>,
+    fixture: &Fixture,
+    stats: &mut SystemStats,
+) {
+    stats.extensions = fixture.serve("extensions", fetch_installed(pool)).await;
+    if stats.extensions.iter().any(|name| name == PGVECTOR) {
+        stats.vector_columns = fixture
+            .serve("vector_columns", pgvector::fetch_vector_columns(pool))
+            .await;
+    }
+    if stats.extensions.iter().any(|name| name == CITUS) {
+        // Group 0 is the coordinator
+        stats.citus_workers = fixture
+            .serve("citus_workers", fetch_count(pool, CITUS_WORKERS_QUERY))
+            .await;
+    }
+    if stats.extensions.iter().any(|name| name == TIMESCALEDB) {
+        stats.timescale_jobs = fixture
+            .serve("timescale_jobs", fetch_count(pool, TIMESCALE_JOBS_QUERY))
+            .await;
+    }
+}
+
+async fn fetch_count(pool: &Pool<Postgres>, query: &str) -> Option<usize> {
+    match sqlx::query_scalar::<_, i64>(query).fetch_one(pool).await {
+        Ok(count) => usize::try_from(count).ok(),
+        Err(err) => {
+            warn!("Failed to run {query}: {err}");
+            None
+        }
+    }
+}
+
+/// Whether `name` is installed, or preloaded for settings dumps that have no
+/// pg_extension to read
+pub fn has_extension(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    name: &str,
+) -> bool {
+    stats.extensions.iter().any(|installed| installed == name)
+        || params.get("shared_preload_libraries").is_some_and(|param| {
+            param
+                .current_value
+                .split(',')
+                .any(|library| library.trim().trim_matches('"') == name)
+        })
+}
+
+/// Checks the settings TimescaleDB, Citus and pgvector add or depend on
+pub fn analyze_extensions(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    if has_extension(params, stats, TIMESCALEDB) {
+        analyze_timescaledb_workers(params, stats, results)?;
+    }
+    if has_extension(params, stats, CITUS) {
+        analyze_citus_shard_count(params, stats, results)?;
+        analyze_citus_executor_pool_size(params, stats, results)?;
+    }
+    if has_extension(params, stats, PGVECTOR) {
+        pgvector::analyze_pgvector(params, stats, results)?;
+    }
+
+    Ok(())
+}
+
+fn analyze_timescaledb_workers(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    let Some(background_workers) =
+        param_value(params, "timescaledb.max_background_workers").and_then(ParamValue::as_count)
+    else {
+        return Ok(());
+    };
+
+    // One scheduler per database plus a worker for each job that may run at once
+    let mut needed_background_workers = background_workers;
+    if let Some(jobs) = stats.timescale_jobs {
+        let recommended = (jobs + 1).max(TIMESCALEDB_MIN_BACKGROUND_WORKERS);
+        if background_workers < jobs + 1 {
+            needed_background_workers = recommended;
+            add_suggestion(
+                results,
+                "timescaledb.max_background_workers",
+                &background_workers.to_string(),
+                &recommended.to_string(),
+                SuggestionLevel::Important,
+                Message::new("extensions.timescaledb_background_workers")
+                    .arg("current", background_workers)
+                    .arg("jobs", jobs)
+                    .arg("recommended", recommended),
+            );
+        }
+    }
+
+    let parallel_workers = param_value(params, "max_parallel_workers")
+        .and_then(ParamValue::as_count)
+        .unwrap_or(8);
+    let required = needed_background_workers + parallel_workers + TIMESCALEDB_EXTRA_WORKERS;
+
+    // The vCPU-based concurrency advice would starve TimescaleDB's jobs
+    if let Some(suggestions) = results
+        .suggestions_by_category
+        .get_mut(&ConfigCategory::Concurrency)
+    {
+        suggestions.retain(|suggestion| {
+            suggestion.parameter != "max_worker_processes"
+                || suggestion
+                    .suggested_value
+                    .parse::<usize>()
+                    .is_ok_and(|value| value >= required)
+        });
+    }
+
+    let Some(current) = param_value(params, "max_worker_processes").and_then(ParamValue::as_count)
+    else {
+        return Ok(());
+    };
+    if current < required {
+        add_suggestion(
+            results,
+            "max_worker_processes",
+            &current.to_string(),
+            &required.to_string(),
+            SuggestionLevel::Important,
+            Message::new("extensions.timescaledb_worker_processes")
+                .arg("background_workers", needed_background_workers)
+                .arg("parallel_workers", parallel_workers)
+                .arg("recommended", required),
+        );
+    }
+
+    Ok(())
+}
+
+fn analyze_citus_shard_count(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    let (Some(shard_count), Some(workers)) = (
+        param_value(params, "citus.shard_count").and_then(ParamValue::as_count),
+        stats.citus_workers.filter(|workers| *workers > 0),
+    ) else {
+        return Ok(());
+    };
+
+    // Analytical queries fan out to every shard, so aim for a shard per worker
+    // core, assuming workers are sized like this node
+    let per_worker = match stats.profile().workload_type() {
+        crate::config::WorkloadType::Oltp => CITUS_OLTP_SHARDS_PER_WORKER,
+        crate::config::WorkloadType::Olap => stats
+            .cpu_count
+            .unwrap_or(CITUS_OLTP_SHARDS_PER_WORKER)
+            .max(CITUS_OLTP_SHARDS_PER_WORKER),
+    };
+    let recommended = workers * per_worker;
+    if shard_count < recommended {
+        add_suggestion(
+            results,
+            "citus.shard_count",
+            &shard_count.to_string(),
+            &recommended.to_string(),
+            if shard_count < workers {
+                SuggestionLevel::Important
+            } else {
+                SuggestionLevel::Recommended
+            },
+            Message::new("extensions.citus_shard_count")
+                .arg("current", shard_count)
+                .arg("workers", workers)
+                .arg("per_worker", per_worker)
+                .arg("recommended", recommended),
+        );
+    }
+
+    Ok(())
+}
+
+fn analyze_citus_executor_pool_size(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    let (Some(pool_size), Some(max_connections), Some(peak)) = (
+        param_value(params, "citus.max_adaptive_executor_pool_size").and_then(ParamValue::as_count),
+        param_value(params, "max_connections").and_then(ParamValue::as_count),
+        stats
+            .peak_connection_count
+            .or(stats.connection_count)
+            .filter(|peak| *peak > 0),
+    ) else {
+        return Ok(());
+    };
+
+    // Each multi-shard query may open up to pool_size connections per worker
+    let total = pool_size * peak;
+    if total > max_connections {
+        let recommended = (max_connections / peak).max(1);
+        add_suggestion(
+            results,
+            "citus.max_adaptive_executor_pool_size",
+            &pool_size.to_string(),
+            &recommended.to_string(),
+            SuggestionLevel::Recommended,
+            Message::new("extensions.citus_executor_pool_size")
+                .arg("current", pool_size)
+                .arg("peak", peak)
+                .arg("total", total)
+                .arg("max_connections", max_connections)
+                .arg("recommended", recommended),
+        );
+    }
+
+    Ok(())
+}
+
+pub(super) fn add_suggestion<'a>(
+    results: &'a mut AnalysisResults,
+    parameter: &str,
+    current_value: &str,
+    suggested_value: &str,
+    level: SuggestionLevel,
+    rationale: Message,
+) -> &'a mut ConfigSuggestion {
+    let suggestion = ConfigSuggestion {
+        parameter: parameter.to_string(),
+        current_value: current_value.to_string(),
+        suggested_value: suggested_value.to_string(),
+        level,
+        rationale: rationale.render(Lang::En),
+        messages: vec![rationale],
+        fingerprint: String::new(),
+    };
+
+    let suggestions = results
+        .suggestions_by_category
+        .entry(ConfigCategory::Extensions)
+        .or_default();
+    suggestions.push(suggestion);
+    suggestions.last_mut().expect("just pushed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn param(name: &str, value: &str) -> (String, PgConfigParam) {
+        (
+            name.to_string(),
+            PgConfigParam {
+                name: name.to_string(),
+                current_value: value.to_string(),
+                default_value: None,
+                unit: None,
+                context: "postmaster".to_string(),
+                value: ParamValue::parse(value, "integer", None),
+                source: None,
+                sourcefile: None,
+                pending_restart: false,
+                min_value: None,
+                max_value: None,
+            },
+        )
+    }
+
+    fn suggested(results: &AnalysisResults, parameter: &str) -> Option<String> {
+        results
+            .suggestions_by_category
+            .values()
+            .flatten()
+            .find(|suggestion| suggestion.parameter == parameter)
+            .map(|suggestion| suggestion.suggested_value.clone())
+    }
+
+    #[test]
+    fn preloaded_libraries_count_as_installed() {
+        let params: HashMap<_, _> = [param(
+            "shared_preload_libraries",
+            "pg_stat_statements, \"timescaledb\"",
+        )]
+        .into_iter()
+        .collect();
+        let stats = SystemStats::default();
+        assert!(has_extension(&params, &stats, TIMESCALEDB));
+        assert!(!has_extension(&params, &stats, CITUS));
+    }
+
+    #[test]
+    fn timescaledb_workers_cover_jobs_and_parallel_queries() {
+        let params: HashMap<_, _> = [
+            param("shared_preload_libraries", "timescaledb"),
+            param("timescaledb.max_background_workers", "8"),
+            param("max_parallel_workers", "8"),
+            param("max_worker_processes", "8"),
+        ]
+        .into_iter()
+        .collect();
+        let stats = SystemStats {
+            timescale_jobs: Some(12),
+            ..Default::default()
+        };
+        let mut results = AnalysisResults::default();
+        // vCPU-based advice that would leave no slots for the jobs
+        results.suggestions_by_category.insert(
+            ConfigCategory::Concurrency,
+            vec![ConfigSuggestion {
+                parameter: "max_worker_processes".to_string(),
+                current_value: "8".to_string(),
+                suggested_value: "8".to_string(),
+                level: SuggestionLevel::Recommended,
+                rationale: String::new(),
+                messages: Vec::new(),
+                fingerprint: String::new(),
+            }],
+        );
+
+        analyze_extensions(&params, &stats, &mut results).unwrap();
+
+        assert_eq!(
+            suggested(&results, "timescaledb.max_background_workers").as_deref(),
+            Some("13")
+        );
+        assert_eq!(
+            suggested(&results, "max_worker_processes").as_deref(),
+            Some("24")
+        );
+        assert!(results.suggestions_by_category[&ConfigCategory::Concurrency].is_empty());
+    }
+
+    #[rstest]
+    #[case(crate::config::WorkloadType::Oltp, "32", None)]
+    #[case(crate::config::WorkloadType::Oltp, "4", Some("8"))]
+    #[case(crate::config::WorkloadType::Olap, "32", Some("64"))]
+    fn citus_shard_count_scales_with_workers(
+        #[case] workload_type: crate::config::WorkloadType,
+        #[case] shard_count: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let params: HashMap<_, _> = [param("citus.shard_count", shard_count)]
+            .into_iter()
+            .collect();
+        let stats = SystemStats {
+            extensions: vec![CITUS.to_string()],
+            citus_workers: Some(4),
+            cpu_count: Some(16),
+            workload_type,
+            ..Default::default()
+        };
+        let mut results = AnalysisResults::default();
+        analyze_extensions(&params, &stats, &mut results).unwrap();
+        assert_eq!(
+            suggested(&results, "citus.shard_count").as_deref(),
+            expected
+        );
+    }
+
+    #[test]
+    fn citus_pool_size_keeps_fan_out_within_max_connections() {
+        let params: HashMap<_, _> = [
+            param("citus.max_adaptive_executor_pool_size", "16"),
+            param("max_connections", "200"),
+        ]
+        .into_iter()
+        .collect();
+        let stats = SystemStats {
+            extensions: vec![CITUS.to_string()],
+            peak_connection_count: Some(50),
+            ..Default::default()
+        };
+        let mut results = AnalysisResults::default();
+        analyze_extensions(&params, &stats, &mut results).unwrap();
+        assert_eq!(
+            suggested(&results, "citus.max_adaptive_executor_pool_size").as_deref(),
+            Some("4")
+        );
+    }
+}
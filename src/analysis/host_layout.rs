@@ -0,0 +1,365 @@
+use crate::checker::CheckerError;
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ConfigCategory, ConfigSuggestion, PgConfigParam, SuggestionLevel,
+};
+use sqlx::{Pool, Postgres, Row};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PROC_MOUNTS: &str = "/proc/self/mounts";
+
+/// Filesystems whose contents vanish with the machine or container, or that
+/// add a network round trip to every fsync
+const UNSUITABLE_FILESYSTEMS: &[&str] = &["tmpfs", "ramfs", "overlay", "nfs", "nfs4", "cifs"];
+
+/// A line of `/proc/self/mounts`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mount {
+    pub mount_point: PathBuf,
+    pub fs_type: String,
+    pub options: Vec<String>,
+}
+
+/// A directory PostgreSQL writes to, with the mount it lives on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placement {
+    pub path: PathBuf,
+    pub mount: Option<Mount>,
+    /// Whether it shares a device with `data_directory`; `None` when the
+    /// directory could not be inspected, typically for lack of permission
+    pub on_data_volume: Option<bool>,
+}
+
+/// Where the cluster's files live on the host running PostGreat, gathered
+/// by [`HostLayout::inspect`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostLayout {
+    pub data: Placement,
+    pub wal: Placement,
+    /// Tablespaces named in `temp_tablespaces`, by name
+    pub temp_tablespaces: Vec<(String, Placement)>,
+}
+
+pub(crate) const TABLESPACES_QUERY: &str = r#"
+    SELECT spcname::text AS name, pg_tablespace_location(oid) AS location
+    FROM pg_tablespace
+"#;
+
+/// Reads the name and directory of every tablespace; the built-in ones have an
+/// empty location because they live in `data_directory`
+pub async fn fetch_tablespaces(
+    pool: &Pool<Postgres>,
+) -> Result<Vec<(String, String)>, CheckerError> {
+    let rows = sqlx::query(TABLESPACES_QUERY)
+        .fetch_all(pool)
+        .await
+        .map_err(|source| CheckerError::QueryError {
+            query: TABLESPACES_QUERY.into(),
+            source,
+        })?;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            (
+                row.get("name"),
+                row.get::<Option<String>, _>("location").unwrap_or_default(),
+            )
+        })
+        .collect())
+}
+
+impl HostLayout {
+    /// Inspects `data_directory`, `pg_wal` and the temp tablespaces on the local
+    /// filesystem. Fails when `data_directory` is unknown or not on this host.
+    pub fn inspect(
+        params: &HashMap<String, PgConfigParam>,
+        tablespaces: &[(String, String)],
+    ) -> Result<Self, String> {
+        let data_directory = params
+            .get("data_directory")
+            .map(|param| PathBuf::from(&param.current_value))
+            .ok_or_else(|| {
+                "data_directory is not readable; grant pg_read_all_settings".to_string()
+            })?;
+        if !data_directory.exists() {
+            return Err(format!(
+                "data_directory {} does not exist on this host",
+                data_directory.display()
+            ));
+        }
+
+        let mounts = fs::read_to_string(PROC_MOUNTS)
+            .map(|contents| parse_mounts(&contents))
+            .unwrap_or_default();
+        let place = |path: PathBuf| {
+            let resolved = path.canonicalize().ok();
+            Placement {
+                mount: resolved
+                    .as_deref()
+                    .and_then(|resolved| mount_for(&mounts, resolved))
+                    .cloned(),
+                on_data_volume: resolved.and_then(|_| same_device(&path, &data_directory)),
+                path,
+            }
+        };
+
+        let temp_names = params
+            .get("temp_tablespaces")
+            .map(|param| split_list(&param.current_value))
+            .unwrap_or_default();
+        let temp_tablespaces = temp_names
+            .into_iter()
+            .filter_map(|name| {
+                let (_, location) = tablespaces.iter().find(|(spcname, _)| *spcname == name)?;
+                let path = if location.is_empty() {
+                    data_directory.clone()
+                } else {
+                    PathBuf::from(location)
+                };
+                Some((name, place(path)))
+            })
+            .collect();
+
+        Ok(Self {
+            data: place(data_directory.clone()),
+            wal: place(data_directory.join("pg_wal")),
+            temp_tablespaces,
+        })
+    }
+}
+
+/// Flags WAL and temp tablespaces sharing the data volume, and unsuitable
+/// filesystems and atime updates on the mounts PostgreSQL writes to
+pub fn analyze_host_layout(layout: &HostLayout, results: &mut AnalysisResults) {
+    if layout.wal.on_data_volume == Some(true) {
+        add_suggestion(
+            results,
+            "directory pg_wal",
+            &layout.data.path.display().to_string(),
+            "separate volume",
+            SuggestionLevel::Recommended,
+            Message::new("host.wal_volume").arg("path", layout.wal.path.display()),
+        );
+    }
+
+    for (name, placement) in &layout.temp_tablespaces {
+        if placement.on_data_volume == Some(true) {
+            add_suggestion(
+                results,
+                &format!("tablespace {name}"),
+                &placement.path.display().to_string(),
+                "separate volume",
+                SuggestionLevel::Info,
+                Message::new("host.temp_tablespace_volume").arg("name", name),
+            );
+        }
+    }
+
+    let mut seen = Vec::new();
+    let placements = [&layout.data, &layout.wal].into_iter().chain(
+        layout
+            .temp_tablespaces
+            .iter()
+            .map(|(_, placement)| placement),
+    );
+    for mount in placements.filter_map(|placement| placement.mount.as_ref()) {
+        if seen.contains(&&mount.mount_point) {
+            continue;
+        }
+        seen.push(&mount.mount_point);
+        let mount_point = mount.mount_point.display().to_string();
+
+        if UNSUITABLE_FILESYSTEMS.contains(&mount.fs_type.as_str()) {
+            add_suggestion(
+                results,
+                &format!("mount {mount_point} filesystem"),
+                &mount.fs_type,
+                "xfs or ext4",
+                SuggestionLevel::Important,
+                Message::new("host.filesystem")
+                    .arg("mount", &mount_point)
+                    .arg("fs", &mount.fs_type),
+            );
+        }
+        if !mount.options.iter().any(|option| option == "noatime") {
+            add_suggestion(
+                results,
+                &format!("mount {mount_point} options"),
+                &mount.options.join(","),
+                "noatime",
+                SuggestionLevel::Recommended,
+                Message::new("host.mount_options").arg("mount", &mount_point),
+            );
+        }
+    }
+}
+
+fn add_suggestion(
+    results: &mut AnalysisResults,
+    parameter: &str,
+    current_value: &str,
+    suggested_value: &str,
+    level: SuggestionLevel,
+    rationale: Message,
+) {
+    results
+        .suggestions_by_category
+        .entry(ConfigCategory::Host)
+        .or_default()
+        .push(ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: current_value.to_string(),
+            suggested_value: suggested_value.to_string(),
+            level,
+            rationale: rationale.render(Lang::En),
+            messages: vec![rationale],
+            fingerprint: String::new(),
+        });
+}
+
+/// Parses `/proc/self/mounts`, which escapes spaces and tabs in mount points
+/// as octal
+fn parse_mounts(contents: &str) -> Vec<Mount> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _device = fields.next()?;
+            let mount_point = fields.next()?;
+            let fs_type = fields.next()?;
+            let options = fields.next()?;
+            Some(Mount {
+                mount_point: PathBuf::from(
+                    mount_point
+                        .replace("\\040", " ")
+                        .replace("\\011", "\t")
+                        .replace("\\134", "\\"),
+                ),
+                fs_type: fs_type.to_string(),
+                options: options.split(',').map(str::to_string).collect(),
+            })
+        })
+        .collect()
+}
+
+/// The mount holding `path`: the longest matching mount point, the last one
+/// mounted when several share it
+fn mount_for<'a>(mounts: &'a [Mount], path: &Path) -> Option<&'a Mount> {
+    mounts
+        .iter()
+        .rev()
+        .filter(|mount| path.starts_with(&mount.mount_point))
+        .max_by_key(|mount| mount.mount_point.components().count())
+}
+
+#[cfg(unix)]
+fn same_device(left: &Path, right: &Path) -> Option<bool> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some(fs::metadata(left).ok()?.dev() == fs::metadata(right).ok()?.dev())
+}
+
+#[cfg(not(unix))]
+fn same_device(_left: &Path, _right: &Path) -> Option<bool> {
+    None
+}
+
+/// `temp_tablespaces` as reported by pg_settings, e.g. `"fast temp", scratch`
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|name| name.trim().trim_matches('"').to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const MOUNTS: &str = "\
+/dev/nvme0n1p1 / ext4 rw,relatime 0 0
+tmpfs /tmp tmpfs rw,nosuid,nodev 0 0
+/dev/nvme1n1 /var/lib/postgresql xfs rw,noatime,attr2 0 0
+/dev/nvme2n1 /mnt/pg\\040temp xfs rw,relatime 0 0
+";
+
+    fn placement(path: &str, on_data_volume: Option<bool>) -> Placement {
+        let mounts = parse_mounts(MOUNTS);
+        Placement {
+            path: PathBuf::from(path),
+            mount: mount_for(&mounts, Path::new(path)).cloned(),
+            on_data_volume,
+        }
+    }
+
+    fn parameters(layout: &HostLayout) -> Vec<String> {
+        let mut results = AnalysisResults::default();
+        analyze_host_layout(layout, &mut results);
+        results
+            .suggestions_by_category
+            .get(&ConfigCategory::Host)
+            .into_iter()
+            .flatten()
+            .map(|s| s.parameter.clone())
+            .collect()
+    }
+
+    #[rstest]
+    #[case("/var/lib/postgresql/17/main", "/var/lib/postgresql")]
+    #[case("/mnt/pg temp/ts", "/mnt/pg temp")]
+    #[case("/tmp/pgdata", "/tmp")]
+    #[case("/srv/pgdata", "/")]
+    fn paths_resolve_to_their_longest_mount_point(#[case] path: &str, #[case] expected: &str) {
+        let mounts = parse_mounts(MOUNTS);
+        let mount = mount_for(&mounts, Path::new(path)).unwrap();
+        assert_eq!(mount.mount_point, PathBuf::from(expected));
+    }
+
+    #[test]
+    fn well_laid_out_host_has_no_findings() {
+        let layout = HostLayout {
+            data: placement("/var/lib/postgresql/17/main", Some(true)),
+            wal: placement("/var/lib/postgresql/17/main/pg_wal", Some(false)),
+            temp_tablespaces: Vec::new(),
+        };
+        assert!(parameters(&layout).is_empty());
+    }
+
+    #[test]
+    fn shared_volumes_and_host_settings_are_flagged() {
+        let layout = HostLayout {
+            data: placement("/tmp/pgdata", Some(true)),
+            wal: placement("/tmp/pgdata/pg_wal", Some(true)),
+            temp_tablespaces: vec![
+                ("scratch".to_string(), placement("/tmp/pgdata", Some(true))),
+                (
+                    "fast".to_string(),
+                    placement("/mnt/pg temp/ts", Some(false)),
+                ),
+            ],
+        };
+        assert_eq!(
+            parameters(&layout),
+            [
+                "directory pg_wal",
+                "tablespace scratch",
+                "mount /tmp filesystem",
+                "mount /tmp options",
+                "mount /mnt/pg temp options",
+            ]
+        );
+    }
+
+    #[test]
+    fn temp_tablespace_names_are_unquoted() {
+        assert_eq!(
+            split_list("\"fast temp\", scratch"),
+            ["fast temp", "scratch"]
+        );
+        assert!(split_list("").is_empty());
+    }
+}
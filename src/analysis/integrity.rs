@@ -0,0 +1,241 @@
+use crate::analysis::extensions::EXTENSION_CATALOG_SCHEMAS;
+use crate::analysis::schema_lint::USER_TABLES;
+use crate::checker::CheckerError;
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ConfigCategory, ConfigSuggestion, DisabledTrigger, SuggestionLevel,
+    SystemStats, UnvalidatedConstraint,
+};
+use sqlx::{Pool, Postgres, Row};
+use tracing::warn;
+
+type Result<T> = std::result::Result<T, CheckerError>;
+
+pub(crate) fn unvalidated_constraints_query() -> String {
+    format!(
+        r#"
+        SELECT
+            n.nspname::text AS schema,
+            c.relname::text AS table_name,
+            k.conname::text AS name,
+            CASE k.contype WHEN 'f' THEN 'FOREIGN KEY' ELSE 'CHECK' END AS kind
+        FROM pg_constraint k
+        JOIN pg_class c ON c.oid = k.conrelid
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE {USER_TABLES}
+          AND k.contype IN ('f', 'c')
+          AND NOT k.convalidated
+        ORDER BY 1, 2, 3
+    "#
+    )
+}
+
+/// Foreign keys and check constraints on user tables that were added
+/// NOT VALID and never validated
+pub async fn fetch_unvalidated_constraints(pool: &Pool<Postgres>) -> Vec<UnvalidatedConstraint> {
+    let query = unvalidated_constraints_query();
+
+    match sqlx::query(&query)
+        .bind(EXTENSION_CATALOG_SCHEMAS)
+        .fetch_all(pool)
+        .await
+    {
+        Ok(rows) => rows
+            .into_iter()
+            .map(|row| UnvalidatedConstraint {
+                schema: row.get("schema"),
+                table_name: row.get("table_name"),
+                name: row.get("name"),
+                kind: row.get("kind"),
+            })
+            .collect(),
+        Err(err) => {
+            warn!("Failed to read unvalidated constraints: {err}");
+            Vec::new()
+        }
+    }
+}
+
+pub(crate) fn disabled_triggers_query() -> String {
+    format!(
+        r#"
+        SELECT DISTINCT
+            n.nspname::text AS schema,
+            c.relname::text AS table_name,
+            coalesce(k.conname, t.tgname)::text AS name,
+            k.oid IS NOT NULL AS foreign_key
+        FROM pg_trigger t
+        LEFT JOIN pg_constraint k
+               ON t.tgisinternal AND k.oid = t.tgconstraint AND k.contype = 'f'
+        JOIN pg_class c ON c.oid = coalesce(k.conrelid, t.tgrelid)
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE t.tgenabled = 'D'
+          AND {USER_TABLES}
+        ORDER BY 1, 2, 3
+    "#
+    )
+}
+
+/// Disabled triggers on user tables; the internal triggers enforcing a
+/// foreign key are reported once, under the key's name and table
+pub async fn fetch_disabled_triggers(pool: &Pool<Postgres>) -> Vec<DisabledTrigger> {
+    let query = disabled_triggers_query();
+
+    match sqlx::query(&query)
+        .bind(EXTENSION_CATALOG_SCHEMAS)
+        .fetch_all(pool)
+        .await
+    {
+        Ok(rows) => rows
+            .into_iter()
+            .map(|row| DisabledTrigger {
+                schema: row.get("schema"),
+                table_name: row.get("table_name"),
+                name: row.get("name"),
+                foreign_key: row.get("foreign_key"),
+            })
+            .collect(),
+        Err(err) => {
+            warn!("Failed to read disabled triggers: {err}");
+            Vec::new()
+        }
+    }
+}
+
+/// Flags constraints the database and planner cannot rely on: NOT VALID
+/// constraints existing rows were never checked against, and disabled
+/// triggers, including those enforcing foreign keys
+pub fn analyze_integrity(stats: &SystemStats, results: &mut AnalysisResults) -> Result<()> {
+    for constraint in &stats.unvalidated_constraints {
+        let table = format!("{}.{}", constraint.schema, constraint.table_name);
+        add_suggestion(
+            results,
+            &format!("constraint {} on {table}", constraint.name),
+            "NOT VALID",
+            "VALIDATE CONSTRAINT",
+            SuggestionLevel::Info,
+            Message::new("schema.not_valid_constraint")
+                .arg("kind", &constraint.kind)
+                .arg("constraint", &constraint.name)
+                .arg("table", &table),
+        );
+    }
+
+    for trigger in &stats.disabled_triggers {
+        let table = format!("{}.{}", trigger.schema, trigger.table_name);
+        if trigger.foreign_key {
+            add_suggestion(
+                results,
+                &format!("foreign key {} on {table}", trigger.name),
+                "triggers disabled",
+                "ENABLE TRIGGER ALL",
+                SuggestionLevel::Important,
+                Message::new("schema.disabled_fk_trigger")
+                    .arg("constraint", &trigger.name)
+                    .arg("table", &table),
+            );
+        } else {
+            add_suggestion(
+                results,
+                &format!("trigger {} on {table}", trigger.name),
+                "disabled",
+                "ENABLE TRIGGER",
+                SuggestionLevel::Important,
+                Message::new("schema.disabled_trigger")
+                    .arg("trigger", &trigger.name)
+                    .arg("table", &table),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn add_suggestion(
+    results: &mut AnalysisResults,
+    parameter: &str,
+    current_value: &str,
+    suggested_value: &str,
+    level: SuggestionLevel,
+    rationale: Message,
+) {
+    results
+        .suggestions_by_category
+        .entry(ConfigCategory::Schema)
+        .or_default()
+        .push(ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: current_value.to_string(),
+            suggested_value: suggested_value.to_string(),
+            level,
+            rationale: rationale.render(Lang::En),
+            messages: vec![rationale],
+            fingerprint: String::new(),
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trigger(name: &str, foreign_key: bool) -> DisabledTrigger {
+        DisabledTrigger {
+            schema: "app".to_string(),
+            table_name: "orders".to_string(),
+            name: name.to_string(),
+            foreign_key,
+        }
+    }
+
+    #[test]
+    fn unvalidated_constraints_and_disabled_triggers_are_reported() {
+        let stats = SystemStats {
+            unvalidated_constraints: vec![UnvalidatedConstraint {
+                schema: "app".to_string(),
+                table_name: "orders".to_string(),
+                name: "orders_customer_fk".to_string(),
+                kind: "FOREIGN KEY".to_string(),
+            }],
+            disabled_triggers: vec![
+                trigger("orders_audit", false),
+                trigger("orders_customer_fk", true),
+            ],
+            ..Default::default()
+        };
+        let mut results = AnalysisResults::default();
+        analyze_integrity(&stats, &mut results).unwrap();
+
+        let found: Vec<(&str, &str, SuggestionLevel)> = results.suggestions_by_category
+            [&ConfigCategory::Schema]
+            .iter()
+            .map(|s| (s.parameter.as_str(), s.suggested_value.as_str(), s.level))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    "constraint orders_customer_fk on app.orders",
+                    "VALIDATE CONSTRAINT",
+                    SuggestionLevel::Info
+                ),
+                (
+                    "trigger orders_audit on app.orders",
+                    "ENABLE TRIGGER",
+                    SuggestionLevel::Important
+                ),
+                (
+                    "foreign key orders_customer_fk on app.orders",
+                    "ENABLE TRIGGER ALL",
+                    SuggestionLevel::Important
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn nothing_is_reported_for_a_sound_schema() {
+        let mut results = AnalysisResults::default();
+        analyze_integrity(&SystemStats::default(), &mut results).unwrap();
+        assert!(results.suggestions_by_category.is_empty());
+    }
+}
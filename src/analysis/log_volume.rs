@@ -0,0 +1,700 @@
+use crate::analysis::pgaudit::{audit_class, AUDIT_CLASSES};
+use crate::checker::CheckerError;
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ByteSize, ConfigCategory, ConfigSuggestion, PgConfigParam, SlowStatementRate,
+    StatementClassRate, StatementRates, SuggestionLevel, SystemStats,
+};
+use sqlx::{Pool, Postgres, Row};
+use std::collections::HashMap;
+use tracing::debug;
+
+type Result<T> = std::result::Result<T, CheckerError>;
+
+/// Durations at which slow-statement rates are kept; other thresholds round
+/// down to the nearest one, overestimating rather than underestimating
+pub const DURATION_THRESHOLDS_MS: &[u64] = &[
+    0, 10, 50, 100, 250, 500, 1000, 2000, 5000, 10_000, 30_000, 60_000,
+];
+/// Log volume per hour a typical log pipeline absorbs without trouble
+pub const LOG_VOLUME_BUDGET_BYTES: f64 = 1024.0 * 1024.0 * 1024.0;
+/// Beyond this multiple of the budget the volume is critical
+const LOG_VOLUME_CRITICAL_FACTOR: f64 = 10.0;
+/// Timestamp, PID, user@database and severity of the default `log_line_prefix`
+const LOG_LINE_PREFIX_BYTES: f64 = 80.0;
+/// `duration: 1234.567 ms  statement: `
+const DURATION_LINE_BYTES: f64 = 40.0;
+/// `connection received: host=... port=...` and the authentication and
+/// authorization lines after it
+const CONNECTION_LINE_BYTES: f64 = 70.0;
+/// `disconnection: session time: ... user=... database=... host=...`
+const DISCONNECTION_LINE_BYTES: f64 = 110.0;
+/// What the logging checks recommend for log_min_duration_statement
+const RECOMMENDED_MIN_DURATION_MS: u64 = 1000;
+/// log_min_duration_statement values above this get the recommendation
+const MAX_MIN_DURATION_MS: u64 = 5000;
+const MIN_SAMPLE_RATE: f64 = 0.01;
+const SECONDS_PER_HOUR: f64 = 3600.0;
+
+pub(crate) const STATEMENTS_QUERY: &str = r#"
+    SELECT query, calls, mean_exec_time, stddev_exec_time, max_exec_time
+    FROM pg_stat_statements
+    WHERE calls > 0
+"#;
+
+pub(crate) const STATEMENTS_WINDOW_QUERY: &str = "SELECT GREATEST(EXTRACT(EPOCH FROM now() - stats_reset), 0)::bigint FROM pg_stat_statements_info";
+pub(crate) const SESSIONS_QUERY: &str = "SELECT sum(sessions)::float8 FROM pg_stat_database";
+
+/// Calls and timing of one pg_stat_statements entry
+#[derive(Debug, Clone, Default)]
+pub struct StatementTiming {
+    pub query: String,
+    pub calls: i64,
+    pub mean_ms: f64,
+    pub stddev_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Reads statement rates from pg_stat_statements (PG13+) and session churn
+/// from pg_stat_database (PG14+). The statement rates cover the
+/// pg_stat_statements window (PG14+), or `fallback_window_secs` on older
+/// servers, which also covers the session count.
+pub async fn fetch_statement_rates(
+    pool: &Pool<Postgres>,
+    fallback_window_secs: Option<u64>,
+) -> Option<StatementRates> {
+    let rows = match sqlx::query(STATEMENTS_QUERY).fetch_all(pool).await {
+        Ok(rows) => rows,
+        Err(err) => {
+            debug!("Statement rates not read; pg_stat_statements is not readable: {err}");
+            return None;
+        }
+    };
+    let window_secs = match sqlx::query_scalar::<_, i64>(STATEMENTS_WINDOW_QUERY)
+        .fetch_one(pool)
+        .await
+    {
+        Ok(secs) => Some(secs as u64),
+        Err(_) => fallback_window_secs,
+    }?;
+    let sessions = sqlx::query_scalar::<_, Option<f64>>(SESSIONS_QUERY)
+        .fetch_one(pool)
+        .await
+        .ok()
+        .flatten();
+
+    let statements: Vec<StatementTiming> = rows
+        .into_iter()
+        .map(|row| StatementTiming {
+            query: row.get("query"),
+            calls: row.get("calls"),
+            mean_ms: row.get("mean_exec_time"),
+            stddev_ms: row.get("stddev_exec_time"),
+            max_ms: row.get("max_exec_time"),
+        })
+        .collect();
+    let sessions_per_second = sessions
+        .zip(fallback_window_secs)
+        .map(|(sessions, window)| sessions / window.max(1) as f64);
+    Some(statement_rates(
+        &statements,
+        window_secs,
+        sessions_per_second,
+    ))
+}
+
+/// Calls per second and mean statement length per pgaudit class and above
+/// each of [`DURATION_THRESHOLDS_MS`]
+pub fn statement_rates(
+    statements: &[StatementTiming],
+    window_secs: u64,
+    sessions_per_second: Option<f64>,
+) -> StatementRates {
+    let window = window_secs.max(1) as f64;
+    let mut classes: HashMap<&'static str, (f64, f64)> = HashMap::new();
+    let mut slow = vec![(0.0, 0.0); DURATION_THRESHOLDS_MS.len()];
+    for statement in statements {
+        let calls = statement.calls as f64;
+        let bytes = statement.query.len() as f64;
+        let (class_calls, class_bytes) = classes.entry(audit_class(&statement.query)).or_default();
+        *class_calls += calls;
+        *class_bytes += calls * bytes;
+        for (threshold, (slow_calls, slow_bytes)) in DURATION_THRESHOLDS_MS.iter().zip(&mut slow) {
+            let over = calls * share_slower_than(statement, *threshold as f64);
+            *slow_calls += over;
+            *slow_bytes += over * bytes;
+        }
+    }
+
+    StatementRates {
+        window_secs,
+        classes: AUDIT_CLASSES
+            .iter()
+            .filter_map(|class| {
+                let (calls, bytes) = classes.get(class)?;
+                Some(StatementClassRate {
+                    class: class.to_string(),
+                    per_second: calls / window,
+                    mean_statement_bytes: bytes / calls,
+                })
+            })
+            .collect(),
+        slower_than: DURATION_THRESHOLDS_MS
+            .iter()
+            .zip(slow)
+            .map(|(threshold_ms, (calls, bytes))| SlowStatementRate {
+                threshold_ms: *threshold_ms,
+                per_second: calls / window,
+                mean_statement_bytes: if calls > 0.0 { bytes / calls } else { 0.0 },
+            })
+            .collect(),
+        sessions_per_second,
+    }
+}
+
+/// Share of a statement's calls that ran longer than `threshold_ms`, assuming
+/// normally distributed run times; at least one call when the maximum did, and
+/// every call at a threshold of zero
+fn share_slower_than(statement: &StatementTiming, threshold_ms: f64) -> f64 {
+    if statement.max_ms < threshold_ms || statement.calls <= 0 {
+        return 0.0;
+    }
+    if threshold_ms <= 0.0 {
+        return 1.0;
+    }
+    let share = if statement.stddev_ms > 0.0 {
+        let z = (threshold_ms - statement.mean_ms) / statement.stddev_ms;
+        0.5 * erfc(z / std::f64::consts::SQRT_2)
+    } else if statement.mean_ms >= threshold_ms {
+        1.0
+    } else {
+        0.0
+    };
+    share.max(1.0 / statement.calls as f64)
+}
+
+/// Complementary error function, Abramowitz and Stegun 7.1.26
+fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erfc = poly * (-x * x).exp();
+    if x >= 0.0 {
+        erfc
+    } else {
+        2.0 - erfc
+    }
+}
+
+/// The settings that decide how many lines PostgreSQL logs per statement and
+/// session
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogSettings {
+    /// `None` when log_min_duration_statement is -1
+    pub min_duration_ms: Option<u64>,
+    /// log_min_duration_sample (PG13+)
+    pub sample_min_duration_ms: Option<u64>,
+    /// log_statement_sample_rate (PG13+)
+    pub sample_rate: f64,
+    /// pgaudit classes `log_statement` covers
+    pub statement_classes: Vec<&'static str>,
+    pub log_duration: bool,
+    pub connection_lines: f64,
+    pub log_disconnections: bool,
+}
+
+impl LogSettings {
+    pub fn from_params(params: &HashMap<String, PgConfigParam>) -> Self {
+        let value = |name: &str| {
+            params
+                .get(name)
+                .map(|param| param.current_value.trim().to_ascii_lowercase())
+                .unwrap_or_default()
+        };
+        let duration = |name: &str| {
+            params
+                .get(name)
+                .and_then(|param| param.value.as_duration())
+                .map(|duration| duration.as_millis() as u64)
+        };
+        let enabled = |name: &str| {
+            params
+                .get(name)
+                .and_then(|param| param.value.as_bool())
+                .unwrap_or(false)
+        };
+
+        Self {
+            min_duration_ms: duration("log_min_duration_statement"),
+            sample_min_duration_ms: duration("log_min_duration_sample"),
+            sample_rate: params
+                .get("log_statement_sample_rate")
+                .and_then(|param| param.value.as_f64())
+                .unwrap_or(1.0),
+            statement_classes: match value("log_statement").as_str() {
+                "ddl" => vec!["ddl", "role"],
+                "mod" => vec!["ddl", "role", "write"],
+                "all" => AUDIT_CLASSES.to_vec(),
+                _ => Vec::new(),
+            },
+            log_duration: enabled("log_duration"),
+            // PostgreSQL 18 takes a list of connection stages instead of a boolean
+            connection_lines: match value("log_connections").as_str() {
+                "" | "off" | "false" => 0.0,
+                "on" | "true" => 3.0,
+                "all" => 4.0,
+                stages => stages.split(',').count() as f64,
+            },
+            log_disconnections: enabled("log_disconnections"),
+        }
+    }
+}
+
+/// Estimated log output per hour, split by what produces it
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LogVolume {
+    pub lines_per_hour: f64,
+    /// log_min_duration_statement, log_min_duration_sample and log_duration
+    pub duration_bytes: f64,
+    /// log_statement
+    pub statement_bytes: f64,
+    /// log_connections and log_disconnections
+    pub connection_bytes: f64,
+}
+
+impl LogVolume {
+    pub fn bytes_per_hour(&self) -> f64 {
+        self.duration_bytes + self.statement_bytes + self.connection_bytes
+    }
+}
+
+/// Calls per second slower than `threshold_ms`, rounded down to the nearest
+/// kept threshold, with their mean statement length
+fn slower_than(rates: &StatementRates, threshold_ms: u64) -> (f64, f64) {
+    rates
+        .slower_than
+        .iter()
+        .rev()
+        .find(|rate| rate.threshold_ms <= threshold_ms)
+        .map_or((0.0, 0.0), |rate| {
+            (rate.per_second, rate.mean_statement_bytes)
+        })
+}
+
+pub fn estimate(settings: &LogSettings, rates: &StatementRates) -> LogVolume {
+    let total_rate: f64 = rates.classes.iter().map(|rate| rate.per_second).sum();
+    let (statement_rate, statement_bytes) = rates
+        .classes
+        .iter()
+        .filter(|rate| settings.statement_classes.contains(&rate.class.as_str()))
+        .fold((0.0, 0.0), |(calls, bytes), rate| {
+            (
+                calls + rate.per_second,
+                bytes
+                    + rate.per_second * (LOG_LINE_PREFIX_BYTES + 11.0 + rate.mean_statement_bytes),
+            )
+        });
+    // Statements log_statement already wrote get duration lines without text
+    let uncovered = if total_rate > 0.0 {
+        1.0 - statement_rate / total_rate
+    } else {
+        1.0
+    };
+
+    let (slow_rate, slow_bytes) = settings
+        .min_duration_ms
+        .map_or((0.0, 0.0), |threshold| slower_than(rates, threshold));
+    let (sampled_rate, sampled_bytes) = match settings.sample_min_duration_ms {
+        Some(threshold) if settings.min_duration_ms.is_none_or(|min| threshold < min) => {
+            let (rate, bytes) = slower_than(rates, threshold);
+            ((rate - slow_rate).max(0.0) * settings.sample_rate, bytes)
+        }
+        _ => (0.0, 0.0),
+    };
+    let duration_line = LOG_LINE_PREFIX_BYTES + DURATION_LINE_BYTES;
+    let mut duration_rate = slow_rate + sampled_rate;
+    let mut duration_bytes = slow_rate * (duration_line + uncovered * slow_bytes)
+        + sampled_rate * (duration_line + uncovered * sampled_bytes);
+    if settings.log_duration {
+        let remaining = (total_rate - duration_rate).max(0.0);
+        duration_rate += remaining;
+        duration_bytes += remaining * duration_line;
+    }
+
+    let sessions = rates.sessions_per_second.unwrap_or(0.0);
+    let disconnection_lines = if settings.log_disconnections {
+        1.0
+    } else {
+        0.0
+    };
+    let connection_bytes = sessions
+        * (settings.connection_lines * (LOG_LINE_PREFIX_BYTES + CONNECTION_LINE_BYTES)
+            + disconnection_lines * (LOG_LINE_PREFIX_BYTES + DISCONNECTION_LINE_BYTES));
+
+    LogVolume {
+        lines_per_hour: (statement_rate
+            + duration_rate
+            + sessions * (settings.connection_lines + disconnection_lines))
+            * SECONDS_PER_HOUR,
+        duration_bytes: duration_bytes * SECONDS_PER_HOUR,
+        statement_bytes: statement_bytes * SECONDS_PER_HOUR,
+        connection_bytes: connection_bytes * SECONDS_PER_HOUR,
+    }
+}
+
+/// Sampled duration logging that fits the budget: every statement above
+/// `log_min_duration_statement`, and `rate` of those above the sample threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SampledLogging {
+    min_duration_ms: u64,
+    sample_min_duration_ms: u64,
+    rate: f64,
+}
+
+/// Keeps statements slower than the first threshold above `sample_from` whose
+/// full logging takes at most a quarter of the budget, and samples the rest
+/// into the remaining half
+fn sampled_logging(
+    settings: &LogSettings,
+    rates: &StatementRates,
+    sample_from: u64,
+) -> SampledLogging {
+    let per_hour = |threshold: u64| {
+        let settings = LogSettings {
+            min_duration_ms: Some(threshold),
+            sample_min_duration_ms: None,
+            log_duration: false,
+            ..settings.clone()
+        };
+        estimate(&settings, rates).duration_bytes
+    };
+    let min_duration_ms = DURATION_THRESHOLDS_MS
+        .iter()
+        .copied()
+        .filter(|threshold| *threshold > sample_from.max(RECOMMENDED_MIN_DURATION_MS))
+        .find(|threshold| per_hour(*threshold) <= LOG_VOLUME_BUDGET_BYTES / 4.0)
+        .unwrap_or(*DURATION_THRESHOLDS_MS.last().expect("thresholds"));
+    let sampled = per_hour(sample_from) - per_hour(min_duration_ms);
+    let rate = if sampled > 0.0 {
+        ((LOG_VOLUME_BUDGET_BYTES / 2.0) / sampled).clamp(MIN_SAMPLE_RATE, 1.0)
+    } else {
+        1.0
+    };
+    SampledLogging {
+        min_duration_ms,
+        sample_min_duration_ms: sample_from,
+        // Two decimals, rounded down so the estimate stays within budget
+        rate: (rate * 100.0).floor() / 100.0,
+    }
+}
+
+/// Estimates the log volume of the current logging settings and of the
+/// recommended log_min_duration_statement, and offers sampled logging when
+/// either would overwhelm a log pipeline
+pub fn analyze_log_volume(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    let Some(rates) = &stats.statement_rates else {
+        return Ok(());
+    };
+    let current = LogSettings::from_params(params);
+    let volume = estimate(&current, rates);
+    let bytes = volume.bytes_per_hour();
+
+    if bytes > LOG_VOLUME_BUDGET_BYTES {
+        let level = if bytes > LOG_VOLUME_BUDGET_BYTES * LOG_VOLUME_CRITICAL_FACTOR {
+            SuggestionLevel::Critical
+        } else {
+            SuggestionLevel::Important
+        };
+        let largest = volume
+            .duration_bytes
+            .max(volume.statement_bytes)
+            .max(volume.connection_bytes);
+        if largest == volume.duration_bytes {
+            let sample_from = current.min_duration_ms.unwrap_or(0);
+            let sampled = sampled_logging(&current, rates, sample_from);
+            add_suggestion(
+                results,
+                "log_statement_sample_rate",
+                &current_value(params, "log_statement_sample_rate", "1"),
+                &sampled.rate.to_string(),
+                level,
+                with_volume(Message::new("logging.log_statement_sample_rate"), &volume)
+                    .arg("min_duration", sampled.min_duration_ms)
+                    .arg("sample_min_duration", sampled.sample_min_duration_ms)
+                    .arg("rate", sampled.rate),
+            );
+        } else if largest == volume.statement_bytes {
+            let ddl_only = LogSettings {
+                statement_classes: vec!["ddl", "role"],
+                ..current.clone()
+            };
+            let sampled = sampled_logging(&ddl_only, rates, 0);
+            add_suggestion(
+                results,
+                "log_statement",
+                &current_value(params, "log_statement", "none"),
+                "ddl",
+                level,
+                with_volume(Message::new("logging.log_statement"), &volume)
+                    .arg("rate", sampled.rate),
+            );
+        } else {
+            add_suggestion(
+                results,
+                "log_connections",
+                &current_value(params, "log_connections", "off"),
+                "off",
+                SuggestionLevel::Recommended,
+                with_volume(Message::new("logging.log_connections"), &volume).arg(
+                    "sessions",
+                    format!("{:.0}", rates.sessions_per_second.unwrap_or(0.0)),
+                ),
+            );
+        }
+        return Ok(());
+    }
+
+    // The logging checks suggest 1000ms when duration logging is off or coarse
+    let coarse = current
+        .min_duration_ms
+        .is_none_or(|ms| ms > MAX_MIN_DURATION_MS);
+    if !coarse {
+        return Ok(());
+    }
+    let recommended = LogSettings {
+        min_duration_ms: Some(RECOMMENDED_MIN_DURATION_MS),
+        ..current.clone()
+    };
+    let projected = estimate(&recommended, rates);
+    if projected.bytes_per_hour() <= LOG_VOLUME_BUDGET_BYTES {
+        return Ok(());
+    }
+    let sampled = sampled_logging(&recommended, rates, RECOMMENDED_MIN_DURATION_MS);
+    let note = with_volume(Message::new("note.log_volume_at_recommended"), &projected)
+        .arg("min_duration", sampled.min_duration_ms)
+        .arg("sample_min_duration", sampled.sample_min_duration_ms)
+        .arg("rate", sampled.rate);
+    if let Some(suggestion) = results
+        .suggestions_by_category
+        .get_mut(&ConfigCategory::Logging)
+        .and_then(|suggestions| {
+            suggestions
+                .iter_mut()
+                .find(|suggestion| suggestion.parameter == "log_min_duration_statement")
+        })
+    {
+        suggestion.push_note(note);
+    }
+
+    Ok(())
+}
+
+fn with_volume(message: Message, volume: &LogVolume) -> Message {
+    message
+        .arg("volume", ByteSize(volume.bytes_per_hour() as i64))
+        .arg("lines", format!("{:.0}", volume.lines_per_hour))
+}
+
+fn current_value(params: &HashMap<String, PgConfigParam>, name: &str, default: &str) -> String {
+    params
+        .get(name)
+        .map_or_else(|| default.to_string(), |param| param.current_value.clone())
+}
+
+fn add_suggestion(
+    results: &mut AnalysisResults,
+    parameter: &str,
+    current_value: &str,
+    suggested_value: &str,
+    level: SuggestionLevel,
+    rationale: Message,
+) {
+    results
+        .suggestions_by_category
+        .entry(ConfigCategory::Logging)
+        .or_default()
+        .push(ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: current_value.to_string(),
+            suggested_value: suggested_value.to_string(),
+            level,
+            rationale: rationale.render(Lang::En),
+            messages: vec![rationale],
+            fingerprint: String::new(),
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ParamValue;
+    use rstest::rstest;
+
+    fn params(settings: &[(&str, &str, &str, Option<&str>)]) -> HashMap<String, PgConfigParam> {
+        settings
+            .iter()
+            .map(|(name, value, vartype, unit)| {
+                let param = PgConfigParam {
+                    name: name.to_string(),
+                    current_value: value.to_string(),
+                    default_value: None,
+                    unit: unit.map(str::to_string),
+                    context: "superuser".to_string(),
+                    value: ParamValue::parse(value, vartype, *unit),
+                    source: None,
+                    sourcefile: None,
+                    pending_restart: false,
+                    min_value: None,
+                    max_value: None,
+                };
+                (param.name.clone(), param)
+            })
+            .collect()
+    }
+
+    fn logging(
+        min_duration: &str,
+        log_statement: &str,
+        log_connections: &str,
+    ) -> HashMap<String, PgConfigParam> {
+        params(&[
+            (
+                "log_min_duration_statement",
+                min_duration,
+                "integer",
+                Some("ms"),
+            ),
+            ("log_statement", log_statement, "enum", None),
+            ("log_connections", log_connections, "bool", None),
+            ("log_disconnections", log_connections, "bool", None),
+        ])
+    }
+
+    /// An hour of statements at `per_second` calls a second, 80 characters each
+    fn timing(per_second: i64, mean_ms: f64, stddev_ms: f64, max_ms: f64) -> StatementTiming {
+        StatementTiming {
+            query: format!("SELECT * FROM orders WHERE id = $1{}", " ".repeat(46)),
+            calls: per_second * 3600,
+            mean_ms,
+            stddev_ms,
+            max_ms,
+        }
+    }
+
+    fn stats(statements: &[StatementTiming], sessions_per_second: f64) -> SystemStats {
+        SystemStats {
+            statement_rates: Some(statement_rates(statements, 3600, Some(sessions_per_second))),
+            ..Default::default()
+        }
+    }
+
+    fn suggestions(
+        params: &HashMap<String, PgConfigParam>,
+        stats: &SystemStats,
+    ) -> Vec<ConfigSuggestion> {
+        let mut results = AnalysisResults::default();
+        analyze_log_volume(params, stats, &mut results).unwrap();
+        results
+            .suggestions_by_category
+            .remove(&ConfigCategory::Logging)
+            .unwrap_or_default()
+    }
+
+    #[rstest]
+    #[case(0, 1000.0)]
+    #[case(100, 500.0)]
+    #[case(250, 1.0)]
+    #[case(500, 0.0)]
+    fn slow_calls_follow_the_timing_distribution(#[case] threshold_ms: u64, #[case] expected: f64) {
+        let rates = statement_rates(&[timing(1000, 100.0, 50.0, 400.0)], 3600, None);
+        let rate = rates
+            .slower_than
+            .iter()
+            .find(|rate| rate.threshold_ms == threshold_ms)
+            .unwrap();
+        assert!(
+            (rate.per_second - expected).abs() < 2.0,
+            "{threshold_ms}ms: {}",
+            rate.per_second
+        );
+    }
+
+    #[test]
+    fn logging_every_statement_is_sampled_instead() {
+        // 10k 2ms reads and one 2s report a second; every one logged is ~6.7GB an hour
+        let stats = stats(
+            &[
+                timing(10_000, 2.0, 1.0, 50.0),
+                timing(1, 2000.0, 500.0, 5000.0),
+            ],
+            1.0,
+        );
+        let suggestions = suggestions(&logging("0", "none", "off"), &stats);
+
+        assert_eq!(suggestions.len(), 1);
+        let suggestion = &suggestions[0];
+        assert_eq!(suggestion.parameter, "log_statement_sample_rate");
+        assert_eq!(suggestion.level, SuggestionLevel::Important);
+        assert_eq!(suggestion.suggested_value, "0.07");
+        assert!(suggestion
+            .rationale
+            .contains("log_min_duration_statement = 2000ms"));
+        assert!(suggestion
+            .rationale
+            .contains("log_min_duration_sample = 0ms"));
+
+        assert!(suggestions_for(&logging("1000", "none", "off"), &stats).is_empty());
+    }
+
+    fn suggestions_for(
+        params: &HashMap<String, PgConfigParam>,
+        stats: &SystemStats,
+    ) -> Vec<String> {
+        suggestions(params, stats)
+            .into_iter()
+            .map(|suggestion| format!("{} -> {}", suggestion.parameter, suggestion.suggested_value))
+            .collect()
+    }
+
+    #[rstest]
+    #[case(logging("1000", "all", "off"), 10_000, 1.0, &["log_statement -> ddl"])]
+    #[case(logging("1000", "mod", "off"), 10_000, 1.0, &[])]
+    #[case(logging("1000", "ddl", "on"), 10, 1000.0, &["log_connections -> off"])]
+    #[case(logging("1000", "ddl", "on"), 10, 100.0, &[])]
+    fn the_largest_log_source_is_narrowed(
+        #[case] params: HashMap<String, PgConfigParam>,
+        #[case] reads_per_second: i64,
+        #[case] sessions_per_second: f64,
+        #[case] expected: &[&str],
+    ) {
+        let stats = stats(
+            &[timing(reads_per_second, 2.0, 1.0, 50.0)],
+            sessions_per_second,
+        );
+        assert_eq!(suggestions_for(&params, &stats), expected);
+    }
+
+    #[test]
+    fn recommended_duration_logging_notes_its_volume() {
+        // Every call takes 1.2s, so log_min_duration_statement = 1000 logs them all
+        let stats = stats(&[timing(10_000, 1200.0, 0.0, 1200.0)], 1.0);
+        let params = logging("-1", "none", "off");
+        let mut results = AnalysisResults::default();
+        crate::analysis::logging::analyze_logging(&params, &stats, &mut results).unwrap();
+
+        let suggestion = results.suggestions_by_category[&ConfigCategory::Logging]
+            .iter()
+            .find(|suggestion| suggestion.parameter == "log_min_duration_statement")
+            .unwrap();
+        assert!(suggestion.rationale.contains(
+            "set it to 2000ms and sample the rest with log_min_duration_sample = 1000ms \
+             and log_statement_sample_rate = 0.07"
+        ));
+    }
+}
@@ -1,18 +1,36 @@
+use crate::analysis::{log_volume, managed, param_value, pgaudit};
 use crate::checker::CheckerError;
-use crate::models::{AnalysisResults, ConfigCategory, ConfigSuggestion, SuggestionLevel};
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ConfigCategory, ConfigSuggestion, ParamValue, SuggestionLevel,
+};
 use std::collections::HashMap;
+use std::time::Duration;
 
 type Result<T> = std::result::Result<T, CheckerError>;
 
+/// Days of server log the rotation settings should keep unless the config
+/// file sets `log_retention_days`
+const DEFAULT_LOG_RETENTION_DAYS: usize = 7;
+/// First release with `log_destination = 'jsonlog'`
+const JSONLOG_VERSION_NUM: i64 = 150_000;
+
 /// Analyzes logging and diagnostics configuration
 pub fn analyze_logging(
     params: &HashMap<String, crate::models::PgConfigParam>,
-    _stats: &crate::models::SystemStats,
+    stats: &crate::models::SystemStats,
     results: &mut AnalysisResults,
 ) -> Result<()> {
     analyze_log_min_duration_statement(params, results)?;
     analyze_log_lock_waits(params, results)?;
     analyze_deadlock_timeout(params, results)?;
+    pgaudit::analyze_pgaudit(params, stats, results)?;
+    log_volume::analyze_log_volume(params, stats, results)?;
+    // Managed services collect and rotate server logs themselves
+    if managed::detect_platform(params).is_none() {
+        analyze_log_destination(params, results)?;
+        analyze_log_rotation(params, stats, results)?;
+    }
 
     Ok(())
 }
@@ -22,8 +40,9 @@ fn analyze_log_min_duration_statement(
     results: &mut AnalysisResults,
 ) -> Result<()> {
     let current_value = get_param_value(params, "log_min_duration_statement");
+    let current = param_value(params, "log_min_duration_statement");
 
-    if current_value == "-1" {
+    if current == Some(&ParamValue::Integer(-1)) {
         // Disabled
         add_suggestion(
             results,
@@ -32,10 +51,12 @@ fn analyze_log_min_duration_statement(
             &current_value,
             "1000",
             SuggestionLevel::Important,
-            "log_min_duration_statement is disabled. This is the primary tool for finding \
-             slow queries. Set to 1000 (1 second) to log all queries taking 1 second or longer.",
+            Message::new("logging.log_min_duration_statement.disabled"),
         );
-    } else if let Ok(current_ms) = current_value.parse::<i64>() {
+    } else if let Some(current_ms) = current
+        .and_then(ParamValue::as_duration)
+        .map(|duration| duration.as_millis())
+    {
         if current_ms > 5000 {
             add_suggestion(
                 results,
@@ -44,12 +65,8 @@ fn analyze_log_min_duration_statement(
                 &current_value,
                 "1000",
                 SuggestionLevel::Recommended,
-                &format!(
-                    "log_min_duration_statement is set quite high ({}ms). For most workloads, \
-                     1000ms (1 second) is a good starting point to identify slow queries without \
-                     excessive log noise.",
-                    current_ms
-                ),
+                Message::new("logging.log_min_duration_statement.high")
+                    .arg("current_ms", current_ms),
             );
         } else if current_ms == 0 {
             add_suggestion(
@@ -59,9 +76,7 @@ fn analyze_log_min_duration_statement(
                 &current_value,
                 "1000",
                 SuggestionLevel::Info,
-                "log_min_duration_statement is logging ALL queries. This may generate \
-                 excessive logs. For most workloads, 1000ms (1 second) is sufficient to \
-                 identify slow queries.",
+                Message::new("logging.log_min_duration_statement.all"),
             );
         }
     }
@@ -83,9 +98,7 @@ fn analyze_log_lock_waits(
             &current_value,
             "on",
             SuggestionLevel::Important,
-            "log_lock_waits is disabled. This is invaluable for diagnosing \
-             application-level concurrency and contention issues. Enable it to log \
-             any session that waits for a lock longer than deadlock_timeout.",
+            Message::new("logging.log_lock_waits"),
         );
     }
 
@@ -97,9 +110,11 @@ fn analyze_deadlock_timeout(
     results: &mut AnalysisResults,
 ) -> Result<()> {
     let current_value = get_param_value(params, "deadlock_timeout");
-    let current_ms = parse_time_to_ms(&current_value).unwrap_or(1000);
+    let current = param_value(params, "deadlock_timeout")
+        .and_then(ParamValue::as_duration)
+        .unwrap_or(Duration::from_secs(1));
 
-    if current_ms > 1000 {
+    if current > Duration::from_secs(1) {
         // Default is 1 second (1000ms)
         add_suggestion(
             results,
@@ -108,15 +123,199 @@ fn analyze_deadlock_timeout(
             &current_value,
             "1s",
             SuggestionLevel::Info,
-            "deadlock_timeout is set higher than the default 1s. While this may reduce \
-             false positives in lock wait logging, it also means deadlock detection \
-             takes longer. The default 1s is typically sufficient for most workloads.",
+            Message::new("logging.deadlock_timeout"),
         );
     }
 
     Ok(())
 }
 
+/// Recommends a machine-readable log format alongside the current
+/// destinations, and the logging collector it needs
+fn analyze_log_destination(
+    params: &HashMap<String, crate::models::PgConfigParam>,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    let Some(destination) = params.get("log_destination") else {
+        return Ok(());
+    };
+    let mut destinations: Vec<String> = destination
+        .current_value
+        .split(',')
+        .map(|item| item.trim().to_ascii_lowercase())
+        .filter(|item| !item.is_empty())
+        .collect();
+
+    if !destinations
+        .iter()
+        .any(|item| item == "csvlog" || item == "jsonlog")
+    {
+        let version = param_value(params, "server_version_num").and_then(ParamValue::as_i64);
+        let format = if version.is_some_and(|num| num >= JSONLOG_VERSION_NUM) {
+            "jsonlog"
+        } else {
+            "csvlog"
+        };
+        destinations.push(format.to_string());
+        add_suggestion(
+            results,
+            ConfigCategory::Logging,
+            "log_destination",
+            &destination.current_value,
+            &destinations.join(","),
+            SuggestionLevel::Recommended,
+            Message::new("logging.log_destination").arg("format", format),
+        );
+    }
+
+    let collector = get_param_value(params, "logging_collector");
+    if param_value(params, "logging_collector").and_then(ParamValue::as_bool) == Some(false) {
+        add_suggestion(
+            results,
+            ConfigCategory::Logging,
+            "logging_collector",
+            &collector,
+            "on",
+            SuggestionLevel::Recommended,
+            Message::new("logging.logging_collector"),
+        );
+    }
+
+    Ok(())
+}
+
+/// Checks that the collector's rotation keeps at least the configured days of
+/// log, and that it does not grow without bound
+fn analyze_log_rotation(
+    params: &HashMap<String, crate::models::PgConfigParam>,
+    stats: &crate::models::SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    if param_value(params, "logging_collector").and_then(ParamValue::as_bool) != Some(true) {
+        return Ok(());
+    }
+    let Some(filename) = params.get("log_filename") else {
+        return Ok(());
+    };
+    let retention_days = stats
+        .log_retention_days
+        .unwrap_or(DEFAULT_LOG_RETENTION_DAYS);
+    let truncate =
+        param_value(params, "log_truncate_on_rotation").and_then(ParamValue::as_bool) == Some(true);
+    let rotation_age = param_value(params, "log_rotation_age").and_then(ParamValue::as_duration);
+    let rotation_size = param_value(params, "log_rotation_size").and_then(ParamValue::as_bytes);
+
+    if rotation_age == Some(Duration::ZERO) && rotation_size == Some(0) {
+        add_suggestion(
+            results,
+            ConfigCategory::Logging,
+            "log_rotation_age",
+            &get_param_value(params, "log_rotation_age"),
+            "1d",
+            SuggestionLevel::Recommended,
+            Message::new("logging.log_rotation_age"),
+        );
+    }
+
+    let cycle = filename_cycle_days(&filename.current_value);
+    let pattern = retention_pattern(retention_days);
+    match cycle {
+        Some(days) if days < retention_days as f64 => add_suggestion(
+            results,
+            ConfigCategory::Logging,
+            "log_filename",
+            &filename.current_value,
+            pattern,
+            if truncate {
+                SuggestionLevel::Important
+            } else {
+                SuggestionLevel::Recommended
+            },
+            Message::new("logging.log_filename.short")
+                .arg("cycle", describe_days(days))
+                .arg("retention", retention_days),
+        ),
+        Some(_) => {}
+        None => add_suggestion(
+            results,
+            ConfigCategory::Logging,
+            "log_filename",
+            &filename.current_value,
+            pattern,
+            SuggestionLevel::Info,
+            Message::new("logging.log_filename.unbounded").arg("retention", retention_days),
+        ),
+    }
+
+    if !truncate {
+        add_suggestion(
+            results,
+            ConfigCategory::Logging,
+            "log_truncate_on_rotation",
+            &get_param_value(params, "log_truncate_on_rotation"),
+            "on",
+            if cycle.is_some() {
+                SuggestionLevel::Recommended
+            } else {
+                SuggestionLevel::Info
+            },
+            Message::new("logging.log_truncate_on_rotation"),
+        );
+    }
+
+    Ok(())
+}
+
+/// Days after which a strftime `log_filename` produces the same name again;
+/// `None` when it never does (it includes the year) or has no time fields
+fn filename_cycle_days(pattern: &str) -> Option<f64> {
+    let fields: Vec<char> = pattern
+        .split('%')
+        .skip(1)
+        .filter_map(|field| field.chars().next())
+        .collect();
+    let has = |chars: &str| fields.iter().any(|field| chars.contains(*field));
+    if has("YyGgCsFDcx") {
+        None
+    } else if has("mbBhjUVW") {
+        Some(365.0)
+    } else if has("de") {
+        Some(28.0)
+    } else if has("aAuw") {
+        Some(7.0)
+    } else if has("Hk") {
+        Some(1.0)
+    } else if has("Ilp") {
+        Some(0.5)
+    } else if has("MR") {
+        Some(1.0 / 24.0)
+    } else if has("ST") {
+        Some(1.0 / 1440.0)
+    } else {
+        None
+    }
+}
+
+/// The coarsest repeating `log_filename` that keeps at least `days` of logs
+fn retention_pattern(days: usize) -> &'static str {
+    match days {
+        0..=7 => "postgresql-%a.log",
+        8..=28 => "postgresql-%d.log",
+        _ => "postgresql-%m-%d.log",
+    }
+}
+
+/// `days` in PostgreSQL's own units, e.g. `7d`, `12h` or `1min`
+fn describe_days(days: f64) -> String {
+    if days >= 1.0 {
+        format!("{days:.0}d")
+    } else if days * 24.0 >= 1.0 {
+        format!("{:.0}h", days * 24.0)
+    } else {
+        format!("{:.0}min", days * 1440.0)
+    }
+}
+
 // Helper functions
 
 fn get_param_value(params: &HashMap<String, crate::models::PgConfigParam>, name: &str) -> String {
@@ -126,29 +325,6 @@ fn get_param_value(params: &HashMap<String, crate::models::PgConfigParam>, name:
         .unwrap_or_else(|| "unknown".to_string())
 }
 
-fn parse_time_to_ms(value: &str) -> Option<u64> {
-    let lower = value.to_lowercase();
-
-    if lower.ends_with("s") {
-        if let Ok(sec) = lower.trim_end_matches("s").trim().parse::<u64>() {
-            return Some(sec * 1000);
-        }
-    } else if lower.ends_with("min") {
-        if let Ok(min) = lower.trim_end_matches("min").trim().parse::<u64>() {
-            return Some(min * 60 * 1000);
-        }
-    } else if lower.ends_with("ms") {
-        if let Ok(ms) = lower.trim_end_matches("ms").trim().parse::<u64>() {
-            return Some(ms);
-        }
-    } else if let Ok(ms) = lower.parse::<u64>() {
-        // Assume ms if no unit
-        return Some(ms);
-    }
-
-    None
-}
-
 fn add_suggestion(
     results: &mut AnalysisResults,
     category: ConfigCategory,
@@ -156,14 +332,16 @@ fn add_suggestion(
     current_value: &str,
     suggested_value: &str,
     level: SuggestionLevel,
-    rationale: &str,
+    rationale: Message,
 ) {
     let suggestion = ConfigSuggestion {
         parameter: parameter.to_string(),
         current_value: current_value.to_string(),
         suggested_value: suggested_value.to_string(),
         level,
-        rationale: rationale.to_string(),
+        rationale: rationale.render(Lang::En),
+        messages: vec![rationale],
+        fingerprint: String::new(),
     };
 
     results
@@ -172,3 +350,121 @@ fn add_suggestion(
         .or_default()
         .push(suggestion);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{PgConfigParam, SystemStats};
+    use rstest::rstest;
+
+    fn params(settings: &[(&str, &str, &str, Option<&str>)]) -> HashMap<String, PgConfigParam> {
+        settings
+            .iter()
+            .map(|(name, value, vartype, unit)| {
+                let param = PgConfigParam {
+                    name: name.to_string(),
+                    current_value: value.to_string(),
+                    default_value: None,
+                    unit: unit.map(str::to_string),
+                    context: "sighup".to_string(),
+                    value: ParamValue::parse(value, vartype, *unit),
+                    source: None,
+                    sourcefile: None,
+                    pending_restart: false,
+                    min_value: None,
+                    max_value: None,
+                };
+                (param.name.clone(), param)
+            })
+            .collect()
+    }
+
+    fn suggested(
+        params: &HashMap<String, PgConfigParam>,
+        retention_days: Option<usize>,
+    ) -> Vec<String> {
+        let stats = SystemStats {
+            log_retention_days: retention_days,
+            ..Default::default()
+        };
+        let mut results = AnalysisResults::default();
+        analyze_log_destination(params, &mut results).unwrap();
+        analyze_log_rotation(params, &stats, &mut results).unwrap();
+        results
+            .suggestions_by_category
+            .remove(&ConfigCategory::Logging)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|suggestion| {
+                format!(
+                    "{} -> {} ({})",
+                    suggestion.parameter,
+                    suggestion.suggested_value,
+                    suggestion.level.as_str()
+                )
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case("postgresql-%Y-%m-%d_%H%M%S.log", None)]
+    #[case("postgresql-%a.log", Some(7.0))]
+    #[case("postgresql-%d.log", Some(28.0))]
+    #[case("postgresql-%m-%d.log", Some(365.0))]
+    #[case("postgresql-%H.log", Some(1.0))]
+    #[case("postgresql.log", None)]
+    fn log_filenames_repeat_at_their_largest_field(
+        #[case] pattern: &str,
+        #[case] expected: Option<f64>,
+    ) {
+        assert_eq!(filename_cycle_days(pattern), expected);
+    }
+
+    #[rstest]
+    #[case("stderr", "150004", "off", &["log_destination -> stderr,jsonlog (RECOMMENDED)", "logging_collector -> on (RECOMMENDED)"])]
+    #[case("stderr", "140011", "on", &["log_destination -> stderr,csvlog (RECOMMENDED)"])]
+    #[case("csvlog", "170002", "on", &[])]
+    fn structured_logging_is_recommended(
+        #[case] destination: &str,
+        #[case] version_num: &str,
+        #[case] collector: &str,
+        #[case] expected: &[&str],
+    ) {
+        let params = params(&[
+            ("log_destination", destination, "string", None),
+            ("server_version_num", version_num, "integer", None),
+            ("logging_collector", collector, "bool", None),
+        ]);
+        assert_eq!(suggested(&params, None), expected);
+    }
+
+    #[rstest]
+    #[case("postgresql-%a.log", "on", "1440", None, &[])]
+    #[case("postgresql-%a.log", "on", "1440", Some(14), &["log_filename -> postgresql-%d.log (IMPORTANT)"])]
+    #[case("postgresql-%H.log", "off", "60", None, &[
+        "log_filename -> postgresql-%a.log (RECOMMENDED)",
+        "log_truncate_on_rotation -> on (RECOMMENDED)",
+    ])]
+    #[case("postgresql-%Y-%m-%d_%H%M%S.log", "off", "0", None, &[
+        "log_rotation_age -> 1d (RECOMMENDED)",
+        "log_filename -> postgresql-%a.log (INFO)",
+        "log_truncate_on_rotation -> on (INFO)",
+    ])]
+    fn log_rotation_keeps_the_retention_period(
+        #[case] filename: &str,
+        #[case] truncate: &str,
+        #[case] rotation_age_min: &str,
+        #[case] retention_days: Option<usize>,
+        #[case] expected: &[&str],
+    ) {
+        let params = params(&[
+            ("log_destination", "jsonlog", "string", None),
+            ("logging_collector", "on", "bool", None),
+            ("log_filename", filename, "string", None),
+            ("log_truncate_on_rotation", truncate, "bool", None),
+            ("log_rotation_age", rotation_age_min, "integer", Some("min")),
+            ("log_rotation_size", "0", "integer", Some("kB")),
+        ]);
+        assert_eq!(suggested(&params, retention_days), expected);
+    }
+}
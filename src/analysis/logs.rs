@@ -0,0 +1,606 @@
+use crate::analysis::auto_explain::{self, PlanCollector, PlanMemory};
+use crate::analysis::query_fingerprint;
+use crate::analysis::workload::{self, WorkloadOptions};
+use crate::models::{
+    AutovacuumLogFinding, LockWaitFinding, LogFindings, LoggedPlanFinding, WorkloadMetadata,
+    WorkloadResults,
+};
+use clap::ValueEnum;
+use snafu::{ResultExt, Snafu};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Snafu)]
+pub enum LogError {
+    #[snafu(display("Failed to read log file {}: {}", path.display(), source))]
+    LogRead {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Failed to parse csvlog file {}: {}", path.display(), source))]
+    CsvParse { path: PathBuf, source: csv::Error },
+}
+
+/// How a server log file was written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// `csvlog` for `.csv` files, `stderr` for everything else
+    #[default]
+    Auto,
+    /// `log_destination = 'csvlog'`
+    Csvlog,
+    /// `log_destination = 'stderr'` with any `log_line_prefix`
+    Stderr,
+}
+
+/// Totals of one statement fingerprint across the logs
+#[derive(Debug, Clone, Default)]
+pub struct LoggedStatement {
+    /// Stable ID derived from the fingerprint; not a `pg_stat_statements` queryid
+    pub id: i64,
+    /// Text of the first logged call
+    pub query: String,
+    /// Logged durations, or temp file entries for statements that only logged those
+    pub calls: i64,
+    pub total_time_ms: f64,
+    pub max_time_ms: f64,
+    pub temp_bytes: i64,
+    pub roles: Vec<String>,
+    pub database: Option<String>,
+}
+
+/// One log message with the lines PostgreSQL logs after it
+#[derive(Debug, Clone, Default, PartialEq)]
+struct LogEntry {
+    severity: String,
+    message: String,
+    statement: Option<String>,
+    user: Option<String>,
+    database: Option<String>,
+}
+
+/// Severities that start a new entry in stderr logs
+const PRIMARY_SEVERITIES: &[&str] = &[
+    "LOG", "ERROR", "WARNING", "FATAL", "PANIC", "NOTICE", "INFO", "DEBUG1", "DEBUG2", "DEBUG3",
+    "DEBUG4", "DEBUG5",
+];
+/// Lines that belong to the entry before them
+const SECONDARY_SEVERITIES: &[&str] = &["STATEMENT", "DETAIL", "HINT", "CONTEXT", "QUERY"];
+
+/// csvlog columns, stable since PostgreSQL 9.0
+const CSV_USER: usize = 1;
+const CSV_DATABASE: usize = 2;
+const CSV_SEVERITY: usize = 11;
+const CSV_MESSAGE: usize = 13;
+const CSV_QUERY: usize = 19;
+
+/// Reads server log files into workload findings: slow-query groups and index
+/// candidates from duration lines, plus temp files, lock waits and autovacuum
+/// runs. For servers where `pg_stat_statements` cannot be installed.
+/// `memory` is the work_mem the auto_explain plans are measured against.
+pub fn analyze_log_files(
+    paths: &[PathBuf],
+    format: LogFormat,
+    opts: &WorkloadOptions,
+    memory: PlanMemory,
+) -> Result<WorkloadResults, LogError> {
+    let mut collector = LogCollector {
+        plans: PlanCollector::new(memory),
+        ..LogCollector::default()
+    };
+    for path in paths {
+        let file = File::open(path).context(LogReadSnafu { path })?;
+        let entries = match format.resolve(path) {
+            LogFormat::Csvlog => parse_csvlog(file).context(CsvParseSnafu { path })?,
+            _ => parse_stderr(BufReader::new(file)).context(LogReadSnafu { path })?,
+        };
+        for entry in &entries {
+            collector.add(entry);
+        }
+        collector.findings.files.push(path.display().to_string());
+    }
+    Ok(collector.finish(opts))
+}
+
+impl LogFormat {
+    fn resolve(self, path: &Path) -> Self {
+        match self {
+            LogFormat::Auto if path.extension().is_some_and(|ext| ext == "csv") => {
+                LogFormat::Csvlog
+            }
+            LogFormat::Auto => LogFormat::Stderr,
+            format => format,
+        }
+    }
+}
+
+fn parse_csvlog<R: Read>(reader: R) -> Result<Vec<LogEntry>, csv::Error> {
+    let mut csv = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(reader);
+    let mut entries = Vec::new();
+    for record in csv.records() {
+        let record = record?;
+        let field = |index: usize| {
+            record
+                .get(index)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        entries.push(LogEntry {
+            severity: field(CSV_SEVERITY).unwrap_or_default(),
+            message: field(CSV_MESSAGE).unwrap_or_default(),
+            statement: field(CSV_QUERY),
+            user: field(CSV_USER),
+            database: field(CSV_DATABASE),
+        });
+    }
+    Ok(entries)
+}
+
+/// Splits stderr output into entries. The `log_line_prefix` is skipped by
+/// looking for the `SEVERITY:  ` marker; lines starting with a tab continue the
+/// previous message or statement.
+fn parse_stderr<R: BufRead>(reader: R) -> std::io::Result<Vec<LogEntry>> {
+    let mut entries: Vec<LogEntry> = Vec::new();
+    // Where continuation lines go: the entry's message, its statement, or nowhere
+    let mut continues: Option<bool> = None;
+    for line in reader.split(b'\n') {
+        let line = String::from_utf8_lossy(&line?)
+            .trim_end_matches('\r')
+            .to_string();
+        if let Some(rest) = line.strip_prefix('\t') {
+            let target = match (continues, entries.last_mut()) {
+                (Some(false), Some(entry)) => Some(&mut entry.message),
+                (Some(true), Some(entry)) => entry.statement.as_mut(),
+                _ => None,
+            };
+            if let Some(target) = target {
+                target.push('\n');
+                target.push_str(rest);
+            }
+            continue;
+        }
+
+        let Some((severity, message)) = split_severity(&line) else {
+            continues = None;
+            continue;
+        };
+        if PRIMARY_SEVERITIES.contains(&severity) {
+            entries.push(LogEntry {
+                severity: severity.to_string(),
+                message: message.to_string(),
+                ..LogEntry::default()
+            });
+            continues = Some(false);
+        } else if let (Some(entry), "STATEMENT") = (entries.last_mut(), severity) {
+            entry.statement = Some(message.to_string());
+            continues = Some(true);
+        } else {
+            continues = None;
+        }
+    }
+    Ok(entries)
+}
+
+/// The earliest `SEVERITY:  ` marker in a line and the message after it
+fn split_severity(line: &str) -> Option<(&str, &str)> {
+    PRIMARY_SEVERITIES
+        .iter()
+        .chain(SECONDARY_SEVERITIES)
+        .filter_map(|severity| {
+            line.find(&format!("{severity}:  "))
+                .map(|start| (start, *severity))
+        })
+        .min_by_key(|(start, _)| *start)
+        .map(|(start, severity)| (severity, &line[start + severity.len() + 3..]))
+}
+
+#[derive(Default)]
+struct LogCollector {
+    statements: HashMap<String, LoggedStatement>,
+    lock_waits: HashMap<(String, String), LockWaitFinding>,
+    autovacuum: HashMap<String, AutovacuumLogFinding>,
+    plans: PlanCollector,
+    findings: LogFindings,
+}
+
+impl LogCollector {
+    fn add(&mut self, entry: &LogEntry) {
+        self.findings.entries += 1;
+        let message = entry.message.as_str();
+        if let Some(rest) = message.strip_prefix("duration: ") {
+            if let Some((duration_ms, plan)) = parse_plan_line(rest) {
+                self.add_plan(duration_ms, plan, entry);
+                return;
+            }
+            let Some((duration_ms, query)) = parse_duration_line(rest) else {
+                return;
+            };
+            self.findings.duration_entries += 1;
+            let statement = self.statement(query, entry);
+            statement.calls += 1;
+            statement.total_time_ms += duration_ms;
+            statement.max_time_ms = statement.max_time_ms.max(duration_ms);
+        } else if let Some(rest) = message.strip_prefix("temporary file: ") {
+            let Some(bytes) = rest
+                .rsplit_once(", size ")
+                .and_then(|(_, size)| size.trim().parse::<i64>().ok())
+            else {
+                return;
+            };
+            self.findings.temp_files += 1;
+            self.findings.temp_bytes.0 += bytes;
+            if let Some(query) = entry.statement.as_deref() {
+                self.statement(query, entry).temp_bytes += bytes;
+            }
+        } else if message.contains(" still waiting for ") || message.contains(" acquired ") {
+            self.add_lock_wait(entry);
+        } else if let Some(rest) = message.strip_prefix("automatic vacuum of table ") {
+            self.add_autovacuum(rest, true);
+        } else if let Some(rest) = message.strip_prefix("automatic analyze of table ") {
+            self.add_autovacuum(rest, false);
+        }
+    }
+
+    fn statement(&mut self, query: &str, entry: &LogEntry) -> &mut LoggedStatement {
+        let fingerprint = query_fingerprint::fingerprint(query);
+        let statement = self
+            .statements
+            .entry(fingerprint)
+            .or_insert_with_key(|fingerprint| LoggedStatement {
+                id: fingerprint_id(fingerprint),
+                query: query.to_string(),
+                database: entry.database.clone(),
+                ..LoggedStatement::default()
+            });
+        if let Some(user) = &entry.user {
+            if !statement.roles.contains(user) {
+                statement.roles.push(user.clone());
+                statement.roles.sort();
+            }
+        }
+        statement
+    }
+
+    /// An auto_explain entry. Its duration is not counted as a call, because
+    /// the same execution is usually logged by `log_min_duration_statement` too.
+    fn add_plan(&mut self, duration_ms: f64, plan: &str, entry: &LogEntry) {
+        self.findings.plan_entries += 1;
+        let Some(plan) = auto_explain::parse_plan(plan) else {
+            return;
+        };
+        let Some(query) = plan.query_text.as_deref().or(entry.statement.as_deref()) else {
+            return;
+        };
+        let id = fingerprint_id(&query_fingerprint::fingerprint(query));
+        self.plans.add(id, query, duration_ms, &plan);
+    }
+
+    /// `process 123 still waiting for ShareLock on transaction 456 after 1000.072 ms`,
+    /// or `acquired` once the lock was granted
+    fn add_lock_wait(&mut self, entry: &LogEntry) {
+        let message = entry.message.as_str();
+        let (waiting, rest) = match message.split_once(" still waiting for ") {
+            Some((_, rest)) => (true, rest),
+            None => match message.split_once(" acquired ") {
+                Some((_, rest)) => (false, rest),
+                None => return,
+            },
+        };
+        let Some((lock, after)) = rest.rsplit_once(" after ") else {
+            return;
+        };
+        let Some(wait_ms) = after
+            .strip_suffix(" ms")
+            .and_then(|ms| ms.trim().parse::<f64>().ok())
+        else {
+            return;
+        };
+        let lock_mode = lock.split(" on ").next().unwrap_or(lock).to_string();
+        let query_text = entry.statement.clone().unwrap_or_default();
+        let key = (
+            query_fingerprint::fingerprint(&query_text),
+            lock_mode.clone(),
+        );
+        let finding = self
+            .lock_waits
+            .entry(key)
+            .or_insert_with(|| LockWaitFinding {
+                lock_mode,
+                waits: 0,
+                max_wait_ms: 0.0,
+                query_text,
+            });
+        if waiting {
+            finding.waits += 1;
+        }
+        finding.max_wait_ms = finding.max_wait_ms.max(wait_ms);
+    }
+
+    /// `"db.schema.table": index scans: ... elapsed: 0.05 s`
+    fn add_autovacuum(&mut self, rest: &str, vacuum: bool) {
+        let Some(table) = rest
+            .strip_prefix('"')
+            .and_then(|rest| rest.split_once('"'))
+            .map(|(table, _)| table.to_string())
+        else {
+            return;
+        };
+        let elapsed = rest
+            .rsplit_once("elapsed: ")
+            .and_then(|(_, elapsed)| elapsed.split_whitespace().next())
+            .and_then(|secs| secs.parse::<f64>().ok())
+            .unwrap_or(0.0);
+        let finding =
+            self.autovacuum
+                .entry(table.clone())
+                .or_insert_with(|| AutovacuumLogFinding {
+                    table,
+                    vacuums: 0,
+                    analyzes: 0,
+                    total_elapsed_secs: 0.0,
+                    max_elapsed_secs: 0.0,
+                });
+        if vacuum {
+            finding.vacuums += 1;
+        } else {
+            finding.analyzes += 1;
+        }
+        finding.total_elapsed_secs += elapsed;
+        finding.max_elapsed_secs = finding.max_elapsed_secs.max(elapsed);
+    }
+
+    fn finish(mut self, opts: &WorkloadOptions) -> WorkloadResults {
+        let mut statements: Vec<LoggedStatement> = self.statements.into_values().collect();
+        for statement in &mut statements {
+            if statement.calls == 0 {
+                statement.calls = 1;
+            }
+        }
+        statements.sort_by_key(|statement| statement.id);
+
+        let mut results = workload::analyze_logged_statements(&statements, opts);
+        results.workload_metadata = WorkloadMetadata {
+            data_source: "server logs".into(),
+            scope: "logged_statements".into(),
+            queries_redacted: opts.redact_queries,
+            ..results.workload_metadata
+        };
+        if self.findings.duration_entries == 0 {
+            results.warnings.push(
+                "No statement durations were found; set log_min_duration_statement to log slow statements with their text."
+                    .to_string(),
+            );
+        }
+
+        self.findings.lock_waits = self
+            .lock_waits
+            .into_values()
+            .map(|wait| LockWaitFinding {
+                query_text: workload::format_query_text(&wait.query_text, opts),
+                ..wait
+            })
+            .collect();
+        self.findings.lock_waits.sort_by(|a, b| {
+            b.waits
+                .cmp(&a.waits)
+                .then(b.max_wait_ms.total_cmp(&a.max_wait_ms))
+        });
+        self.findings.lock_waits.truncate(opts.limit);
+        self.findings.autovacuum_runs = self.autovacuum.into_values().collect();
+        self.findings
+            .autovacuum_runs
+            .sort_by(|a, b| b.total_elapsed_secs.total_cmp(&a.total_elapsed_secs));
+        self.findings.autovacuum_runs.truncate(opts.limit);
+        self.findings.plans = self
+            .plans
+            .finish(opts.limit)
+            .into_iter()
+            .map(|plan| LoggedPlanFinding {
+                query_text: workload::format_query_text(&plan.query_text, opts),
+                ..plan
+            })
+            .collect();
+        if self.findings.plan_entries > 0 && self.findings.plans.is_empty() {
+            results.warnings.push(
+                "auto_explain plans were logged as text; set auto_explain.log_format = json to analyze them."
+                    .to_string(),
+            );
+        }
+        results.log_findings = Some(self.findings);
+        results
+    }
+}
+
+/// `1234.567 ms  statement: SELECT ...` or `... execute S_1: SELECT ...`.
+/// Parse and bind steps of the extended protocol are skipped so a call is
+/// counted once.
+fn parse_duration_line(rest: &str) -> Option<(f64, &str)> {
+    let (duration, rest) = rest.split_once(" ms")?;
+    let duration_ms = duration.trim().parse::<f64>().ok()?;
+    let rest = rest.trim_start();
+    let query = match rest.strip_prefix("statement: ") {
+        Some(query) => query,
+        None => rest.strip_prefix("execute ")?.split_once(": ")?.1,
+    };
+    Some((duration_ms, query.trim())).filter(|(_, query)| !query.is_empty())
+}
+
+/// `1234.567 ms  plan:` followed by the auto_explain output
+fn parse_plan_line(rest: &str) -> Option<(f64, &str)> {
+    let (duration, rest) = rest.split_once(" ms")?;
+    let duration_ms = duration.trim().parse::<f64>().ok()?;
+    let plan = rest.trim_start().strip_prefix("plan:")?;
+    Some((duration_ms, plan.trim()))
+}
+
+/// FNV-1a of the fingerprint, kept positive so it reads like a queryid
+fn fingerprint_id(fingerprint: &str) -> i64 {
+    let hash = fingerprint
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    (hash >> 1) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ByteSize, PlanRegressionKind, SlowQueryKind};
+
+    const STDERR_LOG: &str = "\
+2026-10-16 09:00:00.123 UTC [4211] app@shop LOG:  duration: 1200.500 ms  statement: SELECT * FROM orders
+\tWHERE customer_id = 42
+2026-10-16 09:00:01.000 UTC [4212] app@shop LOG:  duration: 800.000 ms  execute S_1: SELECT * FROM orders WHERE customer_id = $1
+2026-10-16 09:00:01.001 UTC [4212] app@shop LOG:  duration: 0.100 ms  parse S_1: SELECT * FROM orders WHERE customer_id = $1
+2026-10-16 09:00:02.000 UTC [4213] app@shop LOG:  temporary file: path \"base/pgsql_tmp/pgsql_tmp4213.0\", size 16384000
+2026-10-16 09:00:02.000 UTC [4213] app@shop STATEMENT:  SELECT * FROM orders ORDER BY total
+2026-10-16 09:00:03.000 UTC [4214] app@shop LOG:  process 4214 still waiting for ShareLock on transaction 991 after 1000.072 ms
+2026-10-16 09:00:03.000 UTC [4214] app@shop DETAIL:  Process holding the lock: 4215. Wait queue: 4214.
+2026-10-16 09:00:03.000 UTC [4214] app@shop STATEMENT:  UPDATE orders SET status = 'paid' WHERE id = 7
+2026-10-16 09:00:05.000 UTC [4214] app@shop LOG:  process 4214 acquired ShareLock on transaction 991 after 2503.110 ms
+2026-10-16 09:00:05.000 UTC [4214] app@shop STATEMENT:  UPDATE orders SET status = 'paid' WHERE id = 7
+2026-10-16 09:00:06.000 UTC [4300] @ LOG:  automatic vacuum of table \"shop.public.orders\": index scans: 1
+\tpages: 0 removed, 1200 remain, 0 skipped due to pins, 0 skipped frozen
+\tsystem usage: CPU: user: 0.20 s, system: 0.01 s, elapsed: 3.50 s
+2026-10-16 09:00:07.000 UTC [4300] @ LOG:  automatic analyze of table \"shop.public.orders\"
+\tsystem usage: CPU: user: 0.05 s, system: 0.00 s, elapsed: 0.50 s
+";
+
+    #[test]
+    fn stderr_entries_skip_the_prefix_and_join_continuation_lines() {
+        let entries = parse_stderr(STDERR_LOG.as_bytes()).unwrap();
+        assert_eq!(entries.len(), 8);
+        assert_eq!(
+            entries[0].message,
+            "duration: 1200.500 ms  statement: SELECT * FROM orders\nWHERE customer_id = 42"
+        );
+        assert_eq!(
+            entries[3].statement.as_deref(),
+            Some("SELECT * FROM orders ORDER BY total")
+        );
+        assert!(entries[6].message.ends_with("elapsed: 3.50 s"));
+    }
+
+    #[test]
+    fn logged_statements_become_workload_findings() {
+        let mut collector = LogCollector::default();
+        for entry in parse_stderr(STDERR_LOG.as_bytes()).unwrap() {
+            collector.add(&entry);
+        }
+        let opts = WorkloadOptions {
+            min_calls: 1,
+            ..WorkloadOptions::default()
+        };
+        let results = collector.finish(&opts);
+
+        let total = results
+            .slow_query_groups
+            .iter()
+            .find(|group| group.kind == SlowQueryKind::TotalTime)
+            .unwrap();
+        assert_eq!(total.queries[0].calls, 2);
+        assert!((total.queries[0].total_time_ms - 2000.5).abs() < 1e-9);
+        let temp = results
+            .slow_query_groups
+            .iter()
+            .find(|group| group.kind == SlowQueryKind::TempBlksWritten)
+            .unwrap();
+        assert_eq!(temp.queries[0].temp_blks_written, 2000);
+        assert!(results
+            .query_index_candidates
+            .iter()
+            .any(|candidate| candidate.table == "orders" && candidate.columns == ["customer_id"]));
+
+        let findings = results.log_findings.unwrap();
+        assert_eq!(findings.duration_entries, 2);
+        assert_eq!(findings.temp_bytes, ByteSize(16_384_000));
+        assert_eq!(findings.lock_waits.len(), 1);
+        assert_eq!(findings.lock_waits[0].lock_mode, "ShareLock");
+        assert_eq!(findings.lock_waits[0].waits, 1);
+        assert_eq!(findings.lock_waits[0].max_wait_ms, 2503.11);
+        assert_eq!(
+            findings.lock_waits[0].query_text,
+            "UPDATE orders SET status = 'paid' WHERE id = 7"
+        );
+        let orders = &findings.autovacuum_runs[0];
+        assert_eq!(orders.table, "shop.public.orders");
+        assert_eq!((orders.vacuums, orders.analyzes), (1, 1));
+        assert_eq!(orders.total_elapsed_secs, 4.0);
+        assert_eq!(results.workload_metadata.data_source, "server logs");
+    }
+
+    #[test]
+    fn csvlog_reads_user_database_and_query_columns() {
+        let csv = "\
+2026-10-16 09:00:00.123 UTC,\"app\",\"shop\",4211,\"10.0.0.5:5100\",abc.1,1,\"SELECT\",2026-10-16 08:00:00 UTC,3/14,0,LOG,00000,\"duration: 15.000 ms  statement: SELECT 1\",,,,,,,,,\"psql\",\"client backend\",,0
+2026-10-16 09:00:01.000 UTC,\"app\",\"shop\",4213,\"10.0.0.5:5101\",abc.2,1,\"SELECT\",2026-10-16 08:00:00 UTC,3/15,0,LOG,00000,\"temporary file: path \"\"base/pgsql_tmp/pgsql_tmp4213.0\"\", size 8192\",,,,,,\"SELECT * FROM big ORDER BY 1\",,,\"psql\",\"client backend\",,0
+";
+        let entries = parse_csvlog(csv.as_bytes()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].user.as_deref(), Some("app"));
+        assert_eq!(entries[0].database.as_deref(), Some("shop"));
+        assert_eq!(
+            entries[1].statement.as_deref(),
+            Some("SELECT * FROM big ORDER BY 1")
+        );
+    }
+
+    #[test]
+    fn auto_explain_plans_attach_to_the_logged_statement() {
+        let log = "\
+2026-10-16 09:00:00.000 UTC [4211] app@shop LOG:  duration: 950.000 ms  statement: SELECT * FROM orders ORDER BY total
+2026-10-16 09:00:00.000 UTC [4211] app@shop LOG:  duration: 949.000 ms  plan:
+\t{
+\t  \"Query Text\": \"SELECT * FROM orders ORDER BY total\",
+\t  \"Plan\": {
+\t    \"Node Type\": \"Sort\",
+\t    \"Actual Rows\": 90000,
+\t    \"Actual Loops\": 1,
+\t    \"Sort Key\": [\"total\"],
+\t    \"Sort Method\": \"external merge\",
+\t    \"Sort Space Used\": 2048,
+\t    \"Sort Space Type\": \"Disk\"
+\t  }
+\t}
+";
+        let mut collector = LogCollector::default();
+        for entry in parse_stderr(log.as_bytes()).unwrap() {
+            collector.add(&entry);
+        }
+        let results = collector.finish(&WorkloadOptions {
+            min_calls: 1,
+            ..WorkloadOptions::default()
+        });
+
+        let statement = &results.slow_query_groups[0].queries[0];
+        assert_eq!(statement.calls, 1);
+        let findings = results.log_findings.unwrap();
+        assert_eq!(findings.plan_entries, 1);
+        let plan = &findings.plans[0];
+        assert_eq!(plan.queryid, statement.queryid);
+        assert_eq!(plan.max_time_ms, 949.0);
+        assert_eq!(plan.regressions[0].kind, PlanRegressionKind::SortSpill);
+        assert!(results.warnings.is_empty(), "{:?}", results.warnings);
+    }
+
+    #[test]
+    fn duration_lines_without_statement_text_are_skipped() {
+        assert_eq!(
+            parse_duration_line("12.5 ms  statement: SELECT 1"),
+            Some((12.5, "SELECT 1"))
+        );
+        assert_eq!(
+            parse_duration_line("3.0 ms  execute <unnamed>: SELECT 2"),
+            Some((3.0, "SELECT 2"))
+        );
+        assert_eq!(parse_duration_line("0.1 ms  bind S_1: SELECT 3"), None);
+        assert_eq!(parse_duration_line("0.2 ms"), None);
+    }
+}
@@ -0,0 +1,392 @@
+use crate::analysis::validation::in_param_unit;
+use crate::models::{
+    AnalysisResults, ConfigCategory, ManagedPlatform, ManagedRemediation, PgConfigParam,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Parameters Azure flexible server manages itself; `parameter set` rejects them
+const AZURE_READ_ONLY: &[&str] = &[
+    "archive_command",
+    "archive_mode",
+    "config_file",
+    "data_directory",
+    "hba_file",
+    "ident_file",
+    "listen_addresses",
+    "port",
+    "restore_command",
+    "ssl_ca_file",
+    "ssl_cert_file",
+    "ssl_key_file",
+    "unix_socket_directories",
+];
+
+/// Parameters Cloud SQL does not expose as database flags
+const CLOUD_SQL_UNSUPPORTED: &[&str] = &[
+    "archive_command",
+    "archive_mode",
+    "data_directory",
+    "fsync",
+    "full_page_writes",
+    "huge_pages",
+    "listen_addresses",
+    "log_destination",
+    "log_directory",
+    "logging_collector",
+    "port",
+    "restore_command",
+    "shared_preload_libraries",
+    "ssl",
+    "unix_socket_directories",
+];
+
+/// Detects Azure flexible server and Cloud SQL from the `azure.*` and
+/// `cloudsql.*` settings their builds add
+pub fn detect_platform(params: &HashMap<String, PgConfigParam>) -> Option<ManagedPlatform> {
+    if params.keys().any(|name| name.starts_with("azure.")) {
+        Some(ManagedPlatform::AzureFlexibleServer)
+    } else if params.keys().any(|name| name.starts_with("cloudsql.")) {
+        Some(ManagedPlatform::CloudSql)
+    } else {
+        None
+    }
+}
+
+/// Translates the suggestions into `az postgres flexible-server parameter set`
+/// or `gcloud sql instances patch --database-flags` commands. Values are
+/// converted to the parameter's own unit, since neither CLI accepts `64MB`.
+/// Returns `None` when the server is not a recognised managed service or
+/// nothing is left to set.
+pub fn plan_managed_remediation(
+    params: &HashMap<String, PgConfigParam>,
+    results: &AnalysisResults,
+) -> Option<ManagedRemediation> {
+    let platform = detect_platform(params)?;
+
+    let mut seen = HashSet::new();
+    let mut flags = Vec::new();
+    let mut skipped = Vec::new();
+    let mut unconverted = Vec::new();
+    for (category, suggestion) in results.ranked_suggestions() {
+        // Minor and major upgrades go through the provider's maintenance, not a flag
+        if category == ConfigCategory::Version {
+            continue;
+        }
+        let Some(param) = params.get(&suggestion.parameter) else {
+            continue;
+        };
+        if !seen.insert(param.name.as_str()) {
+            continue;
+        }
+        match platform_flag(platform, param, &suggestion.suggested_value) {
+            Some(flag) => {
+                if flag.1 == suggestion.suggested_value && has_unit(&flag.1) {
+                    unconverted.push(param.name.clone());
+                }
+                flags.push((flag, param.context == "postmaster"));
+            }
+            None => skipped.push(param.name.clone()),
+        }
+    }
+    if flags.is_empty() && skipped.is_empty() {
+        return None;
+    }
+
+    let needs_restart = flags.iter().any(|(_, restart)| *restart);
+    let previous: Vec<(String, String)> = flags
+        .iter()
+        .filter_map(|((name, _), _)| previous_flag(params, name))
+        .collect();
+    let mut commands = Vec::new();
+    let mut rollback = Vec::new();
+    let mut notes = Vec::new();
+    match platform {
+        ManagedPlatform::AzureFlexibleServer => {
+            for ((name, value), _) in &flags {
+                commands.push(format!(
+                    "az postgres flexible-server parameter set --resource-group <resource-group> \
+                     --server-name <server-name> --name {name} --value {}",
+                    shell_quote(value)
+                ));
+            }
+            for (name, value) in &previous {
+                rollback.push(format!(
+                    "az postgres flexible-server parameter set --resource-group <resource-group> \
+                     --server-name <server-name> --name {name} --value {}",
+                    shell_quote(value)
+                ));
+            }
+            if needs_restart {
+                let restart =
+                    "az postgres flexible-server restart --resource-group <resource-group> \
+                     --name <server-name>";
+                commands.push(restart.to_string());
+                rollback.push(restart.to_string());
+                notes.push(
+                    "Static parameters only take effect after the restart at the end".to_string(),
+                );
+            }
+        }
+        ManagedPlatform::CloudSql => {
+            if !flags.is_empty() {
+                let flags: Vec<_> = flags.iter().map(|(flag, _)| flag.clone()).collect();
+                commands.push(gcloud_patch(&flags));
+                rollback.push(gcloud_patch(&previous));
+                notes.push(
+                    "--database-flags replaces every flag set on the instance; add the flags \
+                     already set there to the list before running it"
+                        .to_string(),
+                );
+            }
+            if needs_restart {
+                notes.push("Cloud SQL restarts the instance to apply static flags".to_string());
+            }
+        }
+    }
+
+    if !unconverted.is_empty() {
+        notes.push(format!(
+            "A SHOW ALL dump does not say which unit {} is stored in, so the values are \
+             left as suggested; convert them to the parameter's unit before running",
+            unconverted.join(", ")
+        ));
+    }
+
+    Some(ManagedRemediation {
+        platform,
+        commands,
+        rollback,
+        skipped,
+        notes,
+    })
+}
+
+/// The name and value to set on `platform`, or `None` when users cannot
+/// change the parameter there
+fn platform_flag(
+    platform: ManagedPlatform,
+    param: &PgConfigParam,
+    suggested: &str,
+) -> Option<(String, String)> {
+    if param.context == "internal" {
+        return None;
+    }
+    let name = param.name.as_str();
+    match platform {
+        ManagedPlatform::AzureFlexibleServer if AZURE_READ_ONLY.contains(&name) => return None,
+        // Cloud SQL turns on logical decoding with its own flag instead of wal_level
+        ManagedPlatform::CloudSql if name == "wal_level" => {
+            return (suggested == "logical")
+                .then(|| ("cloudsql.logical_decoding".to_string(), "on".to_string()));
+        }
+        ManagedPlatform::CloudSql if CLOUD_SQL_UNSUPPORTED.contains(&name) => return None,
+        _ => {}
+    }
+
+    // A SHOW ALL dump reports values in a display unit rather than the one
+    // pg_settings stores, so its unit cannot be converted to
+    let converted = if param.context.is_empty() {
+        None
+    } else {
+        in_param_unit(suggested, param)
+    };
+    let value = match converted {
+        Some(value) if value.fract() == 0.0 => format!("{value:.0}"),
+        Some(value) => value.to_string(),
+        None => suggested.to_string(),
+    };
+    Some((param.name.clone(), value))
+}
+
+/// The flag as it is set now, to roll a change back to. pg_settings already
+/// reports values in the parameter's unit; Cloud SQL's logical decoding flag
+/// stands in for `wal_level` and is off unless the change turned it on.
+fn previous_flag(params: &HashMap<String, PgConfigParam>, name: &str) -> Option<(String, String)> {
+    match params.get(name) {
+        Some(param) => Some((name.to_string(), param.current_value.clone())),
+        None if name == "cloudsql.logical_decoding" => Some((name.to_string(), "off".to_string())),
+        None => None,
+    }
+}
+
+/// One `gcloud sql instances patch` setting every flag in `flags`
+fn gcloud_patch(flags: &[(String, String)]) -> String {
+    let pairs: Vec<String> = flags
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect();
+    // gcloud splits the list on commas unless another delimiter is declared
+    let list = if pairs.iter().any(|pair| pair.contains(',')) {
+        format!("^|^{}", pairs.join("|"))
+    } else {
+        pairs.join(",")
+    };
+    format!(
+        "gcloud sql instances patch <instance> --database-flags={}",
+        shell_quote(&list)
+    )
+}
+
+/// Whether a value ends in a unit such as `MB` or `5min`
+fn has_unit(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_digit())
+        && value.ends_with(|c: char| c.is_ascii_alphabetic())
+}
+
+/// Single-quotes a value for a POSIX shell unless it is plainly safe
+fn shell_quote(value: &str) -> String {
+    let safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.,:=/+".contains(c));
+    if safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConfigCategory, ConfigSuggestion, ParamValue, SuggestionLevel};
+    use rstest::rstest;
+
+    fn param(name: &str, value: &str, unit: Option<&str>, context: &str) -> PgConfigParam {
+        PgConfigParam {
+            name: name.to_string(),
+            current_value: value.to_string(),
+            default_value: None,
+            unit: unit.map(str::to_string),
+            context: context.to_string(),
+            value: ParamValue::parse(value, "", unit),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        }
+    }
+
+    fn suggestion(parameter: &str, suggested_value: &str) -> ConfigSuggestion {
+        ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: String::new(),
+            suggested_value: suggested_value.to_string(),
+            level: SuggestionLevel::Important,
+            rationale: String::new(),
+            messages: Vec::new(),
+            fingerprint: String::new(),
+        }
+    }
+
+    fn plan(marker: &str) -> Option<ManagedRemediation> {
+        let params: HashMap<_, _> = [
+            param(marker, "on", None, "sighup"),
+            param("shared_buffers", "16384", Some("8kB"), "postmaster"),
+            param("work_mem", "4096", Some("kB"), "user"),
+            param("archive_mode", "on", None, "postmaster"),
+            param("wal_level", "replica", None, "postmaster"),
+        ]
+        .into_iter()
+        .map(|param| (param.name.clone(), param))
+        .collect();
+        let mut results = AnalysisResults::default();
+        results.suggestions_by_category.insert(
+            ConfigCategory::Memory,
+            vec![
+                suggestion("shared_buffers", "16GB"),
+                suggestion("work_mem", "64MB"),
+                suggestion("archive_mode", "off"),
+            ],
+        );
+        results.suggestions_by_category.insert(
+            ConfigCategory::Wal,
+            vec![suggestion("wal_level", "logical")],
+        );
+        plan_managed_remediation(&params, &results)
+    }
+
+    #[test]
+    fn azure_gets_one_parameter_set_per_flag_and_a_restart() {
+        let remediation = plan("azure.extensions").unwrap();
+        assert_eq!(remediation.platform, ManagedPlatform::AzureFlexibleServer);
+        assert!(remediation
+            .commands
+            .iter()
+            .any(|command| command.ends_with("--name shared_buffers --value 2097152")));
+        assert!(remediation
+            .commands
+            .iter()
+            .any(|command| command.ends_with("--name wal_level --value logical")));
+        assert!(remediation.commands.last().unwrap().contains("restart"));
+        assert_eq!(remediation.skipped, vec!["archive_mode"]);
+        assert!(remediation
+            .rollback
+            .iter()
+            .any(|command| command.ends_with("--name shared_buffers --value 16384")));
+        assert!(remediation
+            .rollback
+            .iter()
+            .any(|command| command.ends_with("--name wal_level --value replica")));
+        assert!(remediation.rollback.last().unwrap().contains("restart"));
+    }
+
+    #[test]
+    fn cloud_sql_gets_one_patch_with_every_flag() {
+        let remediation = plan("cloudsql.iam_authentication").unwrap();
+        assert_eq!(remediation.platform, ManagedPlatform::CloudSql);
+        assert_eq!(remediation.commands.len(), 1);
+        let command = &remediation.commands[0];
+        assert!(command.starts_with("gcloud sql instances patch <instance> --database-flags="));
+        for flag in [
+            "shared_buffers=2097152",
+            "work_mem=65536",
+            "cloudsql.logical_decoding=on",
+        ] {
+            assert!(command.contains(flag), "{command}");
+        }
+        assert_eq!(remediation.skipped, vec!["archive_mode"]);
+        let rollback = &remediation.rollback[0];
+        for flag in [
+            "shared_buffers=16384",
+            "work_mem=4096",
+            "cloudsql.logical_decoding=off",
+        ] {
+            assert!(rollback.contains(flag), "{rollback}");
+        }
+    }
+
+    #[test]
+    fn show_all_values_are_left_unconverted() {
+        let params: HashMap<_, _> = [
+            param("azure.extensions", "", None, ""),
+            param("work_mem", "4", Some("MB"), ""),
+        ]
+        .into_iter()
+        .map(|param| (param.name.clone(), param))
+        .collect();
+        let mut results = AnalysisResults::default();
+        results
+            .suggestions_by_category
+            .insert(ConfigCategory::Memory, vec![suggestion("work_mem", "64MB")]);
+
+        let remediation = plan_managed_remediation(&params, &results).unwrap();
+        assert!(remediation.commands[0].ends_with("--name work_mem --value 64MB"));
+        assert!(remediation.notes[0].contains("work_mem"));
+    }
+
+    #[test]
+    fn self_hosted_servers_get_no_provider_commands() {
+        assert!(plan("jit").is_none());
+    }
+
+    #[rstest]
+    #[case("65536", "65536")]
+    #[case("all", "all")]
+    #[case("%m [%p] ", "'%m [%p] '")]
+    #[case("^|^a=1|b=x,y", "'^|^a=1|b=x,y'")]
+    fn values_are_quoted_for_the_shell(#[case] value: &str, #[case] quoted: &str) {
+        assert_eq!(shell_quote(value), quoted);
+    }
+}
@@ -1,10 +1,34 @@
-use crate::analysis::{get_param, param_value_as_gigabytes, param_value_as_megabytes};
+use crate::analysis::concurrency::LARGE_SERVER_VCPU;
+use crate::analysis::sampling::format_window;
+use crate::analysis::{
+    get_param, param_value_as_bytes, param_value_as_gigabytes, param_value_as_megabytes,
+};
 use crate::checker::CheckerError;
+use crate::i18n::{Lang, Message};
 use crate::models::{AnalysisResults, ConfigCategory, ConfigSuggestion, SuggestionLevel};
+use crate::profile::ProfileThresholds;
 use std::collections::HashMap;
 
 type Result<T> = std::result::Result<T, CheckerError>;
 
+const MB: f64 = 1024.0 * 1024.0;
+const GIB: f64 = 1024.0 * MB;
+
+/// Temporary file volume per hour above which work_mem is worth raising even
+/// when it is within the profile's range
+const TEMP_SPILL_BYTES_PER_HOUR: f64 = GIB;
+
+/// PostgreSQL 15's default; 13 and 14 shipped with 1.0
+const HASH_MEM_MULTIPLIER: f64 = 2.0;
+/// Share of RAM that temp_buffers may claim if every connection fills it
+const TEMP_BUFFERS_RAM_SHARE: f64 = 0.25;
+/// Share of RAM that logical_decoding_work_mem may claim across all WAL senders
+const LOGICAL_DECODING_RAM_SHARE: f64 = 0.1;
+/// logical_decoding_work_mem suggested for logical replication on servers with
+/// at least [`LOGICAL_DECODING_MIN_RAM_GB`]
+const LOGICAL_DECODING_WORK_MEM_MB: u64 = 256;
+const LOGICAL_DECODING_MIN_RAM_GB: f64 = 32.0;
+
 /// Analyzes memory configuration parameters based on documentation
 pub fn analyze_memory(
     params: &HashMap<String, crate::models::PgConfigParam>,
@@ -16,6 +40,10 @@ pub fn analyze_memory(
     analyze_work_mem(params, stats, results)?;
     analyze_maintenance_work_mem(params, stats, results)?;
     analyze_wal_buffers(params, results)?;
+    analyze_huge_pages(params, stats, results)?;
+    analyze_hash_mem_multiplier(params, stats, results)?;
+    analyze_temp_buffers(params, stats, results)?;
+    analyze_logical_decoding_work_mem(params, stats, results)?;
 
     Ok(())
 }
@@ -49,11 +77,7 @@ fn analyze_shared_buffers(
                         &current_value,
                         &format!("{}MB", recommended_mb),
                         level,
-                        &format!(
-                            "shared_buffers should be ~25% of total RAM ({}GB). \
-                             This is the single most effective performance parameter.",
-                            spec.memory_gb
-                        ),
+                        Message::new("memory.shared_buffers").arg("memory_gb", spec.memory_gb),
                     );
                 }
             }
@@ -85,9 +109,7 @@ fn analyze_effective_cache_size(
                         &current_value,
                         &format!("{}GB", recommended_gb),
                         SuggestionLevel::Critical,
-                        "effective_cache_size is a cost model hint for the query planner. \
-                         Setting it to ~75% of RAM helps the planner choose index scans over \
-                         sequential scans. The default is dangerously low.",
+                        Message::new("memory.effective_cache_size"),
                     );
                 }
             }
@@ -104,16 +126,14 @@ fn analyze_work_mem(
 ) -> Result<()> {
     let current_value = param_value_string(params, "work_mem");
 
-    let recommended_mb = match stats.total_memory_gb {
-        Some(mem) if mem <= 16.0 => 32,
-        Some(mem) if mem <= 64.0 => 64,
-        Some(mem) if mem > 64.0 => 64,
-        _ => 64,
-    };
+    let thresholds = stats.profile().thresholds();
+    let recommended_mb =
+        ProfileThresholds::memory_tier(thresholds.work_mem_mb, stats.total_memory_gb);
+    let ceiling_mb = thresholds.work_mem_ceiling_mb;
 
     if let Some(param) = get_param(params, "work_mem") {
         if let Some(current_mb) = param_value_as_megabytes(param) {
-            if current_mb > 512 {
+            if current_mb > ceiling_mb {
                 add_suggestion(
                     results,
                     ConfigCategory::Memory,
@@ -121,15 +141,17 @@ fn analyze_work_mem(
                     &current_value,
                     &format!("{}MB", recommended_mb),
                     SuggestionLevel::Critical,
-                    &format!(
-                        "work_mem is dangerously high at {}MB. This setting is multiplied by \
-                         the number of concurrent operations and connections. A high work_mem \
-                         with many connections is the most common cause of OOM errors. \
-                         For OLTP workloads, 16-64MB is recommended.",
-                        current_mb
-                    ),
+                    Message::new("memory.work_mem.too_high")
+                        .arg("current_mb", current_mb)
+                        .arg("recommended_mb", recommended_mb),
                 );
             } else if current_mb < (recommended_mb as f64 * 0.5) as u64 {
+                let rationale = match stats.profile().workload_type() {
+                    crate::config::WorkloadType::Oltp => Message::new("memory.work_mem.too_low"),
+                    crate::config::WorkloadType::Olap => {
+                        Message::new("memory.work_mem.too_low_analytics")
+                    }
+                };
                 add_suggestion(
                     results,
                     ConfigCategory::Memory,
@@ -137,9 +159,20 @@ fn analyze_work_mem(
                     &current_value,
                     &format!("{}MB", recommended_mb),
                     SuggestionLevel::Important,
-                    "work_mem is too low, which may cause sorts and hash joins to spill to disk. \
-                     Use EXPLAIN (ANALYZE) to check for 'external merge Disk' or 'spill' messages.",
+                    rationale,
                 );
+            } else if current_mb < recommended_mb {
+                if let Some(spills) = temp_spills(stats) {
+                    add_suggestion(
+                        results,
+                        ConfigCategory::Memory,
+                        "work_mem",
+                        &current_value,
+                        &format!("{}MB", recommended_mb),
+                        SuggestionLevel::Recommended,
+                        spills.arg("recommended_mb", recommended_mb),
+                    );
+                }
             }
         }
     }
@@ -147,6 +180,22 @@ fn analyze_work_mem(
     Ok(())
 }
 
+/// Message for a temporary file volume above [`TEMP_SPILL_BYTES_PER_HOUR`] over the
+/// `--sample-window`, or over the statistics window without one
+fn temp_spills(stats: &crate::models::SystemStats) -> Option<Message> {
+    let (files, bytes) = stats.temp_files.zip(stats.temp_bytes)?;
+    let window = stats.sample_window_secs.or(stats.stats_age_secs)?;
+    if window == 0 || bytes as f64 / (window as f64 / 3600.0) < TEMP_SPILL_BYTES_PER_HOUR {
+        return None;
+    }
+    Some(
+        Message::new("memory.work_mem.temp_spills")
+            .arg("temp_gb", format!("{:.1}", bytes as f64 / GIB))
+            .arg("temp_files", files)
+            .arg("window", format_window(window)),
+    )
+}
+
 fn analyze_maintenance_work_mem(
     params: &HashMap<String, crate::models::PgConfigParam>,
     stats: &crate::models::SystemStats,
@@ -170,11 +219,10 @@ fn analyze_maintenance_work_mem(
                         &current_value,
                         &format!("{}MB", recommended_mb),
                         SuggestionLevel::Recommended,
-                        &format!(
-                            "maintenance_work_mem should be set to ~{}MB for your system ({}, {}GB RAM). \
-                             A larger value can dramatically speed up index creation and vacuuming.",
-                            recommended_mb, spec.vcpu, spec.memory_gb
-                        ),
+                        Message::new("memory.maintenance_work_mem")
+                            .arg("recommended_mb", recommended_mb)
+                            .arg("vcpu", spec.vcpu)
+                            .arg("memory_gb", spec.memory_gb),
                     );
                 }
             }
@@ -201,9 +249,7 @@ fn analyze_wal_buffers(
                         &current_value,
                         "16MB",
                         SuggestionLevel::Recommended,
-                        "wal_buffers should be set to 16MB for high-write workloads. \
-                         The default -1 (auto-sized) is usually adequate, but a fixed 16MB \
-                         value can improve write performance.",
+                        Message::new("memory.wal_buffers"),
                     );
                 }
             }
@@ -213,6 +259,192 @@ fn analyze_wal_buffers(
     Ok(())
 }
 
+/// On large servers, hundreds of backends each mapping shared_buffers in 4kB
+/// pages spend gigabytes on page tables, so huge pages should be required
+/// rather than tried
+fn analyze_huge_pages(
+    params: &HashMap<String, crate::models::PgConfigParam>,
+    stats: &crate::models::SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    let Some(cpu) = stats.cpu_count.filter(|cpu| *cpu > LARGE_SERVER_VCPU) else {
+        return Ok(());
+    };
+    let current_value = param_value_string(params, "huge_pages");
+    if get_param(params, "huge_pages").is_none() || current_value == "on" {
+        return Ok(());
+    }
+    let pages = get_param(params, "shared_memory_size_in_huge_pages")
+        .map(|param| param.current_value.clone())
+        .filter(|pages| pages != "-1")
+        .unwrap_or_else(|| "`postgres -C shared_memory_size_in_huge_pages`".to_string());
+    add_suggestion(
+        results,
+        ConfigCategory::Memory,
+        "huge_pages",
+        &current_value,
+        "on",
+        SuggestionLevel::Recommended,
+        Message::new("memory.huge_pages")
+            .arg("current", &current_value)
+            .arg("vcpu", cpu)
+            .arg("pages", pages),
+    );
+
+    Ok(())
+}
+
+/// Hash joins and hash aggregates may use work_mem × hash_mem_multiplier
+/// (PG13+). Below 2.0 they spill long before sorts do; well above it every
+/// hash node of every connection may claim a multiple of the work_mem ceiling.
+fn analyze_hash_mem_multiplier(
+    params: &HashMap<String, crate::models::PgConfigParam>,
+    stats: &crate::models::SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    let Some(param) = get_param(params, "hash_mem_multiplier") else {
+        return Ok(());
+    };
+    let Some(multiplier) = param.value.as_f64() else {
+        return Ok(());
+    };
+
+    if multiplier < HASH_MEM_MULTIPLIER {
+        add_suggestion(
+            results,
+            ConfigCategory::Memory,
+            "hash_mem_multiplier",
+            &param.current_value,
+            "2.0",
+            SuggestionLevel::Recommended,
+            Message::new("memory.hash_mem_multiplier.too_low").arg("current", multiplier),
+        );
+        return Ok(());
+    }
+
+    let Some(work_mem) = get_param(params, "work_mem").and_then(param_value_as_bytes) else {
+        return Ok(());
+    };
+    let ceiling_mb = stats.profile().thresholds().work_mem_ceiling_mb;
+    let hash_mb = (work_mem as f64 * multiplier / MB) as u64;
+    if multiplier > HASH_MEM_MULTIPLIER && hash_mb as f64 > ceiling_mb as f64 * HASH_MEM_MULTIPLIER
+    {
+        add_suggestion(
+            results,
+            ConfigCategory::Memory,
+            "hash_mem_multiplier",
+            &param.current_value,
+            "2.0",
+            SuggestionLevel::Important,
+            Message::new("memory.hash_mem_multiplier.too_high")
+                .arg("current", multiplier)
+                .arg("hash_mb", hash_mb),
+        );
+    }
+
+    Ok(())
+}
+
+/// temp_buffers is allocated per session as temporary tables are touched and
+/// kept until the session ends, so pooled connections can hold it indefinitely
+fn analyze_temp_buffers(
+    params: &HashMap<String, crate::models::PgConfigParam>,
+    stats: &crate::models::SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    let (Some(param), Some(memory_gb)) = (get_param(params, "temp_buffers"), stats.total_memory_gb)
+    else {
+        return Ok(());
+    };
+    let (Some(temp_buffers), Some(max_connections)) = (
+        param_value_as_bytes(param),
+        get_param(params, "max_connections").and_then(|param| param.value.as_count()),
+    ) else {
+        return Ok(());
+    };
+
+    let budget = memory_gb * GIB * TEMP_BUFFERS_RAM_SHARE;
+    let total = temp_buffers as f64 * max_connections as f64;
+    if total > budget {
+        let recommended_mb = ((budget / max_connections.max(1) as f64 / MB) as u64).max(8);
+        add_suggestion(
+            results,
+            ConfigCategory::Memory,
+            "temp_buffers",
+            &param.current_value,
+            &format!("{recommended_mb}MB"),
+            SuggestionLevel::Important,
+            Message::new("memory.temp_buffers")
+                .arg("max_connections", max_connections)
+                .arg("total_gb", format!("{:.1}", total / GIB))
+                .arg("memory_gb", memory_gb),
+        );
+    }
+
+    Ok(())
+}
+
+/// Every WAL sender decoding a logical slot buffers changes up to
+/// logical_decoding_work_mem before spilling them to pg_replslot (PG13+)
+fn analyze_logical_decoding_work_mem(
+    params: &HashMap<String, crate::models::PgConfigParam>,
+    stats: &crate::models::SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    let (Some(param), Some(memory_gb)) = (
+        get_param(params, "logical_decoding_work_mem"),
+        stats.total_memory_gb,
+    ) else {
+        return Ok(());
+    };
+    let Some(current) = param_value_as_bytes(param) else {
+        return Ok(());
+    };
+    let wal_senders = get_param(params, "max_wal_senders")
+        .and_then(|param| param.value.as_count())
+        .unwrap_or(10);
+
+    let budget = memory_gb * GIB * LOGICAL_DECODING_RAM_SHARE;
+    let total = current as f64 * wal_senders as f64;
+    if total > budget {
+        let recommended_mb = ((budget / wal_senders.max(1) as f64 / MB) as u64).max(64);
+        add_suggestion(
+            results,
+            ConfigCategory::Memory,
+            "logical_decoding_work_mem",
+            &param.current_value,
+            &format!("{recommended_mb}MB"),
+            SuggestionLevel::Important,
+            Message::new("memory.logical_decoding_work_mem.too_high")
+                .arg("wal_senders", wal_senders)
+                .arg("total_gb", format!("{:.1}", total / GIB))
+                .arg("memory_gb", memory_gb),
+        );
+        return Ok(());
+    }
+
+    let logical = param_value_string(params, "wal_level") == "logical";
+    let recommended = LOGICAL_DECODING_WORK_MEM_MB as f64 * MB;
+    if logical
+        && memory_gb >= LOGICAL_DECODING_MIN_RAM_GB
+        && (current as f64) < recommended
+        && recommended * wal_senders as f64 <= budget
+    {
+        add_suggestion(
+            results,
+            ConfigCategory::Memory,
+            "logical_decoding_work_mem",
+            &param.current_value,
+            &format!("{LOGICAL_DECODING_WORK_MEM_MB}MB"),
+            SuggestionLevel::Recommended,
+            Message::new("memory.logical_decoding_work_mem.too_low")
+                .arg("recommended_mb", LOGICAL_DECODING_WORK_MEM_MB),
+        );
+    }
+
+    Ok(())
+}
+
 fn get_compute_spec(stats: &crate::models::SystemStats) -> Option<crate::config::ComputeSpec> {
     match (stats.cpu_count, stats.total_memory_gb) {
         (Some(cpu), Some(mem)) => Some(crate::config::ComputeSpec {
@@ -240,14 +472,16 @@ fn add_suggestion(
     current_value: &str,
     suggested_value: &str,
     level: SuggestionLevel,
-    rationale: &str,
+    rationale: Message,
 ) {
     let suggestion = ConfigSuggestion {
         parameter: parameter.to_string(),
         current_value: current_value.to_string(),
         suggested_value: suggested_value.to_string(),
         level,
-        rationale: rationale.to_string(),
+        rationale: rationale.render(Lang::En),
+        messages: vec![rationale],
+        fingerprint: String::new(),
     };
 
     results
@@ -260,7 +494,8 @@ fn add_suggestion(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{PgConfigParam, SystemStats};
+    use crate::models::{ParamValue, PgConfigParam, SystemStats};
+    use rstest::rstest;
 
     fn create_param(value: &str) -> PgConfigParam {
         PgConfigParam {
@@ -269,6 +504,12 @@ mod tests {
             default_value: None,
             unit: Some("MB".to_string()),
             context: "user".to_string(),
+            value: ParamValue::parse(value, "integer", Some("MB")),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
         }
     }
 
@@ -309,4 +550,209 @@ mod tests {
         // 64GB * 0.25 = 16GB = 16384MB, but capped at 8192MB
         assert_eq!(suggestion.suggested_value, "8192MB");
     }
+
+    #[test]
+    fn test_huge_pages_required_on_large_servers() {
+        let mut params = HashMap::new();
+        params.insert("huge_pages".to_string(), create_param("try"));
+        params.insert(
+            "shared_memory_size_in_huge_pages".to_string(),
+            create_param("16898"),
+        );
+
+        let mut stats = SystemStats {
+            cpu_count: Some(32),
+            ..Default::default()
+        };
+        let mut results = AnalysisResults::default();
+        analyze_huge_pages(&params, &stats, &mut results).unwrap();
+        assert!(results.suggestions_by_category.is_empty());
+
+        stats.cpu_count = Some(64);
+        analyze_huge_pages(&params, &stats, &mut results).unwrap();
+        let suggestion = &results.suggestions_by_category[&ConfigCategory::Memory][0];
+        assert_eq!(suggestion.suggested_value, "on");
+        assert!(suggestion.rationale.contains("vm.nr_hugepages = 16898"));
+    }
+
+    #[test]
+    fn test_work_mem_olap_targets_higher() {
+        let mut params = HashMap::new();
+        params.insert("work_mem".to_string(), create_param("64"));
+
+        let stats = SystemStats {
+            total_memory_gb: Some(64.0),
+            cpu_count: Some(8),
+            workload_type: crate::config::WorkloadType::Olap,
+            ..Default::default()
+        };
+
+        let mut results = AnalysisResults::default();
+        analyze_work_mem(&params, &stats, &mut results).unwrap();
+
+        let suggestion = &results.suggestions_by_category[&ConfigCategory::Memory][0];
+        assert_eq!(suggestion.suggested_value, "256MB");
+        assert_eq!(suggestion.level, SuggestionLevel::Important);
+    }
+
+    #[test]
+    fn test_work_mem_raised_for_temp_spills_in_the_window() {
+        let mut params = HashMap::new();
+        params.insert("work_mem".to_string(), create_param("48"));
+
+        let mut stats = SystemStats {
+            total_memory_gb: Some(64.0),
+            temp_files: Some(120),
+            temp_bytes: Some(1024 * 1024),
+            sample_window_secs: Some(600),
+            stats_age_secs: Some(30 * 24 * 3600),
+            ..Default::default()
+        };
+        let mut results = AnalysisResults::default();
+        analyze_work_mem(&params, &stats, &mut results).unwrap();
+        assert!(results.suggestions_by_category.is_empty());
+
+        stats.temp_bytes = Some(3 * 1024 * 1024 * 1024);
+        analyze_work_mem(&params, &stats, &mut results).unwrap();
+        let suggestion = &results.suggestions_by_category[&ConfigCategory::Memory][0];
+        assert_eq!(suggestion.suggested_value, "64MB");
+        assert_eq!(suggestion.level, SuggestionLevel::Recommended);
+        assert!(suggestion
+            .rationale
+            .contains("wrote 3.0GB to 120 temporary files in the last 10m"));
+    }
+
+    #[test]
+    fn test_work_mem_olap_allows_large_values() {
+        let mut params = HashMap::new();
+        params.insert("work_mem".to_string(), create_param("1024"));
+
+        let stats = SystemStats {
+            total_memory_gb: Some(256.0),
+            workload_type: crate::config::WorkloadType::Olap,
+            ..Default::default()
+        };
+
+        let mut results = AnalysisResults::default();
+        analyze_work_mem(&params, &stats, &mut results).unwrap();
+
+        assert!(results.suggestions_by_category.is_empty());
+    }
+
+    fn typed_param(value: &str, vartype: &str, unit: Option<&str>) -> PgConfigParam {
+        PgConfigParam {
+            unit: unit.map(str::to_string),
+            value: ParamValue::parse(value, vartype, unit),
+            ..create_param(value)
+        }
+    }
+
+    fn memory_suggestion(
+        results: &AnalysisResults,
+        parameter: &str,
+    ) -> Option<(String, SuggestionLevel)> {
+        results
+            .suggestions_by_category
+            .get(&ConfigCategory::Memory)?
+            .iter()
+            .find(|suggestion| suggestion.parameter == parameter)
+            .map(|suggestion| (suggestion.suggested_value.clone(), suggestion.level))
+    }
+
+    #[rstest]
+    #[case("1", "64", Some(("2.0", SuggestionLevel::Recommended)))]
+    #[case("2", "64", None)]
+    #[case("8", "64", None)]
+    #[case("8", "512", Some(("2.0", SuggestionLevel::Important)))]
+    #[case("1.5", "512", Some(("2.0", SuggestionLevel::Recommended)))]
+    fn test_hash_mem_multiplier(
+        #[case] multiplier: &str,
+        #[case] work_mem_mb: &str,
+        #[case] expected: Option<(&str, SuggestionLevel)>,
+    ) {
+        let params = HashMap::from([
+            (
+                "hash_mem_multiplier".to_string(),
+                typed_param(multiplier, "real", None),
+            ),
+            ("work_mem".to_string(), create_param(work_mem_mb)),
+        ]);
+        let stats = SystemStats::default();
+        let mut results = AnalysisResults::default();
+        analyze_hash_mem_multiplier(&params, &stats, &mut results).unwrap();
+
+        assert_eq!(
+            memory_suggestion(&results, "hash_mem_multiplier"),
+            expected.map(|(value, level)| (value.to_string(), level))
+        );
+    }
+
+    #[rstest]
+    #[case("1024", "100", 16.0, None)]
+    #[case("65536", "1000", 64.0, Some("16MB"))]
+    #[case("131072", "100", 512.0, None)]
+    fn test_temp_buffers_across_max_connections(
+        #[case] temp_buffers_blocks: &str,
+        #[case] max_connections: &str,
+        #[case] memory_gb: f64,
+        #[case] expected: Option<&str>,
+    ) {
+        let params = HashMap::from([
+            (
+                "temp_buffers".to_string(),
+                typed_param(temp_buffers_blocks, "integer", Some("8kB")),
+            ),
+            (
+                "max_connections".to_string(),
+                typed_param(max_connections, "integer", None),
+            ),
+        ]);
+        let stats = SystemStats {
+            total_memory_gb: Some(memory_gb),
+            ..Default::default()
+        };
+        let mut results = AnalysisResults::default();
+        analyze_temp_buffers(&params, &stats, &mut results).unwrap();
+        assert_eq!(
+            memory_suggestion(&results, "temp_buffers").map(|(value, _)| value),
+            expected.map(str::to_string)
+        );
+    }
+
+    #[rstest]
+    #[case("65536", "logical", 64.0, Some(("256MB", SuggestionLevel::Recommended)))]
+    #[case("65536", "replica", 64.0, None)]
+    #[case("65536", "logical", 16.0, None)]
+    #[case("2097152", "logical", 64.0, Some(("655MB", SuggestionLevel::Important)))]
+    fn test_logical_decoding_work_mem(
+        #[case] work_mem_kb: &str,
+        #[case] wal_level: &str,
+        #[case] memory_gb: f64,
+        #[case] expected: Option<(&str, SuggestionLevel)>,
+    ) {
+        let params = HashMap::from([
+            (
+                "logical_decoding_work_mem".to_string(),
+                typed_param(work_mem_kb, "integer", Some("kB")),
+            ),
+            (
+                "wal_level".to_string(),
+                typed_param(wal_level, "enum", None),
+            ),
+            (
+                "max_wal_senders".to_string(),
+                typed_param("10", "integer", None),
+            ),
+        ]);
+        let stats = SystemStats {
+            total_memory_gb: Some(memory_gb),
+            ..Default::default()
+        };
+        let mut results = AnalysisResults::default();
+        analyze_logical_decoding_work_mem(&params, &stats, &mut results).unwrap();
+        assert_eq!(
+            memory_suggestion(&results, "logical_decoding_work_mem"),
+            expected.map(|(value, level)| (value.to_string(), level))
+        );
+    }
 }
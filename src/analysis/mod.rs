@@ -1,15 +1,48 @@
+pub mod auto_explain;
 pub mod autovacuum;
+pub mod backups;
+pub mod catalog_cache;
+pub mod checkpoints;
 pub mod concurrency;
+pub mod connection_history;
+pub mod connection_memory;
+pub mod cursors;
+pub mod extended_stats;
+pub mod extensions;
+pub mod host_layout;
+pub mod integrity;
+pub mod log_volume;
 pub mod logging;
+pub mod logs;
+pub mod managed;
 pub mod memory;
+pub mod os_limits;
+pub(crate) mod partial_indexes;
+pub mod pending_changes;
+pub mod pgaudit;
+pub mod pgvector;
+pub mod pitr;
 pub mod planner;
+pub mod reconcile;
+pub mod replicas;
+pub mod resilience;
+pub mod sampling;
+pub mod schema_lint;
+pub mod security;
 pub mod table_index;
+pub mod temp_schemas;
+pub mod tenants;
+pub mod toast;
+pub mod vacuum_simulation;
+pub mod validation;
+pub mod version;
 pub mod wal;
 pub mod workload;
 
-use crate::models::PgConfigParam;
+use crate::models::{ParamValue, PgConfigParam};
 use std::collections::HashMap;
 
+pub(crate) mod query_fingerprint;
 pub(crate) mod query_parser;
 
 pub(crate) fn get_param<'a>(
@@ -20,18 +53,7 @@ pub(crate) fn get_param<'a>(
 }
 
 pub(crate) fn param_value_as_bytes(param: &PgConfigParam) -> Option<u64> {
-    let value = parse_numeric(&param.current_value)?;
-    let multiplier = match param.unit.as_deref().map(|u| u.to_ascii_lowercase()) {
-        Some(ref unit) if unit == "8kb" => 8.0 * 1024.0,
-        Some(ref unit) if unit == "kb" => 1024.0,
-        Some(ref unit) if unit == "mb" => 1024.0 * 1024.0,
-        Some(ref unit) if unit == "gb" => 1024.0 * 1024.0 * 1024.0,
-        Some(ref unit) if unit == "b" => 1.0,
-        // Units such as "blocks" or empty are treated as already byte-aligned
-        _ => 1.0,
-    };
-
-    Some((value * multiplier) as u64)
+    param.value.as_bytes()
 }
 
 pub(crate) fn param_value_as_megabytes(param: &PgConfigParam) -> Option<u64> {
@@ -43,19 +65,13 @@ pub(crate) fn param_value_as_gigabytes(param: &PgConfigParam) -> Option<u64> {
 }
 
 pub(crate) fn param_value_as_seconds(param: &PgConfigParam) -> Option<u64> {
-    let value = parse_numeric(&param.current_value)?;
-    let seconds = match param.unit.as_deref().map(|u| u.to_ascii_lowercase()) {
-        Some(ref unit) if unit == "ms" => value / 1000.0,
-        Some(ref unit) if unit == "s" => value,
-        Some(ref unit) if unit == "min" => value * 60.0,
-        Some(ref unit) if unit == "h" => value * 3600.0,
-        Some(ref unit) if unit == "d" => value * 86400.0,
-        _ => value,
-    };
-
-    Some(seconds as u64)
+    param.value.as_duration().map(|duration| duration.as_secs())
 }
 
-fn parse_numeric(value: &str) -> Option<f64> {
-    value.trim().parse::<f64>().ok()
+/// The typed value of a parameter, if the server reported it
+pub(crate) fn param_value<'a>(
+    params: &'a HashMap<String, PgConfigParam>,
+    name: &str,
+) -> Option<&'a ParamValue> {
+    params.get(name).map(|param| &param.value)
 }
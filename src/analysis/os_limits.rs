@@ -0,0 +1,496 @@
+use crate::analysis::param_value;
+use crate::checker::CheckerError;
+use crate::i18n::{Lang, Message};
+use crate::models::{AnalysisResults, ConfigCategory, ConfigSuggestion, SuggestionLevel};
+use sqlx::{Pool, Postgres};
+use std::fs;
+use std::path::Path;
+
+const PROC_OVERCOMMIT: &str = "/proc/sys/vm/overcommit_memory";
+const PROC_SWAPPINESS: &str = "/proc/sys/vm/swappiness";
+const SYS_TRANSPARENT_HUGEPAGE: &str = "/sys/kernel/mm/transparent_hugepage/enabled";
+const SYS_NUMA_NODES: &str = "/sys/devices/system/node";
+const PROC_ZONE_RECLAIM: &str = "/proc/sys/vm/zone_reclaim_mode";
+const PROC_NUMA_BALANCING: &str = "/proc/sys/kernel/numa_balancing";
+/// Drop-in file the sysctl commands write to
+const SYSCTL_FILE: &str = "/etc/sysctl.d/90-postgresql.conf";
+
+/// `vm.overcommit_memory` mode that refuses allocations instead of letting the
+/// OOM killer pick a process, possibly the postmaster
+const STRICT_OVERCOMMIT: u32 = 2;
+/// Above this the kernel swaps out shared_buffers and backend memory rather
+/// than dropping page cache
+const MAX_SWAPPINESS: u32 = 10;
+
+/// Kernel settings and resource limits of the running server, read from
+/// `/proc` and `/sys` by [`OsLimits::inspect`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OsLimits {
+    /// Soft open-file limit of a backend, inherited from the postmaster;
+    /// `None` when unlimited
+    pub open_files: Option<u64>,
+    /// Service the postmaster runs under, e.g. `postgresql@17-main.service`
+    pub systemd_unit: Option<String>,
+    pub overcommit_memory: Option<u32>,
+    pub swappiness: Option<u32>,
+    /// The selected `transparent_hugepage/enabled` mode: always, madvise or never
+    pub transparent_hugepage: Option<String>,
+    /// Online NUMA nodes; `None` when the kernel does not expose them
+    pub numa_nodes: Option<usize>,
+    pub zone_reclaim_mode: Option<u32>,
+    pub numa_balancing: Option<u32>,
+}
+
+pub(crate) const BACKEND_PID_QUERY: &str = "SELECT pg_backend_pid()";
+
+/// PID of the backend serving this connection; it shares the postmaster's limits
+pub async fn fetch_backend_pid(pool: &Pool<Postgres>) -> Result<u32, CheckerError> {
+    let pid: i32 = sqlx::query_scalar(BACKEND_PID_QUERY)
+        .fetch_one(pool)
+        .await
+        .map_err(|source| CheckerError::QueryError {
+            query: BACKEND_PID_QUERY.into(),
+            source,
+        })?;
+    Ok(pid.unsigned_abs())
+}
+
+impl OsLimits {
+    /// Reads the limits of `backend_pid`. Fails when that process is not
+    /// visible, i.e. PostgreSQL runs on another host or in another container,
+    /// or when the local process with that PID is not this server's backend.
+    pub fn inspect(backend_pid: u32, data_directory: Option<&str>) -> Result<Self, String> {
+        let proc_dir = format!("/proc/{backend_pid}");
+        let limits = fs::read_to_string(format!("{proc_dir}/limits"))
+            .map_err(|_| format!("server process {backend_pid} is not visible from this host"))?;
+        verify_server_process(Path::new(&proc_dir), backend_pid, data_directory)?;
+        let read_number = |path: &str| {
+            fs::read_to_string(path)
+                .ok()
+                .and_then(|value| value.trim().parse().ok())
+        };
+        Ok(Self {
+            open_files: parse_open_files(&limits),
+            systemd_unit: fs::read_to_string(format!("/proc/{backend_pid}/cgroup"))
+                .ok()
+                .and_then(|cgroup| parse_systemd_unit(&cgroup)),
+            overcommit_memory: read_number(PROC_OVERCOMMIT),
+            swappiness: read_number(PROC_SWAPPINESS),
+            transparent_hugepage: fs::read_to_string(SYS_TRANSPARENT_HUGEPAGE)
+                .ok()
+                .and_then(|modes| parse_selected(&modes)),
+            numa_nodes: fs::read_dir(SYS_NUMA_NODES).ok().map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| is_node_dir(&entry.file_name().to_string_lossy()))
+                    .count()
+            }),
+            zone_reclaim_mode: read_number(PROC_ZONE_RECLAIM),
+            numa_balancing: read_number(PROC_NUMA_BALANCING),
+        })
+    }
+}
+
+/// Compares the server's open-file limit with `max_connections +
+/// max_files_per_process` and checks overcommit, swappiness, transparent huge
+/// pages and, on multi-node hosts, NUMA reclaim and balancing, collecting the commands that fix them in `results.host_commands`
+pub fn analyze_os_limits(limits: &OsLimits, results: &mut AnalysisResults) {
+    let params = &results.params;
+    let needed_files = param_value(params, "max_connections")
+        .and_then(|value| value.as_i64())
+        .zip(param_value(params, "max_files_per_process").and_then(|value| value.as_i64()))
+        .map(|(connections, files)| (connections + files).unsigned_abs());
+    if let (Some(open_files), Some(needed)) = (limits.open_files, needed_files) {
+        if open_files < needed {
+            let suggested = needed.next_power_of_two().max(65_536);
+            add_suggestion(
+                results,
+                "LimitNOFILE",
+                &open_files.to_string(),
+                &suggested.to_string(),
+                SuggestionLevel::Important,
+                Message::new("operating_system.open_files")
+                    .arg("current", open_files)
+                    .arg("needed", needed),
+            );
+            results.host_commands.extend(open_files_commands(
+                limits.systemd_unit.as_deref(),
+                suggested,
+            ));
+        }
+    }
+
+    if let Some(mode) = limits
+        .overcommit_memory
+        .filter(|mode| *mode != STRICT_OVERCOMMIT)
+    {
+        add_suggestion(
+            results,
+            "vm.overcommit_memory",
+            &mode.to_string(),
+            &STRICT_OVERCOMMIT.to_string(),
+            SuggestionLevel::Recommended,
+            Message::new("operating_system.overcommit_memory").arg("current", mode),
+        );
+        results
+            .host_commands
+            .extend(sysctl_commands("vm.overcommit_memory", STRICT_OVERCOMMIT));
+    }
+
+    if let Some(swappiness) = limits.swappiness.filter(|value| *value > MAX_SWAPPINESS) {
+        add_suggestion(
+            results,
+            "vm.swappiness",
+            &swappiness.to_string(),
+            "1",
+            SuggestionLevel::Recommended,
+            Message::new("operating_system.swappiness").arg("current", swappiness),
+        );
+        results
+            .host_commands
+            .extend(sysctl_commands("vm.swappiness", 1));
+    }
+
+    if limits.transparent_hugepage.as_deref() == Some("always") {
+        add_suggestion(
+            results,
+            "transparent_hugepage",
+            "always",
+            "never",
+            SuggestionLevel::Recommended,
+            Message::new("operating_system.transparent_hugepage"),
+        );
+        results
+            .host_commands
+            .push(format!("echo never | sudo tee {SYS_TRANSPARENT_HUGEPAGE}"));
+    }
+
+    let Some(nodes) = limits.numa_nodes.filter(|nodes| *nodes > 1) else {
+        return;
+    };
+    if let Some(mode) = limits.zone_reclaim_mode.filter(|mode| *mode != 0) {
+        add_suggestion(
+            results,
+            "vm.zone_reclaim_mode",
+            &mode.to_string(),
+            "0",
+            SuggestionLevel::Important,
+            Message::new("operating_system.zone_reclaim_mode")
+                .arg("current", mode)
+                .arg("nodes", nodes),
+        );
+        results
+            .host_commands
+            .extend(sysctl_commands("vm.zone_reclaim_mode", 0));
+    }
+    if limits.numa_balancing == Some(1) {
+        add_suggestion(
+            results,
+            "kernel.numa_balancing",
+            "1",
+            "0",
+            SuggestionLevel::Recommended,
+            Message::new("operating_system.numa_balancing").arg("nodes", nodes),
+        );
+        results
+            .host_commands
+            .extend(sysctl_commands("kernel.numa_balancing", 0));
+    }
+}
+
+fn add_suggestion(
+    results: &mut AnalysisResults,
+    parameter: &str,
+    current_value: &str,
+    suggested_value: &str,
+    level: SuggestionLevel,
+    rationale: Message,
+) {
+    results
+        .suggestions_by_category
+        .entry(ConfigCategory::OperatingSystem)
+        .or_default()
+        .push(ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: current_value.to_string(),
+            suggested_value: suggested_value.to_string(),
+            level,
+            rationale: rationale.render(Lang::En),
+            messages: vec![rationale],
+            fingerprint: String::new(),
+        });
+}
+
+/// A systemd drop-in when the server runs as a service, a limits.d entry for
+/// the postgres user otherwise
+fn open_files_commands(unit: Option<&str>, limit: u64) -> Vec<String> {
+    match unit {
+        Some(unit) => {
+            let dir = format!("/etc/systemd/system/{unit}.d");
+            vec![
+                format!("sudo mkdir -p {dir}"),
+                format!("printf '[Service]\\nLimitNOFILE={limit}\\n' | sudo tee {dir}/limits.conf"),
+                "sudo systemctl daemon-reload".to_string(),
+                format!("sudo systemctl restart {unit}"),
+            ]
+        }
+        None => vec![format!(
+            "echo 'postgres - nofile {limit}' | sudo tee /etc/security/limits.d/postgresql.conf"
+        )],
+    }
+}
+
+fn sysctl_commands(name: &str, value: u32) -> Vec<String> {
+    vec![
+        format!("sudo sysctl -w {name}={value}"),
+        format!("echo '{name} = {value}' | sudo tee -a {SYSCTL_FILE}"),
+    ]
+}
+
+/// The soft limit from a `/proc/<pid>/limits` `Max open files` line
+fn parse_open_files(limits: &str) -> Option<u64> {
+    limits
+        .lines()
+        .find_map(|line| line.strip_prefix("Max open files"))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|soft| soft.parse().ok())
+}
+
+/// The `.service` a process belongs to, from `/proc/<pid>/cgroup`
+fn parse_systemd_unit(cgroup: &str) -> Option<String> {
+    cgroup
+        .lines()
+        .filter_map(|line| line.rsplit('/').find(|part| part.ends_with(".service")))
+        .next()
+        .map(str::to_string)
+}
+
+/// `node0`, `node1`, ... under `/sys/devices/system/node`
+fn is_node_dir(name: &str) -> bool {
+    name.strip_prefix("node")
+        .is_some_and(|index| !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Confirms the process under `proc_dir` is a PostgreSQL backend running in
+/// `data_directory`. A PID only identifies a process within one PID namespace,
+/// so a match by number alone may be an unrelated local process.
+fn verify_server_process(
+    proc_dir: &Path,
+    pid: u32,
+    data_directory: Option<&str>,
+) -> Result<(), String> {
+    let comm = fs::read_to_string(proc_dir.join("comm"))
+        .map_err(|_| format!("cannot read the name of process {pid}"))?;
+    let comm = comm.trim();
+    if comm != "postgres" {
+        return Err(format!(
+            "process {pid} on this host is {comm}, not the PostgreSQL backend"
+        ));
+    }
+    let data_directory = data_directory.ok_or_else(|| {
+        format!(
+            "data_directory is not readable, so process {pid} cannot be matched to this server; \
+             grant pg_read_all_settings"
+        )
+    })?;
+    let cwd = fs::read_link(proc_dir.join("cwd")).map_err(|_| {
+        format!(
+            "cannot read the working directory of process {pid}; \
+             run as the server's OS user or root"
+        )
+    })?;
+    // Backends inherit the postmaster's working directory, the data directory
+    let expected = fs::canonicalize(data_directory).unwrap_or_else(|_| data_directory.into());
+    if cwd != expected {
+        return Err(format!(
+            "process {pid} runs in {}, not in data_directory {data_directory}",
+            cwd.display()
+        ));
+    }
+    Ok(())
+}
+
+/// The bracketed choice in a sysfs mode list such as `always [madvise] never`
+fn parse_selected(modes: &str) -> Option<String> {
+    let start = modes.find('[')? + 1;
+    let end = start + modes[start..].find(']')?;
+    Some(modes[start..end].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ParamValue, PgConfigParam};
+    use rstest::rstest;
+    use std::collections::HashMap;
+
+    const LIMITS: &str = "\
+Limit                     Soft Limit           Hard Limit           Units
+Max processes             127436               127436               processes
+Max open files            1024                 524288               files
+Max locked memory         8388608              8388608              bytes
+";
+
+    fn params(max_connections: &str) -> HashMap<String, PgConfigParam> {
+        [
+            ("max_connections", max_connections),
+            ("max_files_per_process", "1000"),
+        ]
+        .into_iter()
+        .map(|(name, value)| {
+            (
+                name.to_string(),
+                PgConfigParam {
+                    name: name.to_string(),
+                    current_value: value.to_string(),
+                    default_value: None,
+                    unit: None,
+                    context: "postmaster".to_string(),
+                    value: ParamValue::parse(value, "integer", None),
+                    source: None,
+                    sourcefile: None,
+                    pending_restart: false,
+                    min_value: None,
+                    max_value: None,
+                },
+            )
+        })
+        .collect()
+    }
+
+    #[rstest]
+    #[case(
+        "0::/system.slice/postgresql@17-main.service\n",
+        Some("postgresql@17-main.service")
+    )]
+    #[case(
+        "12:pids:/system.slice/postgresql.service\n0::/system.slice/postgresql.service\n",
+        Some("postgresql.service")
+    )]
+    #[case("0::/docker/4f3c2a\n", None)]
+    fn systemd_unit_is_read_from_the_cgroup(#[case] cgroup: &str, #[case] expected: Option<&str>) {
+        assert_eq!(parse_systemd_unit(cgroup).as_deref(), expected);
+    }
+
+    #[test]
+    fn proc_and_sysfs_values_are_parsed() {
+        assert_eq!(parse_open_files(LIMITS), Some(1024));
+        assert_eq!(
+            parse_open_files(
+                "Max open files            unlimited            unlimited            files"
+            ),
+            None
+        );
+        assert_eq!(
+            parse_selected("always [madvise] never\n").as_deref(),
+            Some("madvise")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn only_a_backend_in_data_directory_is_trusted() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = dir.path().canonicalize().unwrap().join("data");
+        let proc_dir = dir.path().join("4242");
+        fs::create_dir_all(&data).unwrap();
+        fs::create_dir_all(&proc_dir).unwrap();
+        std::os::unix::fs::symlink(&data, proc_dir.join("cwd")).unwrap();
+        let data = data.to_str().unwrap();
+
+        fs::write(proc_dir.join("comm"), "nginx\n").unwrap();
+        let err = verify_server_process(&proc_dir, 4242, Some(data)).unwrap_err();
+        assert!(err.contains("is nginx"), "{err}");
+
+        fs::write(proc_dir.join("comm"), "postgres\n").unwrap();
+        assert_eq!(verify_server_process(&proc_dir, 4242, Some(data)), Ok(()));
+        assert!(verify_server_process(&proc_dir, 4242, None).is_err());
+        let err = verify_server_process(&proc_dir, 4242, Some("/var/lib/postgresql/17/main"))
+            .unwrap_err();
+        assert!(err.contains("not in data_directory"), "{err}");
+    }
+
+    #[test]
+    fn tuned_host_has_no_findings() {
+        let limits = OsLimits {
+            open_files: Some(65_536),
+            systemd_unit: None,
+            overcommit_memory: Some(2),
+            swappiness: Some(1),
+            transparent_hugepage: Some("madvise".to_string()),
+            numa_nodes: Some(2),
+            zone_reclaim_mode: Some(0),
+            numa_balancing: Some(0),
+        };
+        let mut results = AnalysisResults {
+            params: params("500"),
+            ..AnalysisResults::default()
+        };
+        analyze_os_limits(&limits, &mut results);
+        assert!(results.suggestions_by_category.is_empty());
+        assert!(results.host_commands.is_empty());
+    }
+
+    #[test]
+    fn default_host_settings_are_flagged_with_commands() {
+        let limits = OsLimits {
+            open_files: Some(1024),
+            systemd_unit: Some("postgresql@17-main.service".to_string()),
+            overcommit_memory: Some(0),
+            swappiness: Some(60),
+            transparent_hugepage: Some("always".to_string()),
+            numa_nodes: Some(2),
+            zone_reclaim_mode: Some(1),
+            numa_balancing: Some(1),
+        };
+        let mut results = AnalysisResults {
+            params: params("100"),
+            ..AnalysisResults::default()
+        };
+        analyze_os_limits(&limits, &mut results);
+
+        let suggestions: Vec<_> = results.suggestions_by_category[&ConfigCategory::OperatingSystem]
+            .iter()
+            .map(|s| (s.parameter.as_str(), s.suggested_value.as_str()))
+            .collect();
+        assert_eq!(
+            suggestions,
+            [
+                ("LimitNOFILE", "65536"),
+                ("vm.overcommit_memory", "2"),
+                ("vm.swappiness", "1"),
+                ("transparent_hugepage", "never"),
+                ("vm.zone_reclaim_mode", "0"),
+                ("kernel.numa_balancing", "0"),
+            ]
+        );
+        assert_eq!(
+            results.host_commands[1],
+            "printf '[Service]\\nLimitNOFILE=65536\\n' | sudo tee \
+             /etc/systemd/system/postgresql@17-main.service.d/limits.conf"
+        );
+        assert!(results.host_commands.contains(
+            &"echo never | sudo tee /sys/kernel/mm/transparent_hugepage/enabled".to_string()
+        ));
+        assert_eq!(
+            results.host_commands.last().unwrap(),
+            "echo 'kernel.numa_balancing = 0' | sudo tee -a /etc/sysctl.d/90-postgresql.conf"
+        );
+    }
+
+    #[test]
+    fn numa_checks_need_several_nodes() {
+        let limits = OsLimits {
+            numa_nodes: Some(1),
+            zone_reclaim_mode: Some(1),
+            numa_balancing: Some(1),
+            ..OsLimits::default()
+        };
+        let mut results = AnalysisResults::default();
+        analyze_os_limits(&limits, &mut results);
+        assert!(results.suggestions_by_category.is_empty());
+        assert!(is_node_dir("node12"));
+        assert!(!is_node_dir("node"));
+        assert!(!is_node_dir("possible"));
+    }
+}
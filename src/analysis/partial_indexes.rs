@@ -0,0 +1,448 @@
+use crate::analysis::query_parser::{ConstantPredicate, PredicateTest};
+use crate::checker::CheckerError;
+use crate::models::{ByteSize, IndexIssueKind, IndexUsageInfo};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres, Row};
+use std::collections::HashMap;
+
+/// Share of a table's statement calls that must carry a predicate before it is
+/// worth baking into the table's indexes
+const MIN_PREDICATE_CALL_SHARE: f64 = 0.5;
+/// Partial indexes that would keep more than this fraction of rows are not worth it
+const MAX_MATCHING_FRACTION: f64 = 0.8;
+
+/// Calls observed for one constant predicate on one table
+#[derive(Debug, Clone)]
+pub(crate) struct PredicateUsage {
+    pub schema: String,
+    pub table: String,
+    pub predicate: ConstantPredicate,
+    pub calls: i64,
+    /// Calls of every parsed statement touching the table
+    pub table_calls: i64,
+}
+
+impl PredicateUsage {
+    fn is_frequent(&self) -> bool {
+        self.table_calls > 0
+            && self.calls as f64 / self.table_calls as f64 >= MIN_PREDICATE_CALL_SHARE
+    }
+}
+
+/// An existing index on the predicate's table
+#[derive(Debug, Clone)]
+pub(crate) struct TableIndex {
+    pub name: String,
+    pub key_columns: Vec<String>,
+    pub size_bytes: i64,
+    /// `pg_get_expr` of the index predicate for partial indexes
+    pub predicate: Option<String>,
+    /// Unique, expression, non-btree or invalid indexes cannot simply be swapped for a partial one
+    pub convertible: bool,
+}
+
+/// Planner statistics for one column, from `pg_stats`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct ColumnStats {
+    pub null_frac: f64,
+    pub most_common_vals: Vec<String>,
+    pub most_common_freqs: Vec<f64>,
+}
+
+/// Turns frequent constant predicates into `MissingPartialIndex` findings.
+///
+/// A finding is emitted when no existing partial index already carries the
+/// predicate and the full indexes on the table would shrink meaningfully if
+/// rebuilt with it.
+pub(crate) fn identify_missing_partial_indexes(
+    usages: &[PredicateUsage],
+    indexes: &HashMap<String, Vec<TableIndex>>,
+    column_stats: &HashMap<(String, String, String), ColumnStats>,
+) -> Vec<IndexUsageInfo> {
+    let mut findings = Vec::new();
+
+    for usage in usages.iter().filter(|usage| usage.is_frequent()) {
+        let table_name = format!("{}.{}", usage.schema, usage.table);
+        let table_indexes = indexes.get(&table_name).map(Vec::as_slice).unwrap_or(&[]);
+        if table_indexes
+            .iter()
+            .filter_map(|index| index.predicate.as_deref())
+            .any(|existing| predicate_matches(existing, &usage.predicate))
+        {
+            continue;
+        }
+
+        let full_indexes: Vec<&TableIndex> = table_indexes
+            .iter()
+            .filter(|index| index.convertible && index.predicate.is_none())
+            .collect();
+        let full_bytes: i64 = full_indexes.iter().map(|index| index.size_bytes).sum();
+        if full_bytes == 0 {
+            continue;
+        }
+
+        let key = (
+            usage.schema.clone(),
+            usage.table.clone(),
+            usage.predicate.column.clone(),
+        );
+        let Some(fraction) = column_stats
+            .get(&key)
+            .and_then(|stats| matching_fraction(&usage.predicate.test, stats))
+        else {
+            continue;
+        };
+        if fraction > MAX_MATCHING_FRACTION {
+            continue;
+        }
+
+        let mut key_columns = Vec::new();
+        for index in &full_indexes {
+            for column in &index.key_columns {
+                if !key_columns.contains(column) {
+                    key_columns.push(column.clone());
+                }
+            }
+        }
+
+        findings.push(IndexUsageInfo {
+            issue: IndexIssueKind::MissingPartialIndex,
+            schema: usage.schema.clone(),
+            table_name: usage.table.clone(),
+            index_name: full_indexes
+                .iter()
+                .map(|index| index.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            key_columns,
+            index_size_bytes: ByteSize(full_bytes),
+            index_size_pretty: ByteSize(full_bytes).to_string(),
+            scans: usage.calls,
+            tuples_read: 0,
+            tuples_fetched: 0,
+            avg_tuples_per_scan: 0.0,
+            heap_fetch_ratio: 0.0,
+            table_live_tup: None,
+            table_index_writes: None,
+            table_scans: None,
+            index_definition: None,
+            partial_predicate: Some(usage.predicate.to_sql()),
+            estimated_size_bytes: Some(ByteSize((full_bytes as f64 * fraction).round() as i64)),
+            is_unique: false,
+            enforces_constraint: false,
+            is_expression: false,
+            is_partial: false,
+            fingerprint: String::new(),
+        });
+    }
+
+    findings.sort_by_key(|finding| {
+        std::cmp::Reverse(
+            finding.index_size_bytes.0 - finding.estimated_size_bytes.map_or(0, |size| size.0),
+        )
+    });
+    findings
+}
+
+/// Fraction of rows satisfying `test`, if the column statistics can tell
+fn matching_fraction(test: &PredicateTest, stats: &ColumnStats) -> Option<f64> {
+    match test {
+        PredicateTest::IsNull => Some(stats.null_frac),
+        PredicateTest::IsNotNull => Some(1.0 - stats.null_frac),
+        PredicateTest::Equals(literal) => {
+            let value = match literal.as_str() {
+                "true" => "t".to_string(),
+                "false" => "f".to_string(),
+                other => other
+                    .strip_prefix('\'')
+                    .and_then(|value| value.strip_suffix('\''))
+                    .map(|value| value.replace("''", "'"))
+                    .unwrap_or_else(|| other.to_string()),
+            };
+            stats
+                .most_common_vals
+                .iter()
+                .position(|candidate| *candidate == value)
+                .and_then(|position| stats.most_common_freqs.get(position).copied())
+        }
+    }
+}
+
+/// Whether an index predicate from `pg_get_expr` already enforces `predicate`
+fn predicate_matches(index_predicate: &str, predicate: &ConstantPredicate) -> bool {
+    let wanted: Vec<String> = equivalent_forms(predicate)
+        .iter()
+        .map(|form| normalize_predicate(form))
+        .collect();
+
+    normalize_predicate(index_predicate)
+        .split(CONJUNCTION)
+        .any(|conjunct| wanted.iter().any(|form| form == conjunct))
+}
+
+fn equivalent_forms(predicate: &ConstantPredicate) -> Vec<String> {
+    let column = &predicate.column;
+    match &predicate.test {
+        PredicateTest::Equals(literal) if literal == "true" => vec![
+            column.clone(),
+            format!("{column} = true"),
+            format!("{column} IS TRUE"),
+        ],
+        PredicateTest::Equals(literal) if literal == "false" => vec![
+            format!("NOT {column}"),
+            format!("{column} = false"),
+            format!("{column} IS FALSE"),
+        ],
+        _ => vec![predicate.to_sql()],
+    }
+}
+
+/// Separator left between conjuncts by [`normalize_predicate`]
+const CONJUNCTION: char = '\u{0}';
+
+/// Lowercases and strips casts, parentheses and whitespace, so `((status)::text = 'active'::text)`
+/// and `status = 'active'` compare equal. Conjuncts are separated by [`CONJUNCTION`].
+/// Also used to compare index expressions such as `lower((email)::text)`.
+pub(crate) fn normalize_predicate(predicate: &str) -> String {
+    let mut normalized = predicate.to_lowercase();
+    for cast in [
+        "::character varying",
+        "::text",
+        "::bpchar",
+        "::boolean",
+        "::integer",
+        "::bigint",
+        "::regconfig",
+        "::timestamp with time zone",
+        "::timestamp without time zone",
+    ] {
+        normalized = normalized.replace(cast, "");
+    }
+    normalized
+        .replace(" and ", &CONJUNCTION.to_string())
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '(' && *c != ')')
+        .collect()
+}
+
+pub(crate) const COLUMN_STATS_QUERY: &str = r#"
+    SELECT
+        s.schemaname,
+        s.tablename,
+        s.attname,
+        s.null_frac::double precision AS null_frac,
+        s.most_common_vals::text AS most_common_vals,
+        COALESCE(s.most_common_freqs::double precision[], ARRAY[]::double precision[]) AS most_common_freqs
+    FROM pg_stats s
+    JOIN unnest($1::text[], $2::text[], $3::text[]) AS c(schemaname, tablename, attname)
+        ON c.schemaname = s.schemaname
+       AND c.tablename = s.tablename
+       AND c.attname = s.attname
+"#;
+
+/// Reads `pg_stats` for the given `(schema, table, column)` triples
+pub(crate) async fn fetch_column_stats(
+    pool: &Pool<Postgres>,
+    columns: &[(String, String, String)],
+) -> Result<HashMap<(String, String, String), ColumnStats>, CheckerError> {
+    if columns.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let schemas: Vec<&str> = columns
+        .iter()
+        .map(|(schema, _, _)| schema.as_str())
+        .collect();
+    let tables: Vec<&str> = columns.iter().map(|(_, table, _)| table.as_str()).collect();
+    let names: Vec<&str> = columns
+        .iter()
+        .map(|(_, _, column)| column.as_str())
+        .collect();
+
+    let rows = sqlx::query(COLUMN_STATS_QUERY)
+        .bind(&schemas)
+        .bind(&tables)
+        .bind(&names)
+        .fetch_all(pool)
+        .await
+        .map_err(|source| CheckerError::QueryError {
+            query: COLUMN_STATS_QUERY.into(),
+            source,
+        })?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let most_common_vals: Option<String> = row.get("most_common_vals");
+            (
+                (
+                    row.get("schemaname"),
+                    row.get("tablename"),
+                    row.get("attname"),
+                ),
+                ColumnStats {
+                    null_frac: row.get("null_frac"),
+                    most_common_vals: most_common_vals
+                        .as_deref()
+                        .map(parse_text_array)
+                        .unwrap_or_default(),
+                    most_common_freqs: row.get("most_common_freqs"),
+                },
+            )
+        })
+        .collect())
+}
+
+/// Parses the text form of a PostgreSQL array, e.g. `{active,"on hold",t}`
+fn parse_text_array(text: &str) -> Vec<String> {
+    let inner = text
+        .strip_prefix('{')
+        .and_then(|rest| rest.strip_suffix('}'))
+        .unwrap_or(text);
+    let mut values = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => quoted = !quoted,
+            '\\' if quoted => {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            ',' if !quoted => values.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    if !inner.is_empty() {
+        values.push(current);
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn soft_delete_usage(calls: i64) -> PredicateUsage {
+        PredicateUsage {
+            schema: "public".into(),
+            table: "orders".into(),
+            predicate: ConstantPredicate {
+                column: "deleted_at".into(),
+                test: PredicateTest::IsNull,
+            },
+            calls,
+            table_calls: 1_000,
+        }
+    }
+
+    fn orders_indexes(predicate: Option<&str>) -> HashMap<String, Vec<TableIndex>> {
+        let mut indexes = vec![TableIndex {
+            name: "orders_customer_id_idx".into(),
+            key_columns: vec!["customer_id".into()],
+            size_bytes: 100 * 1024 * 1024,
+            predicate: None,
+            convertible: true,
+        }];
+        if let Some(predicate) = predicate {
+            indexes.push(TableIndex {
+                name: "orders_live_idx".into(),
+                key_columns: vec!["customer_id".into()],
+                size_bytes: 40 * 1024 * 1024,
+                predicate: Some(predicate.into()),
+                convertible: true,
+            });
+        }
+        HashMap::from([("public.orders".to_string(), indexes)])
+    }
+
+    fn deleted_at_stats(null_frac: f64) -> HashMap<(String, String, String), ColumnStats> {
+        HashMap::from([(
+            ("public".into(), "orders".into(), "deleted_at".into()),
+            ColumnStats {
+                null_frac,
+                ..ColumnStats::default()
+            },
+        )])
+    }
+
+    #[test]
+    fn frequent_soft_delete_filter_yields_size_estimate() {
+        let findings = identify_missing_partial_indexes(
+            &[soft_delete_usage(900)],
+            &orders_indexes(None),
+            &deleted_at_stats(0.25),
+        );
+
+        assert_eq!(findings.len(), 1);
+        let finding = &findings[0];
+        assert_eq!(finding.issue, IndexIssueKind::MissingPartialIndex);
+        assert_eq!(
+            finding.partial_predicate.as_deref(),
+            Some("deleted_at IS NULL")
+        );
+        assert_eq!(finding.index_name, "orders_customer_id_idx");
+        assert_eq!(
+            finding.estimated_size_bytes,
+            Some(ByteSize(25 * 1024 * 1024))
+        );
+    }
+
+    #[rstest]
+    #[case::infrequent(100, None, 0.25)]
+    #[case::existing_partial(900, Some("(deleted_at IS NULL)"), 0.25)]
+    #[case::little_to_gain(900, None, 0.95)]
+    fn skips_predicates_not_worth_a_partial_index(
+        #[case] calls: i64,
+        #[case] existing: Option<&str>,
+        #[case] null_frac: f64,
+    ) {
+        assert!(identify_missing_partial_indexes(
+            &[soft_delete_usage(calls)],
+            &orders_indexes(existing),
+            &deleted_at_stats(null_frac),
+        )
+        .is_empty());
+    }
+
+    #[rstest]
+    #[case("((status)::text = 'active'::text)", "status", "'active'", true)]
+    #[case("(NOT is_deleted)", "is_deleted", "false", true)]
+    #[case("(is_deleted = false)", "is_deleted", "false", true)]
+    #[case("((tenant_id = 1) AND (NOT is_deleted))", "is_deleted", "false", true)]
+    #[case("(is_deleted)", "is_deleted", "false", false)]
+    fn matches_catalog_predicate_forms(
+        #[case] index_predicate: &str,
+        #[case] column: &str,
+        #[case] literal: &str,
+        #[case] expected: bool,
+    ) {
+        let predicate = ConstantPredicate {
+            column: column.into(),
+            test: PredicateTest::Equals(literal.into()),
+        };
+        assert_eq!(predicate_matches(index_predicate, &predicate), expected);
+    }
+
+    #[test]
+    fn equality_fraction_comes_from_most_common_values() {
+        let stats = ColumnStats {
+            null_frac: 0.0,
+            most_common_vals: parse_text_array("{archived,\"on hold\",active}"),
+            most_common_freqs: vec![0.7, 0.2, 0.1],
+        };
+        assert_eq!(stats.most_common_vals[1], "on hold");
+        assert_eq!(
+            matching_fraction(&PredicateTest::Equals("'active'".into()), &stats),
+            Some(0.1)
+        );
+        assert_eq!(
+            matching_fraction(&PredicateTest::Equals("'pending'".into()), &stats),
+            None
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use crate::analysis::reconcile::is_object_category;
+use crate::analysis::validation::in_param_unit;
+use crate::checker::CheckerError;
+use crate::i18n::Message;
+use crate::models::{AnalysisResults, PendingApply, PendingChange, PgConfigParam};
+use sqlx::{Pool, Postgres, Row};
+use std::collections::HashMap;
+
+/// `pg_file_settings.error` for entries that are valid but need a restart
+const NEEDS_RESTART: &str = "setting could not be applied";
+
+pub(crate) const PENDING_CHANGES_QUERY: &str = r#"
+    SELECT DISTINCT ON (lower(name))
+        lower(name) AS name,
+        setting,
+        sourcefile,
+        error
+    FROM pg_file_settings
+    WHERE name IS NOT NULL
+    ORDER BY lower(name), seqno DESC
+"#;
+
+/// Reads the last entry per setting across the configuration files, including
+/// `postgresql.auto.conf` written by `ALTER SYSTEM`, and keeps those whose
+/// value differs from the running one. The view is superuser-only unless
+/// granted explicitly.
+pub async fn fetch_pending_changes(
+    pool: &Pool<Postgres>,
+    params: &HashMap<String, PgConfigParam>,
+) -> Result<Vec<PendingChange>, CheckerError> {
+    let rows = sqlx::query(PENDING_CHANGES_QUERY)
+        .fetch_all(pool)
+        .await
+        .map_err(|source| CheckerError::QueryError {
+            query: PENDING_CHANGES_QUERY.into(),
+            source,
+        })?;
+
+    let mut changes = Vec::new();
+    for row in rows {
+        let name: String = row.get("name");
+        let error: Option<String> = row.get("error");
+        if error.is_some_and(|error| error != NEEDS_RESTART) {
+            continue;
+        }
+        let Some(param) = params.get(&name) else {
+            continue;
+        };
+        let staged_value: String = row.get::<Option<String>, _>("setting").unwrap_or_default();
+        if same_value(&staged_value, &param.current_value, param) {
+            continue;
+        }
+        changes.push(PendingChange {
+            name,
+            running_value: param.current_value.clone(),
+            unit: param.unit.clone(),
+            staged_value,
+            sourcefile: row
+                .get::<Option<String>, _>("sourcefile")
+                .unwrap_or_default(),
+            apply: if param.context == "postmaster" {
+                PendingApply::Restart
+            } else {
+                PendingApply::Reload
+            },
+            covers_suggestion: None,
+        });
+    }
+    Ok(changes)
+}
+
+/// Cross-checks settings suggestions against the staged changes: a suggestion
+/// the staged value already carries out is dropped and recorded on the change,
+/// one that disagrees with it gets a note naming the staged value.
+pub fn reconcile_pending_changes(results: &mut AnalysisResults) {
+    if results.pending_changes.is_empty() {
+        return;
+    }
+    for (category, suggestions) in results.suggestions_by_category.iter_mut() {
+        if is_object_category(*category) {
+            continue;
+        }
+        suggestions.retain_mut(|suggestion| {
+            let Some(change) = results
+                .pending_changes
+                .iter_mut()
+                .find(|change| change.name == suggestion.parameter)
+            else {
+                return true;
+            };
+            let carried_out = results.params.get(&change.name).is_some_and(|param| {
+                same_value(&change.staged_value, &suggestion.suggested_value, param)
+            });
+            if carried_out {
+                change.covers_suggestion = Some(suggestion.suggested_value.clone());
+                return false;
+            }
+            let key = match change.apply {
+                PendingApply::Reload => "note.pending_reload",
+                PendingApply::Restart => "note.pending_restart",
+            };
+            suggestion.push_note(
+                Message::new(key)
+                    .arg("value", &change.staged_value)
+                    .arg("file", &change.sourcefile),
+            );
+            true
+        });
+    }
+    results
+        .suggestions_by_category
+        .retain(|_, suggestions| !suggestions.is_empty());
+}
+
+/// Compares two spellings of a setting, e.g. `8GB` and `1048576` for an `8kB`
+/// parameter, or `true` and `on`
+fn same_value(left: &str, right: &str, param: &PgConfigParam) -> bool {
+    if let (Some(left), Some(right)) = (in_param_unit(left, param), in_param_unit(right, param)) {
+        return (left - right).abs() <= 1e-9 * left.abs().max(1.0);
+    }
+    normalize(left) == normalize(right)
+}
+
+fn normalize(value: &str) -> String {
+    let value = value.trim().trim_matches('\'').to_ascii_lowercase();
+    match value.as_str() {
+        "on" | "true" | "yes" | "1" => "on".to_string(),
+        "off" | "false" | "no" | "0" => "off".to_string(),
+        _ => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConfigCategory, ConfigSuggestion, ParamValue, SuggestionLevel};
+    use rstest::rstest;
+
+    fn param(name: &str, value: &str, unit: Option<&str>) -> PgConfigParam {
+        PgConfigParam {
+            name: name.to_string(),
+            current_value: value.to_string(),
+            default_value: None,
+            unit: unit.map(str::to_string),
+            context: "postmaster".to_string(),
+            value: ParamValue::parse(value, "integer", unit),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        }
+    }
+
+    fn suggestion(parameter: &str, suggested_value: &str) -> ConfigSuggestion {
+        ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: String::new(),
+            suggested_value: suggested_value.to_string(),
+            level: SuggestionLevel::Important,
+            rationale: "Raise it.".to_string(),
+            messages: Vec::new(),
+            fingerprint: String::new(),
+        }
+    }
+
+    #[rstest]
+    #[case("8GB", "1048576", Some("8kB"), true)]
+    #[case("4GB", "1048576", Some("8kB"), false)]
+    #[case("5min", "300", Some("s"), true)]
+    #[case("true", "on", None, true)]
+    #[case("'ddl'", "ddl", None, true)]
+    fn values_compare_across_spellings(
+        #[case] staged: &str,
+        #[case] running: &str,
+        #[case] unit: Option<&str>,
+        #[case] expected: bool,
+    ) {
+        let param = param("setting", running, unit);
+        assert_eq!(same_value(staged, running, &param), expected);
+    }
+
+    #[test]
+    fn staged_suggestions_move_to_the_pending_list() {
+        let mut results = AnalysisResults::default();
+        for param in [
+            param("shared_buffers", "16384", Some("8kB")),
+            param("max_wal_size", "1024", Some("MB")),
+        ] {
+            results.params.insert(param.name.clone(), param);
+        }
+        results.suggestions_by_category.insert(
+            ConfigCategory::Memory,
+            vec![suggestion("shared_buffers", "8GB")],
+        );
+        results.suggestions_by_category.insert(
+            ConfigCategory::Wal,
+            vec![suggestion("max_wal_size", "16GB")],
+        );
+        results.pending_changes = ["shared_buffers", "max_wal_size"]
+            .into_iter()
+            .map(|name| PendingChange {
+                name: name.to_string(),
+                running_value: results.params[name].current_value.clone(),
+                unit: results.params[name].unit.clone(),
+                staged_value: "8GB".to_string(),
+                sourcefile: "/var/lib/postgresql/data/postgresql.auto.conf".to_string(),
+                apply: PendingApply::Restart,
+                covers_suggestion: None,
+            })
+            .collect();
+
+        reconcile_pending_changes(&mut results);
+
+        assert!(!results
+            .suggestions_by_category
+            .contains_key(&ConfigCategory::Memory));
+        assert_eq!(
+            results.pending_changes[0].covers_suggestion.as_deref(),
+            Some("8GB")
+        );
+        let wal = &results.suggestions_by_category[&ConfigCategory::Wal][0];
+        assert_eq!(
+            wal.rationale,
+            "Raise it. 8GB is already staged in /var/lib/postgresql/data/postgresql.auto.conf \
+             and waits for a restart."
+        );
+        assert!(results.pending_changes[1].covers_suggestion.is_none());
+    }
+}
@@ -0,0 +1,277 @@
+use crate::checker::CheckerError;
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ByteSize, ConfigCategory, ConfigSuggestion, PgConfigParam, SuggestionLevel,
+    SystemStats,
+};
+use std::collections::HashMap;
+
+type Result<T> = std::result::Result<T, CheckerError>;
+
+/// pgaudit's statement classes, in the order `all` expands to
+pub const AUDIT_CLASSES: &[&str] = &[
+    "read", "write", "function", "role", "ddl", "misc", "misc_set",
+];
+/// Classes kept by the narrower suggestion however much they log, because
+/// they record who changed schema and privileges
+const ESSENTIAL_CLASSES: &[&str] = &["ddl", "role"];
+/// `log_line_prefix` plus `AUDIT: SESSION,1,1,READ,SELECT,,,` ahead of the
+/// statement text
+const AUDIT_LINE_OVERHEAD_BYTES: f64 = 120.0;
+/// Daily audit log volume at which the configuration is flagged, and at which
+/// it becomes critical
+const AUDIT_VOLUME_WARN_BYTES: f64 = 10.0 * GIB;
+const AUDIT_VOLUME_CRITICAL_BYTES: f64 = 100.0 * GIB;
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// The pgaudit class a statement is logged under, from its leading keywords
+pub(crate) fn audit_class(query: &str) -> &'static str {
+    let words: Vec<String> = query
+        .split_whitespace()
+        .take(2)
+        .map(|word| word.trim_start_matches('(').to_ascii_uppercase())
+        .collect();
+    let first = words.first().map(String::as_str).unwrap_or_default();
+    let second = words.get(1).map(String::as_str).unwrap_or_default();
+    match first {
+        "SELECT" | "WITH" | "VALUES" | "TABLE" => "read",
+        "COPY" if query.to_ascii_uppercase().contains(" TO ") => "read",
+        "INSERT" | "UPDATE" | "DELETE" | "MERGE" | "TRUNCATE" | "COPY" => "write",
+        "GRANT" | "REVOKE" => "role",
+        "CREATE" | "ALTER" | "DROP" if matches!(second, "ROLE" | "USER" | "GROUP") => "role",
+        "CREATE" | "ALTER" | "DROP" | "COMMENT" | "REINDEX" | "CLUSTER" => "ddl",
+        "CALL" | "DO" => "function",
+        "SET" | "RESET" => "misc_set",
+        _ => "misc",
+    }
+}
+
+/// Classes `pgaudit.log` enables, e.g. `all, -misc` or `write, ddl`
+pub fn enabled_classes(value: &str) -> Vec<&'static str> {
+    let mut enabled: Vec<&'static str> = Vec::new();
+    for item in value
+        .split(',')
+        .map(|item| item.trim().to_ascii_lowercase())
+    {
+        let (exclude, name) = match item.strip_prefix('-') {
+            Some(name) => (true, name.trim()),
+            None => (false, item.as_str()),
+        };
+        let classes: Vec<&'static str> = match name {
+            "all" => AUDIT_CLASSES.to_vec(),
+            _ => AUDIT_CLASSES
+                .iter()
+                .copied()
+                .filter(|class| *class == name)
+                .collect(),
+        };
+        if exclude {
+            enabled.retain(|class| !classes.contains(class));
+        } else {
+            for class in classes {
+                if !enabled.contains(&class) {
+                    enabled.push(class);
+                }
+            }
+        }
+    }
+    enabled
+}
+
+/// Estimates the daily log volume of the enabled `pgaudit.log` classes and
+/// suggests dropping the noisiest ones when it would overwhelm log storage
+pub fn analyze_pgaudit(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    let (Some(param), Some(rates)) = (params.get("pgaudit.log"), &stats.statement_rates) else {
+        return Ok(());
+    };
+    let enabled = enabled_classes(&param.current_value);
+    let mut volumes: Vec<(&str, f64)> = rates
+        .classes
+        .iter()
+        .filter(|rate| enabled.contains(&rate.class.as_str()))
+        .map(|rate| {
+            let line = AUDIT_LINE_OVERHEAD_BYTES + rate.mean_statement_bytes;
+            (
+                rate.class.as_str(),
+                rate.per_second * line * SECONDS_PER_DAY,
+            )
+        })
+        .collect();
+    let total: f64 = volumes.iter().map(|(_, bytes)| bytes).sum();
+    if total < AUDIT_VOLUME_WARN_BYTES {
+        return Ok(());
+    }
+
+    // Drop the noisiest classes until the rest fits, keeping ddl and role
+    volumes.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let mut remaining = total;
+    let mut dropped = Vec::new();
+    for (class, bytes) in &volumes {
+        if remaining < AUDIT_VOLUME_WARN_BYTES {
+            break;
+        }
+        if !ESSENTIAL_CLASSES.contains(class) {
+            remaining -= bytes;
+            dropped.push(*class);
+        }
+    }
+    let kept: Vec<&str> = enabled
+        .iter()
+        .copied()
+        .filter(|class| !dropped.contains(class))
+        .collect();
+    let suggested = if kept.is_empty() {
+        "ddl, role".to_string()
+    } else if enabled.len() == AUDIT_CLASSES.len() {
+        std::iter::once("all".to_string())
+            .chain(dropped.iter().map(|class| format!("-{class}")))
+            .collect::<Vec<_>>()
+            .join(", ")
+    } else {
+        kept.join(", ")
+    };
+    let noisiest = volumes
+        .iter()
+        .take(3)
+        .map(|(class, bytes)| format!("{class} {}/day", ByteSize(*bytes as i64)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let rationale = Message::new("logging.pgaudit_volume")
+        .arg("volume", ByteSize(total as i64))
+        .arg(
+            "statements",
+            format!(
+                "{:.0}",
+                rates
+                    .classes
+                    .iter()
+                    .map(|rate| rate.per_second)
+                    .sum::<f64>()
+            ),
+        )
+        .arg("classes", noisiest)
+        .arg("remaining", ByteSize(remaining.max(0.0) as i64));
+    let suggestion = ConfigSuggestion {
+        parameter: "pgaudit.log".to_string(),
+        current_value: param.current_value.clone(),
+        suggested_value: suggested,
+        level: if total >= AUDIT_VOLUME_CRITICAL_BYTES {
+            SuggestionLevel::Critical
+        } else {
+            SuggestionLevel::Important
+        },
+        rationale: rationale.render(Lang::En),
+        messages: vec![rationale],
+        fingerprint: String::new(),
+    };
+    results
+        .suggestions_by_category
+        .entry(ConfigCategory::Logging)
+        .or_default()
+        .push(suggestion);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::log_volume::{statement_rates, StatementTiming};
+    use crate::models::ParamValue;
+    use rstest::rstest;
+
+    fn pgaudit_log(value: &str) -> HashMap<String, PgConfigParam> {
+        let param = PgConfigParam {
+            name: "pgaudit.log".to_string(),
+            current_value: value.to_string(),
+            default_value: None,
+            unit: None,
+            context: "superuser".to_string(),
+            value: ParamValue::parse(value, "string", None),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        };
+        HashMap::from([(param.name.clone(), param)])
+    }
+
+    #[rstest]
+    #[case("SELECT * FROM orders WHERE id = $1", "read")]
+    #[case("with recent AS (SELECT 1) SELECT * FROM recent", "read")]
+    #[case("COPY orders TO STDOUT", "read")]
+    #[case("COPY orders FROM STDIN", "write")]
+    #[case("UPDATE orders SET status = $1", "write")]
+    #[case("GRANT SELECT ON orders TO reporting", "role")]
+    #[case("CREATE ROLE reporting", "role")]
+    #[case("CREATE INDEX ON orders (status)", "ddl")]
+    #[case("SET application_name = $1", "misc_set")]
+    #[case("BEGIN", "misc")]
+    fn statements_map_to_audit_classes(#[case] query: &str, #[case] expected: &str) {
+        assert_eq!(audit_class(query), expected);
+    }
+
+    #[rstest]
+    #[case("none", &[])]
+    #[case("write, ddl", &["write", "ddl"])]
+    #[case("all, -misc", &["read", "write", "function", "role", "ddl", "misc_set"])]
+    #[case("misc, misc_set, -misc_set", &["misc"])]
+    #[case("ALL", &["read", "write", "function", "role", "ddl", "misc", "misc_set"])]
+    fn pgaudit_log_classes_are_expanded(#[case] value: &str, #[case] expected: &[&str]) {
+        assert_eq!(enabled_classes(value), expected);
+    }
+
+    fn audited(value: &str, reads_per_call: i64) -> Vec<ConfigSuggestion> {
+        let statement = |query: String, calls: i64| StatementTiming {
+            query,
+            calls,
+            ..Default::default()
+        };
+        let statements = vec![
+            // 80 characters each
+            statement(
+                format!("SELECT * FROM orders WHERE id = $1{}", " ".repeat(46)),
+                reads_per_call,
+            ),
+            statement(
+                format!("UPDATE orders SET status = $1{}", " ".repeat(51)),
+                100_000,
+            ),
+            statement("CREATE INDEX ON orders (status)".to_string(), 1),
+        ];
+        let stats = SystemStats {
+            statement_rates: Some(statement_rates(&statements, 86_400, None)),
+            ..Default::default()
+        };
+        let mut results = AnalysisResults::default();
+        analyze_pgaudit(&pgaudit_log(value), &stats, &mut results).unwrap();
+        results
+            .suggestions_by_category
+            .remove(&ConfigCategory::Logging)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn noisy_read_auditing_is_narrowed() {
+        // 100M reads a day at 200 bytes a line is ~19 GB
+        let suggestions = audited("all", 100_000_000);
+        let suggestion = &suggestions[0];
+        assert_eq!(suggestion.level, SuggestionLevel::Important);
+        assert_eq!(suggestion.suggested_value, "all, -read");
+        assert!(suggestion.rationale.contains("read 19 GB/day"));
+        assert_eq!(
+            audited("read, write, ddl", 100_000_000)[0].suggested_value,
+            "write, ddl"
+        );
+
+        assert!(audited("write, ddl", 100_000_000).is_empty());
+        assert!(audited("all", 1_000_000).is_empty());
+    }
+}
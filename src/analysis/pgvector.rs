@@ -0,0 +1,620 @@
+use crate::analysis::extensions::add_suggestion;
+use crate::analysis::param_value;
+use crate::analysis::table_index::quote_ident;
+use crate::checker::CheckerError;
+use crate::i18n::Message;
+use crate::models::{
+    AnalysisResults, ByteSize, ParamValue, PgConfigParam, SuggestionLevel, SystemStats,
+    VectorColumn, VectorIndex,
+};
+use sqlx::{Pool, Postgres, Row};
+use std::collections::HashMap;
+use tracing::warn;
+
+type Result<T> = std::result::Result<T, CheckerError>;
+
+/// Below this many rows an exact scan is fast enough that an approximate index
+/// only costs recall
+const MIN_VECTOR_TABLE_ROWS: i64 = 10_000;
+/// Distance-query calls at which a column without an index is reported
+const MIN_DISTANCE_CALLS: i64 = 100;
+/// `lists` of an IVFFlat index built without the storage parameter
+const IVFFLAT_DEFAULT_LISTS: usize = 100;
+const IVFFLAT_DEFAULT_PROBES: usize = 1;
+const HNSW_DEFAULT_EF_SEARCH: usize = 40;
+/// Neighbour lists and tuple header per HNSW element at the default `m = 16`
+const HNSW_ELEMENT_OVERHEAD_BYTES: i64 = 200;
+const MB: i64 = 1024 * 1024;
+
+/// pgvector distance operators and the operator class suffix an index needs
+/// to serve each
+const DISTANCE_OPERATORS: &[(&str, &str)] = &[
+    ("<->", "l2_ops"),
+    ("<=>", "cosine_ops"),
+    ("<#>", "ip_ops"),
+    ("<+>", "l1_ops"),
+];
+
+pub(crate) const VECTOR_COLUMNS_QUERY: &str = r#"
+    SELECT
+        n.nspname,
+        c.relname,
+        a.attname,
+        t.typname::text AS type_name,
+        CASE WHEN a.atttypmod > 0 THEN a.atttypmod END AS dimensions,
+        greatest(c.reltuples, 0)::bigint AS estimated_rows
+    FROM pg_attribute a
+    JOIN pg_class c ON a.attrelid = c.oid
+    JOIN pg_namespace n ON c.relnamespace = n.oid
+    JOIN pg_type t ON a.atttypid = t.oid
+    WHERE t.typname IN ('vector', 'halfvec', 'sparsevec')
+      AND a.attnum > 0
+      AND NOT a.attisdropped
+      AND c.relkind IN ('r', 'm')
+      AND n.nspname NOT IN ('pg_catalog', 'information_schema')
+"#;
+
+pub(crate) const VECTOR_INDEXES_QUERY: &str = r#"
+    SELECT
+        n.nspname,
+        c.relname,
+        a.attname,
+        ic.relname AS index_name,
+        am.amname::text AS method,
+        (
+            SELECT split_part(option, '=', 2)::int
+            FROM unnest(ic.reloptions) AS option
+            WHERE option LIKE 'lists=%'
+        ) AS lists,
+        pg_relation_size(ic.oid) AS size_bytes
+    FROM pg_index i
+    JOIN pg_class ic ON ic.oid = i.indexrelid
+    JOIN pg_am am ON am.oid = ic.relam
+    JOIN pg_class c ON c.oid = i.indrelid
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    JOIN pg_attribute a ON a.attrelid = c.oid AND a.attnum = i.indkey[0]
+    WHERE am.amname IN ('hnsw', 'ivfflat')
+      AND i.indisvalid
+"#;
+
+pub(crate) const DISTANCE_STATEMENTS_QUERY: &str =
+    r"SELECT query, calls FROM pg_stat_statements WHERE query ~ '<(->|=>|#>|\+>)'";
+
+/// Reads the vector columns with their HNSW/IVFFlat indexes and, when
+/// pg_stat_statements is readable, how often each is queried by distance
+pub async fn fetch_vector_columns(pool: &Pool<Postgres>) -> Vec<VectorColumn> {
+    let rows = match sqlx::query(VECTOR_COLUMNS_QUERY).fetch_all(pool).await {
+        Ok(rows) => rows,
+        Err(err) => {
+            warn!("Failed to read pgvector columns: {err}");
+            return Vec::new();
+        }
+    };
+    let mut columns: Vec<VectorColumn> = rows
+        .into_iter()
+        .map(|row| VectorColumn {
+            schema: row.get("nspname"),
+            table_name: row.get("relname"),
+            column_name: row.get("attname"),
+            type_name: row.get("type_name"),
+            dimensions: row
+                .get::<Option<i32>, _>("dimensions")
+                .and_then(|dimensions| usize::try_from(dimensions).ok()),
+            estimated_rows: row.get("estimated_rows"),
+            ..VectorColumn::default()
+        })
+        .collect();
+
+    match sqlx::query(VECTOR_INDEXES_QUERY).fetch_all(pool).await {
+        Ok(rows) => {
+            for row in rows {
+                let (schema, table, column): (String, String, String) =
+                    (row.get("nspname"), row.get("relname"), row.get("attname"));
+                let Some(target) = columns.iter_mut().find(|candidate| {
+                    candidate.schema == schema
+                        && candidate.table_name == table
+                        && candidate.column_name == column
+                }) else {
+                    continue;
+                };
+                target.indexes.push(VectorIndex {
+                    name: row.get("index_name"),
+                    method: row.get("method"),
+                    lists: row
+                        .get::<Option<i32>, _>("lists")
+                        .and_then(|lists| usize::try_from(lists).ok()),
+                    size_bytes: ByteSize(row.get("size_bytes")),
+                });
+            }
+        }
+        Err(err) => warn!("Failed to read pgvector indexes: {err}"),
+    }
+
+    match sqlx::query(DISTANCE_STATEMENTS_QUERY).fetch_all(pool).await {
+        Ok(rows) => {
+            let statements: Vec<(String, i64)> = rows
+                .into_iter()
+                .map(|row| (row.get("query"), row.get("calls")))
+                .collect();
+            for column in &mut columns {
+                count_distance_calls(column, &statements);
+            }
+        }
+        Err(err) => warn!("Failed to read distance queries from pg_stat_statements: {err}"),
+    }
+
+    columns
+}
+
+/// Sums the calls of statements using a distance operator on the column and
+/// keeps the operator with the most calls
+fn count_distance_calls(column: &mut VectorColumn, statements: &[(String, i64)]) {
+    let mut calls_by_operator: HashMap<&'static str, i64> = HashMap::new();
+    for (query, calls) in statements {
+        if let Some(operator) = distance_operator_on(query, &column.column_name) {
+            *calls_by_operator.entry(operator).or_default() += calls;
+        }
+    }
+    column.distance_calls = Some(calls_by_operator.values().sum());
+    column.distance_operator = calls_by_operator
+        .into_iter()
+        .max_by_key(|(operator, calls)| (*calls, *operator))
+        .map(|(operator, _)| operator.to_string());
+}
+
+/// The distance operator applied to `column` in `query`, on either side and
+/// with or without a table qualifier
+fn distance_operator_on(query: &str, column: &str) -> Option<&'static str> {
+    let query = query.to_ascii_lowercase();
+    let column = column.to_ascii_lowercase();
+    let names_column = |operand: &str| operand.rsplit('.').next() == Some(column.as_str());
+    DISTANCE_OPERATORS
+        .iter()
+        .map(|(operator, _)| *operator)
+        .find(|operator| {
+            query.match_indices(operator).any(|(at, _)| {
+                let before = query[..at].trim_end();
+                let after = query[at + operator.len()..].trim_start();
+                let left = &before[before
+                    .char_indices()
+                    .rev()
+                    .find(|(_, c)| !is_identifier_char(*c))
+                    .map_or(0, |(end, c)| end + c.len_utf8())..];
+                let right = &after[..after
+                    .find(|c: char| !is_identifier_char(c))
+                    .unwrap_or(after.len())];
+                names_column(left) || names_column(right)
+            })
+        })
+}
+
+/// Identifier characters, counting `.` so qualified names stay together
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '.'
+}
+
+/// Checks pgvector columns for missing indexes, IVFFlat `lists` and probes,
+/// `hnsw.ef_search` and the memory an HNSW build needs
+pub fn analyze_pgvector(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    let large_columns: Vec<&VectorColumn> = stats
+        .vector_columns
+        .iter()
+        .filter(|column| column.estimated_rows >= MIN_VECTOR_TABLE_ROWS)
+        .collect();
+
+    for column in &large_columns {
+        if column.indexes.is_empty() {
+            analyze_missing_index(column, results);
+        }
+        for index in column
+            .indexes
+            .iter()
+            .filter(|index| index.method == "ivfflat")
+        {
+            analyze_ivfflat_lists(column, index, results);
+        }
+    }
+    analyze_ivfflat_probes(params, &large_columns, results);
+    analyze_hnsw_ef_search(params, &large_columns, results);
+    analyze_build_memory(params, stats, &large_columns, results);
+
+    Ok(())
+}
+
+fn analyze_missing_index(column: &VectorColumn, results: &mut AnalysisResults) {
+    if column
+        .distance_calls
+        .is_some_and(|calls| calls < MIN_DISTANCE_CALLS)
+    {
+        return;
+    }
+
+    let operator = column.distance_operator.as_deref().unwrap_or("<->");
+    let operator_class = DISTANCE_OPERATORS
+        .iter()
+        .find(|(candidate, _)| *candidate == operator)
+        .map_or("l2_ops", |(_, suffix)| *suffix);
+    let table = format!("{}.{}", column.schema, column.table_name);
+    let ddl = format!(
+        "CREATE INDEX CONCURRENTLY ON {}.{} USING hnsw ({} {}_{operator_class})",
+        quote_ident(&column.schema),
+        quote_ident(&column.table_name),
+        quote_ident(&column.column_name),
+        column.type_name
+    );
+
+    let suggestion = add_suggestion(
+        results,
+        &format!("vector column {table}.{}", column.column_name),
+        &column.distance_calls.map_or_else(
+            || "no HNSW/IVFFlat index".to_string(),
+            |calls| format!("{calls} distance queries, no index"),
+        ),
+        "Create HNSW index",
+        if column.distance_calls.is_some() {
+            SuggestionLevel::Important
+        } else {
+            SuggestionLevel::Recommended
+        },
+        Message::new("extensions.pgvector_missing_index")
+            .arg("table", &table)
+            .arg("column", &column.column_name)
+            .arg("rows", column.estimated_rows)
+            .arg("operator", operator)
+            .arg("ddl", ddl),
+    );
+    if column.distance_calls.is_none() {
+        suggestion.push_note(Message::new("extensions.pgvector_missing_index.unverified"));
+    }
+}
+
+/// pgvector's guidance: rows / 1000 up to a million rows, the square root above
+fn recommended_lists(rows: i64) -> usize {
+    let rows = rows.max(0) as f64;
+    let lists = if rows <= 1_000_000.0 {
+        rows / 1000.0
+    } else {
+        rows.sqrt()
+    };
+    (lists.round() as usize).max(1)
+}
+
+fn analyze_ivfflat_lists(
+    column: &VectorColumn,
+    index: &VectorIndex,
+    results: &mut AnalysisResults,
+) {
+    let lists = index.lists.unwrap_or(IVFFLAT_DEFAULT_LISTS);
+    let recommended = recommended_lists(column.estimated_rows);
+    if lists * 2 >= recommended && lists <= recommended * 2 {
+        return;
+    }
+
+    let index_name = format!("{}.{}", column.schema, index.name);
+    add_suggestion(
+        results,
+        &format!("index {index_name} lists"),
+        &lists.to_string(),
+        &recommended.to_string(),
+        SuggestionLevel::Recommended,
+        Message::new("extensions.pgvector_ivfflat_lists")
+            .arg("index", index_name)
+            .arg("current", lists)
+            .arg("rows", column.estimated_rows)
+            .arg("recommended", recommended),
+    );
+}
+
+fn analyze_ivfflat_probes(
+    params: &HashMap<String, PgConfigParam>,
+    columns: &[&VectorColumn],
+    results: &mut AnalysisResults,
+) {
+    let Some(lists) = columns
+        .iter()
+        .flat_map(|column| &column.indexes)
+        .filter(|index| index.method == "ivfflat")
+        .map(|index| index.lists.unwrap_or(IVFFLAT_DEFAULT_LISTS))
+        .max()
+    else {
+        return;
+    };
+
+    // The setting only appears in pg_settings once the library is loaded
+    let probes = param_value(params, "ivfflat.probes")
+        .and_then(ParamValue::as_count)
+        .unwrap_or(IVFFLAT_DEFAULT_PROBES);
+    let recommended = ((lists as f64).sqrt().round() as usize).max(1);
+    if probes * 2 >= recommended {
+        return;
+    }
+
+    add_suggestion(
+        results,
+        "ivfflat.probes",
+        &probes.to_string(),
+        &recommended.to_string(),
+        SuggestionLevel::Recommended,
+        Message::new("extensions.pgvector_ivfflat_probes")
+            .arg("current", probes)
+            .arg("lists", lists)
+            .arg("recommended", recommended),
+    );
+}
+
+/// Candidate list size that keeps HNSW recall up as the graph grows
+fn recommended_ef_search(rows: i64) -> usize {
+    match rows {
+        rows if rows < 1_000_000 => HNSW_DEFAULT_EF_SEARCH,
+        rows if rows < 10_000_000 => 100,
+        _ => 200,
+    }
+}
+
+fn analyze_hnsw_ef_search(
+    params: &HashMap<String, PgConfigParam>,
+    columns: &[&VectorColumn],
+    results: &mut AnalysisResults,
+) {
+    let Some(largest) = columns
+        .iter()
+        .filter(|column| column.indexes.iter().any(|index| index.method == "hnsw"))
+        .max_by_key(|column| column.estimated_rows)
+    else {
+        return;
+    };
+
+    let ef_search = param_value(params, "hnsw.ef_search")
+        .and_then(ParamValue::as_count)
+        .unwrap_or(HNSW_DEFAULT_EF_SEARCH);
+    let recommended = recommended_ef_search(largest.estimated_rows);
+    if ef_search >= recommended {
+        return;
+    }
+
+    add_suggestion(
+        results,
+        "hnsw.ef_search",
+        &ef_search.to_string(),
+        &recommended.to_string(),
+        SuggestionLevel::Recommended,
+        Message::new("extensions.pgvector_hnsw_ef_search")
+            .arg("current", ef_search)
+            .arg(
+                "table",
+                format!("{}.{}", largest.schema, largest.table_name),
+            )
+            .arg("rows", largest.estimated_rows)
+            .arg("recommended", recommended),
+    );
+}
+
+/// Size of the HNSW graph for the column: the existing index, or an estimate
+/// from the row count and dimensions when it has no index yet
+fn hnsw_graph_bytes(column: &VectorColumn) -> Option<i64> {
+    if !column.indexes.is_empty() {
+        return column
+            .indexes
+            .iter()
+            .filter(|index| index.method == "hnsw")
+            .map(|index| index.size_bytes.0)
+            .max();
+    }
+    let bytes_per_dimension = match column.type_name.as_str() {
+        "vector" => 4,
+        "halfvec" => 2,
+        _ => return None,
+    };
+    let dimensions = i64::try_from(column.dimensions?).ok()?;
+    Some(column.estimated_rows * (bytes_per_dimension * dimensions + HNSW_ELEMENT_OVERHEAD_BYTES))
+}
+
+fn analyze_build_memory(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    columns: &[&VectorColumn],
+    results: &mut AnalysisResults,
+) {
+    let (Some(param), Some((column, graph_bytes))) = (
+        params.get("maintenance_work_mem"),
+        columns
+            .iter()
+            .filter_map(|column| Some((*column, hnsw_graph_bytes(column)?)))
+            .max_by_key(|(_, bytes)| *bytes),
+    ) else {
+        return;
+    };
+    let Some(current) = param
+        .value
+        .as_bytes()
+        .and_then(|bytes| i64::try_from(bytes).ok())
+    else {
+        return;
+    };
+    if graph_bytes <= current {
+        return;
+    }
+
+    // Round up to 64MB, but leave three quarters of RAM to everything else
+    let mut recommended = (graph_bytes + 64 * MB - 1) / (64 * MB) * (64 * MB);
+    if let Some(memory_gb) = stats.total_memory_gb {
+        recommended = recommended.min((memory_gb * 1024.0 * MB as f64 / 4.0) as i64 / MB * MB);
+    }
+    if recommended <= current {
+        return;
+    }
+
+    let recommended = format!("{}MB", recommended / MB);
+    add_suggestion(
+        results,
+        "maintenance_work_mem",
+        &param.current_value,
+        &recommended,
+        SuggestionLevel::Recommended,
+        Message::new("extensions.pgvector_build_memory")
+            .arg("table", format!("{}.{}", column.schema, column.table_name))
+            .arg("size", ByteSize(graph_bytes))
+            .arg("current", ByteSize(current))
+            .arg("recommended", &recommended),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ConfigCategory;
+    use rstest::rstest;
+
+    fn param(name: &str, value: &str, unit: Option<&str>) -> (String, PgConfigParam) {
+        (
+            name.to_string(),
+            PgConfigParam {
+                name: name.to_string(),
+                current_value: value.to_string(),
+                default_value: None,
+                unit: unit.map(str::to_string),
+                context: "user".to_string(),
+                value: ParamValue::parse(value, "integer", unit),
+                source: None,
+                sourcefile: None,
+                pending_restart: false,
+                min_value: None,
+                max_value: None,
+            },
+        )
+    }
+
+    fn column(rows: i64, indexes: Vec<VectorIndex>, calls: Option<i64>) -> VectorColumn {
+        VectorColumn {
+            schema: "public".to_string(),
+            table_name: "documents".to_string(),
+            column_name: "embedding".to_string(),
+            type_name: "vector".to_string(),
+            dimensions: Some(1536),
+            estimated_rows: rows,
+            indexes,
+            distance_calls: calls,
+            distance_operator: calls.map(|_| "<=>".to_string()),
+        }
+    }
+
+    fn index(method: &str, lists: Option<usize>, size_bytes: i64) -> VectorIndex {
+        VectorIndex {
+            name: format!("documents_embedding_{method}_idx"),
+            method: method.to_string(),
+            lists,
+            size_bytes: ByteSize(size_bytes),
+        }
+    }
+
+    fn analyze(params: &[(String, PgConfigParam)], columns: Vec<VectorColumn>) -> AnalysisResults {
+        let params: HashMap<_, _> = params.iter().cloned().collect();
+        let stats = SystemStats {
+            vector_columns: columns,
+            ..SystemStats::default()
+        };
+        let mut results = AnalysisResults::default();
+        analyze_pgvector(&params, &stats, &mut results).unwrap();
+        results
+    }
+
+    fn suggested<'a>(results: &'a AnalysisResults, parameter: &str) -> Option<&'a str> {
+        results
+            .suggestions_by_category
+            .get(&ConfigCategory::Extensions)?
+            .iter()
+            .find(|suggestion| suggestion.parameter == parameter)
+            .map(|suggestion| suggestion.suggested_value.as_str())
+    }
+
+    #[rstest]
+    #[case(
+        "SELECT id FROM documents ORDER BY embedding <=> $1 LIMIT $2",
+        Some("<=>")
+    )]
+    #[case("SELECT id FROM documents d ORDER BY $1 <-> d.embedding", Some("<->"))]
+    #[case("SELECT id FROM documents ORDER BY old_embedding <#> $1", None)]
+    #[case("SELECT embedding FROM documents WHERE id = $1", None)]
+    fn distance_operators_are_matched_to_the_column(
+        #[case] query: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(distance_operator_on(query, "embedding"), expected);
+    }
+
+    #[rstest]
+    #[case(Some(5_000), Some(SuggestionLevel::Important))]
+    #[case(Some(20), None)]
+    #[case(None, Some(SuggestionLevel::Recommended))]
+    fn queried_vector_columns_without_an_index_are_flagged(
+        #[case] calls: Option<i64>,
+        #[case] expected: Option<SuggestionLevel>,
+    ) {
+        let results = analyze(&[], vec![column(200_000, Vec::new(), calls)]);
+        let suggestion = results
+            .suggestions_by_category
+            .get(&ConfigCategory::Extensions)
+            .and_then(|suggestions| {
+                suggestions
+                    .iter()
+                    .find(|s| s.parameter == "vector column public.documents.embedding")
+            });
+        assert_eq!(suggestion.map(|s| s.level), expected);
+        if let Some(suggestion) = suggestion.filter(|_| calls.is_some()) {
+            assert!(suggestion
+                .rationale
+                .contains("USING hnsw (embedding vector_cosine_ops)"));
+        }
+    }
+
+    #[rstest]
+    #[case(1_000_000, None, Some("1000"))]
+    #[case(4_000_000, Some(2000), None)]
+    #[case(50_000, None, None)]
+    #[case(20_000, Some(1000), Some("20"))]
+    fn ivfflat_lists_follow_the_table_size(
+        #[case] rows: i64,
+        #[case] lists: Option<usize>,
+        #[case] expected: Option<&str>,
+    ) {
+        let results = analyze(
+            &[],
+            vec![column(rows, vec![index("ivfflat", lists, MB)], Some(1_000))],
+        );
+        assert_eq!(
+            suggested(
+                &results,
+                "index public.documents_embedding_ivfflat_idx lists"
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn search_settings_and_build_memory_follow_the_indexes() {
+        let mut large = column(5_000_000, vec![index("hnsw", None, 12 * 1024 * MB)], None);
+        large.table_name = "chunks".to_string();
+        let results = analyze(
+            &[
+                param("maintenance_work_mem", "65536", Some("kB")),
+                param("hnsw.ef_search", "40", None),
+            ],
+            vec![
+                large,
+                column(
+                    1_000_000,
+                    vec![index("ivfflat", Some(1000), 64 * MB)],
+                    Some(1_000),
+                ),
+            ],
+        );
+
+        assert_eq!(suggested(&results, "hnsw.ef_search"), Some("100"));
+        assert_eq!(suggested(&results, "ivfflat.probes"), Some("32"));
+        assert_eq!(suggested(&results, "maintenance_work_mem"), Some("12288MB"));
+    }
+}
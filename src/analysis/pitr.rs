@@ -0,0 +1,582 @@
+use crate::analysis::{managed, replicas};
+use crate::checker::CheckerError;
+use crate::config::DbConfig;
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ArchiverStatus, ByteSize, ConfigCategory, ConfigSuggestion, ParamValue,
+    PgConfigParam, PitrReadiness, ReplicationSlot, StandbySettings, SuggestionLevel, SystemStats,
+};
+use sqlx::{Pool, Postgres, Row};
+use std::collections::HashMap;
+use tracing::warn;
+
+type Result<T> = std::result::Result<T, CheckerError>;
+
+const GB: i64 = 1024 * 1024 * 1024;
+/// Inactive slots holding more WAL than this make an unlimited
+/// max_slot_wal_keep_size important
+const INACTIVE_SLOT_WARN_BYTES: i64 = GB;
+/// Floor for the suggested max_slot_wal_keep_size
+const MIN_SLOT_WAL_KEEP: i64 = 10 * GB;
+/// wal_keep_size should cover this much WAL generation, and at least 1GB
+const WAL_KEEP_SECS: f64 = 3600.0;
+/// Examples from the PostgreSQL documentation; the rationale asks for the real archive
+const ARCHIVE_COMMAND_EXAMPLE: &str = "test ! -f /mnt/wal_archive/%f && cp %p /mnt/wal_archive/%f";
+const RESTORE_COMMAND_EXAMPLE: &str = "cp /mnt/wal_archive/%f %p";
+
+pub(crate) const REPLICATION_SLOTS_QUERY: &str = r#"
+    SELECT
+        slot_name::text,
+        slot_type,
+        active,
+        CASE WHEN pg_is_in_recovery() OR restart_lsn IS NULL THEN 0
+             ELSE pg_wal_lsn_diff(pg_current_wal_lsn(), restart_lsn)::bigint
+        END AS retained_wal,
+        to_jsonb(s) ->> 'wal_status' AS wal_status
+    FROM pg_replication_slots s
+    ORDER BY slot_name
+"#;
+
+pub async fn fetch_replication_slots(pool: &Pool<Postgres>) -> Vec<ReplicationSlot> {
+    match sqlx::query(REPLICATION_SLOTS_QUERY).fetch_all(pool).await {
+        Ok(rows) => rows
+            .into_iter()
+            .map(|row| ReplicationSlot {
+                slot_name: row.get("slot_name"),
+                slot_type: row.get("slot_type"),
+                active: row.get("active"),
+                retained_wal: ByteSize(row.get("retained_wal")),
+                wal_status: row.get("wal_status"),
+            })
+            .collect(),
+        Err(err) => {
+            warn!("Failed to read pg_replication_slots: {err}");
+            Vec::new()
+        }
+    }
+}
+
+pub(crate) const ARCHIVER_QUERY: &str = r#"
+    SELECT
+        archived_count,
+        failed_count,
+        to_char(last_archived_time AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS"Z"') AS last_archived_at,
+        to_char(last_failed_time AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS"Z"') AS last_failed_at,
+        last_failed_wal
+    FROM pg_stat_archiver
+"#;
+
+pub async fn fetch_archiver(pool: &Pool<Postgres>) -> Option<ArchiverStatus> {
+    match sqlx::query(ARCHIVER_QUERY).fetch_one(pool).await {
+        Ok(row) => Some(ArchiverStatus {
+            archived_count: row.get("archived_count"),
+            failed_count: row.get("failed_count"),
+            last_archived_at: row.get("last_archived_at"),
+            last_failed_at: row.get("last_failed_at"),
+            last_failed_wal: row.get("last_failed_wal"),
+        }),
+        Err(err) => {
+            warn!("Failed to read pg_stat_archiver: {err}");
+            None
+        }
+    }
+}
+
+pub(crate) const STANDBY_SETTINGS_QUERY: &str = r#"
+    SELECT
+        pg_is_in_recovery() AS in_recovery,
+        current_setting('restore_command') AS restore_command,
+        (SELECT setting::bigint FROM pg_settings WHERE name = 'recovery_min_apply_delay') AS delay_ms,
+        current_setting('primary_slot_name') AS primary_slot_name
+"#;
+
+/// Reads the recovery settings of every configured replica (PG12+, where
+/// they are ordinary settings); unreachable replicas are left out
+pub async fn fetch_standby_settings(config: &DbConfig) -> Vec<StandbySettings> {
+    if config.replicas.is_empty() {
+        return Vec::new();
+    }
+    let (connected, _) = replicas::connect(config).await;
+    let mut standbys = Vec::new();
+    for replica in connected {
+        match sqlx::query(STANDBY_SETTINGS_QUERY)
+            .fetch_one(&replica.pool)
+            .await
+        {
+            Ok(row) => standbys.push(StandbySettings {
+                label: replica.label,
+                in_recovery: row.get("in_recovery"),
+                restore_command: row.get("restore_command"),
+                recovery_min_apply_delay_ms: row
+                    .get::<Option<i64>, _>("delay_ms")
+                    .unwrap_or(0)
+                    .max(0) as u64,
+                primary_slot_name: row.get("primary_slot_name"),
+            }),
+            Err(err) => warn!(
+                "Failed to read recovery settings on replica {}: {err}",
+                replica.label
+            ),
+        }
+    }
+    standbys
+}
+
+/// Checks WAL archiving, standby restore_command and WAL retention, and sets
+/// an overall point-in-time recovery verdict listing the gaps. Skipped on
+/// managed services, which run archiving and PITR themselves.
+pub fn analyze_pitr(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    if managed::detect_platform(params).is_some() {
+        return Ok(());
+    }
+    let Some(archive_mode) = params.get("archive_mode") else {
+        return Ok(());
+    };
+    let archive_mode = archive_mode.current_value.trim().to_string();
+    let value = |name: &str| {
+        params
+            .get(name)
+            .map(|param| param.current_value.trim().to_string())
+    };
+    let mut gaps = Vec::new();
+    let mut notes = Vec::new();
+
+    if value("wal_level").as_deref() == Some("minimal") {
+        gaps.push(
+            "wal_level is minimal, which cannot be archived or replayed for PITR".to_string(),
+        );
+    }
+
+    let archive_command = value("archive_command").unwrap_or_default();
+    let archive_library = value("archive_library").unwrap_or_default();
+    let archiving = archive_mode != "off";
+    let archiver_set = (!archive_command.is_empty() && archive_command != "(disabled)")
+        || !archive_library.is_empty();
+    if !archiving {
+        gaps.push(
+            "archive_mode is off, so no WAL is archived to replay past a base backup".to_string(),
+        );
+        add_suggestion(
+            results,
+            "archive_mode",
+            &archive_mode,
+            "on",
+            SuggestionLevel::Important,
+            Message::new("backup.archive_mode"),
+        );
+    } else if !archiver_set {
+        gaps.push(
+            "archive_mode is on but neither archive_command nor archive_library is set, so WAL \
+             piles up in pg_wal"
+                .to_string(),
+        );
+        add_suggestion(
+            results,
+            "archive_command",
+            &archive_command,
+            ARCHIVE_COMMAND_EXAMPLE,
+            SuggestionLevel::Critical,
+            Message::new("backup.archive_command"),
+        );
+    } else if let Some(archiver) = &stats.archiver {
+        let failing = match (&archiver.last_failed_at, &archiver.last_archived_at) {
+            (Some(failed), Some(archived)) => failed > archived,
+            (Some(_), None) => true,
+            _ => false,
+        };
+        if failing {
+            gaps.push(format!(
+                "archiving has failed since {} (last failed segment {}); WAL after it is not archived",
+                archiver.last_failed_at.as_deref().unwrap_or_default(),
+                archiver.last_failed_wal.as_deref().unwrap_or("unknown")
+            ));
+        }
+    }
+
+    for standby in stats.standbys.iter().filter(|standby| standby.in_recovery) {
+        if standby.restore_command.is_empty() && archiving && archiver_set {
+            gaps.push(format!(
+                "standby {} has no restore_command and cannot catch up from the archive",
+                standby.label
+            ));
+            add_suggestion(
+                results,
+                &format!("standby {} restore_command", standby.label),
+                "",
+                RESTORE_COMMAND_EXAMPLE,
+                SuggestionLevel::Important,
+                Message::new("backup.restore_command").arg("standby", &standby.label),
+            );
+        }
+        if standby.recovery_min_apply_delay_ms > 0 {
+            let delay = format_delay(standby.recovery_min_apply_delay_ms);
+            notes.push(if standby.primary_slot_name.is_empty() {
+                format!(
+                    "standby {} applies WAL {delay} late, a window to recover from mistakes without a full PITR",
+                    standby.label
+                )
+            } else {
+                format!(
+                    "standby {} applies WAL {delay} late through slot {}, which holds back {delay} of WAL on the primary",
+                    standby.label, standby.primary_slot_name
+                )
+            });
+        }
+    }
+    if stats.standbys.is_empty() {
+        notes.push(
+            "no standby recovery settings were read; list standbys under `replicas:` to check them"
+                .to_string(),
+        );
+    }
+
+    analyze_wal_retention(params, stats, &mut gaps, results);
+
+    results.pitr_readiness = Some(PitrReadiness {
+        ready: gaps.is_empty(),
+        gaps,
+        notes,
+    });
+    Ok(())
+}
+
+/// Lost and unbounded slots, and standbys that depend on the primary keeping
+/// WAL when no slot or wal_keep_size does
+fn analyze_wal_retention(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    gaps: &mut Vec<String>,
+    results: &mut AnalysisResults,
+) {
+    let slots = &stats.replication_slots;
+    for slot in slots
+        .iter()
+        .filter(|slot| slot.wal_status.as_deref() == Some("lost"))
+    {
+        gaps.push(format!(
+            "slot {} has lost required WAL; its consumer must be rebuilt",
+            slot.slot_name
+        ));
+        add_suggestion(
+            results,
+            &format!("slot {}", slot.slot_name),
+            "lost",
+            "drop",
+            SuggestionLevel::Critical,
+            Message::new("backup.lost_slot").arg("slot", &slot.slot_name),
+        );
+    }
+
+    let max_slot_keep = params.get("max_slot_wal_keep_size");
+    let unlimited = max_slot_keep.is_some_and(|param| param.value == ParamValue::Integer(-1));
+    if unlimited && !slots.is_empty() {
+        let stale = slots
+            .iter()
+            .filter(|slot| !slot.active)
+            .max_by_key(|slot| slot.retained_wal);
+        let active_peak = slots
+            .iter()
+            .filter(|slot| slot.active)
+            .map(|slot| slot.retained_wal.0)
+            .max()
+            .unwrap_or(0);
+        let suggested = round_up_gb((2 * active_peak).max(MIN_SLOT_WAL_KEEP));
+        let mut level = SuggestionLevel::Recommended;
+        let mut rationale = Message::new("backup.max_slot_wal_keep_size")
+            .arg("slots", slots.len())
+            .arg("suggested", format!("{}GB", suggested / GB));
+        if let Some(slot) = stale.filter(|slot| slot.retained_wal.0 > INACTIVE_SLOT_WARN_BYTES) {
+            gaps.push(format!(
+                "inactive slot {} holds back {} of WAL with no max_slot_wal_keep_size limit",
+                slot.slot_name, slot.retained_wal
+            ));
+            level = SuggestionLevel::Important;
+            rationale = Message::new("backup.max_slot_wal_keep_size.inactive")
+                .arg("slot", &slot.slot_name)
+                .arg("retained", slot.retained_wal)
+                .arg("suggested", format!("{}GB", suggested / GB));
+        }
+        add_suggestion(
+            results,
+            "max_slot_wal_keep_size",
+            &max_slot_keep
+                .map(|param| param.current_value.clone())
+                .unwrap_or_default(),
+            &format!("{}GB", suggested / GB),
+            level,
+            rationale,
+        );
+    }
+
+    // Standbys streaming without a slot rely on wal_keep_size or the archive
+    let slotless: Vec<&str> = stats
+        .standbys
+        .iter()
+        .filter(|standby| {
+            standby.in_recovery
+                && standby.primary_slot_name.is_empty()
+                && standby.restore_command.is_empty()
+        })
+        .map(|standby| standby.label.as_str())
+        .collect();
+    let wal_keep = params.get("wal_keep_size");
+    let keeps_nothing = wal_keep
+        .and_then(|param| param.value.as_bytes())
+        .is_some_and(|bytes| bytes == 0);
+    if !slotless.is_empty() && keeps_nothing {
+        let per_second = stats
+            .wal_bytes
+            .zip(stats.wal_stats_age_secs)
+            .map(|(bytes, secs)| bytes as f64 / secs.max(1) as f64)
+            .unwrap_or(0.0);
+        let suggested = round_up_gb(((per_second * WAL_KEEP_SECS) as i64).max(GB));
+        gaps.push(format!(
+            "standbys {} use no slot, restore_command or wal_keep_size and break once the primary recycles their WAL",
+            slotless.join(", ")
+        ));
+        add_suggestion(
+            results,
+            "wal_keep_size",
+            &wal_keep
+                .map(|param| param.current_value.clone())
+                .unwrap_or_default(),
+            &format!("{}GB", suggested / GB),
+            SuggestionLevel::Important,
+            Message::new("backup.wal_keep_size")
+                .arg("standbys", slotless.join(", "))
+                .arg("suggested", format!("{}GB", suggested / GB)),
+        );
+    }
+}
+
+fn round_up_gb(bytes: i64) -> i64 {
+    (bytes + GB - 1) / GB * GB
+}
+
+/// `3600000` → `1h`, `90000` → `90s`
+fn format_delay(ms: u64) -> String {
+    let secs = ms / 1000;
+    if secs >= 3600 && secs.is_multiple_of(3600) {
+        format!("{}h", secs / 3600)
+    } else if secs >= 60 && secs.is_multiple_of(60) {
+        format!("{}min", secs / 60)
+    } else if secs > 0 {
+        format!("{secs}s")
+    } else {
+        format!("{ms}ms")
+    }
+}
+
+fn add_suggestion(
+    results: &mut AnalysisResults,
+    parameter: &str,
+    current_value: &str,
+    suggested_value: &str,
+    level: SuggestionLevel,
+    rationale: Message,
+) {
+    results
+        .suggestions_by_category
+        .entry(ConfigCategory::Backup)
+        .or_default()
+        .push(ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: current_value.to_string(),
+            suggested_value: suggested_value.to_string(),
+            level,
+            rationale: rationale.render(Lang::En),
+            messages: vec![rationale],
+            fingerprint: String::new(),
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn params(settings: &[(&str, &str, &str, Option<&str>)]) -> HashMap<String, PgConfigParam> {
+        settings
+            .iter()
+            .map(|(name, value, vartype, unit)| {
+                let param = PgConfigParam {
+                    name: name.to_string(),
+                    current_value: value.to_string(),
+                    default_value: None,
+                    unit: unit.map(str::to_string),
+                    context: "sighup".to_string(),
+                    value: ParamValue::parse(value, vartype, *unit),
+                    source: None,
+                    sourcefile: None,
+                    pending_restart: false,
+                    min_value: None,
+                    max_value: None,
+                };
+                (param.name.clone(), param)
+            })
+            .collect()
+    }
+
+    fn archiving(command: &str) -> HashMap<String, PgConfigParam> {
+        params(&[
+            ("wal_level", "replica", "enum", None),
+            ("archive_mode", "on", "enum", None),
+            ("archive_command", command, "string", None),
+            ("max_slot_wal_keep_size", "-1", "integer", Some("MB")),
+            ("wal_keep_size", "0", "integer", Some("MB")),
+        ])
+    }
+
+    fn standby(label: &str, restore_command: &str, slot: &str) -> StandbySettings {
+        StandbySettings {
+            label: label.to_string(),
+            in_recovery: true,
+            restore_command: restore_command.to_string(),
+            recovery_min_apply_delay_ms: 0,
+            primary_slot_name: slot.to_string(),
+        }
+    }
+
+    fn slot(name: &str, active: bool, retained_wal: i64, wal_status: &str) -> ReplicationSlot {
+        ReplicationSlot {
+            slot_name: name.to_string(),
+            slot_type: "physical".to_string(),
+            active,
+            retained_wal: ByteSize(retained_wal),
+            wal_status: Some(wal_status.to_string()),
+        }
+    }
+
+    fn analyzed(
+        params: &HashMap<String, PgConfigParam>,
+        stats: &SystemStats,
+    ) -> (PitrReadiness, Vec<ConfigSuggestion>) {
+        let mut results = AnalysisResults::default();
+        analyze_pitr(params, stats, &mut results).unwrap();
+        let suggestions = results
+            .suggestions_by_category
+            .remove(&ConfigCategory::Backup)
+            .unwrap_or_default();
+        (results.pitr_readiness.unwrap(), suggestions)
+    }
+
+    #[test]
+    fn archived_server_with_slotted_standbys_is_ready() {
+        let stats = SystemStats {
+            standbys: vec![StandbySettings {
+                recovery_min_apply_delay_ms: 3_600_000,
+                ..standby("replica1", RESTORE_COMMAND_EXAMPLE, "replica1")
+            }],
+            replication_slots: vec![slot("replica1", true, 64 * 1024 * 1024, "reserved")],
+            ..Default::default()
+        };
+        let mut params = archiving(ARCHIVE_COMMAND_EXAMPLE);
+        params.extend(self::params(&[(
+            "max_slot_wal_keep_size",
+            "20480",
+            "integer",
+            Some("MB"),
+        )]));
+        let (readiness, suggestions) = analyzed(&params, &stats);
+        assert!(readiness.ready, "{:?}", readiness.gaps);
+        assert!(suggestions.is_empty());
+        assert_eq!(
+            readiness.notes,
+            ["standby replica1 applies WAL 1h late through slot replica1, which holds back 1h of WAL on the primary"]
+        );
+    }
+
+    #[rstest]
+    #[case("off", "", "archive_mode", "on")]
+    #[case("on", "", "archive_command", ARCHIVE_COMMAND_EXAMPLE)]
+    #[case("on", "(disabled)", "archive_command", ARCHIVE_COMMAND_EXAMPLE)]
+    fn missing_archiving_is_a_gap(
+        #[case] mode: &str,
+        #[case] command: &str,
+        #[case] parameter: &str,
+        #[case] suggested: &str,
+    ) {
+        let mut params = archiving(command);
+        params.extend(self::params(&[("archive_mode", mode, "enum", None)]));
+        let (readiness, suggestions) = analyzed(&params, &SystemStats::default());
+        assert!(!readiness.ready);
+        assert_eq!(readiness.gaps.len(), 1);
+        assert_eq!(suggestions[0].parameter, parameter);
+        assert_eq!(suggestions[0].suggested_value, suggested);
+    }
+
+    #[test]
+    fn failing_archiver_is_a_gap() {
+        let stats = SystemStats {
+            archiver: Some(ArchiverStatus {
+                archived_count: 10,
+                failed_count: 3,
+                last_archived_at: Some("2024-05-01T10:00:00Z".to_string()),
+                last_failed_at: Some("2024-05-01T11:00:00Z".to_string()),
+                last_failed_wal: Some("00000001000000000000000B".to_string()),
+            }),
+            ..Default::default()
+        };
+        let (readiness, suggestions) = analyzed(&archiving(ARCHIVE_COMMAND_EXAMPLE), &stats);
+        assert!(readiness.gaps[0].contains("00000001000000000000000B"));
+        assert!(suggestions.is_empty());
+    }
+
+    #[test]
+    fn standbys_without_restore_command_or_slot_are_flagged() {
+        let stats = SystemStats {
+            standbys: vec![standby("replica1", "", "")],
+            wal_bytes: Some(3600 * 1024 * 1024),
+            wal_stats_age_secs: Some(3600),
+            ..Default::default()
+        };
+        let (readiness, suggestions) = analyzed(&archiving(ARCHIVE_COMMAND_EXAMPLE), &stats);
+        assert_eq!(readiness.gaps.len(), 2);
+        let parameters: Vec<&str> = suggestions.iter().map(|s| s.parameter.as_str()).collect();
+        assert_eq!(
+            parameters,
+            ["standby replica1 restore_command", "wal_keep_size"]
+        );
+        // 1MB/s for an hour rounds up to 4GB
+        assert_eq!(suggestions[1].suggested_value, "4GB");
+    }
+
+    #[test]
+    fn unlimited_slot_retention_is_capped() {
+        let stats = SystemStats {
+            replication_slots: vec![
+                slot("replica1", true, 8 * GB, "extended"),
+                slot("old_subscriber", false, 40 * GB, "extended"),
+                slot("gone", false, 0, "lost"),
+            ],
+            ..Default::default()
+        };
+        let (readiness, suggestions) = analyzed(&archiving(ARCHIVE_COMMAND_EXAMPLE), &stats);
+        assert_eq!(readiness.gaps.len(), 2);
+        assert_eq!(suggestions[0].parameter, "slot gone");
+        assert_eq!(suggestions[0].level, SuggestionLevel::Critical);
+        assert_eq!(suggestions[1].parameter, "max_slot_wal_keep_size");
+        assert_eq!(suggestions[1].suggested_value, "16GB");
+        assert_eq!(suggestions[1].level, SuggestionLevel::Important);
+        assert!(suggestions[1].rationale.contains("old_subscriber"));
+    }
+
+    #[test]
+    fn managed_platforms_are_skipped() {
+        let mut params = archiving("");
+        params.extend(self::params(&[(
+            "cloudsql.iam_authentication",
+            "on",
+            "bool",
+            None,
+        )]));
+        let mut results = AnalysisResults::default();
+        analyze_pitr(&params, &SystemStats::default(), &mut results).unwrap();
+        assert!(results.pitr_readiness.is_none());
+        assert!(results.suggestions_by_category.is_empty());
+    }
+}
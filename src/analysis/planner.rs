@@ -1,5 +1,9 @@
+use crate::analysis::param_value;
 use crate::checker::CheckerError;
-use crate::models::{AnalysisResults, ConfigCategory, ConfigSuggestion, SuggestionLevel};
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ConfigCategory, ConfigSuggestion, ParamValue, SuggestionLevel,
+};
 use std::collections::HashMap;
 
 type Result<T> = std::result::Result<T, CheckerError>;
@@ -12,7 +16,9 @@ pub fn analyze_planner(
 ) -> Result<()> {
     analyze_random_page_cost(params, stats, results)?;
     analyze_effective_io_concurrency(params, stats, results)?;
+    analyze_maintenance_io_concurrency(params, stats, results)?;
     analyze_seq_page_cost(params, results)?;
+    analyze_default_statistics_target(params, stats, results)?;
 
     Ok(())
 }
@@ -23,59 +29,77 @@ fn analyze_random_page_cost(
     results: &mut AnalysisResults,
 ) -> Result<()> {
     let current_value = get_param_value(params, "random_page_cost");
-    let current = current_value.parse::<f64>().unwrap_or(4.0);
-
-    // Recommendation depends on storage type
-    let target_str = match stats.storage_type {
-        crate::config::StorageType::Ssd => "1.1",
-        crate::config::StorageType::Hdd => "4.0",
-    };
-
-    if stats.storage_type == crate::config::StorageType::Ssd {
-        // For SSD, we want it low (1.1)
-        if current > 2.0 {
-            add_suggestion(
-                results,
-                ConfigCategory::Planner,
-                "random_page_cost",
-                &current_value,
-                target_str,
-                if current == 4.0 {
-                    SuggestionLevel::Critical
-                } else {
-                    SuggestionLevel::Important
-                },
-                "random_page_cost is set for HDDs (default 4.0), but you are using SSD storage. \
-                 On SSDs, random reads are nearly as fast as sequential reads. Setting this to 1.1 \
-                 (combined with high effective_cache_size) tells the planner to trust and use indexes \
-                 instead of always choosing sequential scans. This is MANDATORY for modern storage.",
-            );
-        } else if current > 1.5 {
-            add_suggestion(
-                results,
-                ConfigCategory::Planner,
-                "random_page_cost",
-                &current_value,
-                target_str,
-                SuggestionLevel::Recommended,
-                "random_page_cost could be lowered to 1.1 for better index utilization on SSD storage.",
-            );
+    let current = param_value(params, "random_page_cost")
+        .and_then(ParamValue::as_f64)
+        .unwrap_or(4.0);
+
+    match stats.storage_type {
+        crate::config::StorageType::Ssd => {
+            // For SSD, we want it low (1.1)
+            if current > 2.0 {
+                add_suggestion(
+                    results,
+                    ConfigCategory::Planner,
+                    "random_page_cost",
+                    &current_value,
+                    "1.1",
+                    if current == 4.0 {
+                        SuggestionLevel::Critical
+                    } else {
+                        SuggestionLevel::Important
+                    },
+                    Message::new("planner.random_page_cost.hdd_value_on_ssd"),
+                );
+            } else if current > 1.5 {
+                add_suggestion(
+                    results,
+                    ConfigCategory::Planner,
+                    "random_page_cost",
+                    &current_value,
+                    "1.1",
+                    SuggestionLevel::Recommended,
+                    Message::new("planner.random_page_cost.lower_for_ssd"),
+                );
+            }
         }
-    } else {
-        // For HDD, we want it high (4.0)
-        if current < 3.0 {
-            add_suggestion(
-                results,
-                ConfigCategory::Planner,
-                "random_page_cost",
-                &current_value,
-                target_str,
-                SuggestionLevel::Recommended,
-                "random_page_cost is too low for HDD storage. Random I/O is much more expensive \
-                 than sequential I/O on spinning disks. Increasing this to 4.0 (the default) \
-                 prevents the planner from optimistically choosing index scans that will cause \
-                 disk thrashing.",
-            );
+        crate::config::StorageType::Network => {
+            // Network-attached SSD volumes: random reads are cheap, but each I/O pays a
+            // network round-trip, so stay slightly above the local-SSD value.
+            if current > 2.0 {
+                add_suggestion(
+                    results,
+                    ConfigCategory::Planner,
+                    "random_page_cost",
+                    &current_value,
+                    "1.5",
+                    SuggestionLevel::Important,
+                    Message::new("planner.random_page_cost.hdd_value_on_network"),
+                );
+            } else if current < 1.1 {
+                add_suggestion(
+                    results,
+                    ConfigCategory::Planner,
+                    "random_page_cost",
+                    &current_value,
+                    "1.5",
+                    SuggestionLevel::Recommended,
+                    Message::new("planner.random_page_cost.too_low_for_network"),
+                );
+            }
+        }
+        crate::config::StorageType::Hdd => {
+            // For HDD, we want it high (4.0)
+            if current < 3.0 {
+                add_suggestion(
+                    results,
+                    ConfigCategory::Planner,
+                    "random_page_cost",
+                    &current_value,
+                    "4.0",
+                    SuggestionLevel::Recommended,
+                    Message::new("planner.random_page_cost.too_low_for_hdd"),
+                );
+            }
         }
     }
 
@@ -88,46 +112,86 @@ fn analyze_effective_io_concurrency(
     results: &mut AnalysisResults,
 ) -> Result<()> {
     let current_value = get_param_value(params, "effective_io_concurrency");
-    let current = current_value.parse::<u64>().unwrap_or(1);
+    let current = param_value(params, "effective_io_concurrency")
+        .and_then(ParamValue::as_i64)
+        .unwrap_or(1);
+
+    match stats.storage_type {
+        crate::config::StorageType::Ssd | crate::config::StorageType::Network => {
+            if current < 100 {
+                add_suggestion(
+                    results,
+                    ConfigCategory::Planner,
+                    "effective_io_concurrency",
+                    &current_value,
+                    "200",
+                    if current == 1 {
+                        SuggestionLevel::Important
+                    } else {
+                        SuggestionLevel::Recommended
+                    },
+                    Message::new("planner.effective_io_concurrency.fast_storage"),
+                );
+            }
+        }
+        crate::config::StorageType::Hdd => {
+            if current > 10 {
+                add_suggestion(
+                    results,
+                    ConfigCategory::Planner,
+                    "effective_io_concurrency",
+                    &current_value,
+                    "2",
+                    SuggestionLevel::Recommended,
+                    Message::new("planner.effective_io_concurrency.hdd"),
+                );
+            }
+        }
+    }
 
-    // Recommendation depends on storage type
-    let target_str = match stats.storage_type {
-        crate::config::StorageType::Ssd => "200",
-        crate::config::StorageType::Hdd => "2",
+    Ok(())
+}
+
+fn analyze_maintenance_io_concurrency(
+    params: &HashMap<String, crate::models::PgConfigParam>,
+    stats: &crate::models::SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    // maintenance_io_concurrency only exists on PostgreSQL 13+
+    let Some(param) = params.get("maintenance_io_concurrency") else {
+        return Ok(());
+    };
+    let current_value = param.current_value.clone();
+    let Some(current) = param.value.as_i64() else {
+        return Ok(());
     };
 
-    if stats.storage_type == crate::config::StorageType::Ssd {
-        if current < 100 {
-            add_suggestion(
-                results,
-                ConfigCategory::Planner,
-                "effective_io_concurrency",
-                &current_value,
-                target_str,
-                if current == 1 {
-                    SuggestionLevel::Important
-                } else {
-                    SuggestionLevel::Recommended
-                },
-                "effective_io_concurrency should be set to 200 for modern SSD/NVMe storage. \
-                 Default of 1 is for single disk HDDs. Modern storage can handle massive concurrency \
-                 and benefits from higher values for bitmap heap scans.",
-            );
+    match stats.storage_type {
+        crate::config::StorageType::Ssd | crate::config::StorageType::Network => {
+            if current < 100 {
+                add_suggestion(
+                    results,
+                    ConfigCategory::Planner,
+                    "maintenance_io_concurrency",
+                    &current_value,
+                    "200",
+                    SuggestionLevel::Recommended,
+                    Message::new("planner.maintenance_io_concurrency.fast_storage"),
+                );
+            }
         }
-    } else {
-        // HDD
-        if current > 10 {
-            add_suggestion(
-                results,
-                ConfigCategory::Planner,
-                "effective_io_concurrency",
-                &current_value,
-                target_str,
-                SuggestionLevel::Recommended,
-                "effective_io_concurrency is too high for HDD storage. Spinning disks have limited \
-                 IOPS and queue depth. Setting this too high (default for HDD is 1-2) can cause \
-                 excessive seek activity.",
-            );
+        crate::config::StorageType::Hdd => {
+            if current > 10 {
+                add_suggestion(
+                    results,
+                    ConfigCategory::Planner,
+                    "maintenance_io_concurrency",
+                    &current_value,
+                    "10",
+                    SuggestionLevel::Recommended,
+                    Message::new("planner.maintenance_io_concurrency.hdd"),
+                );
+            }
         }
     }
 
@@ -139,7 +203,9 @@ fn analyze_seq_page_cost(
     results: &mut AnalysisResults,
 ) -> Result<()> {
     let current_value = get_param_value(params, "seq_page_cost");
-    let current = current_value.parse::<f64>().unwrap_or(1.0);
+    let current = param_value(params, "seq_page_cost")
+        .and_then(ParamValue::as_f64)
+        .unwrap_or(1.0);
 
     // Should be 1.0, but check if it's been modified unusually
     if current != 1.0 {
@@ -150,14 +216,44 @@ fn analyze_seq_page_cost(
             &current_value,
             "1.0",
             SuggestionLevel::Info,
-            "seq_page_cost has been modified from the default of 1.0. Unless you have a specific reason, \
-             it's recommended to keep it at 1.0 and adjust random_page_cost instead for SSD/HDD tuning.",
+            Message::new("planner.seq_page_cost"),
         );
     }
 
     Ok(())
 }
 
+fn analyze_default_statistics_target(
+    params: &HashMap<String, crate::models::PgConfigParam>,
+    stats: &crate::models::SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    // The default of 100 is fine for OLTP point lookups; only profiles with skewed data
+    // and complex joins ask for larger histograms.
+    let Some(target) = stats.profile().thresholds().default_statistics_target else {
+        return Ok(());
+    };
+
+    let current_value = get_param_value(params, "default_statistics_target");
+    if let Some(current) =
+        param_value(params, "default_statistics_target").and_then(ParamValue::as_i64)
+    {
+        if current < i64::from(target) {
+            add_suggestion(
+                results,
+                ConfigCategory::Planner,
+                "default_statistics_target",
+                &current_value,
+                &target.to_string(),
+                SuggestionLevel::Recommended,
+                Message::new("planner.default_statistics_target"),
+            );
+        }
+    }
+
+    Ok(())
+}
+
 // Helper functions
 
 fn get_param_value(params: &HashMap<String, crate::models::PgConfigParam>, name: &str) -> String {
@@ -174,14 +270,16 @@ fn add_suggestion(
     current_value: &str,
     suggested_value: &str,
     level: SuggestionLevel,
-    rationale: &str,
+    rationale: Message,
 ) {
     let suggestion = ConfigSuggestion {
         parameter: parameter.to_string(),
         current_value: current_value.to_string(),
         suggested_value: suggested_value.to_string(),
         level,
-        rationale: rationale.to_string(),
+        rationale: rationale.render(Lang::En),
+        messages: vec![rationale],
+        fingerprint: String::new(),
     };
 
     results
@@ -205,6 +303,12 @@ mod tests {
             default_value: None,
             unit: None,
             context: "user".to_string(),
+            value: ParamValue::parse(value, "", None),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
         }
     }
 
@@ -271,4 +375,70 @@ mod tests {
 
         assert_eq!(suggestion.suggested_value, "200");
     }
+
+    #[test]
+    fn test_random_page_cost_network() {
+        let mut params = HashMap::new();
+        params.insert("random_page_cost".to_string(), create_param("4.0"));
+
+        let stats = SystemStats {
+            storage_type: StorageType::Network,
+            ..Default::default()
+        };
+
+        let mut results = AnalysisResults::default();
+        analyze_planner(&params, &stats, &mut results).unwrap();
+
+        let suggestion = results.suggestions_by_category[&ConfigCategory::Planner]
+            .iter()
+            .find(|s| s.parameter == "random_page_cost")
+            .expect("Should recommend 1.5 for network storage");
+
+        assert_eq!(suggestion.suggested_value, "1.5");
+        assert_eq!(suggestion.level, SuggestionLevel::Important);
+    }
+
+    #[test]
+    fn test_io_concurrency_hdd() {
+        let mut params = HashMap::new();
+        params.insert("effective_io_concurrency".to_string(), create_param("200"));
+        params.insert("maintenance_io_concurrency".to_string(), create_param("10"));
+
+        let stats = SystemStats {
+            storage_type: StorageType::Hdd,
+            ..Default::default()
+        };
+
+        let mut results = AnalysisResults::default();
+        analyze_planner(&params, &stats, &mut results).unwrap();
+
+        let suggestions = &results.suggestions_by_category[&ConfigCategory::Planner];
+        let suggestion = suggestions
+            .iter()
+            .find(|s| s.parameter == "effective_io_concurrency")
+            .expect("Should recommend 2 for HDD");
+
+        assert_eq!(suggestion.suggested_value, "2");
+        assert!(!suggestions
+            .iter()
+            .any(|s| s.parameter == "maintenance_io_concurrency"));
+    }
+
+    #[test]
+    fn test_default_statistics_target_olap_only() {
+        let mut params = HashMap::new();
+        params.insert("default_statistics_target".to_string(), create_param("100"));
+
+        let mut stats = SystemStats::default();
+        let mut results = AnalysisResults::default();
+        analyze_default_statistics_target(&params, &stats, &mut results).unwrap();
+        assert!(results.suggestions_by_category.is_empty());
+
+        stats.workload_type = crate::config::WorkloadType::Olap;
+        analyze_default_statistics_target(&params, &stats, &mut results).unwrap();
+        assert_eq!(
+            results.suggestions_by_category[&ConfigCategory::Planner][0].suggested_value,
+            "500"
+        );
+    }
 }
@@ -0,0 +1,474 @@
+/// Returns a fingerprint that is equal for statements differing only in literal
+/// values, placeholder numbers, list lengths, case or whitespace.
+///
+/// Before PostgreSQL 18, `pg_stat_statements` keeps `IN ($1, $2)` and
+/// `IN ($1, $2, $3)` as separate entries. Literals and `$n` placeholders become
+/// `?`, a bracketed list made only of `?` collapses to `(?)`, and repeated `(?)`
+/// rows in a `VALUES` list collapse to one row.
+pub(crate) fn fingerprint(query: &str) -> String {
+    collapse_lists(tokenize(query)).join(" ")
+}
+
+/// Rewrites query text so it can be shared without leaking data or schema.
+///
+/// String and numeric literals become `?` and comments are dropped. Table,
+/// column and alias names become `n_` plus a stable hash, so the same name maps
+/// to the same placeholder across a report and between runs. Reserved words,
+/// `$n` placeholders and names called as functions are kept readable; every
+/// part of a qualified name such as `public.events` is a name.
+pub(crate) fn redact(query: &str) -> String {
+    let chars: Vec<char> = query.chars().collect();
+    let mut redacted = String::with_capacity(query.len());
+    let mut previous_word = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '-' && chars.get(i + 1) == Some(&'-') {
+            while chars.get(i).is_some_and(|c| *c != '\n') {
+                i += 1;
+            }
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i = (i + 2).min(chars.len());
+        } else if c == '\'' {
+            i = skip_quoted(&chars, i, '\'');
+            redacted.push('?');
+        } else if c == '"' {
+            let end = skip_quoted(&chars, i, '"');
+            let name: String = chars[i + 1..end.saturating_sub(1).max(i + 1)]
+                .iter()
+                .collect();
+            redacted.push_str(&hashed_name(&name));
+            i = end;
+        } else if c == '$' && chars.get(i + 1).is_some_and(char::is_ascii_digit) {
+            redacted.push(c);
+            i += 1;
+            while let Some(digit) = chars.get(i).filter(|c| c.is_ascii_digit()) {
+                redacted.push(*digit);
+                i += 1;
+            }
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.get(i + 1).is_some_and(char::is_ascii_digit))
+        {
+            while chars
+                .get(i)
+                .is_some_and(|c| c.is_ascii_alphanumeric() || *c == '.')
+            {
+                i += 1;
+            }
+            redacted.push('?');
+        } else if c.is_alphanumeric() || c == '_' {
+            let start = i;
+            while chars
+                .get(i)
+                .is_some_and(|c| c.is_alphanumeric() || *c == '_' || *c == '$')
+            {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if chars.get(i) == Some(&'\'') && matches!(word.as_str(), "E" | "e" | "B" | "b") {
+                continue;
+            }
+            // The parts of `s.t` are judged by the word before the whole name, so
+            // `INTO s.t (a)` is still a table, and nothing after a dot is a keyword
+            let qualified = chars[..start]
+                .iter()
+                .rev()
+                .find(|c| !c.is_whitespace())
+                .is_some_and(|c| *c == '.');
+            let qualifier = chars[i..]
+                .iter()
+                .find(|c| !c.is_whitespace())
+                .is_some_and(|c| *c == '.');
+            // `INSERT INTO t (a, b)` is a table followed by a column list, not a call
+            let is_function = chars[i..]
+                .iter()
+                .find(|c| !c.is_whitespace())
+                .is_some_and(|c| *c == '(')
+                && !matches!(
+                    previous_word.as_str(),
+                    "into" | "table" | "references" | "from" | "join" | "update" | "only"
+                );
+            if is_function || (!qualified && is_keyword(&word)) {
+                redacted.push_str(&word);
+            } else {
+                redacted.push_str(&hashed_name(&word.to_lowercase()));
+            }
+            if !qualified && !qualifier {
+                previous_word = word.to_ascii_lowercase();
+            }
+        } else {
+            redacted.push(c);
+            i += 1;
+        }
+    }
+
+    redacted
+}
+
+/// PostgreSQL's reserved key words, which cannot name a table or column
+/// unquoted, plus the statement words a redacted query needs to stay readable.
+/// Unreserved keywords such as `name`, `status` or `password` are often column
+/// names, so they are redacted like any other name.
+const KEPT_KEYWORDS: &[&str] = &[
+    "all",
+    "analyse",
+    "analyze",
+    "and",
+    "any",
+    "array",
+    "as",
+    "asc",
+    "asymmetric",
+    "authorization",
+    "between",
+    "binary",
+    "both",
+    "case",
+    "cast",
+    "check",
+    "collate",
+    "collation",
+    "column",
+    "concurrently",
+    "constraint",
+    "create",
+    "cross",
+    "current_catalog",
+    "current_date",
+    "current_role",
+    "current_schema",
+    "current_time",
+    "current_timestamp",
+    "current_user",
+    "default",
+    "deferrable",
+    "delete",
+    "desc",
+    "distinct",
+    "do",
+    "else",
+    "end",
+    "except",
+    "exists",
+    "false",
+    "fetch",
+    "for",
+    "foreign",
+    "freeze",
+    "from",
+    "full",
+    "grant",
+    "group",
+    "having",
+    "ilike",
+    "in",
+    "initially",
+    "inner",
+    "insert",
+    "intersect",
+    "into",
+    "is",
+    "isnull",
+    "join",
+    "lateral",
+    "leading",
+    "left",
+    "like",
+    "limit",
+    "localtime",
+    "localtimestamp",
+    "natural",
+    "not",
+    "notnull",
+    "null",
+    "offset",
+    "on",
+    "only",
+    "or",
+    "order",
+    "outer",
+    "overlaps",
+    "placing",
+    "primary",
+    "references",
+    "returning",
+    "right",
+    "select",
+    "session_user",
+    "set",
+    "similar",
+    "some",
+    "symmetric",
+    "system_user",
+    "table",
+    "tablesample",
+    "then",
+    "to",
+    "trailing",
+    "true",
+    "union",
+    "unique",
+    "update",
+    "user",
+    "using",
+    "values",
+    "variadic",
+    "verbose",
+    "when",
+    "where",
+    "window",
+    "with",
+];
+
+fn is_keyword(word: &str) -> bool {
+    KEPT_KEYWORDS.contains(&word.to_ascii_lowercase().as_str())
+}
+
+/// FNV-1a, so placeholders stay the same between runs and releases
+fn hashed_name(name: &str) -> String {
+    let hash = name.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("n_{:08x}", hash as u32)
+}
+
+fn tokenize(query: &str) -> Vec<String> {
+    let chars: Vec<char> = query.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '\'' {
+            i = skip_quoted(&chars, i, '\'');
+            tokens.push("?".to_string());
+        } else if c == '"' {
+            let end = skip_quoted(&chars, i, '"');
+            tokens.push(chars[i..end].iter().collect());
+            i = end;
+        } else if c == '$' && chars.get(i + 1).is_some_and(char::is_ascii_digit) {
+            i += 1;
+            while chars.get(i).is_some_and(char::is_ascii_digit) {
+                i += 1;
+            }
+            tokens.push("?".to_string());
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.get(i + 1).is_some_and(char::is_ascii_digit))
+        {
+            while chars
+                .get(i)
+                .is_some_and(|c| c.is_ascii_alphanumeric() || *c == '.')
+            {
+                i += 1;
+            }
+            tokens.push("?".to_string());
+        } else if c.is_alphanumeric() || c == '_' {
+            let start = i;
+            while chars
+                .get(i)
+                .is_some_and(|c| c.is_alphanumeric() || *c == '_' || *c == '$')
+            {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            // E'...' and B'...' prefixes belong to the literal that follows
+            if chars.get(i) == Some(&'\'') && matches!(word.as_str(), "E" | "e" | "B" | "b") {
+                continue;
+            }
+            tokens.push(word.to_lowercase());
+        } else {
+            tokens.push(c.to_string());
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+/// Returns the index just past a quoted run starting at `start`, treating a
+/// doubled quote as an escaped one
+fn skip_quoted(chars: &[char], start: usize, quote: char) -> usize {
+    let mut i = start + 1;
+    while i < chars.len() {
+        if chars[i] == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    chars.len()
+}
+
+fn collapse_lists(tokens: Vec<String>) -> Vec<String> {
+    let mut collapsed: Vec<String> = Vec::with_capacity(tokens.len());
+
+    for token in tokens {
+        let closes_list = matches!(token.as_str(), ")" | "]");
+        collapsed.push(token);
+        if closes_list {
+            collapse_placeholder_list(&mut collapsed);
+            collapse_repeated_row(&mut collapsed);
+        }
+    }
+
+    collapsed
+}
+
+/// Rewrites a trailing `( ? , ? , ... )` as `( ? )`
+fn collapse_placeholder_list(tokens: &mut Vec<String>) {
+    let close = tokens.len() - 1;
+    let mut i = close;
+    let mut expect_placeholder = true;
+    while i > 0 {
+        i -= 1;
+        let token = tokens[i].as_str();
+        match (expect_placeholder, token) {
+            (true, "?") => expect_placeholder = false,
+            (false, ",") => expect_placeholder = true,
+            (false, "(" | "[") => {
+                if close - i > 2 {
+                    tokens.drain(i + 2..close);
+                }
+                return;
+            }
+            _ => return,
+        }
+    }
+}
+
+/// Drops a trailing `, ( ? )` row that repeats the one before it
+fn collapse_repeated_row(tokens: &mut Vec<String>) {
+    const ROW: [&str; 3] = ["(", "?", ")"];
+    let len = tokens.len();
+    if len >= 7
+        && tokens[len - 3..].iter().map(String::as_str).eq(ROW)
+        && tokens[len - 4] == ","
+        && tokens[len - 7..len - 4].iter().map(String::as_str).eq(ROW)
+    {
+        tokens.truncate(len - 4);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::in_list_length(
+        "SELECT * FROM orders WHERE id IN ($1, $2)",
+        "select *  from orders where id in ($1,$2,$3,$4)"
+    )]
+    #[case::literal_values(
+        "SELECT * FROM orders WHERE status = 'open' AND total > 10.5",
+        "SELECT * FROM orders WHERE status = 'it''s closed' AND total > 3"
+    )]
+    #[case::array_literal(
+        "SELECT * FROM orders WHERE id = ANY(ARRAY[1, 2, 3])",
+        "SELECT * FROM orders WHERE id = ANY(ARRAY[$1])"
+    )]
+    #[case::multi_row_values(
+        "INSERT INTO events (kind, payload) VALUES ($1, $2), ($3, $4), ($5, $6)",
+        "INSERT INTO events (kind, payload) VALUES ($1, $2)"
+    )]
+    fn variants_share_a_fingerprint(#[case] left: &str, #[case] right: &str) {
+        assert_eq!(fingerprint(left), fingerprint(right));
+    }
+
+    #[rstest]
+    #[case::different_columns(
+        "SELECT * FROM orders WHERE id IN ($1, $2)",
+        "SELECT * FROM orders WHERE customer_id IN ($1, $2)"
+    )]
+    #[case::different_tables(
+        "SELECT * FROM orders WHERE id = $1",
+        "SELECT * FROM invoices WHERE id = $1"
+    )]
+    #[case::quoted_identifier_case("SELECT \"Total\" FROM orders", "SELECT \"total\" FROM orders")]
+    #[case::column_list_is_not_a_value_list(
+        "SELECT * FROM orders WHERE (a, b) = ($1, $2)",
+        "SELECT * FROM orders WHERE (a) = ($1)"
+    )]
+    fn different_statements_keep_distinct_fingerprints(#[case] left: &str, #[case] right: &str) {
+        assert_ne!(fingerprint(left), fingerprint(right));
+    }
+
+    #[test]
+    fn redaction_hides_literals_and_names_but_keeps_structure() {
+        let redacted = redact(
+            "SELECT o.total, lower(c.email) FROM orders o JOIN customers c ON c.id = o.customer_id \
+             WHERE c.email = 'jane@example.com' AND o.total > 10.5 AND o.note = E'x' \
+             LIMIT $1 -- account 42",
+        );
+
+        for leaked in [
+            "orders",
+            "customers",
+            "email",
+            "customer_id",
+            "jane",
+            "10.5",
+            "42",
+        ] {
+            assert!(
+                !redacted.contains(leaked),
+                "{leaked} leaked into {redacted}"
+            );
+        }
+        let insert = redact("INSERT INTO events (kind) VALUES ('signup')");
+        for leaked in ["events", "kind", "signup"] {
+            assert!(!insert.contains(leaked), "{leaked} leaked into {insert}");
+        }
+        let qualified = redact("INSERT INTO public.events (kind) SELECT pg_catalog.lower($1)");
+        for leaked in ["public", "events", "kind"] {
+            assert!(
+                !qualified.contains(leaked),
+                "{leaked} leaked into {qualified}"
+            );
+        }
+        assert!(qualified.contains(".lower($1)"));
+        assert!(redacted.starts_with("SELECT n_"));
+        assert!(redacted.contains("lower(n_"));
+        assert!(redacted.contains(" = ? AND "));
+        assert!(insert.contains("VALUES (?)"));
+        assert!(redacted.contains("LIMIT $1"));
+    }
+
+    #[test]
+    fn redaction_hides_column_names_that_are_unreserved_keywords() {
+        let redacted = redact(
+            "SELECT name, status, location FROM users WHERE password = $1 AND u.user_id = $2",
+        );
+        for leaked in ["name", "status", "location", "users", "password", "user_id"] {
+            assert!(
+                !redacted.contains(leaked),
+                "{leaked} leaked into {redacted}"
+            );
+        }
+        assert!(redacted.starts_with("SELECT n_"));
+        assert!(redacted.contains(" FROM n_"));
+        assert!(redacted.contains(" WHERE n_"));
+    }
+
+    #[test]
+    fn redaction_maps_names_consistently() {
+        let first = redact("SELECT id FROM orders WHERE \"Orders\".id = $1");
+        let second = redact("DELETE FROM ORDERS WHERE id = $1");
+
+        let orders = hashed_name("orders");
+        assert_eq!(first.matches(&orders).count(), 1);
+        assert!(first.contains(&hashed_name("Orders")));
+        assert!(second.contains(&orders));
+        assert!(first.contains(&hashed_name("id")));
+    }
+}
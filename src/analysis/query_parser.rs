@@ -1,6 +1,7 @@
 use sqlparser::ast::{
-    BinaryOperator, Expr, FromTable, Join, JoinConstraint, OrderByExpr, Query, Select, SelectItem,
-    SetExpr, Statement, TableFactor, TableWithJoins,
+    BinaryOperator, Delete, Expr, FromTable, Function, FunctionArg, FunctionArgExpr,
+    FunctionArguments, Join, JoinConstraint, OrderByExpr, Query, Select, SelectItem, SetExpr,
+    Statement, TableFactor, TableWithJoins, UnaryOperator, Value,
 };
 use sqlparser::dialect::PostgreSqlDialect;
 use sqlparser::parser::{Parser, ParserError};
@@ -27,12 +28,135 @@ pub struct TableColumnUsage {
     pub non_equality_filters: Vec<String>,
     pub equality_joins: Vec<String>,
     pub orders: Vec<String>,
+    /// Subset of `orders` sorted `DESC`
+    pub descending_orders: Vec<String>,
+    /// Predicates every matching row must satisfy, such as `deleted_at IS NULL`
+    pub constant_predicates: Vec<ConstantPredicate>,
+    /// Filters on a function of a column, such as `lower(email) = $1`, which a
+    /// plain index on the column cannot serve
+    pub expression_filters: Vec<ExpressionFilter>,
+    /// `jsonb` operators only a GIN index can serve, such as `payload @> $1`
+    pub jsonb_filters: Vec<JsonbFilter>,
+    /// Pattern and full-text matches a B-tree cannot serve
+    pub text_search_filters: Vec<TextSearchFilter>,
+    /// Bounding-box overlap (`&&`) and PostGIS predicates such as `ST_Intersects`
+    pub spatial_filters: Vec<SpatialFilter>,
+}
+
+/// PostGIS predicates that use a spatial index on their geometry arguments;
+/// `ST_Disjoint` is absent because no index can serve it
+const SPATIAL_PREDICATES: &[&str] = &[
+    "ST_3DDWithin",
+    "ST_3DIntersects",
+    "ST_Contains",
+    "ST_ContainsProperly",
+    "ST_CoveredBy",
+    "ST_Covers",
+    "ST_Crosses",
+    "ST_DFullyWithin",
+    "ST_DWithin",
+    "ST_Equals",
+    "ST_Intersects",
+    "ST_Overlaps",
+    "ST_Touches",
+    "ST_Within",
+];
+
+/// A column compared with `&&` or passed to a spatial predicate, which only a
+/// GiST, SP-GiST or BRIN index can serve
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpatialFilter {
+    pub column: String,
+    /// `&&` or the predicate as PostGIS documents it, e.g. `ST_Intersects`
+    pub operator: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextSearchFilter {
+    /// `ILIKE`, `LIKE` with a leading wildcard or placeholder pattern, or a regex match
+    Pattern { column: String },
+    /// `@@` against `to_tsvector(...)` over a column, or against a `tsvector` column
+    FullText { column: String, expression: String },
+}
+
+impl TextSearchFilter {
+    pub fn column(&self) -> &str {
+        match self {
+            TextSearchFilter::Pattern { column } | TextSearchFilter::FullText { column, .. } => {
+                column
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonbFilter {
+    pub column: String,
+    pub operator: JsonbOperator,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonbOperator {
+    /// `@>` / `<@`, supported by both `jsonb_ops` and `jsonb_path_ops`
+    Containment,
+    /// `?`, `?|`, `?&`, supported only by the default `jsonb_ops`
+    KeyExists,
+}
+
+/// A filtered expression over a single column, rendered with the column unqualified
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpressionFilter {
+    pub expression: String,
+    pub column: String,
+    pub equality: bool,
+}
+
+/// A filter comparing a column against a constant, taken from the top-level
+/// `AND` chain of a WHERE clause so it holds for every row the statement reads
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConstantPredicate {
+    pub column: String,
+    pub test: PredicateTest,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PredicateTest {
+    IsNull,
+    IsNotNull,
+    /// Equality with a literal, kept as SQL text (`'active'`, `42`, `false`)
+    Equals(String),
+}
+
+impl ConstantPredicate {
+    pub fn to_sql(&self) -> String {
+        match &self.test {
+            PredicateTest::IsNull => format!("{} IS NULL", self.column),
+            PredicateTest::IsNotNull => format!("{} IS NOT NULL", self.column),
+            PredicateTest::Equals(literal) => format!("{} = {}", self.column, literal),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct QueryColumnUsage {
     pub tables: Vec<TableRef>,
     pub usage_by_table: HashMap<String, TableColumnUsage>,
+    /// `LIMIT`/`OFFSET` of the outermost query
+    pub pagination: Pagination,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pagination {
+    /// `LIMIT` or `FETCH FIRST` is present
+    pub limited: bool,
+    pub offset: Option<PaginationOffset>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaginationOffset {
+    Rows(u64),
+    /// Normalized by pg_stat_statements, so the actual offset is unknown
+    Placeholder,
 }
 
 #[derive(Debug)]
@@ -48,6 +172,7 @@ enum ColumnKind {
     NonEqualityFilter,
     EqualityJoin,
     Order,
+    DescendingOrder,
 }
 
 pub fn parse_query_columns(query: &str) -> Result<QueryColumnUsage, ParserError> {
@@ -69,26 +194,49 @@ struct QueryColumnCollector {
     tables: Vec<TableRef>,
     alias_map: HashMap<String, String>,
     pending: Vec<PendingColumn>,
+    pending_predicates: Vec<(Option<String>, ConstantPredicate)>,
+    pending_expressions: Vec<(Option<String>, ExpressionFilter)>,
+    pending_jsonb: Vec<(Option<String>, JsonbFilter)>,
+    pending_text_search: Vec<(Option<String>, TextSearchFilter)>,
+    pending_spatial: Vec<(Option<String>, SpatialFilter)>,
     resolved_usage_by_table: HashMap<String, TableColumnUsage>,
+    pagination: Pagination,
 }
 
 impl QueryColumnCollector {
     fn collect_statement(&mut self, statement: &Statement) -> Result<(), ParserError> {
         match statement {
-            Statement::Query(query) => self.collect_query(query),
+            Statement::Query(query) => {
+                self.pagination = Pagination {
+                    limited: query.limit.is_some() || query.fetch.is_some(),
+                    offset: query
+                        .offset
+                        .as_ref()
+                        .and_then(|offset| match &offset.value {
+                            Expr::Value(Value::Number(rows, _)) => {
+                                rows.parse().ok().map(PaginationOffset::Rows)
+                            }
+                            Expr::Value(Value::Placeholder(_)) => {
+                                Some(PaginationOffset::Placeholder)
+                            }
+                            _ => None,
+                        }),
+                };
+                self.collect_query(query)
+            }
             Statement::Update {
                 table,
                 from,
                 selection,
                 ..
             } => self.collect_update(table, from, selection),
-            Statement::Delete {
+            Statement::Delete(Delete {
                 from,
                 using,
                 selection,
                 order_by,
                 ..
-            } => self.collect_delete(from, using, selection, order_by),
+            }) => self.collect_delete(from, using, selection, order_by),
             _ => return Err(ParserError::ParserError("unsupported statement".into())),
         }
         Ok(())
@@ -116,6 +264,7 @@ impl QueryColumnCollector {
 
         if let Some(filter) = selection {
             self.collect_filter_expr(filter);
+            self.collect_constant_predicates(filter);
         }
     }
 
@@ -141,6 +290,7 @@ impl QueryColumnCollector {
 
         if let Some(filter) = selection {
             self.collect_filter_expr(filter);
+            self.collect_constant_predicates(filter);
         }
 
         for order in order_by {
@@ -167,6 +317,7 @@ impl QueryColumnCollector {
 
         if let Some(selection) = &select.selection {
             self.collect_filter_expr(selection);
+            self.collect_constant_predicates(selection);
         }
 
         for item in &select.projection {
@@ -272,6 +423,48 @@ impl QueryColumnCollector {
                 BinaryOperator::Eq => {
                     self.collect_equality_predicate(left, right, ColumnKind::EqualityFilter);
                 }
+                // The parser binds `->>` looser than comparisons, so `payload->>'type' = $1`
+                // arrives as `payload->>('type' = $1)`; PostgreSQL binds it tighter.
+                BinaryOperator::Arrow
+                | BinaryOperator::LongArrow
+                | BinaryOperator::HashArrow
+                | BinaryOperator::HashLongArrow => match right.as_ref() {
+                    Expr::BinaryOp {
+                        left: key,
+                        op: comparison,
+                        right: value,
+                    } => self.collect_filter_expr(&Expr::BinaryOp {
+                        left: Box::new(Expr::BinaryOp {
+                            left: left.clone(),
+                            op: op.clone(),
+                            right: key.clone(),
+                        }),
+                        op: comparison.clone(),
+                        right: value.clone(),
+                    }),
+                    _ => self.collect_non_equality_predicate(left, right),
+                },
+                BinaryOperator::PGRegexMatch
+                | BinaryOperator::PGRegexIMatch
+                | BinaryOperator::PGLikeMatch
+                | BinaryOperator::PGILikeMatch => self.push_pattern_filter(left),
+                BinaryOperator::AtAt => {
+                    self.push_full_text_filter(left);
+                    self.push_full_text_filter(right);
+                }
+                BinaryOperator::PGOverlap => {
+                    self.push_spatial_filter(left, "&&");
+                    self.push_spatial_filter(right, "&&");
+                }
+                BinaryOperator::AtArrow => self.push_jsonb_filter(left, JsonbOperator::Containment),
+                BinaryOperator::ArrowAt => {
+                    self.push_jsonb_filter(right, JsonbOperator::Containment)
+                }
+                BinaryOperator::Question
+                | BinaryOperator::QuestionAnd
+                | BinaryOperator::QuestionPipe => {
+                    self.push_jsonb_filter(left, JsonbOperator::KeyExists)
+                }
                 _ => self.collect_non_equality_predicate(left, right),
             },
             Expr::InList { expr, .. } => {
@@ -283,11 +476,86 @@ impl QueryColumnCollector {
             Expr::IsNull(expr) | Expr::IsNotNull(expr) => {
                 self.push_column_if_applicable(expr, ColumnKind::NonEqualityFilter)
             }
+            Expr::ILike {
+                negated: false,
+                expr,
+                ..
+            } => self.push_pattern_filter(expr),
+            // `LIKE 'abc%'` can use a `text_pattern_ops` B-tree; only a leading wildcard
+            // (or a normalized `$n` pattern that may have one) needs trigrams
+            Expr::Like {
+                negated: false,
+                expr,
+                pattern,
+                ..
+            } if match pattern.as_ref() {
+                Expr::Value(Value::SingleQuotedString(pattern)) => {
+                    pattern.starts_with('%') || pattern.starts_with('_')
+                }
+                Expr::Value(Value::Placeholder(_)) => true,
+                _ => false,
+            } =>
+            {
+                self.push_pattern_filter(expr)
+            }
+            Expr::Function(function) => self.collect_spatial_predicate(function),
             Expr::Nested(expr) => self.collect_filter_expr(expr),
             _ => {}
         }
     }
 
+    /// Walks only the top-level `AND` chain; a predicate under `OR` or `NOT` does
+    /// not constrain every returned row.
+    fn collect_constant_predicates(&mut self, expr: &Expr) {
+        let (column_expr, test) = match expr {
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::And,
+                right,
+            } => {
+                self.collect_constant_predicates(left);
+                self.collect_constant_predicates(right);
+                return;
+            }
+            Expr::Nested(expr) => {
+                self.collect_constant_predicates(expr);
+                return;
+            }
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::Eq,
+                right,
+            } => match (literal_sql(left), literal_sql(right)) {
+                (None, Some(literal)) => (left.as_ref(), PredicateTest::Equals(literal)),
+                (Some(literal), None) => (right.as_ref(), PredicateTest::Equals(literal)),
+                _ => return,
+            },
+            Expr::IsNull(expr) => (expr.as_ref(), PredicateTest::IsNull),
+            Expr::IsNotNull(expr) => (expr.as_ref(), PredicateTest::IsNotNull),
+            Expr::IsTrue(expr) => (expr.as_ref(), PredicateTest::Equals("true".into())),
+            Expr::IsFalse(expr) => (expr.as_ref(), PredicateTest::Equals("false".into())),
+            Expr::UnaryOp {
+                op: UnaryOperator::Not,
+                expr,
+            } => (expr.as_ref(), PredicateTest::Equals("false".into())),
+            // A bare boolean column, e.g. `WHERE active`
+            Expr::Identifier(_) | Expr::CompoundIdentifier(_) => {
+                (expr, PredicateTest::Equals("true".into()))
+            }
+            _ => return,
+        };
+
+        if let Some(column) = column_ref_from_expr(column_expr) {
+            self.pending_predicates.push((
+                column.relation,
+                ConstantPredicate {
+                    column: column.name,
+                    test,
+                },
+            ));
+        }
+    }
+
     fn collect_join_expr(&mut self, expr: &Expr) {
         match expr {
             Expr::BinaryOp { left, op, right } => match op {
@@ -298,15 +566,26 @@ impl QueryColumnCollector {
                 BinaryOperator::Eq => {
                     self.collect_equality_predicate(left, right, ColumnKind::EqualityJoin);
                 }
+                BinaryOperator::PGOverlap => {
+                    self.push_spatial_filter(left, "&&");
+                    self.push_spatial_filter(right, "&&");
+                }
                 _ => {}
             },
+            // Spatial joins such as `ON ST_Intersects(p.geom, z.boundary)`
+            Expr::Function(function) => self.collect_spatial_predicate(function),
             Expr::Nested(expr) => self.collect_join_expr(expr),
             _ => {}
         }
     }
 
     fn collect_order_by(&mut self, order: &OrderByExpr) {
-        self.push_column_if_applicable(&order.expr, ColumnKind::Order);
+        let kind = if order.asc == Some(false) {
+            ColumnKind::DescendingOrder
+        } else {
+            ColumnKind::Order
+        };
+        self.push_column_if_applicable(&order.expr, kind);
     }
 
     fn collect_projection_expr(&mut self, expr: &Expr) {
@@ -322,6 +601,107 @@ impl QueryColumnCollector {
                 name: column.name,
                 kind,
             });
+            return;
+        }
+
+        let equality = match kind {
+            ColumnKind::EqualityFilter => true,
+            ColumnKind::NonEqualityFilter => false,
+            ColumnKind::EqualityJoin | ColumnKind::Order | ColumnKind::DescendingOrder => return,
+        };
+        if let Some((column, expression)) = expression_ref_from_expr(expr) {
+            self.pending_expressions.push((
+                column.relation,
+                ExpressionFilter {
+                    expression,
+                    column: column.name,
+                    equality,
+                },
+            ));
+        }
+    }
+
+    fn push_pattern_filter(&mut self, expr: &Expr) {
+        if let Some(column) = column_ref_from_expr(expr) {
+            self.pending_text_search.push((
+                column.relation,
+                TextSearchFilter::Pattern {
+                    column: column.name,
+                },
+            ));
+        }
+    }
+
+    /// Records the document side of `@@`: a `tsvector` column or a `to_tsvector(...)` call
+    fn push_full_text_filter(&mut self, expr: &Expr) {
+        let found = match expr {
+            Expr::Function(function)
+                if function
+                    .name
+                    .to_string()
+                    .eq_ignore_ascii_case("to_tsvector") =>
+            {
+                expression_ref_from_expr(expr)
+            }
+            Expr::Nested(inner) => return self.push_full_text_filter(inner),
+            _ => column_ref_from_expr(expr).map(|column| {
+                let expression = column.name.clone();
+                (column, expression)
+            }),
+        };
+
+        if let Some((column, expression)) = found {
+            self.pending_text_search.push((
+                column.relation,
+                TextSearchFilter::FullText {
+                    column: column.name,
+                    expression,
+                },
+            ));
+        }
+    }
+
+    fn collect_spatial_predicate(&mut self, function: &Function) {
+        let Some(name) = function.name.0.last().and_then(|ident| {
+            SPATIAL_PREDICATES
+                .iter()
+                .find(|predicate| predicate.eq_ignore_ascii_case(&ident.value))
+        }) else {
+            return;
+        };
+        let FunctionArguments::List(list) = &function.args else {
+            return;
+        };
+        for arg in &list.args {
+            if let FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)) = arg {
+                self.push_spatial_filter(arg, name);
+            }
+        }
+    }
+
+    /// Records a geometry column; literals and placeholders among the
+    /// predicate's arguments are skipped
+    fn push_spatial_filter(&mut self, expr: &Expr, operator: &str) {
+        if let Some(column) = column_ref_from_expr(expr) {
+            self.pending_spatial.push((
+                column.relation,
+                SpatialFilter {
+                    column: column.name,
+                    operator: operator.to_string(),
+                },
+            ));
+        }
+    }
+
+    fn push_jsonb_filter(&mut self, expr: &Expr, operator: JsonbOperator) {
+        if let Some(column) = column_ref_from_expr(expr) {
+            self.pending_jsonb.push((
+                column.relation,
+                JsonbFilter {
+                    column: column.name,
+                    operator,
+                },
+            ));
         }
     }
 
@@ -373,7 +753,13 @@ impl QueryColumnCollector {
             tables,
             alias_map,
             pending,
+            pending_predicates,
+            pending_expressions,
+            pending_jsonb,
+            pending_text_search,
+            pending_spatial,
             mut resolved_usage_by_table,
+            pagination,
         } = self;
 
         let default_table = if tables.len() == 1 {
@@ -395,12 +781,62 @@ impl QueryColumnCollector {
                 }
                 ColumnKind::EqualityJoin => push_unique(&mut entry.equality_joins, &pending.name),
                 ColumnKind::Order => push_unique(&mut entry.orders, &pending.name),
+                ColumnKind::DescendingOrder => {
+                    push_unique(&mut entry.orders, &pending.name);
+                    push_unique(&mut entry.descending_orders, &pending.name);
+                }
+            }
+        }
+
+        for (relation, predicate) in pending_predicates {
+            let table = resolve_table_name(relation.as_deref(), &alias_map, &default_table);
+            let Some(table_name) = table else { continue };
+            let entry = resolved_usage_by_table.entry(table_name).or_default();
+            if !entry.constant_predicates.contains(&predicate) {
+                entry.constant_predicates.push(predicate);
+            }
+        }
+
+        for (relation, filter) in pending_expressions {
+            let table = resolve_table_name(relation.as_deref(), &alias_map, &default_table);
+            let Some(table_name) = table else { continue };
+            let entry = resolved_usage_by_table.entry(table_name).or_default();
+            if !entry.expression_filters.contains(&filter) {
+                entry.expression_filters.push(filter);
+            }
+        }
+
+        for (relation, filter) in pending_jsonb {
+            let table = resolve_table_name(relation.as_deref(), &alias_map, &default_table);
+            let Some(table_name) = table else { continue };
+            let entry = resolved_usage_by_table.entry(table_name).or_default();
+            if !entry.jsonb_filters.contains(&filter) {
+                entry.jsonb_filters.push(filter);
+            }
+        }
+
+        for (relation, filter) in pending_text_search {
+            let table = resolve_table_name(relation.as_deref(), &alias_map, &default_table);
+            let Some(table_name) = table else { continue };
+            let entry = resolved_usage_by_table.entry(table_name).or_default();
+            if !entry.text_search_filters.contains(&filter) {
+                entry.text_search_filters.push(filter);
+            }
+        }
+
+        for (relation, filter) in pending_spatial {
+            let table = resolve_table_name(relation.as_deref(), &alias_map, &default_table);
+            let Some(table_name) = table else { continue };
+            let entry = resolved_usage_by_table.entry(table_name).or_default();
+            if !entry.spatial_filters.contains(&filter) {
+                entry.spatial_filters.push(filter);
             }
         }
 
         QueryColumnUsage {
             tables,
             usage_by_table: resolved_usage_by_table,
+            pagination,
         }
     }
 }
@@ -438,6 +874,73 @@ fn column_ref_from_expr(expr: &Expr) -> Option<ColumnRef> {
     }
 }
 
+/// A function call, cast or JSON field extraction over exactly one column, such as
+/// `lower(email)`, `date_trunc('day', created_at)`, `created_at::date` or
+/// `payload->>'type'`. Other operands must be literals or placeholders so the
+/// expression is the same for every call.
+fn expression_ref_from_expr(expr: &Expr) -> Option<(ColumnRef, String)> {
+    let (column_expr, column) = match expr {
+        Expr::Nested(expr) => return expression_ref_from_expr(expr),
+        Expr::BinaryOp {
+            left,
+            op:
+                BinaryOperator::Arrow
+                | BinaryOperator::LongArrow
+                | BinaryOperator::HashArrow
+                | BinaryOperator::HashLongArrow,
+            right,
+        } if is_constant_operand(right) => (left.as_ref(), column_ref_from_expr(left)?),
+        Expr::Cast { expr: inner, .. } => (inner.as_ref(), column_ref_from_expr(inner)?),
+        Expr::Function(function) => {
+            if function.over.is_some() || function.filter.is_some() {
+                return None;
+            }
+            let FunctionArguments::List(list) = &function.args else {
+                return None;
+            };
+
+            let mut found = None;
+            for arg in &list.args {
+                let FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)) = arg else {
+                    return None;
+                };
+                if let Some(column) = column_ref_from_expr(arg) {
+                    if found.is_some() {
+                        return None;
+                    }
+                    found = Some((arg, column));
+                } else if !is_constant_operand(arg) {
+                    return None;
+                }
+            }
+            found?
+        }
+        _ => return None,
+    };
+
+    let expression = expr
+        .to_string()
+        .replacen(&column_expr.to_string(), &column.name, 1);
+    Some((column, expression))
+}
+
+fn is_constant_operand(expr: &Expr) -> bool {
+    literal_sql(expr).is_some() || matches!(expr, Expr::Value(Value::Placeholder(_)))
+}
+
+/// SQL text of a literal operand; placeholders such as `$1` are not constants
+fn literal_sql(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Value(Value::SingleQuotedString(value)) => {
+            Some(format!("'{}'", value.replace('\'', "''")))
+        }
+        Expr::Value(Value::Number(value, _)) => Some(value.to_string()),
+        Expr::Value(Value::Boolean(value)) => Some(value.to_string()),
+        Expr::Nested(expr) => literal_sql(expr),
+        _ => None,
+    }
+}
+
 fn resolve_table_name(
     relation: Option<&str>,
     alias_map: &HashMap<String, String>,
@@ -496,6 +999,34 @@ fn merge_table_usage(target: &mut TableColumnUsage, source: &TableColumnUsage) {
     for value in &source.orders {
         push_unique(&mut target.orders, value);
     }
+    for value in &source.descending_orders {
+        push_unique(&mut target.descending_orders, value);
+    }
+    for predicate in &source.constant_predicates {
+        if !target.constant_predicates.contains(predicate) {
+            target.constant_predicates.push(predicate.clone());
+        }
+    }
+    for filter in &source.expression_filters {
+        if !target.expression_filters.contains(filter) {
+            target.expression_filters.push(filter.clone());
+        }
+    }
+    for filter in &source.jsonb_filters {
+        if !target.jsonb_filters.contains(filter) {
+            target.jsonb_filters.push(filter.clone());
+        }
+    }
+    for filter in &source.text_search_filters {
+        if !target.text_search_filters.contains(filter) {
+            target.text_search_filters.push(filter.clone());
+        }
+    }
+    for filter in &source.spatial_filters {
+        if !target.spatial_filters.contains(filter) {
+            target.spatial_filters.push(filter.clone());
+        }
+    }
 }
 
 #[cfg(test)]
@@ -705,4 +1236,157 @@ mod tests {
             .iter()
             .any(|c| c == "archived_at"));
     }
+
+    #[test]
+    fn extracts_constant_predicates_from_and_chain_only() {
+        let query = "SELECT * FROM orders o WHERE o.deleted_at IS NULL AND o.status = 'active' AND NOT o.archived AND o.customer_id = $1 AND (o.region = 'eu' OR o.region = 'us')";
+        let usage = parse_query_columns(query).expect("parse");
+        let orders = usage
+            .usage_by_table
+            .iter()
+            .find(|(k, _)| k.ends_with("orders"))
+            .map(|(_, v)| v)
+            .expect("orders");
+        let predicates: Vec<String> = orders
+            .constant_predicates
+            .iter()
+            .map(ConstantPredicate::to_sql)
+            .collect();
+        assert_eq!(
+            predicates,
+            vec![
+                "deleted_at IS NULL",
+                "status = 'active'",
+                "archived = false"
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_function_wrapped_filters_as_expressions() {
+        let query = "SELECT * FROM users u WHERE lower(u.email) = $1 AND date_trunc('day', u.created_at) >= $2 AND u.tenant_id = $3 AND coalesce(u.a, u.b) = $4";
+        let usage = parse_query_columns(query).expect("parse");
+        let users = usage.usage_by_table.get("users").expect("users");
+
+        assert_eq!(users.equality_filters, vec!["tenant_id"]);
+        assert_eq!(
+            users.expression_filters,
+            vec![
+                ExpressionFilter {
+                    expression: "lower(email)".into(),
+                    column: "email".into(),
+                    equality: true,
+                },
+                ExpressionFilter {
+                    expression: "date_trunc('day', created_at)".into(),
+                    column: "created_at".into(),
+                    equality: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_jsonb_field_extraction_and_containment() {
+        let query = "SELECT * FROM events e WHERE e.payload->>'type' = $1 AND e.payload @> $2 AND e.tags ? 'urgent'";
+        let usage = parse_query_columns(query).expect("parse");
+        let events = usage.usage_by_table.get("events").expect("events");
+
+        assert_eq!(events.expression_filters.len(), 1);
+        assert_eq!(
+            events.expression_filters[0].expression,
+            "payload ->> 'type'"
+        );
+        assert_eq!(
+            events.jsonb_filters,
+            vec![
+                JsonbFilter {
+                    column: "payload".into(),
+                    operator: JsonbOperator::Containment,
+                },
+                JsonbFilter {
+                    column: "tags".into(),
+                    operator: JsonbOperator::KeyExists,
+                },
+            ]
+        );
+        assert!(events.non_equality_filters.is_empty());
+    }
+
+    #[test]
+    fn extracts_pattern_and_full_text_filters() {
+        let query = "SELECT * FROM articles a WHERE a.title ILIKE $1 AND a.slug LIKE 'abc%' AND a.body ~* $2 AND to_tsvector('english', a.body) @@ plainto_tsquery($3) AND a.search @@ $4";
+        let usage = parse_query_columns(query).expect("parse");
+        let articles = usage.usage_by_table.get("articles").expect("articles");
+
+        assert_eq!(
+            articles.text_search_filters,
+            vec![
+                TextSearchFilter::Pattern {
+                    column: "title".into()
+                },
+                TextSearchFilter::Pattern {
+                    column: "body".into()
+                },
+                TextSearchFilter::FullText {
+                    column: "body".into(),
+                    expression: "to_tsvector('english', body)".into(),
+                },
+                TextSearchFilter::FullText {
+                    column: "search".into(),
+                    expression: "search".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_bounding_box_and_spatial_predicates() {
+        let query = "SELECT p.name FROM parcels p JOIN zones z ON ST_Intersects(p.geom, z.boundary) WHERE p.geom && ST_MakeEnvelope($1, $2, $3, $4, 4326) AND ST_DWithin(p.centroid, $5, $6) AND NOT ST_Disjoint(p.geom, $7)";
+        let usage = parse_query_columns(query).expect("parse");
+        let parcels = usage.usage_by_table.get("parcels").expect("parcels");
+
+        assert_eq!(
+            parcels.spatial_filters,
+            vec![
+                SpatialFilter {
+                    column: "geom".into(),
+                    operator: "ST_Intersects".into(),
+                },
+                SpatialFilter {
+                    column: "geom".into(),
+                    operator: "&&".into(),
+                },
+                SpatialFilter {
+                    column: "centroid".into(),
+                    operator: "ST_DWithin".into(),
+                },
+            ]
+        );
+        assert!(parcels.non_equality_filters.is_empty());
+        assert_eq!(
+            usage.usage_by_table["zones"].spatial_filters,
+            vec![SpatialFilter {
+                column: "boundary".into(),
+                operator: "ST_Intersects".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn extracts_sort_direction_and_pagination() {
+        let query = "SELECT * FROM orders WHERE customer_id = $1 ORDER BY created_at DESC, id LIMIT $2 OFFSET 5000";
+        let usage = parse_query_columns(query).expect("parse");
+        let orders = usage.usage_by_table.get("orders").expect("orders");
+
+        assert_eq!(orders.orders, vec!["created_at", "id"]);
+        assert_eq!(orders.descending_orders, vec!["created_at"]);
+        assert_eq!(
+            usage.pagination,
+            Pagination {
+                limited: true,
+                offset: Some(PaginationOffset::Rows(5000)),
+            }
+        );
+    }
 }
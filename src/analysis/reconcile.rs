@@ -0,0 +1,171 @@
+use crate::i18n::Message;
+use crate::models::{AnalysisResults, ConfigCategory, ConfigSuggestion};
+use std::collections::HashMap;
+
+/// Merges suggestions that several analyzers made for the same parameter.
+///
+/// The most severe suggestion wins (ties go to the category that sorts first) and
+/// keeps its category; the others are folded into its rationale, so the report
+/// gives one value per parameter instead of contradictory advice. Table/index and
+/// workload findings name objects rather than parameters and are left alone.
+pub fn reconcile_suggestions(results: &mut AnalysisResults) {
+    let mut categories: Vec<ConfigCategory> = results
+        .suggestions_by_category
+        .keys()
+        .copied()
+        .filter(|category| !is_object_category(*category))
+        .collect();
+    categories.sort_by_key(|category| category.as_str());
+
+    let mut by_parameter: HashMap<String, Vec<(ConfigCategory, ConfigSuggestion)>> = HashMap::new();
+    let mut order = Vec::new();
+    for category in categories {
+        let Some(suggestions) = results.suggestions_by_category.remove(&category) else {
+            continue;
+        };
+        for suggestion in suggestions {
+            let entry = by_parameter
+                .entry(suggestion.parameter.clone())
+                .or_default();
+            if entry.is_empty() {
+                order.push(suggestion.parameter.clone());
+            }
+            entry.push((category, suggestion));
+        }
+    }
+
+    for parameter in order {
+        let Some(mut candidates) = by_parameter.remove(&parameter) else {
+            continue;
+        };
+        // Stable, so equally severe suggestions keep category order
+        candidates.sort_by_key(|(_, suggestion)| suggestion.level.rank());
+        let mut candidates = candidates.into_iter();
+        let Some((category, mut chosen)) = candidates.next() else {
+            continue;
+        };
+        for (other_category, other) in candidates {
+            let note = if other.suggested_value == chosen.suggested_value {
+                Message::new("note.also_flagged")
+            } else {
+                Message::new("note.suggested_instead").arg("value", &other.suggested_value)
+            };
+            chosen.push_note(note.arg("category", other_category.as_str()));
+            if other.messages.is_empty() {
+                chosen.push_note(Message::text(other.rationale));
+            } else {
+                for message in other.messages {
+                    chosen.push_note(message);
+                }
+            }
+        }
+        results
+            .suggestions_by_category
+            .entry(category)
+            .or_default()
+            .push(chosen);
+    }
+}
+
+pub(crate) fn is_object_category(category: ConfigCategory) -> bool {
+    matches!(
+        category,
+        ConfigCategory::TableIndex | ConfigCategory::Workload
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SuggestionLevel;
+
+    fn suggestion(parameter: &str, suggested: &str, level: SuggestionLevel) -> ConfigSuggestion {
+        ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: "4MB".to_string(),
+            suggested_value: suggested.to_string(),
+            level,
+            rationale: format!("{parameter} -> {suggested}."),
+            messages: Vec::new(),
+            fingerprint: String::new(),
+        }
+    }
+
+    #[test]
+    fn keeps_the_most_severe_suggestion_and_merges_rationale() {
+        let mut results = AnalysisResults::default();
+        results.suggestions_by_category.insert(
+            ConfigCategory::Memory,
+            vec![
+                suggestion("work_mem", "64MB", SuggestionLevel::Recommended),
+                suggestion("shared_buffers", "4GB", SuggestionLevel::Critical),
+            ],
+        );
+        results.suggestions_by_category.insert(
+            ConfigCategory::Wal,
+            vec![suggestion("work_mem", "128MB", SuggestionLevel::Important)],
+        );
+        results.suggestions_by_category.insert(
+            ConfigCategory::TableIndex,
+            vec![
+                suggestion(
+                    "index public.a_idx",
+                    "Drop unused index",
+                    SuggestionLevel::Info,
+                ),
+                suggestion("index public.a_idx", "Rebuild", SuggestionLevel::Info),
+            ],
+        );
+
+        reconcile_suggestions(&mut results);
+
+        let memory = &results.suggestions_by_category[&ConfigCategory::Memory];
+        assert_eq!(memory.len(), 1);
+        assert_eq!(memory[0].parameter, "shared_buffers");
+
+        let wal = &results.suggestions_by_category[&ConfigCategory::Wal];
+        assert_eq!(wal.len(), 1);
+        assert_eq!(wal[0].suggested_value, "128MB");
+        assert_eq!(
+            wal[0].rationale,
+            "work_mem -> 128MB. Memory Configuration suggested 64MB instead: work_mem -> 64MB."
+        );
+
+        assert_eq!(
+            results.suggestions_by_category[&ConfigCategory::TableIndex].len(),
+            2
+        );
+    }
+
+    #[test]
+    fn agreeing_suggestions_collapse_into_one() {
+        let mut results = AnalysisResults::default();
+        results.suggestions_by_category.insert(
+            ConfigCategory::Memory,
+            vec![suggestion(
+                "wal_buffers",
+                "16MB",
+                SuggestionLevel::Important,
+            )],
+        );
+        results.suggestions_by_category.insert(
+            ConfigCategory::Wal,
+            vec![suggestion(
+                "wal_buffers",
+                "16MB",
+                SuggestionLevel::Important,
+            )],
+        );
+
+        reconcile_suggestions(&mut results);
+
+        let memory = &results.suggestions_by_category[&ConfigCategory::Memory];
+        assert_eq!(memory.len(), 1);
+        assert!(memory[0]
+            .rationale
+            .ends_with("Also flagged by WAL and Checkpoint Management: wal_buffers -> 16MB."));
+        assert!(!results
+            .suggestions_by_category
+            .contains_key(&ConfigCategory::Wal));
+    }
+}
@@ -0,0 +1,132 @@
+use crate::config::DbConfig;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgPoolOptions;
+use sqlx::{Pool, Postgres, Row};
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// A connected read replica
+#[derive(Debug, Clone)]
+pub struct ReplicaConnection {
+    /// `host:port`, used in logs and reports instead of the connection string
+    pub label: String,
+    pub pool: Pool<Postgres>,
+}
+
+/// Connects to every replica in `config`, returning the connections and the
+/// labels of replicas that could not be reached
+pub async fn connect(config: &DbConfig) -> (Vec<ReplicaConnection>, Vec<String>) {
+    let mut connected = Vec::new();
+    let mut unreachable = Vec::new();
+
+    for replica in &config.replicas {
+        let options = match config.replica_connection_options(replica) {
+            Ok(options) => options,
+            Err(err) => {
+                warn!("Skipping replica: {err}");
+                unreachable.push(replica.clone());
+                continue;
+            }
+        };
+        let label = format!("{}:{}", options.get_host(), options.get_port());
+
+        info!("Connecting to replica {label}...");
+        match PgPoolOptions::new()
+            .max_connections(1)
+            .acquire_timeout(Duration::from_secs(10))
+            .connect_with(options)
+            .await
+        {
+            Ok(pool) => connected.push(ReplicaConnection { label, pool }),
+            Err(err) => {
+                warn!("Failed to connect to replica {label}: {err}");
+                unreachable.push(label);
+            }
+        }
+    }
+
+    (connected, unreachable)
+}
+
+/// Index scan counts read from every configured read replica.
+///
+/// `idx_scan` is tracked per node, so an index that looks idle on the primary may
+/// still serve every read on a replica.
+pub(crate) const REPLICA_INDEX_SCANS_QUERY: &str =
+    "SELECT schemaname, indexrelname, COALESCE(idx_scan, 0) AS idx_scan FROM pg_stat_user_indexes";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplicaIndexScans {
+    /// `idx_scan` summed over replicas, keyed by `(schema, index)`
+    #[serde(with = "crate::fixture::tuple_keys")]
+    pub scans: HashMap<(String, String), i64>,
+    /// Replicas that could not be queried
+    pub unreachable: Vec<String>,
+}
+
+impl ReplicaIndexScans {
+    pub async fn collect(config: &DbConfig) -> Self {
+        let (replicas, unreachable) = connect(config).await;
+        let mut result = Self {
+            unreachable,
+            ..Self::default()
+        };
+
+        for replica in replicas {
+            match sqlx::query(REPLICA_INDEX_SCANS_QUERY)
+                .fetch_all(&replica.pool)
+                .await
+            {
+                Ok(rows) => {
+                    for row in rows {
+                        let scans: i64 = row.get("idx_scan");
+                        *result
+                            .scans
+                            .entry((row.get("schemaname"), row.get("indexrelname")))
+                            .or_default() += scans;
+                    }
+                }
+                Err(err) => {
+                    warn!(
+                        "Failed to read index usage from replica {}: {err}",
+                        replica.label
+                    );
+                    result.unreachable.push(replica.label);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Scans of one index across all reachable replicas
+    pub fn scans_for(&self, schema: &str, index: &str) -> i64 {
+        self.scans
+            .get(&(schema.to_string(), index.to_string()))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Whether every configured replica contributed its counts
+    pub fn is_complete(&self) -> bool {
+        self.unreachable.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scans_for_defaults_to_zero_for_unknown_indexes() {
+        let mut replicas = ReplicaIndexScans::default();
+        replicas
+            .scans
+            .insert(("public".into(), "orders_customer_idx".into()), 42);
+
+        assert_eq!(replicas.scans_for("public", "orders_customer_idx"), 42);
+        assert_eq!(replicas.scans_for("public", "orders_status_idx"), 0);
+        assert!(replicas.is_complete());
+    }
+}
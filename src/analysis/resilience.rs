@@ -0,0 +1,425 @@
+use crate::analysis::managed;
+use crate::checker::CheckerError;
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ConfigCategory, ConfigSuggestion, ParamValue, PgConfigParam, ReplicationRole,
+    SuggestionLevel, SystemStats,
+};
+use sqlx::{Pool, Postgres, Row};
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::warn;
+
+type Result<T> = std::result::Result<T, CheckerError>;
+
+/// Linux defaults used when a `tcp_keepalives_*` setting is 0
+const OS_KEEPALIVE_IDLE_SECS: u64 = 7200;
+const OS_KEEPALIVE_INTERVAL_SECS: u64 = 75;
+const OS_KEEPALIVE_COUNT: u64 = 9;
+/// Suggested keepalives: a dead peer is noticed after 60 + 6 × 10 = 120s
+const KEEPALIVE_IDLE_SECS: u64 = 60;
+const KEEPALIVE_INTERVAL_SECS: u64 = 10;
+const KEEPALIVE_COUNT: u64 = 6;
+/// Dead-peer detection slower than this is flagged
+const DETECTION_LIMIT_SECS: u64 = 300;
+/// Replication timeouts above this leave a failed peer undetected too long
+const REPLICATION_TIMEOUT_LIMIT: Duration = Duration::from_secs(300);
+
+pub(crate) const REPLICATION_ROLE_QUERY: &str = r#"
+    SELECT
+        pg_is_in_recovery() AS in_recovery,
+        (SELECT count(*) FROM pg_stat_replication) AS wal_senders
+"#;
+
+pub async fn fetch_replication_role(pool: &Pool<Postgres>) -> Option<ReplicationRole> {
+    match sqlx::query(REPLICATION_ROLE_QUERY).fetch_one(pool).await {
+        Ok(row) => Some(ReplicationRole {
+            in_recovery: row.get("in_recovery"),
+            wal_senders: row.get::<i64, _>("wal_senders").max(0) as usize,
+        }),
+        Err(err) => {
+            warn!("Failed to read the replication role: {err}");
+            None
+        }
+    }
+}
+
+/// Checks the keepalives and replication timeouts that decide how long dead
+/// connections linger after a failover, and `restart_after_crash` where a
+/// standby could take over instead
+pub fn analyze_failover(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    let replicated = stats
+        .replication_role
+        .as_ref()
+        .is_some_and(|role| role.in_recovery || role.wal_senders > 0)
+        || !stats.replication_slots.is_empty()
+        || !stats.standbys.is_empty();
+
+    analyze_keepalives(params, replicated, results);
+
+    // Managed services run failover themselves and do not expose these
+    if !replicated || managed::detect_platform(params).is_some() {
+        return Ok(());
+    }
+    let in_recovery = stats
+        .replication_role
+        .as_ref()
+        .is_some_and(|role| role.in_recovery);
+    analyze_replication_timeout(
+        params,
+        "wal_receiver_timeout",
+        (
+            "replication.wal_receiver_timeout",
+            "replication.wal_receiver_timeout.disabled",
+        ),
+        results,
+    );
+    analyze_replication_timeout(
+        params,
+        "wal_sender_timeout",
+        (
+            "replication.wal_sender_timeout",
+            "replication.wal_sender_timeout.disabled",
+        ),
+        results,
+    );
+
+    if !in_recovery {
+        if let Some(param) = params.get("restart_after_crash") {
+            if param.value == ParamValue::Bool(true) {
+                add_suggestion(
+                    results,
+                    "restart_after_crash",
+                    &param.current_value,
+                    "off",
+                    SuggestionLevel::Recommended,
+                    Message::new("replication.restart_after_crash"),
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Server-side keepalives, which end sessions whose client host vanished
+fn analyze_keepalives(
+    params: &HashMap<String, PgConfigParam>,
+    replicated: bool,
+    results: &mut AnalysisResults,
+) {
+    let (Some(idle), Some(interval), Some(count)) = (
+        params.get("tcp_keepalives_idle"),
+        params.get("tcp_keepalives_interval"),
+        params.get("tcp_keepalives_count"),
+    ) else {
+        return;
+    };
+    // Over a Unix socket these read 0 whatever is configured
+    if [idle, interval, count].iter().any(|param| {
+        let zero =
+            param.value.as_i64() == Some(0) || param.value.as_duration() == Some(Duration::ZERO);
+        zero && param
+            .source
+            .as_deref()
+            .is_some_and(|source| source != "default")
+    }) {
+        return;
+    }
+    let seconds = |param: &PgConfigParam, os_default: u64| {
+        param
+            .value
+            .as_duration()
+            .map(|duration| duration.as_secs())
+            .or_else(|| param.value.as_i64().map(|value| value.max(0) as u64))
+            .filter(|value| *value > 0)
+            .unwrap_or(os_default)
+    };
+    let idle_secs = seconds(idle, OS_KEEPALIVE_IDLE_SECS);
+    let interval_secs = seconds(interval, OS_KEEPALIVE_INTERVAL_SECS);
+    let probes = seconds(count, OS_KEEPALIVE_COUNT);
+    let detection = idle_secs + interval_secs * probes;
+    if detection <= DETECTION_LIMIT_SECS {
+        return;
+    }
+
+    let level = if replicated {
+        SuggestionLevel::Important
+    } else {
+        SuggestionLevel::Recommended
+    };
+    let suggested_detection = KEEPALIVE_IDLE_SECS + KEEPALIVE_INTERVAL_SECS * KEEPALIVE_COUNT;
+    let settings = [
+        (
+            idle,
+            idle_secs,
+            KEEPALIVE_IDLE_SECS,
+            "replication.tcp_keepalives_idle",
+        ),
+        (
+            interval,
+            interval_secs,
+            KEEPALIVE_INTERVAL_SECS,
+            "replication.tcp_keepalives_interval",
+        ),
+        (
+            count,
+            probes,
+            KEEPALIVE_COUNT,
+            "replication.tcp_keepalives_count",
+        ),
+    ];
+    for (param, current, suggested, key) in settings {
+        if current <= suggested {
+            continue;
+        }
+        add_suggestion(
+            results,
+            &param.name,
+            &param.current_value,
+            &suggested.to_string(),
+            level,
+            Message::new(key)
+                .arg("detection", format_secs(detection))
+                .arg("suggested", format_secs(suggested_detection)),
+        );
+    }
+}
+
+/// `wal_receiver_timeout` and `wal_sender_timeout`: 0 waits on TCP to notice
+/// a dead peer, long values delay the standby reconnecting elsewhere
+fn analyze_replication_timeout(
+    params: &HashMap<String, PgConfigParam>,
+    name: &str,
+    (long_key, disabled_key): (&'static str, &'static str),
+    results: &mut AnalysisResults,
+) {
+    let Some(param) = params.get(name) else {
+        return;
+    };
+    let Some(timeout) = param.value.as_duration() else {
+        return;
+    };
+    let (level, message) = if timeout.is_zero() {
+        (SuggestionLevel::Important, Message::new(disabled_key))
+    } else if timeout > REPLICATION_TIMEOUT_LIMIT {
+        (
+            SuggestionLevel::Recommended,
+            Message::new(long_key).arg("timeout", format_secs(timeout.as_secs())),
+        )
+    } else {
+        return;
+    };
+    add_suggestion(results, name, &param.current_value, "60s", level, message);
+}
+
+/// `7875` → `2h 11min`, `120` → `2min`, `45` → `45s`
+pub(crate) fn format_secs(secs: u64) -> String {
+    match (secs / 3600, secs % 3600 / 60, secs % 60) {
+        (0, 0, s) => format!("{s}s"),
+        (0, m, 0) => format!("{m}min"),
+        (0, m, s) => format!("{m}min {s}s"),
+        (h, 0, _) => format!("{h}h"),
+        (h, m, _) => format!("{h}h {m}min"),
+    }
+}
+
+fn add_suggestion(
+    results: &mut AnalysisResults,
+    parameter: &str,
+    current_value: &str,
+    suggested_value: &str,
+    level: SuggestionLevel,
+    rationale: Message,
+) {
+    results
+        .suggestions_by_category
+        .entry(ConfigCategory::Replication)
+        .or_default()
+        .push(ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: current_value.to_string(),
+            suggested_value: suggested_value.to_string(),
+            level,
+            rationale: rationale.render(Lang::En),
+            messages: vec![rationale],
+            fingerprint: String::new(),
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ReplicationSlot;
+    use rstest::rstest;
+
+    fn params(settings: &[(&str, &str, &str, Option<&str>)]) -> HashMap<String, PgConfigParam> {
+        settings
+            .iter()
+            .map(|(name, value, vartype, unit)| {
+                let param = PgConfigParam {
+                    name: name.to_string(),
+                    current_value: value.to_string(),
+                    default_value: None,
+                    unit: unit.map(str::to_string),
+                    context: "sighup".to_string(),
+                    value: ParamValue::parse(value, vartype, *unit),
+                    source: None,
+                    sourcefile: None,
+                    pending_restart: false,
+                    min_value: None,
+                    max_value: None,
+                };
+                (param.name.clone(), param)
+            })
+            .collect()
+    }
+
+    fn defaults() -> HashMap<String, PgConfigParam> {
+        params(&[
+            ("tcp_keepalives_idle", "0", "integer", Some("s")),
+            ("tcp_keepalives_interval", "0", "integer", Some("s")),
+            ("tcp_keepalives_count", "0", "integer", None),
+            ("wal_receiver_timeout", "60000", "integer", Some("ms")),
+            ("wal_sender_timeout", "60000", "integer", Some("ms")),
+            ("restart_after_crash", "on", "bool", None),
+        ])
+    }
+
+    fn primary() -> SystemStats {
+        SystemStats {
+            replication_role: Some(ReplicationRole {
+                in_recovery: false,
+                wal_senders: 1,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn analyzed(
+        params: &HashMap<String, PgConfigParam>,
+        stats: &SystemStats,
+    ) -> Vec<ConfigSuggestion> {
+        let mut results = AnalysisResults::default();
+        analyze_failover(params, stats, &mut results).unwrap();
+        results
+            .suggestions_by_category
+            .remove(&ConfigCategory::Replication)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn os_default_keepalives_are_flagged_on_a_primary() {
+        let suggestions = analyzed(&defaults(), &primary());
+        let changes: Vec<(&str, &str, SuggestionLevel)> = suggestions
+            .iter()
+            .map(|s| (s.parameter.as_str(), s.suggested_value.as_str(), s.level))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                ("tcp_keepalives_idle", "60", SuggestionLevel::Important),
+                ("tcp_keepalives_interval", "10", SuggestionLevel::Important),
+                ("tcp_keepalives_count", "6", SuggestionLevel::Important),
+                ("restart_after_crash", "off", SuggestionLevel::Recommended),
+            ]
+        );
+        // 7200 + 9 × 75 seconds
+        assert!(suggestions[0].rationale.contains("after 2h 11min"));
+        assert!(suggestions[0].rationale.contains("after 2min."));
+    }
+
+    #[test]
+    fn standalone_servers_only_get_keepalive_advice() {
+        let stats = SystemStats {
+            replication_role: Some(ReplicationRole::default()),
+            ..Default::default()
+        };
+        let suggestions = analyzed(&defaults(), &stats);
+        assert_eq!(suggestions.len(), 3);
+        assert!(suggestions
+            .iter()
+            .all(|s| s.level == SuggestionLevel::Recommended));
+    }
+
+    #[rstest]
+    #[case("60", "10", "6", &[])]
+    // 120 + 9 × 75 is still over 5 minutes; only the count and interval change
+    #[case("120", "0", "0", &["tcp_keepalives_idle", "tcp_keepalives_interval", "tcp_keepalives_count"])]
+    #[case("600", "10", "3", &["tcp_keepalives_idle"])]
+    fn keepalives_are_judged_by_detection_time(
+        #[case] idle: &str,
+        #[case] interval: &str,
+        #[case] count: &str,
+        #[case] expected: &[&str],
+    ) {
+        let mut params = defaults();
+        params.extend(self::params(&[
+            ("tcp_keepalives_idle", idle, "integer", Some("s")),
+            ("tcp_keepalives_interval", interval, "integer", Some("s")),
+            ("tcp_keepalives_count", count, "integer", None),
+            ("restart_after_crash", "off", "bool", None),
+        ]));
+        let suggestions = analyzed(&params, &primary());
+        let flagged: Vec<&str> = suggestions.iter().map(|s| s.parameter.as_str()).collect();
+        assert_eq!(flagged, expected);
+    }
+
+    #[test]
+    fn unix_socket_keepalives_are_not_judged() {
+        let mut params = defaults();
+        for name in ["tcp_keepalives_idle", "tcp_keepalives_interval"] {
+            params.get_mut(name).unwrap().source = Some("configuration file".to_string());
+        }
+        assert!(analyzed(&params, &SystemStats::default()).is_empty());
+    }
+
+    #[rstest]
+    #[case("wal_receiver_timeout", "0", SuggestionLevel::Important, "is 0")]
+    #[case("wal_sender_timeout", "0", SuggestionLevel::Important, "is 0")]
+    #[case(
+        "wal_sender_timeout",
+        "600000",
+        SuggestionLevel::Recommended,
+        "is 10min"
+    )]
+    fn replication_timeouts_are_checked_with_standbys(
+        #[case] name: &str,
+        #[case] value: &str,
+        #[case] level: SuggestionLevel,
+        #[case] rationale: &str,
+    ) {
+        let mut params = defaults();
+        params.extend(self::params(&[
+            (name, value, "integer", Some("ms")),
+            ("tcp_keepalives_idle", "60", "integer", Some("s")),
+            ("tcp_keepalives_interval", "10", "integer", Some("s")),
+            ("tcp_keepalives_count", "6", "integer", None),
+            ("restart_after_crash", "off", "bool", None),
+        ]));
+        // A standby is in recovery; a primary is known here only by its slot
+        let standby = SystemStats {
+            replication_role: Some(ReplicationRole {
+                in_recovery: true,
+                wal_senders: 0,
+            }),
+            ..Default::default()
+        };
+        let slotted = SystemStats {
+            replication_slots: vec![ReplicationSlot::default()],
+            ..Default::default()
+        };
+        for stats in [standby, slotted] {
+            let suggestions = analyzed(&params, &stats);
+            assert_eq!(suggestions.len(), 1, "{suggestions:?}");
+            assert_eq!(suggestions[0].parameter, name);
+            assert_eq!(suggestions[0].level, level);
+            assert!(suggestions[0].rationale.contains(rationale));
+        }
+        assert!(analyzed(&params, &SystemStats::default()).is_empty());
+    }
+}
@@ -0,0 +1,324 @@
+use crate::catalog::{self, Catalog};
+use crate::checker::CheckerError;
+use crate::models::SystemStats;
+use futures_util::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres, Row};
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Statistics younger than this make every counter-derived conclusion premature
+pub const FRESH_STATS_SECS: u64 = 60 * 60;
+/// Minimum statistics history before an index with zero scans is called unused;
+/// weekly jobs and month-end reports would otherwise look like dead indexes.
+pub const MIN_UNUSED_INDEX_WINDOW_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Cumulative counters captured at the start of a `--sample-window`.
+///
+/// Analyses subtract these from the counters read at the end of the window so
+/// recommendations reflect current behavior instead of everything since the last
+/// stats reset.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatsBaseline {
+    pub checkpoints_timed: Option<i64>,
+    pub checkpoints_req: Option<i64>,
+    pub wal_bytes: Option<i64>,
+    pub wal_fpi: Option<i64>,
+    pub temp_files: Option<i64>,
+    pub temp_bytes: Option<i64>,
+    pub connection_count: Option<usize>,
+    /// `(seq_scan, idx_scan)` keyed by `(schema, table)`
+    #[serde(with = "crate::fixture::tuple_keys")]
+    pub table_scans: HashMap<(String, String), (i64, i64)>,
+}
+
+impl StatsBaseline {
+    pub async fn capture(pool: &Pool<Postgres>, catalog: &Catalog) -> Result<Self, CheckerError> {
+        let checkpoints = catalog::fetch_checkpoint_counters(pool, catalog).await;
+        let wal = fetch_wal_counters(pool).await;
+        let temp = fetch_temp_counters(pool).await;
+        let (connection_count, _) = fetch_connection_counts(pool).await;
+        let table_scans = fetch_table_scans(pool).await?;
+
+        Ok(Self {
+            checkpoints_timed: checkpoints.timed,
+            checkpoints_req: checkpoints.requested,
+            wal_bytes: wal.bytes,
+            wal_fpi: wal.fpi,
+            temp_files: temp.files,
+            temp_bytes: temp.bytes,
+            connection_count,
+            table_scans,
+        })
+    }
+
+    /// `(seq_scan, idx_scan)` accumulated during the window for one table
+    pub fn table_scan_delta(
+        &self,
+        schema: &str,
+        table: &str,
+        seq_scan: i64,
+        idx_scan: i64,
+    ) -> (i64, i64) {
+        match self
+            .table_scans
+            .get(&(schema.to_string(), table.to_string()))
+        {
+            Some(&(base_seq, base_idx)) => (
+                counter_delta(seq_scan, base_seq),
+                counter_delta(idx_scan, base_idx),
+            ),
+            // Table created during the window: everything happened inside it
+            None => (seq_scan, idx_scan),
+        }
+    }
+}
+
+/// Difference between two reads of a cumulative counter. A counter that went
+/// backwards was reset mid-window, so the end value is the best available delta.
+pub fn counter_delta(end: i64, start: i64) -> i64 {
+    if end >= start {
+        end - start
+    } else {
+        end
+    }
+}
+
+pub(crate) const CONNECTION_COUNTS_QUERY: &str = r#"
+    SELECT
+        count(*) AS connections,
+        count(*) FILTER (WHERE state = 'active') AS active
+    FROM pg_stat_activity
+"#;
+
+/// Open connections and how many of them are running a query
+pub(crate) async fn fetch_connection_counts(
+    pool: &Pool<Postgres>,
+) -> (Option<usize>, Option<usize>) {
+    match sqlx::query(CONNECTION_COUNTS_QUERY).fetch_one(pool).await {
+        Ok(row) => (
+            row.try_get::<i64, _>("connections")
+                .ok()
+                .map(|count| count as usize),
+            row.try_get::<i64, _>("active")
+                .ok()
+                .map(|count| count as usize),
+        ),
+        Err(err) => {
+            warn!("Failed to read pg_stat_activity for connection count: {err}");
+            (None, None)
+        }
+    }
+}
+
+/// Cumulative WAL volume from `pg_stat_wal` (PG14+)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub(crate) struct WalCounters {
+    pub bytes: Option<i64>,
+    /// Full-page images written
+    pub fpi: Option<i64>,
+    /// Seconds since the counters were last reset, or since server start
+    pub age_secs: Option<u64>,
+}
+
+pub(crate) const WAL_COUNTERS_QUERY: &str = r#"
+    SELECT
+        wal_bytes::bigint AS wal_bytes,
+        wal_fpi,
+        GREATEST(EXTRACT(EPOCH FROM now() - COALESCE(stats_reset, pg_postmaster_start_time())), 0)::bigint AS wal_stats_age_secs
+    FROM pg_stat_wal
+"#;
+
+/// Reads WAL volume counters; older servers without `pg_stat_wal` get empty counters.
+pub(crate) async fn fetch_wal_counters(pool: &Pool<Postgres>) -> WalCounters {
+    match sqlx::query(WAL_COUNTERS_QUERY).fetch_one(pool).await {
+        Ok(row) => WalCounters {
+            bytes: row.try_get("wal_bytes").ok(),
+            fpi: row.try_get("wal_fpi").ok(),
+            age_secs: row
+                .try_get::<i64, _>("wal_stats_age_secs")
+                .ok()
+                .map(|secs| secs as u64),
+        },
+        Err(err) => {
+            warn!("Failed to read WAL volume from pg_stat_wal: {err}");
+            WalCounters::default()
+        }
+    }
+}
+
+/// Temporary files written by queries that spilled past `work_mem`
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub(crate) struct TempCounters {
+    pub files: Option<i64>,
+    pub bytes: Option<i64>,
+}
+
+pub(crate) const TEMP_COUNTERS_QUERY: &str = r#"
+    SELECT temp_files, temp_bytes
+    FROM pg_stat_database
+    WHERE datname = current_database()
+"#;
+
+/// Reads the current database's temporary file counters
+pub(crate) async fn fetch_temp_counters(pool: &Pool<Postgres>) -> TempCounters {
+    match sqlx::query(TEMP_COUNTERS_QUERY).fetch_one(pool).await {
+        Ok(row) => TempCounters {
+            files: row.try_get("temp_files").ok(),
+            bytes: row.try_get("temp_bytes").ok(),
+        },
+        Err(err) => {
+            warn!("Failed to read temporary file counters from pg_stat_database: {err}");
+            TempCounters::default()
+        }
+    }
+}
+
+pub(crate) const STATS_AGE_QUERY: &str = r#"
+    SELECT
+        since::text AS stats_reset_at,
+        GREATEST(EXTRACT(EPOCH FROM now() - since), 0)::bigint AS stats_age_secs
+    FROM (
+        SELECT COALESCE(pg_stat_get_db_stat_reset_time(oid), pg_postmaster_start_time()) AS since
+        FROM pg_database
+        WHERE datname = current_database()
+    ) s
+"#;
+
+/// Start of the current database's cumulative statistics and how many seconds they
+/// cover. Falls back to server start when the stats were never reset, which is a
+/// lower bound on the window since statistics may survive a clean restart.
+pub(crate) async fn fetch_stats_age(pool: &Pool<Postgres>) -> (Option<String>, Option<u64>) {
+    match sqlx::query(STATS_AGE_QUERY).fetch_one(pool).await {
+        Ok(row) => (
+            row.try_get("stats_reset_at").ok(),
+            row.try_get::<i64, _>("stats_age_secs")
+                .ok()
+                .map(|secs| secs as u64),
+        ),
+        Err(err) => {
+            warn!("Failed to read statistics reset time: {err}");
+            (None, None)
+        }
+    }
+}
+
+/// Caveats to surface when the cumulative statistics window is too short to trust
+pub fn stats_age_warnings(stats: &SystemStats) -> Vec<String> {
+    let (Some(age), Some(since)) = (stats.stats_age_secs, stats.stats_reset_at.as_deref()) else {
+        return Vec::new();
+    };
+
+    let mut warnings = Vec::new();
+    if age < FRESH_STATS_SECS {
+        warnings.push(format!(
+            "Statistics were reset only {} ago (at {since}). Sequential-scan, index-usage and bloat findings reflect almost no activity and should not be acted on yet.",
+            format_window(age)
+        ));
+    }
+    if age < MIN_UNUSED_INDEX_WINDOW_SECS {
+        warnings.push(format!(
+            "Unused-index findings are suppressed: statistics cover {} and at least {} is needed to rule out periodic jobs.",
+            format_window(age),
+            format_window(MIN_UNUSED_INDEX_WINDOW_SECS)
+        ));
+    }
+    warnings
+}
+
+/// Compact human-readable duration such as `45m`, `6h 12m` or `9d 3h`
+pub fn format_window(secs: u64) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+
+    if secs >= DAY {
+        format!("{}d {}h", secs / DAY, secs % DAY / HOUR)
+    } else if secs >= HOUR {
+        format!("{}h {}m", secs / HOUR, secs % HOUR / MINUTE)
+    } else if secs >= MINUTE {
+        format!("{}m", secs / MINUTE)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+pub(crate) const TABLE_SCANS_QUERY: &str = r#"
+    SELECT
+        schemaname,
+        relname,
+        COALESCE(seq_scan, 0) AS seq_scan,
+        COALESCE(idx_scan, 0) AS idx_scan
+    FROM pg_stat_user_tables
+"#;
+
+async fn fetch_table_scans(
+    pool: &Pool<Postgres>,
+) -> Result<HashMap<(String, String), (i64, i64)>, CheckerError> {
+    sqlx::query(TABLE_SCANS_QUERY)
+        .fetch(pool)
+        .map_ok(|row| {
+            (
+                (row.get("schemaname"), row.get("relname")),
+                (row.get("seq_scan"), row.get("idx_scan")),
+            )
+        })
+        .try_collect()
+        .await
+        .map_err(|source| CheckerError::QueryError {
+            query: TABLE_SCANS_QUERY.into(),
+            source,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn counter_delta_handles_resets() {
+        assert_eq!(counter_delta(150, 100), 50);
+        assert_eq!(counter_delta(20, 100), 20);
+    }
+
+    #[rstest]
+    #[case(42, "42s")]
+    #[case(600, "10m")]
+    #[case(3 * 3600 + 120, "3h 2m")]
+    #[case(9 * 86400 + 5 * 3600, "9d 5h")]
+    fn format_window_picks_largest_unit(#[case] secs: u64, #[case] expected: &str) {
+        assert_eq!(format_window(secs), expected);
+    }
+
+    #[rstest]
+    #[case(Some(300), 2)]
+    #[case(Some(2 * 86400), 1)]
+    #[case(Some(30 * 86400), 0)]
+    #[case(None, 0)]
+    fn stats_age_warnings_scale_with_window(#[case] age: Option<u64>, #[case] expected: usize) {
+        let stats = SystemStats {
+            stats_reset_at: Some("2026-10-16 09:00:00+00".to_string()),
+            stats_age_secs: age,
+            ..Default::default()
+        };
+        assert_eq!(stats_age_warnings(&stats).len(), expected);
+    }
+
+    #[test]
+    fn table_scan_delta_uses_baseline_when_present() {
+        let mut baseline = StatsBaseline::default();
+        baseline
+            .table_scans
+            .insert(("public".into(), "orders".into()), (1_000, 50));
+
+        assert_eq!(
+            baseline.table_scan_delta("public", "orders", 1_030, 90),
+            (30, 40)
+        );
+        assert_eq!(
+            baseline.table_scan_delta("public", "new_table", 5, 1),
+            (5, 1)
+        );
+    }
+}
@@ -0,0 +1,312 @@
+use crate::analysis::extensions::EXTENSION_CATALOG_SCHEMAS;
+use crate::checker::CheckerError;
+use crate::i18n::{Lang, Message};
+use crate::models::{AnalysisResults, ConfigCategory, ConfigSuggestion, SuggestionLevel};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres, Row};
+use std::collections::HashSet;
+
+type Result<T> = std::result::Result<T, CheckerError>;
+
+/// Fewer varchar(255) columns than this are taken as deliberate limits
+const VARCHAR_255_MIN_COLUMNS: usize = 5;
+/// Objects named in a finding's rationale; the rest are only counted
+const EXAMPLES: usize = 5;
+
+/// Filter on `pg_class c` and `pg_namespace n` for user tables, skipping
+/// partitions and tables owned by extensions; binds the extension catalog
+/// schemas as `$1`
+pub(crate) const USER_TABLES: &str = r#"
+    c.relkind IN ('r', 'p')
+      AND NOT c.relispartition
+      AND n.nspname NOT IN ('pg_catalog', 'information_schema')
+      AND n.nspname NOT LIKE 'pg\_toast%'
+      AND n.nspname NOT LIKE 'pg\_temp\_%'
+      AND n.nspname <> ALL($1)
+      AND NOT EXISTS (
+          SELECT 1 FROM pg_depend d
+          WHERE d.classid = 'pg_class'::regclass AND d.objid = c.oid AND d.deptype = 'e'
+      )
+"#;
+
+/// User tables and columns matching the schema anti-patterns, as
+/// `schema.table.column` (or `schema.table(columns)` for foreign keys)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchemaLint {
+    pub varchar_255: Vec<String>,
+    pub timestamp_without_time_zone: Vec<String>,
+    pub money: Vec<String>,
+    pub tables_without_primary_key: Vec<String>,
+    pub unindexed_nullable_foreign_keys: Vec<String>,
+}
+
+pub(crate) fn lint_columns_query() -> String {
+    format!(
+        r#"
+        SELECT
+            format('%s.%s.%s', n.nspname, c.relname, a.attname) AS name,
+            format_type(a.atttypid, a.atttypmod) AS data_type
+        FROM pg_attribute a
+        JOIN pg_class c ON c.oid = a.attrelid
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE {USER_TABLES}
+          AND a.attnum > 0
+          AND NOT a.attisdropped
+          AND format_type(a.atttypid, a.atttypmod) IN
+              ('character varying(255)', 'timestamp without time zone', 'money')
+        ORDER BY 1
+    "#
+    )
+}
+
+pub(crate) fn without_primary_key_query() -> String {
+    format!(
+        r#"
+        SELECT format('%s.%s', n.nspname, c.relname) AS name
+        FROM pg_class c
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE {USER_TABLES}
+          AND NOT EXISTS (
+              SELECT 1 FROM pg_constraint k WHERE k.conrelid = c.oid AND k.contype = 'p'
+          )
+        ORDER BY 1
+    "#
+    )
+}
+
+pub(crate) fn unindexed_foreign_keys_query() -> String {
+    format!(
+        r#"
+        SELECT format('%s.%s(%s)', n.nspname, c.relname, (
+            SELECT string_agg(a.attname, ', ' ORDER BY array_position(k.conkey, a.attnum))
+            FROM pg_attribute a
+            WHERE a.attrelid = k.conrelid AND a.attnum = ANY(k.conkey)
+        )) AS name
+        FROM pg_constraint k
+        JOIN pg_class c ON c.oid = k.conrelid
+        JOIN pg_namespace n ON n.oid = c.relnamespace
+        WHERE {USER_TABLES}
+          AND k.contype = 'f'
+          AND EXISTS (
+              SELECT 1 FROM pg_attribute a
+              WHERE a.attrelid = k.conrelid AND a.attnum = ANY(k.conkey) AND NOT a.attnotnull
+          )
+          AND NOT EXISTS (
+              SELECT 1 FROM pg_index i
+              WHERE i.indrelid = k.conrelid
+                AND (string_to_array(i.indkey::text, ' ')::int2[])[1:cardinality(k.conkey)]
+                    @> k.conkey
+          )
+        ORDER BY 1
+    "#
+    )
+}
+
+/// Reads the column types, primary keys and foreign keys of user tables,
+/// skipping partitions and tables owned by extensions
+pub async fn fetch_schema_lint(pool: &Pool<Postgres>) -> Result<SchemaLint> {
+    let fetch = |query: String| async move {
+        sqlx::query(&query)
+            .bind(EXTENSION_CATALOG_SCHEMAS)
+            .fetch_all(pool)
+            .await
+            .map_err(|source| CheckerError::QueryError {
+                query: query.clone(),
+                source,
+            })
+    };
+    let mut lint = SchemaLint::default();
+    for row in fetch(lint_columns_query()).await? {
+        let name: String = row.get("name");
+        match row.get::<String, _>("data_type").as_str() {
+            "character varying(255)" => lint.varchar_255.push(name),
+            "timestamp without time zone" => lint.timestamp_without_time_zone.push(name),
+            _ => lint.money.push(name),
+        }
+    }
+    lint.tables_without_primary_key = fetch(without_primary_key_query())
+        .await?
+        .iter()
+        .map(|row| row.get("name"))
+        .collect();
+    lint.unindexed_nullable_foreign_keys = fetch(unindexed_foreign_keys_query())
+        .await?
+        .iter()
+        .map(|row| row.get("name"))
+        .collect();
+    Ok(lint)
+}
+
+/// Reports each anti-pattern found as one Info-level finding with its count,
+/// since no setting compensates for the schema itself
+pub fn analyze_schema_lint(lint: &SchemaLint, results: &mut AnalysisResults) {
+    let column_findings = [
+        (
+            &lint.varchar_255,
+            VARCHAR_255_MIN_COLUMNS,
+            "schema varchar(255) columns",
+            "text",
+            "schema.varchar_255",
+        ),
+        (
+            &lint.timestamp_without_time_zone,
+            1,
+            "schema timestamp columns",
+            "timestamptz",
+            "schema.timestamp_without_time_zone",
+        ),
+        (
+            &lint.money,
+            1,
+            "schema money columns",
+            "numeric",
+            "schema.money",
+        ),
+    ];
+    for (columns, min_columns, parameter, suggested, key) in column_findings {
+        if columns.len() < min_columns {
+            continue;
+        }
+        let tables = columns
+            .iter()
+            .filter_map(|column| column.rsplit_once('.').map(|(table, _)| table))
+            .collect::<HashSet<_>>()
+            .len();
+        add_suggestion(
+            results,
+            parameter,
+            &format!("{} columns in {tables} tables", columns.len()),
+            suggested,
+            Message::new(key)
+                .arg("count", columns.len())
+                .arg("tables", tables)
+                .arg("examples", examples(columns)),
+        );
+    }
+
+    let object_findings = [
+        (
+            &lint.tables_without_primary_key,
+            "schema tables without primary key",
+            "tables",
+            "Add primary keys",
+            "schema.missing_primary_key",
+        ),
+        (
+            &lint.unindexed_nullable_foreign_keys,
+            "schema unindexed nullable foreign keys",
+            "foreign keys",
+            "Index the foreign key columns",
+            "schema.unindexed_nullable_fk",
+        ),
+    ];
+    for (objects, parameter, noun, suggested, key) in object_findings {
+        if objects.is_empty() {
+            continue;
+        }
+        add_suggestion(
+            results,
+            parameter,
+            &format!("{} {noun}", objects.len()),
+            suggested,
+            Message::new(key)
+                .arg("count", objects.len())
+                .arg("examples", examples(objects)),
+        );
+    }
+}
+
+/// The first few names, with a count of the ones left out
+fn examples(names: &[String]) -> String {
+    let shown = names[..names.len().min(EXAMPLES)].join(", ");
+    match names.len().saturating_sub(EXAMPLES) {
+        0 => shown,
+        more => format!("{shown} and {more} more"),
+    }
+}
+
+fn add_suggestion(
+    results: &mut AnalysisResults,
+    parameter: &str,
+    current_value: &str,
+    suggested_value: &str,
+    rationale: Message,
+) {
+    results
+        .suggestions_by_category
+        .entry(ConfigCategory::Schema)
+        .or_default()
+        .push(ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: current_value.to_string(),
+            suggested_value: suggested_value.to_string(),
+            level: SuggestionLevel::Info,
+            rationale: rationale.render(Lang::En),
+            messages: vec![rationale],
+            fingerprint: String::new(),
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn names(prefix: &str, count: usize) -> Vec<String> {
+        (0..count)
+            .map(|i| format!("app.{prefix}{}.col", i % 2))
+            .collect()
+    }
+
+    fn analyzed(lint: &SchemaLint) -> Vec<ConfigSuggestion> {
+        let mut results = AnalysisResults::default();
+        analyze_schema_lint(lint, &mut results);
+        results
+            .suggestions_by_category
+            .remove(&ConfigCategory::Schema)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn clean_schema_has_no_findings() {
+        assert!(analyzed(&SchemaLint::default()).is_empty());
+    }
+
+    #[rstest]
+    #[case::few_varchar_255(SchemaLint { varchar_255: names("t", 4), ..Default::default() }, None)]
+    #[case::widespread_varchar_255(
+        SchemaLint { varchar_255: names("t", 7), ..Default::default() },
+        Some(("schema varchar(255) columns", "7 columns in 2 tables"))
+    )]
+    #[case::timestamp(
+        SchemaLint { timestamp_without_time_zone: names("t", 3), ..Default::default() },
+        Some(("schema timestamp columns", "3 columns in 2 tables"))
+    )]
+    #[case::missing_primary_key(
+        SchemaLint { tables_without_primary_key: vec!["app.events".into()], ..Default::default() },
+        Some(("schema tables without primary key", "1 tables"))
+    )]
+    fn findings_carry_counts(#[case] lint: SchemaLint, #[case] expected: Option<(&str, &str)>) {
+        let suggestions = analyzed(&lint);
+        let found: Vec<(&str, &str)> = suggestions
+            .iter()
+            .map(|s| (s.parameter.as_str(), s.current_value.as_str()))
+            .collect();
+        assert_eq!(found, Vec::from_iter(expected));
+        assert!(suggestions.iter().all(|s| s.level == SuggestionLevel::Info));
+    }
+
+    #[test]
+    fn rationale_names_a_few_examples() {
+        let lint = SchemaLint {
+            unindexed_nullable_foreign_keys: (0..7)
+                .map(|i| format!("app.orders(customer_{i})"))
+                .collect(),
+            ..Default::default()
+        };
+        let suggestions = analyzed(&lint);
+        assert!(suggestions[0]
+            .rationale
+            .contains("app.orders(customer_4) and 2 more"));
+    }
+}
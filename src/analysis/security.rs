@@ -0,0 +1,483 @@
+use crate::checker::CheckerError;
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ConfigCategory, ConfigSuggestion, PgConfigParam, RowSecurityPolicy,
+    RowSecurityTable, SuggestionLevel, SystemStats,
+};
+use sqlx::{Pool, Postgres, Row};
+use std::collections::HashMap;
+use tracing::warn;
+
+type Result<T> = std::result::Result<T, CheckerError>;
+
+pub(crate) const ROW_SECURITY_TABLES_QUERY: &str = r#"
+    SELECT
+        n.nspname::text AS schema,
+        c.relname::text AS table_name,
+        pg_get_userbyid(c.relowner)::text AS owner,
+        c.relrowsecurity AS enabled,
+        c.relforcerowsecurity AS forced
+    FROM pg_class c
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    WHERE c.relkind IN ('r', 'p')
+      AND (c.relrowsecurity OR EXISTS (SELECT 1 FROM pg_policy p WHERE p.polrelid = c.oid))
+    ORDER BY 1, 2
+"#;
+
+pub(crate) const POLICIES_QUERY: &str = r#"
+    SELECT
+        schemaname::text AS schema,
+        tablename::text AS table_name,
+        policyname::text AS name,
+        permissive = 'PERMISSIVE' AS permissive,
+        roles::text[] AS roles,
+        cmd AS command,
+        qual AS using,
+        with_check
+    FROM pg_policies
+    ORDER BY 1, 2, 3
+"#;
+
+/// Tables with row-level security enabled or any policy, with their policies
+pub async fn fetch_row_security(pool: &Pool<Postgres>) -> Vec<RowSecurityTable> {
+    let tables = match sqlx::query(ROW_SECURITY_TABLES_QUERY).fetch_all(pool).await {
+        Ok(rows) => rows,
+        Err(err) => {
+            warn!("Failed to read row-level security tables: {err}");
+            return Vec::new();
+        }
+    };
+    let policies = sqlx::query(POLICIES_QUERY)
+        .fetch_all(pool)
+        .await
+        .unwrap_or_else(|err| {
+            warn!("Failed to read pg_policies: {err}");
+            Vec::new()
+        });
+
+    let mut by_table: HashMap<(String, String), Vec<RowSecurityPolicy>> = HashMap::new();
+    for row in policies {
+        by_table
+            .entry((row.get("schema"), row.get("table_name")))
+            .or_default()
+            .push(RowSecurityPolicy {
+                name: row.get("name"),
+                permissive: row.get("permissive"),
+                roles: row.get("roles"),
+                command: row.get("command"),
+                using: row.get("using"),
+                with_check: row.get("with_check"),
+            });
+    }
+    tables
+        .into_iter()
+        .map(|row| {
+            let schema: String = row.get("schema");
+            let table_name: String = row.get("table_name");
+            let policies = by_table
+                .remove(&(schema.clone(), table_name.clone()))
+                .unwrap_or_default();
+            RowSecurityTable {
+                schema,
+                table_name,
+                owner: row.get("owner"),
+                enabled: row.get("enabled"),
+                forced: row.get("forced"),
+                policies,
+            }
+        })
+        .collect()
+}
+
+pub(crate) const PUBLIC_SCHEMA_CREATE_QUERY: &str = r#"
+    SELECT EXISTS (
+        SELECT 1
+        FROM pg_namespace n,
+             aclexplode(coalesce(n.nspacl, acldefault('n', n.nspowner))) a
+        WHERE n.nspname = 'public'
+          AND a.grantee = 0
+          AND a.privilege_type = 'CREATE'
+    ) AS public_create
+"#;
+
+/// Whether PUBLIC may create objects in the public schema, as it can by
+/// default before PostgreSQL 15
+pub async fn fetch_public_schema_create(pool: &Pool<Postgres>) -> Option<bool> {
+    match sqlx::query_scalar(PUBLIC_SCHEMA_CREATE_QUERY)
+        .fetch_one(pool)
+        .await
+    {
+        Ok(create) => Some(create),
+        Err(err) => {
+            warn!("Failed to read the public schema privileges: {err}");
+            None
+        }
+    }
+}
+
+/// Flags a `search_path` that includes the public schema while every role
+/// may create objects there: unqualified CREATEs land in a schema anyone can
+/// write, and any role can plant functions or tables that shadow the ones
+/// other sessions mean to use
+pub fn analyze_search_path(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    let Some(param) = params.get("search_path") else {
+        return Ok(());
+    };
+    if stats.public_schema_create != Some(true) {
+        return Ok(());
+    }
+    let searches_public = param
+        .current_value
+        .split(',')
+        .any(|schema| schema.trim().trim_matches('"') == "public");
+    if searches_public {
+        add_suggestion(
+            results,
+            "schema public",
+            "CREATE granted to PUBLIC",
+            "REVOKE CREATE ON SCHEMA public FROM PUBLIC",
+            SuggestionLevel::Important,
+            Message::new("security.public_schema_create").arg("search_path", &param.current_value),
+        );
+    }
+
+    Ok(())
+}
+
+/// Flags row-level security that blocks every row, lets every row through,
+/// or does not apply at all: enabled without policies, policies on tables
+/// without RLS, permissive `USING (true)` policies, owners bypassing
+/// unforced RLS, and `row_security = off`
+pub fn analyze_row_security(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    for table in &stats.row_security_tables {
+        let name = format!("{}.{}", table.schema, table.table_name);
+        let parameter = format!("table {name}");
+        if !table.enabled {
+            add_suggestion(
+                results,
+                &parameter,
+                "row security disabled",
+                "Enable row level security",
+                SuggestionLevel::Important,
+                Message::new("security.policies_without_rls")
+                    .arg("table", &name)
+                    .arg("policies", table.policies.len()),
+            );
+            continue;
+        }
+        if table.policies.is_empty() {
+            let key = if table.forced {
+                "security.rls_without_policies.forced"
+            } else {
+                "security.rls_without_policies"
+            };
+            add_suggestion(
+                results,
+                &parameter,
+                "no policies",
+                "Create policies",
+                SuggestionLevel::Important,
+                Message::new(key)
+                    .arg("table", &name)
+                    .arg("owner", &table.owner),
+            );
+            continue;
+        }
+        if !table.forced {
+            add_suggestion(
+                results,
+                &parameter,
+                "owner bypasses policies",
+                "Force row level security",
+                SuggestionLevel::Recommended,
+                Message::new("security.rls_not_forced")
+                    .arg("table", &name)
+                    .arg("owner", &table.owner),
+            );
+        }
+        for policy in table
+            .policies
+            .iter()
+            .filter(|policy| allows_every_row(policy))
+        {
+            let public = policy.roles.iter().any(|role| role == "public");
+            add_suggestion(
+                results,
+                &format!("policy {} on {name}", policy.name),
+                &format!("{} {}", policy.command, describe_expression(policy)),
+                "Restrict the policy expression",
+                if public {
+                    SuggestionLevel::Important
+                } else {
+                    SuggestionLevel::Recommended
+                },
+                Message::new("security.permissive_policy")
+                    .arg("policy", &policy.name)
+                    .arg("table", &name)
+                    .arg("command", &policy.command)
+                    .arg("roles", policy.roles.join(", ")),
+            );
+        }
+    }
+
+    let enabled = stats
+        .row_security_tables
+        .iter()
+        .filter(|table| table.enabled)
+        .count();
+    if let Some(param) = params.get("row_security") {
+        if enabled > 0 && param.current_value == "off" {
+            add_suggestion(
+                results,
+                "row_security",
+                &param.current_value,
+                "on",
+                SuggestionLevel::Important,
+                Message::new("security.row_security").arg("tables", enabled),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// A permissive policy whose expression for its command is constant true
+fn allows_every_row(policy: &RowSecurityPolicy) -> bool {
+    let is_true = |expression: &Option<String>| {
+        expression
+            .as_deref()
+            .is_some_and(|expression| expression.trim().eq_ignore_ascii_case("true"))
+    };
+    // INSERT policies have only WITH CHECK; the others are judged by USING
+    policy.permissive
+        && if policy.command == "INSERT" {
+            is_true(&policy.with_check)
+        } else {
+            is_true(&policy.using)
+        }
+}
+
+fn describe_expression(policy: &RowSecurityPolicy) -> &'static str {
+    if policy.command == "INSERT" {
+        "WITH CHECK (true)"
+    } else {
+        "USING (true)"
+    }
+}
+
+fn add_suggestion(
+    results: &mut AnalysisResults,
+    parameter: &str,
+    current_value: &str,
+    suggested_value: &str,
+    level: SuggestionLevel,
+    rationale: Message,
+) {
+    results
+        .suggestions_by_category
+        .entry(ConfigCategory::Security)
+        .or_default()
+        .push(ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: current_value.to_string(),
+            suggested_value: suggested_value.to_string(),
+            level,
+            rationale: rationale.render(Lang::En),
+            messages: vec![rationale],
+            fingerprint: String::new(),
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ParamValue;
+    use rstest::rstest;
+
+    fn policy(name: &str, command: &str, using: Option<&str>, roles: &[&str]) -> RowSecurityPolicy {
+        RowSecurityPolicy {
+            name: name.to_string(),
+            permissive: true,
+            roles: roles.iter().map(|role| role.to_string()).collect(),
+            command: command.to_string(),
+            using: using.map(str::to_string),
+            with_check: None,
+        }
+    }
+
+    fn table(
+        name: &str,
+        enabled: bool,
+        forced: bool,
+        policies: Vec<RowSecurityPolicy>,
+    ) -> RowSecurityTable {
+        RowSecurityTable {
+            schema: "app".to_string(),
+            table_name: name.to_string(),
+            owner: "app_owner".to_string(),
+            enabled,
+            forced,
+            policies,
+        }
+    }
+
+    fn analyzed(tables: Vec<RowSecurityTable>, row_security: &str) -> Vec<ConfigSuggestion> {
+        let param = PgConfigParam {
+            name: "row_security".to_string(),
+            current_value: row_security.to_string(),
+            default_value: None,
+            unit: None,
+            context: "user".to_string(),
+            value: ParamValue::parse(row_security, "bool", None),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        };
+        let stats = SystemStats {
+            row_security_tables: tables,
+            ..Default::default()
+        };
+        let mut results = AnalysisResults::default();
+        analyze_row_security(
+            &HashMap::from([(param.name.clone(), param)]),
+            &stats,
+            &mut results,
+        )
+        .unwrap();
+        results
+            .suggestions_by_category
+            .remove(&ConfigCategory::Security)
+            .unwrap_or_default()
+    }
+
+    #[rstest]
+    #[case::default_path("\"$user\", public", Some(true), true)]
+    #[case::create_revoked("\"$user\", public", Some(false), false)]
+    #[case::public_not_searched("app, pg_catalog", Some(true), false)]
+    #[case::privileges_unknown("\"$user\", public", None, false)]
+    fn writable_public_schema_on_search_path_is_flagged(
+        #[case] search_path: &str,
+        #[case] public_schema_create: Option<bool>,
+        #[case] flagged: bool,
+    ) {
+        let param = PgConfigParam {
+            name: "search_path".to_string(),
+            current_value: search_path.to_string(),
+            default_value: None,
+            unit: None,
+            context: "user".to_string(),
+            value: ParamValue::parse(search_path, "string", None),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        };
+        let stats = SystemStats {
+            public_schema_create,
+            ..Default::default()
+        };
+        let mut results = AnalysisResults::default();
+        analyze_search_path(
+            &HashMap::from([(param.name.clone(), param)]),
+            &stats,
+            &mut results,
+        )
+        .unwrap();
+        let suggestions = results
+            .suggestions_by_category
+            .remove(&ConfigCategory::Security)
+            .unwrap_or_default();
+        assert_eq!(suggestions.len(), usize::from(flagged));
+        if flagged {
+            assert_eq!(suggestions[0].parameter, "schema public");
+            assert_eq!(suggestions[0].level, SuggestionLevel::Important);
+        }
+    }
+
+    #[test]
+    fn tenant_isolated_forced_tables_pass() {
+        let tenant = policy(
+            "tenant_isolation",
+            "ALL",
+            Some("(tenant_id = (current_setting('app.tenant_id'::text))::uuid)"),
+            &["app_user"],
+        );
+        assert!(analyzed(vec![table("orders", true, true, vec![tenant])], "on").is_empty());
+    }
+
+    #[rstest]
+    #[case(table("orders", true, false, vec![]), "Create policies", SuggestionLevel::Important)]
+    #[case(table("orders", false, false, vec![policy("p", "ALL", Some("(tenant_id = 1)"), &["public"])]), "Enable row level security", SuggestionLevel::Important)]
+    #[case(table("orders", true, false, vec![policy("p", "ALL", Some("(tenant_id = 1)"), &["public"])]), "Force row level security", SuggestionLevel::Recommended)]
+    fn table_level_gaps_are_flagged(
+        #[case] table: RowSecurityTable,
+        #[case] suggested: &str,
+        #[case] level: SuggestionLevel,
+    ) {
+        let suggestions = analyzed(vec![table], "on");
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].parameter, "table app.orders");
+        assert_eq!(suggestions[0].suggested_value, suggested);
+        assert_eq!(suggestions[0].level, level);
+    }
+
+    #[test]
+    fn forced_table_without_policies_mentions_the_owner() {
+        let suggestions = analyzed(vec![table("orders", true, true, vec![])], "on");
+        assert!(suggestions[0]
+            .rationale
+            .contains("including its owner app_owner"));
+    }
+
+    #[test]
+    fn always_true_policies_are_flagged_by_role() {
+        let mut insert = policy("anyone_inserts", "INSERT", None, &["app_user"]);
+        insert.with_check = Some("true".to_string());
+        let mut restrictive = policy("restrict", "ALL", Some("true"), &["public"]);
+        restrictive.permissive = false;
+        let policies = vec![
+            policy("read_all", "SELECT", Some("true"), &["public"]),
+            insert,
+            restrictive,
+        ];
+        let suggestions = analyzed(vec![table("orders", true, true, policies)], "on");
+        let flagged: Vec<(&str, &str, SuggestionLevel)> = suggestions
+            .iter()
+            .map(|s| (s.parameter.as_str(), s.current_value.as_str(), s.level))
+            .collect();
+        assert_eq!(
+            flagged,
+            [
+                (
+                    "policy read_all on app.orders",
+                    "SELECT USING (true)",
+                    SuggestionLevel::Important
+                ),
+                (
+                    "policy anyone_inserts on app.orders",
+                    "INSERT WITH CHECK (true)",
+                    SuggestionLevel::Recommended
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn row_security_off_is_flagged_only_with_rls_tables() {
+        let tenant = policy("tenant", "ALL", Some("(tenant_id = 1)"), &["app_user"]);
+        let suggestions = analyzed(vec![table("orders", true, true, vec![tenant])], "off");
+        assert_eq!(suggestions[0].parameter, "row_security");
+        assert!(analyzed(Vec::new(), "off").is_empty());
+    }
+}
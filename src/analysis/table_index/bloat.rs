@@ -1,24 +1,39 @@
-use super::push_table_index_suggestion;
+use super::top_k::TopK;
+use super::{cap_findings, findings_limit, push_table_index_suggestion};
+use crate::analysis::extensions::EXTENSION_CATALOG_SCHEMAS;
+use crate::analysis::sampling::StatsBaseline;
 use crate::checker::CheckerError;
-use crate::models::{AnalysisResults, SuggestionLevel, TableBloatInfo, TableSeqScanInfo};
+use crate::fixture::Fixture;
+use crate::i18n::Message;
+use crate::models::{
+    AnalysisResults, ByteSize, Seconds, SuggestionLevel, TableBloatInfo, TableSeqScanInfo,
+    TableVacuumActivity, VacuumActivity,
+};
+use futures_util::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
 use sqlx::{Pool, Postgres, Row};
 use std::cmp::Ordering;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 const TABLE_BLOAT_MIN_ROWS: i64 = 10_000;
 const TABLE_MIN_SIZE_BYTES: i64 = 5 * 1024 * 1024; // 5MB
 const TABLE_DEAD_RATIO_ALERT: f64 = 0.20;
 const TABLE_DEAD_RATIO_CRITICAL: f64 = 0.50;
 const AUTOVAC_STALE_SECONDS: f64 = 86_400.0; // 24h
+/// Built-in caps, replaced by `--max-findings-per-kind`
 const MAX_BLOAT_RESULTS: usize = 10;
 const MAX_SEQ_SCAN_RESULTS: usize = 10;
 const SEQ_SCAN_MULTIPLIER: i64 = 50;
+const MAX_VACUUM_ACTIVITY_TABLES: usize = 20;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct TableStatRow {
     schema: String,
     table_name: String,
     live_tuples: i64,
     dead_tuples: i64,
+    dead_tuples_created: i64,
     seq_scan: i64,
     idx_scan: i64,
     table_size_bytes: i64,
@@ -41,95 +56,282 @@ impl TableStatRow {
 
 pub(super) async fn analyze(
     pool: &Pool<Postgres>,
+    fixture: &Fixture,
     results: &mut AnalysisResults,
+    baseline: Option<&StatsBaseline>,
+    max_per_kind: Option<usize>,
 ) -> Result<(), CheckerError> {
-    let table_rows = fetch_table_stats(pool).await?;
+    let selection = TableSelection::new(
+        baseline,
+        findings_limit(max_per_kind, Some(MAX_BLOAT_RESULTS)),
+        findings_limit(max_per_kind, Some(MAX_SEQ_SCAN_RESULTS)),
+    );
+    let mut matches = TableMatches::default();
+    let mut table_rows = fixture
+        .try_serve(
+            "table_stats",
+            fetch_table_stats(pool, selection, &mut matches),
+        )
+        .await?;
+    results.vacuum_activity = Some(vacuum_activity(&table_rows));
+    if let Some(baseline) = baseline {
+        apply_baseline(&mut table_rows, baseline);
+    }
 
-    let bloat_candidates = identify_bloat_tables(&table_rows);
-    results.bloat_info = bloat_candidates.clone();
+    let mut bloat_candidates = identify_bloat_tables(&table_rows);
+    count_unselected(matches.bloat, bloat_candidates.len(), "bloat", results);
+    cap_findings(
+        &mut bloat_candidates,
+        max_per_kind,
+        Some(MAX_BLOAT_RESULTS),
+        "bloat",
+        &mut results.table_health,
+    );
+    results.table_health.bloat_info = bloat_candidates.clone();
     add_bloat_suggestions(&bloat_candidates, results);
 
-    let seq_scan_candidates = identify_seq_scan_hotspots(&table_rows);
-    results.seq_scan_info = seq_scan_candidates.clone();
+    let mut seq_scan_candidates = identify_seq_scan_hotspots(&table_rows);
+    count_unselected(
+        matches.seq_scan,
+        seq_scan_candidates.len(),
+        "seq_scan",
+        results,
+    );
+    cap_findings(
+        &mut seq_scan_candidates,
+        max_per_kind,
+        Some(MAX_SEQ_SCAN_RESULTS),
+        "seq_scan",
+        &mut results.table_health,
+    );
+    results.table_health.seq_scan_info = seq_scan_candidates.clone();
     add_seq_scan_suggestions(&seq_scan_candidates, results);
 
     Ok(())
 }
 
-async fn fetch_table_stats(pool: &Pool<Postgres>) -> Result<Vec<TableStatRow>, CheckerError> {
-    const QUERY: &str = r#"
-        SELECT
-            s.schemaname,
-            s.relname,
-            COALESCE(s.n_live_tup, 0) AS n_live_tup,
-            COALESCE(s.n_dead_tup, 0) AS n_dead_tup,
-            COALESCE(s.seq_scan, 0) AS seq_scan,
-            COALESCE(s.idx_scan, 0) AS idx_scan,
-            pg_relation_size(s.relid) AS table_size_bytes,
-            pg_size_pretty(pg_relation_size(s.relid)) AS table_size_pretty,
-            to_char(s.last_autovacuum, 'YYYY-MM-DD HH24:MI:SS') AS last_autovacuum_text,
-            to_char(s.last_autoanalyze, 'YYYY-MM-DD HH24:MI:SS') AS last_autoanalyze_text,
-            EXTRACT(EPOCH FROM (CURRENT_TIMESTAMP - s.last_autovacuum)) AS seconds_since_last_autovacuum,
-            EXTRACT(EPOCH FROM (CURRENT_TIMESTAMP - s.last_autoanalyze)) AS seconds_since_last_autoanalyze
-        FROM pg_stat_user_tables s
-    "#;
-
-    let rows =
-        sqlx::query(QUERY)
-            .fetch_all(pool)
-            .await
-            .map_err(|source| CheckerError::QueryError {
-                query: QUERY.into(),
-                source,
-            })?;
-
-    let mut stats = Vec::with_capacity(rows.len());
+/// Counts the tables of `kind` the selection dropped as not shown; the ones it
+/// kept beyond the cap are counted by [`cap_findings`]
+fn count_unselected(matches: usize, kept: usize, kind: &str, results: &mut AnalysisResults) {
+    if matches > kept {
+        *results
+            .table_health
+            .not_shown
+            .entry(kind.to_string())
+            .or_default() += matches - kept;
+    }
+}
+
+pub(crate) const TABLE_STATS_QUERY: &str = r#"
+    SELECT
+        s.schemaname,
+        s.relname,
+        COALESCE(s.n_live_tup, 0) AS n_live_tup,
+        COALESCE(s.n_dead_tup, 0) AS n_dead_tup,
+        COALESCE(s.n_tup_upd, 0) + COALESCE(s.n_tup_del, 0) AS dead_tuples_created,
+        COALESCE(s.seq_scan, 0) AS seq_scan,
+        COALESCE(s.idx_scan, 0) AS idx_scan,
+        pg_relation_size(s.relid) AS table_size_bytes,
+        pg_size_pretty(pg_relation_size(s.relid)) AS table_size_pretty,
+        to_char(s.last_autovacuum, 'YYYY-MM-DD HH24:MI:SS') AS last_autovacuum_text,
+        to_char(s.last_autoanalyze, 'YYYY-MM-DD HH24:MI:SS') AS last_autoanalyze_text,
+        EXTRACT(EPOCH FROM (CURRENT_TIMESTAMP - s.last_autovacuum)) AS seconds_since_last_autovacuum,
+        EXTRACT(EPOCH FROM (CURRENT_TIMESTAMP - s.last_autoanalyze)) AS seconds_since_last_autoanalyze
+    FROM pg_stat_user_tables s
+    WHERE s.schemaname <> ALL($1)
+"#;
+
+/// Streams `pg_stat_user_tables` through a [`TableSelection`], so memory stays
+/// bounded however many tables the database has. `matches` receives the number
+/// of tables per finding kind; it stays empty when a fixture is replayed.
+async fn fetch_table_stats(
+    pool: &Pool<Postgres>,
+    mut selection: TableSelection<'_>,
+    matches: &mut TableMatches,
+) -> Result<Vec<TableStatRow>, CheckerError> {
+    let mut rows = sqlx::query(TABLE_STATS_QUERY)
+        .bind(EXTENSION_CATALOG_SCHEMAS)
+        .fetch(pool);
+
+    while let Some(row) = rows
+        .try_next()
+        .await
+        .map_err(|source| CheckerError::QueryError {
+            query: TABLE_STATS_QUERY.into(),
+            source,
+        })?
+    {
+        selection.push(table_stat_row(&row));
+    }
+    let (rows, counted) = selection.into_rows();
+    *matches = counted;
+    Ok(rows)
+}
+
+/// Tables matching each finding kind, including those not kept
+#[derive(Debug, Default, PartialEq)]
+struct TableMatches {
+    bloat: usize,
+    seq_scan: usize,
+}
+
+/// The tables any finding can name, each kind keeping only as many as it
+/// shows: the large tables with the highest dead-tuple ratio and with the most
+/// sequential scans, plus the tables with the most dead tuples created for the
+/// vacuum activity. Scans are ranked by their window deltas under a baseline.
+struct TableSelection<'a> {
+    baseline: Option<&'a StatsBaseline>,
+    /// Keyed by dead tuples per million live ones
+    most_bloated: TopK<i64, TableStatRow>,
+    most_seq_scans: TopK<i64, TableStatRow>,
+    most_dead_tuples: TopK<i64, TableStatRow>,
+    matches: TableMatches,
+    position: usize,
+}
+
+impl<'a> TableSelection<'a> {
+    /// `None` limits keep every table of that kind
+    fn new(
+        baseline: Option<&'a StatsBaseline>,
+        bloat_limit: Option<usize>,
+        seq_scan_limit: Option<usize>,
+    ) -> Self {
+        Self {
+            baseline,
+            most_bloated: TopK::new(bloat_limit.unwrap_or(usize::MAX)),
+            most_seq_scans: TopK::new(seq_scan_limit.unwrap_or(usize::MAX)),
+            most_dead_tuples: TopK::new(MAX_VACUUM_ACTIVITY_TABLES),
+            matches: TableMatches::default(),
+            position: 0,
+        }
+    }
+
+    fn push(&mut self, row: TableStatRow) {
+        let position = self.position;
+        self.position += 1;
+        if is_bloated(&row) {
+            self.matches.bloat += 1;
+            let key = (row.dead_ratio() * 1_000_000.0) as i64;
+            self.most_bloated.push(key, position, row.clone());
+        }
+        let (seq_scan, idx_scan) = match self.baseline {
+            Some(baseline) => {
+                baseline.table_scan_delta(&row.schema, &row.table_name, row.seq_scan, row.idx_scan)
+            }
+            None => (row.seq_scan, row.idx_scan),
+        };
+        if is_seq_scan_hotspot(&row, seq_scan, idx_scan) {
+            self.matches.seq_scan += 1;
+            self.most_seq_scans.push(seq_scan, position, row.clone());
+        }
+        if row.dead_tuples_created > 0 {
+            self.most_dead_tuples
+                .push(row.dead_tuples_created, position, row);
+        }
+    }
+
+    /// The kept rows in the order they were read, each once, and the matches
+    fn into_rows(self) -> (Vec<TableStatRow>, TableMatches) {
+        let mut rows = self.most_bloated.into_rows();
+        rows.extend(self.most_seq_scans.into_rows());
+        rows.extend(self.most_dead_tuples.into_rows());
+        rows.sort_by_key(|(position, _)| *position);
+        rows.dedup_by_key(|(position, _)| *position);
+        (rows.into_iter().map(|(_, row)| row).collect(), self.matches)
+    }
+}
+
+/// Large enough for a bloat or sequential-scan finding
+fn is_large(row: &TableStatRow) -> bool {
+    row.live_tuples >= TABLE_BLOAT_MIN_ROWS && row.table_size_bytes >= TABLE_MIN_SIZE_BYTES
+}
+
+fn is_bloated(row: &TableStatRow) -> bool {
+    is_large(row) && row.dead_ratio() >= TABLE_DEAD_RATIO_ALERT
+}
+
+/// `seq_scan` and `idx_scan` are passed in since they may be window deltas
+fn is_seq_scan_hotspot(row: &TableStatRow, seq_scan: i64, idx_scan: i64) -> bool {
+    is_large(row) && seq_scan * SEQ_SCAN_MULTIPLIER > idx_scan.max(1)
+}
+
+fn table_stat_row(row: &PgRow) -> TableStatRow {
+    TableStatRow {
+        schema: row.get("schemaname"),
+        table_name: row.get("relname"),
+        live_tuples: get_i64_or_zero(row, "n_live_tup"),
+        dead_tuples: get_i64_or_zero(row, "n_dead_tup"),
+        dead_tuples_created: get_i64_or_zero(row, "dead_tuples_created"),
+        seq_scan: get_i64_or_zero(row, "seq_scan"),
+        idx_scan: get_i64_or_zero(row, "idx_scan"),
+        table_size_bytes: row.get("table_size_bytes"),
+        table_size_pretty: row.get("table_size_pretty"),
+        last_autovacuum: get_optional_string(row, "last_autovacuum_text"),
+        last_autoanalyze: get_optional_string(row, "last_autoanalyze_text"),
+        seconds_since_last_autovacuum: get_optional_f64(row, "seconds_since_last_autovacuum"),
+        seconds_since_last_autoanalyze: get_optional_f64(row, "seconds_since_last_autoanalyze"),
+    }
+}
+
+/// Replaces cumulative scan counters with the activity inside the sampling window
+fn apply_baseline(rows: &mut [TableStatRow], baseline: &StatsBaseline) {
     for row in rows {
-        stats.push(TableStatRow {
-            schema: row.get("schemaname"),
-            table_name: row.get("relname"),
-            live_tuples: get_i64_or_zero(&row, "n_live_tup"),
-            dead_tuples: get_i64_or_zero(&row, "n_dead_tup"),
-            seq_scan: get_i64_or_zero(&row, "seq_scan"),
-            idx_scan: get_i64_or_zero(&row, "idx_scan"),
-            table_size_bytes: row.get("table_size_bytes"),
-            table_size_pretty: row.get("table_size_pretty"),
-            last_autovacuum: get_optional_string(&row, "last_autovacuum_text"),
-            last_autoanalyze: get_optional_string(&row, "last_autoanalyze_text"),
-            seconds_since_last_autovacuum: get_optional_f64(&row, "seconds_since_last_autovacuum"),
-            seconds_since_last_autoanalyze: get_optional_f64(
-                &row,
-                "seconds_since_last_autoanalyze",
-            ),
-        });
+        let (seq_scan, idx_scan) =
+            baseline.table_scan_delta(&row.schema, &row.table_name, row.seq_scan, row.idx_scan);
+        row.seq_scan = seq_scan;
+        row.idx_scan = idx_scan;
     }
+}
+
+/// Dead-tuple counters of the tables with the most updates and deletes
+fn vacuum_activity(rows: &[TableStatRow]) -> VacuumActivity {
+    let mut tables: Vec<_> = rows
+        .iter()
+        .filter(|row| row.dead_tuples_created > 0)
+        .collect();
+    tables.sort_by_key(|a| std::cmp::Reverse(a.dead_tuples_created));
+    tables.truncate(MAX_VACUUM_ACTIVITY_TABLES);
 
-    Ok(stats)
+    VacuumActivity {
+        captured_at: Seconds(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs_f64())
+                .unwrap_or_default(),
+        ),
+        tables: tables
+            .into_iter()
+            .map(|row| TableVacuumActivity {
+                schema: row.schema.clone(),
+                table_name: row.table_name.clone(),
+                live_tuples: row.live_tuples,
+                dead_tuples: row.dead_tuples,
+                dead_tuples_created: row.dead_tuples_created,
+                table_size_bytes: ByteSize(row.table_size_bytes),
+            })
+            .collect(),
+    }
 }
 
-fn get_i64_or_zero(row: &sqlx::postgres::PgRow, column: &str) -> i64 {
+fn get_i64_or_zero(row: &PgRow, column: &str) -> i64 {
     row.try_get::<Option<i64>, _>(column)
         .ok()
         .flatten()
         .unwrap_or(0)
 }
 
-fn get_optional_string(row: &sqlx::postgres::PgRow, column: &str) -> Option<String> {
+fn get_optional_string(row: &PgRow, column: &str) -> Option<String> {
     row.try_get::<Option<String>, _>(column).ok().flatten()
 }
 
-fn get_optional_f64(row: &sqlx::postgres::PgRow, column: &str) -> Option<f64> {
+fn get_optional_f64(row: &PgRow, column: &str) -> Option<f64> {
     row.try_get::<Option<f64>, _>(column).ok().flatten()
 }
 
 fn identify_bloat_tables(rows: &[TableStatRow]) -> Vec<TableBloatInfo> {
     let mut candidates: Vec<TableBloatInfo> = rows
         .iter()
-        .filter(|row| {
-            row.live_tuples >= TABLE_BLOAT_MIN_ROWS
-                && row.table_size_bytes >= TABLE_MIN_SIZE_BYTES
-                && row.dead_ratio() >= TABLE_DEAD_RATIO_ALERT
-        })
+        .filter(|row| is_bloated(row))
         .map(|row| TableBloatInfo {
             schema: row.schema.clone(),
             table_name: row.table_name.clone(),
@@ -138,12 +340,13 @@ fn identify_bloat_tables(rows: &[TableStatRow]) -> Vec<TableBloatInfo> {
             dead_tup_ratio: row.dead_ratio(),
             seq_scan: row.seq_scan,
             idx_scan: row.idx_scan,
-            table_size_bytes: row.table_size_bytes,
+            table_size_bytes: ByteSize(row.table_size_bytes),
             table_size_pretty: row.table_size_pretty.clone(),
             last_autovacuum: row.last_autovacuum.clone(),
             last_autoanalyze: row.last_autoanalyze.clone(),
-            seconds_since_last_autovacuum: row.seconds_since_last_autovacuum,
-            seconds_since_last_autoanalyze: row.seconds_since_last_autoanalyze,
+            seconds_since_last_autovacuum: row.seconds_since_last_autovacuum.map(Seconds),
+            seconds_since_last_autoanalyze: row.seconds_since_last_autoanalyze.map(Seconds),
+            fingerprint: String::new(),
         })
         .collect();
 
@@ -152,31 +355,26 @@ fn identify_bloat_tables(rows: &[TableStatRow]) -> Vec<TableBloatInfo> {
             .partial_cmp(&a.dead_tup_ratio)
             .unwrap_or(Ordering::Equal)
     });
-    candidates.truncate(MAX_BLOAT_RESULTS);
     candidates
 }
 
 fn identify_seq_scan_hotspots(rows: &[TableStatRow]) -> Vec<TableSeqScanInfo> {
     let mut hotspots: Vec<TableSeqScanInfo> = rows
         .iter()
-        .filter(|row| {
-            row.live_tuples >= TABLE_BLOAT_MIN_ROWS
-                && row.table_size_bytes >= TABLE_MIN_SIZE_BYTES
-                && row.seq_scan * SEQ_SCAN_MULTIPLIER > row.idx_scan.max(1)
-        })
+        .filter(|row| is_seq_scan_hotspot(row, row.seq_scan, row.idx_scan))
         .map(|row| TableSeqScanInfo {
             schema: row.schema.clone(),
             table_name: row.table_name.clone(),
             seq_scan: row.seq_scan,
             idx_scan: row.idx_scan,
             live_tuples: row.live_tuples,
-            table_size_bytes: row.table_size_bytes,
+            table_size_bytes: ByteSize(row.table_size_bytes),
             table_size_pretty: row.table_size_pretty.clone(),
+            fingerprint: String::new(),
         })
         .collect();
 
-    hotspots.sort_by(|a, b| b.seq_scan.cmp(&a.seq_scan));
-    hotspots.truncate(MAX_SEQ_SCAN_RESULTS);
+    hotspots.sort_by_key(|a| std::cmp::Reverse(a.seq_scan));
     hotspots
 }
 
@@ -184,7 +382,7 @@ fn add_bloat_suggestions(tables: &[TableBloatInfo], results: &mut AnalysisResult
     for table in tables {
         let stale_autovacuum = table
             .seconds_since_last_autovacuum
-            .map(|secs| secs > AUTOVAC_STALE_SECONDS)
+            .map(|secs| secs.0 > AUTOVAC_STALE_SECONDS)
             .unwrap_or(true);
         let level = if table.dead_tup_ratio >= TABLE_DEAD_RATIO_CRITICAL && stale_autovacuum {
             SuggestionLevel::Critical
@@ -193,23 +391,14 @@ fn add_bloat_suggestions(tables: &[TableBloatInfo], results: &mut AnalysisResult
         } else {
             SuggestionLevel::Recommended
         };
-        let rationale = if stale_autovacuum {
-            format!(
-                "{} has {:.1}% dead tuples but its last autovacuum ran {}. This indicates autovacuum tuning is not keeping up; increase per-table autovacuum aggressiveness (lower scale factor/threshold) or schedule a manual VACUUM to prune bloat.",
-                format_table_name(table),
-                table.dead_tup_ratio * 100.0,
-                table
-                    .last_autovacuum
-                    .as_deref()
-                    .unwrap_or("no recorded autovacuum")
-            )
-        } else {
-            format!(
-                "{} shows {:.1}% dead tuples even after a recent autovacuum. High-churn workloads may need more aggressive autovacuum settings or targeted VACUUM (FULL) during low-traffic windows.",
-                format_table_name(table),
-                table.dead_tup_ratio * 100.0
-            )
-        };
+        let rationale = match (stale_autovacuum, table.last_autovacuum.as_deref()) {
+            (true, Some(last_autovacuum)) => Message::new("table_index.bloat.stale_autovacuum")
+                .arg("last_autovacuum", last_autovacuum),
+            (true, None) => Message::new("table_index.bloat.never_autovacuumed"),
+            (false, _) => Message::new("table_index.bloat.recent_autovacuum"),
+        }
+        .arg("table", format_table_name(table))
+        .arg("dead_pct", format!("{:.1}", table.dead_tup_ratio * 100.0));
 
         push_table_index_suggestion(
             results,
@@ -217,7 +406,7 @@ fn add_bloat_suggestions(tables: &[TableBloatInfo], results: &mut AnalysisResult
             &format!("{:.1}% dead tuples", table.dead_tup_ratio * 100.0),
             "Reduce dead tuples with VACUUM or tighter autovacuum thresholds",
             level,
-            &rationale,
+            rationale,
         );
     }
 }
@@ -225,14 +414,12 @@ fn add_bloat_suggestions(tables: &[TableBloatInfo], results: &mut AnalysisResult
 fn add_seq_scan_suggestions(hotspots: &[TableSeqScanInfo], results: &mut AnalysisResults) {
     for table in hotspots {
         let full_table_name = format!("{}.{}", table.schema, table.table_name);
-        let rationale = format!(
-            "{} has {} sequential scans vs {} index scans on ~{} rows ({}). This matches the guidance from docs/6: filter-heavy queries are falling back to seq scans on sizable tables. Investigate pg_stat_statements for the offending queries and create composite/partial indexes to cover their predicates.",
-            full_table_name,
-            table.seq_scan,
-            table.idx_scan,
-            table.live_tuples,
-            table.table_size_pretty
-        );
+        let rationale = Message::new("table_index.seq_scan_hotspot")
+            .arg("table", &full_table_name)
+            .arg("seq_scans", table.seq_scan)
+            .arg("idx_scans", table.idx_scan)
+            .arg("rows", table.live_tuples)
+            .arg("size", &table.table_size_pretty);
 
         push_table_index_suggestion(
             results,
@@ -240,7 +427,7 @@ fn add_seq_scan_suggestions(hotspots: &[TableSeqScanInfo], results: &mut Analysi
             &format!("{} seq / {} idx scans", table.seq_scan, table.idx_scan),
             "Add or extend indexes to lower sequential scans",
             SuggestionLevel::Important,
-            &rationale,
+            rationale,
         );
     }
 }
@@ -260,6 +447,7 @@ mod tests {
             table_name: "orders".into(),
             live_tuples: 200_000,
             dead_tuples: 60_000,
+            dead_tuples_created: 400_000,
             seq_scan: 10,
             idx_scan: 500,
             table_size_bytes: 200 * 1024 * 1024,
@@ -275,6 +463,34 @@ mod tests {
         assert!(candidates[0].dead_tup_ratio > 0.2);
     }
 
+    #[test]
+    fn vacuum_activity_keeps_tables_with_updates_or_deletes_busiest_first() {
+        let row = |table_name: &str, dead_tuples_created: i64| TableStatRow {
+            schema: "public".into(),
+            table_name: table_name.into(),
+            live_tuples: 1_000,
+            dead_tuples: 10,
+            dead_tuples_created,
+            seq_scan: 0,
+            idx_scan: 0,
+            table_size_bytes: 8192,
+            table_size_pretty: "8192 bytes".into(),
+            last_autovacuum: None,
+            last_autoanalyze: None,
+            seconds_since_last_autovacuum: None,
+            seconds_since_last_autoanalyze: None,
+        };
+
+        let activity = vacuum_activity(&[row("audit", 0), row("carts", 50), row("orders", 900)]);
+        let tables: Vec<_> = activity
+            .tables
+            .iter()
+            .map(|table| table.table_name.as_str())
+            .collect();
+        assert_eq!(tables, ["orders", "carts"]);
+        assert!(activity.captured_at.0 > 0.0);
+    }
+
     #[test]
     fn detects_seq_scan_hotspots_only_when_seq_dominates() {
         let rows = vec![TableStatRow {
@@ -282,6 +498,7 @@ mod tests {
             table_name: "events".into(),
             live_tuples: 150_000,
             dead_tuples: 1_000,
+            dead_tuples_created: 0,
             seq_scan: 1000,
             idx_scan: 5,
             table_size_bytes: 100 * 1024 * 1024,
@@ -295,4 +512,87 @@ mod tests {
         let hotspots = identify_seq_scan_hotspots(&rows);
         assert_eq!(hotspots.len(), 1);
     }
+
+    #[test]
+    fn sampled_window_drops_historic_seq_scan_hotspots() {
+        let mut rows = vec![TableStatRow {
+            schema: "public".into(),
+            table_name: "events".into(),
+            live_tuples: 150_000,
+            dead_tuples: 1_000,
+            dead_tuples_created: 0,
+            seq_scan: 1000,
+            idx_scan: 5_000,
+            table_size_bytes: 100 * 1024 * 1024,
+            table_size_pretty: "100 MB".into(),
+            last_autovacuum: None,
+            last_autoanalyze: None,
+            seconds_since_last_autovacuum: None,
+            seconds_since_last_autoanalyze: None,
+        }];
+        let mut baseline = StatsBaseline::default();
+        baseline
+            .table_scans
+            .insert(("public".into(), "events".into()), (1000, 4_000));
+
+        assert_eq!(identify_seq_scan_hotspots(&rows).len(), 1);
+        apply_baseline(&mut rows, &baseline);
+        assert_eq!((rows[0].seq_scan, rows[0].idx_scan), (0, 1_000));
+        assert!(identify_seq_scan_hotspots(&rows).is_empty());
+    }
+
+    #[test]
+    fn streamed_selection_keeps_the_top_tables_of_each_kind_in_order() {
+        let row = |table_name: &str, live_tuples: i64, dead_tuples_created: i64| TableStatRow {
+            schema: "public".into(),
+            table_name: table_name.into(),
+            live_tuples,
+            dead_tuples: 0,
+            dead_tuples_created,
+            seq_scan: 0,
+            idx_scan: 0,
+            table_size_bytes: 64 * 1024 * 1024,
+            table_size_pretty: "64 MB".into(),
+            last_autovacuum: None,
+            last_autoanalyze: None,
+            seconds_since_last_autovacuum: None,
+            seconds_since_last_autoanalyze: None,
+        };
+
+        let mut selection = TableSelection::new(None, Some(2), Some(1));
+        selection.push(row("idle", 10, 0));
+        selection.push(row("orders", 200_000, 0));
+        for index in 0..MAX_VACUUM_ACTIVITY_TABLES + 5 {
+            selection.push(row(&format!("tenant_{index}"), 10, index as i64 + 1));
+        }
+        for (index, dead_tuples) in [30_000, 90_000, 60_000].into_iter().enumerate() {
+            selection.push(TableStatRow {
+                dead_tuples,
+                ..row(&format!("bloated_{index}"), 100_000, 0)
+            });
+        }
+        for (index, seq_scan) in [10, 500].into_iter().enumerate() {
+            selection.push(TableStatRow {
+                seq_scan,
+                ..row(&format!("scanned_{index}"), 100_000, 0)
+            });
+        }
+        let (rows, matches) = selection.into_rows();
+        let names: Vec<_> = rows.iter().map(|row| row.table_name.as_str()).collect();
+
+        assert_eq!(
+            matches,
+            TableMatches {
+                bloat: 3,
+                seq_scan: 2
+            }
+        );
+        assert_eq!(names.len(), MAX_VACUUM_ACTIVITY_TABLES + 3);
+        assert_eq!(names[0], "tenant_5");
+        assert_eq!(
+            names[MAX_VACUUM_ACTIVITY_TABLES..],
+            ["bloated_1", "bloated_2", "scanned_1"]
+        );
+        assert!(!names.contains(&"idle") && !names.contains(&"orders"));
+    }
 }
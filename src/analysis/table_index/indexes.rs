@@ -1,16 +1,23 @@
-use super::push_table_index_suggestion;
+use super::{cap_findings, push_table_index_suggestion};
+use crate::analysis::catalog_cache::{CachedIndex, CatalogCache};
+use crate::analysis::replicas::ReplicaIndexScans;
 use crate::checker::CheckerError;
-use crate::models::{AnalysisResults, IndexIssueKind, IndexUsageInfo, SuggestionLevel};
+use crate::fixture::Fixture;
+use crate::i18n::Message;
+use crate::models::{AnalysisResults, ByteSize, IndexIssueKind, IndexUsageInfo, SuggestionLevel};
+use serde::{Deserialize, Serialize};
 use sqlx::{Pool, Postgres, Row};
 use std::cmp::Ordering;
 
+/// Built-in cap, replaced by `--max-findings-per-kind`
 const MAX_INDEX_RESULTS_PER_KIND: usize = 8;
+const MAX_BRIN_CANDIDATES: usize = 5;
 const MIN_INDEX_SIZE_BYTES: i64 = 5 * 1024 * 1024;
 const LOW_SELECTIVITY_SCAN_THRESHOLD: i64 = 50;
 const FAILED_INDEX_ONLY_MIN_TUP_READ: i64 = 10_000;
 const LARGE_TABLE_MIN_ROWS: i64 = 10_000;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct IndexStatRow {
     schema: String,
     table_name: String,
@@ -22,6 +29,9 @@ struct IndexStatRow {
     idx_tup_read: i64,
     idx_tup_fetch: i64,
     table_live_tup: Option<i64>,
+    table_index_writes: Option<i64>,
+    table_scans: Option<i64>,
+    index_definition: Option<String>,
     is_unique: bool,
     enforces_constraint: bool,
     is_expression: bool,
@@ -29,6 +39,30 @@ struct IndexStatRow {
 }
 
 impl IndexStatRow {
+    /// The row of an index with user statistics
+    fn from_cached(index: &CachedIndex) -> Option<Self> {
+        let usage = index.usage.as_ref()?;
+        Some(Self {
+            schema: index.schema.clone(),
+            table_name: index.table.clone(),
+            index_name: index.index_name.clone(),
+            key_columns: index.key_columns.clone(),
+            index_size_bytes: index.size_bytes,
+            index_size_pretty: index.size_pretty.clone(),
+            idx_scan: usage.idx_scan,
+            idx_tup_read: usage.idx_tup_read,
+            idx_tup_fetch: usage.idx_tup_fetch,
+            table_live_tup: usage.table_live_tup,
+            table_index_writes: usage.table_index_writes,
+            table_scans: usage.table_scans,
+            index_definition: index.definition.clone(),
+            is_unique: index.is_unique,
+            enforces_constraint: index.enforces_constraint,
+            is_expression: index.is_expression,
+            is_partial: index.is_partial,
+        })
+    }
+
     fn avg_tuples_per_scan(&self) -> f64 {
         if self.idx_scan <= 0 {
             0.0
@@ -48,81 +82,166 @@ impl IndexStatRow {
 
 pub(super) async fn analyze(
     pool: &Pool<Postgres>,
+    fixture: &Fixture,
     results: &mut AnalysisResults,
+    report_unused: bool,
+    replica_scans: Option<&ReplicaIndexScans>,
+    catalog: &CatalogCache,
+    max_per_kind: Option<usize>,
 ) -> Result<(), CheckerError> {
-    let index_rows = fetch_index_stats(pool).await?;
+    // Only --gentle leaves the catalog unread, as the LATERAL unnest over every
+    // index's columns is the costliest read on databases with many indexes
+    let index_rows: Vec<_> = if catalog.is_read() {
+        catalog
+            .indexes()?
+            .iter()
+            .filter_map(IndexStatRow::from_cached)
+            .collect()
+    } else {
+        results.warnings.push(
+            "Unused, low-selectivity and index-only findings skipped: --gentle does not read index statistics."
+                .to_string(),
+        );
+        Vec::new()
+    };
 
-    let unused_indexes = identify_unused_indexes(&index_rows);
-    let low_selectivity_indexes = identify_low_selectivity_indexes(&index_rows);
-    let failed_index_only_indexes = identify_failed_index_only_indexes(&index_rows);
+    let mut unused_indexes = if report_unused {
+        identify_unused_indexes(&index_rows, replica_scans)
+    } else {
+        Vec::new()
+    };
+    let mut low_selectivity_indexes = identify_low_selectivity_indexes(&index_rows);
+    let mut failed_index_only_indexes = identify_failed_index_only_indexes(&index_rows);
+    for findings in [
+        &mut unused_indexes,
+        &mut low_selectivity_indexes,
+        &mut failed_index_only_indexes,
+    ] {
+        cap_index_findings(
+            findings,
+            max_per_kind,
+            Some(MAX_INDEX_RESULTS_PER_KIND),
+            results,
+        );
+    }
 
     let mut index_findings = Vec::new();
     index_findings.extend(unused_indexes.clone());
     index_findings.extend(low_selectivity_indexes.clone());
     index_findings.extend(failed_index_only_indexes.clone());
-    results.index_usage_info = index_findings;
+    results.table_health.index_usage_info = index_findings;
 
     add_index_suggestions(&unused_indexes, results);
     add_index_suggestions(&low_selectivity_indexes, results);
     add_index_suggestions(&failed_index_only_indexes, results);
 
     // New checks from docs/2
-    let soft_delete_candidates = fetch_soft_delete_candidates(pool).await?;
-    let missing_partial_indexes = identify_missing_partial_indexes(&soft_delete_candidates);
+    let soft_delete_candidates = fixture
+        .try_serve("soft_delete_candidates", fetch_soft_delete_candidates(pool))
+        .await?;
+    let mut missing_partial_indexes = identify_missing_partial_indexes(&soft_delete_candidates);
+    cap_index_findings(&mut missing_partial_indexes, max_per_kind, None, results);
     add_index_suggestions(&missing_partial_indexes, results);
-    results.index_usage_info.extend(missing_partial_indexes);
-
-    let brin_candidates = fetch_brin_candidates(pool).await?;
-    let brin_findings = identify_brin_candidates(&brin_candidates);
+    results
+        .table_health
+        .index_usage_info
+        .extend(missing_partial_indexes);
+
+    let brin_candidates = fixture
+        .try_serve("brin_candidates", fetch_brin_candidates(pool))
+        .await?;
+    let mut brin_findings = identify_brin_candidates(&brin_candidates);
+    cap_index_findings(
+        &mut brin_findings,
+        max_per_kind,
+        Some(MAX_BRIN_CANDIDATES),
+        results,
+    );
     add_index_suggestions(&brin_findings, results);
-    results.index_usage_info.extend(brin_findings);
+    results.table_health.index_usage_info.extend(brin_findings);
+
+    let unindexed_geometries = fixture
+        .try_serve(
+            "unindexed_geometry_columns",
+            fetch_unindexed_geometry_columns(pool),
+        )
+        .await?;
+    let mut spatial_findings = identify_missing_spatial_indexes(&unindexed_geometries);
+    cap_index_findings(
+        &mut spatial_findings,
+        max_per_kind,
+        Some(MAX_INDEX_RESULTS_PER_KIND),
+        results,
+    );
+    add_index_suggestions(&spatial_findings, results);
+    results
+        .table_health
+        .index_usage_info
+        .extend(spatial_findings);
 
     Ok(())
 }
 
-#[derive(Debug)]
+/// Caps findings that all share one issue kind
+fn cap_index_findings(
+    findings: &mut Vec<IndexUsageInfo>,
+    max_per_kind: Option<usize>,
+    default: Option<usize>,
+    results: &mut AnalysisResults,
+) {
+    if let Some(kind) = findings.first().map(|finding| finding.issue.as_str()) {
+        cap_findings(
+            findings,
+            max_per_kind,
+            default,
+            kind,
+            &mut results.table_health,
+        );
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 struct SoftDeleteCandidate {
     schema: String,
     table_name: String,
     column_name: String,
 }
 
+pub(crate) const SOFT_DELETE_CANDIDATES_QUERY: &str = r#"
+    WITH soft_delete_cols AS (
+        SELECT n.nspname, c.relname, a.attname, c.oid AS relid
+        FROM pg_attribute a
+        JOIN pg_class c ON a.attrelid = c.oid
+        JOIN pg_namespace n ON c.relnamespace = n.oid
+        WHERE a.attname IN ('is_deleted', 'deleted_at', 'archived', 'is_archived')
+          AND c.relkind = 'r'
+          AND n.nspname NOT IN ('pg_catalog', 'information_schema')
+    ),
+    tables_with_partial_idx AS (
+        SELECT DISTINCT indrelid
+        FROM pg_index
+        WHERE indpred IS NOT NULL
+    )
+    SELECT
+        s.nspname,
+        s.relname,
+        s.attname
+    FROM soft_delete_cols s
+    LEFT JOIN tables_with_partial_idx p ON s.relid = p.indrelid
+    WHERE p.indrelid IS NULL -- Table has no partial indexes at all (simplification, but effective)
+"#;
+
 async fn fetch_soft_delete_candidates(
     pool: &Pool<Postgres>,
 ) -> Result<Vec<SoftDeleteCandidate>, CheckerError> {
     // Find tables with soft-delete columns that DO NOT have a partial index filtering on them
-    const QUERY: &str = r#"
-        WITH soft_delete_cols AS (
-            SELECT n.nspname, c.relname, a.attname, c.oid AS relid
-            FROM pg_attribute a
-            JOIN pg_class c ON a.attrelid = c.oid
-            JOIN pg_namespace n ON c.relnamespace = n.oid
-            WHERE a.attname IN ('is_deleted', 'deleted_at', 'archived', 'is_archived')
-              AND c.relkind = 'r'
-              AND n.nspname NOT IN ('pg_catalog', 'information_schema')
-        ),
-        tables_with_partial_idx AS (
-            SELECT DISTINCT indrelid
-            FROM pg_index
-            WHERE indpred IS NOT NULL
-        )
-        SELECT
-            s.nspname,
-            s.relname,
-            s.attname
-        FROM soft_delete_cols s
-        LEFT JOIN tables_with_partial_idx p ON s.relid = p.indrelid
-        WHERE p.indrelid IS NULL -- Table has no partial indexes at all (simplification, but effective)
-    "#;
-
-    let rows =
-        sqlx::query(QUERY)
-            .fetch_all(pool)
-            .await
-            .map_err(|source| CheckerError::QueryError {
-                query: QUERY.into(),
-                source,
-            })?;
+    let rows = sqlx::query(SOFT_DELETE_CANDIDATES_QUERY)
+        .fetch_all(pool)
+        .await
+        .map_err(|source| CheckerError::QueryError {
+            query: SOFT_DELETE_CANDIDATES_QUERY.into(),
+            source,
+        })?;
 
     let mut candidates = Vec::new();
     for row in rows {
@@ -144,7 +263,7 @@ fn identify_missing_partial_indexes(candidates: &[SoftDeleteCandidate]) -> Vec<I
             table_name: c.table_name.clone(),
             index_name: format!("(missing on {})", c.column_name),
             key_columns: vec![c.column_name.clone()],
-            index_size_bytes: 0,
+            index_size_bytes: ByteSize(0),
             index_size_pretty: "0 B".to_string(),
             scans: 0,
             tuples_read: 0,
@@ -152,46 +271,51 @@ fn identify_missing_partial_indexes(candidates: &[SoftDeleteCandidate]) -> Vec<I
             avg_tuples_per_scan: 0.0,
             heap_fetch_ratio: 0.0,
             table_live_tup: None,
+            table_index_writes: None,
+            table_scans: None,
+            index_definition: None,
+            partial_predicate: None,
+            estimated_size_bytes: None,
             is_unique: false,
             enforces_constraint: false,
             is_expression: false,
             is_partial: false,
+            fingerprint: String::new(),
         })
         .collect()
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 struct BrinCandidate {
     schema: String,
     table_name: String,
     column_name: String,
 }
 
+pub(crate) const BRIN_CANDIDATES_QUERY: &str = r#"
+    SELECT
+        s.schemaname,
+        s.tablename,
+        s.attname
+    FROM pg_stats s
+    JOIN pg_class c ON c.relname = s.tablename
+    JOIN pg_namespace n ON c.relnamespace = n.oid AND n.nspname = s.schemaname
+    LEFT JOIN pg_index i ON i.indrelid = c.oid AND i.indnatts = 1 -- Check if single col index exists
+    WHERE s.schemaname NOT IN ('pg_catalog', 'information_schema')
+      AND abs(s.correlation) > 0.95
+      AND pg_relation_size(c.oid) > 10000000 -- > 10MB
+      AND c.relkind = 'r'
+"#;
+
 async fn fetch_brin_candidates(pool: &Pool<Postgres>) -> Result<Vec<BrinCandidate>, CheckerError> {
     // Find large tables with highly correlated columns (good for BRIN) that are NOT the PK (usually)
-    const QUERY: &str = r#"
-        SELECT
-            s.schemaname,
-            s.tablename,
-            s.attname
-        FROM pg_stats s
-        JOIN pg_class c ON c.relname = s.tablename
-        JOIN pg_namespace n ON c.relnamespace = n.oid AND n.nspname = s.schemaname
-        LEFT JOIN pg_index i ON i.indrelid = c.oid AND i.indnatts = 1 -- Check if single col index exists
-        WHERE s.schemaname NOT IN ('pg_catalog', 'information_schema')
-          AND abs(s.correlation) > 0.95
-          AND pg_relation_size(c.oid) > 10000000 -- > 10MB
-          AND c.relkind = 'r'
-    "#;
-
-    let rows =
-        sqlx::query(QUERY)
-            .fetch_all(pool)
-            .await
-            .map_err(|source| CheckerError::QueryError {
-                query: QUERY.into(),
-                source,
-            })?;
+    let rows = sqlx::query(BRIN_CANDIDATES_QUERY)
+        .fetch_all(pool)
+        .await
+        .map_err(|source| CheckerError::QueryError {
+            query: BRIN_CANDIDATES_QUERY.into(),
+            source,
+        })?;
 
     let mut candidates = Vec::new();
     for row in rows {
@@ -205,17 +329,15 @@ async fn fetch_brin_candidates(pool: &Pool<Postgres>) -> Result<Vec<BrinCandidat
 }
 
 fn identify_brin_candidates(candidates: &[BrinCandidate]) -> Vec<IndexUsageInfo> {
-    // Filter to top candidates
     candidates
         .iter()
-        .take(5)
         .map(|c| IndexUsageInfo {
             issue: IndexIssueKind::BrinCandidate,
             schema: c.schema.clone(),
             table_name: c.table_name.clone(),
             index_name: c.column_name.clone(), // Use column name as proxy
             key_columns: vec![c.column_name.clone()],
-            index_size_bytes: 0,
+            index_size_bytes: ByteSize(0),
             index_size_pretty: "0 B".to_string(),
             scans: 0,
             tuples_read: 0,
@@ -223,96 +345,124 @@ fn identify_brin_candidates(candidates: &[BrinCandidate]) -> Vec<IndexUsageInfo>
             avg_tuples_per_scan: 0.0,
             heap_fetch_ratio: 0.0,
             table_live_tup: None,
+            table_index_writes: None,
+            table_scans: None,
+            index_definition: None,
+            partial_predicate: None,
+            estimated_size_bytes: None,
             is_unique: false,
             enforces_constraint: false,
             is_expression: false,
             is_partial: false,
+            fingerprint: String::new(),
         })
         .collect()
 }
 
-async fn fetch_index_stats(pool: &Pool<Postgres>) -> Result<Vec<IndexStatRow>, CheckerError> {
-    const QUERY: &str = r#"
-        SELECT
-            s.schemaname,
-            s.relname,
-            s.indexrelname,
-            COALESCE(
-                array_agg(a.attname ORDER BY arr.ord) FILTER (WHERE a.attname IS NOT NULL),
-                ARRAY[]::text[]
-            ) AS key_columns,
-            s.idx_scan,
-            s.idx_tup_read,
-            s.idx_tup_fetch,
-            pg_relation_size(s.indexrelid) AS index_size_bytes,
-            pg_size_pretty(pg_relation_size(s.indexrelid)) AS index_size_pretty,
-            t.n_live_tup,
-            i.indisunique,
-            (i.indpred IS NOT NULL) AS is_partial,
-            (i.indexprs IS NOT NULL) AS is_expression,
-            EXISTS (
-                SELECT 1 FROM pg_constraint c WHERE c.conindid = s.indexrelid
-            ) AS enforces_constraint
-        FROM pg_stat_user_indexes s
-        JOIN pg_index i ON s.indexrelid = i.indexrelid
-        LEFT JOIN LATERAL unnest(i.indkey) WITH ORDINALITY AS arr(attnum, ord)
-            ON arr.ord <= i.indnkeyatts
-        LEFT JOIN pg_attribute a
-            ON a.attrelid = s.relid
-           AND a.attnum = arr.attnum
-           AND arr.attnum > 0
-        LEFT JOIN pg_stat_user_tables t ON t.relid = s.relid
-        GROUP BY
-            s.schemaname,
-            s.relname,
-            s.indexrelname,
-            s.idx_scan,
-            s.idx_tup_read,
-            s.idx_tup_fetch,
-            s.indexrelid,
-            t.n_live_tup,
-            i.indisunique,
-            i.indpred,
-            i.indexprs
-    "#;
-
-    let rows =
-        sqlx::query(QUERY)
-            .fetch_all(pool)
-            .await
-            .map_err(|source| CheckerError::QueryError {
-                query: QUERY.into(),
-                source,
-            })?;
-
-    let mut stats = Vec::with_capacity(rows.len());
-    for row in rows {
-        stats.push(IndexStatRow {
-            schema: row.get("schemaname"),
+#[derive(Debug, Serialize, Deserialize)]
+struct GeometryColumn {
+    schema: String,
+    table_name: String,
+    column_name: String,
+    /// `reltuples` as of the last ANALYZE
+    estimated_rows: i64,
+}
+
+pub(crate) const UNINDEXED_GEOMETRY_QUERY: &str = r#"
+    SELECT
+        n.nspname,
+        c.relname,
+        a.attname,
+        c.reltuples::bigint AS estimated_rows
+    FROM pg_attribute a
+    JOIN pg_class c ON a.attrelid = c.oid
+    JOIN pg_namespace n ON c.relnamespace = n.oid
+    JOIN pg_type t ON a.atttypid = t.oid
+    WHERE t.typname IN ('geometry', 'geography')
+      AND a.attnum > 0
+      AND NOT a.attisdropped
+      AND c.relkind IN ('r', 'm')
+      AND n.nspname NOT IN ('pg_catalog', 'information_schema')
+      AND c.reltuples >= $1
+      AND NOT EXISTS (
+          SELECT 1
+          FROM pg_index i
+          JOIN pg_class ic ON ic.oid = i.indexrelid
+          JOIN pg_am am ON am.oid = ic.relam
+          WHERE i.indrelid = c.oid
+            AND i.indkey[0] = a.attnum
+            AND am.amname IN ('gist', 'spgist', 'brin')
+      )
+    ORDER BY c.reltuples DESC
+"#;
+
+/// PostGIS `geometry` and `geography` columns on large tables that no GiST,
+/// SP-GiST or BRIN index leads with. Without PostGIS the types do not exist
+/// and nothing is returned.
+async fn fetch_unindexed_geometry_columns(
+    pool: &Pool<Postgres>,
+) -> Result<Vec<GeometryColumn>, CheckerError> {
+    let rows = sqlx::query(UNINDEXED_GEOMETRY_QUERY)
+        .bind(LARGE_TABLE_MIN_ROWS as f32)
+        .fetch_all(pool)
+        .await
+        .map_err(|source| CheckerError::QueryError {
+            query: UNINDEXED_GEOMETRY_QUERY.into(),
+            source,
+        })?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| GeometryColumn {
+            schema: row.get("nspname"),
             table_name: row.get("relname"),
-            index_name: row.get("indexrelname"),
-            key_columns: row.get("key_columns"),
-            index_size_bytes: row.get("index_size_bytes"),
-            index_size_pretty: row.get("index_size_pretty"),
-            idx_scan: row.get("idx_scan"),
-            idx_tup_read: row.get("idx_tup_read"),
-            idx_tup_fetch: row.get("idx_tup_fetch"),
-            table_live_tup: row.get("n_live_tup"),
-            is_unique: row.get("indisunique"),
-            enforces_constraint: row.get("enforces_constraint"),
-            is_expression: row.get("is_expression"),
-            is_partial: row.get("is_partial"),
-        });
-    }
+            column_name: row.get("attname"),
+            estimated_rows: row.get("estimated_rows"),
+        })
+        .collect())
+}
 
-    Ok(stats)
+fn identify_missing_spatial_indexes(columns: &[GeometryColumn]) -> Vec<IndexUsageInfo> {
+    columns
+        .iter()
+        .map(|c| IndexUsageInfo {
+            issue: IndexIssueKind::MissingSpatialIndex,
+            schema: c.schema.clone(),
+            table_name: c.table_name.clone(),
+            index_name: format!("(missing on {})", c.column_name),
+            key_columns: vec![c.column_name.clone()],
+            index_size_bytes: ByteSize(0),
+            index_size_pretty: "0 B".to_string(),
+            scans: 0,
+            tuples_read: 0,
+            tuples_fetched: 0,
+            avg_tuples_per_scan: 0.0,
+            heap_fetch_ratio: 0.0,
+            table_live_tup: Some(c.estimated_rows),
+            table_index_writes: None,
+            table_scans: None,
+            index_definition: None,
+            partial_predicate: None,
+            estimated_size_bytes: None,
+            is_unique: false,
+            enforces_constraint: false,
+            is_expression: false,
+            is_partial: false,
+            fingerprint: String::new(),
+        })
+        .collect()
 }
 
-fn identify_unused_indexes(rows: &[IndexStatRow]) -> Vec<IndexUsageInfo> {
+fn identify_unused_indexes(
+    rows: &[IndexStatRow],
+    replica_scans: Option<&ReplicaIndexScans>,
+) -> Vec<IndexUsageInfo> {
     let mut unused: Vec<IndexUsageInfo> = rows
         .iter()
         .filter(|row| {
             row.idx_scan == 0
+                && replica_scans
+                    .is_none_or(|replicas| replicas.scans_for(&row.schema, &row.index_name) == 0)
                 && row.index_size_bytes >= MIN_INDEX_SIZE_BYTES
                 && !row.is_unique
                 && !row.enforces_constraint
@@ -325,7 +475,7 @@ fn identify_unused_indexes(rows: &[IndexStatRow]) -> Vec<IndexUsageInfo> {
             table_name: row.table_name.clone(),
             index_name: row.index_name.clone(),
             key_columns: row.key_columns.clone(),
-            index_size_bytes: row.index_size_bytes,
+            index_size_bytes: ByteSize(row.index_size_bytes),
             index_size_pretty: row.index_size_pretty.clone(),
             scans: row.idx_scan,
             tuples_read: row.idx_tup_read,
@@ -333,15 +483,20 @@ fn identify_unused_indexes(rows: &[IndexStatRow]) -> Vec<IndexUsageInfo> {
             avg_tuples_per_scan: 0.0,
             heap_fetch_ratio: 0.0,
             table_live_tup: row.table_live_tup,
+            table_index_writes: row.table_index_writes,
+            table_scans: row.table_scans,
+            index_definition: row.index_definition.clone(),
+            partial_predicate: None,
+            estimated_size_bytes: None,
             is_unique: row.is_unique,
             enforces_constraint: row.enforces_constraint,
             is_expression: row.is_expression,
             is_partial: row.is_partial,
+            fingerprint: String::new(),
         })
         .collect();
 
-    unused.sort_by(|a, b| b.index_size_bytes.cmp(&a.index_size_bytes));
-    unused.truncate(MAX_INDEX_RESULTS_PER_KIND);
+    unused.sort_by_key(|a| std::cmp::Reverse(a.index_size_bytes));
     unused
 }
 
@@ -362,7 +517,7 @@ fn identify_low_selectivity_indexes(rows: &[IndexStatRow]) -> Vec<IndexUsageInfo
             table_name: row.table_name.clone(),
             index_name: row.index_name.clone(),
             key_columns: row.key_columns.clone(),
-            index_size_bytes: row.index_size_bytes,
+            index_size_bytes: ByteSize(row.index_size_bytes),
             index_size_pretty: row.index_size_pretty.clone(),
             scans: row.idx_scan,
             tuples_read: row.idx_tup_read,
@@ -370,10 +525,16 @@ fn identify_low_selectivity_indexes(rows: &[IndexStatRow]) -> Vec<IndexUsageInfo
             avg_tuples_per_scan: row.avg_tuples_per_scan(),
             heap_fetch_ratio: row.heap_fetch_ratio(),
             table_live_tup: row.table_live_tup,
+            table_index_writes: row.table_index_writes,
+            table_scans: row.table_scans,
+            index_definition: row.index_definition.clone(),
+            partial_predicate: None,
+            estimated_size_bytes: None,
             is_unique: row.is_unique,
             enforces_constraint: row.enforces_constraint,
             is_expression: row.is_expression,
             is_partial: row.is_partial,
+            fingerprint: String::new(),
         })
         .collect();
 
@@ -382,7 +543,6 @@ fn identify_low_selectivity_indexes(rows: &[IndexStatRow]) -> Vec<IndexUsageInfo
         let b_ratio = selectivity_ratio(b);
         b_ratio.partial_cmp(&a_ratio).unwrap_or(Ordering::Equal)
     });
-    findings.truncate(MAX_INDEX_RESULTS_PER_KIND);
     findings
 }
 
@@ -400,7 +560,7 @@ fn identify_failed_index_only_indexes(rows: &[IndexStatRow]) -> Vec<IndexUsageIn
             table_name: row.table_name.clone(),
             index_name: row.index_name.clone(),
             key_columns: row.key_columns.clone(),
-            index_size_bytes: row.index_size_bytes,
+            index_size_bytes: ByteSize(row.index_size_bytes),
             index_size_pretty: row.index_size_pretty.clone(),
             scans: row.idx_scan,
             tuples_read: row.idx_tup_read,
@@ -408,10 +568,16 @@ fn identify_failed_index_only_indexes(rows: &[IndexStatRow]) -> Vec<IndexUsageIn
             avg_tuples_per_scan: row.avg_tuples_per_scan(),
             heap_fetch_ratio: row.heap_fetch_ratio(),
             table_live_tup: row.table_live_tup,
+            table_index_writes: row.table_index_writes,
+            table_scans: row.table_scans,
+            index_definition: row.index_definition.clone(),
+            partial_predicate: None,
+            estimated_size_bytes: None,
             is_unique: row.is_unique,
             enforces_constraint: row.enforces_constraint,
             is_expression: row.is_expression,
             is_partial: row.is_partial,
+            fingerprint: String::new(),
         })
         .collect();
 
@@ -420,22 +586,20 @@ fn identify_failed_index_only_indexes(rows: &[IndexStatRow]) -> Vec<IndexUsageIn
             .partial_cmp(&a.heap_fetch_ratio)
             .unwrap_or(Ordering::Equal)
     });
-    findings.truncate(MAX_INDEX_RESULTS_PER_KIND);
     findings
 }
 
 fn add_index_suggestions(indexes: &[IndexUsageInfo], results: &mut AnalysisResults) {
+    let stats_age_secs = results.system_stats.stats_age_secs;
     for index in indexes {
         let parameter = format!("index {}.{}", index.schema, index.index_name);
         let (suggested_value, level, rationale) = match index.issue {
             IndexIssueKind::Unused => (
                 "Drop unused index",
                 SuggestionLevel::Important,
-                format!(
-                    "{} has never been scanned and is not enforcing a constraint. Dropping it reclaims {} and removes write overhead, per docs/6 guidance.",
-                    parameter,
-                    index.index_size_pretty
-                ),
+                Message::new("table_index.unused_index")
+                    .arg("index", &parameter)
+                    .arg("size", &index.index_size_pretty),
             ),
             IndexIssueKind::LowSelectivity => {
                 let table_name = format!("{}.{}", index.schema, index.table_name);
@@ -448,50 +612,56 @@ fn add_index_suggestions(indexes: &[IndexUsageInfo], results: &mut AnalysisResul
                 (
                     "Replace with more selective (composite/partial) index",
                     SuggestionLevel::Recommended,
-                    format!(
-                        "{} returns ~{:.1}% of {} on each scan ({} tuples/read). This low selectivity means the planner touches a large fraction of the table; redesign the index per docs/6 section C.2.",
-                        parameter,
-                        selectivity,
-                        table_name,
-                        index.avg_tuples_per_scan as i64
-                    ),
+                    Message::new("table_index.low_selectivity")
+                        .arg("index", &parameter)
+                        .arg("pct", format!("{selectivity:.1}"))
+                        .arg("table", table_name)
+                        .arg("tuples", index.avg_tuples_per_scan as i64),
                 )
             }
             IndexIssueKind::FailedIndexOnly => (
                 "Add INCLUDE columns or VACUUM to refresh visibility",
                 SuggestionLevel::Recommended,
-                format!(
-                    "{} performs index scans but still fetches heap pages {:.0}% of the time. Either add the missing SELECT columns via INCLUDE or VACUUM to refresh the visibility map so index-only scans can succeed, per docs/6 section C.3.",
-                    parameter,
-                    index.heap_fetch_ratio * 100.0
-                ),
+                Message::new("table_index.failed_index_only")
+                    .arg("index", &parameter)
+                    .arg("pct", format!("{:.0}", index.heap_fetch_ratio * 100.0)),
             ),
             IndexIssueKind::MissingPartialIndex => (
                 "Create partial index on soft-delete column",
                 SuggestionLevel::Important,
-                format!(
-                    "Table {}.{} has a soft-delete column but lacks a partial index. Add 'WHERE is_deleted = false' (or deleted_at IS NULL) to excludes dead rows from the index, reducing size and maintenance overhead.",
-                    index.schema, index.table_name
-                ),
+                Message::new("table_index.missing_partial_index")
+                    .arg("table", format!("{}.{}", index.schema, index.table_name)),
             ),
             IndexIssueKind::BrinCandidate => (
                 "Replace B-Tree with BRIN index",
                 SuggestionLevel::Recommended,
-                format!(
-                    "Table {}.{} is large and physically ordered by {}. A BRIN index would be 100x smaller than a B-Tree while maintaining scan performance for range queries.",
-                    index.schema, index.table_name, index.index_name
-                ),
+                Message::new("table_index.brin_candidate")
+                    .arg("table", format!("{}.{}", index.schema, index.table_name))
+                    .arg("column", &index.index_name),
+            ),
+            IndexIssueKind::MissingSpatialIndex => (
+                "Create spatial (GiST) index",
+                SuggestionLevel::Important,
+                Message::new("table_index.missing_spatial_index")
+                    .arg("table", format!("{}.{}", index.schema, index.table_name))
+                    .arg("column", index.key_columns.join(", "))
+                    .arg("rows", index.table_live_tup.unwrap_or(0)),
             ),
         };
 
-        push_table_index_suggestion(
+        let suggestion = push_table_index_suggestion(
             results,
             &parameter,
             &format!("{} scans", index.scans),
             suggested_value,
             level,
-            &rationale,
+            rationale,
         );
+        if matches!(index.issue, IndexIssueKind::Unused) {
+            if let Some(note) = write_overhead_note(index, stats_age_secs) {
+                suggestion.push_note(note);
+            }
+        }
     }
 }
 
@@ -504,9 +674,26 @@ fn selectivity_ratio(index: &IndexUsageInfo) -> f64 {
     }
 }
 
+/// Sentence estimating the index maintenance an unused index costs, normalized per
+/// day when the statistics window is known
+fn write_overhead_note(index: &IndexUsageInfo, stats_age_secs: Option<u64>) -> Option<Message> {
+    let writes = index.table_index_writes.filter(|writes| *writes > 0)?;
+    let table_name = format!("{}.{}", index.schema, index.table_name);
+
+    let note = match stats_age_secs {
+        Some(age) if age >= 86_400 => Message::new("table_index.unused_index.writes_per_day").arg(
+            "writes",
+            (writes as f64 * 86_400.0 / age as f64).round() as i64,
+        ),
+        _ => Message::new("table_index.unused_index.writes_since_reset").arg("writes", writes),
+    };
+    Some(note.arg("table", table_name))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::ConfigCategory;
 
     #[test]
     fn detects_low_selectivity_index_when_half_table_scanned() {
@@ -521,6 +708,9 @@ mod tests {
             idx_tup_read: 45_000_000,
             idx_tup_fetch: 44_000_000,
             table_live_tup: Some(900_000),
+            table_index_writes: None,
+            table_scans: None,
+            index_definition: None,
             is_unique: false,
             enforces_constraint: false,
             is_expression: false,
@@ -531,4 +721,52 @@ mod tests {
         assert_eq!(findings.len(), 1);
         assert!(matches!(findings[0].issue, IndexIssueKind::LowSelectivity));
     }
+
+    fn idle_index_row() -> IndexStatRow {
+        IndexStatRow {
+            schema: "public".into(),
+            table_name: "events".into(),
+            index_name: "events_legacy_idx".into(),
+            key_columns: vec!["legacy_id".into()],
+            index_size_bytes: 80 * 1024 * 1024,
+            index_size_pretty: "80 MB".into(),
+            idx_scan: 0,
+            idx_tup_read: 0,
+            idx_tup_fetch: 0,
+            table_live_tup: Some(2_000_000),
+            table_index_writes: Some(1_400_000),
+            table_scans: None,
+            index_definition: None,
+            is_unique: false,
+            enforces_constraint: false,
+            is_expression: false,
+            is_partial: false,
+        }
+    }
+
+    #[test]
+    fn unused_index_rationale_estimates_daily_write_overhead() {
+        let rows = vec![idle_index_row()];
+        let mut results = AnalysisResults::default();
+        results.system_stats.stats_age_secs = Some(14 * 86_400);
+
+        add_index_suggestions(&identify_unused_indexes(&rows, None), &mut results);
+
+        let suggestion = &results.suggestions_by_category[&ConfigCategory::TableIndex][0];
+        assert!(suggestion
+            .rationale
+            .contains("absorbs ~100000 writes/day (inserts and non-HOT updates on public.events)"));
+    }
+
+    #[test]
+    fn index_scanned_on_replica_is_not_unused() {
+        let rows = vec![idle_index_row()];
+        let mut replicas = ReplicaIndexScans::default();
+        assert_eq!(identify_unused_indexes(&rows, Some(&replicas)).len(), 1);
+
+        replicas
+            .scans
+            .insert(("public".into(), "events_legacy_idx".into()), 3);
+        assert!(identify_unused_indexes(&rows, Some(&replicas)).is_empty());
+    }
 }
@@ -1,39 +1,255 @@
+use crate::analysis::catalog_cache::CatalogCache;
+use crate::analysis::replicas::ReplicaIndexScans;
+use crate::analysis::sampling::{self, StatsBaseline, MIN_UNUSED_INDEX_WINDOW_SECS};
 use crate::checker::CheckerError;
-use crate::models::{AnalysisResults, ConfigCategory, ConfigSuggestion, SuggestionLevel};
+use crate::fixture::Fixture;
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ConfigCategory, ConfigSuggestion, SuggestionLevel, TableIndexHealth,
+};
 use sqlx::{Pool, Postgres};
 
-mod bloat;
-mod indexes;
+pub(crate) mod bloat;
+pub(crate) mod indexes;
+mod remediation;
+mod top_k;
+
+pub use remediation::plan_index_removals;
+pub(crate) use remediation::quote_ident;
+
+/// Optional inputs for [`analyze_table_index_health`]
+#[derive(Debug, Clone, Copy)]
+pub struct TableIndexInputs<'a> {
+    /// Scan counters are reduced to the activity since this was captured
+    pub baseline: Option<&'a StatsBaseline>,
+    /// Replica scans added to the primary's before an index is called unused
+    pub replica_scans: Option<&'a ReplicaIndexScans>,
+    /// Serves the table and index statistics
+    pub fixture: &'a Fixture,
+    /// Index catalog already read this run; read here when `None`
+    pub catalog: Option<&'a CatalogCache>,
+    /// Leave the index catalog unread, see [`AnalyzeOptions::gentle`](crate::checker::AnalyzeOptions::gentle)
+    pub gentle: bool,
+    /// See [`AnalyzeOptions::max_findings_per_kind`](crate::checker::AnalyzeOptions::max_findings_per_kind)
+    pub max_findings_per_kind: Option<usize>,
+}
+
+impl Default for TableIndexInputs<'_> {
+    fn default() -> Self {
+        static LIVE: Fixture = Fixture::Live;
+        Self {
+            baseline: None,
+            replica_scans: None,
+            fixture: &LIVE,
+            catalog: None,
+            gentle: false,
+            max_findings_per_kind: None,
+        }
+    }
+}
 
 /// Entry point that coordinates table bloat and index health analysis.
+///
+/// Unused-index findings are suppressed when `system_stats` shows the statistics
+/// window is too short to tell an idle index from a rarely used one, or when a
+/// configured replica could not be checked.
 pub async fn analyze_table_index_health(
     pool: &Pool<Postgres>,
     results: &mut AnalysisResults,
+    inputs: TableIndexInputs<'_>,
 ) -> Result<(), CheckerError> {
-    bloat::analyze(pool, results).await?;
-    indexes::analyze(pool, results).await?;
+    let mut report_unused = results
+        .system_stats
+        .stats_age_secs
+        .is_none_or(|age| age >= MIN_UNUSED_INDEX_WINDOW_SECS);
+    if let Some(replicas) = inputs.replica_scans.filter(|r| !r.is_complete()) {
+        report_unused = false;
+        results.warnings.push(format!(
+            "Unused-index findings are suppressed: index usage could not be read from replica(s) {}, which may serve the reads.",
+            replicas.unreachable.join(", ")
+        ));
+    }
+
+    bloat::analyze(
+        pool,
+        inputs.fixture,
+        results,
+        inputs.baseline,
+        inputs.max_findings_per_kind,
+    )
+    .await?;
+    let fetched;
+    let catalog = match inputs.catalog {
+        Some(catalog) => catalog,
+        None => {
+            fetched = if inputs.gentle {
+                CatalogCache::empty()
+            } else {
+                CatalogCache::fetch(pool, inputs.fixture).await
+            };
+            &fetched
+        }
+    };
+    indexes::analyze(
+        pool,
+        inputs.fixture,
+        results,
+        report_unused,
+        inputs.replica_scans,
+        catalog,
+        inputs.max_findings_per_kind,
+    )
+    .await?;
+    annotate_measurement_window(results);
     Ok(())
 }
 
-fn push_table_index_suggestion(
-    results: &mut AnalysisResults,
+/// Keeps the first findings of `kind`, sorted worst first, and counts the rest
+/// as not shown. `max_per_kind` of `None` applies the kind's `default` cap,
+/// where `None` lists them all, and `Some(0)` lists every finding.
+fn cap_findings<T>(
+    findings: &mut Vec<T>,
+    max_per_kind: Option<usize>,
+    default: Option<usize>,
+    kind: &str,
+    health: &mut TableIndexHealth,
+) {
+    let Some(limit) = findings_limit(max_per_kind, default).filter(|limit| findings.len() > *limit)
+    else {
+        return;
+    };
+    *health.not_shown.entry(kind.to_string()).or_default() += findings.len() - limit;
+    findings.truncate(limit);
+}
+
+/// How many findings of a kind [`cap_findings`] keeps; `None` keeps them all
+fn findings_limit(max_per_kind: Option<usize>, default: Option<usize>) -> Option<usize> {
+    match max_per_kind {
+        Some(0) => None,
+        Some(max) => Some(max),
+        None => default,
+    }
+}
+
+/// Appends the statistics window to every counter-derived suggestion so readers
+/// can judge how much history backs it. Under `--sample-window` scan counts are
+/// deltas over the window while index usage stays cumulative, so both are named.
+fn annotate_measurement_window(results: &mut AnalysisResults) {
+    let stats = &results.system_stats;
+    let (Some(age), Some(since)) = (stats.stats_age_secs, stats.stats_reset_at.as_deref()) else {
+        return;
+    };
+    let note = match stats.sample_window_secs {
+        Some(window) => Message::new("note.sample_window")
+            .arg("window", sampling::format_window(window))
+            .arg("stats_window", sampling::format_window(age)),
+        None => Message::new("note.statistics_window").arg("window", sampling::format_window(age)),
+    }
+    .arg("since", since);
+
+    if let Some(suggestions) = results
+        .suggestions_by_category
+        .get_mut(&ConfigCategory::TableIndex)
+    {
+        for suggestion in suggestions {
+            suggestion.push_note(note.clone());
+        }
+    }
+}
+
+fn push_table_index_suggestion<'a>(
+    results: &'a mut AnalysisResults,
     parameter: &str,
     current_value: &str,
     suggested_value: &str,
     level: SuggestionLevel,
-    rationale: &str,
-) {
+    rationale: Message,
+) -> &'a mut ConfigSuggestion {
     let suggestion = ConfigSuggestion {
         parameter: parameter.to_string(),
         current_value: current_value.to_string(),
         suggested_value: suggested_value.to_string(),
         level,
-        rationale: rationale.to_string(),
+        rationale: rationale.render(Lang::En),
+        messages: vec![rationale],
+        fingerprint: String::new(),
     };
 
-    results
+    let suggestions = results
         .suggestions_by_category
         .entry(ConfigCategory::TableIndex)
-        .or_default()
-        .push(suggestion);
+        .or_default();
+    suggestions.push(suggestion);
+    suggestions.last_mut().expect("just pushed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measurement_window_is_appended_to_table_index_rationales() {
+        let mut results = AnalysisResults::default();
+        results.system_stats.stats_reset_at = Some("2026-10-01 00:00:00+00".to_string());
+        results.system_stats.stats_age_secs = Some(15 * 86400);
+        push_table_index_suggestion(
+            &mut results,
+            "table public.orders",
+            "0 scans",
+            "Add index",
+            SuggestionLevel::Recommended,
+            Message::text("Frequent sequential scans."),
+        );
+
+        annotate_measurement_window(&mut results);
+
+        let suggestion = &results.suggestions_by_category[&ConfigCategory::TableIndex][0];
+        assert_eq!(
+            suggestion.rationale,
+            "Frequent sequential scans. Statistics cover 15d 0h (since 2026-10-01 00:00:00+00)."
+        );
+    }
+
+    #[test]
+    fn sampled_runs_name_the_window_scan_counts_cover() {
+        let mut results = AnalysisResults::default();
+        results.system_stats.stats_reset_at = Some("2026-10-01 00:00:00+00".to_string());
+        results.system_stats.stats_age_secs = Some(15 * 86400);
+        results.system_stats.sample_window_secs = Some(300);
+        push_table_index_suggestion(
+            &mut results,
+            "table public.orders",
+            "0 scans",
+            "Add index",
+            SuggestionLevel::Recommended,
+            Message::text("Frequent sequential scans."),
+        );
+
+        annotate_measurement_window(&mut results);
+
+        let suggestion = &results.suggestions_by_category[&ConfigCategory::TableIndex][0];
+        assert_eq!(
+            suggestion.rationale,
+            "Frequent sequential scans. Scan counts cover the 5m sample window; index usage \
+             covers 15d 0h (since 2026-10-01 00:00:00+00)."
+        );
+    }
+
+    #[test]
+    fn capped_findings_count_the_rest_as_not_shown() {
+        let mut health = TableIndexHealth::default();
+        let mut findings: Vec<_> = (0..12).collect();
+        cap_findings(&mut findings, None, Some(10), "bloat", &mut health);
+        assert_eq!(findings.len(), 10);
+        cap_findings(&mut findings, Some(4), Some(10), "seq_scan", &mut health);
+        assert_eq!(findings, [0, 1, 2, 3]);
+        assert_eq!(health.not_shown("bloat"), 2);
+        assert_eq!(health.not_shown("seq_scan"), 6);
+
+        let mut all: Vec<_> = (0..12).collect();
+        cap_findings(&mut all, Some(0), Some(10), "unused", &mut health);
+        cap_findings(&mut all, None, None, "missing_partial_index", &mut health);
+        assert_eq!(all.len(), 12);
+        assert_eq!(health.not_shown.len(), 2);
+    }
 }
@@ -0,0 +1,159 @@
+use crate::models::{IndexIssueKind, IndexRemovalDdl, IndexRemovalPlan, IndexUsageInfo};
+
+/// Builds a staged removal plan for every unused index finding.
+///
+/// `idx_scan` only counts scans on the node it is read from, so each plan starts
+/// with a query to confirm the index is idle on replicas too. DDL is only
+/// generated when `emit_ddl` is set.
+pub fn plan_index_removals(indexes: &[IndexUsageInfo], emit_ddl: bool) -> Vec<IndexRemovalPlan> {
+    indexes
+        .iter()
+        .filter(|index| index.issue == IndexIssueKind::Unused)
+        .map(|index| IndexRemovalPlan {
+            schema: index.schema.clone(),
+            table_name: index.table_name.clone(),
+            index_name: index.index_name.clone(),
+            monitor_query: monitor_query(index),
+            notes: removal_notes(index),
+            ddl: emit_ddl.then(|| removal_ddl(index)),
+        })
+        .collect()
+}
+
+fn monitor_query(index: &IndexUsageInfo) -> String {
+    format!(
+        "SELECT idx_scan FROM pg_stat_user_indexes WHERE schemaname = {} AND indexrelname = {};",
+        quote_literal(&index.schema),
+        quote_literal(&index.index_name)
+    )
+}
+
+fn removal_notes(index: &IndexUsageInfo) -> Vec<String> {
+    vec![
+        "Run the monitoring query on the primary and every replica; keep the index if any node reports scans.".to_string(),
+        format!(
+            "Unique and constraint-backing indexes are never reported as unused; {} enforces no constraint.",
+            index.index_name
+        ),
+        "Observe for at least one full business cycle (month-end jobs, reports) after resetting counters before dropping.".to_string(),
+        "DROP INDEX CONCURRENTLY cannot run inside a transaction block; run it on its own.".to_string(),
+    ]
+}
+
+fn removal_ddl(index: &IndexUsageInfo) -> IndexRemovalDdl {
+    let qualified = format!(
+        "{}.{}",
+        quote_ident(&index.schema),
+        quote_ident(&index.index_name)
+    );
+
+    IndexRemovalDdl {
+        reset_stats: format!(
+            "SELECT pg_stat_reset_single_table_counters({}::regclass);",
+            quote_literal(&qualified)
+        ),
+        drop_index: format!("DROP INDEX CONCURRENTLY IF EXISTS {qualified};"),
+        rollback: index.index_definition.as_deref().map(|definition| {
+            format!(
+                "{};",
+                definition.replacen(" INDEX ", " INDEX CONCURRENTLY ", 1)
+            )
+        }),
+    }
+}
+
+pub(crate) fn quote_ident(name: &str) -> String {
+    let is_plain = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+    if is_plain {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+}
+
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unused_index(index_name: &str) -> IndexUsageInfo {
+        IndexUsageInfo {
+            issue: IndexIssueKind::Unused,
+            schema: "public".into(),
+            table_name: "orders".into(),
+            index_name: index_name.into(),
+            key_columns: vec!["legacy_ref".into()],
+            index_size_bytes: crate::models::ByteSize(10 * 1024 * 1024),
+            index_size_pretty: "10 MB".into(),
+            scans: 0,
+            tuples_read: 0,
+            tuples_fetched: 0,
+            avg_tuples_per_scan: 0.0,
+            heap_fetch_ratio: 0.0,
+            table_live_tup: Some(1_000),
+            table_index_writes: None,
+            table_scans: None,
+            index_definition: Some(format!(
+                "CREATE INDEX {index_name} ON public.orders USING btree (legacy_ref)"
+            )),
+            partial_predicate: None,
+            estimated_size_bytes: None,
+            is_unique: false,
+            enforces_constraint: false,
+            is_expression: false,
+            is_partial: false,
+            fingerprint: String::new(),
+        }
+    }
+
+    #[test]
+    fn ddl_is_only_generated_when_requested() {
+        let indexes = vec![unused_index("orders_legacy_ref_idx")];
+
+        let plans = plan_index_removals(&indexes, false);
+        assert_eq!(plans.len(), 1);
+        assert!(plans[0].ddl.is_none());
+        assert!(plans[0]
+            .monitor_query
+            .contains("indexrelname = 'orders_legacy_ref_idx'"));
+
+        let ddl = plan_index_removals(&indexes, true)[0].ddl.clone().unwrap();
+        assert_eq!(
+            ddl.drop_index,
+            "DROP INDEX CONCURRENTLY IF EXISTS public.orders_legacy_ref_idx;"
+        );
+        assert_eq!(
+            ddl.rollback.as_deref(),
+            Some("CREATE INDEX CONCURRENTLY orders_legacy_ref_idx ON public.orders USING btree (legacy_ref);")
+        );
+    }
+
+    #[test]
+    fn mixed_case_names_are_quoted() {
+        let ddl = plan_index_removals(&[unused_index("Orders_Ref")], true)[0]
+            .ddl
+            .clone()
+            .unwrap();
+        assert_eq!(
+            ddl.reset_stats,
+            "SELECT pg_stat_reset_single_table_counters('public.\"Orders_Ref\"'::regclass);"
+        );
+    }
+
+    #[test]
+    fn only_unused_indexes_get_plans() {
+        let mut index = unused_index("orders_status_idx");
+        index.issue = IndexIssueKind::LowSelectivity;
+        assert!(plan_index_removals(&[index], true).is_empty());
+    }
+}
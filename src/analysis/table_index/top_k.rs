@@ -0,0 +1,101 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+/// The `limit` rows with the largest keys of a stream, so memory is bounded by
+/// `limit` rather than by the number of rows. Earlier rows win ties, and rows
+/// keep their position in the stream, as a stable sort of every row would.
+pub(crate) struct TopK<K: Ord, T> {
+    limit: usize,
+    heap: BinaryHeap<Entry<K, T>>,
+}
+
+/// Ordered so the heap's top is the entry to evict: smallest key, then latest row
+struct Entry<K, T> {
+    key: K,
+    position: usize,
+    row: T,
+}
+
+impl<K: Ord, T> Entry<K, T> {
+    fn rank(&self) -> (Reverse<&K>, usize) {
+        (Reverse(&self.key), self.position)
+    }
+}
+
+impl<K: Ord, T> PartialEq for Entry<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rank() == other.rank()
+    }
+}
+
+impl<K: Ord, T> Eq for Entry<K, T> {}
+
+impl<K: Ord, T> PartialOrd for Entry<K, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, T> Ord for Entry<K, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
+impl<K: Ord, T> TopK<K, T> {
+    pub(crate) fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            // The limit may be usize::MAX for "keep everything"
+            heap: BinaryHeap::with_capacity(limit.saturating_add(1).min(1024)),
+        }
+    }
+
+    /// Offers the row at `position` in the stream; positions must increase
+    pub(crate) fn push(&mut self, key: K, position: usize, row: T) {
+        if self.limit == 0 {
+            return;
+        }
+        self.heap.push(Entry { key, position, row });
+        if self.heap.len() > self.limit {
+            self.heap.pop();
+        }
+    }
+
+    /// The kept rows with their positions, in stream order
+    pub(crate) fn into_rows(self) -> Vec<(usize, T)> {
+        let mut rows: Vec<_> = self
+            .heap
+            .into_iter()
+            .map(|entry| (entry.position, entry.row))
+            .collect();
+        rows.sort_by_key(|(position, _)| *position);
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_largest_keys_in_stream_order() {
+        let mut top = TopK::new(3);
+        for (position, key) in [5, 1, 9, 3, 7, 2].into_iter().enumerate() {
+            top.push(key, position, key);
+        }
+        let rows: Vec<_> = top.into_rows().into_iter().map(|(_, row)| row).collect();
+        assert_eq!(rows, [5, 9, 7]);
+    }
+
+    #[test]
+    fn earlier_rows_win_ties() {
+        let mut top = TopK::new(2);
+        for (position, name) in ["a", "b", "c"].into_iter().enumerate() {
+            top.push(10, position, name);
+        }
+        let rows: Vec<_> = top.into_rows().into_iter().map(|(_, row)| row).collect();
+        assert_eq!(rows, ["a", "b"]);
+        assert!(TopK::<i64, ()>::new(0).into_rows().is_empty());
+    }
+}
@@ -0,0 +1,181 @@
+use crate::analysis::get_param;
+use crate::analysis::table_index::quote_ident;
+use crate::catalog::Catalog;
+use crate::checker::CheckerError;
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ByteSize, ConfigCategory, ConfigSuggestion, OrphanedTempSchema, PgConfigParam,
+    SuggestionLevel, SystemStats,
+};
+use sqlx::{Pool, Postgres, Row};
+use std::collections::HashMap;
+use tracing::warn;
+
+type Result<T> = std::result::Result<T, CheckerError>;
+
+/// Leftovers at least this large are Important
+const IMPORTANT_BYTES: i64 = 1024 * 1024 * 1024;
+/// Leftovers holding back freezing by this many transactions are Important
+const IMPORTANT_XID_AGE: i64 = 100_000_000;
+
+pub(crate) const ORPHANED_TEMP_SCHEMAS_QUERY: &str = r#"
+    SELECT
+        n.nspname::text AS schema,
+        count(*) AS tables,
+        coalesce(sum(pg_total_relation_size(c.oid)), 0)::bigint AS bytes,
+        max(age(c.relfrozenxid))::bigint AS xid_age,
+        (array_agg(c.relname::text ORDER BY pg_total_relation_size(c.oid) DESC))[1:5]
+            AS largest
+    FROM pg_namespace n
+    JOIN pg_class c ON c.relnamespace = n.oid AND c.relkind = 'r'
+    WHERE n.nspname ~ '^pg_temp_[0-9]+$'
+      AND substr(n.nspname, 9)::int NOT IN (SELECT pg_stat_get_backend_idset())
+    GROUP BY n.nspname
+    ORDER BY bytes DESC
+"#;
+
+/// Temp schemas with tables but no live backend in their slot. Backend IDs
+/// from `pg_stat_get_backend_idset()` only match the `pg_temp_N` numbering
+/// from PostgreSQL 16, so older servers return nothing.
+pub async fn fetch_orphaned_temp_schemas(
+    pool: &Pool<Postgres>,
+    catalog: &Catalog,
+) -> Vec<OrphanedTempSchema> {
+    if !catalog.backend_ids_match_temp_schemas() {
+        return Vec::new();
+    }
+
+    match sqlx::query(ORPHANED_TEMP_SCHEMAS_QUERY)
+        .fetch_all(pool)
+        .await
+    {
+        Ok(rows) => rows
+            .into_iter()
+            .map(|row| OrphanedTempSchema {
+                schema: row.get("schema"),
+                tables: row.get::<i64, _>("tables").max(0) as usize,
+                size: ByteSize(row.get("bytes")),
+                xid_age: row.get("xid_age"),
+                largest_tables: row.get("largest"),
+            })
+            .collect(),
+        Err(err) => {
+            warn!("Failed to read temp schemas: {err}");
+            Vec::new()
+        }
+    }
+}
+
+/// Flags temp tables left behind by backends that crashed or were killed:
+/// their files use disk nobody can see in the table list, and as autovacuum
+/// cannot vacuum temp tables, their age holds back `datfrozenxid`
+pub fn analyze_orphaned_temp_schemas(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    let autovacuum_on = get_param(params, "autovacuum").is_none_or(|p| p.current_value == "on");
+    for orphan in &stats.orphaned_temp_schemas {
+        let level = if orphan.size.0 >= IMPORTANT_BYTES || orphan.xid_age >= IMPORTANT_XID_AGE {
+            SuggestionLevel::Important
+        } else {
+            SuggestionLevel::Recommended
+        };
+        let drops: Vec<String> = orphan
+            .largest_tables
+            .iter()
+            .map(|table| format!("DROP TABLE {}.{};", orphan.schema, quote_ident(table)))
+            .collect();
+        let key = if autovacuum_on {
+            "table_index.orphaned_temp_schema"
+        } else {
+            "table_index.orphaned_temp_schema.autovacuum_off"
+        };
+        let rationale = Message::new(key)
+            .arg("schema", &orphan.schema)
+            .arg("tables", orphan.tables)
+            .arg("size", orphan.size)
+            .arg("xid_age", orphan.xid_age)
+            .arg("drops", drops.join(" "));
+        results
+            .suggestions_by_category
+            .entry(ConfigCategory::TableIndex)
+            .or_default()
+            .push(ConfigSuggestion {
+                parameter: format!("temp schema {}", orphan.schema),
+                current_value: format!("{} tables, {}", orphan.tables, orphan.size),
+                suggested_value: "Drop the leftover tables".to_string(),
+                level,
+                rationale: rationale.render(Lang::En),
+                messages: vec![rationale],
+                fingerprint: String::new(),
+            });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ParamValue;
+    use rstest::rstest;
+
+    fn orphan(bytes: i64, xid_age: i64) -> OrphanedTempSchema {
+        OrphanedTempSchema {
+            schema: "pg_temp_12".to_string(),
+            tables: 2,
+            size: ByteSize(bytes),
+            xid_age,
+            largest_tables: vec!["report_rows".to_string(), "Staging".to_string()],
+        }
+    }
+
+    fn analyzed(orphan: OrphanedTempSchema, autovacuum: &str) -> ConfigSuggestion {
+        let param = PgConfigParam {
+            name: "autovacuum".to_string(),
+            current_value: autovacuum.to_string(),
+            default_value: None,
+            unit: None,
+            context: "sighup".to_string(),
+            value: ParamValue::parse(autovacuum, "bool", None),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        };
+        let stats = SystemStats {
+            orphaned_temp_schemas: vec![orphan],
+            ..Default::default()
+        };
+        let mut results = AnalysisResults::default();
+        analyze_orphaned_temp_schemas(
+            &HashMap::from([(param.name.clone(), param)]),
+            &stats,
+            &mut results,
+        )
+        .unwrap();
+        results.suggestions_by_category[&ConfigCategory::TableIndex][0].clone()
+    }
+
+    #[rstest]
+    #[case::small(orphan(64 * 1024 * 1024, 1_000_000), SuggestionLevel::Recommended)]
+    #[case::large(orphan(5 * IMPORTANT_BYTES, 1_000_000), SuggestionLevel::Important)]
+    #[case::old(orphan(8192, 150_000_000), SuggestionLevel::Important)]
+    fn level_follows_size_and_age(
+        #[case] orphan: OrphanedTempSchema,
+        #[case] level: SuggestionLevel,
+    ) {
+        assert_eq!(analyzed(orphan, "on").level, level);
+    }
+
+    #[test]
+    fn rationale_lists_drop_statements() {
+        let suggestion = analyzed(orphan(8192, 1000), "off");
+        assert_eq!(suggestion.parameter, "temp schema pg_temp_12");
+        assert!(suggestion
+            .rationale
+            .contains("DROP TABLE pg_temp_12.report_rows; DROP TABLE pg_temp_12.\"Staging\";"));
+        assert!(suggestion.rationale.contains("As autovacuum is off"));
+    }
+}
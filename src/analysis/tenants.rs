@@ -0,0 +1,185 @@
+use crate::analysis::extensions::EXTENSION_CATALOG_SCHEMAS;
+use crate::checker::CheckerError;
+use crate::models::{ByteSize, SchemaUsage, TableIndexHealth};
+use sqlx::{Pool, Postgres, Row};
+use std::collections::HashMap;
+
+type Result<T> = std::result::Result<T, CheckerError>;
+
+/// A schema using this many times an even split of the database is noisy
+const NOISY_SHARE_FACTOR: f64 = 3.0;
+/// Fewer schemas than this are not compared for fairness
+const MIN_SCHEMAS_COMPARED: usize = 3;
+
+pub(crate) const SCHEMA_USAGE_QUERY: &str = r#"
+    SELECT
+        schemaname::text AS schema,
+        count(*) AS tables,
+        coalesce(sum(pg_table_size(relid)), 0)::bigint AS table_bytes,
+        coalesce(sum(pg_indexes_size(relid)), 0)::bigint AS index_bytes,
+        coalesce(sum(n_live_tup), 0)::bigint AS live_tuples,
+        coalesce(sum(n_dead_tup), 0)::bigint AS dead_tuples,
+        coalesce(sum(n_tup_ins + n_tup_upd + n_tup_del), 0)::bigint AS rows_written,
+        coalesce(sum(seq_tup_read + coalesce(idx_tup_fetch, 0)), 0)::bigint AS rows_read
+    FROM pg_stat_user_tables
+    WHERE schemaname <> ALL($1)
+    GROUP BY schemaname
+"#;
+
+/// Sums table storage and activity counters per schema
+pub async fn fetch_schema_usage(pool: &Pool<Postgres>) -> Result<Vec<SchemaUsage>> {
+    let rows = sqlx::query(SCHEMA_USAGE_QUERY)
+        .bind(EXTENSION_CATALOG_SCHEMAS)
+        .fetch_all(pool)
+        .await
+        .map_err(|source| CheckerError::QueryError {
+            query: SCHEMA_USAGE_QUERY.into(),
+            source,
+        })?;
+    Ok(rows
+        .into_iter()
+        .map(|row| SchemaUsage {
+            schema: row.get("schema"),
+            tables: row.get::<i64, _>("tables").max(0) as usize,
+            table_bytes: ByteSize(row.get("table_bytes")),
+            index_bytes: ByteSize(row.get("index_bytes")),
+            live_tuples: row.get("live_tuples"),
+            dead_tuples: row.get("dead_tuples"),
+            rows_written: row.get("rows_written"),
+            rows_read: row.get("rows_read"),
+            ..Default::default()
+        })
+        .collect())
+}
+
+/// Counts each schema's bloat, sequential scan and index findings and ranks
+/// the schemas by their mean share of storage, rows written and rows read
+pub fn rank_schemas(mut usage: Vec<SchemaUsage>, health: &TableIndexHealth) -> Vec<SchemaUsage> {
+    let mut findings: HashMap<&str, usize> = HashMap::new();
+    let schemas = health
+        .bloat_info
+        .iter()
+        .map(|table| table.schema.as_str())
+        .chain(
+            health
+                .seq_scan_info
+                .iter()
+                .map(|table| table.schema.as_str()),
+        )
+        .chain(
+            health
+                .index_usage_info
+                .iter()
+                .map(|index| index.schema.as_str()),
+        );
+    for schema in schemas {
+        *findings.entry(schema).or_default() += 1;
+    }
+
+    let total_bytes: i64 = usage
+        .iter()
+        .map(|schema| schema.table_bytes.0 + schema.index_bytes.0)
+        .sum();
+    let total_written: i64 = usage.iter().map(|schema| schema.rows_written).sum();
+    let total_read: i64 = usage.iter().map(|schema| schema.rows_read).sum();
+    let share = |value: i64, total: i64| {
+        if total > 0 {
+            value as f64 / total as f64
+        } else {
+            0.0
+        }
+    };
+    let fair_share = 1.0 / usage.len().max(1) as f64;
+    let compared = usage.len() >= MIN_SCHEMAS_COMPARED;
+    for schema in &mut usage {
+        schema.findings = findings.get(schema.schema.as_str()).copied().unwrap_or(0);
+        schema.share = (share(schema.table_bytes.0 + schema.index_bytes.0, total_bytes)
+            + share(schema.rows_written, total_written)
+            + share(schema.rows_read, total_read))
+            / 3.0;
+        schema.noisy = compared && schema.share >= NOISY_SHARE_FACTOR * fair_share;
+    }
+    usage.sort_by(|a, b| {
+        b.share
+            .total_cmp(&a.share)
+            .then_with(|| a.schema.cmp(&b.schema))
+    });
+    usage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TableBloatInfo;
+
+    fn schema(name: &str, bytes: i64, rows_written: i64, rows_read: i64) -> SchemaUsage {
+        SchemaUsage {
+            schema: name.to_string(),
+            tables: 10,
+            table_bytes: ByteSize(bytes),
+            rows_written,
+            rows_read,
+            ..Default::default()
+        }
+    }
+
+    fn bloated(schema: &str) -> TableBloatInfo {
+        TableBloatInfo {
+            schema: schema.to_string(),
+            table_name: "events".to_string(),
+            live_tuples: 1000,
+            dead_tuples: 500,
+            dead_tup_ratio: 0.5,
+            seq_scan: 0,
+            idx_scan: 0,
+            table_size_bytes: ByteSize(0),
+            table_size_pretty: String::new(),
+            last_autovacuum: None,
+            last_autoanalyze: None,
+            seconds_since_last_autovacuum: None,
+            seconds_since_last_autoanalyze: None,
+            fingerprint: String::new(),
+        }
+    }
+
+    #[test]
+    fn schemas_are_ranked_by_mean_share_and_noisy_tenants_flagged() {
+        let usage = vec![
+            schema("tenant_a", 100, 100, 100),
+            schema("tenant_b", 100, 100, 100),
+            schema("tenant_c", 100, 100, 100),
+            schema("tenant_d", 100, 100, 100),
+            schema("tenant_big", 1200, 3600, 100),
+        ];
+        let health = TableIndexHealth {
+            bloat_info: vec![
+                bloated("tenant_big"),
+                bloated("tenant_big"),
+                bloated("tenant_a"),
+            ],
+            ..Default::default()
+        };
+        let ranked = rank_schemas(usage, &health);
+        let order: Vec<&str> = ranked.iter().map(|s| s.schema.as_str()).collect();
+        assert_eq!(
+            order,
+            ["tenant_big", "tenant_a", "tenant_b", "tenant_c", "tenant_d"]
+        );
+        // (1200/1600 + 3600/4000 + 100/500) / 3
+        assert!((ranked[0].share - 0.6167).abs() < 0.001);
+        assert!(ranked[0].noisy);
+        assert!(!ranked[1].noisy);
+        assert_eq!(ranked[0].findings, 2);
+        assert_eq!(ranked[1].findings, 1);
+    }
+
+    #[test]
+    fn two_schemas_are_not_judged_noisy() {
+        let ranked = rank_schemas(
+            vec![schema("public", 100, 0, 0), schema("audit", 0, 0, 0)],
+            &TableIndexHealth::default(),
+        );
+        assert!(ranked.iter().all(|s| !s.noisy));
+        assert_eq!(ranked[0].schema, "public");
+    }
+}
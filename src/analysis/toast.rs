@@ -0,0 +1,343 @@
+use crate::analysis::table_index::quote_ident;
+use crate::checker::CheckerError;
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ByteSize, ConfigCategory, ConfigSuggestion, PgConfigParam, SuggestionLevel,
+    SystemStats, ToastColumn, ToastCompression, ToastTable,
+};
+use sqlx::{Pool, Postgres, Row};
+use std::collections::HashMap;
+use tracing::warn;
+
+type Result<T> = std::result::Result<T, CheckerError>;
+
+/// Tables with less TOAST data than this are not worth recompressing
+const MIN_TOAST_BYTES: i64 = 256 * 1024 * 1024;
+/// Rows inserted or updated per live row at which a table counts as write-heavy
+const WRITE_HEAVY_RATIO: f64 = 1.0;
+/// lz4 output is typically about this much larger than pglz on text and JSON
+const LZ4_EXTRA_STORAGE: f64 = 0.1;
+const MAX_TOAST_TABLES: i64 = 20;
+/// Tables read with `--gentle`
+const GENTLE_TOAST_TABLES: i64 = 5;
+
+pub(crate) const TOAST_TABLES_QUERY: &str = r#"
+    SELECT
+        n.nspname,
+        c.relname,
+        pg_relation_size(c.reltoastrelid) AS toast_size_bytes,
+        coalesce(s.n_live_tup, 0) AS live_tuples,
+        coalesce(s.n_tup_ins, 0) + coalesce(s.n_tup_upd, 0) AS writes,
+        (
+            SELECT coalesce(json_agg(json_build_array(
+                a.attname,
+                format_type(a.atttypid, NULL),
+                CASE a.attcompression WHEN 'p' THEN 'pglz' WHEN 'l' THEN 'lz4' END
+            ) ORDER BY a.attnum), '[]')::text
+            FROM pg_attribute a
+            JOIN pg_type t ON t.oid = a.atttypid
+            WHERE a.attrelid = c.oid
+              AND a.attnum > 0
+              AND NOT a.attisdropped
+              AND a.attstorage IN ('x', 'm')
+              AND t.typname IN ('text', 'varchar', 'json', 'jsonb', 'xml', 'bytea')
+        ) AS columns
+    FROM pg_class c
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    LEFT JOIN pg_stat_user_tables s ON s.relid = c.oid
+    WHERE c.relkind IN ('r', 'm')
+      AND c.reltoastrelid <> 0
+      AND n.nspname NOT IN ('pg_catalog', 'information_schema')
+      AND pg_relation_size(c.reltoastrelid) >= $1
+    ORDER BY toast_size_bytes DESC
+    LIMIT $2
+"#;
+
+pub(crate) const LZ4_AVAILABLE_QUERY: &str = r#"
+    SELECT 'lz4' = ANY(enumvals)
+    FROM pg_settings
+    WHERE name = 'default_toast_compression'
+"#;
+
+/// Reads the tables with the most TOAST data and their columns' compression
+/// methods, fewer of them when `gentle`. `None` before PostgreSQL 14, which
+/// has only pglz.
+pub async fn fetch_toast_compression(
+    pool: &Pool<Postgres>,
+    gentle: bool,
+) -> Option<ToastCompression> {
+    let lz4_available = match sqlx::query_scalar::<_, bool>(LZ4_AVAILABLE_QUERY)
+        .fetch_optional(pool)
+        .await
+    {
+        Ok(available) => available?,
+        Err(err) => {
+            warn!("Failed to read default_toast_compression: {err}");
+            return None;
+        }
+    };
+    let rows = match sqlx::query(TOAST_TABLES_QUERY)
+        .bind(MIN_TOAST_BYTES)
+        .bind(if gentle {
+            GENTLE_TOAST_TABLES
+        } else {
+            MAX_TOAST_TABLES
+        })
+        .fetch_all(pool)
+        .await
+    {
+        Ok(rows) => rows,
+        Err(err) => {
+            warn!("Failed to read TOAST sizes: {err}");
+            return None;
+        }
+    };
+
+    let tables = rows
+        .into_iter()
+        .map(|row| {
+            let columns: Vec<(String, String, Option<String>)> =
+                serde_json::from_str(&row.get::<String, _>("columns")).unwrap_or_default();
+            ToastTable {
+                schema: row.get("nspname"),
+                table_name: row.get("relname"),
+                toast_size_bytes: ByteSize(row.get("toast_size_bytes")),
+                live_tuples: row.get("live_tuples"),
+                writes: row.get("writes"),
+                columns: columns
+                    .into_iter()
+                    .map(|(name, type_name, compression)| ToastColumn {
+                        name,
+                        type_name,
+                        compression,
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+    Some(ToastCompression {
+        lz4_available,
+        tables,
+    })
+}
+
+/// Recommends lz4 for the pglz-compressed columns of write-heavy tables with
+/// large TOAST, and as default_toast_compression when any such table exists
+pub fn analyze_toast_compression(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    let Some(toast) = stats.toast.as_ref().filter(|toast| toast.lz4_available) else {
+        return Ok(());
+    };
+    let Some(default) = params.get("default_toast_compression") else {
+        return Ok(());
+    };
+
+    let mut candidates = 0;
+    for table in toast.tables.iter().filter(|table| is_write_heavy(table)) {
+        let pglz: Vec<&ToastColumn> = table
+            .columns
+            .iter()
+            .filter(|column| {
+                column
+                    .compression
+                    .as_deref()
+                    .unwrap_or(&default.current_value)
+                    == "pglz"
+            })
+            .collect();
+        if pglz.is_empty() {
+            continue;
+        }
+        candidates += 1;
+
+        let name = format!("{}.{}", table.schema, table.table_name);
+        let ddl = format!(
+            "ALTER TABLE {}.{} {}",
+            quote_ident(&table.schema),
+            quote_ident(&table.table_name),
+            pglz.iter()
+                .map(|column| format!(
+                    "ALTER COLUMN {} SET COMPRESSION lz4",
+                    quote_ident(&column.name)
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let columns = pglz
+            .iter()
+            .map(|column| format!("{} {}", column.name, column.type_name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        add_suggestion(
+            results,
+            &format!("table {name} compression"),
+            &format!("pglz ({columns})"),
+            "lz4",
+            SuggestionLevel::Recommended,
+            Message::new("table_index.toast_compression")
+                .arg("table", &name)
+                .arg("toast", table.toast_size_bytes)
+                .arg("writes_per_row", format!("{:.1}", writes_per_row(table)))
+                .arg(
+                    "extra",
+                    ByteSize((table.toast_size_bytes.0 as f64 * LZ4_EXTRA_STORAGE) as i64),
+                )
+                .arg("ddl", ddl),
+        );
+    }
+
+    if candidates > 0 && default.current_value == "pglz" {
+        add_suggestion(
+            results,
+            "default_toast_compression",
+            &default.current_value,
+            "lz4",
+            SuggestionLevel::Recommended,
+            Message::new("table_index.default_toast_compression").arg("tables", candidates),
+        );
+    }
+
+    Ok(())
+}
+
+fn writes_per_row(table: &ToastTable) -> f64 {
+    table.writes as f64 / table.live_tuples.max(1) as f64
+}
+
+fn is_write_heavy(table: &ToastTable) -> bool {
+    table.toast_size_bytes.0 >= MIN_TOAST_BYTES && writes_per_row(table) >= WRITE_HEAVY_RATIO
+}
+
+fn add_suggestion(
+    results: &mut AnalysisResults,
+    parameter: &str,
+    current_value: &str,
+    suggested_value: &str,
+    level: SuggestionLevel,
+    rationale: Message,
+) {
+    results
+        .suggestions_by_category
+        .entry(ConfigCategory::TableIndex)
+        .or_default()
+        .push(ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: current_value.to_string(),
+            suggested_value: suggested_value.to_string(),
+            level,
+            rationale: rationale.render(Lang::En),
+            messages: vec![rationale],
+            fingerprint: String::new(),
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ParamValue;
+    use rstest::rstest;
+
+    const GB: i64 = 1024 * 1024 * 1024;
+
+    fn default_compression(value: &str) -> HashMap<String, PgConfigParam> {
+        let param = PgConfigParam {
+            name: "default_toast_compression".to_string(),
+            current_value: value.to_string(),
+            default_value: None,
+            unit: None,
+            context: "user".to_string(),
+            value: ParamValue::parse(value, "enum", None),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        };
+        HashMap::from([(param.name.clone(), param)])
+    }
+
+    fn column(name: &str, type_name: &str, compression: Option<&str>) -> ToastColumn {
+        ToastColumn {
+            name: name.to_string(),
+            type_name: type_name.to_string(),
+            compression: compression.map(str::to_string),
+        }
+    }
+
+    fn stats(lz4_available: bool, writes: i64) -> SystemStats {
+        SystemStats {
+            toast: Some(ToastCompression {
+                lz4_available,
+                tables: vec![ToastTable {
+                    schema: "public".to_string(),
+                    table_name: "events".to_string(),
+                    toast_size_bytes: ByteSize(20 * GB),
+                    live_tuples: 1_000_000,
+                    writes,
+                    columns: vec![
+                        column("payload", "jsonb", None),
+                        column("body", "text", Some("lz4")),
+                        column("note", "text", Some("pglz")),
+                    ],
+                }],
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn parameters(params: &HashMap<String, PgConfigParam>, stats: &SystemStats) -> Vec<String> {
+        let mut results = AnalysisResults::default();
+        analyze_toast_compression(params, stats, &mut results).unwrap();
+        results
+            .suggestions_by_category
+            .get(&ConfigCategory::TableIndex)
+            .into_iter()
+            .flatten()
+            .map(|suggestion| {
+                format!(
+                    "{}: {} -> {}",
+                    suggestion.parameter, suggestion.current_value, suggestion.suggested_value
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pglz_columns_of_write_heavy_tables_move_to_lz4() {
+        let mut results = AnalysisResults::default();
+        analyze_toast_compression(
+            &default_compression("pglz"),
+            &stats(true, 3_000_000),
+            &mut results,
+        )
+        .unwrap();
+
+        let suggestions = &results.suggestions_by_category[&ConfigCategory::TableIndex];
+        assert_eq!(
+            suggestions[0].current_value,
+            "pglz (payload jsonb, note text)"
+        );
+        assert!(suggestions[0].rationale.contains("ALTER TABLE public.events ALTER COLUMN payload SET COMPRESSION lz4, ALTER COLUMN note SET COMPRESSION lz4"));
+        assert!(suggestions[0].rationale.contains("about 2 GB more"));
+        assert_eq!(suggestions[1].parameter, "default_toast_compression");
+    }
+
+    #[rstest]
+    #[case("lz4", true, 3_000_000, &["table public.events compression: pglz (note text) -> lz4"])]
+    #[case("pglz", true, 500_000, &[])]
+    #[case("pglz", false, 3_000_000, &[])]
+    fn only_write_heavy_tables_on_lz4_builds_are_flagged(
+        #[case] default: &str,
+        #[case] lz4_available: bool,
+        #[case] writes: i64,
+        #[case] expected: &[&str],
+    ) {
+        assert_eq!(
+            parameters(&default_compression(default), &stats(lz4_available, writes)),
+            expected
+        );
+    }
+}
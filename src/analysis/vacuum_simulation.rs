@@ -0,0 +1,379 @@
+use crate::analysis::param_value;
+use crate::analysis::sampling::counter_delta;
+use crate::config::parse_duration;
+use crate::models::{
+    AnalysisResults, ByteSize, ConfigCategory, ParamValue, PgConfigParam, Seconds,
+};
+use serde::Serialize;
+use snafu::{ensure, OptionExt, Snafu};
+use std::collections::HashMap;
+
+/// Heap page size the cost model charges per page
+const PAGE_SIZE: f64 = 8192.0;
+/// Tables shown, busiest first
+const MAX_SIMULATED_TABLES: usize = 10;
+
+#[derive(Debug, Snafu)]
+pub enum SimulationError {
+    #[snafu(display(
+        "Snapshot {} has no vacuum activity; it was written by an older PostGreat or without table health checks",
+        index
+    ))]
+    MissingActivity { index: usize },
+
+    #[snafu(display("The last snapshot must be taken after the first"))]
+    EmptyWindow,
+}
+
+/// The autovacuum settings the cost model depends on
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct AutovacuumSettings {
+    /// Cost units all autovacuum workers share per `cost_delay_ms`
+    pub cost_limit: f64,
+    pub cost_delay_ms: f64,
+    pub scale_factor: f64,
+    pub threshold: f64,
+    pub max_workers: usize,
+    pub naptime: Seconds,
+    pub page_miss_cost: f64,
+    pub page_dirty_cost: f64,
+}
+
+/// What-if values that replace the settings and suggestions of the snapshot
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AutovacuumOverrides {
+    pub cost_limit: Option<f64>,
+    pub cost_delay_ms: Option<f64>,
+    pub scale_factor: Option<f64>,
+}
+
+/// One autovacuum cycle of a table under a set of settings
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct VacuumCycle {
+    /// Dead tuples that trigger autovacuum
+    pub trigger_dead_tuples: f64,
+    /// How long the table takes to accumulate `trigger_dead_tuples`; `None` when
+    /// it created no dead tuples during the window
+    pub fill_time: Option<Seconds>,
+    /// Time one pass takes under the cost limit, if it had the budget to itself
+    pub vacuum_duration: Seconds,
+    /// Dead tuples reached before the pass that cleans them up finishes
+    pub peak_dead_tuples: f64,
+    /// A pass finishes before the next one is due
+    pub keeps_up: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TableVacuumSimulation {
+    pub schema: String,
+    pub table_name: String,
+    pub table_size_bytes: ByteSize,
+    pub dead_tuples_per_sec: f64,
+    pub before: VacuumCycle,
+    pub after: VacuumCycle,
+}
+
+/// How the current and the suggested autovacuum settings cope with the dead
+/// tuples observed between two snapshots
+#[derive(Debug, Clone, Serialize)]
+pub struct AutovacuumSimulation {
+    pub window: Seconds,
+    pub before: AutovacuumSettings,
+    pub after: AutovacuumSettings,
+    pub tables: Vec<TableVacuumSimulation>,
+    /// Share of the shared cost budget the simulated tables need; above 1.0
+    /// autovacuum falls further behind every cycle
+    pub before_load: f64,
+    pub after_load: f64,
+}
+
+impl AutovacuumSettings {
+    /// Reads the settings from pg_settings, resolving `-1` to the `vacuum_*`
+    /// value it inherits
+    pub fn from_params(params: &HashMap<String, PgConfigParam>) -> Self {
+        let cost_limit = match param_value(params, "autovacuum_vacuum_cost_limit")
+            .and_then(ParamValue::as_f64)
+        {
+            Some(limit) if limit > 0.0 => limit,
+            _ => number(params, "vacuum_cost_limit").unwrap_or(200.0),
+        };
+        let cost_delay_ms = millis(params, "autovacuum_vacuum_cost_delay")
+            .or_else(|| millis(params, "vacuum_cost_delay"))
+            .unwrap_or(2.0);
+
+        Self {
+            cost_limit,
+            cost_delay_ms,
+            scale_factor: number(params, "autovacuum_vacuum_scale_factor").unwrap_or(0.2),
+            threshold: number(params, "autovacuum_vacuum_threshold").unwrap_or(50.0),
+            max_workers: param_value(params, "autovacuum_max_workers")
+                .and_then(ParamValue::as_count)
+                .unwrap_or(3),
+            naptime: Seconds(
+                param_value(params, "autovacuum_naptime")
+                    .and_then(ParamValue::as_duration)
+                    .map(|naptime| naptime.as_secs_f64())
+                    .unwrap_or(60.0),
+            ),
+            page_miss_cost: number(params, "vacuum_cost_page_miss").unwrap_or(2.0),
+            page_dirty_cost: number(params, "vacuum_cost_page_dirty").unwrap_or(20.0),
+        }
+    }
+
+    /// The settings with PostGreat's autovacuum suggestions applied
+    pub fn with_suggestions(mut self, results: &AnalysisResults) -> Self {
+        let suggestions = results
+            .suggestions_by_category
+            .get(&ConfigCategory::Autovacuum)
+            .into_iter()
+            .flatten();
+        for suggestion in suggestions {
+            let value = suggestion.suggested_value.split_whitespace().next();
+            let number = value.and_then(|value| value.parse::<f64>().ok());
+            match (suggestion.parameter.as_str(), number) {
+                ("autovacuum_vacuum_cost_limit", Some(limit)) => self.cost_limit = limit,
+                ("autovacuum_vacuum_scale_factor", Some(factor)) => self.scale_factor = factor,
+                ("autovacuum_max_workers", Some(workers)) => self.max_workers = workers as usize,
+                ("autovacuum_naptime", _) => {
+                    if let Some(naptime) = value.and_then(|value| parse_duration(value).ok()) {
+                        self.naptime = Seconds(naptime.as_secs_f64());
+                    }
+                }
+                _ => {}
+            }
+        }
+        self
+    }
+
+    pub fn with_overrides(mut self, overrides: &AutovacuumOverrides) -> Self {
+        self.cost_limit = overrides.cost_limit.unwrap_or(self.cost_limit);
+        self.cost_delay_ms = overrides.cost_delay_ms.unwrap_or(self.cost_delay_ms);
+        self.scale_factor = overrides.scale_factor.unwrap_or(self.scale_factor);
+        self
+    }
+
+    /// Cost units per second the workers share; `None` when throttling is off
+    fn cost_budget_per_sec(&self) -> Option<f64> {
+        (self.cost_delay_ms > 0.0).then(|| self.cost_limit / (self.cost_delay_ms / 1000.0))
+    }
+
+    /// Models a pass as reading every heap page once and dirtying one page per
+    /// dead tuple. Index cleanup is left out, so real passes take longer.
+    fn cycle(&self, live_tuples: f64, table_size_bytes: f64, rate: f64) -> VacuumCycle {
+        let trigger = self.threshold + self.scale_factor * live_tuples;
+        let pages = (table_size_bytes / PAGE_SIZE).max(1.0);
+        let cost = pages * self.page_miss_cost + trigger.min(pages) * self.page_dirty_cost;
+        let duration = self
+            .cost_budget_per_sec()
+            .map(|budget| cost / budget)
+            .unwrap_or(0.0);
+        let fill_time = (rate > 0.0).then(|| trigger / rate);
+
+        VacuumCycle {
+            trigger_dead_tuples: trigger,
+            fill_time: fill_time.map(Seconds),
+            vacuum_duration: Seconds(duration),
+            peak_dead_tuples: trigger + rate * (duration + self.naptime.0),
+            keeps_up: fill_time.is_none_or(|fill_time| duration <= fill_time),
+        }
+    }
+}
+
+/// Replays the dead tuples created between the first and last snapshot against
+/// the current autovacuum settings and the suggested ones.
+///
+/// Settings and suggestions come from the last snapshot; `overrides` replace
+/// the suggested values for what-if runs.
+pub fn simulate_autovacuum(
+    snapshots: &[AnalysisResults],
+    overrides: &AutovacuumOverrides,
+) -> Result<AutovacuumSimulation, SimulationError> {
+    let (Some(first), Some(last)) = (snapshots.first(), snapshots.last()) else {
+        return EmptyWindowSnafu.fail();
+    };
+    let start = first
+        .vacuum_activity
+        .as_ref()
+        .context(MissingActivitySnafu { index: 1usize })?;
+    let end = last
+        .vacuum_activity
+        .as_ref()
+        .context(MissingActivitySnafu {
+            index: snapshots.len(),
+        })?;
+    let window = end.captured_at.0 - start.captured_at.0;
+    ensure!(window > 0.0, EmptyWindowSnafu);
+
+    let before = AutovacuumSettings::from_params(&last.params);
+    let after = before.with_suggestions(last).with_overrides(overrides);
+
+    let mut tables: Vec<_> = end
+        .tables
+        .iter()
+        .filter_map(|table| {
+            let baseline = start
+                .tables
+                .iter()
+                .find(|t| t.schema == table.schema && t.table_name == table.table_name)?;
+            let created = counter_delta(table.dead_tuples_created, baseline.dead_tuples_created);
+            let rate = created as f64 / window;
+            let live = table.live_tuples as f64;
+            let size = table.table_size_bytes.0 as f64;
+            Some(TableVacuumSimulation {
+                schema: table.schema.clone(),
+                table_name: table.table_name.clone(),
+                table_size_bytes: table.table_size_bytes,
+                dead_tuples_per_sec: rate,
+                before: before.cycle(live, size, rate),
+                after: after.cycle(live, size, rate),
+            })
+        })
+        .collect();
+    tables.sort_by(|a, b| b.dead_tuples_per_sec.total_cmp(&a.dead_tuples_per_sec));
+    tables.truncate(MAX_SIMULATED_TABLES);
+
+    let load = |cycle: fn(&TableVacuumSimulation) -> &VacuumCycle| {
+        tables
+            .iter()
+            .map(cycle)
+            .filter_map(|cycle| cycle.fill_time.map(|fill| cycle.vacuum_duration.0 / fill.0))
+            .sum()
+    };
+    let before_load = load(|table| &table.before);
+    let after_load = load(|table| &table.after);
+
+    Ok(AutovacuumSimulation {
+        window: Seconds(window),
+        before,
+        after,
+        tables,
+        before_load,
+        after_load,
+    })
+}
+
+fn number(params: &HashMap<String, PgConfigParam>, name: &str) -> Option<f64> {
+    param_value(params, name).and_then(ParamValue::as_f64)
+}
+
+/// A non-negative `ms` setting; `-1` parses as a plain number and is skipped
+fn millis(params: &HashMap<String, PgConfigParam>, name: &str) -> Option<f64> {
+    param_value(params, name)
+        .and_then(ParamValue::as_duration)
+        .map(|delay| delay.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConfigSuggestion, SuggestionLevel, TableVacuumActivity, VacuumActivity};
+
+    fn param(name: &str, setting: &str, vartype: &str, unit: Option<&str>) -> PgConfigParam {
+        PgConfigParam {
+            name: name.to_string(),
+            current_value: setting.to_string(),
+            default_value: None,
+            unit: unit.map(str::to_string),
+            context: "sighup".to_string(),
+            value: ParamValue::parse(setting, vartype, unit),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        }
+    }
+
+    fn snapshot(captured_at: f64, dead_tuples_created: i64) -> AnalysisResults {
+        let mut results = AnalysisResults::default();
+        for param in [
+            param("autovacuum_vacuum_cost_limit", "-1", "integer", None),
+            param("vacuum_cost_limit", "200", "integer", None),
+            param("autovacuum_vacuum_cost_delay", "2", "real", Some("ms")),
+            param("autovacuum_vacuum_scale_factor", "0.2", "real", None),
+            param("autovacuum_naptime", "60", "integer", Some("s")),
+        ] {
+            results.params.insert(param.name.clone(), param);
+        }
+        results.vacuum_activity = Some(VacuumActivity {
+            captured_at: Seconds(captured_at),
+            tables: vec![TableVacuumActivity {
+                schema: "public".to_string(),
+                table_name: "orders".to_string(),
+                live_tuples: 50_000_000,
+                dead_tuples: 1_000_000,
+                dead_tuples_created,
+                table_size_bytes: ByteSize(40 * 1024 * 1024 * 1024),
+            }],
+        });
+        results
+    }
+
+    #[test]
+    fn settings_inherit_vacuum_cost_limit() {
+        let settings = AutovacuumSettings::from_params(&snapshot(0.0, 0).params);
+        assert_eq!(settings.cost_limit, 200.0);
+        assert_eq!(settings.cost_delay_ms, 2.0);
+        assert_eq!(settings.naptime, Seconds(60.0));
+    }
+
+    #[test]
+    fn suggested_cost_limit_lets_autovacuum_keep_up() {
+        let mut last = snapshot(3_600.0, 36_000_000);
+        for (parameter, suggested) in [
+            ("autovacuum_vacuum_cost_limit", "2000"),
+            (
+                "autovacuum_vacuum_scale_factor",
+                "0.1 or per-table override",
+            ),
+        ] {
+            last.suggestions_by_category
+                .entry(ConfigCategory::Autovacuum)
+                .or_default()
+                .push(ConfigSuggestion {
+                    parameter: parameter.to_string(),
+                    current_value: String::new(),
+                    suggested_value: suggested.to_string(),
+                    level: SuggestionLevel::Critical,
+                    rationale: String::new(),
+                    messages: Vec::new(),
+                    fingerprint: String::new(),
+                });
+        }
+
+        let simulation =
+            simulate_autovacuum(&[snapshot(0.0, 0), last], &AutovacuumOverrides::default())
+                .unwrap();
+
+        assert_eq!(simulation.after.cost_limit, 2000.0);
+        assert_eq!(simulation.after.scale_factor, 0.1);
+        let orders = &simulation.tables[0];
+        assert_eq!(orders.dead_tuples_per_sec, 10_000.0);
+        // A 40GB pass costs ~115M units: ~19 minutes at 100k units/s, ~2 at 1M units/s
+        assert!(!orders.before.keeps_up);
+        assert!(orders.after.keeps_up);
+        assert!(simulation.before_load > 1.0);
+        assert!(simulation.after_load < simulation.before_load);
+        assert!(orders.after.peak_dead_tuples < orders.before.peak_dead_tuples);
+    }
+
+    #[test]
+    fn overrides_replace_suggestions_and_old_snapshots_are_rejected() {
+        let overrides = AutovacuumOverrides {
+            cost_limit: Some(800.0),
+            ..AutovacuumOverrides::default()
+        };
+        let simulation =
+            simulate_autovacuum(&[snapshot(0.0, 0), snapshot(60.0, 600)], &overrides).unwrap();
+        assert_eq!(simulation.after.cost_limit, 800.0);
+        assert_eq!(simulation.tables[0].dead_tuples_per_sec, 10.0);
+
+        let mut old = snapshot(0.0, 0);
+        old.vacuum_activity = None;
+        let err = simulate_autovacuum(&[old, snapshot(60.0, 600)], &overrides).unwrap_err();
+        assert!(matches!(err, SimulationError::MissingActivity { index: 1 }));
+        let err =
+            simulate_autovacuum(&[snapshot(60.0, 0), snapshot(60.0, 0)], &overrides).unwrap_err();
+        assert!(matches!(err, SimulationError::EmptyWindow));
+    }
+}
@@ -0,0 +1,187 @@
+use crate::i18n::Message;
+use crate::models::{unit_bytes, unit_seconds, AnalysisResults, PgConfigParam, SystemStats};
+use std::collections::HashMap;
+
+/// Largest shared_buffers worth suggesting on a 32-bit build; the whole
+/// postmaster has to fit in a few GB of address space
+const SHARED_BUFFERS_32BIT_MAX_BYTES: f64 = 2.0 * 1024.0 * 1024.0 * 1024.0;
+
+/// Keeps suggested values inside what the server will accept.
+///
+/// Each suggestion is converted into the parameter's own unit and compared with
+/// `min_val`/`max_val` from pg_settings, plus known platform limits. Values out of
+/// range are clamped to the nearest bound and the rationale says so, so an
+/// `ALTER SYSTEM` built from the report never fails on an invalid value.
+pub fn validate_suggestions(
+    params: &HashMap<String, PgConfigParam>,
+    stats: &SystemStats,
+    results: &mut AnalysisResults,
+) {
+    for suggestion in results.suggestions_by_category.values_mut().flatten() {
+        let Some(param) = params.get(&suggestion.parameter) else {
+            continue;
+        };
+        let Some(suggested) = in_param_unit(&suggestion.suggested_value, param) else {
+            continue;
+        };
+
+        let mut max = bound(param.max_value.as_deref());
+        let mut max_key = "note.clamped_max";
+        if param.name == "shared_buffers" && stats.server_bits == Some(32) {
+            let limit = SHARED_BUFFERS_32BIT_MAX_BYTES
+                / param.unit.as_deref().and_then(unit_bytes).unwrap_or(1.0);
+            if max.is_none_or(|max| limit < max) {
+                max = Some(limit);
+                max_key = "note.clamped_32bit";
+            }
+        }
+
+        let clamped = match (bound(param.min_value.as_deref()), max) {
+            (Some(min), _) if suggested < min => Some((min, "note.clamped_min")),
+            (_, Some(max)) if suggested > max => Some((max, max_key)),
+            _ => None,
+        };
+        if let Some((value, key)) = clamped {
+            let formatted = format_in_unit(value, param.unit.as_deref());
+            suggestion.push_note(
+                Message::new(key)
+                    .arg("value", &formatted)
+                    .arg("parameter", &param.name)
+                    .arg("suggested", &suggestion.suggested_value),
+            );
+            suggestion.suggested_value = formatted;
+        }
+    }
+}
+
+/// Pointer width of the server build, from the text of `version()`
+pub(crate) fn server_bits(version: &str) -> Option<u32> {
+    if version.contains("64-bit") {
+        Some(64)
+    } else if version.contains("32-bit") {
+        Some(32)
+    } else {
+        None
+    }
+}
+
+fn bound(value: Option<&str>) -> Option<f64> {
+    value.and_then(|value| value.trim().parse::<f64>().ok())
+}
+
+/// Converts a suggested value such as `4096MB`, `30s` or `0.9` into a number in
+/// the parameter's unit
+pub(crate) fn in_param_unit(suggested: &str, param: &PgConfigParam) -> Option<f64> {
+    let suggested = suggested.trim();
+    let split = suggested
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .unwrap_or(suggested.len());
+    let number = suggested[..split].parse::<f64>().ok()?;
+    let suffix = suggested[split..].trim();
+    if suffix.is_empty() {
+        return Some(number);
+    }
+
+    let unit = param.unit.as_deref()?;
+    if let (Some(from), Some(to)) = (unit_bytes(suffix), unit_bytes(unit)) {
+        return Some(number * from / to);
+    }
+    if let (Some(from), Some(to)) = (unit_seconds(suffix), unit_seconds(unit)) {
+        return Some(number * from / to);
+    }
+    None
+}
+
+/// Formats a bound so `ALTER SYSTEM` reads it in the parameter's unit; block
+/// units such as `8kB` cannot be written as a suffix, so those stay bare
+fn format_in_unit(value: f64, unit: Option<&str>) -> String {
+    let number = if value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        value.to_string()
+    };
+    match unit {
+        Some(unit) if !unit.starts_with(|c: char| c.is_ascii_digit()) => format!("{number}{unit}"),
+        _ => number,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConfigCategory, ConfigSuggestion, ParamValue, SuggestionLevel};
+    use rstest::rstest;
+
+    fn param(name: &str, unit: Option<&str>, min: &str, max: &str) -> PgConfigParam {
+        PgConfigParam {
+            name: name.to_string(),
+            current_value: min.to_string(),
+            default_value: None,
+            unit: unit.map(str::to_string),
+            context: "postmaster".to_string(),
+            value: ParamValue::parse(min, "", unit),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: Some(min.to_string()),
+            max_value: Some(max.to_string()),
+        }
+    }
+
+    fn validate(param: PgConfigParam, suggested: &str, server_bits: Option<u32>) -> String {
+        let mut results = AnalysisResults::default();
+        results.suggestions_by_category.insert(
+            ConfigCategory::Memory,
+            vec![ConfigSuggestion {
+                parameter: param.name.clone(),
+                current_value: param.current_value.clone(),
+                suggested_value: suggested.to_string(),
+                level: SuggestionLevel::Important,
+                rationale: String::new(),
+                messages: Vec::new(),
+                fingerprint: String::new(),
+            }],
+        );
+        let params = HashMap::from([(param.name.clone(), param)]);
+        let stats = SystemStats {
+            server_bits,
+            ..SystemStats::default()
+        };
+
+        validate_suggestions(&params, &stats, &mut results);
+        results.suggestions_by_category[&ConfigCategory::Memory][0]
+            .suggested_value
+            .clone()
+    }
+
+    #[rstest]
+    #[case::within_range(param("max_connections", None, "1", "262143"), "400", "400")]
+    #[case::above_max(
+        param("autovacuum_max_workers", None, "1", "262143"),
+        "300000",
+        "262143"
+    )]
+    #[case::below_min(param("checkpoint_timeout", Some("s"), "30", "86400"), "10s", "30s")]
+    #[case::converts_units(param("wal_buffers", Some("8kB"), "-1", "262143"), "4GB", "262143")]
+    #[case::kilobyte_bound(param("work_mem", Some("kB"), "64", "2147483647"), "32kB", "64kB")]
+    #[case::text_value(param("max_connections", None, "1", "100"), "on", "on")]
+    fn clamps_to_server_range(
+        #[case] param: PgConfigParam,
+        #[case] suggested: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(validate(param, suggested, Some(64)), expected);
+    }
+
+    #[test]
+    fn shared_buffers_is_capped_on_32_bit_builds() {
+        let shared_buffers = || param("shared_buffers", Some("8kB"), "16", "1073741823");
+
+        assert_eq!(validate(shared_buffers(), "8192MB", Some(64)), "8192MB");
+        assert_eq!(validate(shared_buffers(), "8192MB", Some(32)), "262144");
+        assert_eq!(
+            server_bits("PostgreSQL 16.4 on x86_64-pc-linux-gnu, compiled by gcc, 64-bit"),
+            Some(64)
+        );
+    }
+}
@@ -0,0 +1,340 @@
+use crate::i18n::{Lang, Message};
+use crate::models::{
+    AnalysisResults, ConfigCategory, ConfigSuggestion, PgConfigParam, SuggestionLevel,
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer};
+use snafu::{ResultExt, Snafu};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Snafu)]
+pub enum ReleaseDataError {
+    #[snafu(display("Failed to read release data from {}: {}", path.display(), source))]
+    ReleaseDataRead {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Failed to parse release data {}: {}", path.display(), source))]
+    ReleaseDataParse {
+        path: PathBuf,
+        source: serde_yaml::Error,
+    },
+
+    #[snafu(display("Invalid date '{}' in release data; expected YYYY-MM-DD", value))]
+    ReleaseDataDate { value: String },
+}
+
+/// Release data older than this is reported as possibly missing newer minors
+const STALE_RELEASE_DATA_DAYS: i64 = 183;
+
+/// Newest minor release and end-of-life date of each major branch, as of
+/// [`EMBEDDED_AS_OF`]
+const EMBEDDED_RELEASES: &[(&str, u32, &str)] = &[
+    ("18", 1, "2030-11-14"),
+    ("17", 7, "2029-11-08"),
+    ("16", 11, "2028-11-09"),
+    ("15", 15, "2027-11-11"),
+    ("14", 20, "2026-11-12"),
+    ("13", 23, "2025-11-13"),
+    ("12", 22, "2024-11-21"),
+    ("11", 22, "2023-11-09"),
+    ("10", 23, "2022-11-10"),
+    ("9.6", 24, "2021-11-11"),
+];
+const EMBEDDED_AS_OF: &str = "2025-11-13";
+
+/// PostgreSQL major branches with their newest minor release and EOL date
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseTable {
+    /// When the table was last brought up to date
+    pub as_of: NaiveDate,
+    pub releases: Vec<Release>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    /// `17`, or `9.6` for branches before 10
+    pub major: String,
+    pub latest_minor: u32,
+    pub eol: NaiveDate,
+}
+
+/// On-disk layout of a `--release-data` file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ReleaseDataFile {
+    as_of: String,
+    releases: Vec<ReleaseEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ReleaseEntry {
+    /// YAML reads `17` as a number and `9.6` as a float; either is accepted
+    #[serde(deserialize_with = "deserialize_major")]
+    major: String,
+    latest_minor: u32,
+    eol: String,
+}
+
+impl Default for ReleaseTable {
+    /// The release data shipped with this build
+    fn default() -> Self {
+        Self {
+            as_of: date(EMBEDDED_AS_OF).expect("embedded date is valid"),
+            releases: EMBEDDED_RELEASES
+                .iter()
+                .map(|(major, latest_minor, eol)| Release {
+                    major: major.to_string(),
+                    latest_minor: *latest_minor,
+                    eol: date(eol).expect("embedded date is valid"),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ReleaseTable {
+    /// Loads a YAML or JSON release table, e.g. to pick up minors released
+    /// after this build
+    pub fn from_file(path: &Path) -> Result<Self, ReleaseDataError> {
+        let contents = fs::read_to_string(path).context(ReleaseDataReadSnafu { path })?;
+        let file: ReleaseDataFile =
+            serde_yaml::from_str(&contents).context(ReleaseDataParseSnafu { path })?;
+        Ok(Self {
+            as_of: date(&file.as_of)?,
+            releases: file
+                .releases
+                .into_iter()
+                .map(|entry| {
+                    Ok(Release {
+                        major: entry.major,
+                        latest_minor: entry.latest_minor,
+                        eol: date(&entry.eol)?,
+                    })
+                })
+                .collect::<Result<_, ReleaseDataError>>()?,
+        })
+    }
+
+    fn find(&self, major: &str) -> Option<&Release> {
+        self.releases.iter().find(|release| release.major == major)
+    }
+
+    /// Newest branch that is still supported on `today`
+    fn newest_supported(&self, today: NaiveDate) -> Option<&Release> {
+        self.releases
+            .iter()
+            .filter(|release| release.eol > today)
+            .max_by_key(|release| major_sort_key(&release.major))
+    }
+}
+
+/// Flags servers behind the newest minor release of their branch, and branches
+/// past their end of life, using `server_version_num`
+pub fn analyze_version(
+    params: &HashMap<String, PgConfigParam>,
+    releases: &ReleaseTable,
+    today: NaiveDate,
+    results: &mut AnalysisResults,
+) {
+    let Some((major, minor)) = params
+        .get("server_version_num")
+        .and_then(|param| split_version_num(&param.current_value))
+    else {
+        return;
+    };
+    if (today - releases.as_of).num_days() > STALE_RELEASE_DATA_DAYS {
+        results.warnings.push(format!(
+            "Release data is from {}; newer minor releases are not known. Pass --release-data with a current table.",
+            releases.as_of
+        ));
+    }
+    let Some(release) = releases.find(&major) else {
+        return;
+    };
+    let current = format!("{major}.{minor}");
+
+    if minor < release.latest_minor {
+        let latest = format!("{major}.{}", release.latest_minor);
+        let rationale = Message::new("version.minor_release")
+            .arg("current", &current)
+            .arg("latest", &latest)
+            .arg("behind", release.latest_minor - minor)
+            .arg("major", &major);
+        add_suggestion(results, "server_version", &current, &latest, rationale);
+    }
+
+    if release.eol <= today {
+        let newest = releases.newest_supported(today).map_or_else(
+            || "a supported major version".to_string(),
+            |r| r.major.clone(),
+        );
+        let rationale = Message::new("version.eol_major")
+            .arg("major", &major)
+            .arg("eol", release.eol)
+            .arg("newest", &newest);
+        add_suggestion(
+            results,
+            &format!("major version {major}"),
+            &current,
+            &newest,
+            rationale,
+        );
+    }
+}
+
+fn add_suggestion(
+    results: &mut AnalysisResults,
+    parameter: &str,
+    current_value: &str,
+    suggested_value: &str,
+    rationale: Message,
+) {
+    results
+        .suggestions_by_category
+        .entry(ConfigCategory::Version)
+        .or_default()
+        .push(ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: current_value.to_string(),
+            suggested_value: suggested_value.to_string(),
+            level: SuggestionLevel::Important,
+            rationale: rationale.render(Lang::En),
+            messages: vec![rationale],
+            fingerprint: String::new(),
+        });
+}
+
+/// `170007` -> (`17`, 7); `90624` -> (`9.6`, 24)
+fn split_version_num(value: &str) -> Option<(String, u32)> {
+    let num: u32 = value.trim().parse().ok()?;
+    if num >= 100_000 {
+        Some(((num / 10_000).to_string(), num % 10_000))
+    } else {
+        Some((format!("{}.{}", num / 10_000, num / 100 % 100), num % 100))
+    }
+}
+
+fn major_sort_key(major: &str) -> (u32, u32) {
+    let mut parts = major.split('.').map(|part| part.parse().unwrap_or(0));
+    (parts.next().unwrap_or(0), parts.next().unwrap_or(0))
+}
+
+fn date(value: &str) -> Result<NaiveDate, ReleaseDataError> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").map_err(|_| {
+        ReleaseDataError::ReleaseDataDate {
+            value: value.to_string(),
+        }
+    })
+}
+
+fn deserialize_major<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    match serde_yaml::Value::deserialize(deserializer)? {
+        serde_yaml::Value::Number(number) => Ok(number.to_string()),
+        serde_yaml::Value::String(major) => Ok(major),
+        other => Err(serde::de::Error::custom(format!(
+            "expected a major version such as 17 or 9.6, got {other:?}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ParamValue;
+    use rstest::rstest;
+
+    fn results_for(version_num: &str, today: &str) -> AnalysisResults {
+        let param = PgConfigParam {
+            name: "server_version_num".to_string(),
+            current_value: version_num.to_string(),
+            default_value: None,
+            unit: None,
+            context: "internal".to_string(),
+            value: ParamValue::parse(version_num, "integer", None),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        };
+        let params = HashMap::from([(param.name.clone(), param)]);
+        let mut results = AnalysisResults::default();
+        analyze_version(
+            &params,
+            &ReleaseTable::default(),
+            date(today).unwrap(),
+            &mut results,
+        );
+        results
+    }
+
+    #[rstest]
+    #[case("170007", "2026-01-10", &[])]
+    #[case("160002", "2026-01-10", &[("server_version", "16.2", "16.11")])]
+    #[case("130023", "2026-01-10", &[("major version 13", "13.23", "18")])]
+    #[case(
+        "90620",
+        "2026-01-10",
+        &[("server_version", "9.6.20", "9.6.24"), ("major version 9.6", "9.6.20", "18")]
+    )]
+    #[case("140020", "2026-01-10", &[])]
+    #[case("140020", "2026-11-12", &[("major version 14", "14.20", "18")])]
+    #[case("190000", "2026-01-10", &[])]
+    fn versions_are_compared_with_the_release_table(
+        #[case] version_num: &str,
+        #[case] today: &str,
+        #[case] expected: &[(&str, &str, &str)],
+    ) {
+        let results = results_for(version_num, today);
+        let suggestions: Vec<_> = results
+            .suggestions_by_category
+            .get(&ConfigCategory::Version)
+            .into_iter()
+            .flatten()
+            .map(|s| {
+                (
+                    s.parameter.as_str(),
+                    s.current_value.as_str(),
+                    s.suggested_value.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(suggestions, expected);
+    }
+
+    #[rstest]
+    #[case("2026-01-10", false)]
+    #[case("2026-10-16", true)]
+    fn stale_release_data_is_warned_about(#[case] today: &str, #[case] stale: bool) {
+        let results = results_for("170007", today);
+        assert_eq!(
+            results
+                .warnings
+                .iter()
+                .any(|w| w.starts_with("Release data is from 2025-11-13")),
+            stale
+        );
+    }
+
+    #[test]
+    fn release_data_file_accepts_numeric_and_dotted_majors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("releases.yaml");
+        fs::write(
+            &path,
+            "as_of: 2026-08-13\nreleases:\n  - {major: 18, latest_minor: 4, eol: 2030-11-14}\n  - {major: 9.6, latest_minor: 24, eol: 2021-11-11}\n",
+        )
+        .unwrap();
+
+        let table = ReleaseTable::from_file(&path).unwrap();
+
+        assert_eq!(table.as_of, date("2026-08-13").unwrap());
+        assert_eq!(table.find("18").map(|r| r.latest_minor), Some(4));
+        assert!(table.find("9.6").is_some());
+    }
+}
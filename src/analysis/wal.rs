@@ -1,6 +1,8 @@
 use crate::analysis::{get_param, param_value_as_gigabytes, param_value_as_seconds};
 use crate::checker::CheckerError;
+use crate::i18n::{Lang, Message};
 use crate::models::{AnalysisResults, ConfigCategory, ConfigSuggestion, SuggestionLevel};
+use crate::profile::ProfileThresholds;
 use std::collections::HashMap;
 
 type Result<T> = std::result::Result<T, CheckerError>;
@@ -30,8 +32,14 @@ fn analyze_max_wal_size(
 
         // Bursty I/O detection: if requested checkpoints (size-based) > timed checkpoints,
         // we are running out of WAL space before the timeout hits.
+        // Over a short sampling window even a couple of requested checkpoints is telling.
+        let min_requested = if stats.sample_window_secs.is_some() {
+            1
+        } else {
+            10
+        };
         if let (Some(req), Some(timed)) = (stats.checkpoints_req, stats.checkpoints_timed) {
-            if req > timed && req > 10 {
+            if req > timed && req > min_requested {
                 add_suggestion(
                     results,
                     ConfigCategory::Wal,
@@ -39,23 +47,18 @@ fn analyze_max_wal_size(
                     &current_value,
                     "Increase value",
                     SuggestionLevel::Critical,
-                    &format!(
-                        "Your system is triggering more size-based checkpoints ({}) than time-based ones ({}). \
-                         This causes 'bursty I/O' performance degradation. Increase max_wal_size significantly \
-                         until checkpoints_timed is the dominant reason for checkpoints.",
-                        req, timed
-                    ),
+                    Message::new("wal.max_wal_size.requested_checkpoints")
+                        .arg("requested", req)
+                        .arg("timed", timed),
                 );
                 return Ok(()); // Return early to avoid double suggestion
             }
         }
 
-        let recommended_gb = match stats.total_memory_gb {
-            Some(mem) if mem <= 16.0 => 4,
-            Some(mem) if mem <= 64.0 => 16,
-            Some(mem) if mem > 64.0 => 32,
-            _ => 16,
-        };
+        let recommended_gb = ProfileThresholds::memory_tier(
+            stats.profile().thresholds().max_wal_size_gb,
+            stats.total_memory_gb,
+        );
 
         if current_gb < recommended_gb {
             add_suggestion(
@@ -69,13 +72,9 @@ fn analyze_max_wal_size(
                 } else {
                     SuggestionLevel::Important
                 },
-                &format!(
-                    "max_wal_size is too low at {}GB. The default (1GB) is far too low for \
-                     production write-heavy workloads and causes frequent, size-based checkpoints. \
-                     This creates bursty I/O. Set it high enough that checkpoints are time-based, \
-                     not size-based ({}GB for your system).",
-                    current_gb, recommended_gb
-                ),
+                Message::new("wal.max_wal_size.too_low")
+                    .arg("current_gb", current_gb)
+                    .arg("recommended_gb", recommended_gb),
             );
         }
     }
@@ -105,12 +104,7 @@ fn analyze_min_wal_size(
                 &current_value,
                 &format!("{}GB", recommended_gb),
                 SuggestionLevel::Recommended,
-                &format!(
-                    "min_wal_size manages WAL file recycling. Setting this to {}GB ensures \
-                     enough WAL segments are kept around to handle spikes in write traffic \
-                     without needing to create new files from scratch.",
-                    recommended_gb
-                ),
+                Message::new("wal.min_wal_size").arg("recommended_gb", recommended_gb),
             );
         }
     }
@@ -126,44 +120,29 @@ fn analyze_checkpoint_timeout(
         let current_value = param.current_value.clone();
         let current_seconds = param_value_as_seconds(param).unwrap_or(0);
 
-        // Use explicit workload type or fallback to heuristic
-        let is_oltp = match stats.workload_type {
-            crate::config::WorkloadType::Oltp => true,
-            crate::config::WorkloadType::Olap => false,
-        };
+        let thresholds = stats.profile().thresholds();
+        let recommendation = thresholds.checkpoint_timeout_target;
 
-        let recommendation = if is_oltp {
-            "5min - 10min"
-        } else {
-            "15min - 30min"
-        };
-
-        if is_oltp {
-            if current_seconds < 300 {
-                add_suggestion(
+        if current_seconds < thresholds.checkpoint_timeout_min_secs {
+            match stats.profile().workload_type() {
+                crate::config::WorkloadType::Oltp => add_suggestion(
                     results,
                     ConfigCategory::Wal,
                     "checkpoint_timeout",
                     &current_value,
                     recommendation,
                     SuggestionLevel::Important,
-                    "checkpoint_timeout is too short. Shorter timeouts cause more frequent \
-                     checkpoints, increasing I/O load. For OLTP, 5-10min is a good balance.",
-                );
-            }
-        } else {
-            // OLAP
-            if current_seconds < 900 {
-                add_suggestion(
+                    Message::new("wal.checkpoint_timeout").arg("recommended", recommendation),
+                ),
+                crate::config::WorkloadType::Olap => add_suggestion(
                     results,
                     ConfigCategory::Wal,
                     "checkpoint_timeout",
                     &current_value,
                     recommendation,
                     SuggestionLevel::Recommended,
-                    "For OLAP/batch workloads, consider increasing checkpoint_timeout to 15-30min \
-                     to reduce the number of checkpoints during long-running operations.",
-                );
+                    Message::new("wal.checkpoint_timeout.analytics"),
+                ),
             }
         }
     }
@@ -178,7 +157,7 @@ fn analyze_checkpoint_completion_target(
 ) -> Result<()> {
     if let Some(param) = get_param(params, "checkpoint_completion_target") {
         let current_value = param.current_value.clone();
-        let current = current_value.parse::<f64>().unwrap_or(0.5);
+        let current = param.value.as_f64().unwrap_or(0.5);
 
         if (current - 0.9).abs() > 0.1 {
             let level = if current == 0.5 {
@@ -194,10 +173,7 @@ fn analyze_checkpoint_completion_target(
                 &current_value,
                 "0.9",
                 level,
-                "checkpoint_completion_target is the I/O smoothing knob. The default 0.5 \
-                 creates a high-intensity I/O spike during checkpoints. Setting it to 0.9 \
-                 spreads the same amount of I/O over 90% of the checkpoint interval, \
-                 creating low, slow, continuous background writes.",
+                Message::new("wal.checkpoint_completion_target"),
             );
         }
     }
@@ -212,14 +188,16 @@ fn add_suggestion(
     current_value: &str,
     suggested_value: &str,
     level: SuggestionLevel,
-    rationale: &str,
+    rationale: Message,
 ) {
     let suggestion = ConfigSuggestion {
         parameter: parameter.to_string(),
         current_value: current_value.to_string(),
         suggested_value: suggested_value.to_string(),
         level,
-        rationale: rationale.to_string(),
+        rationale: rationale.render(Lang::En),
+        messages: vec![rationale],
+        fingerprint: String::new(),
     };
 
     results
@@ -233,7 +211,7 @@ fn add_suggestion(
 mod tests {
     use super::*;
     use crate::config::WorkloadType;
-    use crate::models::{PgConfigParam, SystemStats};
+    use crate::models::{ParamValue, PgConfigParam, SystemStats};
     use std::collections::HashMap;
 
     fn create_param(value: &str, unit: Option<&str>) -> PgConfigParam {
@@ -243,6 +221,12 @@ mod tests {
             default_value: None,
             unit: unit.map(|u| u.to_string()),
             context: "user".to_string(),
+            value: ParamValue::parse(value, "", unit),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
         }
     }
 
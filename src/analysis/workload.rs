@@ -1,21 +1,121 @@
+use crate::analysis::catalog_cache::{CachedIndex, CatalogCache};
+use crate::analysis::extended_stats::{self, EqualityGroup};
+use crate::analysis::extensions;
+use crate::analysis::logs::LoggedStatement;
+use crate::analysis::partial_indexes::{self, PredicateUsage, TableIndex};
+use crate::analysis::query_fingerprint;
 use crate::analysis::query_parser::{
-    parse_query_columns, QueryColumnUsage, TableColumnUsage, TableRef,
+    parse_query_columns, ConstantPredicate, ExpressionFilter, JsonbOperator, PaginationOffset,
+    QueryColumnUsage, SpatialFilter, TableColumnUsage, TableRef, TextSearchFilter,
 };
+use crate::analysis::replicas::ReplicaConnection;
+use crate::analysis::table_index::quote_ident;
+use crate::ascii;
+use crate::catalog::{self, Catalog};
 use crate::checker::CheckerError;
+use crate::fixture::Fixture;
 use crate::models::{
-    IndexIssueKind, QueryIndexCandidate, QueryIndexEvidence, SlowQueryGroup, SlowQueryInfo,
-    SlowQueryKind, WorkloadCoverageStats, WorkloadFindingConfidence, WorkloadMetadata,
-    WorkloadResults,
+    ApplicationPatternFinding, ApplicationPatternKind, ByteSize, IndexIssueKind, IndexUsageInfo,
+    QueryIndexCandidate, QueryIndexEvidence, RoleWorkloadSummary, SlowQueryGroup, SlowQueryInfo,
+    SlowQueryKind, TableIndexHealth, WorkloadCoverageStats, WorkloadFindingConfidence,
+    WorkloadMetadata, WorkloadResults,
 };
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
 use sqlx::{query_scalar, Error, Pool, Postgres, Row};
 use std::collections::HashMap;
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct WorkloadOptions {
     pub limit: usize,
     pub min_calls: i64,
     pub max_query_len: usize,
     pub include_full_query: bool,
+    pub group_by: WorkloadGroupBy,
+    /// Replace literals and object names in reported query text
+    pub redact_queries: bool,
+    /// Slow-query groups to produce, in order; empty means every available group
+    pub rank_by: Vec<SlowQueryRank>,
+    /// Fetch only as many statements as the groups list and skip the index
+    /// catalog and the table sampling behind index and statistics suggestions
+    pub gentle: bool,
+}
+
+impl WorkloadOptions {
+    /// Largest number of statements any slow-query group may list
+    fn max_group_limit(&self) -> usize {
+        self.rank_by
+            .iter()
+            .filter_map(|rank| rank.limit)
+            .fold(self.limit, usize::max)
+    }
+}
+
+/// One `--rank-by` entry: a slow-query group and an optional limit overriding `--limit`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlowQueryRank {
+    pub kind: SlowQueryKind,
+    pub limit: Option<usize>,
+}
+
+impl FromStr for SlowQueryRank {
+    type Err = String;
+
+    /// Parses `metric` or `metric=limit`, e.g. `total=50`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (metric, limit) = match value.split_once('=') {
+            Some((metric, limit)) => {
+                let limit = limit
+                    .trim()
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|limit| *limit > 0)
+                    .ok_or_else(|| format!("invalid limit '{limit}' for '{metric}'"))?;
+                (metric, Some(limit))
+            }
+            None => (value, None),
+        };
+        let metric = metric.trim().to_ascii_lowercase();
+        let kind = RANK_NAMES
+            .iter()
+            .find(|(name, _)| *name == metric)
+            .map(|(_, kind)| *kind)
+            .ok_or_else(|| {
+                format!(
+                    "unknown metric '{metric}' (expected total, mean, reads, temp, wal, io or cpu)"
+                )
+            })?;
+        Ok(Self { kind, limit })
+    }
+}
+
+/// `--rank-by` names for each slow-query group
+const RANK_NAMES: [(&str, SlowQueryKind); 7] = [
+    ("total", SlowQueryKind::TotalTime),
+    ("mean", SlowQueryKind::MeanTime),
+    ("reads", SlowQueryKind::SharedBlksRead),
+    ("temp", SlowQueryKind::TempBlksWritten),
+    ("wal", SlowQueryKind::WalBytes),
+    ("io", SlowQueryKind::IoBound),
+    ("cpu", SlowQueryKind::CpuBound),
+];
+
+fn rank_name(kind: SlowQueryKind) -> &'static str {
+    RANK_NAMES
+        .iter()
+        .find(|(_, candidate)| *candidate == kind)
+        .map_or("unknown", |(name, _)| name)
+}
+
+/// How `pg_stat_statements` entries are split into slow-query rows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum WorkloadGroupBy {
+    /// One row per statement, summed over every role that ran it
+    #[default]
+    Query,
+    /// One row per statement and role, plus the time spent by each role
+    Role,
 }
 
 impl Default for WorkloadOptions {
@@ -25,11 +125,15 @@ impl Default for WorkloadOptions {
             min_calls: 10,
             max_query_len: 200,
             include_full_query: false,
+            group_by: WorkloadGroupBy::Query,
+            redact_queries: false,
+            rank_by: Vec::new(),
+            gentle: false,
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct StatementStat {
     queryid: i64,
     query: String,
@@ -43,14 +147,92 @@ struct StatementStat {
     temp_blks_read: i64,
     temp_blks_written: i64,
     wal_bytes: Option<i64>,
+    wal_fpi: Option<i64>,
+    io_time_ms: Option<f64>,
+    roles: Vec<String>,
+    database: Option<String>,
+}
+
+impl StatementStat {
+    fn from_logged(statement: &LoggedStatement) -> Self {
+        Self {
+            queryid: statement.id,
+            query: statement.query.clone(),
+            calls: statement.calls,
+            total_time_ms: statement.total_time_ms,
+            mean_time_ms: if statement.calls > 0 {
+                statement.total_time_ms / statement.calls as f64
+            } else {
+                0.0
+            },
+            max_time_ms: statement.max_time_ms,
+            rows: 0,
+            shared_blks_read: 0,
+            shared_blks_hit: 0,
+            temp_blks_read: 0,
+            temp_blks_written: statement.temp_bytes / TEMP_BLOCK_BYTES,
+            wal_bytes: None,
+            wal_fpi: None,
+            io_time_ms: None,
+            roles: statement.roles.clone(),
+            database: statement.database.clone(),
+        }
+    }
+
+    /// Adds another entry's counters to this one, keeping this entry's text
+    fn absorb(&mut self, other: &StatementStat) {
+        self.calls += other.calls;
+        self.rows += other.rows;
+        self.total_time_ms += other.total_time_ms;
+        self.max_time_ms = self.max_time_ms.max(other.max_time_ms);
+        self.mean_time_ms = if self.calls > 0 {
+            self.total_time_ms / self.calls as f64
+        } else {
+            0.0
+        };
+        self.shared_blks_read += other.shared_blks_read;
+        self.shared_blks_hit += other.shared_blks_hit;
+        self.temp_blks_read += other.temp_blks_read;
+        self.temp_blks_written += other.temp_blks_written;
+        self.wal_bytes = sum_optional(self.wal_bytes, other.wal_bytes);
+        self.wal_fpi = sum_optional(self.wal_fpi, other.wal_fpi);
+        self.io_time_ms = match (self.io_time_ms, other.io_time_ms) {
+            (Some(left), Some(right)) => Some(left + right),
+            (left, right) => left.or(right),
+        };
+        for role in &other.roles {
+            if !self.roles.contains(role) {
+                self.roles.push(role.clone());
+            }
+        }
+        self.roles.sort();
+    }
+}
+
+/// Size of the blocks `temp_blks_written` counts, so logged temp file sizes
+/// rank alongside `pg_stat_statements` counters
+const TEMP_BLOCK_BYTES: i64 = 8192;
+
+fn sum_optional(left: Option<i64>, right: Option<i64>) -> Option<i64> {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(left + right),
+        (left, right) => left.or(right),
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
-struct TimeColumns {
-    total: &'static str,
-    max: &'static str,
+pub(crate) struct TimeColumns {
+    pub total: &'static str,
+    pub max: &'static str,
+    /// Block read and write time columns, only set when `track_io_timing` is on
+    pub io: Option<(&'static str, &'static str)>,
 }
 
+/// I/O share of a statement's time at or above which it is ranked as I/O bound
+const IO_BOUND_SHARE: f64 = 0.5;
+/// I/O share at or below which a statement is ranked as CPU bound
+const CPU_BOUND_SHARE: f64 = 0.2;
+
 #[derive(Debug, Clone, Default)]
 struct WorkloadMetadataSnapshot {
     server_version: Option<i64>,
@@ -83,7 +265,7 @@ impl WorkloadAnalysis {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 enum PgStatStatementsAvailability {
     Available,
     Unavailable { warning: String },
@@ -93,27 +275,42 @@ enum PgStatStatementsAvailability {
 struct StatementKey {
     queryid: i64,
     query: String,
+    roles: Vec<String>,
 }
 
 const RECENT_STATS_RESET_WARNING_WINDOW_SECS: f64 = 24.0 * 60.0 * 60.0;
 const PARSE_FAILURE_WARNING_RATIO: f64 = 0.10;
 const PARSE_FAILURE_WARNING_MIN: usize = 3;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct IndexDefinition {
     schema: String,
     table: String,
+    index_name: String,
     access_method: String,
     key_columns: Vec<String>,
+    /// Whether each key column is stored `DESC`, aligned with `key_columns`
+    key_descending: Vec<bool>,
+    /// First key as `pg_get_indexdef` renders it, the expression for expression indexes
+    leading_key: Option<String>,
+    /// `pg_get_expr` of the index predicate for partial indexes
+    predicate: Option<String>,
+    size_bytes: i64,
+    is_unique: bool,
     is_partial: bool,
     is_expression: bool,
     is_valid: bool,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 struct IndexCatalog {
     indexes_by_table: HashMap<String, Vec<IndexDefinition>>,
     schemas_by_table: HashMap<String, Vec<String>>,
+    /// No catalog was read, so unqualified tables are taken to be in `public`
+    /// and flagged as ambiguous
+    assume_public: bool,
+    /// PostGIS is installed, so `&&` and `ST_*` filters are on geometries
+    postgis: bool,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -121,6 +318,10 @@ struct SearchKey {
     equality_columns: Vec<String>,
     ordered_columns: Vec<String>,
     display_columns: Vec<String>,
+    /// `ORDER BY` columns, a suffix of `ordered_columns`
+    sort_columns: Vec<String>,
+    /// Subset of `sort_columns` sorted `DESC`
+    descending_columns: Vec<String>,
 }
 
 impl SearchKey {
@@ -143,9 +344,17 @@ impl SearchKey {
             equality_columns,
             ordered_columns,
             display_columns,
+            sort_columns: usage.orders.clone(),
+            descending_columns: usage.descending_orders.clone(),
         }
     }
 
+    fn is_descending(&self, column: &str) -> bool {
+        self.descending_columns
+            .iter()
+            .any(|descending| descending.eq_ignore_ascii_case(column))
+    }
+
     fn is_empty(&self) -> bool {
         self.display_columns.is_empty()
     }
@@ -153,11 +362,21 @@ impl SearchKey {
 
 pub(crate) async fn analyze(
     pool: &Pool<Postgres>,
+    replicas: &[ReplicaConnection],
     opts: &WorkloadOptions,
+    fixture: &Fixture,
+    catalog: &Catalog,
+    indexes: &CatalogCache,
 ) -> Result<WorkloadAnalysis, CheckerError> {
     let mut results = WorkloadResults::default();
 
-    match preflight_pg_stat_statements(pool).await? {
+    match fixture
+        .try_serve(
+            "pg_stat_statements_availability",
+            preflight_pg_stat_statements(pool),
+        )
+        .await?
+    {
         PgStatStatementsAvailability::Available => {}
         PgStatStatementsAvailability::Unavailable { warning } => {
             results.warnings.push(warning);
@@ -165,13 +384,40 @@ pub(crate) async fn analyze(
         }
     }
 
-    let metadata = collect_workload_metadata(pool, &mut results).await;
+    let metadata = collect_workload_metadata(pool, fixture, catalog, &mut results).await;
     results.workload_metadata = build_workload_metadata(&metadata);
+    results.workload_metadata.queries_redacted = opts.redact_queries;
     add_metadata_warnings(&metadata, &mut results);
 
-    let time_columns = resolve_time_columns(pool, &mut results, metadata.server_version).await;
+    let mut time_columns = resolve_time_columns(catalog, &mut results);
+    time_columns.io = resolve_io_time_columns(pool, fixture, catalog, &mut results).await;
 
-    let stats = fetch_statements(pool, opts, time_columns, metadata.has_wal_bytes).await?;
+    let mut stats = fixture
+        .try_serve(
+            "statements",
+            fetch_statements(pool, opts, time_columns, metadata.has_wal_bytes),
+        )
+        .await?;
+    let replica_stats = fixture.serve("replica_statements", async {
+        let mut fetched = Vec::new();
+        for replica in replicas {
+            let stats =
+                fetch_statements(&replica.pool, opts, time_columns, metadata.has_wal_bytes).await;
+            fetched.push((replica.label.clone(), stats.map_err(|err| err.to_string())));
+        }
+        fetched
+    });
+    for (label, replica_stats) in replica_stats.await {
+        match replica_stats {
+            Ok(replica_stats) => {
+                merge_replica_statements(&mut stats, replica_stats, opts.group_by);
+                results.workload_metadata.replica_sources.push(label);
+            }
+            Err(err) => results
+                .warnings
+                .push(format!("Skipped replica {label} in workload totals: {err}")),
+        }
+    }
     if stats.is_empty() {
         results
             .warnings
@@ -179,9 +425,51 @@ pub(crate) async fn analyze(
         return Ok(WorkloadAnalysis::available(results));
     }
 
+    if opts.group_by == WorkloadGroupBy::Role {
+        let mut pools = vec![pool];
+        pools.extend(
+            replicas
+                .iter()
+                .filter(|replica| {
+                    results
+                        .workload_metadata
+                        .replica_sources
+                        .contains(&replica.label)
+                })
+                .map(|replica| &replica.pool),
+        );
+        add_role_summaries(&pools, time_columns, &mut results).await;
+    }
+
     results.slow_query_groups = build_slow_query_groups(&stats, opts);
+    for rank in &opts.rank_by {
+        if !results
+            .slow_query_groups
+            .iter()
+            .any(|group| group.kind == rank.kind)
+        {
+            results.warnings.push(format!(
+                "Skipped --rank-by {}: this server does not expose the statistics it needs (wal needs PostgreSQL 13+, io and cpu need track_io_timing).",
+                rank_name(rank.kind)
+            ));
+        }
+    }
+
+    if opts.gentle {
+        results.warnings.push(
+            "Index candidates and extended statistics suggestions skipped: --gentle does not read the index catalog or sample tables."
+                .to_string(),
+        );
+        return Ok(WorkloadAnalysis::available(results));
+    }
 
-    let index_catalog = fetch_index_catalog(pool).await?;
+    let installed_extensions = fixture
+        .serve("extensions", extensions::fetch_installed(pool))
+        .await;
+    let mut index_catalog = IndexCatalog::from_cached(indexes.indexes()?);
+    index_catalog.postgis = installed_extensions
+        .iter()
+        .any(|name| name == extensions::POSTGIS);
     let candidate_build = build_index_candidates(&stats, &index_catalog, opts);
     let mut candidates = candidate_build.candidates;
     results.parse_failures = candidate_build.coverage_stats.parser_errors;
@@ -190,6 +478,24 @@ pub(crate) async fn analyze(
     results.workload_metadata.parse_failures = candidate_build.coverage_stats.parser_errors;
     results.workload_metadata.suppressed_candidates =
         candidate_build.coverage_stats.suppressed_by_existing_index;
+    results.application_patterns = candidate_build.application_patterns;
+    if metadata.has_wal_bytes {
+        let checkpoint_counters = fixture
+            .serve(
+                "checkpoint_counters",
+                catalog::fetch_checkpoint_counters(pool, catalog),
+            )
+            .await;
+        let hypertables = installed_extensions
+            .iter()
+            .any(|name| name == extensions::TIMESCALEDB);
+        results.application_patterns.extend(wal_heavy_findings(
+            &stats,
+            (checkpoint_counters.timed, checkpoint_counters.requested),
+            hypertables,
+            opts,
+        ));
+    }
     let workload_metadata = results.workload_metadata.clone();
     add_parse_failure_warning(stats.len(), &workload_metadata, &mut results);
     candidates.sort_by(|a, b| {
@@ -200,24 +506,224 @@ pub(crate) async fn analyze(
     candidates.truncate(opts.limit);
     results.query_index_candidates = candidates;
 
+    add_missing_partial_indexes(
+        pool,
+        fixture,
+        &index_catalog,
+        &candidate_build.predicate_usages,
+        &mut results,
+    )
+    .await;
+    add_correlated_column_findings(
+        pool,
+        fixture,
+        candidate_build.equality_groups,
+        metadata.server_version,
+        opts,
+        &mut results,
+    )
+    .await;
+
     Ok(WorkloadAnalysis::available(results))
 }
 
-async fn collect_workload_metadata(
+/// Checks frequent constant predicates against existing indexes and column
+/// statistics, reporting full indexes that would shrink as partial indexes
+async fn add_missing_partial_indexes(
     pool: &Pool<Postgres>,
+    fixture: &Fixture,
+    catalog: &IndexCatalog,
+    usages: &[PredicateUsage],
     results: &mut WorkloadResults,
-) -> WorkloadMetadataSnapshot {
-    let server_version = match fetch_server_version(pool).await {
-        Ok(version) => Some(version),
+) {
+    if usages.is_empty() {
+        return;
+    }
+
+    let mut columns: Vec<(String, String, String)> = usages
+        .iter()
+        .map(|usage| {
+            (
+                usage.schema.clone(),
+                usage.table.clone(),
+                usage.predicate.column.clone(),
+            )
+        })
+        .collect();
+    columns.sort();
+    columns.dedup();
+
+    // Keyed by tuples, so served as a list of entries
+    let column_stats = fixture.try_serve("column_stats", async {
+        let mut stats: Vec<_> = partial_indexes::fetch_column_stats(pool, &columns)
+            .await?
+            .into_iter()
+            .collect();
+        stats.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(stats)
+    });
+    let column_stats: HashMap<_, _> = match column_stats.await {
+        Ok(stats) => stats.into_iter().collect(),
         Err(err) => {
-            results
-                .warnings
-                .push(format!("Failed to detect server version: {err}"));
-            None
+            results.warnings.push(format!(
+                "Skipped partial index suggestions: failed to read column statistics: {err}"
+            ));
+            return;
+        }
+    };
+
+    let table_indexes: HashMap<String, Vec<TableIndex>> = catalog
+        .indexes_by_table
+        .iter()
+        .map(|(table, indexes)| {
+            (
+                table.clone(),
+                indexes.iter().map(table_index_from_definition).collect(),
+            )
+        })
+        .collect();
+
+    results.table_health.index_usage_info.extend(
+        partial_indexes::identify_missing_partial_indexes(usages, &table_indexes, &column_stats),
+    );
+}
+
+/// At most this many equality-filter column groups are sampled per run
+const CORRELATED_COLUMNS_LIMIT: usize = 10;
+
+/// Samples the most called multi-column equality filters not covered by
+/// extended statistics and reports the groups whose columns determine each
+/// other with the `CREATE STATISTICS` that would fix their estimates
+async fn add_correlated_column_findings(
+    pool: &Pool<Postgres>,
+    fixture: &Fixture,
+    mut groups: Vec<EqualityGroup>,
+    server_version: Option<i64>,
+    opts: &WorkloadOptions,
+    results: &mut WorkloadResults,
+) {
+    if groups.is_empty() {
+        return;
+    }
+    groups.sort_by_key(|a| std::cmp::Reverse(a.calls));
+
+    let mut tables: Vec<(String, String)> = groups
+        .iter()
+        .map(|group| (group.schema.clone(), group.table.clone()))
+        .collect();
+    tables.sort();
+    tables.dedup();
+    let covered = match fixture
+        .try_serve(
+            "covered_columns",
+            extended_stats::fetch_covered_columns(pool, &tables),
+        )
+        .await
+    {
+        Ok(covered) => covered,
+        Err(err) => {
+            results.warnings.push(format!(
+                "Skipped extended statistics suggestions: failed to read existing statistics: {err}"
+            ));
+            return;
         }
     };
 
-    let query_text_visible = match fetch_query_text_visibility(pool).await {
+    let with_mcv = server_version.is_none_or(|version| version >= 120_000);
+    for group in groups
+        .into_iter()
+        .filter(|group| {
+            !covered.iter().any(|(schema, table, columns)| {
+                *schema == group.schema
+                    && *table == group.table
+                    && group.columns.iter().all(|column| columns.contains(column))
+            })
+        })
+        .take(CORRELATED_COLUMNS_LIMIT)
+    {
+        let name = format!(
+            "sample_distinct.{}.{}.{}",
+            group.schema,
+            group.table,
+            group.columns.join(".")
+        );
+        let distinct = match fixture
+            .try_serve(&name, extended_stats::sample_distinct(pool, &group))
+            .await
+        {
+            Ok(Some(distinct)) => distinct,
+            Ok(None) => continue,
+            Err(err) => {
+                results.warnings.push(format!(
+                    "Skipped correlated column check on {}.{}: {err}",
+                    group.schema, group.table
+                ));
+                continue;
+            }
+        };
+        if !distinct.is_correlated() {
+            continue;
+        }
+
+        results.application_patterns.push(ApplicationPatternFinding {
+            kind: ApplicationPatternKind::CorrelatedColumns,
+            queryid: group.queryid,
+            calls: group.statement_calls,
+            mean_time_ms: group.mean_time_ms,
+            evidence: format!(
+                "{}.{} is filtered by equality on {} in {} calls; a {}-row sample has {} distinct combinations against {} distinct values per column, so the planner's estimate for the combined filter is too low",
+                group.schema,
+                group.table,
+                group.columns.join(", "),
+                group.calls,
+                distinct.sampled,
+                distinct.combined,
+                distinct
+                    .per_column
+                    .iter()
+                    .map(i64::to_string)
+                    .collect::<Vec<_>>()
+                    .join("/"),
+            ),
+            suggestion: format!(
+                "Create extended statistics so the planner knows the columns are correlated: {}",
+                extended_stats::create_statistics_ddl(&group, with_mcv)
+            ),
+            query_text: format_query_text(&group.query, opts),
+        });
+    }
+}
+
+fn table_index_from_definition(index: &IndexDefinition) -> TableIndex {
+    TableIndex {
+        name: index.index_name.clone(),
+        key_columns: index.key_columns.clone(),
+        size_bytes: index.size_bytes,
+        predicate: index.predicate.clone(),
+        convertible: index.access_method.eq_ignore_ascii_case("btree")
+            && index.is_valid
+            && !index.is_unique
+            && !index.is_expression,
+    }
+}
+
+async fn collect_workload_metadata(
+    pool: &Pool<Postgres>,
+    fixture: &Fixture,
+    catalog: &Catalog,
+    results: &mut WorkloadResults,
+) -> WorkloadMetadataSnapshot {
+    let server_version = catalog.server_version;
+    if server_version.is_none() {
+        results
+            .warnings
+            .push("Failed to detect server version.".to_string());
+    }
+
+    let query_text_visible = match fixture
+        .try_serve("query_text_visibility", fetch_query_text_visibility(pool))
+        .await
+    {
         Ok(visible) => visible,
         Err(err) => {
             results
@@ -227,27 +733,24 @@ async fn collect_workload_metadata(
         }
     };
 
-    let has_wal_bytes = match pg_stat_statements_has_column(pool, "wal_bytes").await {
-        Ok(has_column) => has_column,
+    let has_wal_bytes = catalog.statements_have("wal_bytes");
+
+    let (stats_reset_at, seconds_since_reset, entry_deallocations) = match fixture
+        .try_serve(
+            "pg_stat_statements_info",
+            fetch_pg_stat_statements_info(pool),
+        )
+        .await
+    {
+        Ok(info) => info,
         Err(err) => {
-            results.warnings.push(format!(
-                "Failed to detect pg_stat_statements write metrics: {err}"
-            ));
-            false
+            results
+                .warnings
+                .push(format!("Failed to read pg_stat_statements_info: {err}"));
+            (None, None, None)
         }
     };
 
-    let (stats_reset_at, seconds_since_reset, entry_deallocations) =
-        match fetch_pg_stat_statements_info(pool).await {
-            Ok(info) => info,
-            Err(err) => {
-                results
-                    .warnings
-                    .push(format!("Failed to read pg_stat_statements_info: {err}"));
-                (None, None, None)
-            }
-        };
-
     WorkloadMetadataSnapshot {
         server_version,
         stats_reset_at,
@@ -328,100 +831,66 @@ async fn preflight_pg_stat_statements(
     probe_pg_stat_statements(pool).await
 }
 
+pub(crate) const PG_STAT_STATEMENTS_INSTALLED_QUERY: &str =
+    "SELECT EXISTS(SELECT 1 FROM pg_extension WHERE extname = 'pg_stat_statements')";
+
 async fn pg_stat_statements_installed(pool: &Pool<Postgres>) -> Result<bool, CheckerError> {
-    let query = "SELECT EXISTS(SELECT 1 FROM pg_extension WHERE extname = 'pg_stat_statements')";
-    query_scalar::<_, bool>(query)
+    query_scalar::<_, bool>(PG_STAT_STATEMENTS_INSTALLED_QUERY)
         .fetch_one(pool)
         .await
         .map_err(|source| CheckerError::QueryError {
-            query: query.into(),
+            query: PG_STAT_STATEMENTS_INSTALLED_QUERY.into(),
             source,
         })
 }
 
+pub(crate) const PROBE_PG_STAT_STATEMENTS_QUERY: &str = "SELECT 1 FROM pg_stat_statements LIMIT 1";
+
 async fn probe_pg_stat_statements(
     pool: &Pool<Postgres>,
 ) -> Result<PgStatStatementsAvailability, CheckerError> {
-    let query = "SELECT 1 FROM pg_stat_statements LIMIT 1";
-    match query_scalar::<_, i32>(query).fetch_optional(pool).await {
+    match query_scalar::<_, i32>(PROBE_PG_STAT_STATEMENTS_QUERY)
+        .fetch_optional(pool)
+        .await
+    {
         Ok(_) => Ok(PgStatStatementsAvailability::Available),
         Err(source) => match pg_stat_statements_unavailable_warning(&source) {
             Some(warning) => Ok(PgStatStatementsAvailability::Unavailable { warning }),
             None => Err(CheckerError::QueryError {
-                query: query.into(),
+                query: PROBE_PG_STAT_STATEMENTS_QUERY.into(),
                 source,
             }),
         },
     }
 }
 
-async fn fetch_server_version(pool: &Pool<Postgres>) -> Result<i64, CheckerError> {
-    let query = "SELECT current_setting('server_version_num')::bigint";
-    query_scalar::<_, i64>(query)
-        .fetch_one(pool)
-        .await
-        .map_err(|source| CheckerError::QueryError {
-            query: query.into(),
-            source,
-        })
-}
+pub(crate) const QUERY_TEXT_VISIBILITY_QUERY: &str = r#"
+    SELECT current_setting('is_superuser')::boolean
+        OR pg_has_role(current_user, 'pg_read_all_stats', 'MEMBER')
+"#;
 
 async fn fetch_query_text_visibility(pool: &Pool<Postgres>) -> Result<bool, CheckerError> {
-    let query = r#"
-        SELECT current_setting('is_superuser')::boolean
-            OR pg_has_role(current_user, 'pg_read_all_stats', 'MEMBER')
-    "#;
-    query_scalar::<_, bool>(query)
+    query_scalar::<_, bool>(QUERY_TEXT_VISIBILITY_QUERY)
         .fetch_one(pool)
         .await
         .map_err(|source| CheckerError::QueryError {
-            query: query.into(),
+            query: QUERY_TEXT_VISIBILITY_QUERY.into(),
             source,
         })
 }
 
-async fn detect_pg_stat_statements_version(pool: &Pool<Postgres>) -> Option<i64> {
-    pg_stat_statements_has_column(pool, "total_exec_time")
-        .await
-        .ok()
-        .filter(|exists| *exists)
-        .map(|_| 130000)
-}
-
-async fn pg_stat_statements_has_column(
-    pool: &Pool<Postgres>,
-    column_name: &str,
-) -> Result<bool, CheckerError> {
-    let query = r#"
-        SELECT EXISTS(
-            SELECT 1
-            FROM information_schema.columns
-            WHERE table_name = 'pg_stat_statements'
-              AND column_name = $1
-        )
-    "#;
-    query_scalar::<_, bool>(query)
-        .bind(column_name)
-        .fetch_one(pool)
-        .await
-        .map_err(|source| CheckerError::QueryError {
-            query: query.into(),
-            source,
-        })
-}
+pub(crate) const PG_STAT_STATEMENTS_INFO_QUERY: &str = r#"
+    SELECT
+        stats_reset::text AS stats_reset_at,
+        EXTRACT(EPOCH FROM now() - stats_reset)::double precision AS seconds_since_reset,
+        dealloc::bigint AS entry_deallocations
+    FROM pg_stat_statements_info
+"#;
 
 async fn fetch_pg_stat_statements_info(
     pool: &Pool<Postgres>,
 ) -> Result<(Option<String>, Option<f64>, Option<i64>), CheckerError> {
-    let query = r#"
-        SELECT
-            stats_reset::text AS stats_reset_at,
-            EXTRACT(EPOCH FROM now() - stats_reset)::double precision AS seconds_since_reset,
-            dealloc::bigint AS entry_deallocations
-        FROM pg_stat_statements_info
-    "#;
-
-    sqlx::query(query)
+    sqlx::query(PG_STAT_STATEMENTS_INFO_QUERY)
         .fetch_optional(pool)
         .await
         .map(|row| {
@@ -434,36 +903,66 @@ async fn fetch_pg_stat_statements_info(
             })
         })
         .map_err(|source| CheckerError::QueryError {
-            query: query.into(),
+            query: PG_STAT_STATEMENTS_INFO_QUERY.into(),
             source,
         })
 }
 
-async fn resolve_time_columns(
-    pool: &Pool<Postgres>,
-    results: &mut WorkloadResults,
-    server_version: Option<i64>,
-) -> TimeColumns {
-    let version_num = server_version
-        .or(detect_pg_stat_statements_version(pool).await)
-        .unwrap_or_else(|| {
-            results.warnings.push(
-                "Falling back to PostgreSQL 13+ timing columns for pg_stat_statements.".to_string(),
-            );
-            130000
-        });
-
-    if version_num >= 130000 {
+fn resolve_time_columns(catalog: &Catalog, results: &mut WorkloadResults) -> TimeColumns {
+    catalog.statement_time_columns().unwrap_or_else(|| {
+        results.warnings.push(
+            "Falling back to PostgreSQL 13+ timing columns for pg_stat_statements.".to_string(),
+        );
         TimeColumns {
             total: "total_exec_time",
             max: "max_exec_time",
+            io: None,
         }
-    } else {
-        TimeColumns {
-            total: "total_time",
-            max: "max_time",
+    })
+}
+
+pub(crate) const TRACK_IO_TIMING_QUERY: &str = "SELECT current_setting('track_io_timing')";
+
+/// Finds the block I/O time columns when `track_io_timing` is on; PostgreSQL 17
+/// renamed `blk_read_time` to `shared_blk_read_time`
+async fn resolve_io_time_columns(
+    pool: &Pool<Postgres>,
+    fixture: &Fixture,
+    catalog: &Catalog,
+    results: &mut WorkloadResults,
+) -> Option<(&'static str, &'static str)> {
+    let track_io_timing = fixture.try_serve("track_io_timing", async {
+        query_scalar::<_, String>(TRACK_IO_TIMING_QUERY)
+            .fetch_one(pool)
+            .await
+            .map_err(|err| CheckerError::Other {
+                message: err.to_string(),
+            })
+    });
+    let enabled = match track_io_timing.await {
+        Ok(value) => value == "on",
+        Err(err) => {
+            results
+                .warnings
+                .push(format!("Unable to read track_io_timing: {err}"));
+            return None;
         }
+    };
+    results.workload_metadata.track_io_timing = Some(enabled);
+    if !enabled {
+        results.warnings.push(
+            "track_io_timing is off, so statements cannot be split into I/O-bound and CPU-bound groups; enable it (check the clock overhead with pg_test_timing) to see whether the fix is an index, more cache or faster storage.".to_string(),
+        );
+        return None;
     }
+
+    let columns = catalog.statement_io_columns();
+    if columns.is_none() {
+        results
+            .warnings
+            .push("Unable to detect I/O timing columns in pg_stat_statements.".to_string());
+    }
+    columns
 }
 
 async fn fetch_statements(
@@ -472,18 +971,25 @@ async fn fetch_statements(
     columns: TimeColumns,
     has_wal_bytes: bool,
 ) -> Result<Vec<StatementStat>, CheckerError> {
-    let fetch_limit = (opts.limit.max(1) * 5).max(50) as i64;
-    let metrics = [
+    let floor = if opts.gentle { 0 } else { 50 };
+    let fetch_limit = (opts.max_group_limit().max(1) * 5).max(floor) as i64;
+    let mut metrics = vec![
         "total_time_ms",
         "mean_time_ms",
         "shared_blks_read",
         "temp_blks_written",
     ];
+    if has_wal_bytes {
+        metrics.push("wal_bytes");
+    }
+    if columns.io.is_some() {
+        metrics.push("io_time_ms");
+    }
 
     let mut map: HashMap<StatementKey, StatementStat> = HashMap::new();
 
     for metric_column in metrics {
-        let query = build_statement_query(columns, metric_column, has_wal_bytes);
+        let query = build_statement_query(columns, metric_column, has_wal_bytes, opts.group_by);
 
         let rows = sqlx::query(&query)
             .bind(opts.min_calls)
@@ -509,10 +1015,15 @@ async fn fetch_statements(
                 temp_blks_read: row.get("temp_blks_read"),
                 temp_blks_written: row.get("temp_blks_written"),
                 wal_bytes: row.get("wal_bytes"),
+                wal_fpi: row.get("wal_fpi"),
+                io_time_ms: row.get("io_time_ms"),
+                roles: row.get("roles"),
+                database: row.get("database"),
             };
             let key = StatementKey {
                 queryid: stat.queryid,
                 query: stat.query.clone(),
+                roles: stat.roles.clone(),
             };
             if map.contains_key(&key) {
                 continue;
@@ -525,11 +1036,230 @@ async fn fetch_statements(
     Ok(map.into_values().collect())
 }
 
-fn build_statement_query(columns: TimeColumns, metric_column: &str, has_wal_bytes: bool) -> String {
+pub(crate) fn role_summary_query(columns: TimeColumns) -> String {
+    format!(
+        r#"
+        SELECT
+            pg_get_userbyid(s.userid)::text AS role,
+            COUNT(*)::bigint AS statements,
+            SUM(s.calls)::bigint AS calls,
+            SUM(s.{total}) AS total_time_ms
+        FROM pg_stat_statements s
+        WHERE s.dbid = (SELECT oid FROM pg_database WHERE datname = current_database())
+        GROUP BY s.userid
+        "#,
+        total = columns.total
+    )
+}
+
+/// Sums statement time per role over every `pg_stat_statements` entry of the
+/// current database, not just the top-N statements
+async fn add_role_summaries(
+    pools: &[&Pool<Postgres>],
+    columns: TimeColumns,
+    results: &mut WorkloadResults,
+) {
+    let query = role_summary_query(columns);
+
+    let mut summaries: Vec<RoleWorkloadSummary> = Vec::new();
+    for pool in pools {
+        let rows = match sqlx::query(&query).fetch_all(*pool).await {
+            Ok(rows) => rows,
+            Err(err) => {
+                results
+                    .warnings
+                    .push(format!("Skipped per-role workload totals: {err}"));
+                return;
+            }
+        };
+        for row in rows {
+            let role: String = row.get("role");
+            let statements: i64 = row.get("statements");
+            let calls: i64 = row.get("calls");
+            let total_time_ms: f64 = row.get("total_time_ms");
+            match summaries.iter_mut().find(|summary| summary.role == role) {
+                Some(summary) => {
+                    summary.statements += statements;
+                    summary.calls += calls;
+                    summary.total_time_ms += total_time_ms;
+                }
+                None => summaries.push(RoleWorkloadSummary {
+                    role,
+                    statements,
+                    calls,
+                    total_time_ms,
+                    total_time_pct: 0.0,
+                }),
+            }
+        }
+    }
+
+    let total_time_ms: f64 = summaries.iter().map(|summary| summary.total_time_ms).sum();
+    for summary in &mut summaries {
+        summary.total_time_pct = total_time_pct(summary.total_time_ms, total_time_ms);
+    }
+    summaries.sort_by(|a, b| {
+        b.total_time_ms
+            .partial_cmp(&a.total_time_ms)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    results.role_summaries = summaries;
+}
+
+/// Folds a replica's statements into the primary's, matching on queryid so a query
+/// served by several nodes is ranked by its combined cost. Statements without a
+/// queryid are matched on their text.
+/// Slow-query groups, index candidates and application patterns for statements
+/// read from server logs instead of `pg_stat_statements`. Without a catalog no
+/// candidate is suppressed by an existing index.
+pub(crate) fn analyze_logged_statements(
+    statements: &[LoggedStatement],
+    opts: &WorkloadOptions,
+) -> WorkloadResults {
+    let mut results = WorkloadResults::default();
+    let stats: Vec<StatementStat> = statements
+        .iter()
+        .filter(|statement| statement.calls >= opts.min_calls)
+        .map(StatementStat::from_logged)
+        .collect();
+    if stats.is_empty() {
+        results
+            .warnings
+            .push("No logged statements matched the filters.".to_string());
+        return results;
+    }
+
+    // Logs carry no buffer counters, so only time and temp file groups mean anything
+    let mut group_opts = opts.clone();
+    if group_opts.rank_by.is_empty() {
+        group_opts.rank_by = [
+            SlowQueryKind::TotalTime,
+            SlowQueryKind::MeanTime,
+            SlowQueryKind::TempBlksWritten,
+        ]
+        .into_iter()
+        .map(|kind| SlowQueryRank { kind, limit: None })
+        .collect();
+    }
+    results.slow_query_groups = build_slow_query_groups(&stats, &group_opts);
+
+    let catalog = IndexCatalog {
+        assume_public: true,
+        ..IndexCatalog::default()
+    };
+    let candidate_build = build_index_candidates(&stats, &catalog, opts);
+    let mut candidates = candidate_build.candidates;
+    results.parse_failures = candidate_build.coverage_stats.parser_errors;
+    results.coverage_stats = candidate_build.coverage_stats.clone();
+    results.workload_metadata.parsed_queries = candidate_build.parsed_queries;
+    results.workload_metadata.parse_failures = candidate_build.coverage_stats.parser_errors;
+    results.application_patterns = candidate_build.application_patterns;
+    let workload_metadata = results.workload_metadata.clone();
+    add_parse_failure_warning(stats.len(), &workload_metadata, &mut results);
+    candidates.truncate(opts.limit);
+    results.query_index_candidates = candidates;
+    results
+}
+
+fn merge_replica_statements(
+    stats: &mut Vec<StatementStat>,
+    replica: Vec<StatementStat>,
+    group_by: WorkloadGroupBy,
+) {
+    for incoming in replica {
+        let existing = stats.iter_mut().find(|stat| {
+            let same_statement = if incoming.queryid != 0 {
+                stat.queryid == incoming.queryid
+            } else {
+                stat.queryid == 0 && stat.query == incoming.query
+            };
+            same_statement
+                && attribution_key(stat, group_by) == attribution_key(&incoming, group_by)
+        });
+
+        match existing {
+            Some(stat) => stat.absorb(&incoming),
+            None => stats.push(incoming),
+        }
+    }
+}
+
+/// Roles a statement is attributed to when rows are split per role, so the same
+/// statement run by different roles is never merged
+fn attribution_key(stat: &StatementStat, group_by: WorkloadGroupBy) -> &[String] {
+    match group_by {
+        WorkloadGroupBy::Query => &[],
+        WorkloadGroupBy::Role => &stat.roles,
+    }
+}
+
+/// A logical statement: `pg_stat_statements` entries sharing a text fingerprint
+struct FingerprintGroup {
+    /// Summed metrics, with the text and queryid of the costliest entry
+    stat: StatementStat,
+    /// queryids of the other entries folded into `stat`
+    merged_queryids: Vec<i64>,
+}
+
+/// Folds entries that differ only in literals or list lengths into one logical
+/// statement so generated variants do not crowd the top-N lists
+fn group_by_fingerprint(
+    stats: &[StatementStat],
+    group_by: WorkloadGroupBy,
+) -> Vec<FingerprintGroup> {
+    let mut entries = stats.to_vec();
+    entries.sort_by(|a, b| {
+        b.total_time_ms
+            .partial_cmp(&a.total_time_ms)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut groups: Vec<FingerprintGroup> = Vec::new();
+    let mut group_by_key: HashMap<(String, Vec<String>), usize> = HashMap::new();
+    for stat in entries {
+        let key = (
+            query_fingerprint::fingerprint(&stat.query),
+            attribution_key(&stat, group_by).to_vec(),
+        );
+        match group_by_key.get(&key) {
+            Some(&position) => {
+                let group = &mut groups[position];
+                group.stat.absorb(&stat);
+                group.merged_queryids.push(stat.queryid);
+            }
+            None => {
+                group_by_key.insert(key, groups.len());
+                groups.push(FingerprintGroup {
+                    stat,
+                    merged_queryids: Vec::new(),
+                });
+            }
+        }
+    }
+
+    groups
+}
+
+pub(crate) fn build_statement_query(
+    columns: TimeColumns,
+    metric_column: &str,
+    has_wal_bytes: bool,
+    group_by: WorkloadGroupBy,
+) -> String {
     let wal_bytes_select = if has_wal_bytes {
-        "SUM(COALESCE(s.wal_bytes, 0))::bigint AS wal_bytes,"
+        "SUM(COALESCE(s.wal_bytes, 0))::bigint AS wal_bytes,
+                SUM(COALESCE(s.wal_fpi, 0))::bigint AS wal_fpi,"
     } else {
-        "NULL::bigint AS wal_bytes,"
+        "NULL::bigint AS wal_bytes,
+                NULL::bigint AS wal_fpi,"
+    };
+    let io_time_select = match columns.io {
+        Some((read, write)) => format!("SUM(s.{read} + s.{write}) AS io_time_ms,"),
+        None => "NULL::double precision AS io_time_ms,".to_string(),
+    };
+    let role_group = match group_by {
+        WorkloadGroupBy::Query => "",
+        WorkloadGroupBy::Role => ", s.userid",
     };
 
     format!(
@@ -545,16 +1275,18 @@ fn build_statement_query(columns: TimeColumns, metric_column: &str, has_wal_byte
                 SUM(s.temp_blks_read)::bigint AS temp_blks_read,
                 SUM(s.temp_blks_written)::bigint AS temp_blks_written,
                 {wal_bytes}
+                {io_time}
                 SUM(s.{total}) AS total_time_ms,
                 CASE
                     WHEN SUM(s.calls) > 0
                         THEN SUM(s.{total}) / SUM(s.calls)::double precision
                     ELSE 0
                 END AS mean_time_ms,
-                MAX(s.{max}) AS max_time_ms
+                MAX(s.{max}) AS max_time_ms,
+                ARRAY_AGG(DISTINCT pg_get_userbyid(s.userid)::text ORDER BY pg_get_userbyid(s.userid)::text) AS roles
             FROM pg_stat_statements s
             WHERE s.dbid = (SELECT oid FROM pg_database WHERE datname = current_database())
-            GROUP BY COALESCE(s.queryid, 0)::bigint, COALESCE(s.query, '<query text unavailable>')
+            GROUP BY COALESCE(s.queryid, 0)::bigint, COALESCE(s.query, '<query text unavailable>'){role_group}
             HAVING SUM(s.calls) >= $1
         )
         SELECT
@@ -567,75 +1299,128 @@ fn build_statement_query(columns: TimeColumns, metric_column: &str, has_wal_byte
             temp_blks_read,
             temp_blks_written,
             wal_bytes,
+            wal_fpi,
+            io_time_ms,
             total_time_ms,
             mean_time_ms,
-            max_time_ms
+            max_time_ms,
+            roles,
+            current_database()::text AS database
         FROM aggregated
         ORDER BY {metric} DESC
         LIMIT $2
         "#,
         wal_bytes = wal_bytes_select,
+        io_time = io_time_select,
         total = columns.total,
         max = columns.max,
-        metric = metric_column
+        metric = metric_column,
+        role_group = role_group
     )
 }
 
 fn build_slow_query_groups(stats: &[StatementStat], opts: &WorkloadOptions) -> Vec<SlowQueryGroup> {
     let total_measured_time_ms: f64 = stats.iter().map(|stat| stat.total_time_ms).sum();
-    let groups = [
-        (SlowQueryKind::TotalTime, "total"),
-        (SlowQueryKind::MeanTime, "mean"),
-        (SlowQueryKind::SharedBlksRead, "shared_blks_read"),
-        (SlowQueryKind::TempBlksWritten, "temp_blks_written"),
-    ];
+    let has_wal_bytes = stats.iter().any(|stat| stat.wal_bytes.is_some());
+    let has_io_time = stats.iter().any(|stat| stat.io_time_ms.is_some());
+    let available = |kind: SlowQueryKind| match kind {
+        SlowQueryKind::WalBytes => has_wal_bytes,
+        SlowQueryKind::IoBound | SlowQueryKind::CpuBound => has_io_time,
+        _ => true,
+    };
+
+    let ranks: Vec<SlowQueryRank> = if opts.rank_by.is_empty() {
+        // Every group, skipping those whose columns the server does not expose
+        RANK_NAMES
+            .iter()
+            .map(|&(_, kind)| SlowQueryRank { kind, limit: None })
+            .collect()
+    } else {
+        opts.rank_by.clone()
+    };
 
+    let statements = group_by_fingerprint(stats, opts.group_by);
     let mut results = Vec::new();
-    for (kind, metric) in groups {
-        let mut entries = stats.to_vec();
-        match metric {
-            "total" => entries.sort_by(|a, b| {
-                b.total_time_ms
-                    .partial_cmp(&a.total_time_ms)
+    for rank in ranks.into_iter().filter(|rank| available(rank.kind)) {
+        let kind = rank.kind;
+        let mut entries: Vec<&FingerprintGroup> = statements.iter().collect();
+        match kind {
+            SlowQueryKind::TotalTime => entries.sort_by(|a, b| {
+                b.stat
+                    .total_time_ms
+                    .partial_cmp(&a.stat.total_time_ms)
                     .unwrap_or(std::cmp::Ordering::Equal)
             }),
-            "mean" => entries.sort_by(|a, b| {
-                b.mean_time_ms
-                    .partial_cmp(&a.mean_time_ms)
+            SlowQueryKind::MeanTime => entries.sort_by(|a, b| {
+                b.stat
+                    .mean_time_ms
+                    .partial_cmp(&a.stat.mean_time_ms)
                     .unwrap_or(std::cmp::Ordering::Equal)
             }),
-            "shared_blks_read" => {
-                entries.sort_by(|a, b| b.shared_blks_read.cmp(&a.shared_blks_read))
+            SlowQueryKind::SharedBlksRead => {
+                entries.sort_by_key(|a| std::cmp::Reverse(a.stat.shared_blks_read))
             }
-            "temp_blks_written" => {
-                entries.sort_by(|a, b| b.temp_blks_written.cmp(&a.temp_blks_written))
+            SlowQueryKind::TempBlksWritten => {
+                entries.sort_by_key(|a| std::cmp::Reverse(a.stat.temp_blks_written))
+            }
+            SlowQueryKind::WalBytes => entries.sort_by_key(|a| std::cmp::Reverse(a.stat.wal_bytes)),
+            SlowQueryKind::IoBound => {
+                entries.retain(|group| {
+                    io_time_share(&group.stat).is_some_and(|share| share >= IO_BOUND_SHARE)
+                });
+                entries.sort_by(|a, b| {
+                    b.stat
+                        .io_time_ms
+                        .partial_cmp(&a.stat.io_time_ms)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
+            }
+            SlowQueryKind::CpuBound => {
+                entries.retain(|group| {
+                    io_time_share(&group.stat).is_some_and(|share| share <= CPU_BOUND_SHARE)
+                });
+                entries.sort_by(|a, b| {
+                    non_io_time_ms(&b.stat)
+                        .partial_cmp(&non_io_time_ms(&a.stat))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
             }
-            _ => {}
         }
 
         let queries = entries
             .into_iter()
-            .take(opts.limit)
-            .map(|stat| SlowQueryInfo {
-                queryid: stat.queryid,
-                calls: stat.calls,
-                total_time_ms: stat.total_time_ms,
-                mean_time_ms: stat.mean_time_ms,
-                max_time_ms: stat.max_time_ms,
-                rows: stat.rows,
-                shared_blks_read: stat.shared_blks_read,
-                shared_blks_hit: stat.shared_blks_hit,
-                temp_blks_read: stat.temp_blks_read,
-                temp_blks_written: stat.temp_blks_written,
-                total_time_pct: total_time_pct(stat.total_time_ms, total_measured_time_ms),
-                cache_hit_ratio: cache_hit_ratio(stat.shared_blks_hit, stat.shared_blks_read),
-                temp_blks_written_per_call: per_call_i64(stat.temp_blks_written, stat.calls),
-                wal_bytes: stat.wal_bytes,
-                wal_bytes_per_call: stat
-                    .wal_bytes
-                    .and_then(|wal_bytes| per_call_i64(wal_bytes, stat.calls)),
-                query_text: format_query_text(&stat.query, opts),
-            })
+            .take(rank.limit.unwrap_or(opts.limit))
+            .map(
+                |FingerprintGroup {
+                     stat,
+                     merged_queryids,
+                 }| SlowQueryInfo {
+                    queryid: stat.queryid,
+                    calls: stat.calls,
+                    total_time_ms: stat.total_time_ms,
+                    mean_time_ms: stat.mean_time_ms,
+                    max_time_ms: stat.max_time_ms,
+                    rows: stat.rows,
+                    shared_blks_read: stat.shared_blks_read,
+                    shared_blks_hit: stat.shared_blks_hit,
+                    temp_blks_read: stat.temp_blks_read,
+                    temp_blks_written: stat.temp_blks_written,
+                    total_time_pct: total_time_pct(stat.total_time_ms, total_measured_time_ms),
+                    cache_hit_ratio: cache_hit_ratio(stat.shared_blks_hit, stat.shared_blks_read),
+                    temp_blks_written_per_call: per_call_i64(stat.temp_blks_written, stat.calls),
+                    wal_bytes: stat.wal_bytes,
+                    wal_bytes_per_call: stat
+                        .wal_bytes
+                        .and_then(|wal_bytes| per_call_i64(wal_bytes, stat.calls)),
+                    wal_fpi: stat.wal_fpi,
+                    io_time_ms: stat.io_time_ms,
+                    io_time_share: io_time_share(stat),
+                    query_text: format_query_text(&stat.query, opts),
+                    merged_queryids: merged_queryids.clone(),
+                    roles: stat.roles.clone(),
+                    database: stat.database.clone(),
+                },
+            )
             .collect();
 
         results.push(SlowQueryGroup { kind, queries });
@@ -644,11 +1429,27 @@ fn build_slow_query_groups(stats: &[StatementStat], opts: &WorkloadOptions) -> V
     results
 }
 
-fn format_query_text(query: &str, opts: &WorkloadOptions) -> String {
-    if opts.include_full_query {
+/// Share of a statement's execution time spent on block I/O. Parallel workers
+/// add their I/O time separately, so the share is capped at 1.
+fn io_time_share(stat: &StatementStat) -> Option<f64> {
+    let io_time_ms = stat.io_time_ms?;
+    (stat.total_time_ms > 0.0).then(|| (io_time_ms / stat.total_time_ms).min(1.0))
+}
+
+fn non_io_time_ms(stat: &StatementStat) -> f64 {
+    (stat.total_time_ms - stat.io_time_ms.unwrap_or(0.0)).max(0.0)
+}
+
+pub(crate) fn format_query_text(query: &str, opts: &WorkloadOptions) -> String {
+    let query = if opts.redact_queries {
+        normalize_query(&query_fingerprint::redact(query))
+    } else {
         normalize_query(query)
+    };
+    if opts.include_full_query {
+        query
     } else {
-        truncate_query(&normalize_query(query), opts.max_query_len)
+        truncate_query(&query, opts.max_query_len)
     }
 }
 
@@ -661,11 +1462,13 @@ fn truncate_query(query: &str, max_len: usize) -> String {
         return query.to_string();
     }
 
+    // Spelled out with --plain, still within max_len
+    let ellipsis = if ascii::is_plain() { "..." } else { "…" };
     let mut truncated = query
         .chars()
-        .take(max_len.saturating_sub(1))
+        .take(max_len.saturating_sub(ellipsis.chars().count()))
         .collect::<String>();
-    truncated.push('…');
+    truncated.push_str(ellipsis);
     truncated
 }
 
@@ -715,8 +1518,17 @@ struct CandidateBuildResult {
     candidates: Vec<QueryIndexCandidate>,
     coverage_stats: WorkloadCoverageStats,
     parsed_queries: usize,
+    predicate_usages: Vec<PredicateUsage>,
+    application_patterns: Vec<ApplicationPatternFinding>,
+    equality_groups: Vec<EqualityGroup>,
 }
 
+/// Calls per table, and per constant predicate on that table, keyed by `(schema, table)`
+type PredicateCalls = HashMap<(String, String), (i64, HashMap<ConstantPredicate, i64>)>;
+/// Statements filtering a table by equality on several columns, keyed by
+/// `(schema, table, columns)`
+type EqualityGroups = HashMap<(String, String, Vec<String>), EqualityGroup>;
+
 fn build_index_candidates(
     stats: &[StatementStat],
     catalog: &IndexCatalog,
@@ -725,11 +1537,19 @@ fn build_index_candidates(
     let mut deduped: HashMap<String, QueryIndexCandidate> = HashMap::new();
     let mut coverage_stats = WorkloadCoverageStats::default();
     let mut parsed_queries = 0;
+    let mut predicate_calls = PredicateCalls::new();
+    let mut application_patterns = Vec::new();
+    let mut ambiguous_schemas = Vec::new();
+    let mut equality_groups = EqualityGroups::new();
 
     for stat in stats {
         match parse_query_columns(&stat.query) {
             Ok(usage) => {
                 parsed_queries += 1;
+                record_predicate_calls(&mut predicate_calls, stat, &usage, catalog);
+                record_equality_groups(&mut equality_groups, stat, &usage, catalog);
+                application_patterns.extend(offset_pagination_finding(stat, &usage, opts));
+                ambiguous_schemas.extend(ambiguous_schema_finding(stat, &usage, catalog, opts));
                 let per_query = build_candidates_for_usage(stat, &usage, catalog);
                 merge_coverage_stats(&mut coverage_stats, &per_query.coverage_stats);
                 for candidate in per_query.candidates {
@@ -759,13 +1579,330 @@ fn build_index_candidates(
             .unwrap_or(std::cmp::Ordering::Equal)
     });
     candidates.truncate(opts.limit * 2);
+    ambiguous_schemas.sort_by_key(|a| std::cmp::Reverse(a.calls));
+    ambiguous_schemas.truncate(AMBIGUOUS_SCHEMA_LIMIT);
+    application_patterns.extend(ambiguous_schemas);
     CandidateBuildResult {
         candidates,
         coverage_stats,
         parsed_queries,
+        predicate_usages: flatten_predicate_calls(predicate_calls),
+        application_patterns,
+        equality_groups: equality_groups.into_values().collect(),
+    }
+}
+
+/// Share of the analyzed statements' WAL at or above which a writer is reported
+const WAL_HEAVY_SHARE: f64 = 0.10;
+/// At most this many WAL-heavy statements are reported
+const WAL_HEAVY_LIMIT: usize = 5;
+
+/// Reports the statements generating the most WAL with a fix matching the kind
+/// of write. `checkpoint_counters` are `(timed, requested)`; when requested
+/// checkpoints dominate, WAL volume is forcing checkpoints and the finding says so.
+/// With `hypertables` (TimescaleDB installed) deletes are pointed at chunk
+/// retention rather than native partitioning.
+fn wal_heavy_findings(
+    stats: &[StatementStat],
+    checkpoint_counters: (Option<i64>, Option<i64>),
+    hypertables: bool,
+    opts: &WorkloadOptions,
+) -> Vec<ApplicationPatternFinding> {
+    let mut statements = group_by_fingerprint(stats, WorkloadGroupBy::Query);
+    let total_wal_bytes: i64 = statements
+        .iter()
+        .filter_map(|group| group.stat.wal_bytes)
+        .sum();
+    if total_wal_bytes <= 0 {
+        return Vec::new();
+    }
+
+    let checkpoint_pressure = match checkpoint_counters {
+        (Some(timed), Some(requested)) if requested > timed => Some(format!(
+            "{requested} of {} checkpoints were requested rather than timed, so WAL volume is forcing checkpoints",
+            requested + timed
+        )),
+        _ => None,
+    };
+
+    statements.sort_by_key(|a| std::cmp::Reverse(a.stat.wal_bytes));
+    statements
+        .iter()
+        .map(|group| &group.stat)
+        .filter_map(|stat| {
+            let wal_bytes = stat.wal_bytes?;
+            let share = wal_bytes as f64 / total_wal_bytes as f64;
+            (share >= WAL_HEAVY_SHARE).then_some((stat, wal_bytes, share))
+        })
+        .take(WAL_HEAVY_LIMIT)
+        .map(|(stat, wal_bytes, share)| {
+            let mut evidence = format!(
+                "{} of WAL ({:.0}% of analyzed statements), {} per call",
+                ByteSize(wal_bytes),
+                share * 100.0,
+                ByteSize(wal_bytes / stat.calls.max(1))
+            );
+            if let Some(fpi) = stat.wal_fpi.filter(|fpi| *fpi > 0) {
+                evidence.push_str(&format!(", {fpi} full-page images"));
+            }
+            let mut suggestion = wal_write_suggestion(stat, hypertables).to_string();
+            if let Some(pressure) = &checkpoint_pressure {
+                evidence.push_str(&format!("; {pressure}"));
+                suggestion.push_str(
+                    "; raising max_wal_size also spaces checkpoints out, cutting the full-page images every first write after a checkpoint logs",
+                );
+            }
+
+            ApplicationPatternFinding {
+                kind: ApplicationPatternKind::WalHeavyWrites,
+                queryid: stat.queryid,
+                calls: stat.calls,
+                mean_time_ms: stat.mean_time_ms,
+                evidence,
+                suggestion,
+                query_text: format_query_text(&stat.query, opts),
+            }
+        })
+        .collect()
+}
+
+fn wal_write_suggestion(stat: &StatementStat, hypertables: bool) -> &'static str {
+    let verb = stat
+        .query
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match verb.as_str() {
+        "update" => {
+            "Keep updates HOT-eligible: avoid indexing frequently updated columns and lower the table's fillfactor (e.g. 80-90) so new row versions fit on the same page"
+        }
+        "insert" if stat.rows <= stat.calls => {
+            "Batch single-row inserts into multi-row INSERT ... VALUES or COPY and commit per batch"
+        }
+        "insert" => {
+            "Drop indexes on the target table that are never read; every index adds WAL for each inserted row"
+        }
+        "delete" if hypertables => {
+            "Delete in small batches, or if the table is a hypertable, drop old chunks with add_retention_policy() or drop_chunks() instead of deleting rows"
+        }
+        "delete" => {
+            "Delete in small batches, or partition the table and detach/drop old partitions instead of deleting rows"
+        }
+        _ => "Batch the writes and commit less often",
+    }
+}
+
+/// `OFFSET` at or above this many rows is always reported
+const LARGE_OFFSET_ROWS: u64 = 1_000;
+/// Buffers touched per returned row above which a normalized `OFFSET $n` is
+/// assumed to be skipping many rows
+const OFFSET_SCAN_BUFFERS_PER_ROW: f64 = 10.0;
+
+/// Flags `OFFSET` pagination that reads and discards a large number of rows per
+/// call. pg_stat_statements normalizes the offset to `$n`, so for those the
+/// buffers touched per returned row stand in for the offset size.
+fn offset_pagination_finding(
+    stat: &StatementStat,
+    usage: &QueryColumnUsage,
+    opts: &WorkloadOptions,
+) -> Option<ApplicationPatternFinding> {
+    let evidence = match usage.pagination.offset? {
+        PaginationOffset::Rows(rows) if rows >= LARGE_OFFSET_ROWS => {
+            format!("OFFSET {rows} reads and discards {rows} rows on every call")
+        }
+        PaginationOffset::Rows(_) => return None,
+        PaginationOffset::Placeholder => {
+            let buffers = (stat.shared_blks_hit + stat.shared_blks_read) as f64;
+            let buffers_per_row = buffers / stat.rows.max(1) as f64;
+            if buffers_per_row < OFFSET_SCAN_BUFFERS_PER_ROW {
+                return None;
+            }
+            format!(
+                "OFFSET is normalized to a placeholder; {buffers_per_row:.0} buffers touched per returned row suggest skipped rows are read and discarded"
+            )
+        }
+    };
+
+    let sort_columns: Vec<String> = usage.usage_by_table.values().flat_map(sort_keys).collect();
+    let suggestion = if sort_columns.is_empty() {
+        "Add a deterministic ORDER BY on a unique key and page with WHERE key > $last_seen_key LIMIT n instead of OFFSET".to_string()
+    } else {
+        format!(
+            "Use keyset pagination: keep ORDER BY {} (ending in a unique column) and filter on the last row's values, e.g. WHERE ({}) > ($last...) LIMIT n, instead of OFFSET; a matching index serves every page in constant time",
+            sort_columns.join(", "),
+            usage
+                .usage_by_table
+                .values()
+                .flat_map(|table| table.orders.iter().cloned())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    };
+
+    Some(ApplicationPatternFinding {
+        kind: ApplicationPatternKind::OffsetPagination,
+        queryid: stat.queryid,
+        calls: stat.calls,
+        mean_time_ms: stat.mean_time_ms,
+        evidence,
+        suggestion,
+        query_text: format_query_text(&stat.query, opts),
+    })
+}
+
+/// At most this many statements relying on ambiguous table names are reported
+const AMBIGUOUS_SCHEMA_LIMIT: usize = 10;
+
+/// Flags a statement naming tables without a schema when tables of that name
+/// exist in several schemas, so each session's `search_path` decides which
+/// one it reads or writes
+fn ambiguous_schema_finding(
+    stat: &StatementStat,
+    usage: &QueryColumnUsage,
+    catalog: &IndexCatalog,
+    opts: &WorkloadOptions,
+) -> Option<ApplicationPatternFinding> {
+    let mut ambiguous = Vec::new();
+    for table_ref in &usage.tables {
+        if is_internal_postgres_table(table_ref)
+            || !resolve_table_schema(table_ref, catalog).ambiguous_schema
+        {
+            continue;
+        }
+        let Some(schemas) = catalog
+            .schemas_by_table
+            .get(&table_ref.name)
+            .filter(|schemas| schemas.len() > 1)
+        else {
+            continue;
+        };
+        let entry = format!("{} ({})", table_ref.name, schemas.join(", "));
+        if !ambiguous.contains(&entry) {
+            ambiguous.push(entry);
+        }
+    }
+    if ambiguous.is_empty() {
+        return None;
+    }
+
+    Some(ApplicationPatternFinding {
+        kind: ApplicationPatternKind::AmbiguousSchema,
+        queryid: stat.queryid,
+        calls: stat.calls,
+        mean_time_ms: stat.mean_time_ms,
+        evidence: format!(
+            "Unqualified {} exist in several schemas; search_path decides which one each session uses",
+            ambiguous.join(", ")
+        ),
+        suggestion: "Schema-qualify the tables, or pin search_path for the application role with ALTER ROLE ... SET search_path so every session resolves them the same way".to_string(),
+        query_text: format_query_text(&stat.query, opts),
+    })
+}
+
+fn record_predicate_calls(
+    predicate_calls: &mut PredicateCalls,
+    stat: &StatementStat,
+    usage: &QueryColumnUsage,
+    catalog: &IndexCatalog,
+) {
+    let mut seen = Vec::new();
+    for table_ref in &usage.tables {
+        if is_internal_postgres_table(table_ref) || seen.contains(&table_ref.full_name()) {
+            continue;
+        }
+        seen.push(table_ref.full_name());
+        let resolved = resolve_table_schema(table_ref, catalog);
+        if resolved.schema == "unknown" {
+            continue;
+        }
+
+        let (table_calls, predicates) = predicate_calls
+            .entry((resolved.schema, resolved.table))
+            .or_default();
+        *table_calls += stat.calls;
+        if let Some(table_usage) = usage.usage_by_table.get(&table_ref.full_name()) {
+            for predicate in &table_usage.constant_predicates {
+                *predicates.entry(predicate.clone()).or_default() += stat.calls;
+            }
+        }
+    }
+}
+
+/// Extended statistics cover at most this many columns
+const MAX_STATISTICS_COLUMNS: usize = 8;
+
+fn record_equality_groups(
+    groups: &mut EqualityGroups,
+    stat: &StatementStat,
+    usage: &QueryColumnUsage,
+    catalog: &IndexCatalog,
+) {
+    let mut seen = Vec::new();
+    for table_ref in &usage.tables {
+        if is_internal_postgres_table(table_ref) || seen.contains(&table_ref.full_name()) {
+            continue;
+        }
+        seen.push(table_ref.full_name());
+        let Some(table_usage) = usage.usage_by_table.get(&table_ref.full_name()) else {
+            continue;
+        };
+        let mut columns = table_usage.equality_filters.clone();
+        columns.sort();
+        columns.dedup();
+        if !(2..=MAX_STATISTICS_COLUMNS).contains(&columns.len()) {
+            continue;
+        }
+        let resolved = resolve_table_schema(table_ref, catalog);
+        if resolved.schema == "unknown" {
+            continue;
+        }
+
+        let group = groups
+            .entry((
+                resolved.schema.clone(),
+                resolved.table.clone(),
+                columns.clone(),
+            ))
+            .or_insert_with(|| EqualityGroup {
+                schema: resolved.schema,
+                table: resolved.table,
+                columns,
+                calls: 0,
+                queryid: stat.queryid,
+                statement_calls: 0,
+                mean_time_ms: 0.0,
+                query: String::new(),
+            });
+        group.calls += stat.calls;
+        if stat.calls > group.statement_calls {
+            group.queryid = stat.queryid;
+            group.statement_calls = stat.calls;
+            group.mean_time_ms = stat.mean_time_ms;
+            group.query = stat.query.clone();
+        }
     }
 }
 
+fn flatten_predicate_calls(predicate_calls: PredicateCalls) -> Vec<PredicateUsage> {
+    let mut usages: Vec<PredicateUsage> = predicate_calls
+        .into_iter()
+        .flat_map(|((schema, table), (table_calls, predicates))| {
+            predicates
+                .into_iter()
+                .map(move |(predicate, calls)| PredicateUsage {
+                    schema: schema.clone(),
+                    table: table.clone(),
+                    predicate,
+                    calls,
+                    table_calls,
+                })
+        })
+        .collect();
+    usages.sort_by_key(|a| std::cmp::Reverse(a.calls));
+    usages
+}
+
 fn merge_coverage_stats(target: &mut WorkloadCoverageStats, source: &WorkloadCoverageStats) {
     target.suppressed_by_existing_index += source.suppressed_by_existing_index;
     target.skipped_internal_tables += source.skipped_internal_tables;
@@ -784,6 +1921,7 @@ fn build_candidates_for_usage(
         table_map.insert(table.full_name(), table.clone());
     }
 
+    let limited = usage.pagination.limited;
     let mut coverage_stats = WorkloadCoverageStats::default();
     let mut candidates = Vec::new();
     for (table_name, usage) in &usage.usage_by_table {
@@ -795,7 +1933,12 @@ fn build_candidates_for_usage(
         }
 
         let search_key = SearchKey::from_usage(usage);
-        if search_key.is_empty() {
+        if search_key.is_empty()
+            && usage.expression_filters.is_empty()
+            && usage.jsonb_filters.is_empty()
+            && usage.text_search_filters.is_empty()
+            && usage.spatial_filters.is_empty()
+        {
             coverage_stats.skipped_unsupported_parse_shape += 1;
             continue;
         }
@@ -806,6 +1949,48 @@ fn build_candidates_for_usage(
             continue;
         }
 
+        let gin_columns = jsonb_gin_columns(usage);
+        for filter in &usage.expression_filters {
+            if gin_columns
+                .iter()
+                .any(|gin| gin.rewritable_extractions.contains(&filter.expression))
+            {
+                continue;
+            }
+            if has_expression_index(&resolved.full_name, filter, catalog) {
+                coverage_stats.suppressed_by_existing_index += 1;
+                continue;
+            }
+            candidates.push(expression_candidate(stat, &resolved, filter));
+        }
+        for gin in &gin_columns {
+            if has_jsonb_gin_index(&resolved.full_name, gin, catalog) {
+                coverage_stats.suppressed_by_existing_index += 1;
+                continue;
+            }
+            candidates.push(jsonb_gin_candidate(stat, &resolved, gin));
+        }
+        for filter in &usage.text_search_filters {
+            if has_text_search_index(&resolved.full_name, filter, catalog) {
+                coverage_stats.suppressed_by_existing_index += 1;
+                continue;
+            }
+            candidates.push(text_search_candidate(stat, &resolved, filter));
+        }
+        // Without PostGIS, `&&` is array or range overlap, which this does not size
+        if catalog.postgis {
+            for (column, operators) in spatial_columns(&usage.spatial_filters) {
+                if has_spatial_index(&resolved.full_name, column, catalog) {
+                    coverage_stats.suppressed_by_existing_index += 1;
+                    continue;
+                }
+                candidates.push(spatial_candidate(stat, &resolved, column, &operators));
+            }
+        }
+        if search_key.is_empty() {
+            continue;
+        }
+
         let mut notes = collect_non_covering_index_notes(&resolved.full_name, &search_key, catalog);
         if resolved.ambiguous_schema {
             notes.push(
@@ -819,11 +2004,13 @@ fn build_candidates_for_usage(
         }
 
         let columns = output_columns(&search_key);
-        let reason = format_reason(usage, resolved.ambiguous_schema);
+        let reason = format_reason(usage, limited, resolved.ambiguous_schema);
         candidates.push(QueryIndexCandidate {
+            ddl: index_ddl(&resolved.schema, &resolved.table, None, &columns),
             schema: resolved.schema,
             table: resolved.table,
             columns,
+            access_method: None,
             reason,
             confidence: candidate_confidence(usage, resolved.ambiguous_schema, &notes),
             evidence: evidence_from_usage(usage),
@@ -839,6 +2026,392 @@ fn build_candidates_for_usage(
         candidates,
         coverage_stats,
         parsed_queries: 0,
+        predicate_usages: Vec::new(),
+        application_patterns: Vec::new(),
+        equality_groups: Vec::new(),
+    }
+}
+
+/// A `jsonb` column filtered with operators that need a GIN index
+struct JsonbGinColumn {
+    column: String,
+    /// `?`-style key existence needs the default `jsonb_ops`; containment alone
+    /// is served by the smaller `jsonb_path_ops`
+    needs_key_exists: bool,
+    /// `column ->> 'key' = ...` filters on the same column, which the GIN index
+    /// serves once rewritten as containment
+    rewritable_extractions: Vec<String>,
+}
+
+impl JsonbGinColumn {
+    fn operator_class(&self) -> &'static str {
+        if self.needs_key_exists {
+            "jsonb_ops"
+        } else {
+            "jsonb_path_ops"
+        }
+    }
+}
+
+fn jsonb_gin_columns(usage: &TableColumnUsage) -> Vec<JsonbGinColumn> {
+    let mut columns: Vec<JsonbGinColumn> = Vec::new();
+    for filter in &usage.jsonb_filters {
+        let needs_key_exists = filter.operator == JsonbOperator::KeyExists;
+        match columns.iter_mut().find(|gin| gin.column == filter.column) {
+            Some(gin) => gin.needs_key_exists |= needs_key_exists,
+            None => columns.push(JsonbGinColumn {
+                column: filter.column.clone(),
+                needs_key_exists,
+                rewritable_extractions: Vec::new(),
+            }),
+        }
+    }
+
+    for gin in &mut columns {
+        let extraction = format!("{} ->> ", gin.column);
+        gin.rewritable_extractions = usage
+            .expression_filters
+            .iter()
+            .filter(|filter| filter.equality && filter.expression.starts_with(&extraction))
+            .map(|filter| filter.expression.clone())
+            .collect();
+    }
+    columns
+}
+
+fn has_jsonb_gin_index(table: &str, gin: &JsonbGinColumn, catalog: &IndexCatalog) -> bool {
+    let Some(indexes) = catalog.indexes_by_table.get(table) else {
+        return false;
+    };
+
+    indexes.iter().any(|index| {
+        index.is_valid
+            && !index.is_partial
+            && index.access_method.eq_ignore_ascii_case("gin")
+            && index
+                .key_columns
+                .first()
+                .is_some_and(|column| column.eq_ignore_ascii_case(&gin.column))
+            && !(gin.needs_key_exists
+                && index
+                    .leading_key
+                    .as_deref()
+                    .is_some_and(|key| key.contains("jsonb_path_ops")))
+    })
+}
+
+fn jsonb_gin_candidate(
+    stat: &StatementStat,
+    resolved: &ResolvedTable,
+    gin: &JsonbGinColumn,
+) -> QueryIndexCandidate {
+    let operators = if gin.needs_key_exists {
+        "key existence (?, ?|, ?&)"
+    } else {
+        "containment (@>)"
+    };
+    let mut notes = vec![if gin.needs_key_exists {
+        "jsonb_path_ops is smaller but cannot serve ?, ?| or ?&, so the default operator class is needed".to_string()
+    } else {
+        "jsonb_path_ops is smaller and faster than the default jsonb_ops for containment-only queries".to_string()
+    }];
+    for extraction in &gin.rewritable_extractions {
+        notes.push(format!(
+            "rewrite {extraction} = ... as {} @> '{{\"key\": ...}}' to use this index instead of a separate expression index",
+            gin.column
+        ));
+    }
+    notes.push("assumes a jsonb column; arrays need the default GIN operator class".to_string());
+
+    special_index_candidate(
+        stat,
+        resolved,
+        SpecialIndex {
+            columns: vec![format!("{} {}", gin.column, gin.operator_class())],
+            access_method: Some("gin"),
+            reason: format!("jsonb {operators} on {}", gin.column),
+            evidence: QueryIndexEvidence {
+                non_equality_filters: vec![gin.column.clone()],
+                ..QueryIndexEvidence::default()
+            },
+            notes,
+        },
+    )
+}
+
+fn has_expression_index(table: &str, filter: &ExpressionFilter, catalog: &IndexCatalog) -> bool {
+    let Some(indexes) = catalog.indexes_by_table.get(table) else {
+        return false;
+    };
+    let wanted = partial_indexes::normalize_predicate(&filter.expression);
+
+    indexes.iter().any(|index| {
+        index.is_valid
+            && !index.is_partial
+            && index.access_method.eq_ignore_ascii_case("btree")
+            && index
+                .leading_key
+                .as_deref()
+                .is_some_and(|key| partial_indexes::normalize_predicate(key) == wanted)
+    })
+}
+
+/// Candidate for an expression index on a function-wrapped filter, which a plain
+/// index on the underlying column cannot serve
+fn expression_candidate(
+    stat: &StatementStat,
+    resolved: &ResolvedTable,
+    filter: &ExpressionFilter,
+) -> QueryIndexCandidate {
+    let mut evidence = QueryIndexEvidence::default();
+    if filter.equality {
+        evidence.equality_filters.push(filter.expression.clone());
+    } else {
+        evidence
+            .non_equality_filters
+            .push(filter.expression.clone());
+    }
+
+    let mut notes = vec![format!(
+        "a plain index on {} cannot serve this filter; index the expression, or add a STORED generated column for it and index that",
+        filter.column
+    )];
+    if filter.expression.contains('$') {
+        notes.push(
+            "pg_stat_statements replaced a literal argument with a placeholder; substitute the value the application sends"
+                .to_string(),
+        );
+    }
+    if filter
+        .expression
+        .to_ascii_lowercase()
+        .starts_with("date_trunc(")
+    {
+        notes.push(
+            "index expressions must be IMMUTABLE; date_trunc on a timestamptz column is only STABLE, so apply AT TIME ZONE first"
+                .to_string(),
+        );
+    }
+
+    special_index_candidate(
+        stat,
+        resolved,
+        SpecialIndex {
+            columns: vec![filter.expression.clone()],
+            access_method: None,
+            reason: format!("WHERE {}; expression index", filter.expression),
+            evidence,
+            notes,
+        },
+    )
+}
+
+fn has_text_search_index(table: &str, filter: &TextSearchFilter, catalog: &IndexCatalog) -> bool {
+    let Some(indexes) = catalog.indexes_by_table.get(table) else {
+        return false;
+    };
+
+    indexes.iter().any(|index| {
+        let usable = index.is_valid
+            && !index.is_partial
+            && (index.access_method.eq_ignore_ascii_case("gin")
+                || index.access_method.eq_ignore_ascii_case("gist"));
+        let leading_key = index.leading_key.as_deref().unwrap_or_default();
+        let leads_with_column = index
+            .key_columns
+            .first()
+            .is_some_and(|column| column.eq_ignore_ascii_case(filter.column()));
+
+        usable
+            && match filter {
+                TextSearchFilter::Pattern { .. } => {
+                    leads_with_column && leading_key.contains("trgm_ops")
+                }
+                TextSearchFilter::FullText { column, expression } if column == expression => {
+                    leads_with_column
+                }
+                TextSearchFilter::FullText { expression, .. } => {
+                    partial_indexes::normalize_predicate(leading_key)
+                        == partial_indexes::normalize_predicate(expression)
+                }
+            }
+    })
+}
+
+/// Candidate for a trigram or full-text GIN index on a search-style filter
+fn text_search_candidate(
+    stat: &StatementStat,
+    resolved: &ResolvedTable,
+    filter: &TextSearchFilter,
+) -> QueryIndexCandidate {
+    let evidence = QueryIndexEvidence {
+        non_equality_filters: vec![filter.column().to_string()],
+        ..QueryIndexEvidence::default()
+    };
+
+    let index = match filter {
+        TextSearchFilter::Pattern { column } => SpecialIndex {
+            columns: vec![format!("{column} gin_trgm_ops")],
+            access_method: Some("gin"),
+            reason: format!("ILIKE/regex pattern match on {column}"),
+            evidence,
+            notes: vec![
+                "requires the pg_trgm extension: CREATE EXTENSION IF NOT EXISTS pg_trgm;"
+                    .to_string(),
+                "trigram indexes only help search terms of at least 3 characters".to_string(),
+            ],
+        },
+        TextSearchFilter::FullText { column, expression } if column == expression => SpecialIndex {
+            columns: vec![column.clone()],
+            access_method: Some("gin"),
+            reason: format!("full-text match on tsvector column {column}"),
+            evidence,
+            notes: Vec::new(),
+        },
+        TextSearchFilter::FullText { column, expression } => {
+            let mut notes = Vec::new();
+            if !expression.contains(',') {
+                notes.push(format!(
+                    "to_tsvector without an explicit configuration is not IMMUTABLE and cannot be indexed; use to_tsvector('english', {column}) in both the index and the query"
+                ));
+            }
+            notes.push(
+                "the query must repeat the indexed expression exactly; a STORED generated tsvector column avoids that"
+                    .to_string(),
+            );
+            SpecialIndex {
+                columns: vec![expression.clone()],
+                access_method: Some("gin"),
+                reason: format!("{expression} computed per row without a matching GIN index"),
+                evidence,
+                notes,
+            }
+        }
+    };
+
+    special_index_candidate(stat, resolved, index)
+}
+
+/// Spatial filters grouped by column, keeping the order they appear in the query
+fn spatial_columns(filters: &[SpatialFilter]) -> Vec<(&str, Vec<&str>)> {
+    let mut columns: Vec<(&str, Vec<&str>)> = Vec::new();
+    for filter in filters {
+        let operator = filter.operator.as_str();
+        match columns
+            .iter_mut()
+            .find(|(column, _)| *column == filter.column)
+        {
+            Some((_, operators)) if !operators.contains(&operator) => operators.push(operator),
+            Some(_) => {}
+            None => columns.push((&filter.column, vec![operator])),
+        }
+    }
+    columns
+}
+
+/// Any GiST, SP-GiST or BRIN index leading with the column serves `&&` and
+/// the `ST_*` predicates
+fn has_spatial_index(table: &str, column: &str, catalog: &IndexCatalog) -> bool {
+    let Some(indexes) = catalog.indexes_by_table.get(table) else {
+        return false;
+    };
+
+    indexes.iter().any(|index| {
+        index.is_valid
+            && !index.is_partial
+            && ["gist", "spgist", "brin"]
+                .iter()
+                .any(|method| index.access_method.eq_ignore_ascii_case(method))
+            && index
+                .key_columns
+                .first()
+                .is_some_and(|key| key.eq_ignore_ascii_case(column))
+    })
+}
+
+/// Candidate for a GiST index on a geometry or geography column
+fn spatial_candidate(
+    stat: &StatementStat,
+    resolved: &ResolvedTable,
+    column: &str,
+    operators: &[&str],
+) -> QueryIndexCandidate {
+    let mut notes = vec![
+        "a B-tree cannot serve bounding-box or spatial predicates; GiST is the usual choice for geometry and geography".to_string(),
+        "SP-GiST (USING spgist) is often smaller and faster for points and other non-overlapping shapes".to_string(),
+    ];
+    if operators
+        .iter()
+        .any(|operator| operator.contains("DWithin"))
+    {
+        notes.push(format!(
+            "distance predicates use the index only when both arguments have the indexed type; casting {column} to geography in the query needs an index on ({column}::geography) instead"
+        ));
+    }
+
+    special_index_candidate(
+        stat,
+        resolved,
+        SpecialIndex {
+            columns: vec![column.to_string()],
+            access_method: Some("gist"),
+            reason: format!("spatial {} on {column}", operators.join(", ")),
+            evidence: QueryIndexEvidence {
+                non_equality_filters: vec![column.to_string()],
+                ..QueryIndexEvidence::default()
+            },
+            notes,
+        },
+    )
+}
+
+/// Index shape for candidates that a column-list B-tree cannot express
+struct SpecialIndex {
+    columns: Vec<String>,
+    access_method: Option<&'static str>,
+    reason: String,
+    evidence: QueryIndexEvidence,
+    notes: Vec<String>,
+}
+
+fn special_index_candidate(
+    stat: &StatementStat,
+    resolved: &ResolvedTable,
+    index: SpecialIndex,
+) -> QueryIndexCandidate {
+    let mut notes = index.notes;
+    let mut reason = format!("heuristic from slow query: {}", index.reason);
+    if resolved.ambiguous_schema {
+        notes.push(
+            "table name resolved to public, but another schema may contain the same table"
+                .to_string(),
+        );
+        reason.push_str("; schema ambiguous");
+    }
+
+    QueryIndexCandidate {
+        schema: resolved.schema.clone(),
+        table: resolved.table.clone(),
+        ddl: index_ddl(
+            &resolved.schema,
+            &resolved.table,
+            index.access_method,
+            &index.columns,
+        ),
+        columns: index.columns,
+        access_method: index.access_method.map(str::to_string),
+        reason,
+        confidence: if resolved.ambiguous_schema {
+            WorkloadFindingConfidence::Low
+        } else {
+            WorkloadFindingConfidence::Medium
+        },
+        evidence: index.evidence,
+        notes,
+        queryid: stat.queryid,
+        total_time_ms: stat.total_time_ms,
+        mean_time_ms: stat.mean_time_ms,
+        calls: stat.calls,
     }
 }
 
@@ -869,7 +2442,7 @@ fn append_unique(target: &mut Vec<String>, source: &[String]) {
     }
 }
 
-fn format_reason(usage: &TableColumnUsage, ambiguous_schema: bool) -> String {
+fn format_reason(usage: &TableColumnUsage, limited: bool, ambiguous_schema: bool) -> String {
     let mut parts = Vec::new();
     let mut where_columns = Vec::new();
     append_unique(&mut where_columns, &usage.equality_filters);
@@ -881,7 +2454,8 @@ fn format_reason(usage: &TableColumnUsage, ambiguous_schema: bool) -> String {
         parts.push(format!("JOIN {}", usage.equality_joins.join(", ")));
     }
     if !usage.orders.is_empty() {
-        parts.push(format!("ORDER BY {}", usage.orders.join(", ")));
+        let limit = if limited { " LIMIT" } else { "" };
+        parts.push(format!("ORDER BY {}{limit}", sort_keys(usage).join(", ")));
     }
     if ambiguous_schema {
         parts.push("schema ambiguous".to_string());
@@ -894,10 +2468,25 @@ fn evidence_from_usage(usage: &TableColumnUsage) -> QueryIndexEvidence {
         equality_filters: usage.equality_filters.clone(),
         non_equality_filters: usage.non_equality_filters.clone(),
         equality_joins: usage.equality_joins.clone(),
-        order_by: usage.orders.clone(),
+        order_by: sort_keys(usage),
     }
 }
 
+/// `ORDER BY` columns with their direction, e.g. `created_at DESC`
+fn sort_keys(usage: &TableColumnUsage) -> Vec<String> {
+    usage
+        .orders
+        .iter()
+        .map(|column| {
+            if usage.descending_orders.contains(column) {
+                format!("{column} DESC")
+            } else {
+                column.clone()
+            }
+        })
+        .collect()
+}
+
 fn candidate_confidence(
     usage: &TableColumnUsage,
     ambiguous_schema: bool,
@@ -914,12 +2503,34 @@ fn candidate_confidence(
     WorkloadFindingConfidence::High
 }
 
+/// Column entries are already SQL, e.g. `created_at DESC` or `email gin_trgm_ops`
+fn index_ddl(schema: &str, table: &str, access_method: Option<&str>, columns: &[String]) -> String {
+    let using = access_method
+        .map(|method| format!(" USING {method}"))
+        .unwrap_or_default();
+    format!(
+        "CREATE INDEX CONCURRENTLY ON {}.{}{using} ({});",
+        quote_ident(schema),
+        quote_ident(table),
+        columns.join(", ")
+    )
+}
+
 fn output_columns(search_key: &SearchKey) -> Vec<String> {
     let mut columns = search_key.display_columns.clone();
     if columns.len() > 3 {
         columns.truncate(3);
     }
     columns
+        .into_iter()
+        .map(|column| {
+            if search_key.is_descending(&column) {
+                format!("{column} DESC")
+            } else {
+                column
+            }
+        })
+        .collect()
 }
 
 fn is_index_covered(table: &str, search_key: &SearchKey, catalog: &IndexCatalog) -> bool {
@@ -1015,7 +2626,26 @@ fn btree_index_covers_search_key(index: &IndexDefinition, search_key: &SearchKey
     has_exact_prefix(
         &index.key_columns[search_key.equality_columns.len()..],
         &search_key.ordered_columns,
-    )
+    ) && index_matches_sort_directions(index, search_key)
+}
+
+/// A B-tree serves an `ORDER BY` when every sort column has the index's direction,
+/// or every one has the opposite direction (a backward scan)
+fn index_matches_sort_directions(index: &IndexDefinition, search_key: &SearchKey) -> bool {
+    let matches: Vec<bool> = search_key
+        .sort_columns
+        .iter()
+        .filter_map(|column| {
+            let position = index
+                .key_columns
+                .iter()
+                .position(|key| key.eq_ignore_ascii_case(column))?;
+            let index_descending = index.key_descending.get(position).copied().unwrap_or(false);
+            Some(index_descending == search_key.is_descending(column))
+        })
+        .collect();
+
+    matches.iter().all(|&same| same) || matches.iter().all(|&same| !same)
 }
 
 fn same_column_set(left: &[String], right: &[String]) -> bool {
@@ -1042,40 +2672,6 @@ struct ResolvedTable {
     ambiguous_schema: bool,
 }
 
-const FETCH_INDEX_CATALOG_QUERY: &str = r#"
-    SELECT
-        n.nspname AS schema_name,
-        c.relname AS table_name,
-        am.amname AS access_method,
-        (i.indpred IS NOT NULL) AS is_partial,
-        (i.indexprs IS NOT NULL) AS is_expression,
-        i.indisvalid AS is_valid,
-        COALESCE(
-            array_agg(a.attname ORDER BY arr.ord) FILTER (WHERE a.attname IS NOT NULL),
-            ARRAY[]::text[]
-        ) AS key_columns
-    FROM pg_index i
-    JOIN pg_class c ON c.oid = i.indrelid
-    JOIN pg_class idx ON idx.oid = i.indexrelid
-    JOIN pg_namespace n ON n.oid = c.relnamespace
-    JOIN pg_am am ON am.oid = idx.relam
-    LEFT JOIN LATERAL unnest(i.indkey) WITH ORDINALITY AS arr(attnum, ord)
-        ON arr.ord <= i.indnkeyatts
-    LEFT JOIN pg_attribute a
-        ON a.attrelid = c.oid
-       AND a.attnum = arr.attnum
-       AND arr.attnum > 0
-    WHERE n.nspname NOT IN ('pg_catalog', 'information_schema')
-    GROUP BY
-        n.nspname,
-        c.relname,
-        idx.relname,
-        am.amname,
-        i.indpred,
-        i.indexprs,
-        i.indisvalid
-"#;
-
 fn resolve_table_schema(table: &TableRef, catalog: &IndexCatalog) -> ResolvedTable {
     if let Some(schema) = &table.schema {
         let full_name = format!("{}.{}", schema, table.name);
@@ -1103,7 +2699,7 @@ fn resolve_table_schema(table: &TableRef, catalog: &IndexCatalog) -> ResolvedTab
         };
     }
 
-    if schemas.contains(&"public".to_string()) {
+    if schemas.contains(&"public".to_string()) || (schemas.is_empty() && catalog.assume_public) {
         return ResolvedTable {
             schema: "public".to_string(),
             table: table.name.clone(),
@@ -1120,49 +2716,69 @@ fn resolve_table_schema(table: &TableRef, catalog: &IndexCatalog) -> ResolvedTab
     }
 }
 
-async fn fetch_index_catalog(pool: &Pool<Postgres>) -> Result<IndexCatalog, CheckerError> {
-    let rows = sqlx::query(FETCH_INDEX_CATALOG_QUERY)
-        .fetch_all(pool)
-        .await
-        .map_err(|source| CheckerError::QueryError {
-            query: FETCH_INDEX_CATALOG_QUERY.into(),
-            source,
-        })?;
-
-    let mut catalog = IndexCatalog::default();
-    for row in rows {
-        let definition = IndexDefinition {
-            schema: row.get("schema_name"),
-            table: row.get("table_name"),
-            access_method: row.get("access_method"),
-            key_columns: row.get("key_columns"),
-            is_partial: row.get("is_partial"),
-            is_expression: row.get("is_expression"),
-            is_valid: row.get("is_valid"),
-        };
-
-        let full_name = format!("{}.{}", definition.schema, definition.table);
-        catalog
-            .indexes_by_table
-            .entry(full_name)
-            .or_default()
-            .push(definition.clone());
+impl IndexCatalog {
+    fn from_cached(indexes: &[CachedIndex]) -> Self {
+        let mut catalog = IndexCatalog::default();
+        for index in indexes {
+            let definition = IndexDefinition {
+                schema: index.schema.clone(),
+                table: index.table.clone(),
+                index_name: index.index_name.clone(),
+                access_method: index.access_method.clone(),
+                key_columns: index.key_columns.clone(),
+                key_descending: index.key_descending.clone(),
+                leading_key: index.leading_key.clone(),
+                predicate: index.predicate.clone(),
+                size_bytes: index.size_bytes,
+                is_unique: index.is_unique,
+                is_partial: index.is_partial,
+                is_expression: index.is_expression,
+                is_valid: index.is_valid,
+            };
 
-        let entry = catalog
-            .schemas_by_table
-            .entry(definition.table.clone())
-            .or_default();
-        if !entry.contains(&definition.schema) {
-            entry.push(definition.schema.clone());
+            let full_name = format!("{}.{}", definition.schema, definition.table);
+            let entry = catalog
+                .schemas_by_table
+                .entry(definition.table.clone())
+                .or_default();
+            if !entry.contains(&definition.schema) {
+                entry.push(definition.schema.clone());
+            }
+            catalog
+                .indexes_by_table
+                .entry(full_name)
+                .or_default()
+                .push(definition);
         }
+        catalog
     }
+}
 
-    Ok(catalog)
+/// Query-derived partial index findings supersede the name-based heuristic for
+/// the same table
+pub(crate) fn merge_index_findings(
+    table_findings: Vec<IndexUsageInfo>,
+    query_derived: Vec<IndexUsageInfo>,
+) -> Vec<IndexUsageInfo> {
+    let mut merged: Vec<_> = table_findings
+        .into_iter()
+        .filter(|index| {
+            index.issue != IndexIssueKind::MissingPartialIndex
+                || !query_derived.iter().any(|derived| {
+                    derived.schema == index.schema && derived.table_name == index.table_name
+                })
+        })
+        .collect();
+    merged.extend(query_derived);
+    merged
 }
 
-pub(crate) fn correlate_table_health(results: &mut WorkloadResults) {
-    for candidate in &mut results.query_index_candidates {
-        if results.seq_scan_info.iter().any(|table| {
+pub(crate) fn correlate_table_health(
+    candidates: &mut [QueryIndexCandidate],
+    health: &TableIndexHealth,
+) {
+    for candidate in candidates {
+        if health.seq_scan_info.iter().any(|table| {
             table.schema.eq_ignore_ascii_case(&candidate.schema)
                 && table.table_name.eq_ignore_ascii_case(&candidate.table)
         }) {
@@ -1172,7 +2788,7 @@ pub(crate) fn correlate_table_health(results: &mut WorkloadResults) {
             );
         }
 
-        if results.bloat_info.iter().any(|table| {
+        if health.bloat_info.iter().any(|table| {
             table.schema.eq_ignore_ascii_case(&candidate.schema)
                 && table.table_name.eq_ignore_ascii_case(&candidate.table)
         }) {
@@ -1182,7 +2798,7 @@ pub(crate) fn correlate_table_health(results: &mut WorkloadResults) {
             );
         }
 
-        let overlapping_unused_indexes: Vec<_> = results
+        let overlapping_unused_indexes: Vec<_> = health
             .index_usage_info
             .iter()
             .filter(|index| {
@@ -1232,6 +2848,7 @@ fn index_usage_overlaps_candidate(
 mod tests {
     use super::*;
     use crate::analysis::query_parser::{QueryColumnUsage, TableColumnUsage, TableRef};
+    use rstest::rstest;
 
     fn make_usage() -> QueryColumnUsage {
         let mut usage = QueryColumnUsage::default();
@@ -1245,39 +2862,284 @@ mod tests {
             orders: vec!["created_at".into()],
             ..TableColumnUsage::default()
         };
-        usage
-            .usage_by_table
-            .insert("public.orders".into(), table_usage);
-        usage
+        usage
+            .usage_by_table
+            .insert("public.orders".into(), table_usage);
+        usage
+    }
+
+    fn make_stat(queryid: i64, query: &str, total_time_ms: f64) -> StatementStat {
+        StatementStat {
+            queryid,
+            query: query.into(),
+            calls: 10,
+            total_time_ms,
+            mean_time_ms: total_time_ms / 10.0,
+            max_time_ms: total_time_ms / 5.0,
+            rows: 0,
+            shared_blks_read: 0,
+            shared_blks_hit: 0,
+            temp_blks_read: 0,
+            temp_blks_written: 0,
+            wal_bytes: None,
+            wal_fpi: None,
+            io_time_ms: None,
+            roles: vec!["app".into()],
+            database: Some("shop".into()),
+        }
+    }
+
+    fn make_index_definition(columns: &[&str]) -> IndexDefinition {
+        IndexDefinition {
+            schema: "public".into(),
+            table: "orders".into(),
+            index_name: format!("orders_{}_idx", columns.join("_")),
+            access_method: "btree".into(),
+            key_columns: columns.iter().map(|column| column.to_string()).collect(),
+            key_descending: vec![false; columns.len()],
+            leading_key: columns.first().map(|column| column.to_string()),
+            predicate: None,
+            size_bytes: 8192,
+            is_unique: false,
+            is_partial: false,
+            is_expression: false,
+            is_valid: true,
+        }
+    }
+
+    #[test]
+    fn replica_statements_merge_by_queryid() {
+        let mut stats = vec![make_stat(1, "SELECT * FROM orders WHERE id = $1", 100.0)];
+        let mut replica_stat = make_stat(1, "SELECT * FROM orders WHERE id = $1", 300.0);
+        replica_stat.max_time_ms = 50.0;
+        let replica_only = make_stat(2, "SELECT * FROM customers WHERE email = $1", 40.0);
+
+        merge_replica_statements(
+            &mut stats,
+            vec![replica_stat, replica_only],
+            WorkloadGroupBy::Query,
+        );
+
+        assert_eq!(stats.len(), 2);
+        let merged = &stats[0];
+        assert_eq!(merged.total_time_ms, 400.0);
+        assert_eq!(merged.calls, 20);
+        assert_eq!(merged.mean_time_ms, 20.0);
+        assert_eq!(merged.max_time_ms, 50.0);
+        assert_eq!(stats[1].queryid, 2);
+    }
+
+    #[test]
+    fn slow_queries_group_in_list_variants() {
+        let stats = vec![
+            make_stat(1, "SELECT * FROM orders WHERE id IN ($1, $2)", 100.0),
+            make_stat(2, "SELECT * FROM orders WHERE id IN ($1, $2, $3)", 300.0),
+            make_stat(3, "SELECT * FROM customers WHERE id IN ($1, $2)", 200.0),
+        ];
+
+        let groups = build_slow_query_groups(&stats, &WorkloadOptions::default());
+        let by_total = &groups[0].queries;
+
+        assert_eq!(by_total.len(), 2);
+        assert_eq!(by_total[0].queryid, 2);
+        assert_eq!(by_total[0].merged_queryids, vec![1]);
+        assert_eq!(by_total[0].total_time_ms, 400.0);
+        assert_eq!(by_total[0].calls, 20);
+        assert_eq!(by_total[1].queryid, 3);
+        assert!(by_total[1].merged_queryids.is_empty());
+    }
+
+    #[rstest]
+    #[case::timed_checkpoints((Some(40), Some(2)), false)]
+    #[case::requested_checkpoints((Some(4), Some(30)), true)]
+    fn wal_heavy_writers_get_write_specific_suggestions(
+        #[case] checkpoint_counters: (Option<i64>, Option<i64>),
+        #[case] pressure: bool,
+    ) {
+        let mut update = make_stat(1, "UPDATE orders SET status = $1 WHERE id = $2", 100.0);
+        update.wal_bytes = Some(900 * 1024 * 1024);
+        update.wal_fpi = Some(5_000);
+        let mut insert = make_stat(2, "INSERT INTO events (kind) VALUES ($1)", 100.0);
+        insert.rows = 10;
+        insert.wal_bytes = Some(95 * 1024 * 1024);
+        let mut select = make_stat(3, "SELECT * FROM orders WHERE id = $1", 100.0);
+        select.wal_bytes = Some(5 * 1024 * 1024);
+
+        let findings = wal_heavy_findings(
+            &[select, insert, update],
+            checkpoint_counters,
+            false,
+            &WorkloadOptions::default(),
+        );
+
+        assert_eq!(findings.len(), 1);
+        let finding = &findings[0];
+        assert_eq!(finding.kind, ApplicationPatternKind::WalHeavyWrites);
+        assert_eq!(finding.queryid, 1);
+        assert!(finding.evidence.starts_with("900 MB of WAL (90%"));
+        assert!(finding.evidence.contains("5000 full-page images"));
+        assert!(finding.suggestion.contains("fillfactor"));
+        assert_eq!(finding.suggestion.contains("max_wal_size"), pressure);
+    }
+
+    #[rstest]
+    #[case(false, "detach/drop old partitions")]
+    #[case(true, "drop_chunks()")]
+    fn wal_heavy_deletes_point_hypertables_at_chunk_retention(
+        #[case] hypertables: bool,
+        #[case] expected: &str,
+    ) {
+        let mut delete = make_stat(1, "DELETE FROM metrics WHERE time < $1", 100.0);
+        delete.wal_bytes = Some(500 * 1024 * 1024);
+
+        let findings = wal_heavy_findings(
+            &[delete],
+            (None, None),
+            hypertables,
+            &WorkloadOptions::default(),
+        );
+
+        assert!(findings[0].suggestion.contains(expected));
+    }
+
+    #[test]
+    fn io_timing_splits_io_bound_from_cpu_bound_statements() {
+        let mut scan = make_stat(1, "SELECT * FROM events WHERE kind = $1", 1000.0);
+        scan.io_time_ms = Some(800.0);
+        let mut aggregate = make_stat(2, "SELECT count(*) FROM orders", 600.0);
+        aggregate.io_time_ms = Some(30.0);
+        let mut mixed = make_stat(3, "SELECT * FROM customers WHERE id = $1", 400.0);
+        mixed.io_time_ms = Some(140.0);
+
+        let groups =
+            build_slow_query_groups(&[scan, aggregate, mixed], &WorkloadOptions::default());
+        let queries = |kind| {
+            groups.iter().find(|group| group.kind == kind).map(|group| {
+                group
+                    .queries
+                    .iter()
+                    .map(|query| query.queryid)
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        assert_eq!(queries(SlowQueryKind::IoBound), Some(vec![1]));
+        assert_eq!(queries(SlowQueryKind::CpuBound), Some(vec![2]));
+        assert_eq!(groups[0].queries[0].io_time_share, Some(0.8));
+    }
+
+    #[test]
+    fn statement_query_reads_io_time_when_tracked() {
+        let query = build_statement_query(
+            TimeColumns {
+                total: "total_exec_time",
+                max: "max_exec_time",
+                io: Some(("shared_blk_read_time", "shared_blk_write_time")),
+            },
+            "io_time_ms",
+            true,
+            WorkloadGroupBy::Query,
+        );
+        assert!(
+            query.contains("SUM(s.shared_blk_read_time + s.shared_blk_write_time) AS io_time_ms")
+        );
+        assert!(query.contains("ORDER BY io_time_ms DESC"));
+    }
+
+    #[rstest]
+    #[case::bounded(false, "SELECT * FROM order…")]
+    #[case::full(true, "SELECT * FROM orders ORDER BY id OFFSET 5000")]
+    fn query_text_length_applies_to_every_finding(
+        #[case] include_full_query: bool,
+        #[case] expected: &str,
+    ) {
+        let stat = make_stat(1, "SELECT *\n  FROM orders ORDER BY id OFFSET 5000", 100.0);
+        let opts = WorkloadOptions {
+            max_query_len: 20,
+            include_full_query,
+            ..WorkloadOptions::default()
+        };
+
+        let groups = build_slow_query_groups(std::slice::from_ref(&stat), &opts);
+        let build = build_index_candidates(&[stat], &orders_catalog(Vec::new()), &opts);
+
+        assert_eq!(groups[0].queries[0].query_text, expected);
+        assert_eq!(build.application_patterns[0].query_text, expected);
     }
 
-    fn make_stat(queryid: i64, query: &str, total_time_ms: f64) -> StatementStat {
-        StatementStat {
-            queryid,
-            query: query.into(),
-            calls: 10,
-            total_time_ms,
-            mean_time_ms: total_time_ms / 10.0,
-            max_time_ms: total_time_ms / 5.0,
-            rows: 0,
-            shared_blks_read: 0,
-            shared_blks_hit: 0,
-            temp_blks_read: 0,
-            temp_blks_written: 0,
-            wal_bytes: None,
-        }
+    #[rstest]
+    #[case::plain("total", SlowQueryKind::TotalTime, None)]
+    #[case::with_limit("reads=5", SlowQueryKind::SharedBlksRead, Some(5))]
+    #[case::case_and_spaces(" WAL = 3", SlowQueryKind::WalBytes, Some(3))]
+    fn rank_by_parses_metric_and_limit(
+        #[case] value: &str,
+        #[case] kind: SlowQueryKind,
+        #[case] limit: Option<usize>,
+    ) {
+        assert_eq!(value.parse(), Ok(SlowQueryRank { kind, limit }));
     }
 
-    fn make_index_definition(columns: &[&str]) -> IndexDefinition {
-        IndexDefinition {
-            schema: "public".into(),
-            table: "orders".into(),
-            access_method: "btree".into(),
-            key_columns: columns.iter().map(|column| column.to_string()).collect(),
-            is_partial: false,
-            is_expression: false,
-            is_valid: true,
-        }
+    #[rstest]
+    #[case::unknown_metric("latency")]
+    #[case::zero_limit("total=0")]
+    #[case::bad_limit("mean=lots")]
+    fn rank_by_rejects_invalid_values(#[case] value: &str) {
+        assert!(value.parse::<SlowQueryRank>().is_err());
+    }
+
+    #[test]
+    fn rank_by_selects_groups_and_limits_each() {
+        let stats: Vec<StatementStat> = (1..=5)
+            .map(|id| {
+                make_stat(
+                    id,
+                    &format!("SELECT * FROM t{id} WHERE id = $1"),
+                    id as f64 * 100.0,
+                )
+            })
+            .collect();
+        let opts = WorkloadOptions {
+            limit: 4,
+            rank_by: vec![
+                "mean=2".parse().unwrap(),
+                "total".parse().unwrap(),
+                "wal".parse().unwrap(),
+            ],
+            ..WorkloadOptions::default()
+        };
+
+        let groups = build_slow_query_groups(&stats, &opts);
+
+        let shape: Vec<(SlowQueryKind, usize)> = groups
+            .iter()
+            .map(|group| (group.kind, group.queries.len()))
+            .collect();
+        assert_eq!(
+            shape,
+            vec![(SlowQueryKind::MeanTime, 2), (SlowQueryKind::TotalTime, 4)]
+        );
+        assert_eq!(opts.max_group_limit(), 4);
+    }
+
+    #[test]
+    fn redacted_slow_queries_hide_names_and_literals() {
+        let stat = make_stat(
+            1,
+            "SELECT * FROM customers WHERE email = 'jane@example.com'",
+            100.0,
+        );
+        let opts = WorkloadOptions {
+            redact_queries: true,
+            ..WorkloadOptions::default()
+        };
+
+        let groups = build_slow_query_groups(&[stat], &opts);
+        let query_text = &groups[0].queries[0].query_text;
+
+        assert!(query_text.starts_with("SELECT * FROM n_"));
+        assert!(query_text.ends_with(" = ?"));
+        assert!(!query_text.contains("customers"));
+        assert!(!query_text.contains("jane"));
     }
 
     #[test]
@@ -1385,6 +3247,399 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn constant_predicate_calls_are_summed_per_table() {
+        let mut catalog = IndexCatalog::default();
+        catalog
+            .schemas_by_table
+            .insert("orders".into(), vec!["public".into()]);
+        let stats = [
+            make_stat(
+                1,
+                "SELECT * FROM orders WHERE customer_id = $1 AND deleted_at IS NULL",
+                100.0,
+            ),
+            make_stat(2, "SELECT * FROM orders WHERE deleted_at IS NULL", 50.0),
+            make_stat(3, "SELECT * FROM orders WHERE id = $1", 10.0),
+        ];
+
+        let build = build_index_candidates(&stats, &catalog, &WorkloadOptions::default());
+
+        assert_eq!(build.predicate_usages.len(), 1);
+        let usage = &build.predicate_usages[0];
+        assert_eq!(usage.schema, "public");
+        assert_eq!(usage.predicate.to_sql(), "deleted_at IS NULL");
+        assert_eq!(usage.calls, 20);
+        assert_eq!(usage.table_calls, 30);
+    }
+
+    #[test]
+    fn function_wrapped_filter_yields_expression_candidate() {
+        let mut catalog = IndexCatalog::default();
+        catalog
+            .schemas_by_table
+            .insert("orders".into(), vec!["public".into()]);
+        let stat = make_stat(1, "SELECT * FROM orders WHERE lower(email) = $1", 1000.0);
+
+        let build = build_index_candidates(
+            std::slice::from_ref(&stat),
+            &catalog,
+            &WorkloadOptions::default(),
+        );
+        assert_eq!(build.coverage_stats.skipped_unsupported_parse_shape, 0);
+        assert_eq!(build.candidates.len(), 1);
+        assert_eq!(build.candidates[0].columns, vec!["lower(email)"]);
+        assert!(build.candidates[0].reason.contains("expression index"));
+
+        let mut index = make_index_definition(&[]);
+        index.is_expression = true;
+        index.leading_key = Some("lower((email)::text)".into());
+        catalog
+            .indexes_by_table
+            .insert("public.orders".into(), vec![index]);
+        let build = build_index_candidates(&[stat], &catalog, &WorkloadOptions::default());
+        assert!(build.candidates.is_empty());
+        assert_eq!(build.coverage_stats.suppressed_by_existing_index, 1);
+    }
+
+    #[rstest]
+    #[case::extraction_only(
+        "SELECT * FROM events WHERE payload->>'type' = $1",
+        None,
+        "payload ->> 'type'"
+    )]
+    #[case::containment_absorbs_extraction(
+        "SELECT * FROM events WHERE payload->>'type' = $1 AND payload @> $2",
+        Some("gin"),
+        "payload jsonb_path_ops"
+    )]
+    #[case::key_existence(
+        "SELECT * FROM events WHERE payload ? $1",
+        Some("gin"),
+        "payload jsonb_ops"
+    )]
+    fn jsonb_operator_mix_picks_index_type(
+        #[case] query: &str,
+        #[case] access_method: Option<&str>,
+        #[case] column: &str,
+    ) {
+        let mut catalog = IndexCatalog::default();
+        catalog
+            .schemas_by_table
+            .insert("events".into(), vec!["public".into()]);
+
+        let build = build_index_candidates(
+            &[make_stat(1, query, 1000.0)],
+            &catalog,
+            &WorkloadOptions::default(),
+        );
+
+        assert_eq!(build.candidates.len(), 1);
+        assert_eq!(build.candidates[0].access_method.as_deref(), access_method);
+        assert_eq!(build.candidates[0].columns, vec![column]);
+    }
+
+    #[test]
+    fn existing_path_ops_gin_index_covers_containment_only() {
+        let mut catalog = IndexCatalog::default();
+        catalog
+            .schemas_by_table
+            .insert("events".into(), vec!["public".into()]);
+        let mut index = make_index_definition(&["payload"]);
+        index.table = "events".into();
+        index.access_method = "gin".into();
+        index.leading_key = Some("payload jsonb_path_ops".into());
+        catalog
+            .indexes_by_table
+            .insert("public.events".into(), vec![index]);
+
+        let containment = make_stat(1, "SELECT * FROM events WHERE payload @> $1", 1000.0);
+        let existence = make_stat(2, "SELECT * FROM events WHERE payload ? $1", 1000.0);
+        let build = build_index_candidates(
+            &[containment, existence],
+            &catalog,
+            &WorkloadOptions::default(),
+        );
+
+        assert_eq!(build.coverage_stats.suppressed_by_existing_index, 1);
+        assert_eq!(build.candidates.len(), 1);
+        assert_eq!(build.candidates[0].columns, vec!["payload jsonb_ops"]);
+    }
+
+    #[test]
+    fn ilike_yields_trigram_candidate_with_extension_prerequisite() {
+        let mut catalog = IndexCatalog::default();
+        catalog
+            .schemas_by_table
+            .insert("articles".into(), vec!["public".into()]);
+        let stat = make_stat(1, "SELECT * FROM articles WHERE title ILIKE $1", 1000.0);
+
+        let build = build_index_candidates(&[stat], &catalog, &WorkloadOptions::default());
+
+        assert_eq!(build.candidates.len(), 1);
+        let candidate = &build.candidates[0];
+        assert_eq!(candidate.access_method.as_deref(), Some("gin"));
+        assert_eq!(candidate.columns, vec!["title gin_trgm_ops"]);
+        assert_eq!(
+            candidate.ddl,
+            "CREATE INDEX CONCURRENTLY ON public.articles USING gin (title gin_trgm_ops);"
+        );
+        assert!(candidate
+            .notes
+            .iter()
+            .any(|note| note.contains("CREATE EXTENSION IF NOT EXISTS pg_trgm")));
+    }
+
+    #[test]
+    fn to_tsvector_is_flagged_unless_a_matching_gin_index_exists() {
+        let mut catalog = IndexCatalog::default();
+        catalog
+            .schemas_by_table
+            .insert("articles".into(), vec!["public".into()]);
+        let stat = make_stat(
+            1,
+            "SELECT * FROM articles WHERE to_tsvector('english', body) @@ plainto_tsquery($1)",
+            1000.0,
+        );
+
+        let build = build_index_candidates(
+            std::slice::from_ref(&stat),
+            &catalog,
+            &WorkloadOptions::default(),
+        );
+        assert_eq!(build.candidates.len(), 1);
+        assert_eq!(
+            build.candidates[0].columns,
+            vec!["to_tsvector('english', body)"]
+        );
+
+        let mut index = make_index_definition(&[]);
+        index.table = "articles".into();
+        index.access_method = "gin".into();
+        index.is_expression = true;
+        index.leading_key = Some("to_tsvector('english'::regconfig, body)".into());
+        catalog
+            .indexes_by_table
+            .insert("public.articles".into(), vec![index]);
+        let build = build_index_candidates(&[stat], &catalog, &WorkloadOptions::default());
+        assert!(build.candidates.is_empty());
+    }
+
+    #[rstest]
+    #[case(false, None)]
+    #[case(true, Some("gist"))]
+    fn spatial_filters_get_a_gist_candidate_with_postgis(
+        #[case] postgis: bool,
+        #[case] access_method: Option<&str>,
+    ) {
+        let mut catalog = IndexCatalog {
+            postgis,
+            ..IndexCatalog::default()
+        };
+        catalog
+            .schemas_by_table
+            .insert("parcels".into(), vec!["public".into()]);
+        let stat = make_stat(
+            1,
+            "SELECT * FROM parcels WHERE geom && ST_MakeEnvelope($1, $2, $3, $4, 4326) AND ST_Intersects(geom, $5)",
+            1000.0,
+        );
+
+        let build = build_index_candidates(&[stat], &catalog, &WorkloadOptions::default());
+
+        let methods: Vec<_> = build
+            .candidates
+            .iter()
+            .map(|candidate| candidate.access_method.as_deref())
+            .collect();
+        assert_eq!(
+            methods,
+            access_method.into_iter().map(Some).collect::<Vec<_>>()
+        );
+        if let Some(candidate) = build.candidates.first() {
+            assert_eq!(
+                candidate.ddl,
+                "CREATE INDEX CONCURRENTLY ON public.parcels USING gist (geom);"
+            );
+            assert!(candidate
+                .reason
+                .ends_with("spatial &&, ST_Intersects on geom"));
+        }
+    }
+
+    #[test]
+    fn existing_spgist_index_covers_spatial_filters() {
+        let mut index = make_index_definition(&["location"]);
+        index.access_method = "spgist".into();
+        let mut catalog = orders_catalog(vec![index]);
+        catalog.postgis = true;
+        let stat = make_stat(
+            1,
+            "SELECT * FROM orders WHERE ST_DWithin(location, $1, $2)",
+            1000.0,
+        );
+
+        let build = build_index_candidates(&[stat], &catalog, &WorkloadOptions::default());
+
+        assert!(build.candidates.is_empty());
+        assert_eq!(build.coverage_stats.suppressed_by_existing_index, 1);
+    }
+
+    #[rstest]
+    #[case::unqualified_in_two_schemas("SELECT * FROM orders WHERE id = $1", &["public", "tenant_a"], true)]
+    #[case::qualified("SELECT * FROM tenant_a.orders WHERE id = $1", &["public", "tenant_a"], false)]
+    #[case::single_schema("SELECT * FROM orders WHERE id = $1", &["public"], false)]
+    fn ambiguous_unqualified_tables_are_flagged(
+        #[case] query: &str,
+        #[case] schemas: &[&str],
+        #[case] flagged: bool,
+    ) {
+        let mut catalog = IndexCatalog::default();
+        catalog.schemas_by_table.insert(
+            "orders".into(),
+            schemas.iter().map(|schema| schema.to_string()).collect(),
+        );
+        let build = build_index_candidates(
+            &[make_stat(1, query, 1000.0)],
+            &catalog,
+            &WorkloadOptions::default(),
+        );
+
+        let findings: Vec<_> = build
+            .application_patterns
+            .iter()
+            .filter(|finding| finding.kind == ApplicationPatternKind::AmbiguousSchema)
+            .collect();
+        assert_eq!(findings.len(), usize::from(flagged));
+        if let Some(finding) = findings.first() {
+            assert!(finding.evidence.contains("orders (public, tenant_a)"));
+        }
+    }
+
+    #[test]
+    fn multi_column_equality_filters_are_grouped_by_table() {
+        let stats = vec![
+            make_stat(
+                1,
+                "SELECT * FROM orders WHERE country = $1 AND city = $2",
+                1000.0,
+            ),
+            make_stat(
+                2,
+                "SELECT id FROM orders WHERE city = $1 AND country = $2",
+                10.0,
+            ),
+            make_stat(3, "SELECT * FROM orders WHERE country = $1", 10.0),
+        ];
+        let build = build_index_candidates(
+            &stats,
+            &orders_catalog(Vec::new()),
+            &WorkloadOptions::default(),
+        );
+
+        assert_eq!(build.equality_groups.len(), 1);
+        let group = &build.equality_groups[0];
+        assert_eq!(
+            (group.schema.as_str(), group.table.as_str()),
+            ("public", "orders")
+        );
+        assert_eq!(group.columns, ["city", "country"]);
+        assert_eq!(group.calls, 20);
+        assert_eq!(group.queryid, 1);
+    }
+
+    fn orders_catalog(indexes: Vec<IndexDefinition>) -> IndexCatalog {
+        let mut catalog = IndexCatalog::default();
+        catalog
+            .schemas_by_table
+            .insert("orders".into(), vec!["public".into()]);
+        catalog
+            .indexes_by_table
+            .insert("public.orders".into(), indexes);
+        catalog
+    }
+
+    #[test]
+    fn limited_descending_sort_keeps_direction_in_candidate() {
+        let stat = make_stat(
+            1,
+            "SELECT * FROM orders WHERE customer_id = $1 ORDER BY created_at DESC LIMIT $2",
+            1000.0,
+        );
+
+        let build = build_index_candidates(
+            &[stat],
+            &orders_catalog(Vec::new()),
+            &WorkloadOptions::default(),
+        );
+
+        assert_eq!(build.candidates.len(), 1);
+        let candidate = &build.candidates[0];
+        assert_eq!(candidate.columns, vec!["customer_id", "created_at DESC"]);
+        assert_eq!(
+            candidate.ddl,
+            "CREATE INDEX CONCURRENTLY ON public.orders (customer_id, created_at DESC);"
+        );
+        assert!(candidate.reason.contains("ORDER BY created_at DESC LIMIT"));
+    }
+
+    #[rstest]
+    #[case::all_ascending(vec![false, false], true)]
+    #[case::matching_directions(vec![false, true], false)]
+    #[case::backward_scan(vec![true, false], false)]
+    fn mixed_direction_sort_needs_matching_index(
+        #[case] key_descending: Vec<bool>,
+        #[case] expect_candidate: bool,
+    ) {
+        let mut index = make_index_definition(&["created_at", "id"]);
+        index.key_descending = key_descending;
+        let stat = make_stat(
+            1,
+            "SELECT * FROM orders ORDER BY created_at ASC, id DESC LIMIT 20",
+            1000.0,
+        );
+
+        let build = build_index_candidates(
+            &[stat],
+            &orders_catalog(vec![index]),
+            &WorkloadOptions::default(),
+        );
+
+        assert_eq!(!build.candidates.is_empty(), expect_candidate);
+    }
+
+    #[rstest]
+    #[case::large_literal_offset("OFFSET 5000", 0, true)]
+    #[case::small_literal_offset("OFFSET 40", 0, false)]
+    #[case::placeholder_with_heavy_scans("OFFSET $3", 50_000, true)]
+    #[case::placeholder_with_light_scans("OFFSET $3", 500, false)]
+    fn offset_pagination_is_flagged(
+        #[case] offset: &str,
+        #[case] buffers: i64,
+        #[case] flagged: bool,
+    ) {
+        let mut stat = make_stat(
+            1,
+            &format!("SELECT * FROM orders WHERE customer_id = $1 ORDER BY created_at, id LIMIT $2 {offset}"),
+            1000.0,
+        );
+        stat.rows = 200;
+        stat.shared_blks_hit = buffers;
+
+        let build = build_index_candidates(
+            &[stat],
+            &orders_catalog(Vec::new()),
+            &WorkloadOptions::default(),
+        );
+
+        assert_eq!(build.application_patterns.len(), usize::from(flagged));
+        if let Some(finding) = build.application_patterns.first() {
+            assert_eq!(finding.kind, ApplicationPatternKind::OffsetPagination);
+            assert!(finding.suggestion.contains("keyset pagination"));
+            assert!(finding.suggestion.contains("ORDER BY created_at, id"));
+        }
+    }
+
     #[test]
     fn candidate_skips_internal_postgres_tables() {
         let mut usage = QueryColumnUsage::default();
@@ -1589,9 +3844,11 @@ mod tests {
             TimeColumns {
                 total: "total_exec_time",
                 max: "max_exec_time",
+                io: None,
             },
             "total_time_ms",
             true,
+            WorkloadGroupBy::Query,
         );
         assert!(query.contains("SUM(s.calls)::bigint AS calls"));
         assert!(query.contains("SUM(s.total_exec_time) AS total_time_ms"));
@@ -1605,15 +3862,54 @@ mod tests {
             TimeColumns {
                 total: "total_exec_time",
                 max: "max_exec_time",
+                io: None,
             },
             "shared_blks_read",
             false,
+            WorkloadGroupBy::Query,
         );
-        assert!(query.contains("GROUP BY COALESCE(s.queryid, 0)::bigint, COALESCE(s.query, '<query text unavailable>')"));
+        assert!(query.contains("GROUP BY COALESCE(s.queryid, 0)::bigint, COALESCE(s.query, '<query text unavailable>')\n"));
         assert!(query.contains("HAVING SUM(s.calls) >= $1"));
         assert!(query.contains("ORDER BY shared_blks_read DESC"));
     }
 
+    #[test]
+    fn statement_query_splits_rows_by_role_when_grouping_by_role() {
+        let query = build_statement_query(
+            TimeColumns {
+                total: "total_exec_time",
+                max: "max_exec_time",
+                io: None,
+            },
+            "total_time_ms",
+            false,
+            WorkloadGroupBy::Role,
+        );
+        assert!(query.contains("COALESCE(s.query, '<query text unavailable>'), s.userid"));
+        assert!(query.contains("pg_get_userbyid(s.userid)::text"));
+        assert!(query.contains("current_database()::text AS database"));
+    }
+
+    #[rstest]
+    #[case::query(WorkloadGroupBy::Query, 1)]
+    #[case::role(WorkloadGroupBy::Role, 2)]
+    fn group_by_role_keeps_roles_apart(#[case] group_by: WorkloadGroupBy, #[case] rows: usize) {
+        let app = make_stat(1, "SELECT * FROM orders WHERE id = $1", 100.0);
+        let mut reporting = make_stat(1, "SELECT * FROM orders WHERE id = $1", 50.0);
+        reporting.roles = vec!["reporting".into()];
+        let opts = WorkloadOptions {
+            group_by,
+            ..WorkloadOptions::default()
+        };
+
+        let groups = build_slow_query_groups(&[app, reporting], &opts);
+
+        assert_eq!(groups[0].queries.len(), rows);
+        if group_by == WorkloadGroupBy::Query {
+            assert_eq!(groups[0].queries[0].roles, vec!["app", "reporting"]);
+        }
+    }
+
     #[test]
     fn preload_error_message_is_classified_as_unavailable() {
         assert!(is_pg_stat_statements_preload_error_message(
@@ -1621,12 +3917,6 @@ mod tests {
         ));
     }
 
-    #[test]
-    fn fetch_index_catalog_query_limits_to_key_columns() {
-        assert!(FETCH_INDEX_CATALOG_QUERY.contains("arr.ord <= i.indnkeyatts"));
-        assert!(FETCH_INDEX_CATALOG_QUERY.contains("(i.indpred IS NOT NULL) AS is_partial"));
-    }
-
     #[test]
     fn metadata_warnings_cover_recent_reset_deallocations_and_query_visibility() {
         let snapshot = WorkloadMetadataSnapshot {
@@ -1720,28 +4010,31 @@ mod tests {
 
     #[test]
     fn correlate_table_health_adds_table_and_unused_index_notes() {
-        let mut results = WorkloadResults {
-            query_index_candidates: vec![QueryIndexCandidate {
-                schema: "public".into(),
-                table: "orders".into(),
-                columns: vec!["customer_id".into()],
-                reason: "heuristic".into(),
-                confidence: WorkloadFindingConfidence::High,
-                evidence: QueryIndexEvidence::default(),
-                notes: Vec::new(),
-                queryid: 1,
-                total_time_ms: 10.0,
-                mean_time_ms: 1.0,
-                calls: 10,
-            }],
+        let mut candidates = vec![QueryIndexCandidate {
+            schema: "public".into(),
+            table: "orders".into(),
+            columns: vec!["customer_id".into()],
+            access_method: None,
+            ddl: "CREATE INDEX CONCURRENTLY ON public.orders (customer_id);".into(),
+            reason: "heuristic".into(),
+            confidence: WorkloadFindingConfidence::High,
+            evidence: QueryIndexEvidence::default(),
+            notes: Vec::new(),
+            queryid: 1,
+            total_time_ms: 10.0,
+            mean_time_ms: 1.0,
+            calls: 10,
+        }];
+        let health = TableIndexHealth {
             seq_scan_info: vec![crate::models::TableSeqScanInfo {
                 schema: "public".into(),
                 table_name: "orders".into(),
                 seq_scan: 10,
                 idx_scan: 0,
                 live_tuples: 100,
-                table_size_bytes: 1024,
+                table_size_bytes: ByteSize(1024),
                 table_size_pretty: "1 kB".into(),
+                fingerprint: String::new(),
             }],
             bloat_info: vec![crate::models::TableBloatInfo {
                 schema: "public".into(),
@@ -1751,12 +4044,13 @@ mod tests {
                 dead_tup_ratio: 0.1,
                 seq_scan: 10,
                 idx_scan: 0,
-                table_size_bytes: 1024,
+                table_size_bytes: ByteSize(1024),
                 table_size_pretty: "1 kB".into(),
                 last_autovacuum: None,
                 last_autoanalyze: None,
                 seconds_since_last_autovacuum: None,
                 seconds_since_last_autoanalyze: None,
+                fingerprint: String::new(),
             }],
             index_usage_info: vec![crate::models::IndexUsageInfo {
                 issue: IndexIssueKind::Unused,
@@ -1764,7 +4058,7 @@ mod tests {
                 table_name: "orders".into(),
                 index_name: "orders_customer_id_idx".into(),
                 key_columns: vec!["customer_id".into()],
-                index_size_bytes: 1024,
+                index_size_bytes: ByteSize(1024),
                 index_size_pretty: "1 kB".into(),
                 scans: 0,
                 tuples_read: 0,
@@ -1772,27 +4066,83 @@ mod tests {
                 avg_tuples_per_scan: 0.0,
                 heap_fetch_ratio: 0.0,
                 table_live_tup: Some(100),
+                table_index_writes: None,
+                table_scans: None,
+                index_definition: None,
+                partial_predicate: None,
+                estimated_size_bytes: None,
                 is_unique: false,
                 enforces_constraint: false,
                 is_expression: false,
                 is_partial: false,
+                fingerprint: String::new(),
             }],
-            ..WorkloadResults::default()
+            ..TableIndexHealth::default()
         };
 
-        correlate_table_health(&mut results);
+        correlate_table_health(&mut candidates, &health);
 
-        assert!(results.query_index_candidates[0]
+        assert!(candidates[0]
             .notes
             .iter()
             .any(|note| note.contains("sequential scan hotspot")));
-        assert!(results.query_index_candidates[0]
+        assert!(candidates[0]
             .notes
             .iter()
             .any(|note| note.contains("bloat watchlist")));
-        assert!(results.query_index_candidates[0]
+        assert!(candidates[0]
             .notes
             .iter()
             .any(|note| note.contains("unused overlapping index")));
     }
+
+    fn index_finding(issue: IndexIssueKind, table: &str, index_name: &str) -> IndexUsageInfo {
+        IndexUsageInfo {
+            issue,
+            schema: "public".into(),
+            table_name: table.into(),
+            index_name: index_name.into(),
+            key_columns: vec!["status".into()],
+            index_size_bytes: ByteSize(1024),
+            index_size_pretty: "1 kB".into(),
+            scans: 0,
+            tuples_read: 0,
+            tuples_fetched: 0,
+            avg_tuples_per_scan: 0.0,
+            heap_fetch_ratio: 0.0,
+            table_live_tup: Some(100),
+            table_index_writes: None,
+            table_scans: None,
+            index_definition: None,
+            partial_predicate: None,
+            estimated_size_bytes: None,
+            is_unique: false,
+            enforces_constraint: false,
+            is_expression: false,
+            is_partial: false,
+            fingerprint: String::new(),
+        }
+    }
+
+    #[test]
+    fn query_derived_partial_findings_replace_heuristic_ones_per_table() {
+        let merged = merge_index_findings(
+            vec![
+                index_finding(IndexIssueKind::MissingPartialIndex, "orders", "heuristic"),
+                index_finding(IndexIssueKind::MissingPartialIndex, "users", "kept"),
+                index_finding(IndexIssueKind::Unused, "orders", "unused"),
+            ],
+            vec![index_finding(
+                IndexIssueKind::MissingPartialIndex,
+                "orders",
+                "derived",
+            )],
+        );
+
+        let names: Vec<_> = merged
+            .iter()
+            .map(|index| index.index_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["kept", "unused", "derived"]);
+    }
 }
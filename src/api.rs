@@ -0,0 +1,394 @@
+use crate::checker::{AnalyzeOptions, ConfigChecker};
+use crate::config::{parse_duration, DbConfig};
+use crate::i18n::Lang;
+use crate::models::AnalysisResults;
+use crate::probes::{self, LastRun, Probes};
+use axum::extract::{Path, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use snafu::{ResultExt, Snafu};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use tokio::sync::Semaphore;
+use tracing::{info, warn};
+
+#[derive(Debug, Snafu)]
+pub enum ApiError {
+    #[snafu(display("Failed to listen on {}: {}", addr, source))]
+    Bind {
+        addr: String,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("HTTP server failed: {}", source))]
+    Serve { source: std::io::Error },
+}
+
+/// Runs kept for `GET /runs/{id}`; the oldest finished runs are dropped first
+const MAX_RUNS: usize = 256;
+
+/// Analyses running at once; `POST /analyze` answers 429 beyond this
+const MAX_CONCURRENT_RUNS: usize = 4;
+
+/// Body of `POST /analyze`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AnalyzeRequest {
+    /// Database name of an entry in the `--config` file the server was started with
+    #[serde(default)]
+    pub config: Option<String>,
+    /// Connection details, in the same shape as a config file entry
+    #[serde(default)]
+    pub connection: Option<DbConfig>,
+    /// e.g. `60s` or `5m`, see [`AnalyzeOptions::sample_window`]
+    #[serde(default)]
+    pub sample_window: Option<String>,
+    #[serde(default)]
+    pub emit_ddl: bool,
+    #[serde(default)]
+    pub with_workload: bool,
+    #[serde(default)]
+    pub deep: bool,
+    #[serde(default)]
+    pub redact_queries: bool,
+    #[serde(default)]
+    pub lang: Lang,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum RunState {
+    Running,
+    Succeeded { results: Box<AnalysisResults> },
+    Failed { error: String },
+}
+
+#[derive(Debug, Serialize)]
+pub struct Run {
+    pub id: u64,
+    pub database: String,
+    #[serde(flatten)]
+    pub state: RunState,
+}
+
+#[derive(Default)]
+struct Runs {
+    next_id: u64,
+    runs: BTreeMap<u64, Run>,
+}
+
+#[derive(Clone)]
+struct ApiState {
+    configs: Arc<Vec<DbConfig>>,
+    runs: Arc<Mutex<Runs>>,
+    /// One permit per running analysis
+    permits: Arc<Semaphore>,
+    probes: Probes,
+}
+
+impl ApiState {
+    /// Picks the database a request targets, or explains why the request is invalid
+    fn resolve(&self, request: &AnalyzeRequest) -> Result<DbConfig, String> {
+        match (&request.config, &request.connection) {
+            (Some(name), None) => self
+                .configs
+                .iter()
+                .find(|config| config.database == *name)
+                .cloned()
+                .ok_or_else(|| format!("no config entry for database '{name}'")),
+            (None, Some(connection)) => Ok(connection.clone()),
+            (Some(_), Some(_)) => Err("set either 'config' or 'connection', not both".to_string()),
+            (None, None) => Err("'config' or 'connection' is required".to_string()),
+        }
+    }
+
+    /// Registers a new running run and returns its ID and response body
+    fn start(&self, database: &str) -> (u64, serde_json::Value) {
+        let mut runs = self.runs.lock().unwrap();
+        runs.next_id += 1;
+        let run = Run {
+            id: runs.next_id,
+            database: database.to_string(),
+            state: RunState::Running,
+        };
+        let id = run.id;
+        let body = serde_json::to_value(&run).unwrap_or_default();
+        runs.runs.insert(id, run);
+        while runs.runs.len() > MAX_RUNS {
+            let Some(oldest) = runs
+                .runs
+                .iter()
+                .find(|(_, run)| !matches!(run.state, RunState::Running))
+                .map(|(id, _)| *id)
+            else {
+                break;
+            };
+            runs.runs.remove(&oldest);
+        }
+        (id, body)
+    }
+
+    fn finish(&self, id: u64, state: RunState) {
+        if let Some(run) = self.runs.lock().unwrap().runs.get_mut(&id) {
+            run.state = state;
+        }
+    }
+}
+
+/// Normalises `:8080` to `127.0.0.1:8080`, since the API has no authentication;
+/// anything else, such as `0.0.0.0:8080`, is passed to the socket as-is
+pub fn listen_addr(listen: &str) -> String {
+    match listen.strip_prefix(':') {
+        Some(port) => format!("127.0.0.1:{port}"),
+        None => listen.to_string(),
+    }
+}
+
+/// Routes of the HTTP API, with the [`probes`] endpoints; `configs` are the
+/// entries `POST /analyze` can name
+pub fn router(configs: Vec<DbConfig>) -> Router {
+    routes(ApiState {
+        configs: Arc::new(configs),
+        runs: Arc::new(Mutex::new(Runs::default())),
+        permits: Arc::new(Semaphore::new(MAX_CONCURRENT_RUNS)),
+        probes: Probes::ready(),
+    })
+}
+
+fn routes(state: ApiState) -> Router {
+    let probes = state.probes.clone();
+    Router::new()
+        .route("/analyze", post(analyze))
+        .route("/runs/{id}", get(run))
+        .with_state(state)
+        .merge(probes::router(probes))
+}
+
+/// Binds `listen` (see [`listen_addr`])
+pub async fn bind(listen: &str) -> Result<TcpListener, ApiError> {
+    let addr = listen_addr(listen);
+    let listener = TcpListener::bind(&addr)
+        .await
+        .context(BindSnafu { addr: addr.clone() })?;
+    info!("Listening on {}", addr);
+    Ok(listener)
+}
+
+/// Serves `router` on a bound listener until the process is stopped
+pub async fn serve_on(listener: TcpListener, router: Router) -> Result<(), ApiError> {
+    axum::serve(listener, router).await.context(ServeSnafu)
+}
+
+/// Serves the HTTP API until the process is stopped
+pub async fn serve(listen: &str, configs: Vec<DbConfig>) -> Result<(), ApiError> {
+    serve_on(bind(listen).await?, router(configs)).await
+}
+
+fn error(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(json!({ "error": message.into() }))).into_response()
+}
+
+async fn analyze(State(state): State<ApiState>, Json(request): Json<AnalyzeRequest>) -> Response {
+    let config = match state.resolve(&request) {
+        Ok(config) => config,
+        Err(message) => return error(StatusCode::BAD_REQUEST, message),
+    };
+    let sample_window = match request
+        .sample_window
+        .as_deref()
+        .map(parse_duration)
+        .transpose()
+    {
+        Ok(window) => window,
+        Err(message) => return error(StatusCode::BAD_REQUEST, message),
+    };
+    let opts = AnalyzeOptions {
+        sample_window,
+        emit_ddl: request.emit_ddl,
+        with_workload: request.with_workload,
+        deep: request.deep,
+        redact_queries: request.redact_queries,
+        ..AnalyzeOptions::default()
+    };
+
+    let Ok(permit) = state.permits.clone().try_acquire_owned() else {
+        return error(
+            StatusCode::TOO_MANY_REQUESTS,
+            format!("{MAX_CONCURRENT_RUNS} analyses are already running; retry later"),
+        );
+    };
+    let (id, body) = state.start(&config.database);
+    info!("Run {}: analyzing database {}", id, config.database);
+
+    let lang = request.lang;
+    tokio::spawn(async move {
+        let _permit = permit;
+        let started_at = Utc::now();
+        let outcome = match ConfigChecker::new(config.clone()).await {
+            Ok(mut checker) => checker.analyze(&opts).await,
+            Err(err) => Err(err),
+        };
+        state.probes.record(LastRun::finished(
+            &config,
+            started_at,
+            outcome.as_ref().map_err(ToString::to_string),
+        ));
+        let state_after = match outcome {
+            Ok(mut results) => {
+                results.localize(lang);
+                RunState::Succeeded {
+                    results: Box::new(results),
+                }
+            }
+            Err(err) => {
+                warn!("Run {} failed: {}", id, err);
+                RunState::Failed {
+                    error: err.to_string(),
+                }
+            }
+        };
+        state.finish(id, state_after);
+    });
+
+    (
+        StatusCode::ACCEPTED,
+        [(header::LOCATION, format!("/runs/{id}"))],
+        Json(body),
+    )
+        .into_response()
+}
+
+async fn run(State(state): State<ApiState>, Path(id): Path<u64>) -> Response {
+    let runs = state.runs.lock().unwrap();
+    match runs.runs.get(&id) {
+        Some(run) => Json(serde_json::to_value(run).unwrap_or_default()).into_response(),
+        None => error(StatusCode::NOT_FOUND, format!("no run with id {id}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    async fn send(request: Request<Body>) -> (StatusCode, serde_json::Value) {
+        let response = router(vec![DbConfig::for_tests("db.internal", "orders")])
+            .oneshot(request)
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    fn post_analyze(body: &str) -> Request<Body> {
+        Request::post("/analyze")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[rstest]
+    #[case(":8080", "127.0.0.1:8080")]
+    #[case("0.0.0.0:9000", "0.0.0.0:9000")]
+    fn listen_addr_defaults_to_loopback(#[case] listen: &str, #[case] expected: &str) {
+        assert_eq!(listen_addr(listen), expected);
+    }
+
+    #[test]
+    fn resolves_config_entries_by_database_name() {
+        let state = ApiState {
+            configs: Arc::new(vec![
+                DbConfig::for_tests("db.internal", "orders"),
+                DbConfig::for_tests("db.internal", "billing"),
+            ]),
+            runs: Arc::default(),
+            permits: Arc::new(Semaphore::new(MAX_CONCURRENT_RUNS)),
+            probes: Probes::default(),
+        };
+        let request: AnalyzeRequest = serde_json::from_str(r#"{"config": "billing"}"#).unwrap();
+        assert_eq!(state.resolve(&request).unwrap().database, "billing");
+    }
+
+    #[rstest]
+    #[case(r#"{"config": "missing"}"#, "no config entry for database 'missing'")]
+    #[case(r#"{}"#, "'config' or 'connection' is required")]
+    #[case(r#"{"config": "orders", "sample_window": "soon"}"#, "invalid duration")]
+    #[tokio::test]
+    async fn rejects_requests_that_name_no_database(#[case] body: &str, #[case] error: &str) {
+        let (status, response) = send(post_analyze(body)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(
+            response["error"].as_str().unwrap().contains(error),
+            "{response}"
+        );
+    }
+
+    #[tokio::test]
+    async fn analyses_beyond_the_limit_are_turned_away() {
+        let state = ApiState {
+            configs: Arc::new(vec![DbConfig::for_tests("db.internal", "orders")]),
+            runs: Arc::default(),
+            permits: Arc::new(Semaphore::new(0)),
+            probes: Probes::default(),
+        };
+        let response = routes(state.clone())
+            .oneshot(post_analyze(r#"{"config": "orders"}"#))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(state.runs.lock().unwrap().runs.is_empty());
+    }
+
+    #[test]
+    fn eviction_keeps_running_runs() {
+        let state = ApiState {
+            configs: Arc::default(),
+            runs: Arc::default(),
+            permits: Arc::new(Semaphore::new(MAX_CONCURRENT_RUNS)),
+            probes: Probes::default(),
+        };
+        let (running, _) = state.start("orders");
+        for _ in 0..MAX_RUNS {
+            let (id, _) = state.start("billing");
+            state.finish(
+                id,
+                RunState::Failed {
+                    error: "down".to_string(),
+                },
+            );
+        }
+        let (newest, _) = state.start("orders");
+
+        let runs = state.runs.lock().unwrap();
+        assert_eq!(runs.runs.len(), MAX_RUNS);
+        assert!(runs.runs.contains_key(&running));
+        assert!(runs.runs.contains_key(&newest));
+        assert!(!runs.runs.contains_key(&(running + 1)));
+    }
+
+    #[tokio::test]
+    async fn serves_probe_endpoints() {
+        let request = Request::get("/readyz").body(Body::empty()).unwrap();
+        let (status, response) = send(request).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(response["status"], "ready");
+    }
+
+    #[tokio::test]
+    async fn unknown_run_is_not_found() {
+        let request = Request::get("/runs/42").body(Body::empty()).unwrap();
+        let (status, response) = send(request).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(response["error"], "no run with id 42");
+    }
+}
@@ -0,0 +1,752 @@
+use crate::analysis::validation::in_param_unit;
+use crate::checks;
+use crate::models::{
+    AnalysisResults, ConfigCategory, ParamValue, PgConfigParam, SettingsRemediation,
+};
+use crate::reporter::format_setting;
+use chrono::{DateTime, SecondsFormat, Utc};
+use snafu::{ResultExt, Snafu};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Snafu)]
+pub enum ApplyError {
+    #[snafu(display("Failed to read plan {}: {}", path.display(), source))]
+    PlanRead {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display(
+        "{}:{}: only ALTER SYSTEM SET/RESET and pg_reload_conf() may be applied, found '{}'",
+        path.display(),
+        line,
+        statement
+    ))]
+    PlanStatement {
+        path: PathBuf,
+        line: usize,
+        statement: String,
+    },
+
+    #[snafu(display("Failed to write undo script {}: {}", path.display(), source))]
+    UndoWrite {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+type Result<T, E = ApplyError> = std::result::Result<T, E>;
+
+/// One `ALTER SYSTEM` statement of a plan
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApplyStep {
+    pub parameter: String,
+    /// Value to set; `None` resets the parameter to its configuration file value
+    pub value: Option<String>,
+    /// Category of the suggestion, or of the check covering the parameter
+    pub category: Option<ConfigCategory>,
+}
+
+impl ApplyStep {
+    pub fn sql(&self) -> String {
+        match &self.value {
+            Some(value) => format!(
+                "ALTER SYSTEM SET {} = {};",
+                self.parameter,
+                quote_literal(value)
+            ),
+            None => format!("ALTER SYSTEM RESET {};", self.parameter),
+        }
+    }
+}
+
+/// Reads a plan of `ALTER SYSTEM SET`/`RESET` statements. `SELECT pg_reload_conf()`
+/// is accepted and dropped, since apply reloads once after the last change;
+/// anything else is refused so a plan cannot smuggle in other writes.
+pub fn parse_plan(path: &Path) -> Result<Vec<ApplyStep>> {
+    let text = fs::read_to_string(path).context(PlanReadSnafu { path })?;
+    parse_plan_text(&text).map_err(|(line, statement)| ApplyError::PlanStatement {
+        path: path.to_path_buf(),
+        line,
+        statement,
+    })
+}
+
+fn parse_plan_text(text: &str) -> Result<Vec<ApplyStep>, (usize, String)> {
+    let mut steps = Vec::new();
+    for (line, statement) in statements(text) {
+        if let Some(step) = parse_statement(&statement) {
+            steps.push(step);
+        } else if !is_reload(&statement) {
+            return Err((line, statement));
+        }
+    }
+    Ok(steps)
+}
+
+/// Splits on `;` outside quotes and drops `--` comments, keeping the line each
+/// statement starts on. A quoted literal may span lines.
+fn statements(text: &str) -> Vec<(usize, String)> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut start = 1;
+    let mut in_quotes = false;
+    for (index, line) in text.lines().enumerate() {
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if !in_quotes && c == '-' && chars.peek() == Some(&'-') {
+                break;
+            }
+            if c == '\'' {
+                in_quotes = !in_quotes;
+            }
+            if !in_quotes && c == ';' {
+                let statement = current.trim().to_string();
+                if !statement.is_empty() {
+                    statements.push((start, statement));
+                }
+                current.clear();
+                continue;
+            }
+            if current.trim().is_empty() && !c.is_whitespace() {
+                start = index + 1;
+            }
+            current.push(c);
+        }
+        current.push(if in_quotes { '\n' } else { ' ' });
+    }
+    let statement = current.trim().to_string();
+    if !statement.is_empty() {
+        statements.push((start, statement));
+    }
+    statements
+}
+
+fn parse_statement(statement: &str) -> Option<ApplyStep> {
+    let (alter, rest) = next_word(statement)?;
+    let (system, rest) = next_word(rest)?;
+    if !alter.eq_ignore_ascii_case("alter") || !system.eq_ignore_ascii_case("system") {
+        return None;
+    }
+    let (action, rest) = next_word(rest)?;
+    let rest = rest.trim();
+    if action.eq_ignore_ascii_case("reset") {
+        return is_parameter_name(rest).then(|| step(rest, None));
+    }
+    if !action.eq_ignore_ascii_case("set") {
+        return None;
+    }
+
+    let split = rest.find(|c: char| c.is_whitespace() || c == '=')?;
+    let (parameter, rest) = (&rest[..split], rest[split..].trim_start());
+    let value = match rest.strip_prefix('=') {
+        Some(value) => value,
+        None => {
+            let (to, value) = next_word(rest)?;
+            if !to.eq_ignore_ascii_case("to") {
+                return None;
+            }
+            value
+        }
+    };
+    let value = value.trim();
+    // SET ... TO DEFAULT is what RESET does
+    let value = match value.eq_ignore_ascii_case("default") {
+        true => None,
+        false => Some(unquote(value)?),
+    };
+    is_parameter_name(parameter).then(|| step(parameter, value))
+}
+
+/// The first word of `text` and what follows it
+fn next_word(text: &str) -> Option<(&str, &str)> {
+    let text = text.trim_start();
+    let end = text.find(char::is_whitespace).unwrap_or(text.len());
+    (end > 0).then(|| text.split_at(end))
+}
+
+fn step(parameter: &str, value: Option<String>) -> ApplyStep {
+    ApplyStep {
+        parameter: parameter.to_ascii_lowercase(),
+        value,
+        category: checks::for_parameter(&parameter.to_ascii_lowercase())
+            .map(|check| check.category),
+    }
+}
+
+fn is_reload(statement: &str) -> bool {
+    let compact: String = statement
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    compact == "selectpg_reload_conf()"
+}
+
+/// GUC names are identifiers, with a dot for extension settings
+fn is_parameter_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+/// A single-quoted literal or a bare word such as `on` or `64MB`. Lists of
+/// several values are refused; write them as one literal, e.g. `'a, b'`.
+fn unquote(value: &str) -> Option<String> {
+    let Some(quoted) = value.strip_prefix('\'') else {
+        let bare = !value.is_empty()
+            && !value.contains(|c: char| c.is_whitespace() || c == ',' || c == '\'');
+        return bare.then(|| value.to_string());
+    };
+    let mut inner = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        if c != '\'' {
+            inner.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\'') => inner.push('\''),
+            None => return Some(inner),
+            // Anything after the closing quote, such as another list element
+            Some(_) => return None,
+        }
+    }
+    None
+}
+
+const RELOAD_STATEMENT: &str = "SELECT pg_reload_conf();";
+
+pub(crate) fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Turns the suggestions of a run into a plan, best ranked first. Only
+/// suggestions naming a setting with a value the server would take are kept:
+/// numbers in any unit the setting converts from, and single words such as `on`
+/// for boolean, enum and string settings.
+pub fn plan_from_results(results: &AnalysisResults) -> Vec<ApplyStep> {
+    let mut seen = HashSet::new();
+    let mut steps = Vec::new();
+    for (category, suggestion) in results.ranked_suggestions() {
+        if category == ConfigCategory::Version {
+            continue;
+        }
+        let Some(param) = results.params.get(&suggestion.parameter) else {
+            continue;
+        };
+        let value = suggestion.suggested_value.trim();
+        let settable = if matches!(
+            param.value,
+            ParamValue::Bytes(_)
+                | ParamValue::Duration(_)
+                | ParamValue::Integer(_)
+                | ParamValue::Real(_)
+        ) {
+            in_param_unit(value, param).is_some()
+        } else {
+            !value.is_empty() && !value.contains(char::is_whitespace)
+        };
+        if settable && seen.insert(param.name.as_str()) {
+            steps.push(ApplyStep {
+                parameter: param.name.clone(),
+                value: Some(value.to_string()),
+                category: Some(category),
+            });
+        }
+    }
+    steps
+}
+
+/// Why a step of the plan is left out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// The server has no such setting
+    UnknownParameter,
+    /// `internal` settings are fixed at build or initdb time
+    ReadOnly,
+    /// The setting only changes on restart and `--include-restart` was not given
+    NeedsRestart,
+    /// Not in the `--category` filter
+    FilteredOut,
+    /// The server already runs with the value
+    AlreadySet,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            SkipReason::UnknownParameter => "not a setting on this server",
+            SkipReason::ReadOnly => "read-only",
+            SkipReason::NeedsRestart => "needs a restart; pass --include-restart to apply it",
+            SkipReason::FilteredOut => "category not selected",
+            SkipReason::AlreadySet => "already set",
+        };
+        f.write_str(reason)
+    }
+}
+
+/// A step checked against the live setting it changes
+#[derive(Debug, Clone)]
+pub struct PlannedChange {
+    pub step: ApplyStep,
+    /// The setting as read before the change, for the undo script
+    pub current: PgConfigParam,
+}
+
+impl PlannedChange {
+    pub fn needs_restart(&self) -> bool {
+        self.current.context == "postmaster"
+    }
+}
+
+impl fmt::Display for PlannedChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let target = self
+            .step
+            .value
+            .as_deref()
+            .unwrap_or("configuration file value");
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.step.parameter,
+            format_setting(&self.current.current_value, self.current.unit.as_deref()),
+            target
+        )?;
+        if let Some(category) = self.step.category {
+            write!(f, " [{}]", category.as_str())?;
+        }
+        if self.needs_restart() {
+            f.write_str(" (restart required)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Which steps of a plan may run
+#[derive(Debug, Clone, Default)]
+pub struct ApplyFilter {
+    /// Only steps in these categories; empty keeps every category
+    pub categories: Vec<ConfigCategory>,
+    /// Also apply `postmaster` settings, which only change on restart
+    pub include_restart: bool,
+}
+
+/// Checks each step against the live settings, keyed by parameter name, and
+/// splits the plan into changes to confirm and skipped steps with the reason
+pub fn select(
+    steps: Vec<ApplyStep>,
+    live: &HashMap<String, PgConfigParam>,
+    filter: &ApplyFilter,
+) -> (Vec<PlannedChange>, Vec<(ApplyStep, SkipReason)>) {
+    let mut changes = Vec::new();
+    let mut skipped = Vec::new();
+    for step in steps {
+        let Some(current) = live.get(&step.parameter) else {
+            skipped.push((step, SkipReason::UnknownParameter));
+            continue;
+        };
+        let reason = if current.context == "internal" {
+            Some(SkipReason::ReadOnly)
+        } else if !filter.categories.is_empty()
+            && !step
+                .category
+                .is_some_and(|category| filter.categories.contains(&category))
+        {
+            Some(SkipReason::FilteredOut)
+        } else if current.context == "postmaster" && !filter.include_restart {
+            Some(SkipReason::NeedsRestart)
+        } else if step
+            .value
+            .as_deref()
+            .is_some_and(|value| is_current(value, current))
+        {
+            Some(SkipReason::AlreadySet)
+        } else {
+            None
+        };
+        match reason {
+            Some(reason) => skipped.push((step, reason)),
+            None => changes.push(PlannedChange {
+                current: current.clone(),
+                step,
+            }),
+        }
+    }
+    (changes, skipped)
+}
+
+fn is_current(value: &str, param: &PgConfigParam) -> bool {
+    match (
+        in_param_unit(value, param),
+        param.current_value.parse::<f64>(),
+    ) {
+        (Some(target), Ok(current)) => (target - current).abs() < f64::EPSILON,
+        _ => value.eq_ignore_ascii_case(param.current_value.trim()),
+    }
+}
+
+/// Statements restoring the values the changes replace, then a reload.
+/// pg_settings reports a setting in its own unit, which is also how
+/// `ALTER SYSTEM` reads a bare number.
+pub fn rollback_statements(changes: &[PlannedChange]) -> Vec<String> {
+    changes
+        .iter()
+        .map(|change| {
+            ApplyStep {
+                parameter: change.current.name.clone(),
+                value: Some(change.current.current_value.clone()),
+                category: change.step.category,
+            }
+            .sql()
+        })
+        .chain([RELOAD_STATEMENT.to_string()])
+        .collect()
+}
+
+/// The rollback statements of an apply run, headed with where and when it ran
+pub fn undo_script(changes: &[PlannedChange], database: &str, at: DateTime<Utc>) -> String {
+    let mut script = format!(
+        "-- Undo of postgreat apply on {database} at {}\n",
+        at.to_rfc3339_opts(SecondsFormat::Secs, true)
+    );
+    for statement in rollback_statements(changes) {
+        script.push_str(&statement);
+        script.push('\n');
+    }
+    script
+}
+
+/// The suggestions of a run as an `ALTER SYSTEM` script with its rollback, for
+/// `--emit-ddl`. Unlike `postgreat apply`, restart-only settings are included
+/// and listed, since nothing runs until an operator does. `None` when no
+/// suggestion changes a setting.
+pub fn plan_remediation(results: &AnalysisResults) -> Option<SettingsRemediation> {
+    let filter = ApplyFilter {
+        categories: Vec::new(),
+        include_restart: true,
+    };
+    let (changes, _) = select(plan_from_results(results), &results.params, &filter);
+    if changes.is_empty() {
+        return None;
+    }
+
+    let statements = changes
+        .iter()
+        .map(|change| change.step.sql())
+        .chain([RELOAD_STATEMENT.to_string()])
+        .collect();
+    Some(SettingsRemediation {
+        statements,
+        rollback: rollback_statements(&changes),
+        restart_required: changes
+            .iter()
+            .filter(|change| change.needs_restart())
+            .map(|change| change.step.parameter.clone())
+            .collect(),
+    })
+}
+
+/// Writes the undo script before anything is changed, so it exists even if a
+/// statement fails half way
+pub fn write_undo(path: &Path, script: &str) -> Result<()> {
+    fs::write(path, script).context(UndoWriteSnafu { path })
+}
+
+/// Default undo script name, unique per run
+pub fn default_undo_path(at: DateTime<Utc>) -> PathBuf {
+    PathBuf::from(format!(
+        "postgreat-undo-{}.sql",
+        at.format("%Y%m%dT%H%M%SZ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConfigSuggestion, SuggestionLevel};
+    use chrono::TimeZone;
+    use rstest::rstest;
+
+    fn param(
+        name: &str,
+        setting: &str,
+        vartype: &str,
+        unit: Option<&str>,
+        context: &str,
+    ) -> PgConfigParam {
+        PgConfigParam {
+            name: name.to_string(),
+            current_value: setting.to_string(),
+            default_value: None,
+            unit: unit.map(str::to_string),
+            context: context.to_string(),
+            value: ParamValue::parse(setting, vartype, unit),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        }
+    }
+
+    fn live() -> HashMap<String, PgConfigParam> {
+        [
+            param("work_mem", "4096", "integer", Some("kB"), "user"),
+            param(
+                "shared_buffers",
+                "16384",
+                "integer",
+                Some("8kB"),
+                "postmaster",
+            ),
+            param("log_lock_waits", "off", "bool", None, "superuser"),
+            param("block_size", "8192", "integer", None, "internal"),
+        ]
+        .into_iter()
+        .map(|param| (param.name.clone(), param))
+        .collect()
+    }
+
+    fn set(parameter: &str, value: &str, category: ConfigCategory) -> ApplyStep {
+        ApplyStep {
+            parameter: parameter.to_string(),
+            value: Some(value.to_string()),
+            category: Some(category),
+        }
+    }
+
+    #[test]
+    fn plans_parse_set_reset_and_reload() {
+        let plan = "-- tuned by hand\n\
+                    ALTER SYSTEM SET work_mem = '64MB';\n\
+                    alter system set log_line_prefix to '%m [%p] ''app'' ';  -- quoted\n\
+                    ALTER SYSTEM SET log_lock_waits = on;\n\
+                    ALTER SYSTEM\n  RESET random_page_cost;\n\
+                    SELECT pg_reload_conf();\n";
+        let steps = parse_plan_text(plan).unwrap();
+        let sql: Vec<_> = steps.iter().map(ApplyStep::sql).collect();
+        assert_eq!(
+            sql,
+            [
+                "ALTER SYSTEM SET work_mem = '64MB';",
+                "ALTER SYSTEM SET log_line_prefix = '%m [%p] ''app'' ';",
+                "ALTER SYSTEM SET log_lock_waits = 'on';",
+                "ALTER SYSTEM RESET random_page_cost;",
+            ]
+        );
+        assert_eq!(steps[0].category, Some(ConfigCategory::Memory));
+    }
+
+    #[test]
+    fn plans_refuse_other_statements() {
+        let plan = "ALTER SYSTEM SET work_mem = '64MB';\n\nDROP TABLE accounts;\n";
+        assert_eq!(
+            parse_plan_text(plan),
+            Err((3, "DROP TABLE accounts".to_string()))
+        );
+        assert!(parse_plan_text("ALTER SYSTEM SET work_mem = '1'; DELETE FROM t").is_err());
+        assert!(parse_plan_text("ALTER SYSTEM SET \"work;mem\" = '1'").is_err());
+    }
+
+    #[test]
+    fn default_resets_and_literals_may_span_lines() {
+        let plan = "ALTER SYSTEM SET work_mem TO DEFAULT;\n\
+                    ALTER SYSTEM SET log_line_prefix = '%m;\n-- [%p] ';\n\
+                    ALTER SYSTEM SET application_name = 'default';\n";
+        let sql: Vec<_> = parse_plan_text(plan)
+            .unwrap()
+            .iter()
+            .map(ApplyStep::sql)
+            .collect();
+        assert_eq!(
+            sql,
+            [
+                "ALTER SYSTEM RESET work_mem;",
+                "ALTER SYSTEM SET log_line_prefix = '%m;\n-- [%p] ';",
+                "ALTER SYSTEM SET application_name = 'default';",
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("ALTER SYSTEM SET shared_preload_libraries = 'pg_stat_statements', 'auto_explain'")]
+    #[case("ALTER SYSTEM SET search_path = public, app")]
+    #[case("ALTER SYSTEM SET work_mem = '64MB' '1GB'")]
+    fn list_values_are_refused(#[case] plan: &str) {
+        assert!(parse_plan_text(plan).is_err());
+    }
+
+    #[test]
+    fn results_become_steps_for_settable_suggestions() {
+        let mut results = AnalysisResults {
+            params: live(),
+            ..AnalysisResults::default()
+        };
+        for (category, parameter, value) in [
+            (ConfigCategory::Memory, "work_mem", "64MB"),
+            (ConfigCategory::Workload, "work_mem", "128MB"),
+            (ConfigCategory::Logging, "log_lock_waits", "on"),
+            (ConfigCategory::Memory, "shared_buffers", "0.25 of RAM"),
+            (
+                ConfigCategory::TableIndex,
+                "bloat public.orders",
+                "VACUUM FULL",
+            ),
+        ] {
+            results
+                .suggestions_by_category
+                .entry(category)
+                .or_default()
+                .push(ConfigSuggestion {
+                    parameter: parameter.to_string(),
+                    current_value: String::new(),
+                    suggested_value: value.to_string(),
+                    level: SuggestionLevel::Important,
+                    rationale: String::new(),
+                    messages: Vec::new(),
+                    fingerprint: String::new(),
+                });
+        }
+
+        let mut steps = plan_from_results(&results);
+        steps.sort_by(|a, b| a.parameter.cmp(&b.parameter));
+        let steps: Vec<_> = steps
+            .iter()
+            .map(|step| (step.parameter.as_str(), step.value.as_deref().unwrap()))
+            .collect();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0], ("log_lock_waits", "on"));
+        assert_eq!(steps[1].0, "work_mem");
+    }
+
+    #[test]
+    fn only_reload_safe_changes_in_the_filter_are_selected() {
+        let steps = vec![
+            set("work_mem", "64MB", ConfigCategory::Memory),
+            set("shared_buffers", "4GB", ConfigCategory::Memory),
+            set("log_lock_waits", "on", ConfigCategory::Logging),
+            set("block_size", "16384", ConfigCategory::Memory),
+            set("no_such_setting", "1", ConfigCategory::Memory),
+        ];
+        let filter = ApplyFilter {
+            categories: vec![ConfigCategory::Memory],
+            include_restart: false,
+        };
+        let (changes, skipped) = select(steps.clone(), &live(), &filter);
+        let changed: Vec<_> = changes.iter().map(|c| c.step.parameter.as_str()).collect();
+        assert_eq!(changed, ["work_mem"]);
+        let reasons: Vec<_> = skipped
+            .iter()
+            .map(|(step, reason)| (step.parameter.as_str(), *reason))
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                ("shared_buffers", SkipReason::NeedsRestart),
+                ("log_lock_waits", SkipReason::FilteredOut),
+                ("block_size", SkipReason::ReadOnly),
+                ("no_such_setting", SkipReason::UnknownParameter),
+            ]
+        );
+
+        let filter = ApplyFilter {
+            categories: Vec::new(),
+            include_restart: true,
+        };
+        let (changes, _) = select(steps, &live(), &filter);
+        assert_eq!(changes.len(), 3);
+        assert!(changes[1].needs_restart());
+        assert_eq!(
+            changes[1].to_string(),
+            "shared_buffers: 16384 x 8kB -> 4GB [Memory Configuration] (restart required)"
+        );
+    }
+
+    #[test]
+    fn values_already_in_effect_are_skipped() {
+        let steps = vec![
+            set("work_mem", "4MB", ConfigCategory::Memory),
+            set("log_lock_waits", "OFF", ConfigCategory::Logging),
+        ];
+        let (changes, skipped) = select(steps, &live(), &ApplyFilter::default());
+        assert!(changes.is_empty());
+        assert!(skipped
+            .iter()
+            .all(|(_, reason)| *reason == SkipReason::AlreadySet));
+    }
+
+    #[test]
+    fn undo_restores_the_previous_settings() {
+        let (changes, _) = select(
+            vec![
+                set("work_mem", "64MB", ConfigCategory::Memory),
+                set("log_lock_waits", "on", ConfigCategory::Logging),
+            ],
+            &live(),
+            &ApplyFilter::default(),
+        );
+        let at = Utc.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap();
+        assert_eq!(
+            undo_script(&changes, "production_db", at),
+            "-- Undo of postgreat apply on production_db at 2026-10-16T09:30:00Z\n\
+             ALTER SYSTEM SET work_mem = '4096';\n\
+             ALTER SYSTEM SET log_lock_waits = 'off';\n\
+             SELECT pg_reload_conf();\n"
+        );
+        assert_eq!(
+            default_undo_path(at),
+            PathBuf::from("postgreat-undo-20261016T093000Z.sql")
+        );
+    }
+
+    #[test]
+    fn remediation_scripts_come_with_their_rollback() {
+        let mut results = AnalysisResults {
+            params: live(),
+            ..AnalysisResults::default()
+        };
+        for (parameter, value) in [("work_mem", "64MB"), ("shared_buffers", "4GB")] {
+            results
+                .suggestions_by_category
+                .entry(ConfigCategory::Memory)
+                .or_default()
+                .push(ConfigSuggestion {
+                    parameter: parameter.to_string(),
+                    current_value: String::new(),
+                    suggested_value: value.to_string(),
+                    level: SuggestionLevel::Important,
+                    rationale: String::new(),
+                    messages: Vec::new(),
+                    fingerprint: String::new(),
+                });
+        }
+
+        let remediation = plan_remediation(&results).unwrap();
+        assert_eq!(
+            remediation.statements,
+            [
+                "ALTER SYSTEM SET work_mem = '64MB';",
+                "ALTER SYSTEM SET shared_buffers = '4GB';",
+                "SELECT pg_reload_conf();",
+            ]
+        );
+        assert_eq!(
+            remediation.rollback,
+            [
+                "ALTER SYSTEM SET work_mem = '4096';",
+                "ALTER SYSTEM SET shared_buffers = '16384';",
+                "SELECT pg_reload_conf();",
+            ]
+        );
+        assert_eq!(remediation.restart_required, ["shared_buffers"]);
+
+        results.suggestions_by_category.clear();
+        assert!(plan_remediation(&results).is_none());
+    }
+}
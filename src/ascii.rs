@@ -0,0 +1,143 @@
+use std::borrow::Cow;
+use std::io::{self, StdoutLock, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Turns on `--plain` for every report written through [`stdout`]
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// Locked stdout, pure ASCII with `--plain`
+pub fn stdout() -> AsciiWriter<StdoutLock<'static>> {
+    AsciiWriter::new(io::stdout().lock(), is_plain())
+}
+
+/// Replaces the typographic characters reports use with ASCII look-alikes, e.g.
+/// `…` with `...`, and anything else outside ASCII, such as names in other
+/// scripts, with `?`
+pub fn transliterate(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    let mut ascii = String::with_capacity(text.len());
+    for ch in text.chars() {
+        let replacement = match ch {
+            ch if ch.is_ascii() => {
+                ascii.push(ch);
+                continue;
+            }
+            '…' => "...",
+            '→' => "->",
+            '←' => "<-",
+            '×' => "x",
+            '•' => "*",
+            '·' | '–' | '−' => "-",
+            '—' => "--",
+            '≥' => ">=",
+            '≤' => "<=",
+            '≈' => "~",
+            '‘' | '’' => "'",
+            '“' | '”' => "\"",
+            '\u{a0}' => " ",
+            _ => "?",
+        };
+        ascii.push_str(replacement);
+    }
+    Cow::Owned(ascii)
+}
+
+/// Writer passing bytes through, or transliterating them with [`transliterate`]
+/// when enabled. A character split across writes is held until the rest arrives.
+pub struct AsciiWriter<W: Write> {
+    inner: W,
+    enabled: bool,
+    pending: Vec<u8>,
+}
+
+impl<W: Write> AsciiWriter<W> {
+    pub fn new(inner: W, enabled: bool) -> Self {
+        Self {
+            inner,
+            enabled,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for AsciiWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.enabled {
+            return self.inner.write(buf);
+        }
+        self.pending.extend_from_slice(buf);
+        let bytes = std::mem::take(&mut self.pending);
+        let mut rest = bytes.as_slice();
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(text) => {
+                    self.inner.write_all(transliterate(text).as_bytes())?;
+                    break;
+                }
+                Err(err) => {
+                    let (valid, tail) = rest.split_at(err.valid_up_to());
+                    let text = std::str::from_utf8(valid).expect("prefix is valid UTF-8");
+                    self.inner.write_all(transliterate(text).as_bytes())?;
+                    match err.error_len() {
+                        Some(len) => {
+                            self.inner.write_all(b"?")?;
+                            rest = &tail[len..];
+                        }
+                        None => {
+                            self.pending = tail.to_vec();
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typographic_characters_get_ascii_look_alikes() {
+        assert_eq!(
+            transliterate("SELECT * FROM order… → ~17m · 2 × 4"),
+            "SELECT * FROM order... -> ~17m - 2 x 4"
+        );
+        assert_eq!(transliterate("顧客"), "??");
+        assert!(matches!(transliterate("plain"), Cow::Borrowed("plain")));
+    }
+
+    #[test]
+    fn characters_split_across_writes_are_transliterated_once_complete() {
+        let mut writer = AsciiWriter::new(Vec::new(), true);
+        let ellipsis = "a…b".as_bytes();
+        writer.write_all(&ellipsis[..2]).unwrap();
+        writer.write_all(&ellipsis[2..]).unwrap();
+        writer.write_all(b"\xff!").unwrap();
+        assert_eq!(writer.into_inner(), b"a...b?!");
+
+        let mut passthrough = AsciiWriter::new(Vec::new(), false);
+        passthrough.write_all("a…b".as_bytes()).unwrap();
+        assert_eq!(passthrough.into_inner(), "a…b".as_bytes());
+    }
+}
@@ -0,0 +1,386 @@
+use crate::analysis::sampling::counter_delta;
+use crate::analysis::workload::TimeColumns;
+use crate::catalog::{self, Catalog};
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use sqlx::{Pool, Postgres, Row};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::warn;
+
+#[derive(Debug, Snafu)]
+pub enum BenchError {
+    #[snafu(display("Failed to read baseline {}: {}", path.display(), source))]
+    BaselineRead {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("{} is not a bench baseline: {}", path.display(), source))]
+    BaselineParse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[snafu(display("Failed to write baseline {}: {}", path.display(), source))]
+    BaselineWrite {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Failed to serialize baseline: {}", source))]
+    BaselineSerialize { source: serde_json::Error },
+}
+
+type Result<T, E = BenchError> = std::result::Result<T, E>;
+
+/// Characters of query text kept per statement
+const QUERY_TEXT_LEN: usize = 200;
+
+pub(crate) const DATABASE_BLOCKS_QUERY: &str = r#"
+    SELECT blks_hit, blks_read
+    FROM pg_stat_database
+    WHERE datname = current_database()
+"#;
+
+/// Calls and total time of every statement in the current database
+pub(crate) fn bench_statements_query(columns: TimeColumns) -> String {
+    format!(
+        r#"
+        SELECT
+            COALESCE(s.queryid, 0)::bigint AS queryid,
+            left(COALESCE(s.query, '<query text unavailable>'), {len}) AS query,
+            SUM(s.calls)::bigint AS calls,
+            SUM(s.{total})::double precision AS total_time_ms
+        FROM pg_stat_statements s
+        WHERE s.dbid = (SELECT oid FROM pg_database WHERE datname = current_database())
+        GROUP BY 1, 2
+        "#,
+        len = QUERY_TEXT_LEN,
+        total = columns.total,
+    )
+}
+
+/// Compact performance baseline: counters read at both ends of a window, so
+/// the numbers describe the workload running now rather than everything since
+/// the last stats reset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchSnapshot {
+    /// RFC 3339 time the window ended
+    pub captured_at: String,
+    pub database: String,
+    pub host: String,
+    pub window_secs: u64,
+    /// Share of block reads served from shared buffers during the window
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_hit_ratio: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoints_per_hour: Option<f64>,
+    /// Share of the window's checkpoints that were requested rather than timed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requested_checkpoint_share: Option<f64>,
+    /// Statements by time spent during the window, most first
+    #[serde(default)]
+    pub top_queries: Vec<BenchQuery>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchQuery {
+    pub queryid: i64,
+    pub query: String,
+    pub calls: i64,
+    pub mean_time_ms: f64,
+}
+
+/// What to capture with [`capture`]
+#[derive(Debug, Clone, Default)]
+pub struct BenchOptions {
+    pub window: Duration,
+    /// Statements kept, by time spent during the window
+    pub top: usize,
+    /// Statements kept even outside the top, e.g. a baseline's top queries
+    pub include: HashSet<i64>,
+}
+
+/// Per-statement calls and total time, keyed by queryid
+type StatementCounters = HashMap<i64, (String, i64, f64)>;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Counters {
+    blks_hit: Option<i64>,
+    blks_read: Option<i64>,
+    checkpoints_timed: Option<i64>,
+    checkpoints_req: Option<i64>,
+}
+
+/// Reads the counters, waits `opts.window` and reads them again
+pub async fn capture(
+    pool: &Pool<Postgres>,
+    catalog: &Catalog,
+    database: &str,
+    host: &str,
+    opts: &BenchOptions,
+) -> BenchSnapshot {
+    let statements_query = catalog.statement_time_columns().map(bench_statements_query);
+    if statements_query.is_none() {
+        warn!("pg_stat_statements is not installed; the baseline has no query times");
+    }
+
+    let start = fetch_counters(pool, catalog).await;
+    let start_statements = fetch_statements(pool, statements_query.as_deref()).await;
+    tokio::time::sleep(opts.window).await;
+    let end = fetch_counters(pool, catalog).await;
+    let end_statements = fetch_statements(pool, statements_query.as_deref()).await;
+
+    let hours = opts.window.as_secs_f64() / 3600.0;
+    let delta = |end: Option<i64>, start: Option<i64>| {
+        end.zip(start).map(|(end, start)| counter_delta(end, start))
+    };
+    let hit = delta(end.blks_hit, start.blks_hit);
+    let read = delta(end.blks_read, start.blks_read);
+    let timed = delta(end.checkpoints_timed, start.checkpoints_timed);
+    let requested = delta(end.checkpoints_req, start.checkpoints_req);
+    let checkpoints = timed.zip(requested).map(|(timed, req)| timed + req);
+
+    BenchSnapshot {
+        captured_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+        database: database.to_string(),
+        host: host.to_string(),
+        window_secs: opts.window.as_secs(),
+        cache_hit_ratio: hit
+            .zip(read)
+            .filter(|(hit, read)| hit + read > 0)
+            .map(|(hit, read)| hit as f64 / (hit + read) as f64),
+        checkpoints_per_hour: checkpoints
+            .filter(|_| hours > 0.0)
+            .map(|count| count as f64 / hours),
+        requested_checkpoint_share: requested
+            .zip(checkpoints)
+            .filter(|(_, total)| *total > 0)
+            .map(|(requested, total)| requested as f64 / total as f64),
+        top_queries: top_queries(&start_statements, end_statements, opts),
+    }
+}
+
+async fn fetch_counters(pool: &Pool<Postgres>, catalog: &Catalog) -> Counters {
+    let checkpoints = catalog::fetch_checkpoint_counters(pool, catalog).await;
+    let (blks_hit, blks_read) = match sqlx::query(DATABASE_BLOCKS_QUERY).fetch_one(pool).await {
+        Ok(row) => (row.try_get("blks_hit").ok(), row.try_get("blks_read").ok()),
+        Err(err) => {
+            warn!("Failed to read pg_stat_database: {err}");
+            (None, None)
+        }
+    };
+    Counters {
+        blks_hit,
+        blks_read,
+        checkpoints_timed: checkpoints.timed,
+        checkpoints_req: checkpoints.requested,
+    }
+}
+
+async fn fetch_statements(pool: &Pool<Postgres>, query: Option<&str>) -> StatementCounters {
+    let Some(query) = query else {
+        return HashMap::new();
+    };
+    match sqlx::query(query).fetch_all(pool).await {
+        Ok(rows) => rows
+            .iter()
+            .map(|row| {
+                (
+                    row.get("queryid"),
+                    (row.get("query"), row.get("calls"), row.get("total_time_ms")),
+                )
+            })
+            .collect(),
+        Err(err) => {
+            warn!("Failed to read pg_stat_statements: {err}");
+            HashMap::new()
+        }
+    }
+}
+
+/// Statements called during the window, the `opts.top` slowest in total plus
+/// any in `opts.include`
+fn top_queries(
+    start: &StatementCounters,
+    end: StatementCounters,
+    opts: &BenchOptions,
+) -> Vec<BenchQuery> {
+    let mut called: Vec<_> = end
+        .into_iter()
+        .filter_map(|(queryid, (query, calls, total))| {
+            let (start_calls, start_total) = start
+                .get(&queryid)
+                .map_or((0, 0.0), |(_, calls, total)| (*calls, *total));
+            let calls = counter_delta(calls, start_calls);
+            // A reset mid-window leaves the end value as the best estimate
+            let total = if total >= start_total {
+                total - start_total
+            } else {
+                total
+            };
+            (calls > 0).then_some((queryid, query, calls, total))
+        })
+        .collect();
+    called.sort_by(|a, b| b.3.total_cmp(&a.3).then(a.0.cmp(&b.0)));
+
+    called
+        .into_iter()
+        .enumerate()
+        .filter(|(rank, (queryid, ..))| *rank < opts.top || opts.include.contains(queryid))
+        .map(|(_, (queryid, query, calls, total))| BenchQuery {
+            queryid,
+            query,
+            calls,
+            mean_time_ms: total / calls as f64,
+        })
+        .collect()
+}
+
+/// A baseline next to a later capture
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchComparison {
+    pub before: BenchSnapshot,
+    pub after: BenchSnapshot,
+    /// The baseline's top queries with their mean time in both captures
+    pub queries: Vec<QueryComparison>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryComparison {
+    pub queryid: i64,
+    pub query: String,
+    pub before_mean_ms: f64,
+    /// `None` when the query was not called during the second window
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after_mean_ms: Option<f64>,
+}
+
+impl QueryComparison {
+    /// Relative change of the mean time; negative is faster
+    pub fn change(&self) -> Option<f64> {
+        self.after_mean_ms
+            .filter(|_| self.before_mean_ms > 0.0)
+            .map(|after| after / self.before_mean_ms - 1.0)
+    }
+}
+
+pub fn compare(before: BenchSnapshot, after: BenchSnapshot) -> BenchComparison {
+    let after_means: HashMap<_, _> = after
+        .top_queries
+        .iter()
+        .map(|query| (query.queryid, query.mean_time_ms))
+        .collect();
+    let queries = before
+        .top_queries
+        .iter()
+        .map(|query| QueryComparison {
+            queryid: query.queryid,
+            query: query.query.clone(),
+            before_mean_ms: query.mean_time_ms,
+            after_mean_ms: after_means.get(&query.queryid).copied(),
+        })
+        .collect();
+    BenchComparison {
+        before,
+        after,
+        queries,
+    }
+}
+
+pub fn save(path: &Path, snapshot: &BenchSnapshot) -> Result<()> {
+    let json = serde_json::to_vec_pretty(snapshot).context(BaselineSerializeSnafu)?;
+    fs::write(path, json).context(BaselineWriteSnafu { path })
+}
+
+pub fn load(path: &Path) -> Result<BenchSnapshot> {
+    let bytes = fs::read(path).context(BaselineReadSnafu { path })?;
+    serde_json::from_slice(&bytes).context(BaselineParseSnafu { path })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statements(rows: &[(i64, i64, f64)]) -> StatementCounters {
+        rows.iter()
+            .map(|&(queryid, calls, total)| (queryid, (format!("SELECT {queryid}"), calls, total)))
+            .collect()
+    }
+
+    #[test]
+    fn top_queries_rank_by_time_spent_during_the_window() {
+        let start = statements(&[(1, 100, 1_000.0), (2, 10, 50.0), (3, 5, 500.0)]);
+        let end = statements(&[
+            (1, 110, 1_020.0),
+            (2, 20, 450.0),
+            (3, 5, 500.0),
+            (4, 2, 30.0),
+        ]);
+        let opts = BenchOptions {
+            top: 2,
+            ..BenchOptions::default()
+        };
+
+        let top: Vec<_> = top_queries(&start, end, &opts)
+            .into_iter()
+            .map(|query| (query.queryid, query.calls, query.mean_time_ms))
+            .collect();
+        // Query 3 was not called; query 1 is outside the top 2
+        assert_eq!(top, [(2, 10, 40.0), (4, 2, 15.0)]);
+    }
+
+    #[test]
+    fn included_queries_are_kept_outside_the_top() {
+        let start = statements(&[(1, 100, 1_000.0)]);
+        let end = statements(&[(1, 110, 1_020.0), (2, 20, 450.0)]);
+        let opts = BenchOptions {
+            top: 1,
+            include: HashSet::from([1]),
+            ..BenchOptions::default()
+        };
+
+        let ids: Vec<_> = top_queries(&start, end, &opts)
+            .into_iter()
+            .map(|query| query.queryid)
+            .collect();
+        assert_eq!(ids, [2, 1]);
+    }
+
+    #[test]
+    fn comparison_follows_the_baseline_queries() {
+        let snapshot = |queries: &[(i64, f64)]| BenchSnapshot {
+            captured_at: String::new(),
+            database: "app".to_string(),
+            host: "db1".to_string(),
+            window_secs: 60,
+            cache_hit_ratio: None,
+            checkpoints_per_hour: None,
+            requested_checkpoint_share: None,
+            top_queries: queries
+                .iter()
+                .map(|&(queryid, mean_time_ms)| BenchQuery {
+                    queryid,
+                    query: String::new(),
+                    calls: 1,
+                    mean_time_ms,
+                })
+                .collect(),
+        };
+
+        let comparison = compare(
+            snapshot(&[(1, 20.0), (2, 5.0)]),
+            snapshot(&[(1, 15.0), (3, 1.0)]),
+        );
+        let changes: Vec<_> = comparison
+            .queries
+            .iter()
+            .map(|query| (query.queryid, query.change()))
+            .collect();
+        assert_eq!(changes, [(1, Some(-0.25)), (2, None)]);
+    }
+}
@@ -0,0 +1,252 @@
+use crate::analysis::workload::TimeColumns;
+use crate::models::BufferWrites;
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres, Row};
+use tracing::warn;
+
+pub(crate) const CATALOG_QUERY: &str = r#"
+    SELECT
+        current_setting('server_version_num')::bigint AS server_version,
+        ARRAY(
+            SELECT DISTINCT a.attname::text
+            FROM pg_attribute a
+            JOIN pg_class c ON c.oid = a.attrelid
+            WHERE c.relname = 'pg_stat_statements'
+              AND c.relkind = 'v'
+              AND a.attnum > 0
+              AND NOT a.attisdropped
+            ORDER BY 1
+        ) AS pg_stat_statements_columns
+"#;
+
+/// Server version and the columns of the views whose shape changes between
+/// releases, read once per run so fetchers pick their SQL without probing.
+///
+/// `pg_stat_statements` columns follow the installed extension version, which
+/// can lag the server's until `ALTER EXTENSION ... UPDATE`, so they are read
+/// from the view rather than derived from `server_version`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Catalog {
+    /// `server_version_num`, e.g. `170002`
+    pub server_version: Option<i64>,
+    /// Empty when the extension is not installed or the catalog could not be read
+    pub pg_stat_statements_columns: Vec<String>,
+}
+
+impl Catalog {
+    pub async fn detect(pool: &Pool<Postgres>) -> Self {
+        match sqlx::query(CATALOG_QUERY).fetch_one(pool).await {
+            Ok(row) => Self {
+                server_version: row.get("server_version"),
+                pg_stat_statements_columns: row.get("pg_stat_statements_columns"),
+            },
+            Err(err) => {
+                warn!("Failed to read the server version: {err}");
+                Self::default()
+            }
+        }
+    }
+
+    /// Whether the server is `version_num` or newer; false when unknown
+    pub fn at_least(&self, version_num: i64) -> bool {
+        self.server_version
+            .is_some_and(|version| version >= version_num)
+    }
+
+    /// Checkpoint counters moved from `pg_stat_bgwriter` to
+    /// `pg_stat_checkpointer` in PostgreSQL 17
+    pub fn has_pg_stat_checkpointer(&self) -> bool {
+        self.at_least(170_000)
+    }
+
+    /// Per-backend-type I/O counters, PostgreSQL 16+
+    pub fn has_pg_stat_io(&self) -> bool {
+        self.at_least(160_000)
+    }
+
+    /// `pg_stats_ext`, PostgreSQL 12+
+    pub fn has_pg_stats_ext(&self) -> bool {
+        self.at_least(120_000)
+    }
+
+    /// Backend IDs from `pg_stat_get_backend_idset()` match the `pg_temp_N`
+    /// numbering from PostgreSQL 16
+    pub fn backend_ids_match_temp_schemas(&self) -> bool {
+        self.at_least(160_000)
+    }
+
+    pub fn statements_have(&self, column: &str) -> bool {
+        self.pg_stat_statements_columns
+            .iter()
+            .any(|name| name == column)
+    }
+
+    /// Total and max time columns; pg_stat_statements 1.8 (PostgreSQL 13)
+    /// split planning from execution time. `None` when neither is present.
+    pub(crate) fn statement_time_columns(&self) -> Option<TimeColumns> {
+        if self.statements_have("total_exec_time") {
+            Some(TimeColumns {
+                total: "total_exec_time",
+                max: "max_exec_time",
+                io: None,
+            })
+        } else if self.statements_have("total_time") {
+            Some(TimeColumns {
+                total: "total_time",
+                max: "max_time",
+                io: None,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Block read and write time columns; pg_stat_statements 1.11
+    /// (PostgreSQL 17) renamed `blk_read_time` to `shared_blk_read_time`
+    pub(crate) fn statement_io_columns(&self) -> Option<(&'static str, &'static str)> {
+        if self.statements_have("shared_blk_read_time") {
+            Some(("shared_blk_read_time", "shared_blk_write_time"))
+        } else if self.statements_have("blk_read_time") {
+            Some(("blk_read_time", "blk_write_time"))
+        } else {
+            None
+        }
+    }
+}
+
+pub(crate) const CHECKPOINTER_QUERY: &str =
+    "SELECT num_timed AS checkpoints_timed, num_requested AS checkpoints_req FROM pg_stat_checkpointer";
+pub(crate) const BGWRITER_QUERY: &str =
+    "SELECT checkpoints_timed, checkpoints_req FROM pg_stat_bgwriter";
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct CheckpointCounters {
+    pub timed: Option<i64>,
+    pub requested: Option<i64>,
+}
+
+/// The checkpoint counter queries to try, in order. With the version unknown,
+/// `pg_stat_checkpointer` is tried first.
+fn checkpoint_queries(catalog: &Catalog) -> &'static [&'static str] {
+    match catalog.server_version {
+        Some(_) if catalog.has_pg_stat_checkpointer() => &[CHECKPOINTER_QUERY],
+        Some(_) => &[BGWRITER_QUERY],
+        None => &[CHECKPOINTER_QUERY, BGWRITER_QUERY],
+    }
+}
+
+/// Timed and requested checkpoints from the view this server keeps them in
+pub async fn fetch_checkpoint_counters(
+    pool: &Pool<Postgres>,
+    catalog: &Catalog,
+) -> CheckpointCounters {
+    let mut errors = Vec::new();
+    for query in checkpoint_queries(catalog) {
+        match sqlx::query(query).fetch_one(pool).await {
+            Ok(row) => {
+                return CheckpointCounters {
+                    timed: row.try_get("checkpoints_timed").ok(),
+                    requested: row.try_get("checkpoints_req").ok(),
+                }
+            }
+            Err(err) => errors.push(err.to_string()),
+        }
+    }
+    warn!("Failed to read checkpoint stats: {}", errors.join("; "));
+    CheckpointCounters::default()
+}
+
+pub(crate) const IO_WRITES_QUERY: &str = r#"
+    SELECT
+        sum(writes) FILTER (WHERE backend_type = 'checkpointer')::bigint AS checkpointer,
+        sum(writes) FILTER (WHERE backend_type = 'background writer')::bigint AS background_writer,
+        sum(writes) FILTER (WHERE backend_type = 'client backend')::bigint AS backends
+    FROM pg_stat_io
+    WHERE object = 'relation'
+"#;
+pub(crate) const BGWRITER_WRITES_QUERY: &str = r#"
+    SELECT
+        buffers_checkpoint AS checkpointer,
+        buffers_clean AS background_writer,
+        buffers_backend AS backends
+    FROM pg_stat_bgwriter
+"#;
+
+/// Reads buffer writes from `pg_stat_io` on PostgreSQL 16+ or from
+/// `pg_stat_bgwriter` on older servers; `None` when the version is unknown
+pub async fn fetch_buffer_writes(pool: &Pool<Postgres>, catalog: &Catalog) -> Option<BufferWrites> {
+    let query = if catalog.has_pg_stat_io() {
+        IO_WRITES_QUERY
+    } else if catalog.server_version.is_some() {
+        BGWRITER_WRITES_QUERY
+    } else {
+        return None;
+    };
+    match sqlx::query(query).fetch_one(pool).await {
+        Ok(row) => Some(BufferWrites {
+            checkpointer: row.try_get("checkpointer").ok(),
+            background_writer: row.try_get("background_writer").ok(),
+            backends: row.try_get("backends").ok(),
+        }),
+        Err(err) => {
+            warn!("Failed to read buffer writes: {err}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn catalog(version: Option<i64>, columns: &[&str]) -> Catalog {
+        Catalog {
+            server_version: version,
+            pg_stat_statements_columns: columns.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    #[rstest]
+    #[case::pg17(&["total_exec_time", "shared_blk_read_time"], Some("total_exec_time"), Some("shared_blk_read_time"))]
+    #[case::pg13(&["total_exec_time", "blk_read_time"], Some("total_exec_time"), Some("blk_read_time"))]
+    #[case::pg12(&["total_time", "blk_read_time"], Some("total_time"), Some("blk_read_time"))]
+    #[case::not_installed(&[], None, None)]
+    fn statement_columns_follow_the_extension(
+        #[case] columns: &[&str],
+        #[case] total: Option<&str>,
+        #[case] read_time: Option<&str>,
+    ) {
+        // An old extension on a new server keeps the old names
+        let catalog = catalog(Some(170_000), columns);
+        assert_eq!(catalog.statement_time_columns().map(|c| c.total), total);
+        assert_eq!(catalog.statement_io_columns().map(|c| c.0), read_time);
+    }
+
+    #[rstest]
+    #[case(Some(170_002), true, true)]
+    #[case(Some(160_004), false, true)]
+    #[case(Some(150_008), false, false)]
+    #[case(None, false, false)]
+    fn views_follow_the_server_version(
+        #[case] version: Option<i64>,
+        #[case] checkpointer: bool,
+        #[case] io: bool,
+    ) {
+        let catalog = catalog(version, &[]);
+        assert_eq!(catalog.has_pg_stat_checkpointer(), checkpointer);
+        assert_eq!(catalog.has_pg_stat_io(), io);
+    }
+
+    #[rstest]
+    #[case::pg17(Some(170_000), &[CHECKPOINTER_QUERY])]
+    #[case::pg18(Some(180_001), &[CHECKPOINTER_QUERY])]
+    #[case::pg16(Some(160_004), &[BGWRITER_QUERY])]
+    #[case::unknown(None, &[CHECKPOINTER_QUERY, BGWRITER_QUERY])]
+    fn checkpoint_counters_come_from_the_servers_view(
+        #[case] version: Option<i64>,
+        #[case] expected: &[&str],
+    ) {
+        assert_eq!(checkpoint_queries(&catalog(version, &[])), expected);
+    }
+}
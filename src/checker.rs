@@ -1,13 +1,35 @@
+use crate::analysis::backups::{self, BackupCheck};
+use crate::analysis::catalog_cache::CatalogCache;
+use crate::analysis::replicas::{self, ReplicaIndexScans};
+use crate::analysis::sampling::{self, StatsBaseline};
+use crate::analysis::table_index::TableIndexInputs;
+use crate::analysis::version::ReleaseTable;
 use crate::analysis::workload::WorkloadOptions;
 use crate::analysis::{
-    autovacuum, concurrency, logging, memory, planner, table_index, wal, workload,
+    autovacuum, checkpoints, concurrency, connection_history, connection_memory, cursors,
+    extended_stats, extensions, host_layout, integrity, log_volume, logging, managed, memory,
+    os_limits, pending_changes, pitr, planner, query_fingerprint, reconcile, resilience,
+    schema_lint, security, table_index, temp_schemas, tenants, toast, validation, version, wal,
+    workload,
 };
+use crate::apply::{self, ApplyStep, PlannedChange};
+use crate::bench::{self, BenchOptions, BenchSnapshot};
+use crate::catalog::{self, Catalog};
 use crate::config::DbConfig;
-use crate::models::{AnalysisResults, PgConfigParam, SystemStats, WorkloadResults};
+use crate::fingerprint;
+use crate::fixture::{Fixture, FixtureError};
+use crate::models::{
+    AnalysisResults, ConnectionSample, ParamValue, PgConfigParam, SystemStats, WorkloadResults,
+};
+use crate::signing::RunMetadata;
+use crate::telemetry;
+use chrono::Utc;
 use snafu::{ResultExt, Snafu};
-use sqlx::{postgres::PgPoolOptions, query_scalar, Pool, Postgres, Row};
+use sqlx::{postgres::PgPoolOptions, query_scalar, Connection, PgConnection, Pool, Postgres, Row};
 use std::collections::HashMap;
-use tracing::{debug, info, warn};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, info_span, warn, Instrument};
 
 #[derive(Debug, Snafu)]
 pub enum CheckerError {
@@ -16,17 +38,129 @@ pub enum CheckerError {
 
     #[snafu(display("Failed to execute query: {}", query))]
     QueryError { query: String, source: sqlx::Error },
+
+    #[snafu(display(
+        "No primary found among {}; every host is unreachable or in recovery",
+        hosts
+    ))]
+    NoPrimary { hosts: String },
+
+    #[snafu(display("{}", source))]
+    FixtureError { source: FixtureError },
+
+    /// An error known only by its message, such as one replayed from a fixture
+    #[snafu(display("{}", message))]
+    Other { message: String },
 }
 
 type Result<T, E = CheckerError> = std::result::Result<T, E>;
 
+/// Run options for [`ConfigChecker::analyze`]
+#[derive(Debug, Clone, Default)]
+pub struct AnalyzeOptions {
+    /// When set, cumulative counters are read twice this far apart and analyzed as deltas
+    pub sample_window: Option<Duration>,
+    /// Include remediation DDL in index removal plans and an `ALTER SYSTEM`
+    /// script with its rollback for the suggestions
+    pub emit_ddl: bool,
+    /// Attach pg_stat_statements findings to the results as a workload section
+    pub with_workload: bool,
+    /// Run every analysis in one pass and fold query-derived index findings
+    /// into the table/index results, implies `with_workload`
+    pub deep: bool,
+    /// Replace literals and object names in the workload's and idle cursor
+    /// sessions' query text, see [`WorkloadOptions::redact_queries`]
+    pub redact_queries: bool,
+    /// Minor releases and EOL dates the server version is compared against
+    pub releases: ReleaseTable,
+    /// Inspect the server's OS limits, kernel settings and data directory
+    /// filesystem; only meaningful when running on the database host
+    pub host_checks: bool,
+    /// Connection counts from earlier runs, e.g. the scheduler's snapshots,
+    /// added to this run's samples for the saturation history
+    pub connection_history: Vec<ConnectionSample>,
+    /// Look for a successful backup within an RPO
+    pub backup: Option<BackupCheck>,
+    /// Aggregate table storage, activity and findings per schema and rank the
+    /// schemas, e.g. tenants of a schema-per-tenant database
+    pub group_by_schema: bool,
+    /// Scan user tables for column type and constraint anti-patterns
+    pub schema_lint: bool,
+    /// Leave out the per-index statistics, the workload index catalog and the
+    /// table sampling, and read fewer TOAST tables and statements, for busy
+    /// primaries; pair with [`ConfigChecker::gentle`]
+    pub gentle: bool,
+    /// Table and index findings listed per kind, e.g. unused indexes; `None`
+    /// keeps each kind's built-in cap and `Some(0)` lists every finding
+    pub max_findings_per_kind: Option<usize>,
+}
+
+/// Pause before each statement of a [`ConfigChecker::gentle`] run
+const GENTLE_QUERY_DELAY: Duration = Duration::from_millis(250);
+/// `statement_timeout` of a [`ConfigChecker::gentle`] run, so a slow catalog
+/// read is cancelled instead of holding locks and a backend
+const GENTLE_STATEMENT_TIMEOUT: &str = "2s";
+/// Statements listed per slow-query group with [`AnalyzeOptions::gentle`]
+const GENTLE_WORKLOAD_LIMIT: usize = 5;
+
 pub struct ConfigChecker {
     config: DbConfig,
     pool: Pool<Postgres>,
+    fixture: Fixture,
+    /// Read at the start of each run so fetchers query the views this server has
+    catalog: Catalog,
 }
 
+pub(crate) const PARAMS_QUERY: &str = r#"
+    SELECT
+        name,
+        setting,
+        unit,
+        vartype,
+        context,
+        boot_val,
+        source,
+        sourcefile,
+        pending_restart,
+        min_val,
+        max_val
+    FROM pg_settings
+    ORDER BY name
+"#;
+pub(crate) const PARAMETER_QUERY: &str = r#"
+    SELECT
+        name,
+        setting,
+        unit,
+        vartype,
+        context,
+        boot_val,
+        source,
+        sourcefile,
+        pending_restart,
+        min_val,
+        max_val
+    FROM pg_settings
+    WHERE name = lower($1)
+"#;
+
+/// Sent by `postgreat apply` only, after its `ALTER SYSTEM` statements
+pub(crate) const RELOAD_QUERY: &str = "SELECT pg_reload_conf()";
+
+pub(crate) const VERSION_QUERY: &str = "SELECT version()";
+pub(crate) const IN_RECOVERY_QUERY: &str = "SELECT pg_is_in_recovery()";
+
+/// How long each candidate in `hosts` gets to answer the primary probe
+const PRIMARY_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
 impl ConfigChecker {
-    pub async fn new(config: DbConfig) -> Result<Self> {
+    pub async fn new(mut config: DbConfig) -> Result<Self> {
+        if !config.hosts.is_empty() {
+            let (host, port) = discover_primary(&config).await?;
+            config.host = host;
+            config.port = port;
+        }
+
         info!(
             "Connecting to PostgreSQL at {}:{}",
             config.host, config.port
@@ -40,99 +174,560 @@ impl ConfigChecker {
 
         info!("Successfully connected to database: {}", config.database);
 
-        Ok(Self { config, pool })
+        Ok(Self {
+            config,
+            pool,
+            fixture: Fixture::Live,
+            catalog: Catalog::default(),
+        })
+    }
+
+    /// A checker that serves every query from a fixture recorded with
+    /// [`ConfigChecker::recording`] and never connects. Host checks are
+    /// skipped, as the recorded server's host is not this one.
+    pub fn from_fixture(config: DbConfig, dir: PathBuf) -> Self {
+        info!("Replaying fixture {}", dir.display());
+        let pool = PgPoolOptions::new()
+            .max_connections(1)
+            .connect_lazy_with(config.connection_options());
+        Self {
+            config,
+            pool,
+            fixture: Fixture::Replay(dir),
+            catalog: Catalog::default(),
+        }
+    }
+
+    /// Runs every statement on one connection with a pause before each and a
+    /// short `statement_timeout`. Replayed fixtures are not paced.
+    pub fn gentle(mut self) -> Self {
+        if self.fixture.is_replay() {
+            return self;
+        }
+        self.pool = PgPoolOptions::new()
+            .max_connections(1)
+            .before_acquire(|_, _| {
+                Box::pin(async {
+                    tokio::time::sleep(GENTLE_QUERY_DELAY).await;
+                    Ok(true)
+                })
+            })
+            .connect_lazy_with(
+                self.config
+                    .connection_options()
+                    .options([("statement_timeout", GENTLE_STATEMENT_TIMEOUT)]),
+            );
+        self
+    }
+
+    /// Writes the result of every query to `dir` as it runs, for replaying
+    /// with [`ConfigChecker::from_fixture`]
+    pub fn recording(mut self, dir: PathBuf) -> Self {
+        self.fixture = Fixture::Record(dir);
+        self
     }
 
-    pub async fn analyze(&mut self) -> Result<AnalysisResults> {
+    #[tracing::instrument(
+        name = "analyze",
+        skip_all,
+        fields(database = %self.config.database, host = %self.config.host)
+    )]
+    pub async fn analyze(&mut self, opts: &AnalyzeOptions) -> Result<AnalysisResults> {
         let mut results = AnalysisResults::default();
+        self.detect_catalog().await;
 
         // Fetch all configuration parameters
         info!("Fetching configuration parameters...");
-        let params = self.fetch_config_params().await?;
+        let started = Instant::now();
+        let params = self
+            .fixture
+            .try_serve("settings", self.fetch_config_params())
+            .await?;
+        telemetry::record_query(&self.config.database, "pg_settings", started.elapsed());
         results.params = params;
 
-        let stats = self.fetch_system_stats().await?;
+        let baseline = match opts.sample_window {
+            Some(window) => {
+                info!(
+                    "Sampling cumulative statistics over {}s...",
+                    window.as_secs()
+                );
+                let baseline = self
+                    .fixture
+                    .try_serve(
+                        "stats_baseline",
+                        StatsBaseline::capture(&self.pool, &self.catalog),
+                    )
+                    .await?;
+                let samples = self
+                    .fixture
+                    .serve(
+                        "connection_samples",
+                        connection_history::sample_over(&self.pool, window),
+                    )
+                    .await;
+                Some((baseline, samples))
+            }
+            None => None,
+        };
+        let (baseline, connection_samples) = baseline.unzip();
+
+        let started = Instant::now();
+        let mut stats = self.fetch_system_stats(opts.gentle).await?;
+        telemetry::record_query(&self.config.database, "system_stats", started.elapsed());
+        if let (Some(window), Some(baseline)) = (opts.sample_window, &baseline) {
+            stats.sample_window_secs = Some(window.as_secs());
+            stats.checkpoints_timed = stats
+                .checkpoints_timed
+                .zip(baseline.checkpoints_timed)
+                .map(|(end, start)| sampling::counter_delta(end, start));
+            stats.checkpoints_req = stats
+                .checkpoints_req
+                .zip(baseline.checkpoints_req)
+                .map(|(end, start)| sampling::counter_delta(end, start));
+            stats.wal_bytes = stats
+                .wal_bytes
+                .zip(baseline.wal_bytes)
+                .map(|(end, start)| sampling::counter_delta(end, start));
+            stats.wal_fpi = stats
+                .wal_fpi
+                .zip(baseline.wal_fpi)
+                .map(|(end, start)| sampling::counter_delta(end, start));
+            stats.wal_stats_age_secs = Some(window.as_secs());
+            stats.temp_files = stats
+                .temp_files
+                .zip(baseline.temp_files)
+                .map(|(end, start)| sampling::counter_delta(end, start));
+            stats.temp_bytes = stats
+                .temp_bytes
+                .zip(baseline.temp_bytes)
+                .map(|(end, start)| sampling::counter_delta(end, start));
+            stats.peak_connection_count =
+                stats.peak_connection_count.max(baseline.connection_count);
+        }
+        stats.connection_samples = connection_samples.unwrap_or_default();
+        stats.connection_samples.extend(
+            self.fixture
+                .serve(
+                    "connection_sample",
+                    connection_history::fetch_connection_sample(&self.pool),
+                )
+                .await,
+        );
+        let history: Vec<_> = opts
+            .connection_history
+            .iter()
+            .chain(&stats.connection_samples)
+            .cloned()
+            .collect();
+        results.connection_saturation = connection_history::summarize(&history, &results.params);
+        for warning in sampling::stats_age_warnings(&stats) {
+            warn!("{warning}");
+            results.warnings.push(warning);
+        }
         results.system_stats = stats;
 
-        // Run analysis checks
-        if self.config.compute.is_none() {
-            warn!("No compute specification provided; CPU and memory-based recommendations will be limited. Use --compute <tier|<vCPU>vCPU-<GB>GB> to enable full guidance.");
+        match self
+            .fixture
+            .try_serve(
+                "pending_changes",
+                pending_changes::fetch_pending_changes(&self.pool, &results.params),
+            )
+            .await
+        {
+            Ok(changes) => results.pending_changes = changes,
+            Err(err) => warn!("Staged configuration changes not checked: {err}"),
         }
 
-        let params_snapshot = results.params.clone();
-        let stats_snapshot = results.system_stats.clone();
+        if opts.host_checks && self.fixture.is_replay() {
+            results
+                .warnings
+                .push("Host checks skipped: replaying a fixture.".to_string());
+        } else if opts.host_checks {
+            self.analyze_host(&mut results).await;
+        }
+        if let Some(check) = &opts.backup {
+            self.analyze_backups(check, &mut results).await;
+        }
 
-        info!("Running memory configuration analysis...");
-        memory::analyze_memory(&params_snapshot, &stats_snapshot, &mut results)?;
+        analyze_settings(&self.config.database, &opts.releases, &mut results)?;
 
-        info!("Running concurrency analysis...");
-        concurrency::analyze_concurrency(&params_snapshot, &stats_snapshot, &mut results)?;
+        let replica_scans = self
+            .fixture
+            .serve("replica_index_scans", async {
+                if self.config.replicas.is_empty() {
+                    None
+                } else {
+                    Some(ReplicaIndexScans::collect(&self.config).await)
+                }
+            })
+            .await;
 
-        info!("Running WAL configuration analysis...");
-        wal::analyze_wal(&params_snapshot, &stats_snapshot, &mut results)?;
+        let indexes = self.read_catalog_cache(opts.gentle).await;
 
-        info!("Running planner analysis...");
-        planner::analyze_planner(&params_snapshot, &stats_snapshot, &mut results)?;
+        info!("Running table and index health analysis...");
+        let inputs = TableIndexInputs {
+            baseline: baseline.as_ref(),
+            replica_scans: replica_scans.as_ref(),
+            fixture: &self.fixture,
+            catalog: Some(&indexes),
+            gentle: opts.gentle,
+            max_findings_per_kind: opts.max_findings_per_kind,
+        };
+        let started = Instant::now();
+        if let Err(err) = table_index::analyze_table_index_health(&self.pool, &mut results, inputs)
+            .instrument(info_span!("analysis_phase", phase = "table_index"))
+            .await
+        {
+            warn!("Table/index health analysis skipped: {err}");
+        }
+        telemetry::record_phase(&self.config.database, "table_index", started.elapsed());
+        cursors::analyze_open_cursors(&mut results);
+        if opts.redact_queries {
+            for session in &mut results.system_stats.cursor_sessions {
+                session.query = query_fingerprint::redact(&session.query);
+            }
+        }
 
-        info!("Running autovacuum analysis...");
-        autovacuum::analyze_autovacuum(&params_snapshot, &stats_snapshot, &mut results)?;
+        if opts.group_by_schema {
+            info!("Aggregating usage per schema...");
+            match self
+                .fixture
+                .try_serve("schema_usage", tenants::fetch_schema_usage(&self.pool))
+                .await
+            {
+                Ok(usage) => {
+                    results.schema_usage = tenants::rank_schemas(usage, &results.table_health)
+                }
+                Err(err) => {
+                    warn!("Per-schema usage skipped: {err}");
+                    results
+                        .warnings
+                        .push(format!("Per-schema usage skipped: {err}"));
+                }
+            }
+        }
 
-        info!("Running logging analysis...");
-        logging::analyze_logging(&params_snapshot, &stats_snapshot, &mut results)?;
+        if opts.schema_lint {
+            info!("Running schema lint...");
+            match self
+                .fixture
+                .try_serve("schema_lint", schema_lint::fetch_schema_lint(&self.pool))
+                .await
+            {
+                Ok(lint) => schema_lint::analyze_schema_lint(&lint, &mut results),
+                Err(err) => {
+                    warn!("Schema lint skipped: {err}");
+                    results.warnings.push(format!("Schema lint skipped: {err}"));
+                }
+            }
+        }
 
-        info!("Running table and index health analysis...");
-        if let Err(err) = table_index::analyze_table_index_health(&self.pool, &mut results).await {
-            warn!("Table/index health analysis skipped: {err}");
+        if opts.with_workload || opts.deep {
+            info!("Running workload analysis...");
+            let started = Instant::now();
+            let workload_opts = if opts.gentle {
+                WorkloadOptions {
+                    limit: GENTLE_WORKLOAD_LIMIT,
+                    gentle: true,
+                    redact_queries: opts.redact_queries,
+                    ..WorkloadOptions::default()
+                }
+            } else {
+                WorkloadOptions {
+                    redact_queries: opts.redact_queries,
+                    ..WorkloadOptions::default()
+                }
+            };
+            let workload = self
+                .run_workload(&workload_opts, &indexes)
+                .instrument(info_span!("analysis_phase", phase = "workload"))
+                .await;
+            telemetry::record_phase(&self.config.database, "workload", started.elapsed());
+            match workload {
+                Ok((mut workload, _)) => {
+                    if opts.deep {
+                        results.table_health.index_usage_info = workload::merge_index_findings(
+                            std::mem::take(&mut results.table_health.index_usage_info),
+                            std::mem::take(&mut workload.table_health.index_usage_info),
+                        );
+                    }
+                    workload::correlate_table_health(
+                        &mut workload.query_index_candidates,
+                        &results.table_health,
+                    );
+                    results.workload = Some(workload);
+                }
+                Err(err) => {
+                    warn!("Workload analysis skipped: {err}");
+                    results
+                        .warnings
+                        .push(format!("Workload analysis skipped: {err}"));
+                }
+            }
+        }
+
+        results.index_removal_plans =
+            table_index::plan_index_removals(&results.table_health.index_usage_info, opts.emit_ddl);
+        if opts.emit_ddl && results.managed_remediation.is_none() {
+            results.settings_remediation = apply::plan_remediation(&results);
         }
 
+        fingerprint::assign(&mut results);
+        results.run = Some(RunMetadata::capture(&self.config));
+        telemetry::record_findings(&self.config.database, &results);
         Ok(results)
     }
 
+    #[tracing::instrument(
+        name = "analyze_workload",
+        skip_all,
+        fields(database = %self.config.database, host = %self.config.host)
+    )]
     pub async fn analyze_workload(&mut self, opts: WorkloadOptions) -> Result<WorkloadResults> {
-        let analysis = workload::analyze(&self.pool, &opts).await?;
-        let mut results = analysis.results;
-        if !analysis.available {
+        self.detect_catalog().await;
+        let indexes = self.read_catalog_cache(opts.gentle).await;
+        let (mut results, available) = self.run_workload(&opts, &indexes).await?;
+        if !available {
             return Ok(results);
         }
 
         info!("Running table and index health analysis...");
         let mut table_results = AnalysisResults::default();
+        let inputs = TableIndexInputs {
+            catalog: Some(&indexes),
+            gentle: opts.gentle,
+            ..TableIndexInputs::default()
+        };
         if let Err(err) =
-            table_index::analyze_table_index_health(&self.pool, &mut table_results).await
+            table_index::analyze_table_index_health(&self.pool, &mut table_results, inputs).await
         {
             warn!("Table/index health analysis skipped: {err}");
         } else {
-            results.bloat_info = table_results.bloat_info;
-            results.seq_scan_info = table_results.seq_scan_info;
-            results.index_usage_info = table_results.index_usage_info;
-            workload::correlate_table_health(&mut results);
+            table_results.table_health.index_usage_info = workload::merge_index_findings(
+                table_results.table_health.index_usage_info,
+                std::mem::take(&mut results.table_health.index_usage_info),
+            );
+            workload::correlate_table_health(
+                &mut results.query_index_candidates,
+                &table_results.table_health,
+            );
+            results.table_health = table_results.table_health;
         }
+        fingerprint::assign_table_health(&mut results.table_health);
 
         Ok(results)
     }
 
-    async fn fetch_config_params(&self) -> Result<HashMap<String, PgConfigParam>> {
-        let query = r#"
-            SELECT
-                name,
-                setting,
+    /// Reads the live value and context of every parameter a plan changes, for
+    /// `postgreat apply`
+    pub async fn fetch_plan_settings(
+        &self,
+        steps: &[ApplyStep],
+    ) -> Result<HashMap<String, PgConfigParam>> {
+        let mut settings = HashMap::new();
+        for step in steps {
+            if let Some(param) = self.fetch_parameter(&step.parameter).await? {
+                settings.insert(step.parameter.clone(), param);
+            }
+        }
+        Ok(settings)
+    }
+
+    /// Runs the confirmed `ALTER SYSTEM` statements one by one, then reloads the
+    /// configuration once. `ALTER SYSTEM` cannot run in a transaction, so a
+    /// failure leaves the earlier statements applied.
+    pub async fn apply_changes(&self, changes: &[PlannedChange]) -> Result<()> {
+        for change in changes {
+            let sql = change.step.sql();
+            info!("{}", sql);
+            sqlx::raw_sql(&sql)
+                .execute(&self.pool)
+                .await
+                .context(QuerySnafu { query: sql.clone() })?;
+        }
+        sqlx::query(RELOAD_QUERY)
+            .execute(&self.pool)
+            .await
+            .context(QuerySnafu {
+                query: RELOAD_QUERY,
+            })?;
+        Ok(())
+    }
+
+    /// Captures a `postgreat bench` baseline over `opts.window`
+    pub async fn capture_bench(&mut self, opts: &BenchOptions) -> BenchSnapshot {
+        self.detect_catalog().await;
+        info!(
+            "Sampling query times, cache hits and checkpoints over {}s...",
+            opts.window.as_secs()
+        );
+        bench::capture(
+            &self.pool,
+            &self.catalog,
+            &self.config.database,
+            &self.config.host,
+            opts,
+        )
+        .await
+    }
+
+    /// Checks the server process's limits and the filesystem layout around
+    /// `data_directory`; skipped with a warning when the server does not run
+    /// on this host
+    async fn analyze_host(&self, results: &mut AnalysisResults) {
+        info!("Inspecting host limits and filesystem layout...");
+        let data_directory = results
+            .params
+            .get("data_directory")
+            .map(|param| param.current_value.clone());
+        let limits = os_limits::fetch_backend_pid(&self.pool)
+            .await
+            .map_err(|err| err.to_string())
+            .and_then(|pid| os_limits::OsLimits::inspect(pid, data_directory.as_deref()));
+        match limits {
+            Ok(limits) => run_phase(&self.config.database, "os_limits", || {
+                os_limits::analyze_os_limits(&limits, results)
+            }),
+            Err(reason) => {
+                results
+                    .warnings
+                    .push(format!("Host checks skipped: {reason}."));
+                return;
+            }
+        }
+
+        let tablespaces = match host_layout::fetch_tablespaces(&self.pool).await {
+            Ok(tablespaces) => tablespaces,
+            Err(err) => {
+                warn!("Tablespace locations not read: {err}");
+                Vec::new()
+            }
+        };
+        match host_layout::HostLayout::inspect(&results.params, &tablespaces) {
+            Ok(layout) => run_phase(&self.config.database, "host_layout", || {
+                host_layout::analyze_host_layout(&layout, results)
+            }),
+            Err(reason) => results
+                .warnings
+                .push(format!("Host checks skipped: {reason}.")),
+        }
+    }
+
+    /// Looks for a successful backup within the RPO in the WAL archiver, backup
+    /// history files and the check command
+    async fn analyze_backups(&self, check: &BackupCheck, results: &mut AnalysisResults) {
+        info!("Checking backup recency...");
+        let (evidence, warnings) = self
+            .fixture
+            .serve(
+                "backup_evidence",
+                backups::collect_evidence(&self.pool, check),
+            )
+            .await;
+        for warning in warnings {
+            warn!("{warning}");
+            results.warnings.push(warning);
+        }
+        run_phase(&self.config.database, "backups", || {
+            backups::analyze_backups(
+                &self.config.database,
+                &evidence,
+                check.rpo,
+                Utc::now(),
+                results,
+            )
+        });
+    }
+
+    /// The index catalog shared by the table/index and workload analyses of a
+    /// run; left unread with `--gentle`
+    async fn read_catalog_cache(&self, gentle: bool) -> CatalogCache {
+        if gentle {
+            return CatalogCache::empty();
+        }
+        info!("Reading the index catalog...");
+        let started = Instant::now();
+        let cache = CatalogCache::fetch(&self.pool, &self.fixture).await;
+        telemetry::record_query(&self.config.database, "index_catalog", started.elapsed());
+        cache
+    }
+
+    /// pg_stat_statements analysis without table/index health; the flag is false
+    /// when the extension could not be used and only warnings were returned
+    async fn run_workload(
+        &self,
+        opts: &WorkloadOptions,
+        indexes: &CatalogCache,
+    ) -> Result<(WorkloadResults, bool)> {
+        // Replica statements are part of the recorded primary's fixture
+        let (replicas, unreachable) = if self.fixture.is_replay() {
+            Default::default()
+        } else {
+            replicas::connect(&self.config).await
+        };
+        let analysis = workload::analyze(
+            &self.pool,
+            &replicas,
+            opts,
+            &self.fixture,
+            &self.catalog,
+            indexes,
+        )
+        .await?;
+        let mut results = analysis.results;
+        for label in unreachable {
+            results.warnings.push(format!(
+                "Replica {label} could not be reached; its queries are missing from workload totals."
+            ));
+        }
+        Ok((results, analysis.available))
+    }
+
+    /// Reads a single parameter from pg_settings, e.g. for `postgreat explain`
+    pub async fn fetch_parameter(&self, name: &str) -> Result<Option<PgConfigParam>> {
+        let row = sqlx::query(PARAMETER_QUERY)
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await
+            .context(QuerySnafu {
+                query: PARAMETER_QUERY,
+            })?;
+
+        Ok(row.map(|row| {
+            let current_value: String = row.get("setting");
+            let unit: Option<String> = row.get("unit");
+            let vartype: String = row.get("vartype");
+            PgConfigParam {
+                name: row.get("name"),
+                value: ParamValue::parse(&current_value, &vartype, unit.as_deref()),
+                current_value,
+                default_value: row.get("boot_val"),
                 unit,
-                context,
-                boot_val
-            FROM pg_settings
-            ORDER BY name
-        "#;
+                context: row.get("context"),
+                source: row.get("source"),
+                sourcefile: row.get("sourcefile"),
+                pending_restart: row.get("pending_restart"),
+                min_value: row.get("min_val"),
+                max_value: row.get("max_val"),
+            }
+        }))
+    }
 
-        let rows = sqlx::query(query)
+    async fn fetch_config_params(&self) -> Result<HashMap<String, PgConfigParam>> {
+        let rows = sqlx::query(PARAMS_QUERY)
             .fetch_all(&self.pool)
             .await
-            .context(QuerySnafu { query })?;
+            .context(QuerySnafu {
+                query: PARAMS_QUERY,
+            })?;
 
         let mut params = HashMap::new();
         for row in rows {
             let name: String = row.get("name");
             let current_value: String = row.get("setting");
             let unit: Option<String> = row.get("unit");
+            let vartype: String = row.get("vartype");
             let context: String = row.get("context");
             let default_value: Option<String> = row.get("boot_val");
 
@@ -143,10 +738,16 @@ impl ConfigChecker {
 
             let param = PgConfigParam {
                 name: name.clone(),
+                value: ParamValue::parse(&current_value, &vartype, unit.as_deref()),
                 current_value,
                 default_value,
                 unit,
                 context,
+                source: row.get("source"),
+                sourcefile: row.get("sourcefile"),
+                pending_restart: row.get("pending_restart"),
+                min_value: row.get("min_val"),
+                max_value: row.get("max_val"),
             };
 
             params.insert(name, param);
@@ -156,63 +757,377 @@ impl ConfigChecker {
         Ok(params)
     }
 
-    async fn fetch_system_stats(&self) -> Result<SystemStats> {
-        let mut stats = SystemStats::default();
+    /// Reads the server version and the shape of version-dependent views
+    async fn detect_catalog(&mut self) {
+        self.catalog = self
+            .fixture
+            .serve("catalog", Catalog::detect(&self.pool))
+            .await;
+    }
+
+    async fn fetch_system_stats(&self, gentle: bool) -> Result<SystemStats> {
+        let mut stats = configured_stats(&self.config);
 
         // Record active connections for workload heuristics
-        match query_scalar::<_, i64>("SELECT count(*) FROM pg_stat_activity")
-            .fetch_one(&self.pool)
-            .await
-        {
-            Ok(active) => stats.connection_count = Some(active as usize),
-            Err(err) => warn!("Failed to read pg_stat_activity for connection count: {err}"),
+        let (connection_count, active_connection_count) = self
+            .fixture
+            .serve(
+                "connection_counts",
+                sampling::fetch_connection_counts(&self.pool),
+            )
+            .await;
+        stats.connection_count = connection_count;
+        stats.active_connection_count = active_connection_count;
+        stats.peak_connection_count = connection_count;
+
+        let version = self.fixture.serve("version", async {
+            query_scalar::<_, String>(VERSION_QUERY)
+                .fetch_one(&self.pool)
+                .await
+                .inspect_err(|err| warn!("Failed to read server version: {err}"))
+                .ok()
+        });
+        if let Some(version) = version.await {
+            stats.server_bits = validation::server_bits(&version);
         }
 
-        // PostgreSQL 17+ exposes checkpoint counters in pg_stat_checkpointer.
-        match sqlx::query(
-            "SELECT num_timed AS checkpoints_timed, num_requested AS checkpoints_req FROM pg_stat_checkpointer",
-        )
-        .fetch_one(&self.pool)
-        .await
-        {
-            Ok(row) => {
-                stats.checkpoints_timed = row.try_get("checkpoints_timed").ok();
-                stats.checkpoints_req = row.try_get("checkpoints_req").ok();
-            }
-            Err(new_err) => match sqlx::query(
-                "SELECT checkpoints_timed, checkpoints_req FROM pg_stat_bgwriter",
+        let checkpoints = self
+            .fixture
+            .serve(
+                "checkpoint_counters",
+                catalog::fetch_checkpoint_counters(&self.pool, &self.catalog),
             )
-            .fetch_one(&self.pool)
-            .await
-            {
-                Ok(row) => {
-                    stats.checkpoints_timed = row.try_get("checkpoints_timed").ok();
-                    stats.checkpoints_req = row.try_get("checkpoints_req").ok();
-                }
-                Err(old_err) => warn!(
-                    "Failed to read checkpoint stats from pg_stat_checkpointer ({new_err}) or pg_stat_bgwriter ({old_err})"
-                ),
-            },
-        }
+            .await;
+        stats.checkpoints_timed = checkpoints.timed;
+        stats.checkpoints_req = checkpoints.requested;
+        stats.buffer_writes = self
+            .fixture
+            .serve(
+                "buffer_writes",
+                catalog::fetch_buffer_writes(&self.pool, &self.catalog),
+            )
+            .await;
 
-        // Use provided compute spec if available
-        if let Some(compute) = &self.config.compute {
-            stats.total_memory_gb = Some(compute.memory_gb as f64);
-            stats.cpu_count = Some(compute.vcpu);
-        }
+        let wal = self
+            .fixture
+            .serve("wal_counters", sampling::fetch_wal_counters(&self.pool))
+            .await;
+        stats.wal_bytes = wal.bytes;
+        stats.wal_fpi = wal.fpi;
+        stats.wal_stats_age_secs = wal.age_secs;
+
+        let temp = self
+            .fixture
+            .serve("temp_counters", sampling::fetch_temp_counters(&self.pool))
+            .await;
+        stats.temp_files = temp.files;
+        stats.temp_bytes = temp.bytes;
 
-        stats.storage_type = self.config.storage_type;
-        stats.workload_type = self.config.workload_type;
+        let (stats_reset_at, stats_age_secs) = self
+            .fixture
+            .serve("stats_age", sampling::fetch_stats_age(&self.pool))
+            .await;
+        stats.stats_reset_at = stats_reset_at;
+        stats.stats_age_secs = stats_age_secs;
+
+        let pool = &self.pool;
+        let fixture = &self.fixture;
+        extensions::fetch_extension_stats(pool, fixture, &mut stats).await;
+        stats.toast = fixture
+            .serve("toast", toast::fetch_toast_compression(pool, gentle))
+            .await;
+        stats.statement_rates = fixture
+            .serve(
+                "statement_rates",
+                log_volume::fetch_statement_rates(pool, stats.stats_age_secs),
+            )
+            .await;
+        stats.replication_slots = fixture
+            .serve("replication_slots", pitr::fetch_replication_slots(pool))
+            .await;
+        stats.archiver = fixture.serve("archiver", pitr::fetch_archiver(pool)).await;
+        stats.standbys = fixture
+            .serve("standbys", pitr::fetch_standby_settings(&self.config))
+            .await;
+        stats.replication_role = fixture
+            .serve("replication_role", resilience::fetch_replication_role(pool))
+            .await;
+        stats.row_security_tables = fixture
+            .serve("row_security", security::fetch_row_security(pool))
+            .await;
+        stats.public_schema_create = fixture
+            .serve(
+                "public_schema_create",
+                security::fetch_public_schema_create(pool),
+            )
+            .await;
+        stats.unvalidated_constraints = fixture
+            .serve(
+                "unvalidated_constraints",
+                integrity::fetch_unvalidated_constraints(pool),
+            )
+            .await;
+        stats.disabled_triggers = fixture
+            .serve(
+                "disabled_triggers",
+                integrity::fetch_disabled_triggers(pool),
+            )
+            .await;
+        stats.unanalyzed_extended_statistics = fixture
+            .serve(
+                "unanalyzed_extended_statistics",
+                extended_stats::fetch_unanalyzed_statistics(pool, &self.catalog),
+            )
+            .await;
+        stats.cursor_sessions = fixture
+            .serve("cursor_sessions", cursors::fetch_cursor_sessions(pool))
+            .await;
+        stats.orphaned_temp_schemas = fixture
+            .serve(
+                "orphaned_temp_schemas",
+                temp_schemas::fetch_orphaned_temp_schemas(pool, &self.catalog),
+            )
+            .await;
 
         Ok(stats)
     }
 }
 
+/// Probes the candidates in `hosts` in order and returns the first one that
+/// is not in recovery. Patroni and Multi-AZ writers move between hosts, so the
+/// primary is looked up again on every run.
+async fn discover_primary(config: &DbConfig) -> Result<(String, u16)> {
+    for candidate in &config.hosts {
+        let options = match config.host_connection_options(candidate) {
+            Ok(options) => options,
+            Err(err) => {
+                warn!("Skipping host: {err}");
+                continue;
+            }
+        };
+        let label = format!("{}:{}", options.get_host(), options.get_port());
+
+        let probe = async {
+            let mut connection = PgConnection::connect_with(&options).await?;
+            let in_recovery: bool = query_scalar(IN_RECOVERY_QUERY)
+                .fetch_one(&mut connection)
+                .await?;
+            let _ = connection.close().await;
+            Ok::<_, sqlx::Error>(in_recovery)
+        };
+        match tokio::time::timeout(PRIMARY_PROBE_TIMEOUT, probe).await {
+            Ok(Ok(false)) => {
+                info!("Found primary at {label}");
+                return Ok((options.get_host().to_string(), options.get_port()));
+            }
+            Ok(Ok(true)) => info!("Skipping {label}: in recovery"),
+            Ok(Err(err)) => warn!("Failed to probe {label}: {err}"),
+            Err(_) => warn!(
+                "No response from {label} within {}s",
+                PRIMARY_PROBE_TIMEOUT.as_secs()
+            ),
+        }
+    }
+
+    NoPrimarySnafu {
+        hosts: config.hosts.join(", "),
+    }
+    .fail()
+}
+
+/// Analyzes settings exported from a server the tool cannot connect to, such
+/// as a `pg_settings` dump. Only the settings analyzers run; everything that
+/// needs live statistics is left empty.
+pub fn analyze_offline(
+    config: &DbConfig,
+    params: HashMap<String, PgConfigParam>,
+    releases: &ReleaseTable,
+) -> Result<AnalysisResults> {
+    let mut results = AnalysisResults {
+        params,
+        system_stats: configured_stats(config),
+        ..AnalysisResults::default()
+    };
+    analyze_settings(&config.database, releases, &mut results)?;
+    fingerprint::assign(&mut results);
+    results.run = Some(RunMetadata::capture(config));
+    telemetry::record_findings(&config.database, &results);
+    Ok(results)
+}
+
+/// Runs the analyzers that only need `results.params` and `results.system_stats`
+fn analyze_settings(
+    database: &str,
+    releases: &ReleaseTable,
+    results: &mut AnalysisResults,
+) -> Result<()> {
+    if results.system_stats.total_memory_gb.is_none() {
+        warn!("No compute specification provided; CPU and memory-based recommendations will be limited. Use --compute <tier|<vCPU>vCPU-<GB>GB> to enable full guidance.");
+    }
+
+    let params_snapshot = results.params.clone();
+    let stats_snapshot = results.system_stats.clone();
+
+    info!("Running memory configuration analysis...");
+    run_phase(database, "memory", || {
+        memory::analyze_memory(&params_snapshot, &stats_snapshot, results)
+    })?;
+
+    info!("Running concurrency analysis...");
+    run_phase(database, "concurrency", || {
+        concurrency::analyze_concurrency(&params_snapshot, &stats_snapshot, results)
+    })?;
+
+    info!("Running WAL configuration analysis...");
+    run_phase(database, "wal", || {
+        wal::analyze_wal(&params_snapshot, &stats_snapshot, results)
+    })?;
+
+    info!("Running planner analysis...");
+    run_phase(database, "planner", || {
+        planner::analyze_planner(&params_snapshot, &stats_snapshot, results)
+    })?;
+
+    info!("Running autovacuum analysis...");
+    run_phase(database, "autovacuum", || {
+        autovacuum::analyze_autovacuum(&params_snapshot, &stats_snapshot, results)
+    })?;
+
+    info!("Running logging analysis...");
+    run_phase(database, "logging", || {
+        logging::analyze_logging(&params_snapshot, &stats_snapshot, results)
+    })?;
+
+    info!("Running extension analysis...");
+    run_phase(database, "extensions", || {
+        extensions::analyze_extensions(&params_snapshot, &stats_snapshot, results)
+    })?;
+
+    info!("Running TOAST compression analysis...");
+    run_phase(database, "toast", || {
+        toast::analyze_toast_compression(&params_snapshot, &stats_snapshot, results)
+    })?;
+
+    info!("Running PITR readiness analysis...");
+    run_phase(database, "pitr", || {
+        pitr::analyze_pitr(&params_snapshot, &stats_snapshot, results)
+    })?;
+
+    info!("Running failover resilience analysis...");
+    run_phase(database, "resilience", || {
+        resilience::analyze_failover(&params_snapshot, &stats_snapshot, results)
+    })?;
+
+    info!("Running security analysis...");
+    run_phase(database, "security", || {
+        security::analyze_row_security(&params_snapshot, &stats_snapshot, results)?;
+        security::analyze_search_path(&params_snapshot, &stats_snapshot, results)
+    })?;
+
+    info!("Running extended statistics analysis...");
+    run_phase(database, "extended_statistics", || {
+        extended_stats::analyze_extended_statistics(&stats_snapshot, results)
+    })?;
+
+    info!("Running temp schema analysis...");
+    run_phase(database, "temp_schemas", || {
+        temp_schemas::analyze_orphaned_temp_schemas(&params_snapshot, &stats_snapshot, results)
+    })?;
+
+    info!("Running constraint and trigger analysis...");
+    run_phase(database, "integrity", || {
+        integrity::analyze_integrity(&stats_snapshot, results)
+    })?;
+
+    info!("Running server version analysis...");
+    run_phase(database, "version", || {
+        version::analyze_version(&params_snapshot, releases, Utc::now().date_naive(), results)
+    });
+
+    stats_snapshot.profile().apply(results);
+    reconcile::reconcile_suggestions(results);
+    validation::validate_suggestions(&params_snapshot, &stats_snapshot, results);
+    checkpoints::annotate_checkpoint_intervals(&params_snapshot, &stats_snapshot, results);
+    pending_changes::reconcile_pending_changes(results);
+    results.connection_memory =
+        connection_memory::estimate_connection_memory(&params_snapshot, &stats_snapshot);
+    results.managed_remediation = managed::plan_managed_remediation(&params_snapshot, results);
+    Ok(())
+}
+
+/// Runs one synchronous analysis phase in its own span and records how long
+/// it took
+fn run_phase<T>(database: &str, phase: &'static str, run: impl FnOnce() -> T) -> T {
+    let _span = info_span!("analysis_phase", phase).entered();
+    let started = Instant::now();
+    let output = run();
+    telemetry::record_phase(database, phase, started.elapsed());
+    output
+}
+
+/// What the config says about the hardware and workload, which the server cannot tell us
+fn configured_stats(config: &DbConfig) -> SystemStats {
+    let mut stats = SystemStats::default();
+    if let Some(compute) = &config.compute {
+        stats.total_memory_gb = Some(compute.memory_gb as f64);
+        stats.cpu_count = Some(compute.vcpu);
+    }
+
+    stats.storage_type = config.storage_type;
+    stats.workload_type = config.workload_type;
+    stats.log_retention_days = config.log_retention_days;
+    if let Some(profile) = config.profile {
+        stats.workload_type = profile.workload_type();
+        stats.profile = Some(profile);
+    }
+    stats
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::config::ComputeSpec;
+    use super::*;
+    use crate::config::{ComputeSpec, StorageType, WorkloadType};
+    use crate::models::ConfigCategory;
     use rstest::rstest;
 
+    #[test]
+    fn offline_analysis_runs_settings_analyzers_against_the_config() {
+        let config = DbConfig::from_connection_params(
+            String::new(),
+            0,
+            "dump.csv".to_string(),
+            String::new(),
+            String::new(),
+            Some("8vCPU-64GB".to_string()),
+            StorageType::Ssd,
+            WorkloadType::Oltp,
+            None,
+        );
+        let params = [PgConfigParam {
+            name: "shared_buffers".to_string(),
+            current_value: "16384".to_string(),
+            unit: Some("8kB".to_string()),
+            context: "postmaster".to_string(),
+            value: ParamValue::parse("16384", "integer", Some("8kB")),
+            default_value: None,
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        }]
+        .into_iter()
+        .map(|param| (param.name.clone(), param))
+        .collect();
+
+        let results = analyze_offline(&config, params, &ReleaseTable::default()).unwrap();
+        assert_eq!(results.system_stats.total_memory_gb, Some(64.0));
+        assert!(results.suggestions_by_category[&ConfigCategory::Memory]
+            .iter()
+            .any(|suggestion| suggestion.parameter == "shared_buffers"
+                && suggestion.suggested_value == "8192MB"));
+    }
+
     #[rstest]
     #[case("small", 2, 16)]
     #[case("medium", 8, 64)]
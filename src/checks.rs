@@ -0,0 +1,1365 @@
+use crate::models::{ConfigCategory, ConfigSuggestion, SuggestionLevel};
+use serde::Serialize;
+
+/// Static description of a single check.
+///
+/// IDs are stable across releases so they can be referenced from suppressions,
+/// severity overrides and CI tooling.
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckInfo {
+    /// Stable identifier, `<category>.<name>`
+    pub id: &'static str,
+    pub category: ConfigCategory,
+    /// Highest severity the check emits before any overrides
+    pub default_level: SuggestionLevel,
+    /// Roles or grants the connecting user needs for the check to run
+    pub privileges: &'static [&'static str],
+    /// Extensions that must be installed for the check to run
+    pub extensions: &'static [&'static str],
+    pub summary: &'static str,
+    /// How suggestions from this check identify themselves
+    #[serde(skip)]
+    matcher: Matcher,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Matcher {
+    /// Suggestion parameter equals the GUC name
+    Parameter(&'static str),
+    /// Suggestion parameter is `<prefix> <object>` with an optional ` <suffix>`; checks
+    /// sharing a prefix are told apart by their suggested action
+    Object {
+        prefix: &'static str,
+        suffix: Option<&'static str>,
+        action: Option<&'static str>,
+    },
+    /// Findings reported outside `suggestions_by_category`
+    Finding,
+}
+
+const SETTINGS: &[&str] = &["pg_read_all_settings"];
+const STATS: &[&str] = &["pg_read_all_stats"];
+const TABLE_READ: &[&str] = &["pg_read_all_stats", "SELECT on user tables"];
+const NONE: &[&str] = &[];
+
+const fn setting(
+    id: &'static str,
+    category: ConfigCategory,
+    default_level: SuggestionLevel,
+    parameter: &'static str,
+    summary: &'static str,
+) -> CheckInfo {
+    CheckInfo {
+        id,
+        category,
+        default_level,
+        privileges: SETTINGS,
+        extensions: NONE,
+        summary,
+        matcher: Matcher::Parameter(parameter),
+    }
+}
+
+/// Every check PostGreat can run, in report order.
+pub static CHECKS: &[CheckInfo] = &[
+    // Memory
+    setting(
+        "memory.shared_buffers",
+        ConfigCategory::Memory,
+        SuggestionLevel::Critical,
+        "shared_buffers",
+        "shared_buffers is ~25% of RAM (capped at 8GB)",
+    ),
+    setting(
+        "memory.effective_cache_size",
+        ConfigCategory::Memory,
+        SuggestionLevel::Critical,
+        "effective_cache_size",
+        "effective_cache_size is ~75% of RAM",
+    ),
+    setting(
+        "memory.work_mem",
+        ConfigCategory::Memory,
+        SuggestionLevel::Critical,
+        "work_mem",
+        "work_mem is within the profile's safe range",
+    ),
+    setting(
+        "memory.maintenance_work_mem",
+        ConfigCategory::Memory,
+        SuggestionLevel::Recommended,
+        "maintenance_work_mem",
+        "maintenance_work_mem is sized for the instance",
+    ),
+    setting(
+        "memory.wal_buffers",
+        ConfigCategory::Memory,
+        SuggestionLevel::Recommended,
+        "wal_buffers",
+        "wal_buffers is at least 16MB when set explicitly",
+    ),
+    setting(
+        "memory.huge_pages",
+        ConfigCategory::Memory,
+        SuggestionLevel::Recommended,
+        "huge_pages",
+        "huge_pages is required on servers above 32 vCPUs",
+    ),
+    setting(
+        "memory.hash_mem_multiplier",
+        ConfigCategory::Memory,
+        SuggestionLevel::Important,
+        "hash_mem_multiplier",
+        "hash_mem_multiplier is at least 2.0 without stretching hash nodes past the work_mem ceiling (PG13+)",
+    ),
+    setting(
+        "memory.temp_buffers",
+        ConfigCategory::Memory,
+        SuggestionLevel::Important,
+        "temp_buffers",
+        "temp_buffers across max_connections fits in a quarter of RAM",
+    ),
+    setting(
+        "memory.logical_decoding_work_mem",
+        ConfigCategory::Memory,
+        SuggestionLevel::Important,
+        "logical_decoding_work_mem",
+        "logical_decoding_work_mem across WAL senders fits in a tenth of RAM (PG13+)",
+    ),
+    // Concurrency
+    setting(
+        "concurrency.max_connections",
+        ConfigCategory::Concurrency,
+        SuggestionLevel::Critical,
+        "max_connections",
+        "max_connections stays near 4 * vCPU behind a pooler, with headroom over the sampled peak",
+    ),
+    setting(
+        "concurrency.max_worker_processes",
+        ConfigCategory::Concurrency,
+        SuggestionLevel::Recommended,
+        "max_worker_processes",
+        "max_worker_processes matches vCPU count",
+    ),
+    setting(
+        "concurrency.max_parallel_workers",
+        ConfigCategory::Concurrency,
+        SuggestionLevel::Important,
+        "max_parallel_workers",
+        "max_parallel_workers does not exceed vCPU count",
+    ),
+    setting(
+        "concurrency.max_parallel_workers_per_gather",
+        ConfigCategory::Concurrency,
+        SuggestionLevel::Critical,
+        "max_parallel_workers_per_gather",
+        "max_parallel_workers_per_gather limits a single query's CPU share",
+    ),
+    setting(
+        "concurrency.max_parallel_maintenance_workers",
+        ConfigCategory::Concurrency,
+        SuggestionLevel::Recommended,
+        "max_parallel_maintenance_workers",
+        "max_parallel_maintenance_workers uses half of vCPUs",
+    ),
+    // WAL
+    setting(
+        "wal.max_wal_size",
+        ConfigCategory::Wal,
+        SuggestionLevel::Critical,
+        "max_wal_size",
+        "max_wal_size keeps checkpoints time-based rather than size-based",
+    ),
+    setting(
+        "wal.min_wal_size",
+        ConfigCategory::Wal,
+        SuggestionLevel::Recommended,
+        "min_wal_size",
+        "min_wal_size retains enough segments for write spikes",
+    ),
+    setting(
+        "wal.checkpoint_timeout",
+        ConfigCategory::Wal,
+        SuggestionLevel::Important,
+        "checkpoint_timeout",
+        "checkpoint_timeout matches the profile's checkpoint interval",
+    ),
+    setting(
+        "wal.checkpoint_completion_target",
+        ConfigCategory::Wal,
+        SuggestionLevel::Important,
+        "checkpoint_completion_target",
+        "checkpoint_completion_target spreads checkpoint I/O (0.9)",
+    ),
+    // Planner
+    setting(
+        "planner.random_page_cost",
+        ConfigCategory::Planner,
+        SuggestionLevel::Critical,
+        "random_page_cost",
+        "random_page_cost matches the storage type",
+    ),
+    setting(
+        "planner.effective_io_concurrency",
+        ConfigCategory::Planner,
+        SuggestionLevel::Important,
+        "effective_io_concurrency",
+        "effective_io_concurrency matches the storage type",
+    ),
+    setting(
+        "planner.maintenance_io_concurrency",
+        ConfigCategory::Planner,
+        SuggestionLevel::Recommended,
+        "maintenance_io_concurrency",
+        "maintenance_io_concurrency matches the storage type (PG13+)",
+    ),
+    setting(
+        "planner.seq_page_cost",
+        ConfigCategory::Planner,
+        SuggestionLevel::Recommended,
+        "seq_page_cost",
+        "seq_page_cost stays at the 1.0 baseline",
+    ),
+    setting(
+        "planner.default_statistics_target",
+        ConfigCategory::Planner,
+        SuggestionLevel::Recommended,
+        "default_statistics_target",
+        "default_statistics_target is high enough for analytic profiles",
+    ),
+    CheckInfo {
+        id: "planner.extended_statistics_not_analyzed",
+        category: ConfigCategory::Planner,
+        default_level: SuggestionLevel::Recommended,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "Extended statistics objects have been analyzed (PG12+)",
+        matcher: Matcher::Object {
+            prefix: "statistics",
+            suffix: None,
+            action: None,
+        },
+    },
+    // Autovacuum
+    setting(
+        "autovacuum.max_workers",
+        ConfigCategory::Autovacuum,
+        SuggestionLevel::Important,
+        "autovacuum_max_workers",
+        "autovacuum_max_workers is raised for high-churn systems",
+    ),
+    setting(
+        "autovacuum.naptime",
+        ConfigCategory::Autovacuum,
+        SuggestionLevel::Recommended,
+        "autovacuum_naptime",
+        "autovacuum_naptime is not too long",
+    ),
+    setting(
+        "autovacuum.vacuum_cost_limit",
+        ConfigCategory::Autovacuum,
+        SuggestionLevel::Critical,
+        "autovacuum_vacuum_cost_limit",
+        "autovacuum_vacuum_cost_limit lets workers keep up (2000)",
+    ),
+    setting(
+        "autovacuum.work_mem",
+        ConfigCategory::Autovacuum,
+        SuggestionLevel::Critical,
+        "autovacuum_work_mem",
+        "autovacuum_work_mem is set explicitly",
+    ),
+    setting(
+        "autovacuum.vacuum_scale_factor",
+        ConfigCategory::Autovacuum,
+        SuggestionLevel::Critical,
+        "autovacuum_vacuum_scale_factor",
+        "autovacuum_vacuum_scale_factor triggers vacuum early enough on large tables",
+    ),
+    CheckInfo {
+        id: "autovacuum.idle_cursor_transaction",
+        category: ConfigCategory::Autovacuum,
+        default_level: SuggestionLevel::Important,
+        privileges: STATS,
+        extensions: NONE,
+        summary: "No transaction sits idle for 5+ minutes with a cursor open",
+        matcher: Matcher::Object {
+            prefix: "session",
+            suffix: None,
+            action: Some("COMMIT or ROLLBACK"),
+        },
+    },
+    CheckInfo {
+        id: "autovacuum.held_cursor",
+        category: ConfigCategory::Autovacuum,
+        default_level: SuggestionLevel::Recommended,
+        privileges: STATS,
+        extensions: NONE,
+        summary: "No WITH HOLD cursor stays open for an hour after its transaction",
+        matcher: Matcher::Object {
+            prefix: "session",
+            suffix: None,
+            action: Some("CLOSE the cursor"),
+        },
+    },
+    // Logging
+    setting(
+        "logging.log_min_duration_statement",
+        ConfigCategory::Logging,
+        SuggestionLevel::Important,
+        "log_min_duration_statement",
+        "log_min_duration_statement captures slow queries",
+    ),
+    setting(
+        "logging.log_lock_waits",
+        ConfigCategory::Logging,
+        SuggestionLevel::Important,
+        "log_lock_waits",
+        "log_lock_waits is enabled",
+    ),
+    setting(
+        "logging.deadlock_timeout",
+        ConfigCategory::Logging,
+        SuggestionLevel::Info,
+        "deadlock_timeout",
+        "deadlock_timeout is reasonable",
+    ),
+    setting(
+        "logging.log_destination",
+        ConfigCategory::Logging,
+        SuggestionLevel::Recommended,
+        "log_destination",
+        "log_destination includes jsonlog (PG15+) or csvlog for parseable logs",
+    ),
+    setting(
+        "logging.logging_collector",
+        ConfigCategory::Logging,
+        SuggestionLevel::Recommended,
+        "logging_collector",
+        "logging_collector is on to write and rotate log files",
+    ),
+    setting(
+        "logging.log_filename",
+        ConfigCategory::Logging,
+        SuggestionLevel::Important,
+        "log_filename",
+        "log_filename rotation keeps the configured days of logs",
+    ),
+    setting(
+        "logging.log_truncate_on_rotation",
+        ConfigCategory::Logging,
+        SuggestionLevel::Recommended,
+        "log_truncate_on_rotation",
+        "log_truncate_on_rotation overwrites reused log file names",
+    ),
+    setting(
+        "logging.log_rotation_age",
+        ConfigCategory::Logging,
+        SuggestionLevel::Recommended,
+        "log_rotation_age",
+        "Log files are rotated",
+    ),
+    CheckInfo {
+        id: "logging.pgaudit_volume",
+        category: ConfigCategory::Logging,
+        default_level: SuggestionLevel::Critical,
+        privileges: STATS,
+        extensions: &["pgaudit", "pg_stat_statements"],
+        summary: "pgaudit.log classes log under 10GB a day at current statement rates",
+        matcher: Matcher::Parameter("pgaudit.log"),
+    },
+    CheckInfo {
+        id: "logging.log_statement_sample_rate",
+        category: ConfigCategory::Logging,
+        default_level: SuggestionLevel::Critical,
+        privileges: STATS,
+        extensions: &["pg_stat_statements"],
+        summary: "Duration logging stays under 1GB of log an hour at current statement rates",
+        matcher: Matcher::Parameter("log_statement_sample_rate"),
+    },
+    CheckInfo {
+        id: "logging.log_statement",
+        category: ConfigCategory::Logging,
+        default_level: SuggestionLevel::Critical,
+        privileges: STATS,
+        extensions: &["pg_stat_statements"],
+        summary: "log_statement stays under 1GB of log an hour at current statement rates",
+        matcher: Matcher::Parameter("log_statement"),
+    },
+    CheckInfo {
+        id: "logging.log_connections",
+        category: ConfigCategory::Logging,
+        default_level: SuggestionLevel::Recommended,
+        privileges: STATS,
+        extensions: &["pg_stat_statements"],
+        summary: "Connection logging stays under 1GB of log an hour at current session churn",
+        matcher: Matcher::Parameter("log_connections"),
+    },
+    // Extensions
+    CheckInfo {
+        id: "extensions.timescaledb_background_workers",
+        category: ConfigCategory::Extensions,
+        default_level: SuggestionLevel::Important,
+        privileges: SETTINGS,
+        extensions: &["timescaledb"],
+        summary: "timescaledb.max_background_workers covers the scheduled jobs",
+        matcher: Matcher::Parameter("timescaledb.max_background_workers"),
+    },
+    CheckInfo {
+        id: "extensions.timescaledb_worker_processes",
+        category: ConfigCategory::Extensions,
+        default_level: SuggestionLevel::Important,
+        privileges: SETTINGS,
+        extensions: &["timescaledb"],
+        summary: "max_worker_processes leaves room for TimescaleDB jobs and parallel workers",
+        matcher: Matcher::Parameter("max_worker_processes"),
+    },
+    CheckInfo {
+        id: "extensions.citus_shard_count",
+        category: ConfigCategory::Extensions,
+        default_level: SuggestionLevel::Important,
+        privileges: SETTINGS,
+        extensions: &["citus"],
+        summary: "citus.shard_count spreads new distributed tables over every worker",
+        matcher: Matcher::Parameter("citus.shard_count"),
+    },
+    CheckInfo {
+        id: "extensions.citus_executor_pool_size",
+        category: ConfigCategory::Extensions,
+        default_level: SuggestionLevel::Recommended,
+        privileges: SETTINGS,
+        extensions: &["citus"],
+        summary:
+            "citus.max_adaptive_executor_pool_size keeps worker connections within max_connections",
+        matcher: Matcher::Parameter("citus.max_adaptive_executor_pool_size"),
+    },
+    CheckInfo {
+        id: "extensions.pgvector_missing_index",
+        category: ConfigCategory::Extensions,
+        default_level: SuggestionLevel::Important,
+        privileges: STATS,
+        extensions: &["vector"],
+        summary: "Vector columns queried by distance without an HNSW or IVFFlat index",
+        matcher: Matcher::Object {
+            prefix: "vector column",
+            suffix: None,
+            action: Some("Create HNSW index"),
+        },
+    },
+    CheckInfo {
+        id: "extensions.pgvector_ivfflat_lists",
+        category: ConfigCategory::Extensions,
+        default_level: SuggestionLevel::Recommended,
+        privileges: NONE,
+        extensions: &["vector"],
+        summary: "IVFFlat lists sized to the table's row count",
+        matcher: Matcher::Object {
+            prefix: "index",
+            suffix: Some("lists"),
+            action: None,
+        },
+    },
+    CheckInfo {
+        id: "extensions.pgvector_ivfflat_probes",
+        category: ConfigCategory::Extensions,
+        default_level: SuggestionLevel::Recommended,
+        privileges: SETTINGS,
+        extensions: &["vector"],
+        summary: "ivfflat.probes scaled to the lists of the IVFFlat indexes",
+        matcher: Matcher::Parameter("ivfflat.probes"),
+    },
+    CheckInfo {
+        id: "extensions.pgvector_hnsw_ef_search",
+        category: ConfigCategory::Extensions,
+        default_level: SuggestionLevel::Recommended,
+        privileges: SETTINGS,
+        extensions: &["vector"],
+        summary: "hnsw.ef_search large enough for the biggest HNSW-indexed table",
+        matcher: Matcher::Parameter("hnsw.ef_search"),
+    },
+    CheckInfo {
+        id: "extensions.pgvector_build_memory",
+        category: ConfigCategory::Extensions,
+        default_level: SuggestionLevel::Recommended,
+        privileges: SETTINGS,
+        extensions: &["vector"],
+        summary: "maintenance_work_mem fits the HNSW graph during index builds",
+        matcher: Matcher::Parameter("maintenance_work_mem"),
+    },
+    // Server version
+    CheckInfo {
+        id: "version.minor_release",
+        category: ConfigCategory::Version,
+        default_level: SuggestionLevel::Important,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "Server runs the newest minor release of its major version",
+        matcher: Matcher::Parameter("server_version"),
+    },
+    CheckInfo {
+        id: "version.eol_major",
+        category: ConfigCategory::Version,
+        default_level: SuggestionLevel::Important,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "Server major version is still supported",
+        matcher: Matcher::Object {
+            prefix: "major version",
+            suffix: None,
+            action: None,
+        },
+    },
+    // Host and storage layout
+    CheckInfo {
+        id: "host.wal_volume",
+        category: ConfigCategory::Host,
+        default_level: SuggestionLevel::Recommended,
+        privileges: SETTINGS,
+        extensions: NONE,
+        summary: "pg_wal is on a separate volume from the data directory",
+        matcher: Matcher::Object {
+            prefix: "directory",
+            suffix: None,
+            action: None,
+        },
+    },
+    CheckInfo {
+        id: "host.temp_tablespace_volume",
+        category: ConfigCategory::Host,
+        default_level: SuggestionLevel::Info,
+        privileges: SETTINGS,
+        extensions: NONE,
+        summary: "temp_tablespaces point away from the data volume",
+        matcher: Matcher::Object {
+            prefix: "tablespace",
+            suffix: None,
+            action: None,
+        },
+    },
+    CheckInfo {
+        id: "host.filesystem",
+        category: ConfigCategory::Host,
+        default_level: SuggestionLevel::Important,
+        privileges: SETTINGS,
+        extensions: NONE,
+        summary: "Data, WAL and temp files are on a durable local filesystem",
+        matcher: Matcher::Object {
+            prefix: "mount",
+            suffix: Some("filesystem"),
+            action: None,
+        },
+    },
+    CheckInfo {
+        id: "host.mount_options",
+        category: ConfigCategory::Host,
+        default_level: SuggestionLevel::Recommended,
+        privileges: SETTINGS,
+        extensions: NONE,
+        summary: "Volumes PostgreSQL writes to are mounted noatime",
+        matcher: Matcher::Object {
+            prefix: "mount",
+            suffix: Some("options"),
+            action: None,
+        },
+    },
+    // Operating system
+    CheckInfo {
+        id: "operating_system.open_files",
+        category: ConfigCategory::OperatingSystem,
+        default_level: SuggestionLevel::Important,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "Server open-file limit covers max_connections + max_files_per_process",
+        matcher: Matcher::Parameter("LimitNOFILE"),
+    },
+    CheckInfo {
+        id: "operating_system.overcommit_memory",
+        category: ConfigCategory::OperatingSystem,
+        default_level: SuggestionLevel::Recommended,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "vm.overcommit_memory keeps the OOM killer away from PostgreSQL",
+        matcher: Matcher::Parameter("vm.overcommit_memory"),
+    },
+    CheckInfo {
+        id: "operating_system.swappiness",
+        category: ConfigCategory::OperatingSystem,
+        default_level: SuggestionLevel::Recommended,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "vm.swappiness keeps database memory out of swap",
+        matcher: Matcher::Parameter("vm.swappiness"),
+    },
+    CheckInfo {
+        id: "operating_system.transparent_hugepage",
+        category: ConfigCategory::OperatingSystem,
+        default_level: SuggestionLevel::Recommended,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "Transparent huge pages are not forced on",
+        matcher: Matcher::Parameter("transparent_hugepage"),
+    },
+    CheckInfo {
+        id: "operating_system.zone_reclaim_mode",
+        category: ConfigCategory::OperatingSystem,
+        default_level: SuggestionLevel::Important,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "vm.zone_reclaim_mode is off on multi-node NUMA hosts",
+        matcher: Matcher::Parameter("vm.zone_reclaim_mode"),
+    },
+    CheckInfo {
+        id: "operating_system.numa_balancing",
+        category: ConfigCategory::OperatingSystem,
+        default_level: SuggestionLevel::Recommended,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "Automatic NUMA balancing is off on multi-node NUMA hosts",
+        matcher: Matcher::Parameter("kernel.numa_balancing"),
+    },
+    // Backup and recovery
+    CheckInfo {
+        id: "backup.recency",
+        category: ConfigCategory::Backup,
+        default_level: SuggestionLevel::Critical,
+        privileges: &["pg_monitor"],
+        extensions: NONE,
+        summary: "A successful backup is found within --backup-rpo",
+        matcher: Matcher::Object {
+            prefix: "backup",
+            suffix: None,
+            action: None,
+        },
+    },
+    setting(
+        "backup.archive_mode",
+        ConfigCategory::Backup,
+        SuggestionLevel::Important,
+        "archive_mode",
+        "WAL is archived so a base backup can be rolled forward to any point in time",
+    ),
+    setting(
+        "backup.archive_command",
+        ConfigCategory::Backup,
+        SuggestionLevel::Critical,
+        "archive_command",
+        "archive_mode has an archive_command or archive_library to hand WAL to",
+    ),
+    CheckInfo {
+        id: "backup.restore_command",
+        category: ConfigCategory::Backup,
+        default_level: SuggestionLevel::Important,
+        privileges: SETTINGS,
+        extensions: NONE,
+        summary: "Standbys set restore_command to catch up from the WAL archive",
+        matcher: Matcher::Object {
+            prefix: "standby",
+            suffix: Some("restore_command"),
+            action: None,
+        },
+    },
+    CheckInfo {
+        id: "backup.lost_slot",
+        category: ConfigCategory::Backup,
+        default_level: SuggestionLevel::Critical,
+        privileges: STATS,
+        extensions: NONE,
+        summary: "No replication slot has lost the WAL its consumer needs",
+        matcher: Matcher::Object {
+            prefix: "slot",
+            suffix: None,
+            action: None,
+        },
+    },
+    setting(
+        "backup.max_slot_wal_keep_size",
+        ConfigCategory::Backup,
+        SuggestionLevel::Important,
+        "max_slot_wal_keep_size",
+        "Replication slots cannot retain WAL without limit",
+    ),
+    setting(
+        "backup.wal_keep_size",
+        ConfigCategory::Backup,
+        SuggestionLevel::Important,
+        "wal_keep_size",
+        "Standbys streaming without a slot or archive are covered by wal_keep_size",
+    ),
+    // Replication and resilience
+    setting(
+        "replication.tcp_keepalives_idle",
+        ConfigCategory::Replication,
+        SuggestionLevel::Important,
+        "tcp_keepalives_idle",
+        "Sessions whose client vanished are closed within minutes, not hours",
+    ),
+    setting(
+        "replication.tcp_keepalives_interval",
+        ConfigCategory::Replication,
+        SuggestionLevel::Important,
+        "tcp_keepalives_interval",
+        "Unanswered keepalive probes are retried quickly",
+    ),
+    setting(
+        "replication.tcp_keepalives_count",
+        ConfigCategory::Replication,
+        SuggestionLevel::Important,
+        "tcp_keepalives_count",
+        "Few unanswered keepalive probes close a dead connection",
+    ),
+    setting(
+        "replication.wal_receiver_timeout",
+        ConfigCategory::Replication,
+        SuggestionLevel::Important,
+        "wal_receiver_timeout",
+        "Standbys notice a silent primary within minutes",
+    ),
+    setting(
+        "replication.wal_sender_timeout",
+        ConfigCategory::Replication,
+        SuggestionLevel::Important,
+        "wal_sender_timeout",
+        "The primary drops replication connections of standbys that went silent",
+    ),
+    setting(
+        "replication.restart_after_crash",
+        ConfigCategory::Replication,
+        SuggestionLevel::Recommended,
+        "restart_after_crash",
+        "A crashed primary with standbys is left to the cluster manager to fail over",
+    ),
+    // Security
+    CheckInfo {
+        id: "security.policies_without_rls",
+        category: ConfigCategory::Security,
+        default_level: SuggestionLevel::Important,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "Tables with policies have row-level security enabled",
+        matcher: Matcher::Object {
+            prefix: "table",
+            suffix: None,
+            action: Some("Enable row level security"),
+        },
+    },
+    CheckInfo {
+        id: "security.rls_without_policies",
+        category: ConfigCategory::Security,
+        default_level: SuggestionLevel::Important,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "Tables with row-level security enabled have policies",
+        matcher: Matcher::Object {
+            prefix: "table",
+            suffix: None,
+            action: Some("Create policies"),
+        },
+    },
+    CheckInfo {
+        id: "security.rls_not_forced",
+        category: ConfigCategory::Security,
+        default_level: SuggestionLevel::Recommended,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "Row-level security is forced so the table owner is filtered too",
+        matcher: Matcher::Object {
+            prefix: "table",
+            suffix: None,
+            action: Some("Force row level security"),
+        },
+    },
+    CheckInfo {
+        id: "security.permissive_policy",
+        category: ConfigCategory::Security,
+        default_level: SuggestionLevel::Important,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "No permissive policy lets every row through with USING (true)",
+        matcher: Matcher::Object {
+            prefix: "policy",
+            suffix: None,
+            action: None,
+        },
+    },
+    CheckInfo {
+        id: "security.public_schema_create",
+        category: ConfigCategory::Security,
+        default_level: SuggestionLevel::Important,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "The public schema on search_path is not writable by every role",
+        matcher: Matcher::Object {
+            prefix: "schema",
+            suffix: None,
+            action: None,
+        },
+    },
+    setting(
+        "security.row_security",
+        ConfigCategory::Security,
+        SuggestionLevel::Important,
+        "row_security",
+        "row_security is on where tables rely on row-level security",
+    ),
+    // Schema design
+    CheckInfo {
+        id: "schema.not_valid_constraint",
+        category: ConfigCategory::Schema,
+        default_level: SuggestionLevel::Info,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "Foreign keys and check constraints added NOT VALID have been validated",
+        matcher: Matcher::Object {
+            prefix: "constraint",
+            suffix: None,
+            action: Some("VALIDATE CONSTRAINT"),
+        },
+    },
+    CheckInfo {
+        id: "schema.disabled_trigger",
+        category: ConfigCategory::Schema,
+        default_level: SuggestionLevel::Important,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "No trigger on a user table is disabled",
+        matcher: Matcher::Object {
+            prefix: "trigger",
+            suffix: None,
+            action: Some("ENABLE TRIGGER"),
+        },
+    },
+    CheckInfo {
+        id: "schema.disabled_fk_trigger",
+        category: ConfigCategory::Schema,
+        default_level: SuggestionLevel::Important,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "The triggers enforcing foreign keys are enabled",
+        matcher: Matcher::Object {
+            prefix: "foreign key",
+            suffix: None,
+            action: None,
+        },
+    },
+    CheckInfo {
+        id: "schema.varchar_255",
+        category: ConfigCategory::Schema,
+        default_level: SuggestionLevel::Info,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "varchar(255) is not the default string type (with --schema-lint)",
+        matcher: Matcher::Object {
+            prefix: "schema",
+            suffix: None,
+            action: Some("text"),
+        },
+    },
+    CheckInfo {
+        id: "schema.timestamp_without_time_zone",
+        category: ConfigCategory::Schema,
+        default_level: SuggestionLevel::Info,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "Timestamps are stored with time zone (with --schema-lint)",
+        matcher: Matcher::Object {
+            prefix: "schema",
+            suffix: None,
+            action: Some("timestamptz"),
+        },
+    },
+    CheckInfo {
+        id: "schema.money",
+        category: ConfigCategory::Schema,
+        default_level: SuggestionLevel::Info,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "Amounts use numeric rather than money (with --schema-lint)",
+        matcher: Matcher::Object {
+            prefix: "schema",
+            suffix: None,
+            action: Some("numeric"),
+        },
+    },
+    CheckInfo {
+        id: "schema.missing_primary_key",
+        category: ConfigCategory::Schema,
+        default_level: SuggestionLevel::Info,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "Every table has a primary key (with --schema-lint)",
+        matcher: Matcher::Object {
+            prefix: "schema",
+            suffix: None,
+            action: Some("Add primary keys"),
+        },
+    },
+    CheckInfo {
+        id: "schema.unindexed_nullable_fk",
+        category: ConfigCategory::Schema,
+        default_level: SuggestionLevel::Info,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "Nullable foreign key columns are indexed (with --schema-lint)",
+        matcher: Matcher::Object {
+            prefix: "schema",
+            suffix: None,
+            action: Some("Index the foreign key columns"),
+        },
+    },
+    // Table and index health
+    CheckInfo {
+        id: "table_index.orphaned_temp_schema",
+        category: ConfigCategory::TableIndex,
+        default_level: SuggestionLevel::Important,
+        privileges: NONE,
+        extensions: NONE,
+        summary: "No temp tables are left behind by backends that are gone (PG16+)",
+        matcher: Matcher::Object {
+            prefix: "temp schema",
+            suffix: None,
+            action: None,
+        },
+    },
+    CheckInfo {
+        id: "table_index.bloat",
+        category: ConfigCategory::TableIndex,
+        default_level: SuggestionLevel::Critical,
+        privileges: STATS,
+        extensions: NONE,
+        summary: "Tables with a high dead-tuple ratio and stale autovacuum",
+        matcher: Matcher::Object {
+            prefix: "table",
+            suffix: Some("bloat"),
+            action: None,
+        },
+    },
+    CheckInfo {
+        id: "table_index.seq_scan_hotspot",
+        category: ConfigCategory::TableIndex,
+        default_level: SuggestionLevel::Important,
+        privileges: STATS,
+        extensions: NONE,
+        summary: "Large tables dominated by sequential scans",
+        matcher: Matcher::Object {
+            prefix: "table",
+            suffix: Some("sequential scans"),
+            action: None,
+        },
+    },
+    CheckInfo {
+        id: "table_index.unused_index",
+        category: ConfigCategory::TableIndex,
+        default_level: SuggestionLevel::Important,
+        privileges: STATS,
+        extensions: NONE,
+        summary: "Non-constraint indexes that have never been scanned",
+        matcher: Matcher::Object {
+            prefix: "index",
+            suffix: None,
+            action: Some("Drop unused index"),
+        },
+    },
+    CheckInfo {
+        id: "table_index.low_selectivity",
+        category: ConfigCategory::TableIndex,
+        default_level: SuggestionLevel::Recommended,
+        privileges: STATS,
+        extensions: NONE,
+        summary: "Indexes that return a large fraction of the table per scan",
+        matcher: Matcher::Object {
+            prefix: "index",
+            suffix: None,
+            action: Some("Replace with more selective (composite/partial) index"),
+        },
+    },
+    CheckInfo {
+        id: "table_index.failed_index_only",
+        category: ConfigCategory::TableIndex,
+        default_level: SuggestionLevel::Recommended,
+        privileges: STATS,
+        extensions: NONE,
+        summary: "Index scans that still fetch most rows from the heap",
+        matcher: Matcher::Object {
+            prefix: "index",
+            suffix: None,
+            action: Some("Add INCLUDE columns or VACUUM to refresh visibility"),
+        },
+    },
+    CheckInfo {
+        id: "table_index.missing_partial_index",
+        category: ConfigCategory::TableIndex,
+        default_level: SuggestionLevel::Important,
+        privileges: TABLE_READ,
+        extensions: NONE,
+        summary: "Soft-delete tables without a partial index excluding deleted rows",
+        matcher: Matcher::Object {
+            prefix: "index",
+            suffix: None,
+            action: Some("Create partial index on soft-delete column"),
+        },
+    },
+    CheckInfo {
+        id: "table_index.brin_candidate",
+        category: ConfigCategory::TableIndex,
+        default_level: SuggestionLevel::Recommended,
+        privileges: TABLE_READ,
+        extensions: NONE,
+        summary: "Large, physically ordered columns that suit a BRIN index",
+        matcher: Matcher::Object {
+            prefix: "index",
+            suffix: None,
+            action: Some("Replace B-Tree with BRIN index"),
+        },
+    },
+    CheckInfo {
+        id: "table_index.missing_spatial_index",
+        category: ConfigCategory::TableIndex,
+        default_level: SuggestionLevel::Important,
+        privileges: NONE,
+        extensions: &["postgis"],
+        summary: "Large tables with geometry or geography columns and no spatial index",
+        matcher: Matcher::Object {
+            prefix: "index",
+            suffix: None,
+            action: Some("Create spatial (GiST) index"),
+        },
+    },
+    setting(
+        "table_index.default_toast_compression",
+        ConfigCategory::TableIndex,
+        SuggestionLevel::Recommended,
+        "default_toast_compression",
+        "default_toast_compression is lz4 when write-heavy tables hold large TOAST data (PG14+)",
+    ),
+    CheckInfo {
+        id: "table_index.toast_compression",
+        category: ConfigCategory::TableIndex,
+        default_level: SuggestionLevel::Recommended,
+        privileges: STATS,
+        extensions: NONE,
+        summary: "pglz-compressed text and JSON columns of write-heavy tables with large TOAST (PG14+)",
+        matcher: Matcher::Object {
+            prefix: "table",
+            suffix: Some("compression"),
+            action: None,
+        },
+    },
+    // Workload
+    CheckInfo {
+        id: "workload.slow_queries",
+        category: ConfigCategory::Workload,
+        default_level: SuggestionLevel::Info,
+        privileges: STATS,
+        extensions: &["pg_stat_statements"],
+        summary: "Top statements by total time, mean time, I/O and temp usage",
+        matcher: Matcher::Finding,
+    },
+    CheckInfo {
+        id: "workload.index_candidates",
+        category: ConfigCategory::Workload,
+        default_level: SuggestionLevel::Info,
+        privileges: TABLE_READ,
+        extensions: &["pg_stat_statements"],
+        summary: "Missing indexes inferred from slow statement predicates",
+        matcher: Matcher::Finding,
+    },
+];
+
+/// Looks up a check by its stable ID
+pub fn find(id: &str) -> Option<&'static CheckInfo> {
+    CHECKS.iter().find(|check| check.id == id)
+}
+
+/// Looks up the settings check covering a PostgreSQL parameter
+pub fn for_parameter(name: &str) -> Option<&'static CheckInfo> {
+    CHECKS.iter().find(|check| {
+        check
+            .parameter()
+            .is_some_and(|p| p.eq_ignore_ascii_case(name))
+    })
+}
+
+/// Resolves the check that produced a suggestion
+pub fn for_suggestion(
+    category: ConfigCategory,
+    suggestion: &ConfigSuggestion,
+) -> Option<&'static CheckInfo> {
+    CHECKS
+        .iter()
+        .filter(|check| check.category == category)
+        .find(|check| check.matches(suggestion))
+}
+
+impl CheckInfo {
+    /// The GUC a settings check inspects, if any
+    pub fn parameter(&self) -> Option<&'static str> {
+        match self.matcher {
+            Matcher::Parameter(name) => Some(name),
+            Matcher::Object { .. } | Matcher::Finding => None,
+        }
+    }
+
+    /// What a matched suggestion is about: the parameter for settings checks,
+    /// the table, index or column for object checks
+    pub fn object<'a>(&self, suggestion: &'a ConfigSuggestion) -> &'a str {
+        let parameter = suggestion.parameter.as_str();
+        match self.matcher {
+            Matcher::Object { prefix, suffix, .. } => {
+                let object = parameter
+                    .strip_prefix(prefix)
+                    .map_or(parameter, str::trim_start);
+                suffix
+                    .and_then(|suffix| object.strip_suffix(suffix))
+                    .map_or(object, str::trim_end)
+            }
+            Matcher::Parameter(_) | Matcher::Finding => parameter,
+        }
+    }
+
+    /// Whether the check reports findings rather than suggestions
+    pub fn is_finding(&self) -> bool {
+        matches!(self.matcher, Matcher::Finding)
+    }
+
+    fn matches(&self, suggestion: &ConfigSuggestion) -> bool {
+        match self.matcher {
+            Matcher::Parameter(name) => suggestion.parameter == name,
+            Matcher::Object {
+                prefix,
+                suffix,
+                action,
+            } => {
+                let Some(rest) = suggestion
+                    .parameter
+                    .strip_prefix(prefix)
+                    .and_then(|rest| rest.strip_prefix(' '))
+                else {
+                    return false;
+                };
+                let suffix_matches =
+                    suffix.is_none_or(|suffix| rest.ends_with(&format!(" {suffix}")));
+                let action_matches =
+                    action.is_none_or(|action| suggestion.suggested_value == action);
+                suffix_matches && action_matches
+            }
+            Matcher::Finding => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{autovacuum, concurrency, extensions, logging, memory, planner, wal};
+    use crate::models::{
+        AnalysisResults, ByteSize, ParamValue, PgConfigParam, SystemStats, VectorColumn,
+        VectorIndex,
+    };
+    use rstest::rstest;
+    use std::collections::{HashMap, HashSet};
+
+    fn param(name: &str, value: &str, unit: Option<&str>) -> (String, PgConfigParam) {
+        (
+            name.to_string(),
+            PgConfigParam {
+                name: name.to_string(),
+                current_value: value.to_string(),
+                default_value: None,
+                unit: unit.map(str::to_string),
+                context: "user".to_string(),
+                value: ParamValue::parse(value, "", unit),
+                source: None,
+                sourcefile: None,
+                pending_restart: false,
+                min_value: None,
+                max_value: None,
+            },
+        )
+    }
+
+    #[test]
+    fn check_ids_are_unique_and_prefixed_by_category() {
+        let mut seen = HashSet::new();
+        for check in CHECKS {
+            assert!(seen.insert(check.id), "duplicate check id {}", check.id);
+            let prefix = serde_json::to_value(check.category).unwrap();
+            assert!(
+                check
+                    .id
+                    .starts_with(&format!("{}.", prefix.as_str().unwrap())),
+                "{} should be prefixed by its category",
+                check.id
+            );
+        }
+    }
+
+    #[test]
+    fn every_settings_suggestion_maps_to_a_registered_check() {
+        // Deliberately poor values so every settings analyzer emits something
+        let params: HashMap<_, _> = [
+            param("shared_buffers", "16", Some("MB")),
+            param("effective_cache_size", "1", Some("GB")),
+            param("work_mem", "1", Some("MB")),
+            param("maintenance_work_mem", "64", Some("MB")),
+            param("wal_buffers", "4", Some("MB")),
+            param("max_connections", "1000", None),
+            param("max_worker_processes", "2", None),
+            param("max_parallel_workers", "64", None),
+            param("max_parallel_workers_per_gather", "64", None),
+            param("max_parallel_maintenance_workers", "0", None),
+            param("max_wal_size", "1024", Some("MB")),
+            param("min_wal_size", "80", Some("MB")),
+            param("checkpoint_timeout", "60", Some("s")),
+            param("checkpoint_completion_target", "0.5", None),
+            param("random_page_cost", "4.0", None),
+            param("effective_io_concurrency", "1", None),
+            param("maintenance_io_concurrency", "10", None),
+            param("seq_page_cost", "2.0", None),
+            param("default_statistics_target", "100", None),
+            param("autovacuum_max_workers", "3", None),
+            param("autovacuum_naptime", "600", Some("s")),
+            param("autovacuum_vacuum_cost_limit", "-1", None),
+            param("autovacuum_work_mem", "-1", Some("kB")),
+            param("autovacuum_vacuum_scale_factor", "0.2", None),
+            param("log_min_duration_statement", "-1", Some("ms")),
+            param("log_lock_waits", "off", None),
+            param("deadlock_timeout", "1000", Some("ms")),
+            param("shared_preload_libraries", "timescaledb,citus", None),
+            param("timescaledb.max_background_workers", "1", None),
+            param("citus.shard_count", "2", None),
+            param("citus.max_adaptive_executor_pool_size", "16", None),
+            param("hnsw.ef_search", "40", None),
+        ]
+        .into_iter()
+        .collect();
+        let stats = SystemStats {
+            total_memory_gb: Some(64.0),
+            cpu_count: Some(16),
+            workload_type: crate::config::WorkloadType::Olap,
+            peak_connection_count: Some(500),
+            citus_workers: Some(4),
+            timescale_jobs: Some(4),
+            extensions: vec!["vector".to_string()],
+            vector_columns: vec![
+                VectorColumn {
+                    schema: "public".to_string(),
+                    table_name: "documents".to_string(),
+                    column_name: "embedding".to_string(),
+                    type_name: "vector".to_string(),
+                    dimensions: Some(1536),
+                    estimated_rows: 2_000_000,
+                    distance_calls: Some(10_000),
+                    ..Default::default()
+                },
+                VectorColumn {
+                    schema: "public".to_string(),
+                    table_name: "chunks".to_string(),
+                    column_name: "embedding".to_string(),
+                    type_name: "vector".to_string(),
+                    estimated_rows: 5_000_000,
+                    indexes: vec![
+                        VectorIndex {
+                            name: "chunks_embedding_ivfflat_idx".to_string(),
+                            method: "ivfflat".to_string(),
+                            lists: None,
+                            size_bytes: ByteSize(0),
+                        },
+                        VectorIndex {
+                            name: "chunks_embedding_hnsw_idx".to_string(),
+                            method: "hnsw".to_string(),
+                            lists: None,
+                            size_bytes: ByteSize(0),
+                        },
+                    ],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut results = AnalysisResults::default();
+        memory::analyze_memory(&params, &stats, &mut results).unwrap();
+        concurrency::analyze_concurrency(&params, &stats, &mut results).unwrap();
+        wal::analyze_wal(&params, &stats, &mut results).unwrap();
+        planner::analyze_planner(&params, &stats, &mut results).unwrap();
+        autovacuum::analyze_autovacuum(&params, &stats, &mut results).unwrap();
+        logging::analyze_logging(&params, &stats, &mut results).unwrap();
+        extensions::analyze_extensions(&params, &stats, &mut results).unwrap();
+
+        assert!(results
+            .suggestions_by_category
+            .contains_key(&ConfigCategory::Extensions));
+        for (category, suggestions) in &results.suggestions_by_category {
+            for suggestion in suggestions {
+                assert!(
+                    for_suggestion(*category, suggestion).is_some(),
+                    "no registered check for {:?} / {}",
+                    category,
+                    suggestion.parameter
+                );
+            }
+        }
+    }
+
+    fn suggestion(parameter: &str, suggested_value: &str) -> ConfigSuggestion {
+        ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: String::new(),
+            suggested_value: suggested_value.to_string(),
+            level: SuggestionLevel::Important,
+            rationale: String::new(),
+            messages: Vec::new(),
+            fingerprint: String::new(),
+        }
+    }
+
+    #[rstest]
+    #[case("table public.orders bloat", "VACUUM", Some("table_index.bloat"))]
+    #[case(
+        "table public.orders sequential scans",
+        "Add indexes",
+        Some("table_index.seq_scan_hotspot")
+    )]
+    #[case(
+        "index public.orders_status_idx",
+        "Drop unused index",
+        Some("table_index.unused_index")
+    )]
+    #[case(
+        "index public.orders_created_at_idx",
+        "Replace B-Tree with BRIN index",
+        Some("table_index.brin_candidate")
+    )]
+    #[case(
+        "index public.(missing on geom)",
+        "Create spatial (GiST) index",
+        Some("table_index.missing_spatial_index")
+    )]
+    #[case(
+        "table public.events compression",
+        "lz4",
+        Some("table_index.toast_compression")
+    )]
+    #[case("index public.orders_status_idx", "Something else", None)]
+    fn table_index_suggestions_resolve_by_object_pattern(
+        #[case] parameter: &str,
+        #[case] suggested_value: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let found = for_suggestion(
+            ConfigCategory::TableIndex,
+            &suggestion(parameter, suggested_value),
+        );
+        assert_eq!(found.map(|check| check.id), expected);
+    }
+
+    #[test]
+    fn settings_checks_do_not_match_other_categories() {
+        assert!(for_suggestion(
+            ConfigCategory::Memory,
+            &suggestion("max_connections", "100")
+        )
+        .is_none());
+        assert!(find("workload.slow_queries").is_some());
+    }
+}
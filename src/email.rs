@@ -0,0 +1,247 @@
+use crate::config::DbConfig;
+use crate::models::AnalysisResults;
+use crate::reporter::{ReportFormat, Reporter, ReporterError};
+use crate::signing::SigningKey;
+use chrono::{DateTime, Utc};
+use lettre::address::AddressError;
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment, Mailbox, MultiPart, SinglePart};
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use snafu::{ensure, ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+pub enum EmailError {
+    #[snafu(display("Invalid email address '{}': {}", address, source))]
+    InvalidAddress {
+        address: String,
+        source: AddressError,
+    },
+
+    #[snafu(display("No email recipients"))]
+    NoRecipients,
+
+    #[snafu(display("Invalid SMTP URL: {}", source))]
+    SmtpUrl {
+        source: lettre::transport::smtp::Error,
+    },
+
+    #[snafu(display("Failed to render the report: {}", source))]
+    Render { source: ReporterError },
+
+    #[snafu(display("Failed to build the email: {}", source))]
+    Build { source: lettre::error::Error },
+
+    #[snafu(display("Failed to send the email: {}", source))]
+    Send {
+        source: lettre::transport::smtp::Error,
+    },
+}
+
+type Result<T, E = EmailError> = std::result::Result<T, E>;
+
+/// Sender used when `--email-from` is not given
+pub const DEFAULT_SENDER: &str = "PostGreat <postgreat@localhost>";
+
+/// Emails each report as an HTML body with the JSON report attached, for
+/// teams reading morning report emails rather than dashboards
+pub struct ReportMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+    signing_key: Option<SigningKey>,
+}
+
+impl ReportMailer {
+    /// Checks the SMTP URL and every address up front, so a typo fails at
+    /// startup rather than after the first scheduled run
+    pub fn new(smtp_url: &str, from: &str, to: &[String]) -> Result<Self> {
+        ensure!(!to.is_empty(), NoRecipientsSnafu);
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::from_url(smtp_url)
+            .context(SmtpUrlSnafu)?
+            .build();
+        Ok(Self {
+            transport,
+            from: parse_mailbox(from)?,
+            to: to
+                .iter()
+                .map(|address| parse_mailbox(address))
+                .collect::<Result<_>>()?,
+            signing_key: None,
+        })
+    }
+
+    /// Signs the attached JSON report as `--signing-key-file` does
+    pub fn with_signing_key(mut self, key: Option<SigningKey>) -> Self {
+        self.signing_key = key;
+        self
+    }
+
+    pub async fn send(
+        &self,
+        config: &DbConfig,
+        results: &AnalysisResults,
+        at: DateTime<Utc>,
+    ) -> Result<()> {
+        let message = report_message(
+            &self.from,
+            &self.to,
+            config,
+            results,
+            self.signing_key.as_ref(),
+            at,
+        )?;
+        self.transport.send(message).await.context(SendSnafu)?;
+        Ok(())
+    }
+}
+
+fn parse_mailbox(address: &str) -> Result<Mailbox> {
+    address
+        .trim()
+        .parse()
+        .context(InvalidAddressSnafu { address })
+}
+
+fn report_message(
+    from: &Mailbox,
+    to: &[Mailbox],
+    config: &DbConfig,
+    results: &AnalysisResults,
+    signing_key: Option<&SigningKey>,
+    at: DateTime<Utc>,
+) -> Result<Message> {
+    let mut html = Vec::new();
+    Reporter::new(ReportFormat::Html)
+        .write(&mut html, results)
+        .context(RenderSnafu)?;
+    let mut json = Vec::new();
+    Reporter::new(ReportFormat::Json)
+        .with_signing_key(signing_key.cloned())
+        .write(&mut json, results)
+        .context(RenderSnafu)?;
+
+    let attachment = format!(
+        "postgreat-{}-{}.json",
+        config.database,
+        at.format("%Y%m%dT%H%M%SZ")
+    );
+    let mut builder = Message::builder()
+        .from(from.clone())
+        .subject(subject(config, results));
+    for recipient in to {
+        builder = builder.to(recipient.clone());
+    }
+    builder
+        .multipart(
+            MultiPart::mixed()
+                .singlepart(SinglePart::html(
+                    String::from_utf8_lossy(&html).into_owned(),
+                ))
+                .singlepart(Attachment::new(attachment).body(
+                    json,
+                    ContentType::parse("application/json").expect("valid content type"),
+                )),
+        )
+        .context(BuildSnafu)
+}
+
+/// `PostGreat report for production_db (db1.example.com): 1 critical, 4 important`,
+/// naming only the levels that have findings
+fn subject(config: &DbConfig, results: &AnalysisResults) -> String {
+    let counts = results.summary(0).level_counts;
+    let levels: Vec<_> = [
+        (counts.critical, "critical"),
+        (counts.important, "important"),
+        (counts.recommended, "recommended"),
+    ]
+    .into_iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, level)| format!("{count} {level}"))
+    .collect();
+    format!(
+        "PostGreat report for {} ({}): {}",
+        config.database,
+        config.host,
+        if levels.is_empty() {
+            "no findings".to_string()
+        } else {
+            levels.join(", ")
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConfigCategory, ConfigSuggestion, SuggestionLevel};
+    use chrono::TimeZone;
+
+    fn results() -> AnalysisResults {
+        let mut results = AnalysisResults::default();
+        results
+            .suggestions_by_category
+            .entry(ConfigCategory::Memory)
+            .or_default()
+            .push(ConfigSuggestion {
+                parameter: "shared_buffers".to_string(),
+                current_value: "128MB".to_string(),
+                suggested_value: "16GB".to_string(),
+                level: SuggestionLevel::Critical,
+                rationale: String::new(),
+                messages: Vec::new(),
+                fingerprint: String::new(),
+            });
+        results
+    }
+
+    #[test]
+    fn report_email_has_an_html_body_and_the_json_attached() {
+        let from = parse_mailbox(DEFAULT_SENDER).unwrap();
+        let to = [
+            parse_mailbox("ops@example.com").unwrap(),
+            parse_mailbox("dba@example.com").unwrap(),
+        ];
+        let at = Utc.with_ymd_and_hms(2026, 10, 17, 6, 0, 0).unwrap();
+        let message = report_message(
+            &from,
+            &to,
+            &DbConfig::for_tests("db1.example.com", "production_db"),
+            &results(),
+            None,
+            at,
+        )
+        .unwrap();
+        let email = String::from_utf8(message.formatted()).unwrap();
+
+        assert!(email
+            .contains("Subject: PostGreat report for production_db (db1.example.com): 1 critical"));
+        assert!(email.contains("To: ops@example.com, dba@example.com"));
+        assert!(email.contains("Content-Type: text/html"));
+        assert!(email.contains("postgreat-production_db-20261017T060000Z.json"));
+        assert!(email.contains("Content-Type: application/json"));
+    }
+
+    #[test]
+    fn mailer_rejects_bad_addresses_before_any_run() {
+        assert!(matches!(
+            ReportMailer::new(
+                "smtp://localhost",
+                DEFAULT_SENDER,
+                &["not an address".into()]
+            ),
+            Err(EmailError::InvalidAddress { .. })
+        ));
+        assert!(matches!(
+            ReportMailer::new("smtp://localhost", DEFAULT_SENDER, &[]),
+            Err(EmailError::NoRecipients)
+        ));
+        assert!(matches!(
+            ReportMailer::new(
+                "ftp://localhost",
+                DEFAULT_SENDER,
+                &["ops@example.com".into()]
+            ),
+            Err(EmailError::SmtpUrl { .. })
+        ));
+    }
+}
@@ -0,0 +1,328 @@
+use crate::analysis::validation::in_param_unit;
+use crate::apply::{self, ApplyFilter, PlannedChange};
+use crate::models::AnalysisResults;
+use clap::ValueEnum;
+
+/// Parameters RDS keeps to itself; parameter groups reject them
+const RDS_UNMODIFIABLE: &[&str] = &[
+    "archive_command",
+    "archive_mode",
+    "config_file",
+    "data_directory",
+    "hba_file",
+    "ident_file",
+    "listen_addresses",
+    "port",
+    "restore_command",
+    "ssl_ca_file",
+    "ssl_cert_file",
+    "ssl_key_file",
+    "unix_socket_directories",
+];
+
+/// Parameters CloudNativePG sets itself and rejects in `spec.postgresql.parameters`
+const CNPG_FIXED: &[&str] = &[
+    "archive_command",
+    "archive_mode",
+    "archive_cleanup_command",
+    "cluster_name",
+    "config_file",
+    "data_directory",
+    "hba_file",
+    "hot_standby",
+    "ident_file",
+    "listen_addresses",
+    "log_destination",
+    "log_directory",
+    "log_filename",
+    "logging_collector",
+    "port",
+    "primary_conninfo",
+    "primary_slot_name",
+    "restore_command",
+    "shared_preload_libraries",
+    "ssl",
+    "ssl_ca_file",
+    "ssl_cert_file",
+    "ssl_key_file",
+    "synchronous_standby_names",
+    "unix_socket_directories",
+];
+
+/// Infrastructure-as-code snippets the suggestions can be rendered as, for
+/// teams that may not run `ALTER SYSTEM` themselves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EmitFormat {
+    /// `aws_db_parameter_group` resource with one `parameter` block per change
+    TerraformAwsRdsParameterGroup,
+    /// `community.postgresql.postgresql_set` tasks
+    Ansible,
+    /// CloudNativePG `Cluster` manifest with `spec.postgresql.parameters`
+    #[value(name = "cloudnative-pg")]
+    CloudNativePg,
+    /// Zalando postgres-operator `postgresql` manifest with `spec.postgresql.parameters`
+    Zalando,
+}
+
+/// Renders the settable suggestions of a run, best ranked first. Settings the
+/// server already runs with and read-only ones are left out.
+pub fn render(format: EmitFormat, results: &AnalysisResults) -> String {
+    let filter = ApplyFilter {
+        categories: Vec::new(),
+        include_restart: true,
+    };
+    let (changes, _) = apply::select(apply::plan_from_results(results), &results.params, &filter);
+    match format {
+        EmitFormat::TerraformAwsRdsParameterGroup => terraform_rds(&changes, results),
+        EmitFormat::Ansible => ansible(&changes),
+        EmitFormat::CloudNativePg | EmitFormat::Zalando => {
+            operator_manifest(format, &changes, major_version(results))
+        }
+    }
+}
+
+fn terraform_rds(changes: &[PlannedChange], results: &AnalysisResults) -> String {
+    let family = major_version(results)
+        .map(|major| format!("postgres{major}"))
+        .unwrap_or_else(|| "<family>".to_string());
+    let mut hcl = String::from(
+        "# Use aurora-postgresql<major> as the family for Aurora clusters\n\
+         resource \"aws_db_parameter_group\" \"postgres\" {\n  \
+         name   = \"<parameter-group-name>\"\n",
+    );
+    hcl.push_str(&format!("  family = \"{family}\"\n"));
+
+    let mut unmodifiable = Vec::new();
+    for change in changes {
+        let name = change.step.parameter.as_str();
+        if RDS_UNMODIFIABLE.contains(&name) {
+            unmodifiable.push(name);
+            continue;
+        }
+        let Some(value) = &change.step.value else {
+            continue;
+        };
+        // Static parameters are rejected with "immediate"
+        let apply_method = if change.needs_restart() {
+            "pending-reboot"
+        } else {
+            "immediate"
+        };
+        hcl.push_str(&format!(
+            "\n  parameter {{\n    name         = \"{name}\"\n    value        = {}\n    \
+             apply_method = \"{apply_method}\"\n  }}\n",
+            hcl_string(&native_value(change, value))
+        ));
+    }
+    hcl.push_str("}\n");
+    if !unmodifiable.is_empty() {
+        hcl.push_str(&format!(
+            "# Not settable in an RDS parameter group: {}\n",
+            unmodifiable.join(", ")
+        ));
+    }
+    hcl
+}
+
+fn ansible(changes: &[PlannedChange]) -> String {
+    let mut yaml = String::from(
+        "# Tasks for community.postgresql; settings marked as needing a restart only\n\
+         # take effect once PostgreSQL restarts\n",
+    );
+    for change in changes {
+        let name = change.step.parameter.as_str();
+        yaml.push_str(&format!(
+            "- name: Set {name}\n  community.postgresql.postgresql_set:\n    name: {name}\n"
+        ));
+        match &change.step.value {
+            Some(value) => yaml.push_str(&format!("    value: {}\n", yaml_string(value))),
+            None => yaml.push_str("    reset: true\n"),
+        }
+        if change.needs_restart() {
+            yaml.push_str("  # needs a restart\n");
+        }
+    }
+    yaml
+}
+
+/// The cluster manifest of a Kubernetes operator, parameters sorted by name so
+/// the section diffs cleanly against the one in a GitOps repository
+fn operator_manifest(format: EmitFormat, changes: &[PlannedChange], major: Option<u32>) -> String {
+    let mut yaml = match format {
+        EmitFormat::Zalando => format!(
+            "# Patroni marks the members pending restart for settings that need one\n\
+             apiVersion: acid.zalan.do/v1\n\
+             kind: postgresql\n\
+             metadata:\n  name: <cluster-name>\n\
+             spec:\n  postgresql:\n    version: {}\n    parameters:\n",
+            yaml_string(&major.map_or_else(|| "<major>".to_string(), |major| major.to_string()))
+        ),
+        _ => "# CloudNativePG restarts the instances for settings that need it\n\
+              apiVersion: postgresql.cnpg.io/v1\n\
+              kind: Cluster\n\
+              metadata:\n  name: <cluster-name>\n\
+              spec:\n  postgresql:\n    parameters:\n"
+            .to_string(),
+    };
+
+    let mut parameters: Vec<_> = changes
+        .iter()
+        .filter_map(|change| Some((change, change.step.value.as_deref()?)))
+        .collect();
+    parameters.sort_by_key(|(change, _)| change.step.parameter.as_str());
+    let mut fixed = Vec::new();
+    for (change, value) in parameters {
+        let name = change.step.parameter.as_str();
+        if format == EmitFormat::CloudNativePg && CNPG_FIXED.contains(&name) {
+            fixed.push(name);
+            continue;
+        }
+        yaml.push_str(&format!("      {name}: {}", yaml_string(value)));
+        if change.needs_restart() {
+            yaml.push_str("  # needs a restart");
+        }
+        yaml.push('\n');
+    }
+    if !fixed.is_empty() {
+        yaml.push_str(&format!(
+            "# Managed by CloudNativePG, not settable here: {}\n",
+            fixed.join(", ")
+        ));
+    }
+    yaml
+}
+
+/// The value in the parameter's own unit, since parameter groups take bare
+/// numbers; values without a number are kept as suggested
+fn native_value(change: &PlannedChange, value: &str) -> String {
+    match in_param_unit(value, &change.current) {
+        Some(number) if number.fract() == 0.0 => format!("{number:.0}"),
+        Some(number) => number.to_string(),
+        None => value.to_string(),
+    }
+}
+
+fn major_version(results: &AnalysisResults) -> Option<u32> {
+    let num: u32 = results
+        .params
+        .get("server_version_num")?
+        .current_value
+        .parse()
+        .ok()?;
+    Some(num / 10000)
+}
+
+fn hcl_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Quotes every value, so `on`/`off` stay strings rather than YAML booleans
+fn yaml_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        ConfigCategory, ConfigSuggestion, ParamValue, PgConfigParam, SuggestionLevel,
+    };
+
+    fn param(name: &str, setting: &str, unit: Option<&str>, context: &str) -> PgConfigParam {
+        PgConfigParam {
+            name: name.to_string(),
+            current_value: setting.to_string(),
+            default_value: None,
+            unit: unit.map(str::to_string),
+            context: context.to_string(),
+            value: ParamValue::parse(setting, "", unit),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        }
+    }
+
+    fn results() -> AnalysisResults {
+        let mut results = AnalysisResults {
+            params: [
+                param("server_version_num", "160004", None, "internal"),
+                param("shared_buffers", "16384", Some("8kB"), "postmaster"),
+                param("log_lock_waits", "off", None, "superuser"),
+                param("archive_mode", "off", None, "postmaster"),
+            ]
+            .into_iter()
+            .map(|param| (param.name.clone(), param))
+            .collect(),
+            ..AnalysisResults::default()
+        };
+        for (level, parameter, value) in [
+            (SuggestionLevel::Critical, "shared_buffers", "4GB"),
+            (SuggestionLevel::Important, "log_lock_waits", "on"),
+            (SuggestionLevel::Recommended, "archive_mode", "on"),
+        ] {
+            results
+                .suggestions_by_category
+                .entry(ConfigCategory::Memory)
+                .or_default()
+                .push(ConfigSuggestion {
+                    parameter: parameter.to_string(),
+                    current_value: String::new(),
+                    suggested_value: value.to_string(),
+                    level,
+                    rationale: String::new(),
+                    messages: Vec::new(),
+                    fingerprint: String::new(),
+                });
+        }
+        results
+    }
+
+    #[test]
+    fn terraform_uses_native_units_and_reboot_for_static_parameters() {
+        let hcl = render(EmitFormat::TerraformAwsRdsParameterGroup, &results());
+        assert!(hcl.contains("  family = \"postgres16\"\n"));
+        assert!(hcl.contains(
+            "  parameter {\n    name         = \"shared_buffers\"\n    value        = \"524288\"\n    apply_method = \"pending-reboot\"\n  }\n"
+        ));
+        assert!(hcl.contains(
+            "    name         = \"log_lock_waits\"\n    value        = \"on\"\n    apply_method = \"immediate\"\n"
+        ));
+        assert!(hcl.ends_with("}\n# Not settable in an RDS parameter group: archive_mode\n"));
+    }
+
+    #[test]
+    fn ansible_emits_one_postgresql_set_task_per_change() {
+        let yaml = render(EmitFormat::Ansible, &results());
+        assert!(yaml.contains(
+            "- name: Set shared_buffers\n  community.postgresql.postgresql_set:\n    name: shared_buffers\n    value: '4GB'\n  # needs a restart\n"
+        ));
+        assert!(yaml.contains("    name: log_lock_waits\n    value: 'on'\n"));
+        assert!(yaml.contains("    name: archive_mode\n"));
+    }
+
+    #[test]
+    fn operator_manifests_list_parameters_by_name() {
+        let cnpg = render(EmitFormat::CloudNativePg, &results());
+        assert!(cnpg.contains("apiVersion: postgresql.cnpg.io/v1\nkind: Cluster\n"));
+        assert!(cnpg.ends_with(
+            "    parameters:\n      log_lock_waits: 'on'\n      shared_buffers: '4GB'  # needs a restart\n\
+             # Managed by CloudNativePG, not settable here: archive_mode\n"
+        ));
+
+        let zalando = render(EmitFormat::Zalando, &results());
+        assert!(zalando.contains("kind: postgresql\n"));
+        assert!(zalando.contains("    version: '16'\n    parameters:\n"));
+        assert!(zalando.contains("      archive_mode: 'on'  # needs a restart\n"));
+    }
+
+    #[test]
+    fn nothing_to_change_leaves_an_empty_snippet() {
+        let results = AnalysisResults::default();
+        let hcl = render(EmitFormat::TerraformAwsRdsParameterGroup, &results);
+        assert!(hcl.contains("family = \"<family>\""));
+        assert!(!hcl.contains("parameter {"));
+        assert!(!render(EmitFormat::Ansible, &results).contains("- name"));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::models::{AnalysisResults, LevelCounts};
+use serde::Serialize;
+use std::time::Duration;
+
+/// One line closing a run with `--exit-summary`, so log-based alerting can
+/// match e.g. `critical>0` without parsing the report
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExitSummary {
+    pub status: RunStatus,
+    /// Databases analyzed, in order; empty for commands that read none
+    pub databases: Vec<String>,
+    #[serde(flatten)]
+    pub level_counts: LevelCounts,
+    pub duration_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunStatus {
+    #[default]
+    Ok,
+    Error,
+}
+
+impl ExitSummary {
+    /// Adds one database's suggestion counts, after `--rules` were applied;
+    /// fixtures replay without a database name
+    pub fn record(&mut self, database: &str, results: &AnalysisResults) {
+        if !database.is_empty() {
+            self.databases.push(database.to_string());
+        }
+        let counts = results.summary(0).level_counts;
+        self.level_counts.critical += counts.critical;
+        self.level_counts.important += counts.important;
+        self.level_counts.recommended += counts.recommended;
+        self.level_counts.info += counts.info;
+    }
+
+    /// Marks the run done, failed when `error` is set
+    pub fn finish(&mut self, elapsed: Duration, error: Option<&anyhow::Error>) {
+        self.duration_ms = elapsed.as_millis();
+        if let Some(error) = error {
+            self.status = RunStatus::Error;
+            self.error = Some(error.to_string());
+        }
+    }
+
+    /// `postgreat status=ok database=app critical=1 ...`, quoting values with
+    /// spaces, quotes or `=`
+    pub fn to_key_value(&self) -> String {
+        let status = match self.status {
+            RunStatus::Ok => "ok",
+            RunStatus::Error => "error",
+        };
+        let counts = self.level_counts;
+        let mut line = format!(
+            "postgreat status={status} database={} critical={} important={} recommended={} info={} duration_ms={}",
+            kv_value(&self.databases.join(",")),
+            counts.critical,
+            counts.important,
+            counts.recommended,
+            counts.info,
+            self.duration_ms
+        );
+        if let Some(error) = &self.error {
+            line.push_str(&format!(" error={}", kv_value(error)));
+        }
+        line
+    }
+
+    /// The summary as one JSON object, for `--log-json` collectors
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("exit summary serializes")
+    }
+}
+
+fn kv_value(value: &str) -> String {
+    if !value.is_empty() && !value.contains([' ', '"', '=', '\n']) {
+        return value.to_string();
+    }
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConfigCategory, ConfigSuggestion, SuggestionLevel};
+
+    #[test]
+    fn key_value_line_counts_suggestions_per_level_across_databases() {
+        let mut results = AnalysisResults::default();
+        for level in [SuggestionLevel::Critical, SuggestionLevel::Recommended] {
+            results
+                .suggestions_by_category
+                .entry(ConfigCategory::Memory)
+                .or_default()
+                .push(ConfigSuggestion {
+                    parameter: "shared_buffers".into(),
+                    current_value: String::new(),
+                    suggested_value: String::new(),
+                    level,
+                    rationale: String::new(),
+                    messages: Vec::new(),
+                    fingerprint: String::new(),
+                });
+        }
+
+        let mut summary = ExitSummary::default();
+        summary.record("app", &results);
+        summary.record("billing", &results);
+        summary.finish(Duration::from_millis(1520), None);
+        assert_eq!(
+            summary.to_key_value(),
+            "postgreat status=ok database=app,billing critical=2 important=0 recommended=2 info=0 duration_ms=1520"
+        );
+        assert_eq!(
+            summary.to_json(),
+            r#"{"status":"ok","databases":["app","billing"],"critical":2,"important":0,"recommended":2,"info":0,"duration_ms":1520}"#
+        );
+    }
+
+    #[test]
+    fn failed_runs_quote_the_error() {
+        let mut summary = ExitSummary::default();
+        let error = anyhow::anyhow!("password authentication failed for user \"app\"");
+        summary.finish(Duration::from_millis(40), Some(&error));
+        assert_eq!(
+            summary.to_key_value(),
+            r#"postgreat status=error database="" critical=0 important=0 recommended=0 info=0 duration_ms=40 error="password authentication failed for user \"app\"""#
+        );
+        assert!(summary.to_json().contains(r#""status":"error""#));
+    }
+}
@@ -0,0 +1,192 @@
+use crate::checks;
+use crate::models::{
+    AnalysisResults, ConfigCategory, ConfigSuggestion, IndexIssueKind, TableIndexHealth,
+};
+
+/// 64-bit FNV-1a parameters
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Hashes a check ID and the identity of the object it reported on into 16
+/// hex digits. FNV-1a rather than `DefaultHasher`, whose output may change
+/// between Rust releases, because fingerprints are saved and compared later.
+pub fn fingerprint(check_id: &str, object: &str) -> String {
+    let hash = check_id
+        .bytes()
+        .chain(std::iter::once(0))
+        .chain(object.bytes())
+        .fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+        });
+    format!("{hash:016x}")
+}
+
+/// Fills in every fingerprint that is still empty, so results saved before
+/// fingerprints existed compare equal to fresh ones
+pub fn assign(results: &mut AnalysisResults) {
+    for (category, suggestions) in results.suggestions_by_category.iter_mut() {
+        for suggestion in suggestions.iter_mut() {
+            if suggestion.fingerprint.is_empty() {
+                suggestion.fingerprint = suggestion_fingerprint(*category, suggestion);
+            }
+        }
+    }
+    assign_table_health(&mut results.table_health);
+    if let Some(workload) = results.workload.as_mut() {
+        assign_table_health(&mut workload.table_health);
+    }
+}
+
+/// Fingerprints bloat, sequential scan and index findings by table or index name
+pub fn assign_table_health(health: &mut TableIndexHealth) {
+    for table in health
+        .bloat_info
+        .iter_mut()
+        .filter(|table| table.fingerprint.is_empty())
+    {
+        table.fingerprint = fingerprint(
+            "table_index.bloat",
+            &format!("{}.{}", table.schema, table.table_name),
+        );
+    }
+    for table in health
+        .seq_scan_info
+        .iter_mut()
+        .filter(|table| table.fingerprint.is_empty())
+    {
+        table.fingerprint = fingerprint(
+            "table_index.seq_scan_hotspot",
+            &format!("{}.{}", table.schema, table.table_name),
+        );
+    }
+    for index in health
+        .index_usage_info
+        .iter_mut()
+        .filter(|index| index.fingerprint.is_empty())
+    {
+        index.fingerprint = fingerprint(
+            index_check_id(&index.issue),
+            &format!("{}.{}", index.schema, index.index_name),
+        );
+    }
+}
+
+/// Suggestions no check claims fall back to their category and parameter
+fn suggestion_fingerprint(category: ConfigCategory, suggestion: &ConfigSuggestion) -> String {
+    match checks::for_suggestion(category, suggestion) {
+        Some(check) => fingerprint(check.id, check.object(suggestion)),
+        None => fingerprint(category.as_str(), &suggestion.parameter),
+    }
+}
+
+fn index_check_id(issue: &IndexIssueKind) -> &'static str {
+    match issue {
+        IndexIssueKind::Unused => "table_index.unused_index",
+        IndexIssueKind::LowSelectivity => "table_index.low_selectivity",
+        IndexIssueKind::FailedIndexOnly => "table_index.failed_index_only",
+        IndexIssueKind::MissingPartialIndex => "table_index.missing_partial_index",
+        IndexIssueKind::BrinCandidate => "table_index.brin_candidate",
+        IndexIssueKind::MissingSpatialIndex => "table_index.missing_spatial_index",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{SuggestionLevel, TableSeqScanInfo};
+    use rstest::rstest;
+
+    fn suggestion(parameter: &str, suggested_value: &str) -> ConfigSuggestion {
+        ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: String::new(),
+            suggested_value: suggested_value.to_string(),
+            level: SuggestionLevel::Important,
+            rationale: String::new(),
+            messages: Vec::new(),
+            fingerprint: String::new(),
+        }
+    }
+
+    #[test]
+    fn fingerprints_are_pinned_across_releases() {
+        assert_eq!(
+            fingerprint("table_index.bloat", "public.orders"),
+            "f6885ae7cdc1f7f4"
+        );
+    }
+
+    #[rstest]
+    #[case(
+        ConfigCategory::TableIndex,
+        "table public.orders sequential scans",
+        "Add or extend indexes to lower sequential scans",
+        "table_index.seq_scan_hotspot",
+        "public.orders"
+    )]
+    #[case(
+        ConfigCategory::TableIndex,
+        "index public.orders_status_idx",
+        "Drop unused index",
+        "table_index.unused_index",
+        "public.orders_status_idx"
+    )]
+    #[case(
+        ConfigCategory::Memory,
+        "maintenance_work_mem",
+        "1GB",
+        "memory.maintenance_work_mem",
+        "maintenance_work_mem"
+    )]
+    #[case(
+        ConfigCategory::Extensions,
+        "maintenance_work_mem",
+        "2GB",
+        "extensions.pgvector_build_memory",
+        "maintenance_work_mem"
+    )]
+    fn suggestions_hash_their_check_and_object(
+        #[case] category: ConfigCategory,
+        #[case] parameter: &str,
+        #[case] suggested_value: &str,
+        #[case] check_id: &str,
+        #[case] object: &str,
+    ) {
+        let mut results = AnalysisResults::default();
+        results
+            .suggestions_by_category
+            .insert(category, vec![suggestion(parameter, suggested_value)]);
+        assign(&mut results);
+        assert_eq!(
+            results.suggestions_by_category[&category][0].fingerprint,
+            fingerprint(check_id, object)
+        );
+    }
+
+    #[test]
+    fn findings_share_the_fingerprint_of_their_suggestion() {
+        let mut results = AnalysisResults::default();
+        results.suggestions_by_category.insert(
+            ConfigCategory::TableIndex,
+            vec![suggestion(
+                "table public.orders sequential scans",
+                "Add or extend indexes to lower sequential scans",
+            )],
+        );
+        results.table_health.seq_scan_info.push(TableSeqScanInfo {
+            schema: "public".into(),
+            table_name: "orders".into(),
+            seq_scan: 10,
+            idx_scan: 0,
+            live_tuples: 100,
+            table_size_bytes: Default::default(),
+            table_size_pretty: String::new(),
+            fingerprint: String::new(),
+        });
+        assign(&mut results);
+        assert_eq!(
+            results.table_health.seq_scan_info[0].fingerprint,
+            results.suggestions_by_category[&ConfigCategory::TableIndex][0].fingerprint
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use crate::checker::CheckerError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use snafu::{ResultExt, Snafu};
+use std::fs::{self, File};
+use std::future::Future;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+#[derive(Debug, Snafu)]
+pub enum FixtureError {
+    #[snafu(display("Failed to write fixture {}: {}", path.display(), source))]
+    FixtureWrite {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Failed to serialize fixture {}: {}", path.display(), source))]
+    FixtureSerialize {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[snafu(display("Failed to read fixture {}: {}", path.display(), source))]
+    FixtureRead {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("{} is not a fixture of this version: {}", path.display(), source))]
+    FixtureParse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+/// Where `analyze` reads catalog and statistics data from.
+///
+/// Each fetch is served under a stable name; a recording writes the fetched
+/// value to `<dir>/<name>.json` and a replay reads it back instead of querying,
+/// so the analyzers and reporters run unchanged without a database. Failed
+/// fetches are recorded as `{"Err": "<message>"}` and replayed as the same
+/// error, so replayed warnings match the recorded run.
+#[derive(Debug, Clone, Default)]
+pub enum Fixture {
+    /// Query the database
+    #[default]
+    Live,
+    /// Query the database and write every result to the directory
+    Record(PathBuf),
+    /// Serve every result from the directory without connecting
+    Replay(PathBuf),
+}
+
+impl Fixture {
+    pub fn is_replay(&self) -> bool {
+        matches!(self, Fixture::Replay(_))
+    }
+
+    /// Serves a fetch that reports its own failures and falls back to a
+    /// default; a missing or unreadable fixture is a warning and the default
+    pub(crate) async fn serve<T, F>(&self, name: &str, fetch: F) -> T
+    where
+        T: Serialize + DeserializeOwned + Default,
+        F: Future<Output = T>,
+    {
+        match self {
+            Fixture::Live => fetch.await,
+            Fixture::Record(dir) => {
+                let value = fetch.await;
+                if let Err(err) = write(dir, name, &value) {
+                    warn!("{err}");
+                }
+                value
+            }
+            Fixture::Replay(dir) => read(dir, name).unwrap_or_else(|err| {
+                warn!("{err}");
+                T::default()
+            }),
+        }
+    }
+
+    /// Serves a fallible fetch, recording its error message when it fails
+    pub(crate) async fn try_serve<T, F>(&self, name: &str, fetch: F) -> Result<T, CheckerError>
+    where
+        T: Serialize + DeserializeOwned,
+        F: Future<Output = Result<T, CheckerError>>,
+    {
+        match self {
+            Fixture::Live => fetch.await,
+            Fixture::Record(dir) => {
+                let result = fetch.await;
+                let recorded = result.as_ref().map_err(ToString::to_string);
+                if let Err(err) = write(dir, name, &recorded) {
+                    warn!("{err}");
+                }
+                result
+            }
+            Fixture::Replay(dir) => {
+                let recorded: Result<T, String> =
+                    read(dir, name).map_err(|source| CheckerError::FixtureError { source })?;
+                recorded.map_err(|message| CheckerError::Other { message })
+            }
+        }
+    }
+}
+
+/// File name for a fetch; names built from object names keep only characters
+/// that are safe in a path
+fn path(dir: &Path, name: &str) -> PathBuf {
+    let file: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    dir.join(format!("{file}.json"))
+}
+
+/// Serializes maps keyed by tuples, which JSON objects cannot hold, as a
+/// list of `[key, value]` pairs sorted by key
+pub(crate) mod tuple_keys {
+    use serde::de::DeserializeOwned;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+    use std::hash::Hash;
+
+    pub fn serialize<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize + Ord,
+        V: Serialize,
+        S: Serializer,
+    {
+        let mut entries: Vec<(&K, &V)> = map.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        entries.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        K: DeserializeOwned + Eq + Hash,
+        V: DeserializeOwned,
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<(K, V)>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
+
+fn write<T: Serialize>(dir: &Path, name: &str, value: &T) -> Result<(), FixtureError> {
+    let path = path(dir, name);
+    fs::create_dir_all(dir).context(FixtureWriteSnafu { path: &path })?;
+    let file = File::create(&path).context(FixtureWriteSnafu { path: &path })?;
+    serde_json::to_writer_pretty(BufWriter::new(file), value)
+        .context(FixtureSerializeSnafu { path: &path })
+}
+
+fn read<T: DeserializeOwned>(dir: &Path, name: &str) -> Result<T, FixtureError> {
+    let path = path(dir, name);
+    let file = File::open(&path).context(FixtureReadSnafu { path: &path })?;
+    serde_json::from_reader(BufReader::new(file)).context(FixtureParseSnafu { path: &path })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn recorded_values_and_errors_are_replayed() {
+        let dir = tempdir().unwrap();
+        let record = Fixture::Record(dir.path().join("fixture"));
+        assert_eq!(record.serve("slots", async { vec![1, 2] }).await, [1, 2]);
+        let failed: Result<i64, _> = record
+            .try_serve("version", async {
+                Err(CheckerError::NoPrimary {
+                    hosts: "a, b".to_string(),
+                })
+            })
+            .await;
+        assert!(failed.is_err());
+
+        let replay = Fixture::Replay(dir.path().join("fixture"));
+        let slots: Vec<i32> = replay
+            .serve("slots", async { unreachable!("replay does not fetch") })
+            .await;
+        assert_eq!(slots, [1, 2]);
+        let replayed: Result<i64, _> = replay
+            .try_serve("version", async { unreachable!("replay does not fetch") })
+            .await;
+        assert_eq!(
+            replayed.unwrap_err().to_string(),
+            "No primary found among a, b; every host is unreachable or in recovery"
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_fixtures_fall_back_or_fail() {
+        let dir = tempdir().unwrap();
+        let replay = Fixture::Replay(dir.path().to_path_buf());
+        let slots: Vec<i32> = replay.serve("slots", async { vec![1] }).await;
+        assert!(slots.is_empty());
+        let version: Result<i64, _> = replay.try_serve("version", async { Ok(1) }).await;
+        assert!(matches!(version, Err(CheckerError::FixtureError { .. })));
+    }
+
+    #[test]
+    fn names_are_made_safe_for_paths() {
+        assert_eq!(
+            path(Path::new("fx"), "sample_distinct.public/\"Orders\""),
+            Path::new("fx").join("sample_distinct.public__Orders_.json")
+        );
+    }
+}
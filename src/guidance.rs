@@ -0,0 +1,614 @@
+use crate::models::PgConfigParam;
+use serde::Serialize;
+
+/// What it takes for a changed value to take effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApplyMode {
+    /// Requires a server restart (`postmaster` context)
+    Restart,
+    /// Picked up on configuration reload (`sighup` context); `backend` and
+    /// `superuser-backend` settings then apply to new sessions only
+    Reload,
+    /// Can be changed per session or per role (`user` context; `superuser`
+    /// needs a superuser or a granted SET privilege)
+    Session,
+    /// A kernel setting or service limit, changed on the host outside PostgreSQL
+    Host,
+    /// Fixed when PostgreSQL was compiled or the cluster initialized
+    /// (`internal` context, e.g. `block_size`); cannot be changed
+    ReadOnly,
+}
+
+impl ApplyMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApplyMode::Restart => "server restart required",
+            ApplyMode::Reload => "configuration reload (pg_reload_conf())",
+            ApplyMode::Session => "per session, role, or database (SET / ALTER ROLE)",
+            ApplyMode::Host => "on the database host (sysctl or the service manager)",
+            ApplyMode::ReadOnly => "read-only (fixed at build or initdb time)",
+        }
+    }
+
+    /// Maps a `pg_settings.context` value to the apply mode
+    pub fn from_context(context: &str) -> Self {
+        match context {
+            "postmaster" => ApplyMode::Restart,
+            "internal" => ApplyMode::ReadOnly,
+            "sighup" | "superuser-backend" | "backend" => ApplyMode::Reload,
+            _ => ApplyMode::Session,
+        }
+    }
+}
+
+/// Stored guidance for a single PostgreSQL parameter
+#[derive(Debug, Clone, Serialize)]
+pub struct ParameterGuide {
+    pub name: &'static str,
+    /// What the parameter controls
+    pub purpose: &'static str,
+    /// How PostGreat derives its recommendation
+    pub recommendation: &'static str,
+    pub apply_mode: ApplyMode,
+    pub related: &'static [&'static str],
+}
+
+/// Guidance plus, when a connection was available, the live server values
+#[derive(Debug, Clone, Serialize)]
+pub struct ParameterExplanation {
+    pub name: String,
+    pub guide: Option<&'static ParameterGuide>,
+    pub check_id: Option<&'static str>,
+    pub live: Option<PgConfigParam>,
+}
+
+impl ParameterExplanation {
+    pub fn new(name: &str, live: Option<PgConfigParam>) -> Self {
+        Self {
+            name: name.to_string(),
+            guide: find(name),
+            check_id: crate::checks::for_parameter(name).map(|check| check.id),
+            live,
+        }
+    }
+
+    /// Apply mode from the live server if known, otherwise from stored guidance
+    pub fn apply_mode(&self) -> Option<ApplyMode> {
+        self.live
+            .as_ref()
+            .map(|param| ApplyMode::from_context(&param.context))
+            .or(self.guide.map(|guide| guide.apply_mode))
+    }
+}
+
+/// Looks up stored guidance by parameter name (case-insensitive)
+pub fn find(name: &str) -> Option<&'static ParameterGuide> {
+    GUIDES
+        .iter()
+        .find(|guide| guide.name.eq_ignore_ascii_case(name))
+}
+
+pub static GUIDES: &[ParameterGuide] = &[
+    ParameterGuide {
+        name: "shared_buffers",
+        purpose: "Size of PostgreSQL's own page cache shared by all backends.",
+        recommendation: "~25% of RAM from --compute, capped at 8GB; the OS page cache covers the rest. \
+                         Flagged when more than 20% away from the target.",
+        apply_mode: ApplyMode::Restart,
+        related: &["effective_cache_size", "huge_pages", "wal_buffers"],
+    },
+    ParameterGuide {
+        name: "huge_pages",
+        purpose: "Whether shared memory is allocated in huge pages, which the kernel must have reserved via vm.nr_hugepages.",
+        recommendation: "on for servers above 32 vCPUs, with vm.nr_hugepages set to shared_memory_size_in_huge_pages; \
+                         try silently falls back to normal pages when the reservation is short.",
+        apply_mode: ApplyMode::Restart,
+        related: &["shared_buffers", "transparent_hugepage"],
+    },
+    ParameterGuide {
+        name: "hash_mem_multiplier",
+        purpose: "Multiple of work_mem that hash joins and hash aggregates may use before spilling (PG13+).",
+        recommendation: "2.0, the PG15 default; flagged below it, and above it when work_mem × hash_mem_multiplier \
+                         exceeds twice the profile's work_mem ceiling.",
+        apply_mode: ApplyMode::Session,
+        related: &["work_mem"],
+    },
+    ParameterGuide {
+        name: "temp_buffers",
+        purpose: "Per-session buffer cache for temporary tables, kept until the session ends.",
+        recommendation: "Small enough that temp_buffers × max_connections stays within a quarter of RAM; \
+                         the 8MB default suits sessions that rarely use temporary tables.",
+        apply_mode: ApplyMode::Session,
+        related: &["max_connections", "work_mem"],
+    },
+    ParameterGuide {
+        name: "logical_decoding_work_mem",
+        purpose: "Memory each logical decoding WAL sender buffers before spilling changes to pg_replslot (PG13+).",
+        recommendation: "256MB with wal_level = logical on servers with 32GB or more, as long as \
+                         logical_decoding_work_mem × max_wal_senders stays within a tenth of RAM.",
+        apply_mode: ApplyMode::Session,
+        related: &["max_wal_senders", "wal_level"],
+    },
+    ParameterGuide {
+        name: "effective_cache_size",
+        purpose: "Planner hint for how much memory (shared_buffers + OS cache) is available for caching data.",
+        recommendation: "~75% of RAM from --compute. It allocates nothing; a low value makes index scans look expensive.",
+        apply_mode: ApplyMode::Session,
+        related: &["shared_buffers", "random_page_cost"],
+    },
+    ParameterGuide {
+        name: "work_mem",
+        purpose: "Memory per sort, hash, or materialize node before spilling to temporary files.",
+        recommendation: "Profile-dependent tiers by RAM (web 32-64MB, warehouse 128-512MB). Flagged as critical above \
+                         the profile ceiling because it multiplies by nodes per query and concurrent connections.",
+        apply_mode: ApplyMode::Session,
+        related: &["hash_mem_multiplier", "max_connections", "log_temp_files"],
+    },
+    ParameterGuide {
+        name: "maintenance_work_mem",
+        purpose: "Memory for VACUUM, CREATE INDEX, and ALTER TABLE ADD FOREIGN KEY.",
+        recommendation: "512MB up to 16GB RAM, 1GB up to 64GB, 2GB above. Flagged when below 80% of the target.",
+        apply_mode: ApplyMode::Session,
+        related: &["autovacuum_work_mem", "max_parallel_maintenance_workers"],
+    },
+    ParameterGuide {
+        name: "wal_buffers",
+        purpose: "Shared memory for WAL not yet written to disk.",
+        recommendation: "Leave at -1 (auto, 1/32 of shared_buffers) or set at least 16MB for write-heavy systems.",
+        apply_mode: ApplyMode::Restart,
+        related: &["shared_buffers", "max_wal_size"],
+    },
+    ParameterGuide {
+        name: "max_connections",
+        purpose: "Maximum concurrent client connections; each is a full backend process.",
+        recommendation: "GREATEST(4 * vCPU, 100) behind a connection pooler. Critical above twice that. \
+                         Disabled for the warehouse profile.",
+        apply_mode: ApplyMode::Restart,
+        related: &["work_mem", "superuser_reserved_connections"],
+    },
+    ParameterGuide {
+        name: "max_worker_processes",
+        purpose: "Upper bound on background workers, including parallel query workers and extensions.",
+        recommendation: "Match the vCPU count; with TimescaleDB, at least \
+                         timescaledb.max_background_workers + max_parallel_workers + 3.",
+        apply_mode: ApplyMode::Restart,
+        related: &["max_parallel_workers", "max_parallel_workers_per_gather"],
+    },
+    ParameterGuide {
+        name: "max_parallel_workers",
+        purpose: "Total parallel query workers across all sessions.",
+        recommendation: "Must not exceed vCPU count; flagged as underutilized below the profile's ratio of vCPUs.",
+        apply_mode: ApplyMode::Session,
+        related: &["max_worker_processes", "max_parallel_workers_per_gather"],
+    },
+    ParameterGuide {
+        name: "max_parallel_workers_per_gather",
+        purpose: "Parallel workers a single Gather node (one query) may use.",
+        recommendation: "Profile ratio of vCPUs: half for web/mixed/time-series, 3/4 for warehouse, 1/4 for multi-tenant. \
+                         Critical above vCPU count.",
+        apply_mode: ApplyMode::Session,
+        related: &["max_parallel_workers", "max_worker_processes"],
+    },
+    ParameterGuide {
+        name: "max_parallel_maintenance_workers",
+        purpose: "Parallel workers for CREATE INDEX and VACUUM.",
+        recommendation: "Half of vCPUs.",
+        apply_mode: ApplyMode::Session,
+        related: &["maintenance_work_mem", "max_parallel_workers"],
+    },
+    ParameterGuide {
+        name: "max_wal_size",
+        purpose: "WAL volume that triggers a size-based checkpoint.",
+        recommendation: "Profile tiers by RAM (web 4/16/32GB). Critical when requested checkpoints outnumber timed ones, \
+                         which means checkpoints are size-driven and I/O is bursty.",
+        apply_mode: ApplyMode::Reload,
+        related: &["checkpoint_timeout", "min_wal_size", "checkpoint_completion_target"],
+    },
+    ParameterGuide {
+        name: "min_wal_size",
+        purpose: "WAL retained for recycling instead of being removed after checkpoints.",
+        recommendation: "1GB up to 16GB RAM, 2GB above.",
+        apply_mode: ApplyMode::Reload,
+        related: &["max_wal_size"],
+    },
+    ParameterGuide {
+        name: "checkpoint_timeout",
+        purpose: "Maximum time between automatic checkpoints.",
+        recommendation: "Profile target: 5-10min for web, 15-30min for warehouse and time-series ingest.",
+        apply_mode: ApplyMode::Reload,
+        related: &["max_wal_size", "checkpoint_completion_target"],
+    },
+    ParameterGuide {
+        name: "checkpoint_completion_target",
+        purpose: "Fraction of the checkpoint interval over which checkpoint writes are spread.",
+        recommendation: "0.9 (the PG14+ default); 0.5 is flagged as important.",
+        apply_mode: ApplyMode::Reload,
+        related: &["checkpoint_timeout", "max_wal_size"],
+    },
+    ParameterGuide {
+        name: "random_page_cost",
+        purpose: "Planner cost of a non-sequential page fetch relative to seq_page_cost.",
+        recommendation: "1.1 for SSD/NVMe, 1.5 for network-attached volumes, 4.0 for HDD, per --storage-type.",
+        apply_mode: ApplyMode::Session,
+        related: &["seq_page_cost", "effective_cache_size", "effective_io_concurrency"],
+    },
+    ParameterGuide {
+        name: "effective_io_concurrency",
+        purpose: "Concurrent I/O requests the executor issues for bitmap heap scans.",
+        recommendation: "200 for SSD and network storage, 2 for HDD, per --storage-type.",
+        apply_mode: ApplyMode::Session,
+        related: &["maintenance_io_concurrency", "random_page_cost"],
+    },
+    ParameterGuide {
+        name: "maintenance_io_concurrency",
+        purpose: "Prefetch depth used by VACUUM and other maintenance work (PG13+).",
+        recommendation: "200 for SSD and network storage; no more than 10 on HDD.",
+        apply_mode: ApplyMode::Session,
+        related: &["effective_io_concurrency"],
+    },
+    ParameterGuide {
+        name: "default_toast_compression",
+        purpose: "Compression method for new TOAST values in columns without their own (PG14+).",
+        recommendation: "lz4 when the server is built with it and write-heavy tables hold large TOAST data; \
+                         it compresses several times faster than pglz for roughly 10% more storage.",
+        apply_mode: ApplyMode::Session,
+        related: &[],
+    },
+    ParameterGuide {
+        name: "seq_page_cost",
+        purpose: "Planner cost of a sequential page fetch; the baseline for other cost constants.",
+        recommendation: "Keep at 1.0 and tune random_page_cost instead.",
+        apply_mode: ApplyMode::Session,
+        related: &["random_page_cost"],
+    },
+    ParameterGuide {
+        name: "default_statistics_target",
+        purpose: "Histogram and most-common-value list size ANALYZE collects per column.",
+        recommendation: "Left at 100 for web; 500 for warehouse and 200 for mixed profiles.",
+        apply_mode: ApplyMode::Session,
+        related: &["random_page_cost"],
+    },
+    ParameterGuide {
+        name: "autovacuum_max_workers",
+        purpose: "Maximum concurrent autovacuum workers.",
+        recommendation: "At least 5 for high-churn systems; more workers share the same cost limit.",
+        apply_mode: ApplyMode::Restart,
+        related: &["autovacuum_vacuum_cost_limit", "autovacuum_naptime"],
+    },
+    ParameterGuide {
+        name: "autovacuum_naptime",
+        purpose: "Delay between autovacuum launcher runs per database.",
+        recommendation: "30s or less so busy tables are revisited promptly.",
+        apply_mode: ApplyMode::Reload,
+        related: &["autovacuum_max_workers"],
+    },
+    ParameterGuide {
+        name: "autovacuum_vacuum_cost_limit",
+        purpose: "I/O budget autovacuum workers share before sleeping.",
+        recommendation: "2000 (10x the inherited vacuum_cost_limit of 200); critical when left at the default.",
+        apply_mode: ApplyMode::Reload,
+        related: &["autovacuum_max_workers", "autovacuum_vacuum_cost_delay"],
+    },
+    ParameterGuide {
+        name: "autovacuum_work_mem",
+        purpose: "Memory each autovacuum worker uses to track dead tuples.",
+        recommendation: "Set explicitly (512MB) rather than inheriting maintenance_work_mem.",
+        apply_mode: ApplyMode::Reload,
+        related: &["maintenance_work_mem", "autovacuum_max_workers"],
+    },
+    ParameterGuide {
+        name: "autovacuum_vacuum_scale_factor",
+        purpose: "Fraction of a table that must be dead before autovacuum triggers.",
+        recommendation: "Lower the 0.2 default globally or per large table so vacuum runs before bloat accumulates.",
+        apply_mode: ApplyMode::Reload,
+        related: &["autovacuum_vacuum_threshold", "autovacuum_vacuum_insert_scale_factor"],
+    },
+    ParameterGuide {
+        name: "log_min_duration_statement",
+        purpose: "Logs statements that run longer than this duration.",
+        recommendation: "1000ms to capture slow queries without flooding logs; disabled (-1), 0, and values above 5s are flagged.",
+        apply_mode: ApplyMode::Reload,
+        related: &["log_lock_waits", "auto_explain.log_min_duration"],
+    },
+    ParameterGuide {
+        name: "log_lock_waits",
+        purpose: "Logs sessions that wait longer than deadlock_timeout for a lock.",
+        recommendation: "on.",
+        apply_mode: ApplyMode::Reload,
+        related: &["deadlock_timeout"],
+    },
+    ParameterGuide {
+        name: "deadlock_timeout",
+        purpose: "Wait before checking for deadlock; also the log_lock_waits threshold.",
+        recommendation: "Keep the 1s default; higher values are reported as informational.",
+        apply_mode: ApplyMode::Reload,
+        related: &["log_lock_waits"],
+    },
+    ParameterGuide {
+        name: "log_destination",
+        purpose: "Where and in which formats the server log is written: stderr, csvlog, jsonlog, syslog, eventlog.",
+        recommendation: "Add jsonlog on PostgreSQL 15+, csvlog before, so log mining tools can parse statements and errors.",
+        apply_mode: ApplyMode::Reload,
+        related: &["logging_collector", "log_filename"],
+    },
+    ParameterGuide {
+        name: "logging_collector",
+        purpose: "Background process that captures stderr, csvlog and jsonlog output into rotated files.",
+        recommendation: "on; csvlog and jsonlog are only written through the collector.",
+        apply_mode: ApplyMode::Restart,
+        related: &["log_destination", "log_rotation_age"],
+    },
+    ParameterGuide {
+        name: "log_filename",
+        purpose: "strftime pattern naming the collector's log files, which decides when names repeat.",
+        recommendation: "A pattern that repeats after the retention period, e.g. postgresql-%a.log for 7 days \
+                         (log_retention_days in the config file, default 7).",
+        apply_mode: ApplyMode::Reload,
+        related: &["log_truncate_on_rotation", "log_rotation_age"],
+    },
+    ParameterGuide {
+        name: "log_truncate_on_rotation",
+        purpose: "Overwrites instead of appending when time-based rotation reuses a log file name.",
+        recommendation: "on with a repeating log_filename, so disk use stays bounded.",
+        apply_mode: ApplyMode::Reload,
+        related: &["log_filename"],
+    },
+    ParameterGuide {
+        name: "log_rotation_age",
+        purpose: "Time after which the collector starts a new log file.",
+        recommendation: "1d; with log_rotation_size also 0 the log file never rotates.",
+        apply_mode: ApplyMode::Reload,
+        related: &["log_rotation_size", "log_filename"],
+    },
+    ParameterGuide {
+        name: "archive_mode",
+        purpose: "Hands each completed WAL segment to archive_command or archive_library.",
+        recommendation: "on, with an archiver set, for point-in-time recovery from base backups.",
+        apply_mode: ApplyMode::Restart,
+        related: &["archive_command", "wal_level"],
+    },
+    ParameterGuide {
+        name: "archive_command",
+        purpose: "Shell command that copies a completed WAL segment (%p) to the archive as %f.",
+        recommendation: "A command that refuses to overwrite and exits non-zero on any failure, \
+                         e.g. test ! -f /archive/%f && cp %p /archive/%f, or a backup tool's archive-push.",
+        apply_mode: ApplyMode::Reload,
+        related: &["archive_mode", "archive_library"],
+    },
+    ParameterGuide {
+        name: "max_slot_wal_keep_size",
+        purpose: "Most WAL a replication slot may hold back before it is invalidated.",
+        recommendation: "About twice the largest active slot's lag, at least 10GB; -1 lets a stalled slot fill the disk.",
+        apply_mode: ApplyMode::Reload,
+        related: &["wal_keep_size"],
+    },
+    ParameterGuide {
+        name: "wal_keep_size",
+        purpose: "WAL kept in pg_wal for standbys that stream without a replication slot.",
+        recommendation: "0 when every standby uses a slot or restore_command; otherwise about an hour of WAL.",
+        apply_mode: ApplyMode::Reload,
+        related: &["max_slot_wal_keep_size"],
+    },
+    ParameterGuide {
+        name: "tcp_keepalives_idle",
+        purpose: "Seconds a client connection is idle before the server sends TCP keepalive probes; 0 uses the OS default (7200 on Linux).",
+        recommendation: "60, so with tcp_keepalives_interval 10 and tcp_keepalives_count 6 a vanished client is dropped after 2 minutes.",
+        apply_mode: ApplyMode::Reload,
+        related: &["tcp_keepalives_interval", "tcp_keepalives_count"],
+    },
+    ParameterGuide {
+        name: "tcp_keepalives_interval",
+        purpose: "Seconds between unanswered TCP keepalive probes; 0 uses the OS default (75 on Linux).",
+        recommendation: "10.",
+        apply_mode: ApplyMode::Reload,
+        related: &["tcp_keepalives_idle", "tcp_keepalives_count"],
+    },
+    ParameterGuide {
+        name: "tcp_keepalives_count",
+        purpose: "Unanswered TCP keepalive probes before the connection is considered dead; 0 uses the OS default (9 on Linux).",
+        recommendation: "6.",
+        apply_mode: ApplyMode::Reload,
+        related: &["tcp_keepalives_idle", "tcp_keepalives_interval"],
+    },
+    ParameterGuide {
+        name: "wal_receiver_timeout",
+        purpose: "How long a standby waits for the primary before dropping the replication connection and reconnecting.",
+        recommendation: "60s (the default); 0 leaves a standby attached to a dead primary until TCP gives up.",
+        apply_mode: ApplyMode::Reload,
+        related: &["wal_sender_timeout"],
+    },
+    ParameterGuide {
+        name: "wal_sender_timeout",
+        purpose: "How long the primary waits for a standby's replies before ending its replication connection.",
+        recommendation: "60s (the default); 0 keeps a dead standby's connection and slot marked active.",
+        apply_mode: ApplyMode::Reload,
+        related: &["wal_receiver_timeout"],
+    },
+    ParameterGuide {
+        name: "restart_after_crash",
+        purpose: "Reinitializes the server automatically after a backend crash.",
+        recommendation: "off when Patroni, pg_auto_failover or Pacemaker manage the cluster, so they fail over instead; on otherwise.",
+        apply_mode: ApplyMode::Reload,
+        related: &[],
+    },
+    ParameterGuide {
+        name: "row_security",
+        purpose: "Whether policies filter queries; off makes a query that a policy would filter fail instead.",
+        recommendation: "on; pg_dump sets it off per session to fail rather than dump a filtered table.",
+        apply_mode: ApplyMode::Reload,
+        related: &[],
+    },
+    ParameterGuide {
+        name: "pgaudit.log",
+        purpose: "Statement classes pgaudit writes to the server log: read, write, function, role, ddl, misc, misc_set.",
+        recommendation: "Classes whose volume at the pg_stat_statements call rate stays under 10GB a day; \
+                         audit reads of sensitive tables with object auditing (pgaudit.role) rather than the read class.",
+        apply_mode: ApplyMode::Reload,
+        related: &["log_destination"],
+    },
+    ParameterGuide {
+        name: "log_statement_sample_rate",
+        purpose: "Share of statements between log_min_duration_sample and log_min_duration_statement that are logged.",
+        recommendation: "1 unless duration logging would write more than 1GB an hour at the pg_stat_statements call rate; \
+                         then the highest rate that fits, with log_min_duration_statement kept for the slowest statements.",
+        apply_mode: ApplyMode::Reload,
+        related: &["log_min_duration_sample", "log_min_duration_statement"],
+    },
+    ParameterGuide {
+        name: "log_statement",
+        purpose: "Statement kinds logged as they start: none, ddl, mod or all.",
+        recommendation: "ddl; mod and all log every write or statement and are flagged when they exceed 1GB an hour.",
+        apply_mode: ApplyMode::Reload,
+        related: &["log_min_duration_statement", "log_statement_sample_rate"],
+    },
+    ParameterGuide {
+        name: "log_connections",
+        purpose: "Logs each connection attempt, its authentication and authorization.",
+        recommendation: "on, unless session churn makes connection lines the bulk of the log; pool connections instead where possible.",
+        apply_mode: ApplyMode::Reload,
+        related: &["log_disconnections"],
+    },
+    ParameterGuide {
+        name: "timescaledb.max_background_workers",
+        purpose: "Background workers TimescaleDB may use for compression, retention and continuous aggregate jobs.",
+        recommendation: "At least the scheduled jobs plus one scheduler per database, and no less than 8.",
+        apply_mode: ApplyMode::Restart,
+        related: &["max_worker_processes"],
+    },
+    ParameterGuide {
+        name: "citus.shard_count",
+        purpose: "Shards created for each new distributed table.",
+        recommendation: "At least 2 per worker for multi-tenant workloads, one per worker vCPU for analytics.",
+        apply_mode: ApplyMode::Session,
+        related: &["citus.max_adaptive_executor_pool_size"],
+    },
+    ParameterGuide {
+        name: "citus.max_adaptive_executor_pool_size",
+        purpose: "Connections a single session may open to each worker for a multi-shard query.",
+        recommendation: "Low enough that peak sessions times the pool size fits within the workers' max_connections.",
+        apply_mode: ApplyMode::Session,
+        related: &["max_connections", "citus.shard_count"],
+    },
+    ParameterGuide {
+        name: "ivfflat.probes",
+        purpose: "IVFFlat lists searched per nearest-neighbour query; more probes trade speed for recall.",
+        recommendation: "Start at the square root of the index's lists and raise it until recall is acceptable.",
+        apply_mode: ApplyMode::Session,
+        related: &["hnsw.ef_search"],
+    },
+    ParameterGuide {
+        name: "hnsw.ef_search",
+        purpose: "Candidate list size an HNSW index scan keeps; it caps how many rows a query can return.",
+        recommendation: "At least the query's LIMIT; 40 up to a million rows, 100 or more for larger tables.",
+        apply_mode: ApplyMode::Session,
+        related: &["ivfflat.probes", "maintenance_work_mem"],
+    },
+    ParameterGuide {
+        name: "server_version",
+        purpose: "PostgreSQL version the server runs; read-only, changed by installing new binaries.",
+        recommendation: "The newest minor release of a major version that has not reached end of life, per the embedded or --release-data release table.",
+        apply_mode: ApplyMode::Restart,
+        related: &[],
+    },
+    ParameterGuide {
+        name: "LimitNOFILE",
+        purpose: "Open-file limit of the postmaster and every backend, set by the systemd unit or limits.conf.",
+        recommendation: "At least max_connections + max_files_per_process, rounded up to a power of two and no lower than 65536. Takes effect when the server restarts.",
+        apply_mode: ApplyMode::Host,
+        related: &["max_connections", "max_files_per_process"],
+    },
+    ParameterGuide {
+        name: "vm.overcommit_memory",
+        purpose: "How the Linux kernel grants memory allocations beyond what is available.",
+        recommendation: "2, so allocations fail with an error instead of the OOM killer terminating a backend or the postmaster; review vm.overcommit_ratio alongside it.",
+        apply_mode: ApplyMode::Host,
+        related: &["vm.swappiness", "shared_buffers"],
+    },
+    ParameterGuide {
+        name: "vm.swappiness",
+        purpose: "How readily the Linux kernel swaps out process memory rather than dropping page cache.",
+        recommendation: "1 on database hosts; flagged above 10.",
+        apply_mode: ApplyMode::Host,
+        related: &["vm.overcommit_memory", "shared_buffers"],
+    },
+    ParameterGuide {
+        name: "transparent_hugepage",
+        purpose: "Whether the Linux kernel backs memory with huge pages on its own, compacting memory in the background to do so.",
+        recommendation: "never or madvise; use explicit huge pages through huge_pages instead.",
+        apply_mode: ApplyMode::Host,
+        related: &["huge_pages"],
+    },
+    ParameterGuide {
+        name: "vm.zone_reclaim_mode",
+        purpose: "Whether the Linux kernel reclaims memory on the local NUMA node before allocating from another one.",
+        recommendation: "0 on multi-node hosts, so the page cache is not evicted while other nodes have free memory.",
+        apply_mode: ApplyMode::Host,
+        related: &["kernel.numa_balancing"],
+    },
+    ParameterGuide {
+        name: "kernel.numa_balancing",
+        purpose: "Whether the Linux kernel migrates pages between NUMA nodes to follow the processes using them.",
+        recommendation: "0 on multi-node hosts; shared_buffers is used from every node, so migrations only add stalls. \
+                         Start the postmaster under numactl --interleave=all to spread it evenly instead.",
+        apply_mode: ApplyMode::Host,
+        related: &["vm.zone_reclaim_mode", "huge_pages"],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ParamValue;
+
+    #[test]
+    fn every_settings_check_has_guidance() {
+        for check in crate::checks::CHECKS {
+            if let Some(parameter) = check.parameter() {
+                assert!(find(parameter).is_some(), "no guidance for {parameter}");
+            }
+        }
+    }
+
+    #[test]
+    fn lookup_is_case_insensitive_and_related_params_are_distinct() {
+        let guide = find("Shared_Buffers").expect("shared_buffers guidance");
+        assert_eq!(guide.apply_mode, ApplyMode::Restart);
+        assert!(!guide.related.contains(&guide.name));
+    }
+
+    #[test]
+    fn live_context_overrides_stored_apply_mode() {
+        let live = PgConfigParam {
+            name: "autovacuum_max_workers".to_string(),
+            current_value: "3".to_string(),
+            default_value: Some("3".to_string()),
+            unit: None,
+            context: "sighup".to_string(),
+            value: ParamValue::parse("3", "integer", None),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        };
+
+        let explanation = ParameterExplanation::new("autovacuum_max_workers", Some(live));
+        assert_eq!(explanation.apply_mode(), Some(ApplyMode::Reload));
+        assert_eq!(explanation.check_id, Some("autovacuum.max_workers"));
+    }
+
+    #[test]
+    fn contexts_map_to_when_a_change_takes_effect() {
+        assert_eq!(ApplyMode::from_context("postmaster"), ApplyMode::Restart);
+        assert_eq!(ApplyMode::from_context("internal"), ApplyMode::ReadOnly);
+        assert_eq!(ApplyMode::from_context("sighup"), ApplyMode::Reload);
+        assert_eq!(ApplyMode::from_context("backend"), ApplyMode::Reload);
+        assert_eq!(
+            ApplyMode::from_context("superuser-backend"),
+            ApplyMode::Reload
+        );
+        assert_eq!(ApplyMode::from_context("superuser"), ApplyMode::Session);
+        assert_eq!(ApplyMode::from_context("user"), ApplyMode::Session);
+    }
+}
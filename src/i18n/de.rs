@@ -0,0 +1,1010 @@
+pub(super) static MESSAGES: &[(&str, &str)] = &[
+    ("text", "{text}"),
+    // Memory
+    (
+        "memory.shared_buffers",
+        "shared_buffers sollte etwa 25 % des gesamten RAM betragen ({memory_gb}GB). \
+         Dies ist der wirkungsvollste einzelne Performance-Parameter.",
+    ),
+    (
+        "memory.effective_cache_size",
+        "effective_cache_size ist ein Hinweis für das Kostenmodell des Query-Planers. \
+         Ein Wert von etwa 75 % des RAM hilft dem Planer, Index-Scans statt sequenzieller \
+         Scans zu wählen. Der Standardwert ist gefährlich niedrig.",
+    ),
+    (
+        "memory.work_mem.too_high",
+        "work_mem ist mit {current_mb}MB gefährlich hoch. Dieser Wert wird mit der Anzahl \
+         gleichzeitiger Operationen und Verbindungen multipliziert. Ein hoher work_mem-Wert \
+         bei vielen Verbindungen ist die häufigste Ursache für OOM-Fehler. \
+         Für dieses System und diesen Workload-Typ werden etwa {recommended_mb}MB empfohlen.",
+    ),
+    (
+        "memory.work_mem.too_low",
+        "work_mem ist zu niedrig, wodurch Sortierungen und Hash-Joins auf die Festplatte \
+         ausgelagert werden können. Prüfen Sie mit EXPLAIN (ANALYZE) auf Meldungen wie \
+         'external merge Disk' oder 'spill'.",
+    ),
+    (
+        "memory.work_mem.too_low_analytics",
+        "work_mem ist für einen Analyse-Workload zu niedrig. Große Sortierungen, Aggregate \
+         und Hash-Joins werden in temporäre Dateien ausgelagert. Bei wenigen gleichzeitigen \
+         Verbindungen kann ein Data Warehouse jeder Operation problemlos mehrere hundert MB \
+         zuweisen; bestätigen Sie dies mit EXPLAIN (ANALYZE) und log_temp_files.",
+    ),
+    (
+        "memory.work_mem.temp_spills",
+        "Abfragen haben in den letzten {window} {temp_gb}GB in {temp_files} temporäre Dateien \
+         geschrieben; Sortierungen und Hashes überschreiten also work_mem. Erhöhen Sie den Wert \
+         in Richtung {recommended_mb}MB oder nur für die Rollen mit diesen Abfragen; \
+         log_temp_files nennt sie.",
+    ),
+    (
+        "memory.maintenance_work_mem",
+        "maintenance_work_mem sollte für Ihr System ({vcpu}, {memory_gb}GB RAM) auf etwa \
+         {recommended_mb}MB gesetzt werden. Ein größerer Wert kann das Erstellen von Indizes \
+         und das Vacuuming erheblich beschleunigen.",
+    ),
+    (
+        "memory.wal_buffers",
+        "wal_buffers sollte bei schreibintensiven Workloads auf 16MB gesetzt werden. \
+         Der Standardwert -1 (automatische Größe) reicht meist aus, aber ein fester Wert \
+         von 16MB kann die Schreibleistung verbessern.",
+    ),
+    (
+        "memory.huge_pages",
+        "huge_pages ist {current} auf einem Server mit {vcpu} vCPUs. Bei so vielen Backends, die \
+         shared_buffers jeweils in 4kB-Seiten abbilden, belegen allein die Seitentabellen \
+         Gigabytes, und TLB-Fehlgriffe verlangsamen jeden Pufferzugriff. Reservieren Sie \
+         vm.nr_hugepages = {pages} und setzen Sie huge_pages = on, damit der Server nicht \
+         startet, statt stillschweigend auf normale Seiten zurückzufallen.",
+    ),
+    (
+        "memory.hash_mem_multiplier.too_low",
+        "hash_mem_multiplier ist {current}, daher lagern Hash-Joins und Hash-Aggregate beim \
+         selben work_mem wie Sortierungen auf die Festplatte aus, obwohl sie dabei deutlich \
+         stärker einbrechen. PostgreSQL 15 hat den Standardwert auf 2.0 angehoben.",
+    ),
+    (
+        "memory.hash_mem_multiplier.too_high",
+        "hash_mem_multiplier ist {current}, daher darf jeder Hash-Join und jedes Hash-Aggregat \
+         {hash_mb}MB belegen, mehr als das Doppelte des work_mem, das dieses Profil für sicher \
+         hält. Der Wert vervielfacht sich über die Hash-Knoten jeder Verbindung und jedes \
+         parallelen Workers; erhöhen Sie stattdessen work_mem für die Rollen, die es brauchen.",
+    ),
+    (
+        "memory.temp_buffers",
+        "temp_buffers mal max_connections ({max_connections}) ergibt {total_gb}GB der {memory_gb}GB \
+         des Servers. Jede Sitzung behält die mit temporären Tabellen gefüllten Puffer bis zum \
+         Verbindungsende, langlebige Pool-Verbindungen also dauerhaft. Senken Sie den Wert global \
+         und erhöhen Sie ihn pro Sitzung, wo temporäre Tabellen stark genutzt werden.",
+    ),
+    (
+        "memory.logical_decoding_work_mem.too_high",
+        "logical_decoding_work_mem über max_wal_senders ({wal_senders}) ergibt {total_gb}GB der \
+         {memory_gb}GB des Servers. Jeder WAL-Sender, der einen logischen Slot dekodiert, kann \
+         so viel puffern, bevor er nach pg_replslot auslagert.",
+    ),
+    (
+        "memory.logical_decoding_work_mem.too_low",
+        "wal_level ist logical, aber logical_decoding_work_mem liegt unter {recommended_mb}MB. \
+         Große Transaktionen werden beim Dekodieren nach pg_replslot auf die Festplatte \
+         ausgelagert, was Subscriber verzögert und I/O erzeugt; dieser Server hat Platz, mehr \
+         davon im Speicher zu puffern.",
+    ),
+    // Concurrency
+    (
+        "concurrency.max_connections.too_high",
+        "max_connections ist mit {current} gefährlich hoch. Jede Verbindung verbraucht \
+         Speicher und CPU. Best Practice ist ein Connection-Pooler (PgBouncer) und \
+         max_connections von etwa 4 * vCPU ({recommended} für Ihr System). \
+         Dieser Parameter ist eine häufige Falle.",
+    ),
+    (
+        "concurrency.max_connections.high",
+        "max_connections sollte relativ niedrig gehalten werden. Erwägen Sie einen \
+         Connection-Pooler für eine bessere Verbindungsverwaltung.",
+    ),
+    (
+        "concurrency.max_connections.saturated",
+        "Die gemessenen Verbindungen erreichten einen Höchststand von {peak} connections, \
+         {pct}% von max_connections ({current}), um {at} ({samples} Messungen). Spitzen über \
+         max_connections werden mit 'too many clients' abgewiesen. Schalten Sie einen \
+         Connection-Pooler (PgBouncer) vor den Server oder erhöhen Sie max_connections auf \
+         25% Reserve über dem Höchststand.",
+    ),
+    (
+        "concurrency.max_worker_processes",
+        "max_worker_processes sollte der Anzahl Ihrer vCPUs ({recommended}) entsprechen. \
+         Dies ist die Obergrenze für alle Hintergrund-Worker-Prozesse.",
+    ),
+    (
+        "concurrency.max_parallel_workers.too_high",
+        "max_parallel_workers (gesamt über alle Abfragen) sollte die Anzahl der vCPUs \
+         ({recommended}) nicht überschreiten. Ein höherer Wert kann zu CPU-Konkurrenz führen.",
+    ),
+    (
+        "concurrency.max_parallel_workers.underutilized",
+        "max_parallel_workers wird nicht ausgeschöpft. Erhöhen Sie den Wert auf die Anzahl \
+         Ihrer vCPUs, um parallele Abfragen zu beschleunigen.",
+    ),
+    (
+        "concurrency.max_parallel_workers_per_gather.above_vcpu",
+        "max_parallel_workers_per_gather (pro Abfrage) sollte die Anzahl der vCPUs ({vcpu}) \
+         nicht überschreiten. Ein Wert von {current} erlaubt einer einzelnen Abfrage, alle \
+         CPU-Ressourcen zu belegen und andere gleichzeitige Abfragen auszubremsen.",
+    ),
+    (
+        "concurrency.max_parallel_workers_per_gather.equals_vcpu",
+        "max_parallel_workers_per_gather gleich der Anzahl der vCPUs zu setzen ist gefährlich. \
+         Eine einzelne komplexe Abfrage kann so alle parallelen Worker belegen und andere \
+         Abfragen ausbremsen. Setzen Sie den Wert auf die Hälfte der vCPUs, um die Auswirkungen \
+         einer außer Kontrolle geratenen Abfrage zu begrenzen.",
+    ),
+    (
+        "concurrency.max_parallel_workers_per_gather.above_profile",
+        "Das Profil {profile} begrenzt eine einzelne Abfrage auf {recommended} parallele Worker \
+         pro Gather, damit die Abfrage eines Mandanten nicht die CPUs belegt, die sich alle \
+         Mandanten teilen; {current} erlaubt mehr. Senken Sie max_parallel_workers_per_gather \
+         auf {recommended}.",
+    ),
+    (
+        "concurrency.max_parallel_workers_per_gather.underutilized_analytics",
+        "max_parallel_workers_per_gather wird für einen Analyse-Workload nicht ausgeschöpft. \
+         Data Warehouses führen wenige gleichzeitige Abfragen aus; wenn jeder Scan, Join und \
+         jedes Aggregat etwa 3/4 der vCPUs (z. B. {recommended}) nutzen darf, werden lang \
+         laufende Berichte schneller.",
+    ),
+    (
+        "concurrency.max_parallel_workers_per_gather.underutilized",
+        "max_parallel_workers_per_gather wird nicht ausgeschöpft. Bei gemischten Workloads \
+         erlaubt ein Wert von {recommended}, dass mehrere komplexe Abfragen vollständig parallel \
+         laufen, ohne dass eine davon die anderen ausbremst.",
+    ),
+    (
+        "concurrency.max_parallel_maintenance_workers",
+        "max_parallel_maintenance_workers steuert die Parallelität manueller VACUUM- und \
+         CREATE INDEX-Befehle. Ein Wert von {recommended} (die Hälfte der vCPUs) kann \
+         Wartungsarbeiten deutlich beschleunigen.",
+    ),
+    // WAL
+    (
+        "wal.max_wal_size.requested_checkpoints",
+        "Ihr System löst mehr größenbasierte Checkpoints ({requested}) als zeitbasierte \
+         ({timed}) aus. Das führt zu stoßweiser I/O-Last und Leistungseinbußen. Erhöhen Sie \
+         max_wal_size deutlich, bis checkpoints_timed der überwiegende Grund für Checkpoints ist.",
+    ),
+    (
+        "wal.max_wal_size.too_low",
+        "max_wal_size ist mit {current_gb}GB zu niedrig. Der Standardwert (1GB) ist für \
+         schreibintensive Produktions-Workloads viel zu niedrig und verursacht häufige, \
+         größenbasierte Checkpoints und damit stoßweise I/O-Last. Setzen Sie den Wert so hoch, \
+         dass Checkpoints zeitbasiert statt größenbasiert erfolgen ({recommended_gb}GB für Ihr System).",
+    ),
+    (
+        "wal.min_wal_size",
+        "min_wal_size steuert die Wiederverwendung von WAL-Dateien. Ein Wert von \
+         {recommended_gb}GB stellt sicher, dass genügend WAL-Segmente vorgehalten werden, um \
+         Schreibspitzen abzufangen, ohne neue Dateien von Grund auf anlegen zu müssen.",
+    ),
+    (
+        "wal.checkpoint_timeout",
+        "checkpoint_timeout ist zu kurz. Kürzere Timeouts führen zu häufigeren Checkpoints \
+         und erhöhen die I/O-Last. Für diesen Workload ist {recommended} ein guter Kompromiss.",
+    ),
+    (
+        "wal.checkpoint_timeout.analytics",
+        "Erhöhen Sie checkpoint_timeout bei OLAP- oder Batch-Workloads auf 15-30min, um die \
+         Anzahl der Checkpoints während lang laufender Operationen zu verringern.",
+    ),
+    (
+        "wal.checkpoint_completion_target",
+        "checkpoint_completion_target ist der Regler zur Glättung der I/O-Last. Der \
+         Standardwert 0.5 erzeugt während Checkpoints eine starke I/O-Spitze. Ein Wert von 0.9 \
+         verteilt dieselbe I/O-Menge auf 90 % des Checkpoint-Intervalls und sorgt so für \
+         gleichmäßige, kontinuierliche Schreibvorgänge im Hintergrund.",
+    ),
+    // Planner
+    (
+        "planner.random_page_cost.hdd_value_on_ssd",
+        "random_page_cost ist für HDDs eingestellt (Standard 4.0), Sie verwenden jedoch \
+         SSD-Speicher. Auf SSDs sind zufällige Lesezugriffe fast so schnell wie sequenzielle. \
+         Ein Wert von 1.1 (zusammen mit einem hohen effective_cache_size) bringt den Planer dazu, \
+         Indizes zu nutzen, statt immer sequenzielle Scans zu wählen. Für moderne Speichersysteme \
+         ist dies UNERLÄSSLICH.",
+    ),
+    (
+        "planner.random_page_cost.lower_for_ssd",
+        "random_page_cost könnte auf 1.1 gesenkt werden, um Indizes auf SSD-Speicher besser \
+         zu nutzen.",
+    ),
+    (
+        "planner.random_page_cost.hdd_value_on_network",
+        "random_page_cost ist für rotierende Festplatten eingestellt, Sie verwenden jedoch \
+         Netzwerkspeicher (z. B. EBS, Persistent Disk). Diese Volumes basieren auf SSDs, daher \
+         kosten zufällige Lesezugriffe kaum mehr als sequenzielle; die zusätzliche Netzwerklatenz \
+         betrifft beide. Ein Wert von 1.5 lässt den Planer Indizes nutzen, ohne so optimistisch \
+         wie bei lokalem NVMe zu sein.",
+    ),
+    (
+        "planner.random_page_cost.too_low_for_network",
+        "random_page_cost liegt bei Netzwerkspeicher unter dem Wert für lokale SSDs. Jeder \
+         zufällige Lesezugriff kostet einen Netzwerk-Roundtrip, daher kann ein zu niedriger Wert \
+         den Planer zu Index-Scans verleiten, die viele verstreute Seiten lesen.",
+    ),
+    (
+        "planner.random_page_cost.too_low_for_hdd",
+        "random_page_cost ist für HDD-Speicher zu niedrig. Zufällige I/O ist auf rotierenden \
+         Festplatten deutlich teurer als sequenzielle I/O. Eine Erhöhung auf 4.0 (den Standard) \
+         verhindert, dass der Planer optimistisch Index-Scans wählt, die die Festplatte \
+         überlasten.",
+    ),
+    (
+        "planner.effective_io_concurrency.fast_storage",
+        "effective_io_concurrency sollte für SSD/NVMe und Netzwerkspeicher auf 200 gesetzt \
+         werden. Der Standardwert 1 ist für einzelne HDDs gedacht. Moderne Speichersysteme \
+         verkraften sehr viele parallele Anfragen und profitieren bei Bitmap-Heap-Scans von \
+         höheren Werten; insbesondere Netzwerk-Volumes benötigen tiefe Warteschlangen, um die \
+         Latenz einzelner Anfragen zu verbergen.",
+    ),
+    (
+        "planner.effective_io_concurrency.hdd",
+        "effective_io_concurrency ist für HDD-Speicher zu hoch. Rotierende Festplatten haben \
+         begrenzte IOPS und Warteschlangentiefe. Ein zu hoher Wert (Standard für HDDs ist 1-2) \
+         kann übermäßige Suchbewegungen verursachen.",
+    ),
+    (
+        "planner.maintenance_io_concurrency.fast_storage",
+        "maintenance_io_concurrency ist die Prefetch-Tiefe für VACUUM und andere \
+         Wartungsarbeiten. Der Standardwert 10 schöpft SSD- und Netzwerkspeicher nicht aus; \
+         gleichen Sie ihn an effective_io_concurrency an, damit Wartungsarbeiten das Gerät \
+         auslasten können.",
+    ),
+    (
+        "planner.maintenance_io_concurrency.hdd",
+        "maintenance_io_concurrency ist höher, als rotierende Festplatten bedienen können. \
+         Tiefe Prefetch-Warteschlangen während VACUUM verursachen zusätzliche Suchbewegungen, \
+         die mit Vordergrundabfragen konkurrieren; behalten Sie auf HDD-Speicher den \
+         Standardwert 10 bei.",
+    ),
+    (
+        "planner.seq_page_cost",
+        "seq_page_cost wurde vom Standardwert 1.0 geändert. Sofern es keinen besonderen Grund \
+         gibt, wird empfohlen, ihn bei 1.0 zu belassen und für speicherspezifisches Tuning \
+         stattdessen random_page_cost anzupassen.",
+    ),
+    (
+        "planner.default_statistics_target",
+        "default_statistics_target legt fest, wie viele Histogramm-Buckets und häufigste Werte \
+         ANALYZE sammelt. Analyseabfragen über große, ungleich verteilte Tabellen mit \
+         Mehrfach-Joins erhalten mit einem höheren Wert deutlich bessere Zeilenschätzungen. \
+         ANALYZE dauert länger, was für Reporting-Workloads meist akzeptabel ist.",
+    ),
+    (
+        "planner.extended_statistics_not_analyzed",
+        "Die erweiterte Statistik {statistics} auf {table} wurde nie analysiert, sodass der \
+         Planer ihre Spalten weiterhin als unabhängig behandelt und das Objekt nur ANALYZE-Zeit \
+         kostet. CREATE STATISTICS sammelt selbst nichts: Führen Sie ANALYZE {table} aus und \
+         prüfen Sie, ob Autovacuum die Tabelle danach analysiert.",
+    ),
+    // Autovacuum
+    (
+        "autovacuum.max_workers",
+        "autovacuum_max_workers ist zu niedrig. Der Standardwert 3 reicht für Server mit vielen \
+         aktiven Datenbanken und Tabellen oft nicht aus. Eine Erhöhung auf 5 erlaubt mehr \
+         parallele Autovacuum-Prozesse, die mit schreibintensiven Workloads Schritt halten.",
+    ),
+    (
+        "autovacuum.naptime",
+        "autovacuum_naptime legt fest, wie oft der Launcher nach Arbeit sucht. Eine Senkung \
+         auf 30s macht Autovacuum auf Systemen mit hoher Änderungsrate reaktionsschneller, \
+         besonders bei vielen Datenbanken und Tabellen.",
+    ),
+    (
+        "autovacuum.vacuum_cost_limit",
+        "autovacuum_vacuum_cost_limit ist zu niedrig. Beim Standardwert 200 pausiert Autovacuum \
+         bereits nach nur 10 geänderten Seiten. Dadurch kann Autovacuum mit schreibintensiven \
+         Workloads nicht Schritt halten. Erhöhen Sie den Wert auf 2000 (das Zehnfache des \
+         Standards), damit Worker vor jeder Pause mehr Arbeit erledigen.",
+    ),
+    (
+        "autovacuum.work_mem.inherited",
+        "autovacuum_work_mem ist -1 (übernimmt maintenance_work_mem). Ist maintenance_work_mem \
+         auf einen großen Wert gesetzt (z. B. 2GB) und autovacuum_max_workers 3 oder mehr, kann \
+         das System für routinemäßiges Autovacuum plötzlich mehrere GB RAM belegen. Setzen Sie \
+         autovacuum_work_mem immer explizit, um es von manuellen Wartungswerten zu entkoppeln.",
+    ),
+    (
+        "autovacuum.work_mem.low",
+        "autovacuum_work_mem kann für eine bessere Vacuum-Leistung auf 512MB pro Worker erhöht \
+         werden. Das ist unbedenklich, da Autovacuum im Vergleich zu normalen Abfragen selten \
+         läuft.",
+    ),
+    (
+        "autovacuum.vacuum_scale_factor",
+        "autovacuum_vacuum_scale_factor ist mit {current} zu hoch (Standard 20 %). Dadurch \
+         wartet Autovacuum bei einer Tabelle mit 1 Milliarde Zeilen auf 200 Millionen tote Tupel, \
+         bevor es startet. Für große Tabellen sollte autovacuum_vacuum_scale_factor 0 sein und \
+         autovacuum_vacuum_threshold auf einen festen Wert (z. B. 10.000) gesetzt werden. \
+         Dies ist pro Tabelle möglich: \
+         ALTER TABLE my_large_table SET (autovacuum_vacuum_scale_factor = 0, autovacuum_vacuum_threshold = 10000);",
+    ),
+    (
+        "autovacuum.idle_cursor_transaction",
+        "Sitzung {session} ist seit {idle} idle in transaction, nachdem sie einen Cursor \
+         deklariert oder gelesen hat. Der Snapshot der offenen Transaktion hält den xmin-Horizont \
+         {xmin_age} Transaktionen zurück, sodass VACUUM keine seitdem gelöschten oder geänderten \
+         Tupel entfernen kann, in keiner Tabelle der Datenbank. Schließen Sie den Cursor und \
+         beenden Sie die Transaktion in der Anwendung, lesen Sie stapelweise über kurze \
+         Transaktionen und setzen Sie idle_in_transaction_session_timeout als Absicherung.",
+    ),
+    (
+        "autovacuum.idle_cursor_transaction.bloat",
+        "Bereits wegen toter Tupel gemeldete Tabellen: {tables}.",
+    ),
+    (
+        "autovacuum.idle_cursor_transaction.tables",
+        "Der Cursor liest {tables}.",
+    ),
+    (
+        "autovacuum.held_cursor",
+        "Sitzung {session} hat vor {idle} zuletzt einen WITH HOLD-Cursor verwendet und ihn nicht \
+         geschlossen. Ein gehaltener Cursor hält keinen Snapshot mehr, aber sein gesamtes \
+         Ergebnis wurde beim Commit materialisiert und bleibt bis CLOSE oder Verbindungsende im \
+         Speicher oder in temporären Dateien des Backends. Schließen Sie Cursor, sobald der \
+         Client sie gelesen hat; Connection-Pooler im Transaktionsmodus verlieren sie zudem aus \
+         dem Blick.",
+    ),
+    (
+        "autovacuum.held_cursor.tables",
+        "Der Cursor wurde über {tables} deklariert.",
+    ),
+    // Logging
+    (
+        "logging.log_min_duration_statement.disabled",
+        "log_min_duration_statement ist deaktiviert. Dies ist das wichtigste Werkzeug, um \
+         langsame Abfragen zu finden. Setzen Sie den Wert auf 1000 (1 Sekunde), um alle Abfragen \
+         zu protokollieren, die 1 Sekunde oder länger dauern.",
+    ),
+    (
+        "logging.log_min_duration_statement.high",
+        "log_min_duration_statement ist recht hoch eingestellt ({current_ms}ms). Für die \
+         meisten Workloads sind 1000ms (1 Sekunde) ein guter Ausgangspunkt, um langsame Abfragen \
+         ohne übermäßiges Log-Rauschen zu erkennen.",
+    ),
+    (
+        "logging.log_min_duration_statement.all",
+        "log_min_duration_statement protokolliert ALLE Abfragen. Das kann übermäßig viele Logs \
+         erzeugen. Für die meisten Workloads genügen 1000ms (1 Sekunde), um langsame Abfragen \
+         zu erkennen.",
+    ),
+    (
+        "logging.log_lock_waits",
+        "log_lock_waits ist deaktiviert. Die Einstellung ist unverzichtbar, um Nebenläufigkeits- \
+         und Konkurrenzprobleme der Anwendung zu diagnostizieren. Aktivieren Sie sie, um jede \
+         Sitzung zu protokollieren, die länger als deadlock_timeout auf eine Sperre wartet.",
+    ),
+    (
+        "logging.deadlock_timeout",
+        "deadlock_timeout ist höher als der Standardwert 1s. Das kann Fehlalarme im Sperr-Logging \
+         reduzieren, bedeutet aber auch, dass Deadlocks später erkannt werden. Der Standardwert \
+         1s reicht für die meisten Workloads aus.",
+    ),
+    (
+        "logging.log_destination",
+        "log_destination schreibt kein maschinenlesbares Format. Ergänzen Sie {format}, damit \
+         Anweisungen, Fehler und deren Kontext von Log-Auswertungen (postgreat logs) und \
+         Log-Pipelines unabhängig vom log_line_prefix zuverlässig gelesen werden können.",
+    ),
+    (
+        "logging.logging_collector",
+        "logging_collector ist aus, daher werden csvlog und jsonlog nicht geschrieben und \
+         PostgreSQL rotiert seine Log-Dateien nicht. Das Einschalten erfordert einen Neustart.",
+    ),
+    (
+        "logging.log_filename.short",
+        "log_filename wiederholt sich alle {cycle}, daher überschreibt die Rotation Logs, die \
+         jünger als die {retention} aufzubewahrenden Tage sind. Das vorgeschlagene Muster \
+         wiederholt sich erst nach mindestens {retention} Tagen.",
+    ),
+    (
+        "logging.log_filename.unbounded",
+        "log_filename wiederholt sich nie, daher sammeln sich Log-Dateien an, bis etwas außerhalb \
+         von PostgreSQL log_directory aufräumt. Ein sich wiederholendes Muster mit \
+         log_truncate_on_rotation = on hält {retention} Tage bei begrenzter Größe.",
+    ),
+    (
+        "logging.log_truncate_on_rotation",
+        "log_truncate_on_rotation ist aus, daher wird an einen wiederverwendeten Dateinamen \
+         angehängt und mehrere Zeiträume landen in einer stetig wachsenden Datei. Schalten Sie es \
+         ein, um die alte Datei zu überschreiben.",
+    ),
+    (
+        "logging.log_rotation_age",
+        "log_rotation_age und log_rotation_size sind beide 0, daher wird die Log-Datei nie rotiert \
+         und wächst unbegrenzt. Rotieren Sie täglich.",
+    ),
+    (
+        "logging.pgaudit_volume",
+        "pgaudit.log würde bei den aktuellen {statements} Anweisungen pro Sekunde etwa {volume} \
+         Audit-Log pro Tag schreiben ({classes}), genug, um Log-Volumes zu füllen und jede \
+         Anweisung durch Log-I/O zu bremsen. Die vorgeschlagenen Klassen senken es auf etwa \
+         {remaining}; prüfen Sie Lesezugriffe auf sensible Tabellen per Objekt-Auditing \
+         (pgaudit.role) statt mit der Klasse read.",
+    ),
+    (
+        "logging.log_statement_sample_rate",
+        "Das Dauer-Logging schreibt bei den aktuellen Anweisungsraten etwa {volume} ({lines} Zeilen) \
+         Server-Log pro Stunde, mehr als die meisten Log-Pipelines verkraften. Behalten Sie \
+         log_min_duration_statement = {min_duration}ms für die langsamsten Anweisungen und \
+         stichproben Sie den Rest mit log_min_duration_sample = {sample_min_duration}ms und \
+         log_statement_sample_rate = {rate}.",
+    ),
+    (
+        "logging.log_statement",
+        "log_statement schreibt bei den aktuellen Anweisungsraten etwa {volume} ({lines} Zeilen) \
+         Server-Log pro Stunde, mehr als die meisten Log-Pipelines verkraften. Protokollieren Sie \
+         nur ddl; wo jede Anweisung nötig ist, nehmen Sie Stichproben mit \
+         log_min_duration_sample = 0 und log_statement_sample_rate = {rate}.",
+    ),
+    (
+        "logging.log_connections",
+        "Das Verbindungs-Logging schreibt bei {sessions} neuen Sessions pro Sekunde etwa {volume} \
+         ({lines} Zeilen) Server-Log pro Stunde. Bündeln Sie Verbindungen mit einem Pooler, oder \
+         schalten Sie log_connections ab, wenn die Verbindungszeilen nicht gebraucht werden.",
+    ),
+    // Extensions
+    (
+        "extensions.timescaledb_background_workers",
+        "timescaledb.max_background_workers ({current}) reicht nicht für die {jobs} geplanten \
+         TimescaleDB-Jobs plus den Scheduler der Datenbank. Kompressions-, Aufbewahrungs- und \
+         Continuous-Aggregate-Jobs warten aufeinander oder verpassen ihren Zeitplan. Erhöhen Sie \
+         den Wert auf {recommended}.",
+    ),
+    (
+        "extensions.timescaledb_worker_processes",
+        "TimescaleDB benötigt max_worker_processes von mindestens \
+         timescaledb.max_background_workers ({background_workers}) + max_parallel_workers \
+         ({parallel_workers}) + 3 = {recommended}; darunter konkurrieren Hintergrund-Jobs und \
+         parallele Abfragen um dieselben Worker-Plätze.",
+    ),
+    (
+        "extensions.citus_shard_count",
+        "Mit citus.shard_count ({current}) erhalten neue verteilte Tabellen weniger als \
+         {per_worker} Shards auf jedem der {workers} Worker, sodass ihre Abfragen nicht alle \
+         Worker nutzen. Verwenden Sie {recommended}; bestehende Tabellen behalten ihre \
+         Shard-Anzahl, bis sie mit alter_distributed_table() geändert wird.",
+    ),
+    (
+        "extensions.citus_executor_pool_size",
+        "Mit citus.max_adaptive_executor_pool_size bei {current} können {peak} Sitzungen bis zu \
+         {total} Verbindungen zu jedem Worker öffnen, mehr als max_connections \
+         ({max_connections}) erlaubt, wenn die Worker wie dieser Knoten dimensioniert sind. \
+         Senken Sie den Wert auf {recommended}, damit Multi-Shard-Abfragen auf dem Koordinator \
+         warten, statt auf den Workern fehlzuschlagen.",
+    ),
+    (
+        "extensions.pgvector_missing_index",
+        "{table}.{column} enthält ~{rows} Vektoren ohne HNSW- oder IVFFlat-Index, daher vergleicht \
+         jede Nächste-Nachbarn-Abfrage den Suchvektor mit jeder Zeile. Legen Sie einen mit {ddl} \
+         an; die Operatorklasse muss zum Distanzoperator der Abfragen passen ({operator}).",
+    ),
+    (
+        "extensions.pgvector_missing_index.unverified",
+        "pg_stat_statements konnte nicht gelesen werden, daher ist unbekannt, wie oft die Spalte \
+         per Distanz abgefragt wird.",
+    ),
+    (
+        "extensions.pgvector_ivfflat_lists",
+        "{index} wurde mit {current} Listen für ~{rows} Zeilen erstellt; pgvector empfiehlt \
+         Zeilen / 1000 bis zu einer Million Zeilen und sqrt(Zeilen) darüber, also etwa \
+         {recommended}. Zu wenige Listen lassen jede Probe einen großen Teil der Tabelle lesen, \
+         zu viele senken den Recall bei gleichen Probes. lists wird beim Erstellen festgelegt, \
+         erstellen Sie den Index daher WITH (lists = {recommended}) neu.",
+    ),
+    (
+        "extensions.pgvector_ivfflat_probes",
+        "ivfflat.probes ist {current}, während der größte IVFFlat-Index {lists} Listen hat; \
+         Abfragen durchsuchen so nur einen kleinen Teil davon und verpassen nahe Treffer. \
+         Beginnen Sie mit sqrt(lists) = {recommended} und erhöhen Sie, bis der Recall genügt.",
+    ),
+    (
+        "extensions.pgvector_hnsw_ef_search",
+        "hnsw.ef_search ist {current}, während {table} ~{rows} Zeilen unter einem HNSW-Index hat. \
+         Größere Graphen brauchen eine längere Kandidatenliste für guten Recall, und eine Abfrage \
+         liefert nie mehr Zeilen als ef_search; verwenden Sie mindestens {recommended}.",
+    ),
+    (
+        "extensions.pgvector_build_memory",
+        "Der HNSW-Graph für {table} ist etwa {size} groß, mehr als maintenance_work_mem \
+         ({current}). Builds, die nicht in den Speicher passen, sind um ein Vielfaches langsamer; \
+         setzen Sie maintenance_work_mem in der Sitzung, die den Index erstellt oder neu \
+         aufbaut, auf {recommended}, oder serverweit, wenn andere Wartung so viel Speicher \
+         teilen kann.",
+    ),
+    // Server version
+    (
+        "version.minor_release",
+        "PostgreSQL {current} liegt {behind} Minor-Release(s) hinter {latest}, dem neuesten \
+         {major}.x-Release. Minor-Releases enthalten nur Fehler- und Sicherheitskorrekturen, \
+         behalten das Speicherformat bei und werden durch Installation der neuen Binärdateien \
+         und einen Neustart eingespielt.",
+    ),
+    (
+        "version.eol_major",
+        "PostgreSQL {major} hat am {eol} das Ende des Supports erreicht und erhält keine Fehler- \
+         oder Sicherheitskorrekturen mehr. Planen Sie ein Upgrade auf eine unterstützte \
+         Hauptversion wie {newest} mit pg_upgrade oder logischer Replikation.",
+    ),
+    // Host and storage layout
+    (
+        "host.wal_volume",
+        "{path} liegt auf demselben Volume wie das Datenverzeichnis, daher warten WAL-fsyncs auf \
+         Schreibvorgänge in Datendateien, und ein volles Daten-Volume stoppt auch das WAL. Legen \
+         Sie pg_wal auf Bare Metal oder einer VM auf ein eigenes Volume (initdb --waldir, oder \
+         bei gestopptem Server verschieben und einen Symlink hinterlassen).",
+    ),
+    (
+        "host.temp_tablespace_volume",
+        "Der temporäre Tablespace {name} liegt auf dem Daten-Volume, daher konkurrieren Sortier- \
+         und Hash-Operationen, die auf die Platte ausweichen, mit Daten- und WAL-I/O. Richten Sie \
+         temp_tablespaces auf einen Tablespace auf einem separaten, möglichst lokalen Volume.",
+    ),
+    (
+        "host.filesystem",
+        "{mount} ist ein {fs}-Dateisystem. Sein Inhalt übersteht keinen Neustart oder \
+         Container-Austausch, oder jeder fsync kostet einen Netzwerk-Roundtrip; legen Sie \
+         PostgreSQL-Dateien auf ein lokales xfs- oder ext4-Volume.",
+    ),
+    (
+        "host.mount_options",
+        "{mount} ist ohne noatime eingehängt, daher aktualisieren auch Lesezugriffe die \
+         Zugriffszeiten der Inodes. Hängen Sie es mit noatime ein; PostgreSQL liest \
+         Zugriffszeiten nie.",
+    ),
+    // Operating system
+    (
+        "operating_system.open_files",
+        "Der Server darf pro Prozess nur {current} Dateien öffnen, weniger als max_connections + \
+         max_files_per_process ({needed}), daher schließen und öffnen ausgelastete Backends \
+         Dateien ständig neu oder scheitern mit \"too many open files\". Erhöhen Sie \
+         LimitNOFILE für den Dienst und starten Sie ihn neu.",
+    ),
+    (
+        "operating_system.overcommit_memory",
+        "vm.overcommit_memory ist {current}, daher beendet der OOM-Killer bei Speichermangel \
+         einen Prozess, womöglich den Postmaster, was alle Verbindungen neu startet. Mit 2 \
+         schlagen Anforderungen stattdessen mit einem Fehler fehl; prüfen Sie, dass \
+         vm.overcommit_ratio Platz für shared_buffers und die Backends lässt.",
+    ),
+    (
+        "operating_system.swappiness",
+        "vm.swappiness ist {current}, daher lagert der Kernel shared_buffers und Backend-Speicher \
+         aus, statt den Page Cache zu verwerfen. Setzen Sie vm.swappiness = 1.",
+    ),
+    (
+        "operating_system.transparent_hugepage",
+        "Transparent Huge Pages stehen auf always, was durch Speicherkompaktierung im \
+         Hintergrund Latenzspitzen verursacht und den Backend-Speicher aufbläht. Setzen Sie sie \
+         auf never (mit transparent_hugepage=never in der Kernel-Kommandozeile auch nach einem \
+         Neustart) und verwenden Sie huge_pages für den gemeinsamen Speicher.",
+    ),
+    (
+        "operating_system.zone_reclaim_mode",
+        "vm.zone_reclaim_mode ist {current} auf einem Host mit {nodes} NUMA-Knoten, daher \
+         verdrängt der Kernel den Page Cache auf dem lokalen Knoten, statt Speicher von einem \
+         anderen zu nehmen, und Lesezugriffe, die den Cache treffen sollten, gehen auf die Platte. \
+         Setzen Sie ihn auf 0.",
+    ),
+    (
+        "operating_system.numa_balancing",
+        "Automatisches NUMA-Balancing ist über {nodes} NUMA-Knoten aktiv. Jedes Backend greift \
+         auf shared_buffers zu, daher verschiebt der Kernel dessen Seiten ständig und blockiert \
+         die Prozesse, die sie nutzen. Setzen Sie kernel.numa_balancing = 0 und starten Sie den \
+         Postmaster unter numactl --interleave=all, damit der gemeinsame Speicher gleichmäßig \
+         auf die Knoten verteilt wird.",
+    ),
+    // Backup and recovery
+    (
+        "backup.recency.none",
+        "Kein erfolgreiches Base-Backup gefunden: In pg_wal liegt keine \
+         Base-Backup-History-Datei und kein Backup-Prüfbefehl hat eines gemeldet. Archiviertes \
+         WAL lässt sich ohne Base-Backup, auf das es eingespielt wird, nicht wiederherstellen. \
+         Ohne Backup innerhalb von {rpo} lässt sich ein verlorenes Volume oder eine fehlerhafte \
+         Migration nicht innerhalb des RPO wiederherstellen. Prüfen Sie, ob Backups laufen und \
+         gelingen.",
+    ),
+    (
+        "backup.recency.stale",
+        "Das neueste erfolgreiche Backup ist {age} alt ({source}, {at}) und liegt außerhalb des \
+         RPO von {rpo}. Prüfen Sie, ob Backups und WAL-Archivierung noch laufen und gelingen.",
+    ),
+    (
+        "backup.archive_mode",
+        "archive_mode ist aus, daher wird kein WAL archiviert und ein Base-Backup lässt sich nur \
+         auf den Zeitpunkt seines Abschlusses wiederherstellen. Schalten Sie archive_mode ein \
+         (Neustart) und setzen Sie archive_command oder archive_library, damit der Server auf \
+         jeden beliebigen Zeitpunkt wiederhergestellt werden kann.",
+    ),
+    (
+        "backup.archive_command",
+        "archive_mode ist an, aber weder archive_command noch archive_library ist gesetzt. Der \
+         Server behält jedes WAL-Segment für den Archiver, sodass pg_wal wächst, bis die Platte \
+         voll ist, und nichts für PITR archiviert wird. Setzen Sie einen archive_command, der \
+         jedes Segment auf dauerhaften Speicher kopiert und fehlschlägt, wenn die Kopie \
+         fehlschlägt.",
+    ),
+    (
+        "backup.restore_command",
+        "Standby {standby} hat keinen restore_command. Fällt er hinter das WAL zurück, das der \
+         Primary vorhält, kann er die fehlenden Segmente nicht aus dem Archiv holen und muss aus \
+         einem neuen Base-Backup neu aufgebaut werden.",
+    ),
+    (
+        "backup.lost_slot",
+        "Replikations-Slot {slot} hat WAL verloren, das sein Verbraucher noch benötigt, daher \
+         kann dieser das Streaming nicht fortsetzen. Bauen Sie Standby oder Subscriber neu auf \
+         und löschen Sie den Slot.",
+    ),
+    (
+        "backup.max_slot_wal_keep_size",
+        "max_slot_wal_keep_size ist unbegrenzt bei {slots} Replikations-Slots. Ein getrennter \
+         Verbraucher lässt seinen Slot WAL zurückhalten, bis pg_wal die Platte füllt und der \
+         Primary stoppt. Begrenzen Sie das zurückgehaltene WAL auf {suggested}, damit ein \
+         hängender Slot stattdessen invalidiert wird.",
+    ),
+    (
+        "backup.max_slot_wal_keep_size.inactive",
+        "Der inaktive Replikations-Slot {slot} hält bereits {retained} WAL zurück und \
+         max_slot_wal_keep_size ist unbegrenzt, daher wächst er weiter, bis pg_wal die Platte \
+         füllt. Begrenzen Sie das zurückgehaltene WAL auf {suggested} und löschen Sie den Slot, \
+         wenn sein Verbraucher nicht mehr existiert.",
+    ),
+    (
+        "backup.wal_keep_size",
+        "Die Standbys {standbys} streamen ohne Replikations-Slot oder restore_command und \
+         wal_keep_size ist 0, daher verliert jede Verzögerung über einen Checkpoint hinaus WAL, \
+         das sie noch brauchen. Halten Sie {suggested} WAL vor oder geben Sie ihnen einen Slot \
+         oder restore_command.",
+    ),
+    // Replication and resilience
+    (
+        "replication.tcp_keepalives_idle",
+        "Mit den aktuellen Keepalive-Einstellungen bemerkt der Server einen verschwundenen \
+         Client, etwa einen durch Failover oder Netzwerkpartition abgeschnittenen Anwendungshost, \
+         erst nach {detection}. Bis dahin hält sein Backend Sperren, eine offene Transaktion und \
+         einen Verbindungsslot. Senden Sie die erste Probe nach 60 Sekunden Stille; mit \
+         tcp_keepalives_interval 10 und tcp_keepalives_count 6 werden tote Verbindungen nach \
+         {suggested} getrennt.",
+    ),
+    (
+        "replication.tcp_keepalives_interval",
+        "Unbeantwortete Keepalive-Proben werden langsam wiederholt, daher wird eine tote Client- \
+         Verbindung erst nach insgesamt {detection} getrennt. Eine Probe alle 10 Sekunden, mit \
+         tcp_keepalives_idle 60 und tcp_keepalives_count 6, senkt das auf {suggested}.",
+    ),
+    (
+        "replication.tcp_keepalives_count",
+        "Der Server wartet auf viele unbeantwortete Keepalive-Proben, daher wird eine tote \
+         Client-Verbindung erst nach insgesamt {detection} getrennt. Nach 6 Proben aufzugeben, \
+         mit tcp_keepalives_idle 60 und tcp_keepalives_interval 10, senkt das auf {suggested}.",
+    ),
+    (
+        "replication.wal_receiver_timeout",
+        "wal_receiver_timeout ist {timeout}, so lange braucht ein Standby, um einen stummen \
+         Primary zu bemerken und sich mit dem beförderten zu verbinden. 60s hält Failover kurz.",
+    ),
+    (
+        "replication.wal_receiver_timeout.disabled",
+        "wal_receiver_timeout ist 0, daher wartet ein Standby, dessen Primary ausfällt, ohne die \
+         Verbindung zu schließen, bis TCP aufgibt, oft stundenlang, statt sich mit dem neuen \
+         Primary zu verbinden oder auf das Archiv auszuweichen. Verwenden Sie 60s.",
+    ),
+    (
+        "replication.wal_sender_timeout",
+        "wal_sender_timeout ist {timeout}, so lange bleiben die Replikationsverbindung eines \
+         verschwundenen Standbys und der von ihr aktiv gehaltene Slot bestehen. 60s gibt sie \
+         schnell frei.",
+    ),
+    (
+        "replication.wal_sender_timeout.disabled",
+        "wal_sender_timeout ist 0, daher wird der WAL-Sender eines verschwundenen Standbys nie \
+         beendet: Sein Slot bleibt aktiv und hält weiter WAL zurück, und der Standby kann den \
+         Slot erst wieder nutzen, wenn TCP aufgibt. Verwenden Sie 60s.",
+    ),
+    (
+        "replication.restart_after_crash",
+        "restart_after_crash ist auf einem Server mit Standbys an. Übernimmt ein Cluster-Manager \
+         wie Patroni, pg_auto_failover oder Pacemaker das Failover, konkurriert der Neustart des \
+         Postmasters nach einem Absturz mit der Beförderung eines Standbys; schalten Sie es aus, \
+         damit der Cluster-Manager entscheidet. Ohne Cluster-Manager lassen Sie es an.",
+    ),
+    // Security
+    (
+        "security.policies_without_rls",
+        "{table} hat {policies} Row-Level-Security-Policies, aber Row-Level Security ist nicht \
+         aktiviert, daher werden die Policies ignoriert und jede Rolle mit Tabellenrechten sieht \
+         und ändert jede Zeile. Führen Sie ALTER TABLE {table} ENABLE ROW LEVEL SECURITY aus, \
+         wenn die Policies Mandanten trennen sollen.",
+    ),
+    (
+        "security.rls_without_policies",
+        "{table} hat Row-Level Security aktiviert, aber keine Policies, daher erhält jede Rolle \
+         außer dem Eigentümer {owner}, Superusern und BYPASSRLS-Rollen keine Zeilen und kann \
+         keine schreiben. Legen Sie Policies für die Rollen an, die die Tabelle nutzen, oder \
+         deaktivieren Sie Row-Level Security.",
+    ),
+    (
+        "security.rls_without_policies.forced",
+        "{table} erzwingt Row-Level Security, hat aber keine Policies, daher erhält jede Rolle, \
+         auch der Eigentümer {owner}, keine Zeilen und kann keine schreiben; nur Superuser und \
+         BYPASSRLS-Rollen sehen die Daten. Legen Sie Policies für die Rollen an, die die Tabelle \
+         nutzen.",
+    ),
+    (
+        "security.rls_not_forced",
+        "Row-Level Security auf {table} ist nicht erzwungen, daher umgeht der Eigentümer {owner} \
+         die Policies. Verbindet sich die Anwendung als Eigentümer, sind Mandanten überhaupt \
+         nicht getrennt. Führen Sie ALTER TABLE {table} FORCE ROW LEVEL SECURITY aus oder \
+         verbinden Sie die Anwendung mit einer Rolle, der die Tabelle nicht gehört.",
+    ),
+    (
+        "security.permissive_policy",
+        "Die permissive Policy {policy} auf {table} erlaubt {command} auf jeder Zeile für \
+         {roles}. Permissive Policies werden mit OR verknüpft, daher hebelt diese jeden \
+         Mandantenfilter für diese Rollen aus. Schränken Sie ihren Ausdruck ein, machen Sie sie \
+         RESTRICTIVE oder beschränken Sie sie auf eine administrative Rolle.",
+    ),
+    (
+        "security.row_security",
+        "row_security ist aus, während {tables} Tabellen auf Row-Level Security angewiesen sind. \
+         Abfragen, die eine Policy filtern würde, schlagen mit einem Fehler fehl, statt die \
+         sichtbaren Zeilen zu liefern. Schalten Sie es nur für Sitzungen wie pg_dump aus, die \
+         alles sehen oder fehlschlagen müssen.",
+    ),
+    (
+        "security.public_schema_create",
+        "Jede Rolle darf Objekte im Schema public anlegen, das der search_path ({search_path}) \
+         durchsucht. Unqualifizierte CREATE-Anweisungen landen dort, sobald kein früheres Schema \
+         existiert, und jede Rolle kann eine Tabelle, Funktion oder einen Operator anlegen, der \
+         das Objekt verdeckt, auf das unqualifizierte Namen anderer Sitzungen auflösen \
+         (CVE-2018-1058). Führen Sie REVOKE CREATE ON SCHEMA public FROM PUBLIC aus und vergeben \
+         Sie CREATE nur an die Rollen, denen die Anwendungsobjekte gehören.",
+    ),
+    // Schema design
+    (
+        "schema.varchar_255",
+        "{count} Spalten in {tables} Tabellen sind varchar(255), z. B. {examples}. In PostgreSQL \
+         werden text und varchar gleich gespeichert, und 255 ist eine Gewohnheit aus anderen \
+         Datenbanken, keine Grenze, die die Daten brauchen; sie lehnt längere Werte nur zur \
+         Laufzeit ab. Verwenden Sie text, mit einem CHECK-Constraint, wo es eine echte \
+         Längengrenze gibt.",
+    ),
+    (
+        "schema.timestamp_without_time_zone",
+        "{count} Spalten in {tables} Tabellen sind timestamp without time zone, z. B. {examples}. \
+         Sie speichern die Uhrzeit, die der Client geschickt hat, sodass Werte aus Sitzungen in \
+         verschiedenen Zeitzonen oder über eine Sommerzeitumstellung hinweg nicht vergleichbar \
+         sind. Verwenden Sie timestamptz, das einen absoluten Zeitpunkt speichert und ihn bei der \
+         Ausgabe in die TimeZone der Sitzung umrechnet.",
+    ),
+    (
+        "schema.money",
+        "{count} Spalten in {tables} Tabellen verwenden den Typ money, z. B. {examples}. \
+         Genauigkeit und Formatierung folgen lc_monetary, sodass ein unter einer anderen Locale \
+         wiederhergestellter Dump die Werte falsch liest, und die Währung wird nicht gespeichert. \
+         Verwenden Sie numeric, mit einer Währungsspalte, wo Beträge in verschiedenen Währungen \
+         vorkommen.",
+    ),
+    (
+        "schema.missing_primary_key",
+        "{count} Tabellen haben keinen Primärschlüssel, z. B. {examples}. Doppelte Zeilen lassen \
+         sich nicht unterscheiden, logische Replikation lehnt ihre UPDATEs und DELETEs ohne \
+         REPLICA IDENTITY FULL ab, und Werkzeuge wie pg_repack verweigern sie. Legen Sie einen \
+         Primärschlüssel an oder verwenden Sie einen eindeutigen Index auf NOT-NULL-Spalten als \
+         Replica Identity.",
+    ),
+    (
+        "schema.unindexed_nullable_fk",
+        "{count} Fremdschlüssel auf nullbaren Spalten haben keinen Index, der mit ihren Spalten \
+         beginnt, z. B. {examples}. Jedes DELETE oder Schlüssel-UPDATE auf der referenzierten \
+         Tabelle durchsucht die referenzierende Tabelle, um den Schlüssel zu prüfen, und Joins \
+         darüber können keinen Index nutzen. Indizieren Sie die referenzierenden Spalten; ein \
+         partieller Index WHERE die Spalte IS NOT NULL lässt Zeilen ohne Referenz aus.",
+    ),
+    (
+        "schema.not_valid_constraint",
+        "{kind}-Constraint {constraint} auf {table} wurde mit NOT VALID angelegt und nie \
+         validiert. Seitdem geschriebene Zeilen werden geprüft, aber bestehende Zeilen können ihn \
+         verletzen, und der Planer verlässt sich nicht auf einen unvalidierten Constraint, etwa \
+         um Tabellen oder Partitionen per CHECK auszuschließen. Führen Sie ALTER TABLE {table} \
+         VALIDATE CONSTRAINT {constraint} aus; es nimmt eine SHARE UPDATE EXCLUSIVE-Sperre, \
+         sodass Lese- und Schreibzugriffe während des Scans weiterlaufen.",
+    ),
+    (
+        "schema.disabled_trigger",
+        "Trigger {trigger} auf {table} ist deaktiviert, sodass alles, was er pflegt, etwa Audit- \
+         Zeilen, abgeleitete Spalten oder Änderungen an anderen Tabellen, bei jedem \
+         Schreibvorgang unbemerkt ausbleibt. Wurde er für einen Massenimport deaktiviert, führen \
+         Sie ALTER TABLE {table} ENABLE TRIGGER {trigger} aus und holen Sie das Versäumte nach; \
+         wird er nicht mehr gebraucht, löschen Sie ihn.",
+    ),
+    (
+        "schema.disabled_fk_trigger",
+        "Die Trigger, die den Fremdschlüssel {constraint} auf {table} durchsetzen, sind \
+         deaktiviert, meist durch ALTER TABLE ... DISABLE TRIGGER ALL, sodass Zeilen, die den \
+         Schlüssel verletzen, angenommen werden, während der Constraint gültig aussieht. Führen \
+         Sie ALTER TABLE ... ENABLE TRIGGER ALL auf beiden Tabellen aus und suchen Sie nach \
+         verwaisten Zeilen, da das Aktivieren bestehende Zeilen nicht erneut prüft.",
+    ),
+    (
+        "table_index.orphaned_temp_schema",
+        "{schema} enthält {tables} temporäre Tabellen ({size}), aber kein Backend nutzt seinen \
+         Slot, sodass sie von einer abgestürzten oder beendeten Sitzung zurückgelassen wurden. \
+         Sie belegen Speicherplatz, den keine Tabellenliste zeigt, und da Autovacuum temporäre \
+         Tabellen nicht bereinigen kann, hält ihr Alter von {xid_age} Transaktionen das \
+         datfrozenxid der Datenbank zurück. Autovacuum löscht verwaiste temporäre Tabellen beim \
+         nächsten Durchlauf über die Datenbank; bleiben sie bestehen, löschen Sie sie als \
+         Superuser, die größten zuerst: {drops}",
+    ),
+    (
+        "table_index.orphaned_temp_schema.autovacuum_off",
+        "{schema} enthält {tables} temporäre Tabellen ({size}), aber kein Backend nutzt seinen \
+         Slot, sodass sie von einer abgestürzten oder beendeten Sitzung zurückgelassen wurden. \
+         Sie belegen Speicherplatz, den keine Tabellenliste zeigt, und da Autovacuum temporäre \
+         Tabellen nicht bereinigen kann, hält ihr Alter von {xid_age} Transaktionen das \
+         datfrozenxid der Datenbank zurück. Da autovacuum aus ist und gerade Autovacuum verwaiste \
+         temporäre Tabellen löscht, bleiben sie, bis ein Superuser sie löscht, die größten \
+         zuerst: {drops}",
+    ),
+    // Table and index health
+    (
+        "table_index.toast_compression",
+        "{table} enthält {toast} TOAST-Daten und schreibt {writes_per_row} Zeilen pro lebender \
+         Zeile, alles mit pglz komprimiert. lz4 komprimiert um ein Mehrfaches schneller und \
+         dekomprimiert etwa doppelt so schnell, bei rund 10% mehr Speicher (hier etwa {extra} \
+         mehr). Neue Werte verwenden lz4 nach {ddl}; bestehende bleiben pglz, bis die Tabelle \
+         neu geschrieben wird, z. B. durch VACUUM FULL oder pg_repack.",
+    ),
+    (
+        "table_index.default_toast_compression",
+        "default_toast_compression ist pglz, während {tables} schreibintensive Tabelle(n) mit \
+         großen TOAST-Daten von lz4 profitieren würden. Mit lz4 als Standard erhalten neue \
+         Spalten und Tabellen es ohne ALTER pro Spalte.",
+    ),
+    (
+        "table_index.bloat.stale_autovacuum",
+        "{table} hat {dead_pct} % tote Tupel, aber das letzte Autovacuum lief {last_autovacuum}. \
+         Das Autovacuum-Tuning hält offenbar nicht Schritt; erhöhen Sie die Aggressivität von \
+         Autovacuum pro Tabelle (niedrigerer Scale Factor/Threshold) oder planen Sie ein \
+         manuelles VACUUM, um den Bloat zu entfernen.",
+    ),
+    (
+        "table_index.bloat.never_autovacuumed",
+        "{table} hat {dead_pct} % tote Tupel und kein erfasstes Autovacuum. Das \
+         Autovacuum-Tuning hält offenbar nicht Schritt; erhöhen Sie die Aggressivität von \
+         Autovacuum pro Tabelle (niedrigerer Scale Factor/Threshold) oder planen Sie ein \
+         manuelles VACUUM, um den Bloat zu entfernen.",
+    ),
+    (
+        "table_index.bloat.recent_autovacuum",
+        "{table} weist selbst nach einem kürzlichen Autovacuum {dead_pct} % tote Tupel auf. \
+         Workloads mit hoher Änderungsrate benötigen eventuell aggressivere \
+         Autovacuum-Einstellungen oder gezieltes VACUUM (FULL) in verkehrsarmen Zeitfenstern.",
+    ),
+    (
+        "table_index.seq_scan_hotspot",
+        "{table} hat {seq_scans} sequenzielle Scans gegenüber {idx_scans} Index-Scans bei etwa \
+         {rows} Zeilen ({size}). Das entspricht der Beschreibung in docs/6: filterlastige Abfragen \
+         weichen bei großen Tabellen auf sequenzielle Scans aus. Suchen Sie in \
+         pg_stat_statements nach den betroffenen Abfragen und legen Sie zusammengesetzte oder \
+         partielle Indizes für deren Bedingungen an.",
+    ),
+    (
+        "table_index.unused_index",
+        "{index} wurde nie gescannt und sichert keinen Constraint ab. Das Löschen gibt {size} \
+         frei und entfernt Schreibaufwand, gemäß den Empfehlungen in docs/6.",
+    ),
+    (
+        "table_index.unused_index.writes_per_day",
+        "Er verarbeitet etwa {writes} Schreibvorgänge pro Tag (Inserts und Nicht-HOT-Updates auf \
+         {table}) ohne einen einzigen Lesezugriff.",
+    ),
+    (
+        "table_index.unused_index.writes_since_reset",
+        "Er hat seit dem Zurücksetzen der Statistiken {writes} Schreibvorgänge (Inserts und \
+         Nicht-HOT-Updates auf {table}) verarbeitet, ohne einen einzigen Lesezugriff.",
+    ),
+    (
+        "table_index.low_selectivity",
+        "{index} liefert bei jedem Scan etwa {pct} % von {table} ({tuples} Tupel pro Lesevorgang). \
+         Diese geringe Selektivität bedeutet, dass der Planer einen großen Teil der Tabelle \
+         berührt; gestalten Sie den Index gemäß docs/6 Abschnitt C.2 neu.",
+    ),
+    (
+        "table_index.failed_index_only",
+        "{index} führt Index-Scans aus, liest aber in {pct} % der Fälle trotzdem Heap-Seiten. \
+         Fügen Sie die fehlenden SELECT-Spalten per INCLUDE hinzu oder führen Sie VACUUM aus, \
+         um die Visibility Map zu aktualisieren, damit Index-Only-Scans gelingen (docs/6 \
+         Abschnitt C.3).",
+    ),
+    (
+        "table_index.missing_partial_index",
+        "Tabelle {table} hat eine Soft-Delete-Spalte, aber keinen partiellen Index. Fügen Sie \
+         'WHERE is_deleted = false' (oder deleted_at IS NULL) hinzu, um gelöschte Zeilen aus dem \
+         Index auszuschließen und so Größe und Wartungsaufwand zu verringern.",
+    ),
+    (
+        "table_index.brin_candidate",
+        "Tabelle {table} ist groß und physisch nach {column} geordnet. Ein BRIN-Index wäre \
+         100-mal kleiner als ein B-Tree und böte dennoch gute Scan-Leistung für Bereichsabfragen.",
+    ),
+    (
+        "table_index.missing_spatial_index",
+        "Tabelle {table} hat ~{rows} Zeilen, aber keinen GiST-, SP-GiST- oder BRIN-Index auf \
+         der Spalte {column}, daher liest jeder ST_Intersects-, ST_DWithin- oder &&-Filter \
+         darauf die ganze Tabelle. Legen Sie einen mit CREATE INDEX CONCURRENTLY ... USING gist \
+         ({column}) an.",
+    ),
+    // Notes appended by shared passes
+    ("note.statistics_window", "Die Statistiken umfassen {window} (seit {since})."),
+    (
+        "note.sample_window",
+        "Die Scan-Zähler umfassen das Messfenster von {window}; die Indexnutzung umfasst \
+         {stats_window} (seit {since}).",
+    ),
+    ("note.also_flagged", "Ebenfalls gemeldet von {category}:"),
+    ("note.suggested_instead", "{category} schlug stattdessen {value} vor:"),
+    (
+        "note.clamped_min",
+        "Auf {value} begrenzt, das Minimum des Servers für {parameter} (die Heuristik schlug \
+         {suggested} vor).",
+    ),
+    (
+        "note.clamped_max",
+        "Auf {value} begrenzt, das Maximum des Servers für {parameter} (die Heuristik schlug \
+         {suggested} vor).",
+    ),
+    (
+        "note.clamped_32bit",
+        "Auf {value} begrenzt, die praktische Grenze eines 32-Bit-Builds für {parameter} \
+         (die Heuristik schlug {suggested} vor).",
+    ),
+    (
+        "note.checkpoint_interval",
+        "Bei der gemessenen WAL-Rate von {wal_rate} läuft alle ~{before} ein Checkpoint.",
+    ),
+    (
+        "note.checkpoint_interval_change",
+        "Bei der gemessenen WAL-Rate von {wal_rate} laufen Checkpoints heute alle ~{before} \
+         → ~{after} mit den vorgeschlagenen WAL-Einstellungen.",
+    ),
+    (
+        "note.full_page_writes",
+        "Full-Page-Images machen ~{share}% des WAL aus; das längere Intervall senkt das \
+         WAL-Volumen um bis zu ~{savings}%.",
+    ),
+    ("note.severity_override", "Schweregrad durch die Regeldatei auf {level} gesetzt (die Prüfung meldete {original})."),
+    ("note.pending_reload", "{value} ist bereits in {file} hinterlegt und wartet auf ein Neuladen der Konfiguration."),
+    ("note.pending_restart", "{value} ist bereits in {file} hinterlegt und wartet auf einen Neustart."),
+    (
+        "note.large_server_parallel",
+        "Auf einem Server mit {vcpu} vCPUs, der vermutlich mehrere NUMA-Knoten umfasst, erzeugen \
+         Worker auf entfernten Knoten mehr Speicherverkehr als Tempo, daher begrenzt PostGreat \
+         den Zielwert auf {target}.",
+    ),
+    ("note.connection_peak_hours", "Die Verbindungen erreichen ihren Höchststand in diesen Zeiträumen: {hours}."),
+    (
+        "note.observed_connection_peak",
+        "Die gemessenen Verbindungen erreichten höchstens {peak} connections ({pct}% von \
+         max_connections) in {samples} Messungen von {first} bis {last}; der Zielwert hält 25% \
+         Reserve über diesem Höchststand.",
+    ),
+    (
+        "note.log_volume_at_recommended",
+        "Bei den aktuellen Anweisungsraten würde das etwa {volume} ({lines} Zeilen) pro Stunde \
+         protokollieren; um unter 1GB zu bleiben, setzen Sie {min_duration}ms und nehmen vom Rest \
+         Stichproben mit log_min_duration_sample = {sample_min_duration}ms und \
+         log_statement_sample_rate = {rate}.",
+    ),
+];
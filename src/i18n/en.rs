@@ -0,0 +1,945 @@
+pub(super) static MESSAGES: &[(&str, &str)] = &[
+    ("text", "{text}"),
+    // Memory
+    (
+        "memory.shared_buffers",
+        "shared_buffers should be ~25% of total RAM ({memory_gb}GB). \
+         This is the single most effective performance parameter.",
+    ),
+    (
+        "memory.effective_cache_size",
+        "effective_cache_size is a cost model hint for the query planner. \
+         Setting it to ~75% of RAM helps the planner choose index scans over \
+         sequential scans. The default is dangerously low.",
+    ),
+    (
+        "memory.work_mem.too_high",
+        "work_mem is dangerously high at {current_mb}MB. This setting is multiplied by \
+         the number of concurrent operations and connections. A high work_mem \
+         with many connections is the most common cause of OOM errors. \
+         ~{recommended_mb}MB is recommended for this system and workload type.",
+    ),
+    (
+        "memory.work_mem.too_low",
+        "work_mem is too low, which may cause sorts and hash joins to spill to disk. \
+         Use EXPLAIN (ANALYZE) to check for 'external merge Disk' or 'spill' messages.",
+    ),
+    (
+        "memory.work_mem.too_low_analytics",
+        "work_mem is too low for an analytics workload. Large sorts, aggregates and \
+         hash joins will spill to temporary files. With few concurrent connections a \
+         warehouse can safely give each operation several hundred MB; confirm with \
+         EXPLAIN (ANALYZE) and log_temp_files.",
+    ),
+    (
+        "memory.work_mem.temp_spills",
+        "Queries wrote {temp_gb}GB to {temp_files} temporary files in the last {window}, so \
+         sorts and hashes are spilling past work_mem. Raise it toward {recommended_mb}MB, or \
+         only for the roles running the spilling queries; log_temp_files names them.",
+    ),
+    (
+        "memory.maintenance_work_mem",
+        "maintenance_work_mem should be set to ~{recommended_mb}MB for your system ({vcpu}, {memory_gb}GB RAM). \
+         A larger value can dramatically speed up index creation and vacuuming.",
+    ),
+    (
+        "memory.wal_buffers",
+        "wal_buffers should be set to 16MB for high-write workloads. \
+         The default -1 (auto-sized) is usually adequate, but a fixed 16MB \
+         value can improve write performance.",
+    ),
+    (
+        "memory.huge_pages",
+        "huge_pages is {current} on a {vcpu}-vCPU server. With this many backends, each mapping \
+         shared_buffers in 4kB pages, page tables alone can take gigabytes and TLB misses slow \
+         every buffer access. Reserve vm.nr_hugepages = {pages} and set huge_pages = on, so the \
+         server refuses to start instead of silently falling back to normal pages.",
+    ),
+    (
+        "memory.hash_mem_multiplier.too_low",
+        "hash_mem_multiplier is {current}, so hash joins and hash aggregates spill to disk at the \
+         same work_mem as sorts, although they slow down far more when they do. PostgreSQL 15 \
+         raised the default to 2.0.",
+    ),
+    (
+        "memory.hash_mem_multiplier.too_high",
+        "hash_mem_multiplier is {current}, so every hash join and hash aggregate may use {hash_mb}MB, \
+         more than twice the work_mem this profile considers safe. It multiplies across the hash \
+         nodes of every connection and parallel worker; raise work_mem for the roles that need \
+         it instead.",
+    ),
+    (
+        "memory.temp_buffers",
+        "temp_buffers times max_connections ({max_connections}) comes to {total_gb}GB of the \
+         server's {memory_gb}GB. Each session keeps the buffers it fills with temporary tables \
+         until it disconnects, so long-lived pooled connections hold them indefinitely. Lower it \
+         globally and raise it per session where temporary tables are heavy.",
+    ),
+    (
+        "memory.logical_decoding_work_mem.too_high",
+        "logical_decoding_work_mem across max_wal_senders ({wal_senders}) comes to {total_gb}GB \
+         of the server's {memory_gb}GB. Each WAL sender decoding a logical slot can buffer that \
+         much before spilling to pg_replslot.",
+    ),
+    (
+        "memory.logical_decoding_work_mem.too_low",
+        "wal_level is logical but logical_decoding_work_mem is below {recommended_mb}MB. Large \
+         transactions spill to pg_replslot on disk while being decoded, delaying subscribers and \
+         adding I/O; this server has room to buffer more of them in memory.",
+    ),
+    // Concurrency
+    (
+        "concurrency.max_connections.too_high",
+        "max_connections is dangerously high at {current}. Each connection consumes \
+         memory and CPU. Best practice is to use a connection pooler (PgBouncer) \
+         and set max_connections to ~4 * vCPU ({recommended} for your system). \
+         This is a common trap parameter.",
+    ),
+    (
+        "concurrency.max_connections.high",
+        "max_connections should be kept relatively low. Consider using a connection pooler \
+         for better connection management.",
+    ),
+    (
+        "concurrency.max_connections.saturated",
+        "Sampled connections peaked at {peak} connections, {pct}% of max_connections ({current}), \
+         at {at} ({samples} samples). Bursts beyond max_connections are refused with \
+         'too many clients'. Put a connection pooler (PgBouncer) in front of the server, or \
+         raise max_connections to keep 25% headroom over the peak.",
+    ),
+    (
+        "concurrency.max_worker_processes",
+        "max_worker_processes should match your vCPU count ({recommended}). \
+         This is the master limit for all background worker processes.",
+    ),
+    (
+        "concurrency.max_parallel_workers.too_high",
+        "max_parallel_workers (total across all queries) should not exceed vCPU count ({recommended}). \
+         Setting it higher can cause CPU contention.",
+    ),
+    (
+        "concurrency.max_parallel_workers.underutilized",
+        "max_parallel_workers is underutilized. Consider increasing it to match \
+         your vCPU count for better parallel query performance.",
+    ),
+    (
+        "concurrency.max_parallel_workers_per_gather.above_vcpu",
+        "max_parallel_workers_per_gather (per query) should not exceed vCPU count ({vcpu}). \
+         Setting it to {current} would allow a single query to consume all CPU resources, \
+         starving other concurrent queries.",
+    ),
+    (
+        "concurrency.max_parallel_workers_per_gather.equals_vcpu",
+        "Setting max_parallel_workers_per_gather equal to vCPU count is dangerous. \
+         It allows a single complex query to consume all parallel workers, starving \
+         other queries. Set it to half of vCPUs to limit the blast radius of a runaway query.",
+    ),
+    (
+        "concurrency.max_parallel_workers_per_gather.above_profile",
+        "The {profile} profile holds a single query to {recommended} parallel workers per \
+         gather, so one tenant's query cannot take the CPUs all tenants share; {current} allows \
+         more. Lower max_parallel_workers_per_gather to {recommended}.",
+    ),
+    (
+        "concurrency.max_parallel_workers_per_gather.underutilized_analytics",
+        "max_parallel_workers_per_gather is underutilized for an analytics workload. \
+         Warehouses run few concurrent queries, so letting each scan, join and \
+         aggregate use ~3/4 of vCPUs (e.g., {recommended}) shortens long-running reports.",
+    ),
+    (
+        "concurrency.max_parallel_workers_per_gather.underutilized",
+        "max_parallel_workers_per_gather is underutilized. For mixed workloads, \
+         setting it to {recommended} allows several complex queries to run in parallel fully \
+         without one of them starving the rest.",
+    ),
+    (
+        "concurrency.max_parallel_maintenance_workers",
+        "max_parallel_maintenance_workers controls parallelism for manual \
+         VACUUM and CREATE INDEX commands. Setting it to {recommended} (half of vCPUs) \
+         can significantly speed up maintenance operations.",
+    ),
+    // WAL
+    (
+        "wal.max_wal_size.requested_checkpoints",
+        "Your system is triggering more size-based checkpoints ({requested}) than time-based ones ({timed}). \
+         This causes 'bursty I/O' performance degradation. Increase max_wal_size significantly \
+         until checkpoints_timed is the dominant reason for checkpoints.",
+    ),
+    (
+        "wal.max_wal_size.too_low",
+        "max_wal_size is too low at {current_gb}GB. The default (1GB) is far too low for \
+         production write-heavy workloads and causes frequent, size-based checkpoints. \
+         This creates bursty I/O. Set it high enough that checkpoints are time-based, \
+         not size-based ({recommended_gb}GB for your system).",
+    ),
+    (
+        "wal.min_wal_size",
+        "min_wal_size manages WAL file recycling. Setting this to {recommended_gb}GB ensures \
+         enough WAL segments are kept around to handle spikes in write traffic \
+         without needing to create new files from scratch.",
+    ),
+    (
+        "wal.checkpoint_timeout",
+        "checkpoint_timeout is too short. Shorter timeouts cause more frequent \
+         checkpoints, increasing I/O load. For this workload, {recommended} is a good balance.",
+    ),
+    (
+        "wal.checkpoint_timeout.analytics",
+        "For OLAP/batch workloads, consider increasing checkpoint_timeout to 15-30min \
+         to reduce the number of checkpoints during long-running operations.",
+    ),
+    (
+        "wal.checkpoint_completion_target",
+        "checkpoint_completion_target is the I/O smoothing knob. The default 0.5 \
+         creates a high-intensity I/O spike during checkpoints. Setting it to 0.9 \
+         spreads the same amount of I/O over 90% of the checkpoint interval, \
+         creating low, slow, continuous background writes.",
+    ),
+    // Planner
+    (
+        "planner.random_page_cost.hdd_value_on_ssd",
+        "random_page_cost is set for HDDs (default 4.0), but you are using SSD storage. \
+         On SSDs, random reads are nearly as fast as sequential reads. Setting this to 1.1 \
+         (combined with high effective_cache_size) tells the planner to trust and use indexes \
+         instead of always choosing sequential scans. This is MANDATORY for modern storage.",
+    ),
+    (
+        "planner.random_page_cost.lower_for_ssd",
+        "random_page_cost could be lowered to 1.1 for better index utilization on SSD storage.",
+    ),
+    (
+        "planner.random_page_cost.hdd_value_on_network",
+        "random_page_cost is tuned for spinning disks, but you are using network-attached \
+         storage (e.g. EBS, Persistent Disk). These volumes are SSD-backed, so random reads \
+         cost little more than sequential reads; the extra network latency applies to both. \
+         Setting this to 1.5 lets the planner use indexes without the optimism of a local-NVMe value.",
+    ),
+    (
+        "planner.random_page_cost.too_low_for_network",
+        "random_page_cost is below the local-SSD value on network-attached storage. \
+         Every random read pays a network round-trip, so an overly low value can make \
+         the planner favour index scans that fetch many scattered pages.",
+    ),
+    (
+        "planner.random_page_cost.too_low_for_hdd",
+        "random_page_cost is too low for HDD storage. Random I/O is much more expensive \
+         than sequential I/O on spinning disks. Increasing this to 4.0 (the default) \
+         prevents the planner from optimistically choosing index scans that will cause \
+         disk thrashing.",
+    ),
+    (
+        "planner.effective_io_concurrency.fast_storage",
+        "effective_io_concurrency should be set to 200 for SSD/NVMe and network-attached storage. \
+         Default of 1 is for single disk HDDs. Modern storage can handle massive concurrency \
+         and benefits from higher values for bitmap heap scans; network volumes in particular \
+         need deep queues to hide per-request latency.",
+    ),
+    (
+        "planner.effective_io_concurrency.hdd",
+        "effective_io_concurrency is too high for HDD storage. Spinning disks have limited \
+         IOPS and queue depth. Setting this too high (default for HDD is 1-2) can cause \
+         excessive seek activity.",
+    ),
+    (
+        "planner.maintenance_io_concurrency.fast_storage",
+        "maintenance_io_concurrency is the prefetch depth used by VACUUM and other \
+         maintenance work. The default of 10 under-uses SSD and network-attached storage; \
+         match effective_io_concurrency so maintenance can keep the device busy.",
+    ),
+    (
+        "planner.maintenance_io_concurrency.hdd",
+        "maintenance_io_concurrency is higher than spinning disks can service. \
+         Deep prefetch queues during VACUUM cause extra seeks that compete with \
+         foreground queries; keep the default of 10 on HDD storage.",
+    ),
+    (
+        "planner.seq_page_cost",
+        "seq_page_cost has been modified from the default of 1.0. Unless you have a specific reason, \
+         it's recommended to keep it at 1.0 and adjust random_page_cost instead for storage-specific tuning.",
+    ),
+    (
+        "planner.default_statistics_target",
+        "default_statistics_target controls how many histogram buckets and most-common \
+         values ANALYZE collects. Analytics queries over large, skewed tables with \
+         multi-way joins get much better row estimates with a higher target. ANALYZE \
+         takes longer, which is usually acceptable for reporting workloads.",
+    ),
+    (
+        "planner.extended_statistics_not_analyzed",
+        "Extended statistics {statistics} on {table} have never been analyzed, so the planner \
+         still treats their columns as independent and the object only costs ANALYZE time. CREATE \
+         STATISTICS does not collect anything itself: run ANALYZE {table}, and check that \
+         autovacuum analyzes the table afterwards.",
+    ),
+    // Autovacuum
+    (
+        "autovacuum.max_workers",
+        "autovacuum_max_workers is too low. Default of 3 is often insufficient for \
+         servers with many active databases and tables. Increasing to 5 allows more \
+         parallel autovacuum processes to keep up with write-heavy workloads.",
+    ),
+    (
+        "autovacuum.naptime",
+        "autovacuum_naptime controls how often the launcher checks for work. \
+         Lowering it to 30s makes autovacuum more responsive on high-churn systems, \
+         especially those with many databases and tables.",
+    ),
+    (
+        "autovacuum.vacuum_cost_limit",
+        "autovacuum_vacuum_cost_limit is too low. Default 200 is so aggressive that \
+         autovacuum sleeps after processing only 10 dirty pages. This prevents autovacuum \
+         from keeping up with write-intensive workloads. Increase to 2000 (10x default) \
+         to allow workers to do more work before sleeping.",
+    ),
+    (
+        "autovacuum.work_mem.inherited",
+        "autovacuum_work_mem is -1 (inheriting maintenance_work_mem). \
+         If maintenance_work_mem is set to a large value (e.g., 2GB), \
+         and autovacuum_max_workers is 3 or more, the system could suddenly \
+         allocate several GB of RAM for routine autovacuum. Always set autovacuum_work_mem \
+         explicitly to decouple it from manual maintenance values.",
+    ),
+    (
+        "autovacuum.work_mem.low",
+        "autovacuum_work_mem can be increased to 512MB per worker for better \
+         vacuum performance. This is safe because autovacuum operations are infrequent \
+         compared to normal queries.",
+    ),
+    (
+        "autovacuum.vacuum_scale_factor",
+        "autovacuum_vacuum_scale_factor is too high at {current} (20% default). \
+         This causes autovacuum to wait for 200 million dead tuples on a 1-billion row table \
+         before starting. For large tables, autovacuum_vacuum_scale_factor should be 0 \
+         and autovacuum_vacuum_threshold should be set to a fixed value (e.g., 10,000). \
+         This can be done per-table: \
+         ALTER TABLE my_large_table SET (autovacuum_vacuum_scale_factor = 0, autovacuum_vacuum_threshold = 10000);",
+    ),
+    (
+        "autovacuum.idle_cursor_transaction",
+        "Session {session} has been idle in transaction for {idle} after declaring or reading a \
+         cursor. The open transaction's snapshot holds the xmin horizon {xmin_age} transactions \
+         back, so VACUUM cannot remove any tuple deleted or updated since, in any table of the \
+         database. Close the cursor and end the transaction in the application, fetch in batches \
+         across short transactions, and set idle_in_transaction_session_timeout as a backstop.",
+    ),
+    (
+        "autovacuum.idle_cursor_transaction.bloat",
+        "Tables already flagged for dead tuples: {tables}.",
+    ),
+    (
+        "autovacuum.idle_cursor_transaction.tables",
+        "The cursor reads {tables}.",
+    ),
+    (
+        "autovacuum.held_cursor",
+        "Session {session} last used a WITH HOLD cursor {idle} ago and has not closed it. A held \
+         cursor no longer pins a snapshot, but its whole result was materialized at commit and \
+         stays in the backend's memory or temporary files until CLOSE or disconnect. Close \
+         cursors once the client has read them; connection poolers in transaction mode also lose \
+         track of them.",
+    ),
+    (
+        "autovacuum.held_cursor.tables",
+        "The cursor was declared over {tables}.",
+    ),
+    // Logging
+    (
+        "logging.log_min_duration_statement.disabled",
+        "log_min_duration_statement is disabled. This is the primary tool for finding \
+         slow queries. Set to 1000 (1 second) to log all queries taking 1 second or longer.",
+    ),
+    (
+        "logging.log_min_duration_statement.high",
+        "log_min_duration_statement is set quite high ({current_ms}ms). For most workloads, \
+         1000ms (1 second) is a good starting point to identify slow queries without \
+         excessive log noise.",
+    ),
+    (
+        "logging.log_min_duration_statement.all",
+        "log_min_duration_statement is logging ALL queries. This may generate \
+         excessive logs. For most workloads, 1000ms (1 second) is sufficient to \
+         identify slow queries.",
+    ),
+    (
+        "logging.log_lock_waits",
+        "log_lock_waits is disabled. This is invaluable for diagnosing \
+         application-level concurrency and contention issues. Enable it to log \
+         any session that waits for a lock longer than deadlock_timeout.",
+    ),
+    (
+        "logging.deadlock_timeout",
+        "deadlock_timeout is set higher than the default 1s. While this may reduce \
+         false positives in lock wait logging, it also means deadlock detection \
+         takes longer. The default 1s is typically sufficient for most workloads.",
+    ),
+    (
+        "logging.log_destination",
+        "log_destination writes no machine-readable format. Add {format} so statements, errors and \
+         their context can be parsed reliably by log mining (postgreat logs) and log pipelines, \
+         whatever the log_line_prefix.",
+    ),
+    (
+        "logging.logging_collector",
+        "logging_collector is off, so csvlog and jsonlog are not written and PostgreSQL does not \
+         rotate its log files. Turning it on needs a restart.",
+    ),
+    (
+        "logging.log_filename.short",
+        "log_filename repeats every {cycle}, so rotation overwrites logs younger than the {retention} \
+         days to keep. The suggested pattern repeats only after {retention} days or more.",
+    ),
+    (
+        "logging.log_filename.unbounded",
+        "log_filename never repeats, so log files accumulate until something outside PostgreSQL \
+         prunes log_directory. A pattern that repeats, with log_truncate_on_rotation on, keeps \
+         {retention} days at a bounded size.",
+    ),
+    (
+        "logging.log_truncate_on_rotation",
+        "log_truncate_on_rotation is off, so a reused log file name is appended to and mixes \
+         several periods of logs in one ever-growing file. Turn it on to overwrite the old file.",
+    ),
+    (
+        "logging.log_rotation_age",
+        "log_rotation_age and log_rotation_size are both 0, so the log file is never rotated and \
+         grows without bound. Rotate daily.",
+    ),
+    (
+        "logging.pgaudit_volume",
+        "pgaudit.log would write about {volume} of audit log a day at the current {statements} \
+         statements per second ({classes}), enough to fill log volumes and slow every statement \
+         on log I/O. The suggested classes cut it to about {remaining}; audit reads of sensitive \
+         tables with object auditing (pgaudit.role) instead of the read class.",
+    ),
+    (
+        "logging.log_statement_sample_rate",
+        "Duration logging writes about {volume} ({lines} lines) of server log an hour at the \
+         current statement rates, more than most log pipelines absorb. Keep \
+         log_min_duration_statement = {min_duration}ms for the slowest statements and sample the \
+         rest with log_min_duration_sample = {sample_min_duration}ms and \
+         log_statement_sample_rate = {rate}.",
+    ),
+    (
+        "logging.log_statement",
+        "log_statement writes about {volume} ({lines} lines) of server log an hour at the current \
+         statement rates, more than most log pipelines absorb. Log only ddl; where every statement \
+         is needed, sample it with log_min_duration_sample = 0 and log_statement_sample_rate = \
+         {rate}.",
+    ),
+    (
+        "logging.log_connections",
+        "Connection logging writes about {volume} ({lines} lines) of server log an hour for \
+         {sessions} new sessions a second. Pool connections to cut the churn, or turn \
+         log_connections off if the connection lines are not needed.",
+    ),
+    // Extensions
+    (
+        "extensions.timescaledb_background_workers",
+        "timescaledb.max_background_workers ({current}) does not cover the {jobs} scheduled \
+         TimescaleDB jobs plus the database's scheduler, so compression, retention and continuous \
+         aggregate jobs wait for each other or miss their schedule. Raise it to {recommended}.",
+    ),
+    (
+        "extensions.timescaledb_worker_processes",
+        "TimescaleDB needs max_worker_processes of at least timescaledb.max_background_workers \
+         ({background_workers}) + max_parallel_workers ({parallel_workers}) + 3 = {recommended}; \
+         below that, background jobs and parallel queries compete for the same worker slots.",
+    ),
+    (
+        "extensions.citus_shard_count",
+        "citus.shard_count ({current}) gives new distributed tables fewer than {per_worker} shards \
+         on each of the {workers} workers, so their queries cannot use every worker. Use \
+         {recommended}; existing tables keep their shard count until changed with \
+         alter_distributed_table().",
+    ),
+    (
+        "extensions.citus_executor_pool_size",
+        "With citus.max_adaptive_executor_pool_size at {current}, {peak} sessions can open up to \
+         {total} connections to each worker, more than max_connections ({max_connections}) if the \
+         workers are sized like this node. Lower it to {recommended} so multi-shard queries queue \
+         on the coordinator instead of failing on the workers.",
+    ),
+    (
+        "extensions.pgvector_missing_index",
+        "{table}.{column} holds ~{rows} vectors without an HNSW or IVFFlat index, so every \
+         nearest-neighbour query compares the query vector with every row. Build one with \
+         {ddl}; the operator class must match the distance operator the queries use ({operator}).",
+    ),
+    (
+        "extensions.pgvector_missing_index.unverified",
+        "pg_stat_statements could not be read, so it is not known how often the column is \
+         queried by distance.",
+    ),
+    (
+        "extensions.pgvector_ivfflat_lists",
+        "{index} was built with {current} lists for ~{rows} rows; pgvector recommends rows / 1000 \
+         up to a million rows and sqrt(rows) above, about {recommended}. Too few lists make each \
+         probe scan a large share of the table, too many lower recall at the same probes. lists \
+         is fixed when the index is built, so recreate it WITH (lists = {recommended}).",
+    ),
+    (
+        "extensions.pgvector_ivfflat_probes",
+        "ivfflat.probes is {current} while the largest IVFFlat index has {lists} lists, so queries \
+         search only a small share of them and miss close matches. Start at sqrt(lists) = \
+         {recommended} and raise it until recall is acceptable.",
+    ),
+    (
+        "extensions.pgvector_hnsw_ef_search",
+        "hnsw.ef_search is {current} while {table} has ~{rows} rows under an HNSW index. Larger \
+         graphs need a longer candidate list to keep recall, and a query never returns more \
+         rows than ef_search; use at least {recommended}.",
+    ),
+    (
+        "extensions.pgvector_build_memory",
+        "The HNSW graph for {table} is about {size}, more than maintenance_work_mem ({current}). \
+         Builds that do not fit in memory are many times slower; set maintenance_work_mem to \
+         {recommended} in the session that builds or reindexes it, or server-wide if other \
+         maintenance can share that much memory.",
+    ),
+    // Server version
+    (
+        "version.minor_release",
+        "PostgreSQL {current} is {behind} minor release(s) behind {latest}, the newest {major}.x \
+         release. Minor releases only contain bug and security fixes, keep the on-disk format and \
+         are applied by installing the new binaries and restarting.",
+    ),
+    (
+        "version.eol_major",
+        "PostgreSQL {major} reached end of life on {eol} and no longer receives bug or security \
+         fixes. Plan an upgrade to a supported major version such as {newest} with pg_upgrade or \
+         logical replication.",
+    ),
+    // Host and storage layout
+    (
+        "host.wal_volume",
+        "{path} is on the same volume as the data directory, so WAL fsyncs queue behind data file \
+         writes and a full data volume also stops WAL. On bare metal or a VM, put pg_wal on its own \
+         volume (initdb --waldir, or move it and leave a symlink while the server is stopped).",
+    ),
+    (
+        "host.temp_tablespace_volume",
+        "Temp tablespace {name} is on the data volume, so sorts and hashes that spill to disk \
+         compete with data and WAL I/O. Point temp_tablespaces at a tablespace on a separate, \
+         ideally local, volume.",
+    ),
+    (
+        "host.filesystem",
+        "{mount} is a {fs} filesystem. Its contents do not survive a reboot or container \
+         replacement, or each fsync makes a network round trip; keep PostgreSQL files on a local \
+         xfs or ext4 volume.",
+    ),
+    (
+        "host.mount_options",
+        "{mount} is mounted without noatime, so reads also update inode access times. Mount it \
+         with noatime; PostgreSQL never reads access times.",
+    ),
+    // Operating system
+    (
+        "operating_system.open_files",
+        "The server may open only {current} files per process, fewer than max_connections + \
+         max_files_per_process ({needed}), so busy backends close and reopen files or fail with \
+         \"too many open files\". Raise LimitNOFILE for the service and restart it.",
+    ),
+    (
+        "operating_system.overcommit_memory",
+        "vm.overcommit_memory is {current}, so when memory runs out the OOM killer terminates a \
+         process, possibly the postmaster, which restarts every connection. With 2, allocations \
+         fail with an error instead; check that vm.overcommit_ratio leaves room for \
+         shared_buffers and the backends.",
+    ),
+    (
+        "operating_system.swappiness",
+        "vm.swappiness is {current}, so the kernel swaps out shared_buffers and backend memory \
+         instead of dropping page cache. Set vm.swappiness = 1.",
+    ),
+    (
+        "operating_system.transparent_hugepage",
+        "Transparent huge pages are set to always, which causes latency spikes from background \
+         memory compaction and inflates backend memory. Set them to never (add \
+         transparent_hugepage=never to the kernel command line to keep it after a reboot) and use \
+         huge_pages for shared memory.",
+    ),
+    (
+        "operating_system.zone_reclaim_mode",
+        "vm.zone_reclaim_mode is {current} on a host with {nodes} NUMA nodes, so the kernel evicts \
+         page cache on the local node rather than allocate from another one, and reads that \
+         should hit cache go to disk. Set it to 0.",
+    ),
+    (
+        "operating_system.numa_balancing",
+        "Automatic NUMA balancing is on across {nodes} NUMA nodes. Every backend touches \
+         shared_buffers, so the kernel keeps migrating its pages and stalls the processes using \
+         them. Set kernel.numa_balancing = 0 and start the postmaster under numactl \
+         --interleave=all so shared memory is spread evenly across the nodes.",
+    ),
+    // Backup and recovery
+    (
+        "backup.recency.none",
+        "No successful base backup was found: no base backup history file is left in pg_wal and \
+         no backup check command reported one. Archived WAL cannot be restored without a base \
+         backup to replay it onto. Without a backup within {rpo}, a lost volume or a bad migration cannot be recovered within the \
+         RPO. Verify that backups run and succeed.",
+    ),
+    (
+        "backup.recency.stale",
+        "The newest successful backup is {age} old ({source}, {at}), beyond the {rpo} RPO. \
+         Check that backups and WAL archiving are still running and succeeding.",
+    ),
+    (
+        "backup.archive_mode",
+        "archive_mode is off, so no WAL is archived and a base backup can only be restored to the \
+         moment it finished. Turn archive_mode on (a restart) and set archive_command or \
+         archive_library so the server can be recovered to any point in time.",
+    ),
+    (
+        "backup.archive_command",
+        "archive_mode is on but neither archive_command nor archive_library is set. The server \
+         keeps every WAL segment waiting for the archiver, so pg_wal grows until the disk fills, \
+         and nothing is archived for PITR. Set an archive_command that copies each segment to \
+         durable storage and fails if the copy does.",
+    ),
+    (
+        "backup.restore_command",
+        "Standby {standby} has no restore_command. If it falls behind past the WAL the primary \
+         keeps, it cannot fetch the missing segments from the archive and must be rebuilt from a \
+         new base backup.",
+    ),
+    (
+        "backup.lost_slot",
+        "Replication slot {slot} has lost WAL its consumer still needs, so the consumer cannot \
+         resume streaming from it. Rebuild the standby or subscriber and drop the slot so it no \
+         longer reports as lost.",
+    ),
+    (
+        "backup.max_slot_wal_keep_size",
+        "max_slot_wal_keep_size is unlimited with {slots} replication slots. A consumer that \
+         disconnects keeps its slot holding WAL until pg_wal fills the disk and the primary \
+         stops. Cap retained WAL at {suggested} so a stalled slot is invalidated instead.",
+    ),
+    (
+        "backup.max_slot_wal_keep_size.inactive",
+        "Inactive replication slot {slot} already holds back {retained} of WAL and \
+         max_slot_wal_keep_size is unlimited, so it will keep growing until pg_wal fills the \
+         disk. Cap retained WAL at {suggested} and drop the slot if its consumer is gone.",
+    ),
+    (
+        "backup.wal_keep_size",
+        "Standbys {standbys} stream without a replication slot or restore_command and \
+         wal_keep_size is 0, so any lag past a checkpoint loses WAL they still need. Keep \
+         {suggested} of WAL, or give them a slot or restore_command.",
+    ),
+    // Replication and resilience
+    (
+        "replication.tcp_keepalives_idle",
+        "With the current keepalive settings the server notices a client that vanished, such as \
+         an application host cut off by a failover or network partition, only after {detection}. \
+         Until then its backend keeps holding locks, an open transaction and a connection slot. \
+         Send the first probe after 60 seconds of silence; with tcp_keepalives_interval 10 and \
+         tcp_keepalives_count 6 dead connections are dropped after {suggested}.",
+    ),
+    (
+        "replication.tcp_keepalives_interval",
+        "Unanswered keepalive probes are retried slowly, so a dead client connection is dropped \
+         only after {detection} in total. Probing every 10 seconds, with tcp_keepalives_idle 60 \
+         and tcp_keepalives_count 6, brings that down to {suggested}.",
+    ),
+    (
+        "replication.tcp_keepalives_count",
+        "The server waits for many unanswered keepalive probes, so a dead client connection is \
+         dropped only after {detection} in total. Giving up after 6 probes, with \
+         tcp_keepalives_idle 60 and tcp_keepalives_interval 10, brings that down to {suggested}.",
+    ),
+    (
+        "replication.wal_receiver_timeout",
+        "wal_receiver_timeout is {timeout}, so a standby takes that long to notice a silent \
+         primary and reconnect to the promoted one. 60s keeps failovers short.",
+    ),
+    (
+        "replication.wal_receiver_timeout.disabled",
+        "wal_receiver_timeout is 0, so a standby whose primary fails without closing the \
+         connection waits on it until TCP gives up, often for hours, instead of reconnecting to \
+         the new primary or falling back to the archive. Use 60s.",
+    ),
+    (
+        "replication.wal_sender_timeout",
+        "wal_sender_timeout is {timeout}, so the replication connection of a standby that \
+         vanished, and the slot it keeps active, linger that long. 60s releases them quickly.",
+    ),
+    (
+        "replication.wal_sender_timeout.disabled",
+        "wal_sender_timeout is 0, so the WAL sender of a standby that vanished is never ended: \
+         its slot stays active and keeps holding back WAL, and the standby cannot reattach to the \
+         slot until TCP gives up. Use 60s.",
+    ),
+    (
+        "replication.restart_after_crash",
+        "restart_after_crash is on on a server with standbys. If a cluster manager such as \
+         Patroni, pg_auto_failover or Pacemaker handles failover, the postmaster restarting \
+         itself after a crash races the promotion of a standby; turn it off so the cluster \
+         manager decides. Leave it on when no cluster manager runs.",
+    ),
+    // Security
+    (
+        "security.policies_without_rls",
+        "{table} has {policies} row-level security policies, but row-level security is not \
+         enabled on it, so the policies are ignored and every role with table privileges sees and \
+         changes every row. Run ALTER TABLE {table} ENABLE ROW LEVEL SECURITY if the policies are \
+         meant to isolate tenants.",
+    ),
+    (
+        "security.rls_without_policies",
+        "{table} has row-level security enabled but no policies, so every role other than its \
+         owner {owner}, superusers and BYPASSRLS roles gets no rows and cannot write any. Create \
+         policies for the roles that use the table, or disable row-level security.",
+    ),
+    (
+        "security.rls_without_policies.forced",
+        "{table} forces row-level security but has no policies, so every role, including its \
+         owner {owner}, gets no rows and cannot write any; only superusers and BYPASSRLS roles \
+         see the data. Create policies for the roles that use the table.",
+    ),
+    (
+        "security.rls_not_forced",
+        "Row-level security on {table} is not forced, so its owner {owner} bypasses the policies. \
+         When the application connects as the owner, tenants are not isolated at all. Run ALTER \
+         TABLE {table} FORCE ROW LEVEL SECURITY, or connect the application as a role that does \
+         not own the table.",
+    ),
+    (
+        "security.permissive_policy",
+        "Permissive policy {policy} on {table} allows {command} on every row for {roles}. \
+         Permissive policies are OR-ed together, so this one overrides any tenant filter for \
+         those roles. Restrict its expression, make it RESTRICTIVE, or limit it to an \
+         administrative role.",
+    ),
+    (
+        "security.row_security",
+        "row_security is off while {tables} tables rely on row-level security. Queries that a \
+         policy would filter fail with an error instead of returning the visible rows. Leave it \
+         off only for sessions such as pg_dump that must see everything or fail.",
+    ),
+    (
+        "security.public_schema_create",
+        "Every role may create objects in the public schema, which search_path ({search_path}) \
+         searches. Unqualified CREATE statements land there whenever no earlier schema exists, \
+         and any role can plant a table, function or operator that shadows the one other \
+         sessions' unqualified names resolve to (CVE-2018-1058). Run REVOKE CREATE ON SCHEMA \
+         public FROM PUBLIC and grant CREATE only to the roles that own application objects.",
+    ),
+    // Schema design
+    (
+        "schema.varchar_255",
+        "{count} columns in {tables} tables are varchar(255), e.g. {examples}. In PostgreSQL text \
+         and varchar are stored alike and 255 is a habit carried over from other databases rather \
+         than a limit the data needs; it only rejects longer values at runtime. Use text, with a \
+         CHECK constraint where a real length limit exists.",
+    ),
+    (
+        "schema.timestamp_without_time_zone",
+        "{count} columns in {tables} tables are timestamp without time zone, e.g. {examples}. \
+         They keep whatever wall-clock time the client sent, so values written from sessions in \
+         different time zones, or across a daylight saving change, cannot be compared. Use \
+         timestamptz, which stores an absolute instant and converts it to the session's TimeZone \
+         on output.",
+    ),
+    (
+        "schema.money",
+        "{count} columns in {tables} tables use the money type, e.g. {examples}. Its precision \
+         and formatting follow lc_monetary, so a dump restored under another locale misreads the \
+         values, and it records no currency. Use numeric, with a currency column where amounts \
+         can differ in currency.",
+    ),
+    (
+        "schema.missing_primary_key",
+        "{count} tables have no primary key, e.g. {examples}. Duplicate rows cannot be told \
+         apart, logical replication rejects their UPDATEs and DELETEs unless REPLICA IDENTITY \
+         FULL is set, and tools such as pg_repack refuse them. Add a primary key, or use a unique \
+         index on NOT NULL columns as the replica identity.",
+    ),
+    (
+        "schema.unindexed_nullable_fk",
+        "{count} foreign keys on nullable columns have no index leading with their columns, e.g. \
+         {examples}. Every DELETE or key UPDATE on the referenced table scans the referencing \
+         table to enforce the key, and joins along it cannot use an index. Index the referencing \
+         columns; a partial index WHERE the column IS NOT NULL leaves out the rows without a \
+         reference.",
+    ),
+    (
+        "schema.not_valid_constraint",
+        "{kind} constraint {constraint} on {table} was added NOT VALID and never validated. Rows \
+         written since are checked, but existing rows may violate it, and the planner does not \
+         rely on an unvalidated constraint, e.g. to exclude tables or partitions by a CHECK. Run \
+         ALTER TABLE {table} VALIDATE CONSTRAINT {constraint}; it takes a SHARE UPDATE EXCLUSIVE \
+         lock, so reads and writes continue while it scans.",
+    ),
+    (
+        "schema.disabled_trigger",
+        "Trigger {trigger} on {table} is disabled, so whatever it maintains, such as audit rows, \
+         derived columns or changes to other tables, silently stops for every write. If it was \
+         disabled for a bulk load, run ALTER TABLE {table} ENABLE TRIGGER {trigger} and backfill \
+         what it missed; if it is no longer needed, drop it.",
+    ),
+    (
+        "schema.disabled_fk_trigger",
+        "The triggers enforcing foreign key {constraint} on {table} are disabled, typically by \
+         ALTER TABLE ... DISABLE TRIGGER ALL, so rows violating the key are accepted while the \
+         constraint still looks valid. Run ALTER TABLE ... ENABLE TRIGGER ALL on both tables and \
+         look for orphaned rows, since enabling the triggers does not recheck existing rows.",
+    ),
+    (
+        "table_index.orphaned_temp_schema",
+        "{schema} holds {tables} temp tables ({size}) but no backend is using its slot, so they \
+         were left behind by a session that crashed or was killed. They take disk space no table \
+         list shows, and because autovacuum cannot vacuum temp tables, their age of {xid_age} \
+         transactions holds back the database's datfrozenxid. Autovacuum drops orphaned temp \
+         tables on its next pass over the database; if they persist, drop them as a superuser, \
+         largest first: {drops}",
+    ),
+    (
+        "table_index.orphaned_temp_schema.autovacuum_off",
+        "{schema} holds {tables} temp tables ({size}) but no backend is using its slot, so they \
+         were left behind by a session that crashed or was killed. They take disk space no table \
+         list shows, and because autovacuum cannot vacuum temp tables, their age of {xid_age} \
+         transactions holds back the database's datfrozenxid. As autovacuum is off, and it is \
+         what drops orphaned temp tables, they stay until a superuser drops them, largest first: \
+         {drops}",
+    ),
+    // Table and index health
+    (
+        "table_index.toast_compression",
+        "{table} holds {toast} of TOAST data and writes {writes_per_row} rows per live row, \
+         all compressed with pglz. lz4 compresses several times faster and decompresses about \
+         twice as fast, for roughly 10% more storage (about {extra} more here). New values use \
+         lz4 after {ddl}; existing ones keep pglz until the table is rewritten, e.g. by \
+         VACUUM FULL or pg_repack.",
+    ),
+    (
+        "table_index.default_toast_compression",
+        "default_toast_compression is pglz while {tables} write-heavy table(s) with large TOAST \
+         data would benefit from lz4. With lz4 as the default, new columns and tables get it \
+         without a per-column ALTER.",
+    ),
+    (
+        "table_index.bloat.stale_autovacuum",
+        "{table} has {dead_pct}% dead tuples but its last autovacuum ran {last_autovacuum}. \
+         This indicates autovacuum tuning is not keeping up; increase per-table autovacuum \
+         aggressiveness (lower scale factor/threshold) or schedule a manual VACUUM to prune bloat.",
+    ),
+    (
+        "table_index.bloat.never_autovacuumed",
+        "{table} has {dead_pct}% dead tuples and no recorded autovacuum. \
+         This indicates autovacuum tuning is not keeping up; increase per-table autovacuum \
+         aggressiveness (lower scale factor/threshold) or schedule a manual VACUUM to prune bloat.",
+    ),
+    (
+        "table_index.bloat.recent_autovacuum",
+        "{table} shows {dead_pct}% dead tuples even after a recent autovacuum. \
+         High-churn workloads may need more aggressive autovacuum settings or targeted \
+         VACUUM (FULL) during low-traffic windows.",
+    ),
+    (
+        "table_index.seq_scan_hotspot",
+        "{table} has {seq_scans} sequential scans vs {idx_scans} index scans on ~{rows} rows ({size}). \
+         This matches the guidance from docs/6: filter-heavy queries are falling back to seq scans \
+         on sizable tables. Investigate pg_stat_statements for the offending queries and create \
+         composite/partial indexes to cover their predicates.",
+    ),
+    (
+        "table_index.unused_index",
+        "{index} has never been scanned and is not enforcing a constraint. \
+         Dropping it reclaims {size} and removes write overhead, per docs/6 guidance.",
+    ),
+    (
+        "table_index.unused_index.writes_per_day",
+        "It absorbs ~{writes} writes/day (inserts and non-HOT updates on {table}) for zero reads.",
+    ),
+    (
+        "table_index.unused_index.writes_since_reset",
+        "It has absorbed {writes} writes (inserts and non-HOT updates on {table}) \
+         since statistics were reset, for zero reads.",
+    ),
+    (
+        "table_index.low_selectivity",
+        "{index} returns ~{pct}% of {table} on each scan ({tuples} tuples/read). \
+         This low selectivity means the planner touches a large fraction of the table; \
+         redesign the index per docs/6 section C.2.",
+    ),
+    (
+        "table_index.failed_index_only",
+        "{index} performs index scans but still fetches heap pages {pct}% of the time. \
+         Either add the missing SELECT columns via INCLUDE or VACUUM to refresh the visibility map \
+         so index-only scans can succeed, per docs/6 section C.3.",
+    ),
+    (
+        "table_index.missing_partial_index",
+        "Table {table} has a soft-delete column but lacks a partial index. \
+         Add 'WHERE is_deleted = false' (or deleted_at IS NULL) to exclude dead rows from the index, \
+         reducing size and maintenance overhead.",
+    ),
+    (
+        "table_index.brin_candidate",
+        "Table {table} is large and physically ordered by {column}. A BRIN index would be \
+         100x smaller than a B-Tree while maintaining scan performance for range queries.",
+    ),
+    (
+        "table_index.missing_spatial_index",
+        "Table {table} has ~{rows} rows but no GiST, SP-GiST or BRIN index on its {column} \
+         column, so every ST_Intersects, ST_DWithin or && filter on it reads the whole table. \
+         Create one with CREATE INDEX CONCURRENTLY ... USING gist ({column}).",
+    ),
+    // Notes appended by shared passes
+    ("note.statistics_window", "Statistics cover {window} (since {since})."),
+    (
+        "note.sample_window",
+        "Scan counts cover the {window} sample window; index usage covers {stats_window} \
+         (since {since}).",
+    ),
+    ("note.also_flagged", "Also flagged by {category}:"),
+    ("note.suggested_instead", "{category} suggested {value} instead:"),
+    (
+        "note.clamped_min",
+        "Clamped to {value}, the server's minimum for {parameter} (the heuristic suggested {suggested}).",
+    ),
+    (
+        "note.clamped_max",
+        "Clamped to {value}, the server's maximum for {parameter} (the heuristic suggested {suggested}).",
+    ),
+    (
+        "note.clamped_32bit",
+        "Clamped to {value}, the practical limit for a 32-bit build for {parameter} \
+         (the heuristic suggested {suggested}).",
+    ),
+    (
+        "note.checkpoint_interval",
+        "At the measured WAL rate of {wal_rate}, a checkpoint runs every ~{before}.",
+    ),
+    (
+        "note.checkpoint_interval_change",
+        "At the measured WAL rate of {wal_rate}, checkpoints run every ~{before} today \
+         → ~{after} with the suggested WAL settings.",
+    ),
+    (
+        "note.full_page_writes",
+        "Full-page images are ~{share}% of WAL; the longer interval cuts WAL volume by up to ~{savings}%.",
+    ),
+    ("note.severity_override", "Severity set to {level} by the rules file (the check reported {original})."),
+    ("note.pending_reload", "{value} is already staged in {file} and waits for a configuration reload."),
+    ("note.pending_restart", "{value} is already staged in {file} and waits for a restart."),
+    (
+        "note.large_server_parallel",
+        "On a {vcpu}-vCPU server, which likely spans several NUMA nodes, workers on remote nodes \
+         add more memory traffic than speed, so PostGreat caps the target at {target}.",
+    ),
+    ("note.connection_peak_hours", "Connections peak during {hours}."),
+    (
+        "note.observed_connection_peak",
+        "Sampled connections peaked at {peak} connections ({pct}% of max_connections) across \
+         {samples} samples from {first} to {last}; the target keeps 25% headroom over that peak.",
+    ),
+    (
+        "note.log_volume_at_recommended",
+        "At the current statement rates this would log about {volume} ({lines} lines) an hour; \
+         to stay under 1GB, set it to {min_duration}ms and sample the rest with \
+         log_min_duration_sample = {sample_min_duration}ms and log_statement_sample_rate = {rate}.",
+    ),
+];
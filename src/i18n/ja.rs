@@ -0,0 +1,996 @@
+pub(super) static MESSAGES: &[(&str, &str)] = &[
+    ("text", "{text}"),
+    // Memory
+    (
+        "memory.shared_buffers",
+        "shared_buffers は総 RAM ({memory_gb}GB) の約 25% に設定してください。\
+         パフォーマンスに最も大きく影響するパラメータです。",
+    ),
+    (
+        "memory.effective_cache_size",
+        "effective_cache_size はクエリプランナのコストモデルへのヒントです。\
+         RAM の約 75% に設定すると、プランナがシーケンシャルスキャンよりインデックススキャンを\
+         選びやすくなります。デフォルト値は危険なほど低く設定されています。",
+    ),
+    (
+        "memory.work_mem.too_high",
+        "work_mem が {current_mb}MB と危険なほど高く設定されています。この値は同時実行される\
+         操作数と接続数で乗算されます。接続数が多い環境で work_mem を高くすることは、\
+         OOM エラーの最も一般的な原因です。このシステムとワークロード種別では約 \
+         {recommended_mb}MB を推奨します。",
+    ),
+    (
+        "memory.work_mem.too_low",
+        "work_mem が低すぎるため、ソートやハッシュ結合がディスクに書き出される可能性があります。\
+         EXPLAIN (ANALYZE) で 'external merge Disk' や 'spill' が出ていないか確認してください。",
+    ),
+    (
+        "memory.work_mem.too_low_analytics",
+        "分析ワークロードに対して work_mem が低すぎます。大きなソート、集約、ハッシュ結合が\
+         一時ファイルに書き出されます。同時接続数の少ないデータウェアハウスでは各操作に数百 MB \
+         を割り当てても問題ありません。EXPLAIN (ANALYZE) と log_temp_files で確認してください。",
+    ),
+    (
+        "memory.work_mem.temp_spills",
+        "直近 {window} でクエリが {temp_files} 個の一時ファイルに {temp_gb}GB を書き出しており、\
+         ソートやハッシュが work_mem を超えています。{recommended_mb}MB に向けて引き上げるか、\
+         該当クエリを実行するロールに限って引き上げてください。log_temp_files で特定できます。",
+    ),
+    (
+        "memory.maintenance_work_mem",
+        "このシステム ({vcpu}、RAM {memory_gb}GB) では maintenance_work_mem を約 \
+         {recommended_mb}MB に設定してください。大きな値にするとインデックス作成や \
+         VACUUM が大幅に高速化されます。",
+    ),
+    (
+        "memory.wal_buffers",
+        "書き込みの多いワークロードでは wal_buffers を 16MB に設定してください。\
+         デフォルトの -1 (自動調整) で通常は十分ですが、16MB に固定すると書き込み性能が\
+         向上する場合があります。",
+    ),
+    (
+        "memory.huge_pages",
+        "{vcpu} vCPU のサーバーで huge_pages が {current} です。これだけ多くのバックエンドが \
+         shared_buffers を 4kB ページでマップすると、ページテーブルだけで数 GB を消費し、TLB \
+         ミスがすべてのバッファアクセスを遅くします。vm.nr_hugepages = {pages} を確保して \
+         huge_pages = on に設定し、通常ページへ黙ってフォールバックせず起動を拒否するように\
+         してください。",
+    ),
+    (
+        "memory.hash_mem_multiplier.too_low",
+        "hash_mem_multiplier が {current} のため、ハッシュ結合とハッシュ集約はソートと同じ work_mem で\
+         ディスクに書き出されますが、その際の速度低下はソートよりはるかに大きくなります。\
+         PostgreSQL 15 でデフォルトは 2.0 に引き上げられました。",
+    ),
+    (
+        "memory.hash_mem_multiplier.too_high",
+        "hash_mem_multiplier が {current} のため、ハッシュ結合やハッシュ集約はそれぞれ {hash_mb}MB \
+         まで使用でき、このプロファイルが安全とみなす work_mem の 2 倍を超えます。この値はすべての\
+         接続とパラレルワーカーのハッシュノードごとに掛かります。必要なロールだけ work_mem を\
+         上げてください。",
+    ),
+    (
+        "memory.temp_buffers",
+        "temp_buffers × max_connections ({max_connections}) はサーバーの {memory_gb}GB のうち \
+         {total_gb}GB になります。各セッションは一時テーブルで埋めたバッファを切断まで保持するため、\
+         長寿命のプール接続はそれを無期限に抱えます。全体では下げ、一時テーブルを多用する\
+         セッションでのみ上げてください。",
+    ),
+    (
+        "memory.logical_decoding_work_mem.too_high",
+        "logical_decoding_work_mem × max_wal_senders ({wal_senders}) はサーバーの {memory_gb}GB の\
+         うち {total_gb}GB になります。論理スロットをデコードする各 WAL センダーは、pg_replslot に\
+         書き出す前にこれだけバッファできます。",
+    ),
+    (
+        "memory.logical_decoding_work_mem.too_low",
+        "wal_level は logical ですが logical_decoding_work_mem が {recommended_mb}MB 未満です。\
+         大きなトランザクションはデコード中に pg_replslot へ書き出され、サブスクライバーを遅らせ\
+         I/O を増やします。このサーバーにはより多くをメモリに保持する余裕があります。",
+    ),
+    // Concurrency
+    (
+        "concurrency.max_connections.too_high",
+        "max_connections が {current} と危険なほど高く設定されています。各接続はメモリと CPU を\
+         消費します。ベストプラクティスはコネクションプーラ (PgBouncer) を使用し、\
+         max_connections を約 4 * vCPU (このシステムでは {recommended}) に設定することです。\
+         このパラメータはよくある落とし穴です。",
+    ),
+    (
+        "concurrency.max_connections.high",
+        "max_connections は比較的低く保つべきです。接続管理を改善するためにコネクションプーラの\
+         利用を検討してください。",
+    ),
+    (
+        "concurrency.max_connections.saturated",
+        "計測した接続数は {at} に {peak} connections に達し、max_connections ({current}) の \
+         {pct}% でした ({samples} 回計測)。max_connections を超える接続は 'too many clients' で\
+         拒否されます。サーバーの前段にコネクションプーラ (PgBouncer) を置くか、ピークに対して \
+         25% の余裕を持つよう max_connections を引き上げてください。",
+    ),
+    (
+        "concurrency.max_worker_processes",
+        "max_worker_processes は vCPU 数 ({recommended}) と同じにしてください。\
+         これはすべてのバックグラウンドワーカープロセスの上限です。",
+    ),
+    (
+        "concurrency.max_parallel_workers.too_high",
+        "max_parallel_workers (全クエリ合計) は vCPU 数 ({recommended}) を超えないようにしてください。\
+         これより高いと CPU の競合が発生する可能性があります。",
+    ),
+    (
+        "concurrency.max_parallel_workers.underutilized",
+        "max_parallel_workers が十分に活用されていません。並列クエリの性能を高めるため、\
+         vCPU 数まで引き上げてください。",
+    ),
+    (
+        "concurrency.max_parallel_workers_per_gather.above_vcpu",
+        "max_parallel_workers_per_gather (クエリごと) は vCPU 数 ({vcpu}) を超えないように\
+         してください。{current} に設定すると、1 つのクエリがすべての CPU リソースを占有し、\
+         他の同時実行クエリが処理できなくなる可能性があります。",
+    ),
+    (
+        "concurrency.max_parallel_workers_per_gather.equals_vcpu",
+        "max_parallel_workers_per_gather を vCPU 数と同じにするのは危険です。\
+         1 つの複雑なクエリがすべての並列ワーカーを占有し、他のクエリが処理できなくなる可能性が\
+         あります。暴走クエリの影響を抑えるため、vCPU 数の半分に設定してください。",
+    ),
+    (
+        "concurrency.max_parallel_workers_per_gather.above_profile",
+        "{profile} プロファイルでは、1 つのテナントのクエリが全テナントで共有する CPU を\
+         占有しないよう、1 つのクエリの Gather あたりの並列ワーカーを {recommended} に抑えます。\
+         現在の {current} ではそれ以上使えます。max_parallel_workers_per_gather を \
+         {recommended} に下げてください。",
+    ),
+    (
+        "concurrency.max_parallel_workers_per_gather.underutilized_analytics",
+        "分析ワークロードに対して max_parallel_workers_per_gather が十分に活用されていません。\
+         データウェアハウスでは同時実行クエリが少ないため、各スキャン、結合、集約が vCPU の\
+         約 3/4 (例: {recommended}) を使えるようにすると、長時間のレポートが短縮されます。",
+    ),
+    (
+        "concurrency.max_parallel_workers_per_gather.underutilized",
+        "max_parallel_workers_per_gather が十分に活用されていません。混在ワークロードでは \
+         {recommended} に設定すると、複数の複雑なクエリが互いを妨げることなく完全に並列で\
+         実行できます。",
+    ),
+    (
+        "concurrency.max_parallel_maintenance_workers",
+        "max_parallel_maintenance_workers は手動の VACUUM や CREATE INDEX の並列度を制御します。\
+         {recommended} (vCPU 数の半分) に設定するとメンテナンス作業が大幅に高速化されます。",
+    ),
+    // WAL
+    (
+        "wal.max_wal_size.requested_checkpoints",
+        "サイズ起因のチェックポイント ({requested}) が時間起因のチェックポイント ({timed}) より\
+         多く発生しています。これは I/O の急増と性能低下を招きます。checkpoints_timed が\
+         チェックポイントの主な要因になるまで max_wal_size を大幅に引き上げてください。",
+    ),
+    (
+        "wal.max_wal_size.too_low",
+        "max_wal_size が {current_gb}GB と低すぎます。デフォルト値 (1GB) は書き込みの多い本番\
+         ワークロードには小さすぎ、サイズ起因のチェックポイントが頻発して I/O が急増します。\
+         チェックポイントがサイズではなく時間で発生するよう十分大きな値 (このシステムでは \
+         {recommended_gb}GB) に設定してください。",
+    ),
+    (
+        "wal.min_wal_size",
+        "min_wal_size は WAL ファイルの再利用を制御します。{recommended_gb}GB に設定すると、\
+         書き込みの急増時にも新しいファイルを一から作成せずに済むだけの WAL セグメントが\
+         確保されます。",
+    ),
+    (
+        "wal.checkpoint_timeout",
+        "checkpoint_timeout が短すぎます。タイムアウトが短いとチェックポイントが頻発し、\
+         I/O 負荷が増加します。このワークロードでは {recommended} が適切な妥協点です。",
+    ),
+    (
+        "wal.checkpoint_timeout.analytics",
+        "OLAP やバッチ処理のワークロードでは、長時間の処理中のチェックポイント回数を減らすため、\
+         checkpoint_timeout を 15-30min に引き上げることを検討してください。",
+    ),
+    (
+        "wal.checkpoint_completion_target",
+        "checkpoint_completion_target は I/O を平準化するための設定です。デフォルトの 0.5 では\
+         チェックポイント中に大きな I/O の急増が発生します。0.9 に設定すると同じ量の I/O が\
+         チェックポイント間隔の 90% に分散され、バックグラウンドで滑らかかつ継続的に書き込まれます。",
+    ),
+    // Planner
+    (
+        "planner.random_page_cost.hdd_value_on_ssd",
+        "random_page_cost が HDD 向け (デフォルト 4.0) に設定されていますが、SSD ストレージを\
+         使用しています。SSD ではランダム読み取りがシーケンシャル読み取りとほぼ同じ速さです。\
+         1.1 に設定すると (effective_cache_size を大きくすることと合わせて)、プランナが常に\
+         シーケンシャルスキャンを選ぶのではなくインデックスを使うようになります。\
+         最新のストレージでは必須の設定です。",
+    ),
+    (
+        "planner.random_page_cost.lower_for_ssd",
+        "SSD ストレージでインデックスをより活用するため、random_page_cost を 1.1 に\
+         下げることができます。",
+    ),
+    (
+        "planner.random_page_cost.hdd_value_on_network",
+        "random_page_cost が回転ディスク向けに設定されていますが、ネットワークストレージ \
+         (EBS、Persistent Disk など) を使用しています。これらのボリュームは SSD ベースのため、\
+         ランダム読み取りのコストはシーケンシャル読み取りとほとんど変わらず、ネットワーク遅延は\
+         両方に等しく加わります。1.5 に設定すると、ローカル NVMe ほど楽観的にならずに\
+         プランナがインデックスを使うようになります。",
+    ),
+    (
+        "planner.random_page_cost.too_low_for_network",
+        "ネットワークストレージで random_page_cost がローカル SSD 向けの値より低く設定されています。\
+         ランダム読み取りのたびにネットワークの往復が発生するため、低すぎる値はプランナを\
+         多数の散在ページを読むインデックススキャンに誘導する可能性があります。",
+    ),
+    (
+        "planner.random_page_cost.too_low_for_hdd",
+        "HDD ストレージに対して random_page_cost が低すぎます。回転ディスクではランダム I/O が\
+         シーケンシャル I/O よりはるかに高コストです。4.0 (デフォルト) に引き上げると、\
+         プランナがディスクを酷使するインデックススキャンを楽観的に選ぶのを防げます。",
+    ),
+    (
+        "planner.effective_io_concurrency.fast_storage",
+        "SSD/NVMe およびネットワークストレージでは effective_io_concurrency を 200 に設定して\
+         ください。デフォルトの 1 は単一の HDD を想定しています。最新のストレージは非常に多くの\
+         並列リクエストを処理でき、ビットマップヒープスキャンで高い値の恩恵を受けます。\
+         特にネットワークボリュームでは、リクエストごとの遅延を隠すために深いキューが必要です。",
+    ),
+    (
+        "planner.effective_io_concurrency.hdd",
+        "HDD ストレージに対して effective_io_concurrency が高すぎます。回転ディスクは IOPS と\
+         キュー深度が限られています。高すぎる値 (HDD のデフォルトは 1-2) は過剰なシークを\
+         引き起こす可能性があります。",
+    ),
+    (
+        "planner.maintenance_io_concurrency.fast_storage",
+        "maintenance_io_concurrency は VACUUM などのメンテナンス作業のプリフェッチ深度です。\
+         デフォルトの 10 では SSD やネットワークストレージを活用しきれません。メンテナンス作業が\
+         デバイスを飽和させられるよう effective_io_concurrency と同じ値にしてください。",
+    ),
+    (
+        "planner.maintenance_io_concurrency.hdd",
+        "maintenance_io_concurrency が回転ディスクで処理できる値を超えています。VACUUM 中の深い\
+         プリフェッチキューは余分なシークを生み、フォアグラウンドのクエリと競合します。\
+         HDD ストレージではデフォルトの 10 を維持してください。",
+    ),
+    (
+        "planner.seq_page_cost",
+        "seq_page_cost がデフォルトの 1.0 から変更されています。特別な理由がない限り 1.0 の\
+         ままにし、ストレージ固有の調整には random_page_cost を使用することを推奨します。",
+    ),
+    (
+        "planner.default_statistics_target",
+        "default_statistics_target は ANALYZE が収集するヒストグラムのバケット数と最頻値の数を\
+         決めます。大きく偏りのあるテーブルを複数結合する分析クエリでは、高い値にすると行数推定が\
+         大きく改善します。ANALYZE の時間は長くなりますが、レポート用途では通常許容できます。",
+    ),
+    (
+        "planner.extended_statistics_not_analyzed",
+        "{table}の拡張統計{statistics}は一度も収集されていないため、\
+         プランナは列を独立したものとして扱い続け、\
+         このオブジェクトはANALYZEの時間を増やすだけです。\
+         CREATE STATISTICSは自らは何も収集しません。\
+         ANALYZE {table}を実行し、\
+         その後autovacuumがテーブルを解析していることを確認してください。",
+    ),
+    // Autovacuum
+    (
+        "autovacuum.max_workers",
+        "autovacuum_max_workers が低すぎます。デフォルトの 3 は、アクティブなデータベースや\
+         テーブルが多いサーバーでは不足しがちです。5 に引き上げると、書き込みの多いワークロードに\
+         追従できるよう autovacuum を並列に実行できます。",
+    ),
+    (
+        "autovacuum.naptime",
+        "autovacuum_naptime はランチャーが作業を確認する頻度を決めます。30s に下げると、\
+         特にデータベースやテーブルが多い場合に、更新頻度の高いシステムで autovacuum の\
+         反応が速くなります。",
+    ),
+    (
+        "autovacuum.vacuum_cost_limit",
+        "autovacuum_vacuum_cost_limit が低すぎます。デフォルトの 200 では、autovacuum は\
+         わずか 10 ページを変更しただけで休止します。そのため書き込みの多いワークロードに\
+         追従できません。ワーカーが休止するまでにより多くの作業を行えるよう、2000 \
+         (デフォルトの 10 倍) に引き上げてください。",
+    ),
+    (
+        "autovacuum.work_mem.inherited",
+        "autovacuum_work_mem が -1 (maintenance_work_mem を継承) です。maintenance_work_mem が\
+         大きな値 (例: 2GB) で autovacuum_max_workers が 3 以上の場合、通常の autovacuum で\
+         突然数 GB の RAM が消費される可能性があります。手動メンテナンスの値と切り離すため、\
+         autovacuum_work_mem は常に明示的に設定してください。",
+    ),
+    (
+        "autovacuum.work_mem.low",
+        "VACUUM の性能を高めるため、autovacuum_work_mem をワーカーあたり 512MB まで引き上げる\
+         ことができます。autovacuum は通常のクエリに比べて実行頻度が低いため安全です。",
+    ),
+    (
+        "autovacuum.vacuum_scale_factor",
+        "autovacuum_vacuum_scale_factor が {current} と高すぎます (デフォルト 20%)。これでは\
+         10 億行のテーブルで、不要タプルが 2 億に達するまで autovacuum が実行されません。\
+         大きなテーブルでは autovacuum_vacuum_scale_factor を 0 にし、\
+         autovacuum_vacuum_threshold を固定値 (例: 10,000) に設定してください。\
+         テーブル単位で設定できます: \
+         ALTER TABLE my_large_table SET (autovacuum_vacuum_scale_factor = 0, autovacuum_vacuum_threshold = 10000);",
+    ),
+    (
+        "autovacuum.idle_cursor_transaction",
+        "セッション{session}はカーソルを宣言または読み取った後、\
+         {idle}の間idle in transactionのままです。\
+         開いたトランザクションのスナップショットがxminホライズンを{xmin_age}トランザクション分引き留めているため、\
+         VACUUMはそれ以降に削除・更新されたタプルをデータベースのどのテーブルからも回収できません。\
+         アプリケーションでカーソルを閉じてトランザクションを終了し、\
+         短いトランザクションに分けてバッチで読み取り、\
+         保険としてidle_in_transaction_session_timeoutを設定してください。",
+    ),
+    (
+        "autovacuum.idle_cursor_transaction.bloat",
+        "すでに不要タプルで指摘されているテーブル: {tables}。",
+    ),
+    (
+        "autovacuum.idle_cursor_transaction.tables",
+        "このカーソルは{tables}を読み取ります。",
+    ),
+    (
+        "autovacuum.held_cursor",
+        "セッション{session}は{idle}前にWITH HOLDカーソルを最後に使い、\
+         まだ閉じていません。保持されたカーソルはスナップショットを固定しませんが、\
+         結果全体がコミット時に実体化され、\
+         CLOSEか切断までバックエンドのメモリまたは一時ファイルに残ります。\
+         クライアントが読み終えたらカーソルを閉じてください。\
+         トランザクションモードのコネクションプーラーもこれを追跡できません。",
+    ),
+    (
+        "autovacuum.held_cursor.tables",
+        "このカーソルは{tables}に対して宣言されました。",
+    ),
+    // Logging
+    (
+        "logging.log_min_duration_statement.disabled",
+        "log_min_duration_statement が無効です。これは遅いクエリを見つけるための最も重要な\
+         ツールです。1000 (1 秒) に設定して、1 秒以上かかるすべてのクエリを記録してください。",
+    ),
+    (
+        "logging.log_min_duration_statement.high",
+        "log_min_duration_statement がかなり高く設定されています ({current_ms}ms)。多くの\
+         ワークロードでは、ログを増やしすぎずに遅いクエリを捉えられる 1000ms (1 秒) が\
+         適切な出発点です。",
+    ),
+    (
+        "logging.log_min_duration_statement.all",
+        "log_min_duration_statement がすべてのクエリを記録しています。ログが過剰に出力される\
+         可能性があります。多くのワークロードでは遅いクエリの検出に 1000ms (1 秒) で十分です。",
+    ),
+    (
+        "logging.log_lock_waits",
+        "log_lock_waits が無効です。この設定はアプリケーションの並行性や競合の問題を診断する\
+         うえで不可欠です。有効にすると、deadlock_timeout より長くロックを待ったセッションが\
+         記録されます。",
+    ),
+    (
+        "logging.deadlock_timeout",
+        "deadlock_timeout がデフォルトの 1s より高く設定されています。ロックのログの誤検知を\
+         減らせる一方、デッドロックの検出が遅れます。多くのワークロードではデフォルトの 1s で\
+         十分です。",
+    ),
+    (
+        "logging.log_destination",
+        "log_destination は機械可読な形式を出力していません。{format} を追加すると、\
+         log_line_prefix に関係なく、ステートメント、エラーとその文脈をログ分析(postgreat logs)や\
+         ログパイプラインで確実に解析できます。",
+    ),
+    (
+        "logging.logging_collector",
+        "logging_collector が off のため、csvlog と jsonlog は出力されず、PostgreSQL はログ\
+         ファイルをローテーションしません。有効化には再起動が必要です。",
+    ),
+    (
+        "logging.log_filename.short",
+        "log_filename は {cycle} ごとに同じ名前になるため、保持すべき {retention} 日より新しい\
+         ログがローテーションで上書きされます。提案するパターンは {retention} 日以上経ってから\
+         繰り返します。",
+    ),
+    (
+        "logging.log_filename.unbounded",
+        "log_filename は同じ名前を繰り返さないため、PostgreSQL 以外の仕組みが log_directory を\
+         整理するまでログファイルが増え続けます。繰り返すパターンと log_truncate_on_rotation = on \
+         で {retention} 日分を一定のサイズで保持できます。",
+    ),
+    (
+        "logging.log_truncate_on_rotation",
+        "log_truncate_on_rotation が off のため、再利用されたファイル名には追記され、複数期間の\
+         ログが 1 つのファイルに増え続けます。有効にして古いファイルを上書きしてください。",
+    ),
+    (
+        "logging.log_rotation_age",
+        "log_rotation_age と log_rotation_size がどちらも 0 のため、ログファイルはローテーション\
+         されず際限なく大きくなります。毎日ローテーションしてください。",
+    ),
+    (
+        "logging.pgaudit_volume",
+        "現在の毎秒 {statements} 件のステートメントでは、pgaudit.log は 1 日あたり約 {volume} の\
+         監査ログを書き込みます({classes})。ログボリュームを埋め、ログ I/O ですべての\
+         ステートメントを遅くするのに十分な量です。提案するクラスでは約 {remaining} に減ります。\
+         機密テーブルの読み取りは read クラスではなくオブジェクト監査(pgaudit.role)で記録して\
+         ください。",
+    ),
+    (
+        "logging.log_statement_sample_rate",
+        "現在のステートメントレートでは、実行時間のログが 1 時間あたり約 {volume}({lines} 行)の\
+         サーバーログを書き込み、多くのログパイプラインの処理量を超えます。最も遅いステートメント\
+         には log_min_duration_statement = {min_duration}ms を維持し、残りは \
+         log_min_duration_sample = {sample_min_duration}ms と log_statement_sample_rate = {rate} \
+         でサンプリングしてください。",
+    ),
+    (
+        "logging.log_statement",
+        "現在のステートメントレートでは、log_statement が 1 時間あたり約 {volume}({lines} 行)の\
+         サーバーログを書き込み、多くのログパイプラインの処理量を超えます。ddl のみを記録し、\
+         すべてのステートメントが必要な場合は log_min_duration_sample = 0 と \
+         log_statement_sample_rate = {rate} でサンプリングしてください。",
+    ),
+    (
+        "logging.log_connections",
+        "毎秒 {sessions} 件の新規セッションにより、接続ログが 1 時間あたり約 {volume}({lines} 行)\
+         のサーバーログを書き込みます。コネクションプーラーで接続の入れ替わりを減らすか、接続行が\
+         不要なら log_connections を off にしてください。",
+    ),
+    // Extensions
+    (
+        "extensions.timescaledb_background_workers",
+        "timescaledb.max_background_workers ({current}) では、スケジュール済みの TimescaleDB \
+         ジョブ {jobs} 件とデータベースのスケジューラをまかなえません。圧縮・保持・連続集約の\
+         ジョブが互いに待ち合うか、スケジュールどおりに実行されません。{recommended} に\
+         引き上げてください。",
+    ),
+    (
+        "extensions.timescaledb_worker_processes",
+        "TimescaleDB では max_worker_processes を少なくとも \
+         timescaledb.max_background_workers ({background_workers}) + max_parallel_workers \
+         ({parallel_workers}) + 3 = {recommended} にする必要があります。これを下回ると、\
+         バックグラウンドジョブとパラレルクエリが同じワーカー枠を奪い合います。",
+    ),
+    (
+        "extensions.citus_shard_count",
+        "citus.shard_count ({current}) では、新しい分散テーブルの shard が {workers} 台の\
+         ワーカーそれぞれに {per_worker} 個未満しか割り当てられず、クエリがすべてのワーカーを\
+         使えません。{recommended} を使用してください。既存のテーブルの shard 数は \
+         alter_distributed_table() で変更するまでそのままです。",
+    ),
+    (
+        "extensions.citus_executor_pool_size",
+        "citus.max_adaptive_executor_pool_size が {current} のため、{peak} セッションが各\
+         ワーカーに最大 {total} 接続を開く可能性があり、ワーカーがこのノードと同じ構成なら \
+         max_connections ({max_connections}) を超えます。{recommended} に下げて、複数 shard の\
+         クエリがワーカーで失敗せずコーディネーター側で待つようにしてください。",
+    ),
+    (
+        "extensions.pgvector_missing_index",
+        "{table}.{column} には約 {rows} 件のベクトルがありますが HNSW / IVFFlat インデックスが\
+         ないため、近傍検索のたびに全行と比較しています。{ddl} で作成してください。演算子クラスは\
+         クエリが使う距離演算子 ({operator}) に合わせる必要があります。",
+    ),
+    (
+        "extensions.pgvector_missing_index.unverified",
+        "pg_stat_statements を読み取れなかったため、この列が距離検索にどの程度使われているかは\
+         不明です。",
+    ),
+    (
+        "extensions.pgvector_ivfflat_lists",
+        "{index} は約 {rows} 行に対して lists = {current} で作成されています。pgvector の推奨は\
+         100 万行までは 行数 / 1000、それ以上は sqrt(行数) で、約 {recommended} です。lists が\
+         少なすぎると各 probe がテーブルの大部分を読み、多すぎると同じ probes で再現率が\
+         下がります。lists は作成時に固定されるため、WITH (lists = {recommended}) で作り直して\
+         ください。",
+    ),
+    (
+        "extensions.pgvector_ivfflat_probes",
+        "ivfflat.probes が {current} ですが、最大の IVFFlat インデックスは {lists} lists あるため、\
+         検索対象がごく一部に限られ近い候補を取りこぼします。sqrt(lists) = {recommended} から\
+         始め、再現率が十分になるまで上げてください。",
+    ),
+    (
+        "extensions.pgvector_hnsw_ef_search",
+        "hnsw.ef_search が {current} ですが、{table} には HNSW インデックス付きで約 {rows} 行\
+         あります。グラフが大きいほど再現率を保つには候補リストを長くする必要があり、クエリは\
+         ef_search を超える行を返せません。{recommended} 以上にしてください。",
+    ),
+    (
+        "extensions.pgvector_build_memory",
+        "{table} の HNSW グラフは約 {size} で、maintenance_work_mem ({current}) を超えています。\
+         メモリに収まらないビルドは何倍も遅くなります。インデックスを作成・再構築するセッションで\
+         maintenance_work_mem を {recommended} に設定するか、他のメンテナンスと共有できるなら\
+         サーバー全体で設定してください。",
+    ),
+    // Server version
+    (
+        "version.minor_release",
+        "PostgreSQL {current} は {major}.x の最新リリース {latest} より {behind} マイナーリリース\
+         遅れています。マイナーリリースはバグとセキュリティの修正のみを含み、ディスク上の形式は\
+         変わらず、新しいバイナリをインストールして再起動するだけで適用できます。",
+    ),
+    (
+        "version.eol_major",
+        "PostgreSQL {major} は {eol} にサポート終了を迎え、バグやセキュリティの修正が提供されなく\
+         なりました。pg_upgrade または論理レプリケーションで {newest} などのサポート対象の\
+         メジャーバージョンへのアップグレードを計画してください。",
+    ),
+    // Host and storage layout
+    (
+        "host.wal_volume",
+        "{path} はデータディレクトリと同じボリュームにあるため、WAL の fsync がデータファイルの\
+         書き込みの後ろで待たされ、データボリュームが満杯になると WAL も止まります。ベアメタルや \
+         VM では pg_wal を専用ボリュームに置いてください(initdb --waldir、またはサーバー停止中に\
+         移動してシンボリックリンクを残す)。",
+    ),
+    (
+        "host.temp_tablespace_volume",
+        "一時テーブルスペース {name} はデータボリューム上にあるため、ディスクに溢れたソートや\
+         ハッシュがデータや WAL の I/O と競合します。temp_tablespaces を別の(できればローカルの)\
+         ボリューム上のテーブルスペースに向けてください。",
+    ),
+    (
+        "host.filesystem",
+        "{mount} は {fs} ファイルシステムです。内容が再起動やコンテナの入れ替えで失われるか、\
+         fsync のたびにネットワークの往復が発生します。PostgreSQL のファイルはローカルの xfs \
+         または ext4 ボリュームに置いてください。",
+    ),
+    (
+        "host.mount_options",
+        "{mount} は noatime なしでマウントされているため、読み取りでも inode のアクセス時刻が\
+         更新されます。noatime でマウントしてください。PostgreSQL はアクセス時刻を使いません。",
+    ),
+    // Operating system
+    (
+        "operating_system.open_files",
+        "サーバーはプロセスあたり {current} 個のファイルしか開けず、max_connections + \
+         max_files_per_process ({needed}) を下回っています。負荷の高いバックエンドはファイルの\
+         開閉を繰り返すか \"too many open files\" で失敗します。サービスの LimitNOFILE を\
+         引き上げて再起動してください。",
+    ),
+    (
+        "operating_system.overcommit_memory",
+        "vm.overcommit_memory が {current} のため、メモリ不足時に OOM キラーがプロセスを終了させ、\
+         それが postmaster だとすべての接続が再起動されます。2 にすると割り当てはエラーとして\
+         失敗します。vm.overcommit_ratio が shared_buffers とバックエンドの分を確保できるか\
+         確認してください。",
+    ),
+    (
+        "operating_system.swappiness",
+        "vm.swappiness が {current} のため、カーネルはページキャッシュを捨てる代わりに \
+         shared_buffers やバックエンドのメモリをスワップアウトします。vm.swappiness = 1 を\
+         設定してください。",
+    ),
+    (
+        "operating_system.transparent_hugepage",
+        "Transparent Huge Pages が always に設定されており、バックグラウンドのメモリ\
+         コンパクションによるレイテンシの急増やバックエンドのメモリ肥大を招きます。never に\
+         設定し(再起動後も維持するにはカーネルコマンドラインに transparent_hugepage=never を\
+         追加)、共有メモリには huge_pages を使ってください。",
+    ),
+    (
+        "operating_system.zone_reclaim_mode",
+        "{nodes} 個の NUMA ノードを持つホストで vm.zone_reclaim_mode が {current} のため、\
+         カーネルは別ノードから確保する代わりにローカルノードのページキャッシュを追い出し、\
+         キャッシュに当たるはずの読み取りがディスクに向かいます。0 に設定してください。",
+    ),
+    (
+        "operating_system.numa_balancing",
+        "{nodes} 個の NUMA ノードで自動 NUMA バランシングが有効です。すべてのバックエンドが \
+         shared_buffers にアクセスするため、カーネルはそのページを移動し続け、使用中の\
+         プロセスを停止させます。kernel.numa_balancing = 0 に設定し、共有メモリが各ノードに\
+         均等に分散されるよう postmaster を numactl --interleave=all で起動してください。",
+    ),
+    // Backup and recovery
+    (
+        "backup.recency.none",
+        "成功したベースバックアップが見つかりません。pg_wal にベースバックアップの履歴ファイルがなく、\
+         バックアップ確認コマンドも報告していません。\
+         アーカイブされた WAL は適用先のベースバックアップがなければ復元できません。\
+         {rpo} 以内のバックアップがなければ、ボリュームの喪失や誤ったマイグレーションから RPO \
+         以内に復旧できません。バックアップが実行され成功していることを確認してください。",
+    ),
+    (
+        "backup.recency.stale",
+        "最新の成功したバックアップは {age} 前({source}、{at})で、RPO の {rpo} を超えています。\
+         バックアップと WAL アーカイブが実行され成功していることを確認してください。",
+    ),
+    (
+        "backup.archive_mode",
+        "archive_mode が off のため WAL がアーカイブされず、\
+         ベースバックアップは取得完了時点にしか復元できません。\
+         archive_mode を on にし(再起動が必要)、\
+         archive_command または archive_library を設定して任意の時点に復旧できるようにしてください。",
+    ),
+    (
+        "backup.archive_command",
+        "archive_mode は on ですが archive_command も archive_library も設定されていません。\
+         サーバーはアーカイバを待ってすべての WAL セグメントを保持するため、\
+         pg_wal はディスクが満杯になるまで増え続け、PITR 用には何もアーカイブされません。\
+         各セグメントを永続ストレージにコピーし、\
+         コピーに失敗したら失敗を返す archive_command を設定してください。",
+    ),
+    (
+        "backup.restore_command",
+        "スタンバイ {standby} に restore_command がありません。\
+         プライマリが保持する WAL より遅れると、不足したセグメントをアーカイブから取得できず、\
+         新しいベースバックアップから再構築が必要になります。",
+    ),
+    (
+        "backup.lost_slot",
+        "レプリケーションスロット {slot} は、利用側がまだ必要とする WAL を失っており、\
+         ストリーミングを再開できません。スタンバイまたはサブスクライバを再構築し、\
+         スロットを削除してください。",
+    ),
+    (
+        "backup.max_slot_wal_keep_size",
+        "{slots} 個のレプリケーションスロットがあるのに max_slot_wal_keep_size が無制限です。\
+         切断された利用側のスロットは pg_wal がディスクを満たしプライマリが停止するまで WAL を保持し続けます。\
+         保持する WAL を {suggested} に制限し、\
+         停滞したスロットが無効化されるようにしてください。",
+    ),
+    (
+        "backup.max_slot_wal_keep_size.inactive",
+        "非アクティブなレプリケーションスロット {slot} がすでに {retained} の WAL を保持しており、\
+         max_slot_wal_keep_size が無制限のため pg_wal がディスクを満たすまで増え続けます。\
+         保持する WAL を {suggested} に制限し、\
+         利用側がなくなっていればスロットを削除してください。",
+    ),
+    (
+        "backup.wal_keep_size",
+        "スタンバイ {standbys} はレプリケーションスロットも restore_command もなくストリーミングしており、\
+         wal_keep_size が 0 のため、\
+         チェックポイントを超える遅延でまだ必要な WAL を失います。\
+         {suggested} の WAL を保持するか、\
+         スロットまたは restore_command を設定してください。",
+    ),
+    // Replication and resilience
+    (
+        "replication.tcp_keepalives_idle",
+        "現在のキープアライブ設定では、\
+         フェイルオーバーやネットワーク分断で切り離されたアプリケーションホストのように消えたクライアントにサーバーが気付くのは {detection} 後です。\
+         それまでバックエンドはロック、開いたトランザクション、接続スロットを保持し続けます。\
+         60 秒の無通信後に最初のプローブを送り、tcp_keepalives_interval 10、\
+         tcp_keepalives_count 6 と組み合わせれば、\
+         切れた接続は {suggested} で切断されます。",
+    ),
+    (
+        "replication.tcp_keepalives_interval",
+        "応答のないキープアライブプローブの再送が遅いため、\
+         切れたクライアント接続が切断されるまで合計 {detection} かかります。\
+         tcp_keepalives_idle 60、\
+         tcp_keepalives_count 6 と組み合わせて 10 秒ごとにプローブすれば {suggested} に短縮されます。",
+    ),
+    (
+        "replication.tcp_keepalives_count",
+        "サーバーが多くの未応答プローブを待つため、\
+         切れたクライアント接続が切断されるまで合計 {detection} かかります。\
+         tcp_keepalives_idle 60、\
+         tcp_keepalives_interval 10 と組み合わせて 6 回で打ち切れば {suggested} に短縮されます。",
+    ),
+    (
+        "replication.wal_receiver_timeout",
+        "wal_receiver_timeout が {timeout} のため、\
+         スタンバイが応答のないプライマリに気付き、\
+         昇格した新プライマリに再接続するまでそれだけかかります。\
+         60s にするとフェイルオーバーが短く済みます。",
+    ),
+    (
+        "replication.wal_receiver_timeout.disabled",
+        "wal_receiver_timeout が 0 のため、\
+         接続を閉じずに停止したプライマリをスタンバイは TCP が諦めるまで(多くは数時間)待ち続け、\
+         新しいプライマリへの再接続やアーカイブへの切り替えを行いません。60s を使用してください。",
+    ),
+    (
+        "replication.wal_sender_timeout",
+        "wal_sender_timeout が {timeout} のため、\
+         消えたスタンバイのレプリケーション接続と、それがアクティブに保つスロットがそれだけ残ります。\
+         60s にすると速やかに解放されます。",
+    ),
+    (
+        "replication.wal_sender_timeout.disabled",
+        "wal_sender_timeout が 0 のため、\
+         消えたスタンバイの WAL センダーは終了しません。\
+         スロットはアクティブのまま WAL を保持し続け、\
+         TCP が諦めるまでスタンバイはスロットに再接続できません。60s を使用してください。",
+    ),
+    (
+        "replication.restart_after_crash",
+        "スタンバイを持つサーバーで restart_after_crash が on です。\
+         Patroni、pg_auto_failover、\
+         Pacemaker などのクラスタマネージャがフェイルオーバーを扱う場合、\
+         クラッシュ後にポストマスタが自ら再起動するとスタンバイの昇格と競合します。\
+         off にしてクラスタマネージャに判断させてください。\
+         クラスタマネージャがない場合は on のままにしてください。",
+    ),
+    // Security
+    (
+        "security.policies_without_rls",
+        "{table} には {policies} 個の行レベルセキュリティポリシーがありますが、\
+         行レベルセキュリティが有効になっていないため、ポリシーは無視され、\
+         テーブル権限を持つすべてのロールがすべての行を参照・変更できます。\
+         ポリシーでテナントを分離する意図であれば ALTER TABLE {table} ENABLE ROW LEVEL SECURITY を実行してください。",
+    ),
+    (
+        "security.rls_without_policies",
+        "{table} は行レベルセキュリティが有効ですがポリシーがないため、所有者 {owner}、\
+         スーパーユーザー、BYPASSRLS ロール以外のロールは行を取得できず、書き込みもできません。\
+         テーブルを使うロール向けのポリシーを作成するか、行レベルセキュリティを無効にしてください。",
+    ),
+    (
+        "security.rls_without_policies.forced",
+        "{table} は行レベルセキュリティを強制していますがポリシーがないため、\
+         所有者 {owner} を含むすべてのロールが行を取得できず、書き込みもできません。\
+         データを参照できるのはスーパーユーザーと BYPASSRLS ロールだけです。\
+         テーブルを使うロール向けのポリシーを作成してください。",
+    ),
+    (
+        "security.rls_not_forced",
+        "{table} の行レベルセキュリティは強制されていないため、\
+         所有者 {owner} はポリシーを迂回します。アプリケーションが所有者として接続している場合、\
+         テナントはまったく分離されません。\
+         ALTER TABLE {table} FORCE ROW LEVEL SECURITY を実行するか、\
+         テーブルを所有しないロールでアプリケーションを接続してください。",
+    ),
+    (
+        "security.permissive_policy",
+        "{table} の許容ポリシー {policy} は {roles} に対してすべての行への {command} を許可します。\
+         許容ポリシーは OR で結合されるため、\
+         このポリシーはそれらのロールに対するテナントフィルタをすべて無効にします。式を絞り込むか、\
+         RESTRICTIVE にするか、管理用ロールに限定してください。",
+    ),
+    (
+        "security.row_security",
+        "{tables} 個のテーブルが行レベルセキュリティに依存しているのに row_security が off です。\
+         ポリシーで絞り込まれるはずのクエリは、参照可能な行を返す代わりにエラーになります。\
+         off にするのは、\
+         すべてを参照するか失敗すべき pg_dump のようなセッションだけにしてください。",
+    ),
+    (
+        "security.public_schema_create",
+        "すべてのロールが、\
+         search_path（{search_path}）で検索されるpublicスキーマにオブジェクトを作成できます。\
+         前にあるスキーマが存在しない場合、スキーマ修飾のないCREATE文はそこに作成され、\
+         どのロールも他のセッションの修飾なしの名前が解決されるテーブル、関数、\
+         演算子を差し替えられます（CVE-2018-1058）。\
+         REVOKE CREATE ON SCHEMA public FROM PUBLICを実行し、\
+         CREATEはアプリケーションのオブジェクトを所有するロールにのみ付与してください。",
+    ),
+    // Schema design
+    (
+        "schema.varchar_255",
+        "{tables}個のテーブルの{count}列がvarchar(255)です（例: {examples}）。\
+         PostgreSQLではtextとvarcharは同じように格納され、\
+         255はデータに必要な上限ではなく他のデータベースからの習慣で、\
+         実行時に長い値を拒否するだけです。textを使い、\
+         本当に長さの上限がある場合はCHECK制約を付けてください。",
+    ),
+    (
+        "schema.timestamp_without_time_zone",
+        "{tables}個のテーブルの{count}列がtimestamp without time zoneです（例: {examples}）。\
+         クライアントが送った時刻をそのまま保持するため、\
+         異なるタイムゾーンのセッションや夏時間の切り替えをまたいで書かれた値は比較できません。\
+         絶対時刻を格納し、\
+         出力時にセッションのTimeZoneに変換するtimestamptzを使ってください。",
+    ),
+    (
+        "schema.money",
+        "{tables}個のテーブルの{count}列がmoney型です（例: {examples}）。\
+         精度と書式がlc_monetaryに従うため、別のロケールでリストアしたダンプは値を誤って読み、\
+         通貨も記録されません。numericを使い、通貨が異なりうる場合は通貨の列を設けてください。",
+    ),
+    (
+        "schema.missing_primary_key",
+        "{count}個のテーブルに主キーがありません（例: {examples}）。\
+         重複行を区別できず、\
+         REPLICA IDENTITY FULLがないと論理レプリケーションはUPDATEとDELETEを拒否し、\
+         pg_repackなどのツールも扱えません。主キーを追加するか、\
+         NOT NULL列の一意インデックスをレプリカアイデンティティにしてください。",
+    ),
+    (
+        "schema.unindexed_nullable_fk",
+        "NULL可能な列の外部キー{count}個に、\
+         その列で始まるインデックスがありません（例: {examples}）。\
+         参照先テーブルのDELETEやキーのUPDATEのたびに、\
+         キーを検証するため参照元テーブルがスキャンされ、その結合でもインデックスを使えません。\
+         参照元の列にインデックスを作成してください。\
+         列がIS NOT NULLの部分インデックスなら参照のない行を除けます。",
+    ),
+    (
+        "schema.not_valid_constraint",
+        "{table}の{kind}制約{constraint}はNOT VALIDで追加され、\
+         一度も検証されていません。その後に書き込まれた行は検査されますが、\
+         既存の行が違反している可能性があり、\
+         プランナは未検証の制約を前提にしません（例: CHECKによるテーブルやパーティションの除外）。\
+         ALTER TABLE {table} VALIDATE CONSTRAINT {constraint}を実行してください。\
+         SHARE UPDATE EXCLUSIVEロックしか取らないため、\
+         スキャン中も読み書きは続けられます。",
+    ),
+    (
+        "schema.disabled_trigger",
+        "{table}のトリガ{trigger}が無効化されているため、監査行、派生列、\
+         他のテーブルへの変更など、それが維持していた処理がすべての書き込みで黙って止まっています。\
+         一括ロードのために無効化したのであれば、\
+         ALTER TABLE {table} ENABLE TRIGGER {trigger}を実行し、\
+         漏れた分を補ってください。不要になったのであれば削除してください。",
+    ),
+    (
+        "schema.disabled_fk_trigger",
+        "{table}の外部キー{constraint}を強制するトリガが、\
+         通常ALTER TABLE ... DISABLE TRIGGER ALLによって無効化されており、\
+         制約が有効に見えたままキーに違反する行が受け入れられます。\
+         両方のテーブルでALTER TABLE ... ENABLE TRIGGER ALLを実行し、\
+         トリガを有効にしても既存の行は再検査されないため、孤立した行を探してください。",
+    ),
+    (
+        "table_index.orphaned_temp_schema",
+        "{schema}には{tables}個の一時テーブル（{size}）がありますが、\
+         そのスロットを使うバックエンドがないため、\
+         クラッシュまたは強制終了したセッションが残したものです。\
+         どのテーブル一覧にも出ないディスク容量を使い、\
+         autovacuumは一時テーブルをバキュームできないため、\
+         {xid_age}トランザクションという経過がデータベースのdatfrozenxidを引き留めます。\
+         autovacuumは次にデータベースを巡回する際に孤立した一時テーブルを削除します。\
+         残り続ける場合は、スーパーユーザーとして大きいものから削除してください: {drops}",
+    ),
+    (
+        "table_index.orphaned_temp_schema.autovacuum_off",
+        "{schema}には{tables}個の一時テーブル（{size}）がありますが、\
+         そのスロットを使うバックエンドがないため、\
+         クラッシュまたは強制終了したセッションが残したものです。\
+         どのテーブル一覧にも出ないディスク容量を使い、\
+         autovacuumは一時テーブルをバキュームできないため、\
+         {xid_age}トランザクションという経過がデータベースのdatfrozenxidを引き留めます。\
+         autovacuumがオフで、孤立した一時テーブルを削除するのはautovacuumなので、\
+         スーパーユーザーが削除するまで残ります。大きいものから削除してください: {drops}",
+    ),
+    // Table and index health
+    (
+        "table_index.toast_compression",
+        "{table} は {toast} の TOAST データを持ち、生存行あたり {writes_per_row} 行を書き込んでおり、\
+         すべて pglz で圧縮されています。lz4 は圧縮が数倍速く、展開も約 2 倍速い一方、\
+         ストレージは約 10% 増えます(ここでは約 {extra} 増)。{ddl} の後、新しい値は lz4 を\
+         使います。既存の値は VACUUM FULL や pg_repack などでテーブルを書き直すまで pglz の\
+         ままです。",
+    ),
+    (
+        "table_index.default_toast_compression",
+        "default_toast_compression が pglz ですが、大きな TOAST データを持つ書き込みの多い\
+         テーブルが {tables} 個あり、lz4 の恩恵を受けられます。lz4 をデフォルトにすると、新しい\
+         列やテーブルは列ごとの ALTER なしで lz4 を使います。",
+    ),
+    (
+        "table_index.bloat.stale_autovacuum",
+        "{table} の不要タプルは {dead_pct}% ですが、最後の autovacuum は {last_autovacuum} に\
+         実行されました。autovacuum の調整が追いついていないようです。テーブル単位で autovacuum \
+         をより積極的にする (scale factor/threshold を下げる) か、手動の VACUUM を計画して\
+         肥大化を解消してください。",
+    ),
+    (
+        "table_index.bloat.never_autovacuumed",
+        "{table} の不要タプルは {dead_pct}% で、autovacuum の実行記録がありません。\
+         autovacuum の調整が追いついていないようです。テーブル単位で autovacuum をより\
+         積極的にする (scale factor/threshold を下げる) か、手動の VACUUM を計画して\
+         肥大化を解消してください。",
+    ),
+    (
+        "table_index.bloat.recent_autovacuum",
+        "{table} は最近 autovacuum が実行されたにもかかわらず不要タプルが {dead_pct}% あります。\
+         更新頻度の高いワークロードでは、より積極的な autovacuum 設定や、閑散時間帯の\
+         VACUUM (FULL) が必要になる場合があります。",
+    ),
+    (
+        "table_index.seq_scan_hotspot",
+        "{table} ではインデックススキャン {idx_scans} 回に対しシーケンシャルスキャンが \
+         {seq_scans} 回実行されています (約 {rows} 行、{size})。これは docs/6 で説明されている、\
+         フィルタの多いクエリが大きなテーブルでシーケンシャルスキャンにフォールバックする\
+         パターンに一致します。pg_stat_statements で該当クエリを特定し、その条件に合わせた\
+         複合インデックスまたは部分インデックスを追加してください。",
+    ),
+    (
+        "table_index.unused_index",
+        "{index} は一度もスキャンされておらず、制約も担っていません。docs/6 のガイダンスに従い\
+         削除すると {size} が解放され、書き込みのオーバーヘッドもなくなります。",
+    ),
+    (
+        "table_index.unused_index.writes_per_day",
+        "このインデックスは一度も読まれないまま、1 日あたり約 {writes} 件の書き込み \
+         ({table} への INSERT と非 HOT UPDATE) を処理しています。",
+    ),
+    (
+        "table_index.unused_index.writes_since_reset",
+        "このインデックスは統計のリセット以降、一度も読まれないまま {writes} 件の書き込み \
+         ({table} への INSERT と非 HOT UPDATE) を処理しています。",
+    ),
+    (
+        "table_index.low_selectivity",
+        "{index} はスキャンごとに {table} の約 {pct}% を返しています (読み取りあたり {tuples} \
+         タプル)。選択性が低いため、プランナはテーブルの大部分にアクセスしています。docs/6 の\
+         C.2 節に従ってインデックスを見直してください。",
+    ),
+    (
+        "table_index.failed_index_only",
+        "{index} はインデックススキャンを実行していますが、{pct}% の割合でヒープページも\
+         読み取っています。不足している SELECT 列を INCLUDE で追加するか、VACUUM で可視性マップを\
+         更新して、インデックスオンリースキャンが成功するようにしてください (docs/6 C.3 節)。",
+    ),
+    (
+        "table_index.missing_partial_index",
+        "テーブル {table} には論理削除列がありますが、部分インデックスがありません。\
+         'WHERE is_deleted = false' (または deleted_at IS NULL) を追加して削除済みの行を\
+         インデックスから除外すると、サイズとメンテナンスコストを削減できます。",
+    ),
+    (
+        "table_index.brin_candidate",
+        "テーブル {table} は大きく、{column} の順に物理的に並んでいます。BRIN インデックスなら \
+         B-tree の 100 分の 1 のサイズで、範囲クエリに対して良好なスキャン性能を得られます。",
+    ),
+    (
+        "table_index.missing_spatial_index",
+        "テーブル {table} には約 {rows} 行ありますが、{column} 列に GiST、SP-GiST、BRIN \
+         のいずれのインデックスもないため、ST_Intersects、ST_DWithin、&& によるフィルタは毎回\
+         テーブル全体を読み取ります。CREATE INDEX CONCURRENTLY ... USING gist ({column}) \
+         で作成してください。",
+    ),
+    // Notes appended by shared passes
+    ("note.statistics_window", "統計の対象期間は {window} ({since} 以降) です。"),
+    (
+        "note.sample_window",
+        "スキャン回数は {window} のサンプル期間、インデックス使用状況は {stats_window} \
+         ({since} 以降) を対象としています。",
+    ),
+    ("note.also_flagged", "{category} でも指摘されています:"),
+    ("note.suggested_instead", "{category} は代わりに {value} を提案しています:"),
+    (
+        "note.clamped_min",
+        "{parameter} のサーバー最小値である {value} に制限しました (ヒューリスティックの提案値は \
+         {suggested})。",
+    ),
+    (
+        "note.clamped_max",
+        "{parameter} のサーバー最大値である {value} に制限しました (ヒューリスティックの提案値は \
+         {suggested})。",
+    ),
+    (
+        "note.clamped_32bit",
+        "{parameter} の 32 ビットビルドでの実用上限である {value} に制限しました \
+         (ヒューリスティックの提案値は {suggested})。",
+    ),
+    (
+        "note.checkpoint_interval",
+        "計測した WAL 生成量 {wal_rate} では、チェックポイントは約 {before} ごとに実行されます。",
+    ),
+    (
+        "note.checkpoint_interval_change",
+        "計測した WAL 生成量 {wal_rate} では、チェックポイントは現在約 {before} ごと → \
+         提案した WAL 設定では約 {after} ごとになります。",
+    ),
+    (
+        "note.full_page_writes",
+        "フルページイメージは WAL の約 {share}% を占めます。間隔が延びることで WAL 量は\
+         最大で約 {savings}% 減少します。",
+    ),
+    ("note.severity_override", "ルールファイルにより重要度を {level} に設定しました (チェックの判定は {original})。"),
+    ("note.pending_reload", "{value} は既に {file} に設定済みで、設定の再読み込みを待っています。"),
+    ("note.pending_restart", "{value} は既に {file} に設定済みで、再起動を待っています。"),
+    (
+        "note.large_server_parallel",
+        "{vcpu} vCPU のサーバーは複数の NUMA ノードにまたがる可能性が高く、リモートノード上の\
+         ワーカーは速度よりメモリトラフィックを増やすため、PostGreat は目標値を {target} に\
+         抑えています。",
+    ),
+    ("note.connection_peak_hours", "接続数は {hours} にピークを迎えます。"),
+    (
+        "note.observed_connection_peak",
+        "{first} から {last} までの {samples} 回の計測で接続数は最大 {peak} connections \
+         (max_connections の {pct}%) でした。目標値はこのピークに 25% の余裕を持たせています。",
+    ),
+    (
+        "note.log_volume_at_recommended",
+        "現在のステートメントレートでは 1 時間あたり約 {volume}({lines} 行)のログになります。\
+         1GB 未満に抑えるには {min_duration}ms に設定し、残りは log_min_duration_sample = \
+         {sample_min_duration}ms と log_statement_sample_rate = {rate} でサンプリングしてください。",
+    ),
+];
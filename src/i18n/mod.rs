@@ -0,0 +1,240 @@
+use clap::ValueEnum;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+mod de;
+mod en;
+mod ja;
+
+/// Language report rationales are written in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    #[default]
+    En,
+    De,
+    Ja,
+}
+
+impl Lang {
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Lang::En => en::MESSAGES,
+            Lang::De => de::MESSAGES,
+            Lang::Ja => ja::MESSAGES,
+        }
+    }
+}
+
+/// Key of the catalog entry that renders its `text` argument verbatim
+const TEXT: &str = "text";
+
+/// A message catalog entry and the values for its `{placeholders}`.
+///
+/// Keys are check IDs, followed by a variant when a check words its finding in
+/// more than one way (`memory.work_mem.too_high`). Notes appended by shared
+/// passes, such as reconciliation and range validation, live under `note.`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    key: &'static str,
+    args: Vec<(&'static str, String)>,
+}
+
+impl Message {
+    pub fn new(key: &'static str) -> Self {
+        Self {
+            key,
+            args: Vec::new(),
+        }
+    }
+
+    /// Text that has no catalog entry and is shown as-is in every language
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::new(TEXT).arg("text", text.into())
+    }
+
+    pub fn arg(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.args.push((name, value.to_string()));
+        self
+    }
+
+    pub fn key(&self) -> &'static str {
+        self.key
+    }
+
+    /// Fills in the template for `lang`, falling back to English for keys that are
+    /// not translated yet
+    pub fn render(&self, lang: Lang) -> String {
+        let template = lookup(lang, self.key)
+            .or_else(|| lookup(Lang::En, self.key))
+            .unwrap_or(self.key);
+        let mut rendered = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            rendered.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let value = after.find('}').and_then(|end| {
+                let name = &after[..end];
+                self.args
+                    .iter()
+                    .find(|(arg, _)| *arg == name)
+                    .map(|(_, value)| (value, end))
+            });
+            match value {
+                Some((value, end)) => {
+                    rendered.push_str(value);
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    rendered.push('{');
+                    rest = after;
+                }
+            }
+        }
+        rendered.push_str(rest);
+        rendered
+    }
+}
+
+/// Saved as `{"key": ..., "args": [[name, value], ...]}` so saved results can
+/// be rendered in another language later
+impl Serialize for Message {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Message", 2)?;
+        state.serialize_field("key", self.key)?;
+        state.serialize_field("args", &self.args)?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+struct SavedMessage {
+    key: String,
+    #[serde(default)]
+    args: Vec<(String, String)>,
+}
+
+impl Message {
+    /// Rebuilds a saved message from the catalog; `None` for keys this build
+    /// does not know
+    fn restore(saved: SavedMessage) -> Option<Self> {
+        let (key, template) = Lang::En
+            .catalog()
+            .iter()
+            .find(|(key, _)| *key == saved.key)?;
+        let args = saved
+            .args
+            .into_iter()
+            .filter_map(|(name, value)| {
+                let start = template.find(&format!("{{{name}}}"))? + 1;
+                Some((&template[start..start + name.len()], value))
+            })
+            .collect();
+        Some(Self { key, args })
+    }
+}
+
+/// Reads saved messages. If any key is unknown to this build, none are kept,
+/// so the saved rationale is shown as-is instead of a partial one.
+pub fn deserialize_messages<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Message>, D::Error> {
+    let saved = Vec::<SavedMessage>::deserialize(deserializer)?;
+    Ok(saved
+        .into_iter()
+        .map(Message::restore)
+        .collect::<Option<Vec<_>>>()
+        .unwrap_or_default())
+}
+
+/// Templates `lang` words a check's findings with: the one keyed by its ID and
+/// its variants, such as `memory.work_mem.too_high`
+pub fn check_templates(lang: Lang, check_id: &str) -> Vec<(&'static str, &'static str)> {
+    lang.catalog()
+        .iter()
+        .filter(|(key, _)| {
+            key.strip_prefix(check_id)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        })
+        .copied()
+        .collect()
+}
+
+fn lookup(lang: Lang, key: &str) -> Option<&'static str> {
+    lang.catalog()
+        .iter()
+        .find(|(candidate, _)| *candidate == key)
+        .map(|(_, template)| *template)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::collections::BTreeSet;
+
+    fn placeholders(template: &str) -> BTreeSet<&str> {
+        template
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[rstest]
+    #[case(Lang::De)]
+    #[case(Lang::Ja)]
+    fn translations_cover_every_message_with_the_same_placeholders(#[case] lang: Lang) {
+        for (key, english) in en::MESSAGES {
+            let translated = lookup(lang, key).unwrap_or_else(|| panic!("{lang:?} lacks {key}"));
+            assert_eq!(
+                placeholders(translated),
+                placeholders(english),
+                "{lang:?} {key}"
+            );
+        }
+        assert_eq!(lang.catalog().len(), en::MESSAGES.len());
+    }
+
+    #[test]
+    fn keys_are_check_ids_or_notes() {
+        for (key, _) in en::MESSAGES {
+            let known = *key == TEXT
+                || key.starts_with("note.")
+                || crate::checks::CHECKS
+                    .iter()
+                    .any(|check| *key == check.id || key.starts_with(&format!("{}.", check.id)));
+            assert!(known, "{key} is not keyed by a check ID");
+        }
+    }
+
+    #[test]
+    fn saved_messages_render_in_another_language() {
+        let messages = vec![
+            Message::new("memory.shared_buffers").arg("memory_gb", 64),
+            Message::text("As is"),
+        ];
+        let json = serde_json::to_string(&messages).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"key":"memory.shared_buffers","args":[["memory_gb","64"]]},{"key":"text","args":[["text","As is"]]}]"#
+        );
+
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let restored = deserialize_messages(&mut deserializer).unwrap();
+        assert_eq!(restored, messages);
+
+        let unknown = r#"[{"key":"memory.shared_buffers","args":[]},{"key":"gone.key","args":[]}]"#;
+        let mut deserializer = serde_json::Deserializer::from_str(unknown);
+        assert!(deserialize_messages(&mut deserializer).unwrap().is_empty());
+    }
+
+    #[test]
+    fn renders_arguments_and_falls_back_to_english() {
+        let message = Message::new("memory.shared_buffers").arg("memory_gb", 64);
+        assert!(message.render(Lang::En).contains("(64GB)"));
+        assert!(message.render(Lang::De).contains("64"));
+        assert_eq!(Message::text("As is {x}").render(Lang::Ja), "As is {x}");
+        assert_eq!(Message::new("unknown.key").render(Lang::De), "unknown.key");
+    }
+}
@@ -1,5 +1,36 @@
 pub mod analysis;
+pub mod api;
+pub mod apply;
+pub mod ascii;
+pub mod bench;
+pub mod catalog;
 pub mod checker;
+pub mod checks;
 pub mod config;
+pub mod email;
+pub mod emit;
+pub mod exit_summary;
+pub mod fingerprint;
+pub mod fixture;
+pub mod guidance;
+pub mod i18n;
+pub mod json_log;
+pub mod lifecycle;
 pub mod models;
+pub mod probes;
+pub mod profile;
+pub mod queries;
 pub mod reporter;
+pub mod rule_docs;
+pub mod rules;
+pub mod saved_results;
+pub mod scheduler;
+pub mod scoring;
+pub mod settings_dump;
+pub mod signing;
+pub mod telemetry;
+#[cfg(feature = "integration-tests")]
+pub mod testing;
+pub mod upload;
+
+pub use queries::all_queries as queries;
@@ -0,0 +1,388 @@
+use crate::models::{AnalysisResults, ConfigCategory, SuggestionLevel};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, OptionExt, ResultExt, Snafu};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Snafu)]
+pub enum LifecycleError {
+    #[snafu(display("Failed to read finding lifecycle {}: {}", path.display(), source))]
+    LedgerRead {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("{} is not a finding lifecycle file: {}", path.display(), source))]
+    LedgerParse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[snafu(display("Failed to write finding lifecycle {}: {}", path.display(), source))]
+    LedgerWrite {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Failed to serialize finding lifecycle: {}", source))]
+    LedgerSerialize { source: serde_json::Error },
+
+    #[snafu(display("No finding with fingerprint {} in {}", fingerprint, path.display()))]
+    UnknownFingerprint { fingerprint: String, path: PathBuf },
+
+    #[snafu(display("No snapshots under {} have seen finding {}", dir.display(), fingerprint))]
+    FingerprintNotSeen { fingerprint: String, dir: PathBuf },
+
+    #[snafu(display(
+        "Finding {} was seen on several databases ({}); pick one with --database",
+        fingerprint,
+        entries.join(", ")
+    ))]
+    AmbiguousFingerprint {
+        fingerprint: String,
+        entries: Vec<String>,
+    },
+}
+
+type Result<T, E = LifecycleError> = std::result::Result<T, E>;
+
+/// File each snapshot directory keeps its findings' lifecycle in
+pub const LEDGER_FILE: &str = "findings.json";
+
+/// Where a finding stands across stored runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingState {
+    Open,
+    Acknowledged,
+    Resolved,
+}
+
+/// One fingerprint's history. Timestamps are RFC 3339 in UTC, so they sort as strings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FindingLifecycle {
+    pub fingerprint: String,
+    pub category: ConfigCategory,
+    pub parameter: String,
+    /// Level of the most recent sighting
+    pub level: SuggestionLevel,
+    pub first_seen: String,
+    pub last_seen: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acknowledged_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acknowledged_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ack_reason: Option<String>,
+    /// When a run first no longer reported it; cleared if it comes back
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<String>,
+}
+
+impl FindingLifecycle {
+    pub fn state(&self) -> FindingState {
+        if self.resolved_at.is_some() {
+            FindingState::Resolved
+        } else if self.acknowledged_by.is_some() {
+            FindingState::Acknowledged
+        } else {
+            FindingState::Open
+        }
+    }
+
+    /// One-line status for reports, dated to the day
+    pub fn status(&self) -> String {
+        let day = |at: &str| at.get(..10).unwrap_or(at).to_string();
+        let since = format!("open since {}", day(&self.first_seen));
+        match (self.state(), &self.acknowledged_by) {
+            (FindingState::Resolved, _) => format!(
+                "resolved {}",
+                day(self.resolved_at.as_deref().unwrap_or_default())
+            ),
+            (_, Some(by)) => format!(
+                "acknowledged by {} on {}: {} ({since})",
+                by,
+                day(self.acknowledged_at.as_deref().unwrap_or_default()),
+                self.ack_reason.as_deref().unwrap_or_default()
+            ),
+            (_, None) => since,
+        }
+    }
+}
+
+/// The lifecycle of every finding a snapshot directory has seen, keyed by fingerprint
+#[derive(Debug, Default)]
+pub struct Ledger {
+    path: PathBuf,
+    findings: BTreeMap<String, FindingLifecycle>,
+}
+
+impl Ledger {
+    /// Reads the ledger in `dir`; a directory without one starts empty
+    pub fn open(dir: &Path) -> Result<Self> {
+        let path = dir.join(LEDGER_FILE);
+        let findings = match fs::read(&path) {
+            Ok(bytes) => {
+                let findings: Vec<FindingLifecycle> =
+                    serde_json::from_slice(&bytes).context(LedgerParseSnafu { path: &path })?;
+                findings
+                    .into_iter()
+                    .map(|finding| (finding.fingerprint.clone(), finding))
+                    .collect()
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err).context(LedgerReadSnafu { path }),
+        };
+        Ok(Self { path, findings })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let findings: Vec<_> = self.findings.values().collect();
+        let json = serde_json::to_vec_pretty(&findings).context(LedgerSerializeSnafu)?;
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).context(LedgerWriteSnafu { path: parent })?;
+        }
+        fs::write(&self.path, json).context(LedgerWriteSnafu { path: &self.path })
+    }
+
+    /// Records a run: findings it reported are seen at `at` (and reopened if
+    /// they had been resolved), and open findings it no longer reported are
+    /// resolved at `at`. Acknowledgements survive both.
+    pub fn observe(&mut self, results: &AnalysisResults, at: DateTime<Utc>) {
+        let at = timestamp(at);
+        let mut seen = HashSet::new();
+        for (category, suggestion) in results.ranked_suggestions() {
+            if suggestion.fingerprint.is_empty() || !seen.insert(&suggestion.fingerprint) {
+                continue;
+            }
+            let finding = self
+                .findings
+                .entry(suggestion.fingerprint.clone())
+                .or_insert_with(|| FindingLifecycle {
+                    fingerprint: suggestion.fingerprint.clone(),
+                    category,
+                    parameter: suggestion.parameter.clone(),
+                    level: suggestion.level,
+                    first_seen: at.clone(),
+                    last_seen: at.clone(),
+                    acknowledged_by: None,
+                    acknowledged_at: None,
+                    ack_reason: None,
+                    resolved_at: None,
+                });
+            finding.level = suggestion.level;
+            finding.last_seen = at.clone();
+            finding.resolved_at = None;
+        }
+        for finding in self.findings.values_mut() {
+            if finding.resolved_at.is_none() && !seen.contains(&finding.fingerprint) {
+                finding.resolved_at = Some(at.clone());
+            }
+        }
+    }
+
+    /// Marks a finding as acknowledged; a later run that still reports it
+    /// keeps the acknowledgement
+    pub fn acknowledge(
+        &mut self,
+        fingerprint: &str,
+        by: &str,
+        reason: &str,
+        at: DateTime<Utc>,
+    ) -> Result<&FindingLifecycle> {
+        let finding = self
+            .findings
+            .get_mut(fingerprint)
+            .context(UnknownFingerprintSnafu {
+                fingerprint,
+                path: &self.path,
+            })?;
+        finding.acknowledged_by = Some(by.to_string());
+        finding.acknowledged_at = Some(timestamp(at));
+        finding.ack_reason = Some(reason.to_string());
+        Ok(finding)
+    }
+
+    pub fn contains(&self, fingerprint: &str) -> bool {
+        self.findings.contains_key(fingerprint)
+    }
+
+    pub fn is_acknowledged(&self, fingerprint: &str) -> bool {
+        self.findings
+            .get(fingerprint)
+            .is_some_and(|finding| finding.acknowledged_by.is_some())
+    }
+
+    /// Every finding, by fingerprint
+    pub fn findings(&self) -> Vec<FindingLifecycle> {
+        self.findings.values().cloned().collect()
+    }
+}
+
+/// Acknowledges `fingerprint` in the one snapshot directory under `root` that
+/// has seen it. `database` narrows the search to entries named `<database>@...`
+/// (or exactly `database`) when the same finding is on several databases.
+pub fn acknowledge_in(
+    root: &Path,
+    database: Option<&str>,
+    fingerprint: &str,
+    by: &str,
+    reason: &str,
+    at: DateTime<Utc>,
+) -> Result<(String, FindingLifecycle)> {
+    let entries = fs::read_dir(root).context(LedgerReadSnafu { path: root })?;
+    let mut matches = Vec::new();
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let wanted = database
+            .is_none_or(|database| name == database || name.starts_with(&format!("{database}@")));
+        if !wanted || !entry.path().join(LEDGER_FILE).is_file() {
+            continue;
+        }
+        let ledger = Ledger::open(&entry.path())?;
+        if ledger.contains(fingerprint) {
+            matches.push((name, ledger));
+        }
+    }
+    ensure!(
+        matches.len() <= 1,
+        AmbiguousFingerprintSnafu {
+            fingerprint,
+            entries: matches
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<_>>(),
+        }
+    );
+    let (name, mut ledger) = matches.pop().context(FingerprintNotSeenSnafu {
+        fingerprint,
+        dir: root,
+    })?;
+    let finding = ledger.acknowledge(fingerprint, by, reason, at)?.clone();
+    ledger.save()?;
+    Ok((name, finding))
+}
+
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fingerprint;
+    use crate::models::ConfigSuggestion;
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+
+    fn results(parameters: &[&str]) -> AnalysisResults {
+        let mut results = AnalysisResults::default();
+        for parameter in parameters {
+            results
+                .suggestions_by_category
+                .entry(ConfigCategory::Memory)
+                .or_default()
+                .push(ConfigSuggestion {
+                    parameter: parameter.to_string(),
+                    current_value: "1".to_string(),
+                    suggested_value: "2".to_string(),
+                    level: SuggestionLevel::Important,
+                    rationale: String::new(),
+                    messages: Vec::new(),
+                    fingerprint: String::new(),
+                });
+        }
+        fingerprint::assign(&mut results);
+        results
+    }
+
+    fn day(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, day, 3, 0, 0).unwrap()
+    }
+
+    fn finding<'a>(ledger: &'a Ledger, parameter: &str) -> &'a FindingLifecycle {
+        ledger
+            .findings
+            .values()
+            .find(|finding| finding.parameter == parameter)
+            .unwrap()
+    }
+
+    #[test]
+    fn findings_are_resolved_when_a_run_stops_reporting_them_and_reopen_when_they_return() {
+        let mut ledger = Ledger::default();
+        ledger.observe(&results(&["work_mem", "shared_buffers"]), day(1));
+        ledger.observe(&results(&["work_mem"]), day(2));
+
+        let work_mem = finding(&ledger, "work_mem");
+        assert_eq!(work_mem.first_seen, "2026-10-01T03:00:00Z");
+        assert_eq!(work_mem.last_seen, "2026-10-02T03:00:00Z");
+        assert_eq!(work_mem.state(), FindingState::Open);
+        let shared_buffers = finding(&ledger, "shared_buffers");
+        assert_eq!(shared_buffers.state(), FindingState::Resolved);
+        assert_eq!(
+            shared_buffers.resolved_at.as_deref(),
+            Some("2026-10-02T03:00:00Z")
+        );
+
+        ledger.observe(&results(&["work_mem", "shared_buffers"]), day(3));
+        let shared_buffers = finding(&ledger, "shared_buffers");
+        assert_eq!(shared_buffers.state(), FindingState::Open);
+        assert_eq!(shared_buffers.first_seen, "2026-10-01T03:00:00Z");
+    }
+
+    #[test]
+    fn acknowledgements_are_saved_and_survive_later_runs() {
+        let root = tempdir().unwrap();
+        let dir = root.path().join("production_db@db1.example.com");
+        let mut ledger = Ledger::open(&dir).unwrap();
+        ledger.observe(&results(&["work_mem"]), day(1));
+        ledger.save().unwrap();
+        let fingerprint = finding(&ledger, "work_mem").fingerprint.clone();
+
+        let (entry, acknowledged) = acknowledge_in(
+            root.path(),
+            Some("production_db"),
+            &fingerprint,
+            "alice",
+            "Batch jobs need it",
+            day(2),
+        )
+        .unwrap();
+        assert_eq!(entry, "production_db@db1.example.com");
+        assert_eq!(acknowledged.state(), FindingState::Acknowledged);
+
+        let mut ledger = Ledger::open(&dir).unwrap();
+        ledger.observe(&results(&["work_mem"]), day(3));
+        assert!(ledger.is_acknowledged(&fingerprint));
+        let work_mem = finding(&ledger, "work_mem");
+        assert_eq!(work_mem.ack_reason.as_deref(), Some("Batch jobs need it"));
+        assert_eq!(work_mem.last_seen, "2026-10-03T03:00:00Z");
+    }
+
+    #[test]
+    fn acknowledging_needs_exactly_one_entry_with_the_finding() {
+        let root = tempdir().unwrap();
+        for entry in ["orders@db1", "billing@db2"] {
+            let mut ledger = Ledger::open(&root.path().join(entry)).unwrap();
+            ledger.observe(&results(&["work_mem"]), day(1));
+            ledger.save().unwrap();
+        }
+        let fingerprint = results(&["work_mem"]).suggestions_by_category[&ConfigCategory::Memory]
+            [0]
+        .fingerprint
+        .clone();
+
+        let err = acknowledge_in(root.path(), None, &fingerprint, "a", "b", day(2)).unwrap_err();
+        assert!(matches!(err, LifecycleError::AmbiguousFingerprint { .. }));
+        let err =
+            acknowledge_in(root.path(), None, "0000000000000000", "a", "b", day(2)).unwrap_err();
+        assert!(matches!(err, LifecycleError::FingerprintNotSeen { .. }));
+        assert!(
+            acknowledge_in(root.path(), Some("billing"), &fingerprint, "a", "b", day(2)).is_ok()
+        );
+    }
+}
@@ -1,5 +1,11 @@
+use crate::i18n::{Lang, Message};
+use crate::lifecycle::FindingLifecycle;
+use crate::scoring::{self, HealthScore};
+use crate::signing::RunMetadata;
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 
 /// Represents a PostgreSQL configuration parameter with its current value and metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +15,177 @@ pub struct PgConfigParam {
     pub default_value: Option<String>,
     pub unit: Option<String>,
     pub context: String,
+    /// `current_value` parsed with the setting's `vartype` and `unit`
+    #[serde(default)]
+    pub value: ParamValue,
+    /// Where the value came from, e.g. `configuration file` or `default`
+    #[serde(default)]
+    pub source: Option<String>,
+    /// The file that set the value; only visible to superusers and `pg_read_all_settings`
+    #[serde(default)]
+    pub sourcefile: Option<String>,
+    /// Changed in a configuration file but not applied until the server restarts
+    #[serde(default)]
+    pub pending_restart: bool,
+    /// Lowest value the server accepts, in `unit`, for numeric settings
+    #[serde(default)]
+    pub min_value: Option<String>,
+    /// Highest value the server accepts, in `unit`, for numeric settings
+    #[serde(default)]
+    pub max_value: Option<String>,
+}
+
+impl PgConfigParam {
+    /// Whether the value was set explicitly rather than left at the built-in default.
+    ///
+    /// `override` values are computed by the server and `client`/`session` values
+    /// come from the connection itself, so neither counts as an operator change.
+    pub fn is_changed(&self) -> bool {
+        self.source
+            .as_deref()
+            .is_some_and(|source| !matches!(source, "default" | "override" | "client" | "session"))
+    }
+
+    /// Where the value was set, including the file when it is visible
+    pub fn set_in(&self) -> String {
+        match (self.source.as_deref(), self.sourcefile.as_deref()) {
+            (Some(source), Some(file)) => format!("{source} ({file})"),
+            (Some(source), None) => source.to_string(),
+            (None, _) => "unknown".to_string(),
+        }
+    }
+}
+
+/// A pg_settings value parsed once, so analyzers compare typed values instead of
+/// re-parsing strings with their own fallbacks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum ParamValue {
+    /// A memory setting, converted from its `unit` (e.g. `8kB` blocks) to bytes
+    Bytes(u64),
+    /// A time setting, converted from its `unit` (e.g. `ms`, `min`)
+    Duration(std::time::Duration),
+    /// A unitless integer, or a negative sentinel such as `-1` for "disabled"
+    Integer(i64),
+    /// A unitless real, or a negative sentinel
+    Real(f64),
+    Bool(bool),
+    Enum(String),
+    String(String),
+}
+
+impl Default for ParamValue {
+    fn default() -> Self {
+        Self::String(String::new())
+    }
+}
+
+impl ParamValue {
+    /// Parses a `setting` from pg_settings using its `vartype` and `unit`.
+    ///
+    /// An empty or unknown `vartype` infers a number from the setting, which keeps
+    /// hand-built parameters (tests, imported snapshots) usable.
+    pub fn parse(setting: &str, vartype: &str, unit: Option<&str>) -> Self {
+        let setting = setting.trim();
+        match vartype {
+            "bool" => return Self::Bool(setting == "on"),
+            "enum" => return Self::Enum(setting.to_string()),
+            "string" => return Self::String(setting.to_string()),
+            _ => {}
+        }
+        let Ok(number) = setting.parse::<f64>() else {
+            return Self::String(setting.to_string());
+        };
+        let is_integer = vartype == "integer" || (vartype != "real" && number.fract() == 0.0);
+        if number >= 0.0 {
+            if let Some(multiplier) = unit.and_then(unit_bytes) {
+                return Self::Bytes((number * multiplier) as u64);
+            }
+            if let Some(multiplier) = unit.and_then(unit_seconds) {
+                return Self::Duration(std::time::Duration::from_secs_f64(number * multiplier));
+            }
+        }
+        if is_integer {
+            Self::Integer(number as i64)
+        } else {
+            Self::Real(number)
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<u64> {
+        match self {
+            Self::Bytes(bytes) => Some(*bytes),
+            _ => None,
+        }
+    }
+
+    pub fn as_duration(&self) -> Option<std::time::Duration> {
+        match self {
+            Self::Duration(duration) => Some(*duration),
+            _ => None,
+        }
+    }
+
+    /// The value of a unitless integer setting
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// A non-negative unitless integer, such as a worker or connection count
+    pub fn as_count(&self) -> Option<usize> {
+        self.as_i64().and_then(|value| usize::try_from(value).ok())
+    }
+
+    /// The value of a unitless numeric setting
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Integer(value) => Some(*value as f64),
+            Self::Real(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+/// Bytes per pg_settings memory unit, e.g. `8kB` for block-sized settings
+pub(crate) fn unit_bytes(unit: &str) -> Option<f64> {
+    let digits = unit.len() - unit.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let count = if digits == 0 {
+        1.0
+    } else {
+        unit[..digits].parse::<f64>().ok()?
+    };
+    let scale = match &unit[digits..] {
+        "B" => 1.0,
+        "kB" => 1024.0,
+        "MB" => 1024.0 * 1024.0,
+        "GB" => 1024.0 * 1024.0 * 1024.0,
+        "TB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some(count * scale)
+}
+
+/// Seconds per pg_settings time unit
+pub(crate) fn unit_seconds(unit: &str) -> Option<f64> {
+    match unit {
+        "us" => Some(0.000_001),
+        "ms" => Some(0.001),
+        "s" => Some(1.0),
+        "min" => Some(60.0),
+        "h" => Some(3600.0),
+        "d" => Some(86400.0),
+        _ => None,
+    }
 }
 
 /// Represents a suggestion level for configuration improvements
@@ -33,6 +210,16 @@ impl SuggestionLevel {
             SuggestionLevel::Info => "INFO",
         }
     }
+
+    /// Sort key that puts the most severe level first
+    pub fn rank(self) -> u8 {
+        match self {
+            SuggestionLevel::Critical => 0,
+            SuggestionLevel::Important => 1,
+            SuggestionLevel::Recommended => 2,
+            SuggestionLevel::Info => 3,
+        }
+    }
 }
 
 /// Represents a single configuration suggestion
@@ -48,11 +235,50 @@ pub struct ConfigSuggestion {
     pub level: SuggestionLevel,
     /// Rationale for the suggestion
     pub rationale: String,
+    /// Catalog messages the rationale was rendered from, used to re-render it
+    /// in another language
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "crate::i18n::deserialize_messages"
+    )]
+    pub messages: Vec<Message>,
+    /// Stable hash of the check ID and the object the suggestion is about,
+    /// assigned once analysis finishes
+    #[serde(default)]
+    pub fingerprint: String,
+}
+
+impl ConfigSuggestion {
+    /// Appends a sentence to the rationale, keeping the message list in step
+    pub fn push_note(&mut self, note: Message) {
+        if self.messages.is_empty() && !self.rationale.is_empty() {
+            self.messages.push(Message::text(self.rationale.clone()));
+        }
+        if !self.rationale.is_empty() {
+            self.rationale.push(' ');
+        }
+        self.rationale.push_str(&note.render(Lang::En));
+        self.messages.push(note);
+    }
+
+    /// Re-renders the rationale in `lang`
+    pub fn localize(&mut self, lang: Lang) {
+        if !self.messages.is_empty() {
+            self.rationale = self
+                .messages
+                .iter()
+                .map(|message| message.render(lang))
+                .collect::<Vec<_>>()
+                .join(" ");
+        }
+    }
 }
 
 /// Represents a category of configuration settings
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ValueEnum)]
 #[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
 pub enum ConfigCategory {
     /// Memory allocation parameters
     Memory,
@@ -68,6 +294,24 @@ pub enum ConfigCategory {
     Logging,
     /// Table and index health checks
     TableIndex,
+    /// Statement-level workload findings from pg_stat_statements
+    Workload,
+    /// Settings added by extensions such as TimescaleDB and Citus
+    Extensions,
+    /// Minor release and end-of-life status of the server version
+    Version,
+    /// Filesystem and kernel settings on the database host
+    Host,
+    /// Kernel settings and resource limits of the server process
+    OperatingSystem,
+    /// Backup recency and recoverability
+    Backup,
+    /// Timeouts and keepalives that decide how quickly failures are noticed
+    Replication,
+    /// Row-level security and other access control
+    Security,
+    /// Column types, constraints and triggers that weaken the schema
+    Schema,
 }
 
 impl ConfigCategory {
@@ -80,10 +324,83 @@ impl ConfigCategory {
             ConfigCategory::Autovacuum => "Autovacuum Configuration",
             ConfigCategory::Logging => "Logging and Diagnostics",
             ConfigCategory::TableIndex => "Table and Index Health",
+            ConfigCategory::Workload => "Workload Analysis",
+            ConfigCategory::Extensions => "Extensions",
+            ConfigCategory::Version => "Server Version",
+            ConfigCategory::Host => "Host and Storage Layout",
+            ConfigCategory::OperatingSystem => "Operating System",
+            ConfigCategory::Backup => "Backup and Recovery",
+            ConfigCategory::Replication => "Replication and Resilience",
+            ConfigCategory::Security => "Security",
+            ConfigCategory::Schema => "Schema Design",
+        }
+    }
+}
+
+/// A size in bytes; serialized as a plain integer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ByteSize(pub i64);
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["bytes", "kB", "MB", "GB"];
+        let mut value = self.0 as f64;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        if unit == 0 {
+            write!(f, "{} bytes", self.0)
+        } else {
+            write!(f, "{value:.0} {}", UNITS[unit])
         }
     }
 }
 
+/// An elapsed time in seconds; serialized as a plain number
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Seconds(pub f64);
+
+/// Table and index health findings, shared by the analyze and workload results
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TableIndexHealth {
+    /// Table bloat information
+    pub bloat_info: Vec<TableBloatInfo>,
+    /// Sequential scan hotspots
+    pub seq_scan_info: Vec<TableSeqScanInfo>,
+    /// Index usage information (unused/inefficient/etc.)
+    pub index_usage_info: Vec<IndexUsageInfo>,
+    /// Findings left out by `--max-findings-per-kind`, keyed by `bloat`,
+    /// `seq_scan` or the index issue
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub not_shown: BTreeMap<String, usize>,
+}
+
+impl TableIndexHealth {
+    pub fn is_empty(&self) -> bool {
+        self.bloat_info.is_empty()
+            && self.seq_scan_info.is_empty()
+            && self.index_usage_info.is_empty()
+    }
+
+    pub fn extend(&mut self, other: TableIndexHealth) {
+        self.bloat_info.extend(other.bloat_info);
+        self.seq_scan_info.extend(other.seq_scan_info);
+        self.index_usage_info.extend(other.index_usage_info);
+        for (kind, count) in other.not_shown {
+            *self.not_shown.entry(kind).or_default() += count;
+        }
+    }
+
+    /// Findings of `kind` beyond the per-kind cap
+    pub fn not_shown(&self, kind: &str) -> usize {
+        self.not_shown.get(kind).copied().unwrap_or(0)
+    }
+}
+
 /// Represents a table bloat analysis result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableBloatInfo {
@@ -94,12 +411,244 @@ pub struct TableBloatInfo {
     pub dead_tup_ratio: f64,
     pub seq_scan: i64,
     pub idx_scan: i64,
-    pub table_size_bytes: i64,
+    pub table_size_bytes: ByteSize,
     pub table_size_pretty: String,
     pub last_autovacuum: Option<String>,
     pub last_autoanalyze: Option<String>,
-    pub seconds_since_last_autovacuum: Option<f64>,
-    pub seconds_since_last_autoanalyze: Option<f64>,
+    pub seconds_since_last_autovacuum: Option<Seconds>,
+    pub seconds_since_last_autoanalyze: Option<Seconds>,
+    #[serde(default)]
+    pub fingerprint: String,
+}
+
+/// Storage and activity of one schema, e.g. one tenant of a schema-per-tenant
+/// database, summed over its tables
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchemaUsage {
+    pub schema: String,
+    pub tables: usize,
+    /// Heap and TOAST
+    pub table_bytes: ByteSize,
+    pub index_bytes: ByteSize,
+    pub live_tuples: i64,
+    pub dead_tuples: i64,
+    /// Rows inserted, updated and deleted since the stats reset
+    pub rows_written: i64,
+    /// Rows returned by sequential and index scans since the stats reset
+    pub rows_read: i64,
+    /// Bloat, sequential scan and index findings on the schema's tables
+    pub findings: usize,
+    /// Mean of the schema's shares of storage, rows written and rows read
+    pub share: f64,
+    /// At least three times an even split between the schemas
+    pub noisy: bool,
+}
+
+/// Cumulative dead-tuple counters of the busiest tables, recorded so two
+/// snapshots can be turned into dead-tuple generation rates
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VacuumActivity {
+    /// Unix time the counters were read at
+    pub captured_at: Seconds,
+    pub tables: Vec<TableVacuumActivity>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableVacuumActivity {
+    pub schema: String,
+    pub table_name: String,
+    pub live_tuples: i64,
+    pub dead_tuples: i64,
+    /// `n_tup_upd + n_tup_del` since the statistics were reset
+    pub dead_tuples_created: i64,
+    pub table_size_bytes: ByteSize,
+}
+
+/// A setting whose value in a configuration file differs from the running one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingChange {
+    pub name: String,
+    /// Running value, in `unit`
+    pub running_value: String,
+    pub unit: Option<String>,
+    /// Value as written in the file, e.g. `8GB`
+    pub staged_value: String,
+    pub sourcefile: String,
+    pub apply: PendingApply,
+    /// Value of the suggestion this change already carries out
+    #[serde(default)]
+    pub covers_suggestion: Option<String>,
+}
+
+/// What it takes for a staged setting to take effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PendingApply {
+    Reload,
+    Restart,
+}
+
+impl PendingApply {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PendingApply::Reload => "reload",
+            PendingApply::Restart => "restart",
+        }
+    }
+}
+
+/// Client backends at one instant, summed from `pg_stat_database.numbackends`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectionSample {
+    /// Server time, RFC 3339 in UTC
+    pub at: String,
+    pub connections: usize,
+}
+
+/// Observed connection peak against max_connections, over this run's samples
+/// and any earlier ones, such as the scheduler's snapshots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionSaturation {
+    pub samples: usize,
+    pub first_at: String,
+    pub last_at: String,
+    pub peak_connections: usize,
+    pub peak_at: String,
+    pub max_connections: usize,
+    /// Peak as a share of max_connections
+    pub peak_utilization: f64,
+    /// Highest count per UTC hour of day, for the hours that have samples
+    pub hourly_peaks: Vec<HourlyPeak>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HourlyPeak {
+    /// 0-23, UTC
+    pub hour: u32,
+    pub peak_connections: usize,
+    pub samples: usize,
+}
+
+/// Estimated memory the backends take outside shared memory, per connection and
+/// at the observed and configured connection counts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionMemory {
+    /// Private memory a backend holds even when idle: catalog caches, plans, buffers
+    pub backend_overhead: ByteSize,
+    pub work_mem: ByteSize,
+    /// Sort and hash nodes assumed to run at once in one active query
+    pub work_mem_nodes: u32,
+    /// Share of connections running a query when sampled, the likelihood a
+    /// connection is using work_mem at all
+    pub active_share: f64,
+    /// Only allocated once a session touches temporary tables
+    pub temp_buffers: ByteSize,
+    /// Overhead plus work_mem weighted by `active_share`
+    pub expected_per_connection: ByteSize,
+    /// Overhead, every work_mem node and a full temp_buffers
+    pub worst_per_connection: ByteSize,
+    pub peak_connections: usize,
+    pub max_connections: usize,
+    pub expected_at_peak: ByteSize,
+    pub worst_at_max_connections: ByteSize,
+    /// Total RAM minus shared_buffers, when the compute size is known
+    pub available: Option<ByteSize>,
+    pub headroom: MemoryHeadroom,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryHeadroom {
+    /// Even every allowed connection at its worst fits in RAM
+    Comfortable,
+    /// The observed peak fits, but max_connections at their worst would not
+    Tight,
+    /// The observed peak is already expected to exceed RAM
+    Overcommitted,
+    /// No compute size was given to compare against
+    Unknown,
+}
+
+impl MemoryHeadroom {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MemoryHeadroom::Comfortable => "Comfortable",
+            MemoryHeadroom::Tight => "Tight",
+            MemoryHeadroom::Overcommitted => "Overcommitted",
+            MemoryHeadroom::Unknown => "Unknown",
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            MemoryHeadroom::Comfortable => {
+                "every allowed connection could use its full work_mem and temp_buffers at once"
+            }
+            MemoryHeadroom::Tight => {
+                "the observed peak fits, but max_connections at full work_mem would exhaust RAM; \
+                 lower max_connections behind a pooler or keep work_mem modest"
+            }
+            MemoryHeadroom::Overcommitted => {
+                "the observed peak is expected to exceed RAM left after shared_buffers; \
+                 lower work_mem or the connection count before the OOM killer does it for you"
+            }
+            MemoryHeadroom::Unknown => "pass --compute to compare against total RAM",
+        }
+    }
+}
+
+/// Managed PostgreSQL service, detected from settings only the provider defines
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ManagedPlatform {
+    /// Azure Database for PostgreSQL flexible server
+    AzureFlexibleServer,
+    /// Google Cloud SQL for PostgreSQL
+    CloudSql,
+}
+
+impl ManagedPlatform {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ManagedPlatform::AzureFlexibleServer => "Azure Database for PostgreSQL flexible server",
+            ManagedPlatform::CloudSql => "Cloud SQL for PostgreSQL",
+        }
+    }
+}
+
+/// Point-in-time recovery verdict with what stands in its way
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PitrReadiness {
+    pub ready: bool,
+    pub gaps: Vec<String>,
+    /// Observations that are not gaps, such as delayed standbys
+    #[serde(default)]
+    pub notes: Vec<String>,
+}
+
+/// Provider CLI commands that apply the suggested parameter changes, since
+/// `ALTER SYSTEM` is not available on managed services
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManagedRemediation {
+    pub platform: ManagedPlatform,
+    pub commands: Vec<String>,
+    /// Commands setting the changed flags back to their current values
+    #[serde(default)]
+    pub rollback: Vec<String>,
+    /// Suggested parameters the provider does not let users change
+    pub skipped: Vec<String>,
+    pub notes: Vec<String>,
+}
+
+/// `ALTER SYSTEM` statements applying the suggested settings on a self-hosted
+/// server, with the statements restoring the values they replace
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SettingsRemediation {
+    pub statements: Vec<String>,
+    pub rollback: Vec<String>,
+    /// Changed parameters that only take effect after a restart
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub restart_required: Vec<String>,
 }
 
 /// Represents an index usage analysis
@@ -110,7 +659,7 @@ pub struct IndexUsageInfo {
     pub table_name: String,
     pub index_name: String,
     pub key_columns: Vec<String>,
-    pub index_size_bytes: i64,
+    pub index_size_bytes: ByteSize,
     pub index_size_pretty: String,
     pub scans: i64,
     pub tuples_read: i64,
@@ -118,10 +667,52 @@ pub struct IndexUsageInfo {
     pub avg_tuples_per_scan: f64,
     pub heap_fetch_ratio: f64,
     pub table_live_tup: Option<i64>,
+    /// Inserts plus non-HOT updates on the owning table since the stats reset;
+    /// each one also has to maintain this index
+    #[serde(default)]
+    pub table_index_writes: Option<i64>,
+    /// Sequential plus index scans of the owning table since the stats reset,
+    /// the evidence behind an unused-index finding
+    #[serde(default)]
+    pub table_scans: Option<i64>,
+    /// `pg_get_indexdef` output, used to generate rollback DDL
+    #[serde(default)]
+    pub index_definition: Option<String>,
+    /// For missing partial indexes: the predicate the index should carry
+    #[serde(default)]
+    pub partial_predicate: Option<String>,
+    /// For missing partial indexes: estimated size once rebuilt with the predicate
+    #[serde(default)]
+    pub estimated_size_bytes: Option<ByteSize>,
     pub is_unique: bool,
     pub enforces_constraint: bool,
     pub is_expression: bool,
     pub is_partial: bool,
+    #[serde(default)]
+    pub fingerprint: String,
+}
+
+/// Staged remediation for an index flagged as unused
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexRemovalPlan {
+    pub schema: String,
+    pub table_name: String,
+    pub index_name: String,
+    /// Usage query to run on the primary and every replica, since idx_scan is per node
+    pub monitor_query: String,
+    pub notes: Vec<String>,
+    /// Remediation scripts; only generated with `--emit-ddl`
+    pub ddl: Option<IndexRemovalDdl>,
+}
+
+/// SQL for each stage of removing an unused index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexRemovalDdl {
+    /// Restarts the usage counters so the observation window starts clean
+    pub reset_stats: String,
+    pub drop_index: String,
+    /// Recreates the index if something turns out to need it
+    pub rollback: Option<String>,
 }
 
 /// Represents sequential scan hotspots that likely require new indexes
@@ -132,8 +723,10 @@ pub struct TableSeqScanInfo {
     pub seq_scan: i64,
     pub idx_scan: i64,
     pub live_tuples: i64,
-    pub table_size_bytes: i64,
+    pub table_size_bytes: ByteSize,
     pub table_size_pretty: String,
+    #[serde(default)]
+    pub fingerprint: String,
 }
 
 /// Types of index issues detected during analysis
@@ -145,6 +738,35 @@ pub enum IndexIssueKind {
     FailedIndexOnly,
     MissingPartialIndex,
     BrinCandidate,
+    MissingSpatialIndex,
+}
+
+impl IndexIssueKind {
+    /// Name in JSON output and in `TableIndexHealth::not_shown`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IndexIssueKind::Unused => "unused",
+            IndexIssueKind::LowSelectivity => "low_selectivity",
+            IndexIssueKind::FailedIndexOnly => "failed_index_only",
+            IndexIssueKind::MissingPartialIndex => "missing_partial_index",
+            IndexIssueKind::BrinCandidate => "brin_candidate",
+            IndexIssueKind::MissingSpatialIndex => "missing_spatial_index",
+        }
+    }
+
+    /// Inverse of [`IndexIssueKind::as_str`]
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            IndexIssueKind::Unused,
+            IndexIssueKind::LowSelectivity,
+            IndexIssueKind::FailedIndexOnly,
+            IndexIssueKind::MissingPartialIndex,
+            IndexIssueKind::BrinCandidate,
+            IndexIssueKind::MissingSpatialIndex,
+        ]
+        .into_iter()
+        .find(|kind| kind.as_str() == name)
+    }
 }
 
 /// Represents system statistics
@@ -156,10 +778,391 @@ pub struct SystemStats {
     pub total_memory_gb: Option<f64>,
     pub cpu_count: Option<usize>,
     pub connection_count: Option<usize>,
+    /// Connections running a query when `connection_count` was read
+    pub active_connection_count: Option<usize>,
+    /// Highest `connection_count` seen, including the start of a `--sample-window`
+    pub peak_connection_count: Option<usize>,
+    /// Client backends counted during this run, several across a `--sample-window`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub connection_samples: Vec<ConnectionSample>,
     pub storage_type: crate::config::StorageType,
     pub workload_type: crate::config::WorkloadType,
+    /// Explicit recommendation profile; derived from `workload_type` when unset
+    pub profile: Option<crate::profile::Profile>,
     pub checkpoints_timed: Option<i64>,
     pub checkpoints_req: Option<i64>,
+    /// Shared buffers written by the checkpointer, background writer and backends
+    #[serde(default)]
+    pub buffer_writes: Option<BufferWrites>,
+    /// WAL generated, from `pg_stat_wal` (PG14+)
+    pub wal_bytes: Option<i64>,
+    /// Full-page images among the WAL above
+    pub wal_fpi: Option<i64>,
+    /// Seconds the WAL counters cover
+    pub wal_stats_age_secs: Option<u64>,
+    /// Temporary files written by queries that spilled past `work_mem`, from
+    /// `pg_stat_database`
+    #[serde(default)]
+    pub temp_files: Option<i64>,
+    /// Bytes written to those temporary files
+    #[serde(default)]
+    pub temp_bytes: Option<i64>,
+    /// Length of the `--sample-window`; when set, counters above are deltas over it
+    pub sample_window_secs: Option<u64>,
+    /// When the database's cumulative statistics started accumulating: the last
+    /// stats reset, or server start if they were never reset
+    pub stats_reset_at: Option<String>,
+    /// Seconds of activity covered by the cumulative statistics
+    pub stats_age_secs: Option<u64>,
+    /// Pointer width of the server build (32 or 64), from `version()`
+    pub server_bits: Option<u32>,
+    /// Extensions installed in the analyzed database, from pg_extension
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    /// Active Citus worker nodes, from pg_dist_node
+    pub citus_workers: Option<usize>,
+    /// Scheduled TimescaleDB background jobs
+    pub timescale_jobs: Option<usize>,
+    /// pgvector columns and their approximate-nearest-neighbour indexes
+    #[serde(default)]
+    pub vector_columns: Vec<VectorColumn>,
+    /// Statement and session rates from pg_stat_statements and pg_stat_database
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub statement_rates: Option<StatementRates>,
+    /// TOAST sizes and column compression methods (PG14+)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toast: Option<ToastCompression>,
+    /// Days of server log to keep, from the config file's `log_retention_days`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_retention_days: Option<usize>,
+    /// Replication slots on the analyzed server
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replication_slots: Vec<ReplicationSlot>,
+    /// WAL archiver counters from pg_stat_archiver
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archiver: Option<ArchiverStatus>,
+    /// Whether the analyzed server is a standby and how many standbys stream
+    /// from it, from pg_is_in_recovery() and pg_stat_replication
+    pub replication_role: Option<ReplicationRole>,
+    /// Tables with row-level security enabled or policies defined
+    pub row_security_tables: Vec<RowSecurityTable>,
+    /// Whether PUBLIC holds CREATE on the public schema
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_schema_create: Option<bool>,
+    /// Foreign keys and check constraints added NOT VALID and never validated
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unvalidated_constraints: Vec<UnvalidatedConstraint>,
+    /// Disabled triggers on user tables
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_triggers: Vec<DisabledTrigger>,
+    /// Extended statistics objects that ANALYZE has not built yet
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unanalyzed_extended_statistics: Vec<ExtendedStatistics>,
+    /// Idle sessions whose last statement opened or read a cursor
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cursor_sessions: Vec<CursorSession>,
+    /// `pg_temp_N` schemas holding tables with no backend in their slot
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub orphaned_temp_schemas: Vec<OrphanedTempSchema>,
+    /// Recovery settings of the configured replicas
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub standbys: Vec<StandbySettings>,
+}
+
+/// A table with row-level security enabled, policies defined, or both
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RowSecurityTable {
+    pub schema: String,
+    pub table_name: String,
+    pub owner: String,
+    /// `relrowsecurity`: ENABLE ROW LEVEL SECURITY
+    pub enabled: bool,
+    /// `relforcerowsecurity`: policies apply to the owner too
+    pub forced: bool,
+    pub policies: Vec<RowSecurityPolicy>,
+}
+
+/// One row of `pg_policies`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RowSecurityPolicy {
+    pub name: String,
+    /// PERMISSIVE policies are OR-ed together, RESTRICTIVE ones AND-ed
+    pub permissive: bool,
+    pub roles: Vec<String>,
+    /// ALL, SELECT, INSERT, UPDATE or DELETE
+    pub command: String,
+    pub using: Option<String>,
+    pub with_check: Option<String>,
+}
+
+/// An idle session from `pg_stat_activity` whose last statement was DECLARE,
+/// FETCH or MOVE, i.e. one that most likely still holds a cursor open
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CursorSession {
+    pub pid: i32,
+    pub usename: Option<String>,
+    pub application_name: String,
+    /// `idle in transaction`, `idle in transaction (aborted)` or `idle`
+    pub state: String,
+    pub query: String,
+    /// Seconds since the session went idle
+    pub idle_secs: i64,
+    /// Transactions the session's snapshot holds back, from `age(backend_xmin)`
+    pub xmin_age: Option<i64>,
+}
+
+/// Shared buffers written out, by who wrote them, since the counters were
+/// last reset. Backends writing a large share means the background writer
+/// and checkpoints are not keeping enough clean buffers available.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct BufferWrites {
+    pub checkpointer: Option<i64>,
+    pub background_writer: Option<i64>,
+    pub backends: Option<i64>,
+}
+
+/// Temp tables left in a `pg_temp_N` schema by a backend that is gone
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrphanedTempSchema {
+    pub schema: String,
+    pub tables: usize,
+    /// Tables with their indexes and TOAST
+    pub size: ByteSize,
+    /// Oldest `age(relfrozenxid)` among the tables
+    pub xid_age: i64,
+    /// Up to five table names, largest first
+    pub largest_tables: Vec<String>,
+}
+
+/// A `CREATE STATISTICS` object
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExtendedStatistics {
+    pub schema: String,
+    pub name: String,
+    /// Schema-qualified table the statistics are on
+    pub table_name: String,
+}
+
+/// A foreign key or check constraint with `convalidated = false`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UnvalidatedConstraint {
+    pub schema: String,
+    pub table_name: String,
+    pub name: String,
+    /// `FOREIGN KEY` or `CHECK`
+    pub kind: String,
+}
+
+/// A trigger disabled with ALTER TABLE ... DISABLE TRIGGER
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DisabledTrigger {
+    pub schema: String,
+    pub table_name: String,
+    /// The trigger name, or the foreign key's name for its internal triggers
+    pub name: String,
+    /// An internal trigger enforcing a foreign key, disabled by DISABLE TRIGGER ALL
+    pub foreign_key: bool,
+}
+
+/// The analyzed server's place in a replication setup
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplicationRole {
+    pub in_recovery: bool,
+    /// Connected WAL senders, i.e. streaming standbys and subscribers
+    pub wal_senders: usize,
+}
+
+/// A replication slot and the WAL it holds back
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplicationSlot {
+    pub slot_name: String,
+    /// `physical` or `logical`
+    pub slot_type: String,
+    pub active: bool,
+    /// WAL between the current position and the slot's restart_lsn
+    pub retained_wal: ByteSize,
+    /// `reserved`, `extended`, `unreserved` or `lost` (PG13+)
+    pub wal_status: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArchiverStatus {
+    pub archived_count: i64,
+    pub failed_count: i64,
+    /// RFC 3339, UTC
+    pub last_archived_at: Option<String>,
+    pub last_failed_at: Option<String>,
+    pub last_failed_wal: Option<String>,
+}
+
+/// Recovery settings read from a configured replica
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StandbySettings {
+    /// `host:port`
+    pub label: String,
+    pub in_recovery: bool,
+    pub restore_command: String,
+    pub recovery_min_apply_delay_ms: u64,
+    pub primary_slot_name: String,
+}
+
+/// How fast statements and sessions arrive, for log volume estimates
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatementRates {
+    /// Seconds the pg_stat_statements counters cover
+    pub window_secs: u64,
+    /// Calls grouped by the pgaudit class they are logged under, which also
+    /// decides what `log_statement` logs
+    pub classes: Vec<StatementClassRate>,
+    /// Calls per second estimated to run longer than each threshold
+    pub slower_than: Vec<SlowStatementRate>,
+    /// New sessions per second across all databases (PG14+)
+    pub sessions_per_second: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SlowStatementRate {
+    pub threshold_ms: u64,
+    pub per_second: f64,
+    pub mean_statement_bytes: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatementClassRate {
+    pub class: String,
+    pub per_second: f64,
+    /// Mean normalized statement length, weighted by calls
+    pub mean_statement_bytes: f64,
+}
+
+/// Compression methods available to TOAST and the tables holding the most
+/// TOAST data
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToastCompression {
+    /// Whether the server was built with lz4
+    pub lz4_available: bool,
+    pub tables: Vec<ToastTable>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToastTable {
+    pub schema: String,
+    pub table_name: String,
+    pub toast_size_bytes: ByteSize,
+    pub live_tuples: i64,
+    /// Rows inserted or updated since statistics were reset
+    pub writes: i64,
+    /// Compressible text, json, jsonb, xml and bytea columns
+    pub columns: Vec<ToastColumn>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToastColumn {
+    pub name: String,
+    pub type_name: String,
+    /// `pglz` or `lz4` when set on the column; `None` follows
+    /// default_toast_compression
+    pub compression: Option<String>,
+}
+
+/// A pgvector `vector`, `halfvec` or `sparsevec` column
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VectorColumn {
+    pub schema: String,
+    pub table_name: String,
+    pub column_name: String,
+    pub type_name: String,
+    /// Declared dimensions; `None` for a column declared without them
+    pub dimensions: Option<usize>,
+    /// Table rows as of the last ANALYZE
+    pub estimated_rows: i64,
+    /// HNSW and IVFFlat indexes leading with the column
+    pub indexes: Vec<VectorIndex>,
+    /// Calls of pg_stat_statements entries using a distance operator on the
+    /// column; `None` when pg_stat_statements cannot be read
+    pub distance_calls: Option<i64>,
+    /// The distance operator those statements use most, e.g. `<=>`
+    pub distance_operator: Option<String>,
+}
+
+/// An HNSW or IVFFlat index on a [`VectorColumn`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VectorIndex {
+    pub name: String,
+    /// `hnsw` or `ivfflat`
+    pub method: String,
+    /// IVFFlat `lists` storage parameter, when set explicitly
+    pub lists: Option<usize>,
+    pub size_bytes: ByteSize,
+}
+
+impl SystemStats {
+    /// Recommendation profile in effect for this analysis
+    pub fn profile(&self) -> crate::profile::Profile {
+        self.profile
+            .unwrap_or_else(|| crate::profile::Profile::for_workload(self.workload_type))
+    }
+}
+
+/// Condensed view of an analysis, printed with `--summary`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportSummary {
+    pub health: HealthScore,
+    pub total_suggestions: usize,
+    pub level_counts: LevelCounts,
+    /// Most severe suggestions across all categories
+    pub top_suggestions: Vec<RankedSuggestion>,
+    pub table_index: TableIndexHeadline,
+}
+
+/// One database's line in the summary printed after analyzing a config file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FleetEntry {
+    pub database: String,
+    pub host: String,
+    pub health: HealthScore,
+    pub level_counts: LevelCounts,
+}
+
+/// Number of suggestions at each level
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelCounts {
+    pub critical: usize,
+    pub important: usize,
+    pub recommended: usize,
+    pub info: usize,
+}
+
+impl LevelCounts {
+    /// Counts paired with their level, most severe first
+    pub fn iter(&self) -> [(SuggestionLevel, usize); 4] {
+        [
+            (SuggestionLevel::Critical, self.critical),
+            (SuggestionLevel::Important, self.important),
+            (SuggestionLevel::Recommended, self.recommended),
+            (SuggestionLevel::Info, self.info),
+        ]
+    }
+}
+
+/// A suggestion together with the category it was filed under
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankedSuggestion {
+    pub category: ConfigCategory,
+    #[serde(flatten)]
+    pub suggestion: ConfigSuggestion,
+}
+
+/// Headline numbers from the table and index health checks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableIndexHeadline {
+    pub bloated_tables: usize,
+    pub seq_scan_hotspots: usize,
+    pub unused_indexes: usize,
+    /// Combined size of the unused indexes
+    pub unused_index_size: ByteSize,
+    /// Low-selectivity, failed index-only, missing partial and BRIN findings
+    pub other_index_findings: usize,
 }
 
 /// Overall analysis results
@@ -169,29 +1172,165 @@ pub struct AnalysisResults {
     pub params: HashMap<String, PgConfigParam>,
     /// Suggestions grouped by category
     pub suggestions_by_category: HashMap<ConfigCategory, Vec<ConfigSuggestion>>,
-    /// Table bloat information
-    pub bloat_info: Vec<TableBloatInfo>,
-    /// Sequential scan hotspots
-    pub seq_scan_info: Vec<TableSeqScanInfo>,
-    /// Index usage information (unused/inefficient/etc.)
-    pub index_usage_info: Vec<IndexUsageInfo>,
+    /// Table bloat, sequential scan and index usage findings
+    #[serde(flatten)]
+    pub table_health: TableIndexHealth,
+    /// Staged removal plans for unused indexes
+    #[serde(default)]
+    pub index_removal_plans: Vec<IndexRemovalPlan>,
     /// System statistics
     pub system_stats: SystemStats,
+    /// Caveats about how far the results can be trusted
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Slow queries and index candidates, when run with `--with-workload`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workload: Option<WorkloadResults>,
+    /// Per-schema usage ranked by share, when run with `--group-by-schema`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schema_usage: Vec<SchemaUsage>,
+    /// Dead-tuple counters read by the table health checks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vacuum_activity: Option<VacuumActivity>,
+    /// Per-connection memory estimate and headroom verdict
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection_memory: Option<ConnectionMemory>,
+    /// Sampled connection peak against max_connections
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection_saturation: Option<ConnectionSaturation>,
+    /// Whether WAL archiving, standbys and slots support point-in-time recovery
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pitr_readiness: Option<PitrReadiness>,
+    /// Provider commands for the suggestions, when the server is a managed service
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub managed_remediation: Option<ManagedRemediation>,
+    /// `ALTER SYSTEM` script for the suggestions and its rollback, with `--emit-ddl`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings_remediation: Option<SettingsRemediation>,
+    /// Settings staged in a configuration file, e.g. by `ALTER SYSTEM`, that the
+    /// server does not run with yet
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pending_changes: Vec<PendingChange>,
+    /// Shell commands that apply the Operating System suggestions on the
+    /// database host
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub host_commands: Vec<String>,
+    /// First/last sighting, acknowledgement and resolution of every finding
+    /// the scheduler's snapshots have seen, including resolved ones
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub finding_lifecycle: Vec<FindingLifecycle>,
+    /// Tool version, machine, time and target of the run that produced these results
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run: Option<RunMetadata>,
 }
 
 impl AnalysisResults {
+    /// Re-renders every suggestion's rationale in `lang`
+    pub fn localize(&mut self, lang: Lang) {
+        for suggestion in self.suggestions_by_category.values_mut().flatten() {
+            suggestion.localize(lang);
+        }
+    }
+
+    /// Every suggestion with its category, most severe first and then by category
+    pub fn ranked_suggestions(&self) -> Vec<(ConfigCategory, &ConfigSuggestion)> {
+        let mut ranked: Vec<_> = self
+            .suggestions_by_category
+            .iter()
+            .flat_map(|(category, suggestions)| suggestions.iter().map(|s| (*category, s)))
+            .collect();
+        ranked.sort_by_key(|(category, suggestion)| (suggestion.level.rank(), category.as_str()));
+        ranked
+    }
+
+    /// Health score, level counts, the `top` most severe suggestions and
+    /// table/index headline numbers
+    pub fn summary(&self, top: usize) -> ReportSummary {
+        let ranked = self.ranked_suggestions();
+        let mut level_counts = LevelCounts::default();
+        for (_, suggestion) in &ranked {
+            match suggestion.level {
+                SuggestionLevel::Critical => level_counts.critical += 1,
+                SuggestionLevel::Important => level_counts.important += 1,
+                SuggestionLevel::Recommended => level_counts.recommended += 1,
+                SuggestionLevel::Info => level_counts.info += 1,
+            }
+        }
+
+        let health = &self.table_health;
+        let (unused, other): (Vec<_>, Vec<_>) = health
+            .index_usage_info
+            .iter()
+            .partition(|index| index.issue == IndexIssueKind::Unused);
+        let table_index = TableIndexHeadline {
+            bloated_tables: health.bloat_info.len(),
+            seq_scan_hotspots: health.seq_scan_info.len(),
+            unused_indexes: unused.len(),
+            unused_index_size: ByteSize(unused.iter().map(|index| index.index_size_bytes.0).sum()),
+            other_index_findings: other.len(),
+        };
+
+        ReportSummary {
+            health: scoring::score(self),
+            total_suggestions: ranked.len(),
+            level_counts,
+            top_suggestions: ranked
+                .into_iter()
+                .take(top)
+                .map(|(category, suggestion)| RankedSuggestion {
+                    category,
+                    suggestion: suggestion.clone(),
+                })
+                .collect(),
+            table_index,
+        }
+    }
+
+    /// Parameters set away from their defaults, sorted by name
+    pub fn changed_params(&self) -> Vec<&PgConfigParam> {
+        let mut params: Vec<_> = self.params.values().filter(|p| p.is_changed()).collect();
+        params.sort_by_key(|p| &p.name);
+        params
+    }
+
+    /// Parameters whose new value waits for a server restart, sorted by name
+    pub fn pending_restart_params(&self) -> Vec<&PgConfigParam> {
+        let mut params: Vec<_> = self.params.values().filter(|p| p.pending_restart).collect();
+        params.sort_by_key(|p| &p.name);
+        params
+    }
+
     pub fn merge(&mut self, other: AnalysisResults) {
         self.params.extend(other.params);
+        self.warnings.extend(other.warnings);
         for (category, suggestions) in other.suggestions_by_category {
             self.suggestions_by_category
                 .entry(category)
                 .or_default()
                 .extend(suggestions);
         }
-        self.bloat_info.extend(other.bloat_info);
-        self.seq_scan_info.extend(other.seq_scan_info);
-        self.index_usage_info.extend(other.index_usage_info);
+        self.table_health.extend(other.table_health);
+        self.index_removal_plans.extend(other.index_removal_plans);
         self.system_stats = other.system_stats;
+        if other.workload.is_some() {
+            self.workload = other.workload;
+        }
+        if other.vacuum_activity.is_some() {
+            self.vacuum_activity = other.vacuum_activity;
+        }
+        if other.connection_memory.is_some() {
+            self.connection_memory = other.connection_memory;
+        }
+        if other.connection_saturation.is_some() {
+            self.connection_saturation = other.connection_saturation;
+        }
+        if other.managed_remediation.is_some() {
+            self.managed_remediation = other.managed_remediation;
+        }
+        if other.settings_remediation.is_some() {
+            self.settings_remediation = other.settings_remediation;
+        }
+        self.host_commands.extend(other.host_commands);
     }
 }
 
@@ -210,6 +1349,12 @@ pub enum SlowQueryKind {
     MeanTime,
     SharedBlksRead,
     TempBlksWritten,
+    /// Only reported when `pg_stat_statements` exposes `wal_bytes` (PostgreSQL 13+)
+    WalBytes,
+    /// Statements spending most of their time waiting on block I/O; needs `track_io_timing`
+    IoBound,
+    /// Statements spending little of their time on block I/O; needs `track_io_timing`
+    CpuBound,
 }
 
 /// Represents a single slow query entry from pg_stat_statements.
@@ -230,7 +1375,25 @@ pub struct SlowQueryInfo {
     pub temp_blks_written_per_call: Option<f64>,
     pub wal_bytes: Option<i64>,
     pub wal_bytes_per_call: Option<f64>,
+    /// Full-page images written; the first change to a page after a checkpoint logs the whole page
+    #[serde(default)]
+    pub wal_fpi: Option<i64>,
+    /// Time spent reading and writing blocks, when `track_io_timing` is on
+    #[serde(default)]
+    pub io_time_ms: Option<f64>,
+    /// Share of `total_time_ms` spent on block I/O
+    #[serde(default)]
+    pub io_time_share: Option<f64>,
     pub query_text: String,
+    /// queryids of entries that differ only in literals or list lengths and were
+    /// summed into this one
+    #[serde(default)]
+    pub merged_queryids: Vec<i64>,
+    /// Roles that ran the statement; exactly one with `--group-by role`
+    #[serde(default)]
+    pub roles: Vec<String>,
+    #[serde(default)]
+    pub database: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -265,6 +1428,12 @@ pub struct QueryIndexCandidate {
     pub schema: String,
     pub table: String,
     pub columns: Vec<String>,
+    /// Index type and operator class when a plain B-tree will not do, e.g. `gin`
+    #[serde(default)]
+    pub access_method: Option<String>,
+    /// `CREATE INDEX CONCURRENTLY` statement that builds the candidate
+    #[serde(default)]
+    pub ddl: String,
     pub reason: String,
     pub confidence: WorkloadFindingConfidence,
     pub evidence: QueryIndexEvidence,
@@ -286,6 +1455,15 @@ pub struct WorkloadMetadata {
     pub parsed_queries: usize,
     pub parse_failures: usize,
     pub suppressed_candidates: usize,
+    /// Replicas whose pg_stat_statements were merged into the totals
+    #[serde(default)]
+    pub replica_sources: Vec<String>,
+    /// Whether `track_io_timing` was on, so statements could be ranked by I/O time
+    #[serde(default)]
+    pub track_io_timing: Option<bool>,
+    /// Query text had literals and object names replaced (`--redact-queries`)
+    #[serde(default)]
+    pub queries_redacted: bool,
 }
 
 impl Default for WorkloadMetadata {
@@ -300,6 +1478,9 @@ impl Default for WorkloadMetadata {
             parsed_queries: 0,
             parse_failures: 0,
             suppressed_candidates: 0,
+            replica_sources: Vec::new(),
+            track_io_timing: None,
+            queries_redacted: false,
         }
     }
 }
@@ -320,16 +1501,191 @@ pub struct WorkloadResults {
     pub coverage_stats: WorkloadCoverageStats,
     pub slow_query_groups: Vec<SlowQueryGroup>,
     pub query_index_candidates: Vec<QueryIndexCandidate>,
-    pub index_usage_info: Vec<IndexUsageInfo>,
-    pub seq_scan_info: Vec<TableSeqScanInfo>,
-    pub bloat_info: Vec<TableBloatInfo>,
+    #[serde(flatten)]
+    pub table_health: TableIndexHealth,
     pub warnings: Vec<String>,
     pub parse_failures: usize,
+    /// Query shapes to fix in the application rather than with an index
+    #[serde(default)]
+    pub application_patterns: Vec<ApplicationPatternFinding>,
+    /// Statement time per role, filled with `--group-by role`
+    #[serde(default)]
+    pub role_summaries: Vec<RoleWorkloadSummary>,
+    /// Lock waits and autovacuum runs, when the workload was read from server logs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_findings: Option<LogFindings>,
+}
+
+/// What `postgreat logs` found beyond statement durations
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LogFindings {
+    pub files: Vec<String>,
+    /// Log entries read, counting multi-line entries once
+    pub entries: usize,
+    pub duration_entries: usize,
+    pub temp_files: usize,
+    pub temp_bytes: ByteSize,
+    /// Statements that waited longer than `deadlock_timeout`, most waits first
+    pub lock_waits: Vec<LockWaitFinding>,
+    /// Tables autovacuum spent the most time on, longest first
+    pub autovacuum_runs: Vec<AutovacuumLogFinding>,
+    /// auto_explain entries read, including those not in JSON format
+    #[serde(default)]
+    pub plan_entries: usize,
+    /// auto_explain plans per statement, those with regressions first
+    #[serde(default)]
+    pub plans: Vec<LoggedPlanFinding>,
+}
+
+/// auto_explain JSON plans of one statement fingerprint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggedPlanFinding {
+    /// Same ID as the statement in the slow query groups
+    pub queryid: i64,
+    pub query_text: String,
+    pub plans: usize,
+    pub total_time_ms: f64,
+    pub max_time_ms: f64,
+    /// Whether the plans carried actual row counts (`auto_explain.log_analyze`)
+    pub analyzed: bool,
+    /// Plan nodes across every logged plan, most rows first
+    pub nodes: Vec<PlanNodeStat>,
+    pub regressions: Vec<PlanRegression>,
+}
+
+/// One plan node type, with its relation for scans, summed over logged plans
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanNodeStat {
+    pub node: String,
+    pub count: usize,
+    /// Actual rows times loops, or the planner estimate without `log_analyze`
+    pub rows: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanRegressionKind {
+    /// A nested loop that ran its inner side for a large number of outer rows
+    NestedLoopLargeOuter,
+    /// A sort that did not fit in work_mem and went to disk
+    SortSpill,
+    /// A hash join whose hash table was split into batches on disk
+    HashSpill,
+    /// Sort, hash and materializing nodes whose combined work_mem budget,
+    /// across parallel workers, reaches gigabytes
+    WorkMemExposure,
+}
+
+/// A plan shape seen in one or more of a statement's logged plans
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanRegression {
+    pub kind: PlanRegressionKind,
+    /// Logged plans that had it
+    pub plans: usize,
+    /// The worst occurrence, e.g. `outer side 250000 rows into Index Scan on items`
+    pub detail: String,
+    pub suggestion: String,
+}
+
+/// `log_lock_waits` entries of one statement and lock mode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockWaitFinding {
+    pub lock_mode: String,
+    pub waits: usize,
+    /// Longest wait logged, from the "still waiting" or "acquired" entries
+    pub max_wait_ms: f64,
+    /// Empty when the entries had no STATEMENT line
+    pub query_text: String,
+}
+
+/// `log_autovacuum_min_duration` entries of one table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutovacuumLogFinding {
+    pub table: String,
+    pub vacuums: usize,
+    pub analyzes: usize,
+    pub total_elapsed_secs: f64,
+    pub max_elapsed_secs: f64,
+}
+
+/// Time spent in `pg_stat_statements` by one role across every statement it ran
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleWorkloadSummary {
+    pub role: String,
+    pub statements: i64,
+    pub calls: i64,
+    pub total_time_ms: f64,
+    pub total_time_pct: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApplicationPatternKind {
+    /// `OFFSET` pagination that reads and discards every skipped row
+    OffsetPagination,
+    /// A statement generating a large share of the WAL
+    WalHeavyWrites,
+    /// Unqualified table names that exist in several schemas, resolved by `search_path`
+    AmbiguousSchema,
+    /// Equality filters on columns that determine each other, which the planner
+    /// treats as independent without extended statistics
+    CorrelatedColumns,
+}
+
+/// A statement whose cost comes from how the application queries, not from a missing index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplicationPatternFinding {
+    pub kind: ApplicationPatternKind,
+    pub queryid: i64,
+    pub calls: i64,
+    pub mean_time_ms: f64,
+    pub evidence: String,
+    pub suggestion: String,
+    pub query_text: String,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
+    use std::time::Duration;
+
+    #[rstest]
+    #[case::blocks("16384", "integer", Some("8kB"), ParamValue::Bytes(128 * 1024 * 1024))]
+    #[case::kilobytes("4096", "integer", Some("kB"), ParamValue::Bytes(4 * 1024 * 1024))]
+    #[case::milliseconds(
+        "1000",
+        "integer",
+        Some("ms"),
+        ParamValue::Duration(Duration::from_secs(1))
+    )]
+    #[case::minutes(
+        "5",
+        "integer",
+        Some("min"),
+        ParamValue::Duration(Duration::from_secs(300))
+    )]
+    #[case::fractional_ms(
+        "2",
+        "real",
+        Some("ms"),
+        ParamValue::Duration(Duration::from_millis(2))
+    )]
+    #[case::disabled_sentinel("-1", "integer", Some("ms"), ParamValue::Integer(-1))]
+    #[case::integer("100", "integer", None, ParamValue::Integer(100))]
+    #[case::real("0.2", "real", None, ParamValue::Real(0.2))]
+    #[case::whole_real("4", "real", None, ParamValue::Real(4.0))]
+    #[case::bool("on", "bool", None, ParamValue::Bool(true))]
+    #[case::enumeration("replica", "enum", None, ParamValue::Enum("replica".into()))]
+    #[case::inferred("3", "", None, ParamValue::Integer(3))]
+    fn param_values_are_parsed_from_vartype_and_unit(
+        #[case] setting: &str,
+        #[case] vartype: &str,
+        #[case] unit: Option<&str>,
+        #[case] expected: ParamValue,
+    ) {
+        assert_eq!(ParamValue::parse(setting, vartype, unit), expected);
+    }
 
     #[test]
     fn analysis_results_are_serializable() {
@@ -342,9 +1698,69 @@ mod tests {
                 suggested_value: "8GB".into(),
                 level: SuggestionLevel::Critical,
                 rationale: "test".into(),
+                messages: Vec::new(),
+                fingerprint: String::new(),
             }],
         );
 
         serde_json::to_string(&results).expect("AnalysisResults should serialize");
     }
+
+    #[test]
+    fn notes_are_re_rendered_when_localized() {
+        let mut suggestion = ConfigSuggestion {
+            parameter: "max_connections".into(),
+            current_value: "100".into(),
+            suggested_value: "400".into(),
+            level: SuggestionLevel::Important,
+            rationale: "Inline rationale.".into(),
+            messages: Vec::new(),
+            fingerprint: String::new(),
+        };
+        suggestion
+            .push_note(Message::new("note.also_flagged").arg("category", "Memory Configuration"));
+        assert_eq!(
+            suggestion.rationale,
+            "Inline rationale. Also flagged by Memory Configuration:"
+        );
+
+        suggestion.localize(Lang::De);
+        assert_eq!(
+            suggestion.rationale,
+            "Inline rationale. Ebenfalls gemeldet von Memory Configuration:"
+        );
+    }
+
+    #[test]
+    fn table_health_and_units_keep_the_flat_json_shape() {
+        let mut results = AnalysisResults::default();
+        results.table_health.seq_scan_info.push(TableSeqScanInfo {
+            schema: "public".into(),
+            table_name: "orders".into(),
+            seq_scan: 10,
+            idx_scan: 0,
+            live_tuples: 100,
+            table_size_bytes: ByteSize(8192),
+            table_size_pretty: "8192 bytes".into(),
+            fingerprint: String::new(),
+        });
+
+        let json = serde_json::to_value(&results).expect("AnalysisResults should serialize");
+        assert_eq!(json["seq_scan_info"][0]["table_size_bytes"], 8192);
+        assert!(json["bloat_info"].as_array().is_some_and(Vec::is_empty));
+        assert!(json.get("table_health").is_none());
+
+        let parsed: AnalysisResults =
+            serde_json::from_value(json).expect("AnalysisResults should deserialize");
+        assert_eq!(
+            parsed.table_health.seq_scan_info[0].table_size_bytes,
+            ByteSize(8192)
+        );
+    }
+
+    #[test]
+    fn byte_sizes_display_in_binary_units() {
+        assert_eq!(ByteSize(512).to_string(), "512 bytes");
+        assert_eq!(ByteSize(25 * 1024 * 1024).to_string(), "25 MB");
+    }
 }
@@ -0,0 +1,195 @@
+use crate::config::DbConfig;
+use crate::models::{AnalysisResults, LevelCounts};
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+/// Liveness, readiness and last-run state of a long-running mode (`api`,
+/// `scheduler`), served by [`router`]
+#[derive(Clone, Default)]
+pub struct Probes {
+    state: Arc<Mutex<ProbeState>>,
+}
+
+#[derive(Default)]
+struct ProbeState {
+    ready: bool,
+    last_run: Option<LastRun>,
+}
+
+/// Summary of the most recently finished analysis, returned by `GET /last-run`
+#[derive(Debug, Clone, Serialize)]
+pub struct LastRun {
+    pub database: String,
+    pub host: String,
+    /// RFC 3339, UTC
+    pub started_at: String,
+    pub finished_at: String,
+    pub duration_ms: i64,
+    #[serde(flatten)]
+    pub outcome: LastRunOutcome,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum LastRunOutcome {
+    Succeeded {
+        total_suggestions: usize,
+        level_counts: LevelCounts,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+impl LastRun {
+    /// A run of `config` that started at `started_at` and has just finished
+    pub fn finished(
+        config: &DbConfig,
+        started_at: DateTime<Utc>,
+        outcome: Result<&AnalysisResults, String>,
+    ) -> Self {
+        let finished_at = Utc::now();
+        Self {
+            database: config.database.clone(),
+            host: config.host.clone(),
+            started_at: started_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            finished_at: finished_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            duration_ms: (finished_at - started_at).num_milliseconds(),
+            outcome: match outcome {
+                Ok(results) => {
+                    let summary = results.summary(0);
+                    LastRunOutcome::Succeeded {
+                        total_suggestions: summary.total_suggestions,
+                        level_counts: summary.level_counts,
+                    }
+                }
+                Err(error) => LastRunOutcome::Failed { error },
+            },
+        }
+    }
+}
+
+impl Probes {
+    /// Probes that report ready straight away
+    pub fn ready() -> Self {
+        let probes = Self::default();
+        probes.set_ready();
+        probes
+    }
+
+    /// Marks start-up as done; `/readyz` answers 503 until then
+    pub fn set_ready(&self) {
+        self.state.lock().unwrap().ready = true;
+    }
+
+    pub fn record(&self, run: LastRun) {
+        self.state.lock().unwrap().last_run = Some(run);
+    }
+
+    pub fn last_run(&self) -> Option<LastRun> {
+        self.state.lock().unwrap().last_run.clone()
+    }
+}
+
+/// `GET /healthz`, `GET /readyz` and `GET /last-run`
+pub fn router(probes: Probes) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .route("/last-run", get(last_run))
+        .with_state(probes)
+}
+
+async fn healthz() -> Response {
+    Json(json!({ "status": "ok" })).into_response()
+}
+
+async fn readyz(State(probes): State<Probes>) -> Response {
+    if probes.state.lock().unwrap().ready {
+        Json(json!({ "status": "ready" })).into_response()
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({ "status": "starting" })),
+        )
+            .into_response()
+    }
+}
+
+async fn last_run(State(probes): State<Probes>) -> Response {
+    match probes.last_run() {
+        Some(run) => Json(run).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "no run has finished yet" })),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use rstest::rstest;
+    use tower::ServiceExt;
+
+    async fn get_json(probes: &Probes, path: &str) -> (StatusCode, serde_json::Value) {
+        let response = router(probes.clone())
+            .oneshot(Request::get(path).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[rstest]
+    #[case(false, StatusCode::SERVICE_UNAVAILABLE, "starting")]
+    #[case(true, StatusCode::OK, "ready")]
+    #[tokio::test]
+    async fn readiness_follows_start_up(
+        #[case] ready: bool,
+        #[case] expected: StatusCode,
+        #[case] status: &str,
+    ) {
+        let probes = Probes::default();
+        if ready {
+            probes.set_ready();
+        }
+        assert_eq!(get_json(&probes, "/healthz").await.0, StatusCode::OK);
+        let (code, body) = get_json(&probes, "/readyz").await;
+        assert_eq!(code, expected);
+        assert_eq!(body["status"], status);
+    }
+
+    #[tokio::test]
+    async fn last_run_reports_the_most_recent_outcome() {
+        let probes = Probes::ready();
+        assert_eq!(
+            get_json(&probes, "/last-run").await.0,
+            StatusCode::NOT_FOUND
+        );
+
+        let config = DbConfig::for_tests("db.internal", "orders");
+        probes.record(LastRun::finished(
+            &config,
+            Utc::now(),
+            Err("connection refused".to_string()),
+        ));
+
+        let (code, body) = get_json(&probes, "/last-run").await;
+        assert_eq!(code, StatusCode::OK);
+        assert_eq!(body["database"], "orders");
+        assert_eq!(body["status"], "failed");
+        assert_eq!(body["error"], "connection refused");
+    }
+}
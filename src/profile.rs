@@ -0,0 +1,225 @@
+use crate::config::WorkloadType;
+use crate::models::AnalysisResults;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Recommendation profile describing the workload archetype a database serves.
+///
+/// A profile bundles the thresholds analyzers compare against and the checks that
+/// make sense for that archetype, so tuning for a warehouse does not mean fighting
+/// heuristics written for a web app.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Profile {
+    /// OLTP web application: many short transactions behind a connection pooler
+    #[default]
+    Web,
+    /// Analytics warehouse: few connections running large scans, sorts and joins
+    Warehouse,
+    /// OLTP with a meaningful share of reporting queries
+    Mixed,
+    /// Append-heavy time-series ingest with range scans over recent data
+    TimeSeries,
+    /// Multi-tenant SaaS: many tenants sharing one cluster, fairness over peak speed
+    MultiTenant,
+}
+
+/// Threshold set used by the analyzers for a given [`Profile`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileThresholds {
+    /// Target work_mem in MB for systems with <=16GB, <=64GB and >64GB of RAM
+    pub work_mem_mb: [u64; 3],
+    /// work_mem above this value (MB) is reported as dangerous
+    pub work_mem_ceiling_mb: u64,
+    /// Recommended max_connections per vCPU (floored at 100)
+    pub max_connections_per_vcpu: usize,
+    /// Fraction of vCPUs a single query may use via max_parallel_workers_per_gather
+    pub parallel_gather_ratio: f64,
+    /// Parallelism settings below this fraction of the target are reported as underutilized
+    pub parallel_underutilized_ratio: f64,
+    /// Target max_wal_size in GB for systems with <=16GB, <=64GB and >64GB of RAM
+    pub max_wal_size_gb: [u64; 3],
+    /// checkpoint_timeout below this many seconds is reported
+    pub checkpoint_timeout_min_secs: u64,
+    /// Human-readable checkpoint_timeout target
+    pub checkpoint_timeout_target: &'static str,
+    /// Minimum default_statistics_target, if the profile cares about it
+    pub default_statistics_target: Option<u32>,
+    /// Parameters whose checks do not apply to this profile
+    pub disabled_checks: &'static [&'static str],
+}
+
+impl Profile {
+    /// Profile used when none is configured explicitly
+    pub fn for_workload(workload_type: WorkloadType) -> Self {
+        match workload_type {
+            WorkloadType::Oltp => Self::Web,
+            WorkloadType::Olap => Self::Warehouse,
+        }
+    }
+
+    /// Coarse workload classification implied by the profile
+    pub fn workload_type(&self) -> WorkloadType {
+        match self {
+            Self::Warehouse => WorkloadType::Olap,
+            Self::Web | Self::Mixed | Self::TimeSeries | Self::MultiTenant => WorkloadType::Oltp,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Web => "web",
+            Self::Warehouse => "warehouse",
+            Self::Mixed => "mixed",
+            Self::TimeSeries => "time-series",
+            Self::MultiTenant => "multi-tenant",
+        }
+    }
+
+    pub fn thresholds(&self) -> ProfileThresholds {
+        match self {
+            Self::Web => ProfileThresholds {
+                work_mem_mb: [32, 64, 64],
+                work_mem_ceiling_mb: 512,
+                max_connections_per_vcpu: 4,
+                parallel_gather_ratio: 0.5,
+                parallel_underutilized_ratio: 0.5,
+                max_wal_size_gb: [4, 16, 32],
+                checkpoint_timeout_min_secs: 300,
+                checkpoint_timeout_target: "5min - 10min",
+                default_statistics_target: None,
+                disabled_checks: &[],
+            },
+            Self::Warehouse => ProfileThresholds {
+                work_mem_mb: [128, 256, 512],
+                work_mem_ceiling_mb: 4096,
+                max_connections_per_vcpu: 4,
+                parallel_gather_ratio: 0.75,
+                parallel_underutilized_ratio: 1.0,
+                max_wal_size_gb: [4, 16, 32],
+                checkpoint_timeout_min_secs: 900,
+                checkpoint_timeout_target: "15min - 30min",
+                default_statistics_target: Some(500),
+                // The ~4 * vCPU rule targets pooled OLTP apps; a 5-connection warehouse
+                // never hits it and a high cap is rarely the bottleneck.
+                disabled_checks: &["max_connections"],
+            },
+            Self::Mixed => ProfileThresholds {
+                work_mem_mb: [64, 128, 128],
+                work_mem_ceiling_mb: 1024,
+                max_connections_per_vcpu: 4,
+                parallel_gather_ratio: 0.5,
+                parallel_underutilized_ratio: 0.5,
+                max_wal_size_gb: [4, 16, 32],
+                checkpoint_timeout_min_secs: 300,
+                checkpoint_timeout_target: "5min - 15min",
+                default_statistics_target: Some(200),
+                disabled_checks: &[],
+            },
+            Self::TimeSeries => ProfileThresholds {
+                work_mem_mb: [32, 64, 128],
+                work_mem_ceiling_mb: 1024,
+                max_connections_per_vcpu: 4,
+                parallel_gather_ratio: 0.5,
+                parallel_underutilized_ratio: 0.5,
+                // Sustained ingest writes WAL continuously; size-based checkpoints arrive early
+                max_wal_size_gb: [8, 32, 64],
+                checkpoint_timeout_min_secs: 900,
+                checkpoint_timeout_target: "15min - 30min",
+                default_statistics_target: None,
+                disabled_checks: &[],
+            },
+            Self::MultiTenant => ProfileThresholds {
+                work_mem_mb: [16, 32, 64],
+                work_mem_ceiling_mb: 256,
+                max_connections_per_vcpu: 4,
+                // Keep one tenant's report from taking every parallel worker
+                parallel_gather_ratio: 0.25,
+                parallel_underutilized_ratio: 0.5,
+                max_wal_size_gb: [4, 16, 32],
+                checkpoint_timeout_min_secs: 300,
+                checkpoint_timeout_target: "5min - 10min",
+                default_statistics_target: None,
+                disabled_checks: &[],
+            },
+        }
+    }
+
+    /// Whether checks for `parameter` apply to this profile
+    pub fn is_check_enabled(&self, parameter: &str) -> bool {
+        !self.thresholds().disabled_checks.contains(&parameter)
+    }
+
+    /// Drops suggestions for checks the profile disables
+    pub fn apply(&self, results: &mut AnalysisResults) {
+        for suggestions in results.suggestions_by_category.values_mut() {
+            suggestions.retain(|s| self.is_check_enabled(&s.parameter));
+        }
+        results
+            .suggestions_by_category
+            .retain(|_, suggestions| !suggestions.is_empty());
+    }
+}
+
+impl ProfileThresholds {
+    /// Picks the entry of a `[<=16GB, <=64GB, >64GB]` tier array for the given RAM size
+    pub fn memory_tier<T: Copy>(tiers: [T; 3], total_memory_gb: Option<f64>) -> T {
+        match total_memory_gb {
+            Some(mem) if mem <= 16.0 => tiers[0],
+            Some(mem) if mem <= 64.0 => tiers[1],
+            Some(_) => tiers[2],
+            None => tiers[1],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConfigCategory, ConfigSuggestion, SuggestionLevel};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(WorkloadType::Oltp, Profile::Web)]
+    #[case(WorkloadType::Olap, Profile::Warehouse)]
+    fn test_profile_for_workload_round_trips(
+        #[case] workload: WorkloadType,
+        #[case] profile: Profile,
+    ) {
+        assert_eq!(Profile::for_workload(workload), profile);
+        assert_eq!(profile.workload_type(), workload);
+    }
+
+    #[test]
+    fn test_warehouse_profile_drops_max_connections() {
+        let mut results = AnalysisResults::default();
+        results
+            .suggestions_by_category
+            .entry(ConfigCategory::Concurrency)
+            .or_default()
+            .push(ConfigSuggestion {
+                parameter: "max_connections".to_string(),
+                current_value: "500".to_string(),
+                suggested_value: "100".to_string(),
+                level: SuggestionLevel::Critical,
+                rationale: String::new(),
+                messages: Vec::new(),
+                fingerprint: String::new(),
+            });
+
+        Profile::Web.apply(&mut results);
+        assert_eq!(results.suggestions_by_category.len(), 1);
+
+        Profile::Warehouse.apply(&mut results);
+        assert!(results.suggestions_by_category.is_empty());
+    }
+
+    #[test]
+    fn test_memory_tier_selection() {
+        let tiers = [1, 2, 3];
+        assert_eq!(ProfileThresholds::memory_tier(tiers, Some(8.0)), 1);
+        assert_eq!(ProfileThresholds::memory_tier(tiers, Some(64.0)), 2);
+        assert_eq!(ProfileThresholds::memory_tier(tiers, Some(128.0)), 3);
+        assert_eq!(ProfileThresholds::memory_tier(tiers, None), 2);
+    }
+}
@@ -0,0 +1,771 @@
+use crate::analysis::catalog_cache::INDEX_CATALOG_QUERY;
+use crate::analysis::extended_stats::{
+    sample_distinct_query, COVERED_COLUMNS_QUERY, RELTUPLES_QUERY, UNANALYZED_STATISTICS_QUERY,
+};
+use crate::analysis::extensions::{
+    CITUS_WORKERS_QUERY, INSTALLED_EXTENSIONS_QUERY, TIMESCALE_JOBS_QUERY,
+};
+use crate::analysis::partial_indexes::COLUMN_STATS_QUERY;
+use crate::analysis::pgvector::{
+    DISTANCE_STATEMENTS_QUERY, VECTOR_COLUMNS_QUERY, VECTOR_INDEXES_QUERY,
+};
+use crate::analysis::sampling::{
+    CONNECTION_COUNTS_QUERY, STATS_AGE_QUERY, TABLE_SCANS_QUERY, TEMP_COUNTERS_QUERY,
+    WAL_COUNTERS_QUERY,
+};
+use crate::analysis::table_index::bloat::TABLE_STATS_QUERY;
+use crate::analysis::table_index::indexes::{
+    BRIN_CANDIDATES_QUERY, SOFT_DELETE_CANDIDATES_QUERY, UNINDEXED_GEOMETRY_QUERY,
+};
+use crate::analysis::workload::{
+    build_statement_query, role_summary_query, TimeColumns, PG_STAT_STATEMENTS_INFO_QUERY,
+    PG_STAT_STATEMENTS_INSTALLED_QUERY, PROBE_PG_STAT_STATEMENTS_QUERY,
+    QUERY_TEXT_VISIBILITY_QUERY, TRACK_IO_TIMING_QUERY,
+};
+use crate::analysis::{
+    backups, connection_history, cursors, host_layout, integrity, log_volume, os_limits,
+    pending_changes, pitr, replicas, resilience, schema_lint, security, temp_schemas, tenants,
+    toast, workload::WorkloadGroupBy,
+};
+use crate::apply::ApplyStep;
+use crate::bench::{bench_statements_query, DATABASE_BLOCKS_QUERY};
+use crate::catalog::{
+    BGWRITER_QUERY, BGWRITER_WRITES_QUERY, CATALOG_QUERY, CHECKPOINTER_QUERY, IO_WRITES_QUERY,
+};
+use crate::checker::{
+    AnalyzeOptions, IN_RECOVERY_QUERY, PARAMETER_QUERY, PARAMS_QUERY, RELOAD_QUERY, VERSION_QUERY,
+};
+use serde::Serialize;
+
+/// A statement PostGreat may run, listed by `--show-queries` and `permissions`
+/// so the tool can be reviewed before it is given access
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogQuery {
+    /// What the statement reads
+    pub purpose: &'static str,
+    /// Flag, server version or extension the statement depends on; `None`
+    /// when every run executes it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub when: Option<&'static str>,
+    /// Changes server settings; only `apply` runs these, for confirmed steps
+    pub writes: bool,
+    pub sql: String,
+}
+
+/// Options of `analyze` that add statements to a run
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Gate {
+    /// Run by other commands only
+    Never,
+    /// Run by `apply` only; the statements that change the server
+    Write,
+    Always,
+    SampleWindow,
+    HostChecks,
+    Backup,
+    GroupBySchema,
+    SchemaLint,
+    Workload,
+    /// Left out by `--gentle`
+    NotGentle,
+    /// Workload statements left out by `--gentle`
+    WorkloadCatalog,
+}
+
+impl Gate {
+    fn enabled(self, opts: &AnalyzeOptions) -> bool {
+        match self {
+            Gate::Never | Gate::Write => false,
+            Gate::Always => true,
+            Gate::SampleWindow => opts.sample_window.is_some(),
+            Gate::HostChecks => opts.host_checks,
+            Gate::Backup => opts.backup.is_some(),
+            Gate::GroupBySchema => opts.group_by_schema,
+            Gate::SchemaLint => opts.schema_lint,
+            Gate::Workload => opts.with_workload || opts.deep,
+            Gate::NotGentle => !opts.gentle,
+            Gate::WorkloadCatalog => Gate::Workload.enabled(opts) && !opts.gentle,
+        }
+    }
+}
+
+/// Every statement PostGreat can execute, in the order `analyze` runs them,
+/// followed by those only other commands run. Only the `ALTER SYSTEM` and
+/// `pg_reload_conf()` statements of `apply` write, and they are marked
+/// [`CatalogQuery::writes`]; every other one is a single SELECT free of
+/// data-modifying, DDL and privilege statements and of functions with side
+/// effects, which the tests below enforce. Re-exported as `postgreat::queries()`
+/// for embedders that need the same guarantee.
+pub fn all_queries() -> Vec<CatalogQuery> {
+    catalog().into_iter().map(|(_, query)| query).collect()
+}
+
+/// The statements `analyze` runs with `opts`. Statements that depend on the
+/// server version or installed extensions are listed with their condition,
+/// since which of them run is only known once connected.
+pub fn analyze_queries(opts: &AnalyzeOptions) -> Vec<CatalogQuery> {
+    catalog()
+        .into_iter()
+        .filter(|(gate, _)| gate.enabled(opts))
+        .map(|(_, query)| query)
+        .collect()
+}
+
+fn catalog() -> Vec<(Gate, CatalogQuery)> {
+    let entry = |gate: Gate, purpose: &'static str, when: Option<&'static str>, sql: &str| {
+        (
+            gate,
+            CatalogQuery {
+                purpose,
+                when,
+                writes: gate == Gate::Write,
+                sql: dedent(sql),
+            },
+        )
+    };
+    let pg13_columns = TimeColumns {
+        total: "total_exec_time",
+        max: "max_exec_time",
+        io: None,
+    };
+
+    vec![
+        entry(
+            Gate::Always,
+            "Find the primary among the host candidates",
+            Some("several --host candidates"),
+            IN_RECOVERY_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Server version and pg_stat_statements columns",
+            None,
+            CATALOG_QUERY,
+        ),
+        entry(Gate::Always, "Server settings", None, PARAMS_QUERY),
+        entry(
+            Gate::SampleWindow,
+            "Table scan counters at the start of the window",
+            Some("--sample-window"),
+            TABLE_SCANS_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Open and active connections",
+            None,
+            CONNECTION_COUNTS_QUERY,
+        ),
+        entry(Gate::Always, "Server build", None, VERSION_QUERY),
+        entry(
+            Gate::Always,
+            "Checkpoint counters",
+            Some("PostgreSQL 17+"),
+            CHECKPOINTER_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Checkpoint counters",
+            Some("before PostgreSQL 17"),
+            BGWRITER_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Buffers written by the checkpointer, background writer and backends",
+            Some("PostgreSQL 16+"),
+            IO_WRITES_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Buffers written by the checkpointer, background writer and backends",
+            Some("before PostgreSQL 16"),
+            BGWRITER_WRITES_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "WAL volume",
+            Some("PostgreSQL 14+"),
+            WAL_COUNTERS_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Temporary files written by queries",
+            None,
+            TEMP_COUNTERS_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Age of the cumulative statistics",
+            None,
+            STATS_AGE_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Installed extensions",
+            None,
+            INSTALLED_EXTENSIONS_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Vector columns",
+            Some("pgvector installed"),
+            VECTOR_COLUMNS_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Vector indexes",
+            Some("pgvector installed"),
+            VECTOR_INDEXES_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Statements using vector distance operators",
+            Some("pgvector installed"),
+            DISTANCE_STATEMENTS_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Citus worker count",
+            Some("citus installed"),
+            CITUS_WORKERS_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Scheduled TimescaleDB jobs",
+            Some("timescaledb installed"),
+            TIMESCALE_JOBS_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Whether lz4 TOAST compression is available",
+            None,
+            toast::LZ4_AVAILABLE_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Tables with the most TOAST data",
+            Some("PostgreSQL 14+"),
+            toast::TOAST_TABLES_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Statement rates for the log volume estimate",
+            Some("pg_stat_statements installed"),
+            log_volume::STATEMENTS_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "pg_stat_statements window",
+            Some("pg_stat_statements installed, PostgreSQL 14+"),
+            log_volume::STATEMENTS_WINDOW_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Sessions started",
+            Some("PostgreSQL 14+"),
+            log_volume::SESSIONS_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Replication slots",
+            None,
+            pitr::REPLICATION_SLOTS_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "WAL archiver status",
+            None,
+            pitr::ARCHIVER_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Recovery settings of the replicas",
+            Some("on each configured replica"),
+            pitr::STANDBY_SETTINGS_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Primary or standby, and its WAL receiver",
+            None,
+            resilience::REPLICATION_ROLE_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Tables with row-level security or policies",
+            None,
+            security::ROW_SECURITY_TABLES_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Row-level security policies",
+            None,
+            security::POLICIES_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Whether PUBLIC may create in the public schema",
+            None,
+            security::PUBLIC_SCHEMA_CREATE_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "NOT VALID constraints",
+            None,
+            &integrity::unvalidated_constraints_query(),
+        ),
+        entry(
+            Gate::Always,
+            "Disabled triggers",
+            None,
+            &integrity::disabled_triggers_query(),
+        ),
+        entry(
+            Gate::Always,
+            "Extended statistics without data",
+            Some("PostgreSQL 12+"),
+            UNANALYZED_STATISTICS_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Idle sessions holding cursors",
+            None,
+            cursors::CURSOR_SESSIONS_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Temp schemas left by backends that are gone",
+            Some("PostgreSQL 16+"),
+            temp_schemas::ORPHANED_TEMP_SCHEMAS_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Connections across all databases",
+            None,
+            connection_history::CONNECTION_SAMPLE_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Settings changed in the config files but not yet applied",
+            None,
+            pending_changes::PENDING_CHANGES_QUERY,
+        ),
+        entry(
+            Gate::HostChecks,
+            "PID of the backend, to read its limits",
+            Some("--host-checks"),
+            os_limits::BACKEND_PID_QUERY,
+        ),
+        entry(
+            Gate::HostChecks,
+            "Tablespace locations",
+            Some("--host-checks"),
+            host_layout::TABLESPACES_QUERY,
+        ),
+        entry(
+            Gate::Backup,
+            "Last archived WAL segment",
+            Some("--backup-rpo or --backup-check-command"),
+            backups::ARCHIVER_QUERY,
+        ),
+        entry(
+            Gate::Backup,
+            "Newest backup history file",
+            Some("--backup-rpo or --backup-check-command"),
+            backups::BACKUP_HISTORY_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Index usage on the replicas",
+            Some("on each configured replica"),
+            replicas::REPLICA_INDEX_SCANS_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Table activity and dead tuples",
+            None,
+            TABLE_STATS_QUERY,
+        ),
+        entry(
+            Gate::NotGentle,
+            "Index definitions, sizes and usage, read once for the table/index and workload analyses",
+            Some("without --gentle"),
+            INDEX_CATALOG_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Soft-delete columns without a partial index",
+            None,
+            SOFT_DELETE_CANDIDATES_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Append-only timestamp columns for BRIN",
+            None,
+            BRIN_CANDIDATES_QUERY,
+        ),
+        entry(
+            Gate::Always,
+            "Geometry columns without a spatial index",
+            None,
+            UNINDEXED_GEOMETRY_QUERY,
+        ),
+        entry(
+            Gate::GroupBySchema,
+            "Size and activity per schema",
+            Some("--group-by-schema"),
+            tenants::SCHEMA_USAGE_QUERY,
+        ),
+        entry(
+            Gate::SchemaLint,
+            "Column types of user tables",
+            Some("--schema-lint"),
+            &schema_lint::lint_columns_query(),
+        ),
+        entry(
+            Gate::SchemaLint,
+            "Tables without a primary key",
+            Some("--schema-lint"),
+            &schema_lint::without_primary_key_query(),
+        ),
+        entry(
+            Gate::SchemaLint,
+            "Nullable foreign keys without an index",
+            Some("--schema-lint"),
+            &schema_lint::unindexed_foreign_keys_query(),
+        ),
+        entry(
+            Gate::Workload,
+            "Whether pg_stat_statements is installed",
+            Some("--with-workload, --deep or workload"),
+            PG_STAT_STATEMENTS_INSTALLED_QUERY,
+        ),
+        entry(
+            Gate::Workload,
+            "Whether pg_stat_statements is readable",
+            Some("--with-workload, --deep or workload"),
+            PROBE_PG_STAT_STATEMENTS_QUERY,
+        ),
+        entry(
+            Gate::Workload,
+            "Whether the role sees other roles' query text",
+            Some("--with-workload, --deep or workload"),
+            QUERY_TEXT_VISIBILITY_QUERY,
+        ),
+        entry(
+            Gate::Workload,
+            "pg_stat_statements reset time and evictions",
+            Some("--with-workload, --deep or workload, PostgreSQL 14+"),
+            PG_STAT_STATEMENTS_INFO_QUERY,
+        ),
+        entry(
+            Gate::Workload,
+            "Whether I/O timing is collected",
+            Some("--with-workload, --deep or workload"),
+            TRACK_IO_TIMING_QUERY,
+        ),
+        entry(
+            Gate::Workload,
+            "Top statements, once per ranking metric and configured replica; columns vary with \
+             the server version and track_io_timing",
+            Some("--with-workload, --deep or workload"),
+            &build_statement_query(pg13_columns, "total_time_ms", true, WorkloadGroupBy::Query),
+        ),
+        entry(
+            Gate::WorkloadCatalog,
+            "Column statistics of filtered columns",
+            Some("--with-workload, --deep or workload without --gentle"),
+            COLUMN_STATS_QUERY,
+        ),
+        entry(
+            Gate::WorkloadCatalog,
+            "Extended statistics on filtered tables",
+            Some("--with-workload, --deep or workload without --gentle"),
+            COVERED_COLUMNS_QUERY,
+        ),
+        entry(
+            Gate::WorkloadCatalog,
+            "Row estimate of a table filtered on several columns",
+            Some("--with-workload, --deep or workload without --gentle"),
+            RELTUPLES_QUERY,
+        ),
+        entry(
+            Gate::WorkloadCatalog,
+            "Distinct values in a sample of up to 30,000 rows, for up to 10 tables filtered on \
+             several columns; reads table rows",
+            Some("--with-workload, --deep or workload without --gentle, SELECT on the table"),
+            &sample_distinct_query(
+                &["<column_1>".to_string(), "<column_2>".to_string()],
+                "<schema>.<table>",
+                "<percent>",
+            ),
+        ),
+        entry(
+            Gate::Never,
+            "Time spent per role",
+            Some("workload --group-by role"),
+            &role_summary_query(pg13_columns),
+        ),
+        entry(
+            Gate::Never,
+            "One setting",
+            Some("explain with a database"),
+            PARAMETER_QUERY,
+        ),
+        entry(
+            Gate::Never,
+            "Block hits and reads of the database, at both ends of the window",
+            Some("bench"),
+            DATABASE_BLOCKS_QUERY,
+        ),
+        entry(
+            Gate::Never,
+            "Calls and time per statement, at both ends of the window",
+            Some("bench, pg_stat_statements"),
+            &bench_statements_query(pg13_columns),
+        ),
+        entry(
+            Gate::Write,
+            "Change a setting",
+            Some("apply, for each confirmed step"),
+            apply_sql(Some("<value>")).trim_end_matches(';'),
+        ),
+        entry(
+            Gate::Write,
+            "Reset a setting to its configuration file value",
+            Some("apply, for each confirmed step"),
+            apply_sql(None).trim_end_matches(';'),
+        ),
+        entry(
+            Gate::Write,
+            "Reload the configuration",
+            Some("apply, once after the last change"),
+            RELOAD_QUERY,
+        ),
+    ]
+}
+
+/// An `apply` step with placeholders for the setting and value
+fn apply_sql(value: Option<&str>) -> String {
+    ApplyStep {
+        parameter: "<parameter>".to_string(),
+        value: value.map(str::to_string),
+        category: None,
+    }
+    .sql()
+}
+
+/// Strips the blank lines around a statement and the indentation its lines share
+fn dedent(sql: &str) -> String {
+    let lines: Vec<&str> = sql
+        .lines()
+        .skip_while(|line| line.trim().is_empty())
+        .collect();
+    let lines = &lines[..lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(0, |last| last + 1)];
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn statements_are_dedented() {
+        assert_eq!(
+            dedent("\n        SELECT a,\n            b\n        FROM t\n    "),
+            "SELECT a,\n    b\nFROM t"
+        );
+    }
+
+    #[rstest]
+    #[case::defaults(AnalyzeOptions::default(), false, false)]
+    #[case::schema_lint(AnalyzeOptions { schema_lint: true, ..Default::default() }, true, false)]
+    #[case::deep(AnalyzeOptions { deep: true, ..Default::default() }, false, true)]
+    #[case::gentle(AnalyzeOptions { deep: true, gentle: true, ..Default::default() }, false, false)]
+    fn flags_add_their_statements(
+        #[case] opts: AnalyzeOptions,
+        #[case] lint: bool,
+        #[case] workload: bool,
+    ) {
+        let queries = analyze_queries(&opts);
+        let runs = |fragment: &str| queries.iter().any(|query| query.sql.contains(fragment));
+        assert!(runs("FROM pg_settings"));
+        assert_eq!(runs("k.contype = 'p'"), lint);
+        assert_eq!(runs("TABLESAMPLE SYSTEM"), workload);
+        assert!(queries.len() < all_queries().len());
+    }
+
+    /// Keywords of statements that write, change the schema or privileges, or
+    /// lock rows; `INTO` covers `SELECT ... INTO new_table`
+    const WRITE_KEYWORDS: &[&str] = &[
+        "insert",
+        "update",
+        "delete",
+        "merge",
+        "truncate",
+        "copy",
+        "into",
+        "create",
+        "alter",
+        "drop",
+        "rename",
+        "comment",
+        "grant",
+        "revoke",
+        "vacuum",
+        "analyze",
+        "cluster",
+        "reindex",
+        "refresh",
+        "lock",
+        "call",
+        "do",
+        "set",
+        "reset",
+        "notify",
+        "listen",
+        "prepare",
+        "execute",
+        "checkpoint",
+        "discard",
+        "load",
+        "share",
+    ];
+
+    /// Functions that change server state when called from a SELECT
+    const WRITE_FUNCTIONS: &[&str] = &[
+        "nextval",
+        "setval",
+        "set_config",
+        "pg_cancel_backend",
+        "pg_terminate_backend",
+        "pg_reload_conf",
+        "pg_rotate_logfile",
+        "pg_promote",
+        "pg_switch_wal",
+        "pg_create_restore_point",
+        "pg_create_physical_replication_slot",
+        "pg_create_logical_replication_slot",
+        "pg_drop_replication_slot",
+        "pg_stat_reset",
+        "pg_stat_reset_shared",
+        "pg_stat_reset_single_table_counters",
+        "pg_stat_statements_reset",
+        "pg_advisory_lock",
+        "pg_advisory_xact_lock",
+        "lo_unlink",
+        "lo_import",
+        "lo_export",
+        "dblink_exec",
+    ];
+
+    /// The first write keyword or function in `sql`, ignoring string literals,
+    /// quoted identifiers and comments
+    fn first_write(sql: &str) -> Option<String> {
+        let mut code = String::new();
+        let mut chars = sql.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\'' | '"' => {
+                    for inner in chars.by_ref() {
+                        if inner == c {
+                            break;
+                        }
+                    }
+                    code.push(' ');
+                }
+                '-' if chars.peek() == Some(&'-') => {
+                    for inner in chars.by_ref() {
+                        if inner == '\n' {
+                            break;
+                        }
+                    }
+                    code.push(' ');
+                }
+                ';' => return Some(";".to_string()),
+                _ => code.push(c),
+            }
+        }
+        code.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .map(str::to_ascii_lowercase)
+            .find(|word| {
+                WRITE_KEYWORDS.contains(&word.as_str()) || WRITE_FUNCTIONS.contains(&word.as_str())
+            })
+    }
+
+    #[rstest]
+    #[case::select("SELECT deleted_at, n_tup_upd FROM pg_stat_user_tables", None)]
+    #[case::literal("SELECT 1 FROM t WHERE privilege_type = 'CREATE'", None)]
+    #[case::comment("SELECT 1 -- not an UPDATE\nFROM t", None)]
+    #[case::row_lock("SELECT * FROM t FOR UPDATE", Some("update"))]
+    #[case::select_into("SELECT * INTO copy FROM t", Some("into"))]
+    #[case::writable_cte("WITH d AS (DELETE FROM t RETURNING *) SELECT 1", Some("delete"))]
+    #[case::function(
+        "SELECT pg_terminate_backend(pid) FROM pg_stat_activity",
+        Some("pg_terminate_backend")
+    )]
+    #[case::second_statement("SELECT 1; DROP TABLE t", Some(";"))]
+    fn writes_are_detected(#[case] sql: &str, #[case] write: Option<&str>) {
+        assert_eq!(first_write(sql).as_deref(), write);
+    }
+
+    #[test]
+    fn every_statement_is_read_only() {
+        for query in all_queries().into_iter().filter(|query| !query.writes) {
+            let first = query.sql.split_whitespace().next().unwrap_or("");
+            assert!(
+                ["SELECT", "WITH"].contains(&first.to_ascii_uppercase().as_str()),
+                "{}: {}",
+                query.purpose,
+                query.sql
+            );
+            assert_eq!(
+                first_write(&query.sql),
+                None,
+                "{}: {}",
+                query.purpose,
+                query.sql
+            );
+        }
+    }
+
+    #[test]
+    fn only_apply_statements_write() {
+        let writes: Vec<_> = all_queries()
+            .into_iter()
+            .filter(|query| query.writes)
+            .map(|query| query.sql)
+            .collect();
+        assert_eq!(
+            writes,
+            [
+                "ALTER SYSTEM SET <parameter> = '<value>'",
+                "ALTER SYSTEM RESET <parameter>",
+                "SELECT pg_reload_conf()",
+            ]
+        );
+        let everything = AnalyzeOptions {
+            sample_window: Some(std::time::Duration::from_secs(60)),
+            host_checks: true,
+            group_by_schema: true,
+            schema_lint: true,
+            deep: true,
+            ..Default::default()
+        };
+        assert!(analyze_queries(&everything)
+            .iter()
+            .all(|query| !query.writes));
+    }
+}
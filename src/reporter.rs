@@ -1,10 +1,24 @@
+use crate::analysis::sampling::format_window;
+use crate::analysis::vacuum_simulation::{AutovacuumSimulation, VacuumCycle};
+use crate::ascii;
+use crate::bench::BenchComparison;
+use crate::checks::CheckInfo;
+use crate::guidance::ParameterExplanation;
+use crate::lifecycle::FindingLifecycle;
 use crate::models::{
-    AnalysisResults, ConfigCategory, ConfigSuggestion, IndexIssueKind, SlowQueryKind,
-    SuggestionLevel, WorkloadResults,
+    AnalysisResults, ApplicationPatternKind, ByteSize, ConfigCategory, ConfigSuggestion,
+    ConnectionMemory, ConnectionSaturation, FleetEntry, IndexIssueKind, IndexRemovalPlan,
+    LogFindings, LoggedPlanFinding, ManagedRemediation, PitrReadiness, PlanRegressionKind,
+    RankedSuggestion, ReportSummary, SchemaUsage, SettingsRemediation, SlowQueryInfo,
+    SlowQueryKind, SuggestionLevel, TableIndexHealth, WorkloadResults,
 };
+use crate::queries::CatalogQuery;
+use crate::rule_docs::RuleDoc;
+use crate::scoring::{self, HealthScore};
+use crate::signing::{self, SigningKey};
 use clap::ValueEnum;
 use snafu::{ResultExt, Snafu};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Snafu)]
 pub enum ReporterError {
@@ -22,35 +36,114 @@ pub enum ReportFormat {
     Json,
     /// Plain text summary
     Text,
+    /// Standalone HTML page with the same sections as Markdown
+    Html,
+    /// Compact Markdown for a pull request comment, tagged so a bot can update it
+    GithubComment,
 }
 
+impl ReportFormat {
+    /// File extension for a report saved in this format
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown | ReportFormat::GithubComment => "md",
+            ReportFormat::Json => "json",
+            ReportFormat::Text => "txt",
+            ReportFormat::Html => "html",
+        }
+    }
+}
+
+/// Hidden first line of every `github-comment` report; bots search for it to
+/// update their previous comment instead of adding a new one
+pub const GITHUB_COMMENT_MARKER: &str = "<!-- postgreat:report -->";
+
+/// How much of the analysis report to print
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReportDetail {
+    /// Every section
+    #[default]
+    Full,
+    /// Level counts, the top suggestions and table/index headline numbers
+    Summary,
+    /// Findings only; nothing at all when there are none
+    Quiet,
+}
+
+/// Suggestions listed in a `--summary` report
+const SUMMARY_TOP_SUGGESTIONS: usize = 5;
+
 pub struct Reporter {
     format: ReportFormat,
+    detail: ReportDetail,
+    signing_key: Option<SigningKey>,
 }
 
 impl Reporter {
     pub fn new(format: ReportFormat) -> Self {
-        Self { format }
+        Self {
+            format,
+            detail: ReportDetail::default(),
+            signing_key: None,
+        }
+    }
+
+    pub fn with_detail(mut self, detail: ReportDetail) -> Self {
+        self.detail = detail;
+        self
+    }
+
+    /// Signs full JSON reports with an HMAC-SHA256 `integrity` field
+    pub fn with_signing_key(mut self, key: Option<SigningKey>) -> Self {
+        self.signing_key = key;
+        self
     }
 
     pub fn report(&self, results: &AnalysisResults) -> Result<()> {
+        self.write(&mut ascii::stdout(), results)
+    }
+
+    /// The report `report` prints, written to `handle` instead, e.g. for an email body
+    pub fn write<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        results: &AnalysisResults,
+    ) -> Result<()> {
+        match self.detail {
+            ReportDetail::Full => {}
+            ReportDetail::Summary => return self.write_summary(handle, results),
+            ReportDetail::Quiet => return self.write_findings(handle, results),
+        }
         match self.format {
-            ReportFormat::Markdown => self.report_markdown(results)?,
-            ReportFormat::Json => self.report_json(results)?,
-            ReportFormat::Text => self.report_text(results)?,
+            ReportFormat::Markdown => self.write_markdown(handle, results)?,
+            ReportFormat::GithubComment => self.write_github_comment(handle, results, None)?,
+            ReportFormat::Json => self.write_json(handle, results)?,
+            ReportFormat::Text => self.write_text(handle, results)?,
+            ReportFormat::Html => {
+                let mut markdown = Vec::new();
+                self.write_markdown(&mut markdown, results)?;
+                write_html(
+                    handle,
+                    "PostgreSQL Configuration Analysis Report",
+                    &markdown,
+                )?;
+            }
         }
         Ok(())
     }
 
-    fn report_markdown(&self, results: &AnalysisResults) -> Result<()> {
-        use std::io::Write;
-
-        let stdout = std::io::stdout();
-        let mut handle = stdout.lock();
-
+    fn write_markdown<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        results: &AnalysisResults,
+    ) -> Result<()> {
         // Header
         writeln!(handle, "# PostgreSQL Configuration Analysis Report\n").context(OutputSnafu)?;
 
+        for warning in &results.warnings {
+            writeln!(handle, "> **Warning**: {}\n", warning).context(OutputSnafu)?;
+        }
+
         // Summary statistics
         writeln!(handle, "## System Information\n").context(OutputSnafu)?;
         writeln!(
@@ -83,6 +176,32 @@ impl Reporter {
                 .unwrap_or_else(|| "Unknown".to_string())
         )
         .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "- **Profile**: {}",
+            results.system_stats.profile().as_str()
+        )
+        .context(OutputSnafu)?;
+        if let (Some(age), Some(since)) = (
+            results.system_stats.stats_age_secs,
+            &results.system_stats.stats_reset_at,
+        ) {
+            writeln!(
+                handle,
+                "- **Statistics Window**: {} (since {})",
+                format_window(age),
+                since
+            )
+            .context(OutputSnafu)?;
+        }
+        if let Some(secs) = results.system_stats.sample_window_secs {
+            writeln!(
+                handle,
+                "- **Sample Window**: {}s (checkpoint and scan counters are deltas over this window)",
+                secs
+            )
+            .context(OutputSnafu)?;
+        }
         writeln!(
             handle,
             "- **Configuration Parameters**: {}",
@@ -91,6 +210,10 @@ impl Reporter {
         .context(OutputSnafu)?;
         writeln!(handle).context(OutputSnafu)?;
 
+        write_health_markdown(handle, &scoring::score(results))?;
+
+        self.write_parameter_state_markdown(handle, results)?;
+
         // Summary of suggestions by level
         let mut level_counts: HashMap<SuggestionLevel, usize> = HashMap::new();
         let total_suggestions: usize = results
@@ -159,41 +282,54 @@ impl Reporter {
             writeln!(handle).context(OutputSnafu)?;
         }
 
-        // Detailed suggestions by category
-        let mut categories: Vec<ConfigCategory> =
-            results.suggestions_by_category.keys().copied().collect();
-        categories.sort_by_key(|c| c.as_str());
+        self.write_suggestions_markdown(handle, results)?;
 
-        for category in categories {
-            let suggestions = &results.suggestions_by_category[&category];
+        if let Some(saturation) = &results.connection_saturation {
+            self.write_connection_saturation_markdown(handle, saturation)?;
+        }
 
-            // Sort by level (Critical first)
-            let mut sorted_suggestions = suggestions.clone();
-            sorted_suggestions.sort_by(|a, b| {
-                let level_order = |level: &SuggestionLevel| match level {
-                    SuggestionLevel::Critical => 0,
-                    SuggestionLevel::Important => 1,
-                    SuggestionLevel::Recommended => 2,
-                    SuggestionLevel::Info => 3,
-                };
-                level_order(&a.level).cmp(&level_order(&b.level))
-            });
+        if let Some(memory) = &results.connection_memory {
+            self.write_connection_memory_markdown(handle, memory)?;
+        }
 
-            writeln!(handle, "## {}\n", category.as_str()).context(OutputSnafu)?;
+        if let Some(pitr) = &results.pitr_readiness {
+            self.write_pitr_readiness_markdown(handle, pitr)?;
+        }
 
-            for suggestion in &sorted_suggestions {
-                self.write_suggestion_markdown(&mut handle, suggestion)?;
-            }
+        if let Some(remediation) = &results.managed_remediation {
+            self.write_managed_remediation_markdown(handle, remediation)?;
+        }
 
-            writeln!(handle).context(OutputSnafu)?;
+        if let Some(remediation) = &results.settings_remediation {
+            self.write_settings_remediation_markdown(handle, remediation)?;
+        }
+
+        if !results.host_commands.is_empty() {
+            writeln!(
+                handle,
+                "## Apply on the Host\n\nRun on the database host to apply the Operating System suggestions:\n\n```bash\n{}\n```\n",
+                results.host_commands.join("\n")
+            )
+            .context(OutputSnafu)?;
         }
 
         // Table & Index health summary
-        if !results.bloat_info.is_empty()
-            || !results.seq_scan_info.is_empty()
-            || !results.index_usage_info.is_empty()
-        {
-            self.write_table_index_markdown(&mut handle, results)?;
+        if !results.table_health.is_empty() {
+            write_table_index_markdown(handle, &results.table_health)?;
+        }
+
+        if !results.schema_usage.is_empty() {
+            write_schema_usage_markdown(handle, &results.schema_usage)?;
+        }
+
+        if !results.index_removal_plans.is_empty() {
+            self.write_index_removal_markdown(handle, &results.index_removal_plans)?;
+        }
+
+        if let Some(workload) = &results.workload {
+            writeln!(handle, "# Workload Analysis\n").context(OutputSnafu)?;
+            WorkloadReporter::new(self.format)
+                .write_workload_sections_markdown(handle, workload)?;
         }
 
         // System configuration table
@@ -228,945 +364,3139 @@ impl Reporter {
         Ok(())
     }
 
-    fn write_suggestion_markdown(
+    fn write_summary<W: std::io::Write>(
         &self,
-        handle: &mut std::io::StdoutLock,
-        suggestion: &ConfigSuggestion,
+        handle: &mut W,
+        results: &AnalysisResults,
     ) -> Result<()> {
-        use std::io::Write;
+        let summary = results.summary(SUMMARY_TOP_SUGGESTIONS);
+        match self.format {
+            ReportFormat::Markdown => self.write_summary_markdown(handle, results, &summary),
+            ReportFormat::Json => write_json(handle, &summary),
+            ReportFormat::Text => self.write_summary_text(handle, results, &summary),
+            ReportFormat::GithubComment => {
+                self.write_github_comment(handle, results, Some(SUMMARY_TOP_SUGGESTIONS))
+            }
+            ReportFormat::Html => {
+                let mut markdown = Vec::new();
+                self.write_summary_markdown(&mut markdown, results, &summary)?;
+                write_html(
+                    handle,
+                    "PostgreSQL Configuration Analysis Summary",
+                    &markdown,
+                )
+            }
+        }
+    }
 
-        let level_badge = self.format_level_badge(&suggestion.level);
+    fn write_summary_markdown<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        results: &AnalysisResults,
+        summary: &ReportSummary,
+    ) -> Result<()> {
+        writeln!(handle, "# PostgreSQL Configuration Analysis Summary\n").context(OutputSnafu)?;
 
-        writeln!(handle, "### {} {}\n", suggestion.parameter, level_badge).context(OutputSnafu)?;
+        for warning in &results.warnings {
+            writeln!(handle, "> **Warning**: {}\n", warning).context(OutputSnafu)?;
+        }
 
-        writeln!(handle, "**Current Value**: `{}`", suggestion.current_value)
-            .context(OutputSnafu)?;
+        writeln!(handle, "**Health Score**: {}\n", summary.health).context(OutputSnafu)?;
         writeln!(
             handle,
-            "**Suggested Value**: `{}`",
-            suggestion.suggested_value
+            "Found **{}** configuration suggestions:\n",
+            summary.total_suggestions
         )
         .context(OutputSnafu)?;
+        for (level, count) in summary.level_counts.iter() {
+            if count > 0 {
+                writeln!(
+                    handle,
+                    "- **{} ({})**: {} suggestions",
+                    level.as_str(),
+                    self.format_level_badge(&level),
+                    count
+                )
+                .context(OutputSnafu)?;
+            }
+        }
         writeln!(handle).context(OutputSnafu)?;
 
-        writeln!(handle, "**Rationale**:\n").context(OutputSnafu)?;
-        writeln!(handle, "{}", suggestion.rationale).context(OutputSnafu)?;
-        writeln!(handle).context(OutputSnafu)?;
-
-        Ok(())
-    }
-
-    fn format_level_badge(&self, level: &SuggestionLevel) -> String {
-        let badge = match level {
-            SuggestionLevel::Critical => "![CRITICAL](https://img.shields.io/badge/CRITICAL-red)",
-            SuggestionLevel::Important => {
-                "![IMPORTANT](https://img.shields.io/badge/IMPORTANT-orange)"
-            }
-            SuggestionLevel::Recommended => {
-                "![RECOMMENDED](https://img.shields.io/badge/RECOMMENDED-yellow)"
+        if !summary.top_suggestions.is_empty() {
+            writeln!(handle, "## Top Suggestions\n").context(OutputSnafu)?;
+            writeln!(
+                handle,
+                "| Level | Category | Parameter | Current Value | Suggested Value |"
+            )
+            .context(OutputSnafu)?;
+            writeln!(
+                handle,
+                "|-------|----------|-----------|---------------|-----------------|"
+            )
+            .context(OutputSnafu)?;
+            for ranked in &summary.top_suggestions {
+                let suggestion = &ranked.suggestion;
+                writeln!(
+                    handle,
+                    "| {} | {} | {} | `{}` | `{}` |",
+                    suggestion.level.as_str(),
+                    ranked.category.as_str(),
+                    suggestion.parameter,
+                    suggestion.current_value,
+                    suggestion.suggested_value
+                )
+                .context(OutputSnafu)?;
             }
-            SuggestionLevel::Info => "![INFO](https://img.shields.io/badge/INFO-blue)",
-        };
-        badge.to_string()
-    }
-
-    fn report_json(&self, results: &AnalysisResults) -> Result<()> {
-        use serde_json;
+            writeln!(handle).context(OutputSnafu)?;
+        }
 
-        let json = serde_json::to_string_pretty(results)
-            .map_err(std::io::Error::other)
+        let headline = &summary.table_index;
+        writeln!(handle, "## Table & Index Health\n").context(OutputSnafu)?;
+        writeln!(handle, "- **Bloated Tables**: {}", headline.bloated_tables)
             .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "- **Sequential Scan Hotspots**: {}",
+            headline.seq_scan_hotspots
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "- **Unused Indexes**: {} ({})",
+            headline.unused_indexes, headline.unused_index_size
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "- **Other Index Findings**: {}",
+            headline.other_index_findings
+        )
+        .context(OutputSnafu)?;
+        writeln!(handle).context(OutputSnafu)?;
 
-        println!("{}", json);
         Ok(())
     }
 
-    fn report_text(&self, results: &AnalysisResults) -> Result<()> {
-        use std::io::Write;
-
-        let stdout = std::io::stdout();
-        let mut handle = stdout.lock();
-
-        writeln!(handle, "PostgreSQL Configuration Analysis Report").context(OutputSnafu)?;
+    fn write_summary_text<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        results: &AnalysisResults,
+        summary: &ReportSummary,
+    ) -> Result<()> {
+        writeln!(handle, "PostgreSQL Configuration Analysis Summary").context(OutputSnafu)?;
         writeln!(handle, "==========================================\n").context(OutputSnafu)?;
 
-        // Summary
-        let total_suggestions: usize = results
-            .suggestions_by_category
-            .values()
-            .map(|s| s.len())
-            .sum();
-
-        writeln!(handle, "Summary:").context(OutputSnafu)?;
-        writeln!(handle, "  Total Suggestions: {}", total_suggestions).context(OutputSnafu)?;
+        for warning in &results.warnings {
+            writeln!(handle, "WARNING: {}\n", warning).context(OutputSnafu)?;
+        }
 
-        for (level, count) in &[
-            (
-                SuggestionLevel::Critical,
-                results
-                    .suggestions_by_category
-                    .values()
-                    .flat_map(|s| s.iter())
-                    .filter(|s| s.level == SuggestionLevel::Critical)
-                    .count(),
-            ),
-            (
-                SuggestionLevel::Important,
-                results
-                    .suggestions_by_category
-                    .values()
-                    .flat_map(|s| s.iter())
-                    .filter(|s| s.level == SuggestionLevel::Important)
-                    .count(),
-            ),
-            (
-                SuggestionLevel::Recommended,
-                results
-                    .suggestions_by_category
-                    .values()
-                    .flat_map(|s| s.iter())
-                    .filter(|s| s.level == SuggestionLevel::Recommended)
-                    .count(),
-            ),
-            (
-                SuggestionLevel::Info,
-                results
-                    .suggestions_by_category
-                    .values()
-                    .flat_map(|s| s.iter())
-                    .filter(|s| s.level == SuggestionLevel::Info)
-                    .count(),
-            ),
-        ] {
-            if *count > 0 {
+        writeln!(handle, "Health Score: {}", summary.health).context(OutputSnafu)?;
+        writeln!(handle, "Total Suggestions: {}", summary.total_suggestions)
+            .context(OutputSnafu)?;
+        for (level, count) in summary.level_counts.iter() {
+            if count > 0 {
                 writeln!(
                     handle,
                     "  {} ({}): {}",
                     level.as_str(),
-                    self.format_level_text(level),
+                    self.format_level_text(&level),
                     count
                 )
                 .context(OutputSnafu)?;
             }
         }
-
         writeln!(handle).context(OutputSnafu)?;
 
-        // Suggestions by category
-        for (category, suggestions) in &results.suggestions_by_category {
-            if !suggestions.is_empty() {
-                writeln!(handle, "{}", category.as_str()).context(OutputSnafu)?;
-                writeln!(handle, "{}", "=".repeat(category.as_str().len())).context(OutputSnafu)?;
-                writeln!(handle).context(OutputSnafu)?;
-
-                for suggestion in suggestions {
-                    writeln!(
-                        handle,
-                        "  [{}] {}",
-                        self.format_level_text(&suggestion.level),
-                        suggestion.parameter
-                    )
-                    .context(OutputSnafu)?;
-                    writeln!(handle, "    Current:  {}", suggestion.current_value)
-                        .context(OutputSnafu)?;
-                    writeln!(handle, "    Suggest:  {}", suggestion.suggested_value)
-                        .context(OutputSnafu)?;
-                    writeln!(handle, "    Why:      {}", suggestion.rationale)
-                        .context(OutputSnafu)?;
-                    writeln!(handle).context(OutputSnafu)?;
-                }
-            }
-        }
-
-        if !results.bloat_info.is_empty() {
-            writeln!(handle, "Table Bloat Watchlist:").context(OutputSnafu)?;
-            for table in &results.bloat_info {
+        if !summary.top_suggestions.is_empty() {
+            writeln!(handle, "Top Suggestions:").context(OutputSnafu)?;
+            for ranked in &summary.top_suggestions {
+                let suggestion = &ranked.suggestion;
                 writeln!(
                     handle,
-                    "  - {}.{}: {:.1}% dead tuples (last autovacuum: {})",
-                    table.schema,
-                    table.table_name,
-                    table.dead_tup_ratio * 100.0,
-                    table.last_autovacuum.as_deref().unwrap_or("never")
+                    "  [{}] {}: {} -> {} ({})",
+                    self.format_level_text(&suggestion.level),
+                    suggestion.parameter,
+                    suggestion.current_value,
+                    suggestion.suggested_value,
+                    ranked.category.as_str()
                 )
                 .context(OutputSnafu)?;
             }
             writeln!(handle).context(OutputSnafu)?;
         }
 
-        if !results.seq_scan_info.is_empty() {
-            writeln!(handle, "Sequential Scan Hotspots:").context(OutputSnafu)?;
-            for table in &results.seq_scan_info {
-                writeln!(
-                    handle,
-                    "  - {}.{}: {} seq vs {} idx scans ({} rows, {})",
-                    table.schema,
-                    table.table_name,
-                    table.seq_scan,
-                    table.idx_scan,
-                    table.live_tuples,
-                    table.table_size_pretty
-                )
-                .context(OutputSnafu)?;
-            }
-            writeln!(handle).context(OutputSnafu)?;
-        }
-
-        if !results.index_usage_info.is_empty() {
-            writeln!(handle, "Index Findings:").context(OutputSnafu)?;
-            for index in &results.index_usage_info {
-                writeln!(
-                    handle,
-                    "  - [{}] {}.{} on {}.{} ({})",
-                    self.format_issue_name(&index.issue),
-                    index.schema,
-                    index.index_name,
-                    index.schema,
-                    index.table_name,
-                    index.index_size_pretty
-                )
-                .context(OutputSnafu)?;
-            }
-            writeln!(handle).context(OutputSnafu)?;
-        }
+        let headline = &summary.table_index;
+        writeln!(
+            handle,
+            "Table & Index Health: {} bloated tables, {} seq scan hotspots, {} unused indexes ({}), {} other index findings",
+            headline.bloated_tables,
+            headline.seq_scan_hotspots,
+            headline.unused_indexes,
+            headline.unused_index_size,
+            headline.other_index_findings
+        )
+        .context(OutputSnafu)?;
 
         Ok(())
     }
 
-    fn format_level_text(&self, level: &SuggestionLevel) -> &str {
-        match level {
-            SuggestionLevel::Critical => "CRIT",
-            SuggestionLevel::Important => "IMP",
-            SuggestionLevel::Recommended => "REC",
-            SuggestionLevel::Info => "INFO",
+    /// Only the suggestions, without headers, system information or the
+    /// configuration table, so an empty run prints nothing
+    fn write_findings<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        results: &AnalysisResults,
+    ) -> Result<()> {
+        let findings = results.ranked_suggestions();
+        if findings.is_empty() {
+            return Ok(());
+        }
+        match self.format {
+            ReportFormat::Markdown => self.write_suggestions_markdown(handle, results),
+            ReportFormat::Json => {
+                let findings: Vec<_> = findings
+                    .into_iter()
+                    .map(|(category, suggestion)| RankedSuggestion {
+                        category,
+                        suggestion: suggestion.clone(),
+                    })
+                    .collect();
+                write_json(handle, &findings)
+            }
+            ReportFormat::Text => self.write_suggestions_text(handle, results),
+            ReportFormat::GithubComment => self.write_github_comment(handle, results, None),
+            ReportFormat::Html => {
+                let mut markdown = Vec::new();
+                self.write_suggestions_markdown(&mut markdown, results)?;
+                write_html(
+                    handle,
+                    "PostgreSQL Configuration Analysis Findings",
+                    &markdown,
+                )
+            }
         }
     }
 
-    fn write_table_index_markdown(
+    /// Compact pull request comment: a headline with the level counts, one table
+    /// row per suggestion and the rationales folded into a `<details>` block.
+    ///
+    /// With `limit`, only that many of the most severe suggestions are listed and
+    /// the rationales are left out.
+    fn write_github_comment<W: std::io::Write>(
         &self,
-        handle: &mut std::io::StdoutLock,
+        handle: &mut W,
         results: &AnalysisResults,
+        limit: Option<usize>,
     ) -> Result<()> {
-        use std::io::Write;
-
-        writeln!(handle, "## Table & Index Health\n").context(OutputSnafu)?;
+        let summary = results.summary(limit.unwrap_or(usize::MAX));
+        writeln!(handle, "{GITHUB_COMMENT_MARKER}").context(OutputSnafu)?;
 
-        if !results.bloat_info.is_empty() {
-            writeln!(handle, "### Table Bloat Watchlist\n").context(OutputSnafu)?;
+        let counts: Vec<String> = summary
+            .level_counts
+            .iter()
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(level, count)| {
+                format!(
+                    "{} {count} {}",
+                    level_emoji(level),
+                    level.as_str().to_lowercase()
+                )
+            })
+            .collect();
+        if counts.is_empty() {
             writeln!(
                 handle,
-                "| Table | Dead % | Dead Tuples | Live Tuples | Last Autovacuum | Size |"
+                "### PostGreat: no configuration suggestions :white_check_mark:\n"
             )
             .context(OutputSnafu)?;
+        } else {
             writeln!(
                 handle,
-                "|-------|--------|-------------|-------------|-----------------|------|"
+                "### PostGreat {}: {}\n",
+                summary.health,
+                counts.join(" · ")
             )
             .context(OutputSnafu)?;
+        }
 
-            for table in &results.bloat_info {
-                writeln!(
-                    handle,
-                    "| {}.{} | {:.1}% | {} | {} | {} | {} |",
-                    table.schema,
-                    table.table_name,
-                    table.dead_tup_ratio * 100.0,
-                    table.dead_tuples,
-                    table.live_tuples,
-                    table.last_autovacuum.as_deref().unwrap_or("never"),
-                    table.table_size_pretty
-                )
-                .context(OutputSnafu)?;
-            }
-            writeln!(handle).context(OutputSnafu)?;
+        for warning in &results.warnings {
+            writeln!(handle, "> :warning: {}\n", warning).context(OutputSnafu)?;
         }
 
-        if !results.seq_scan_info.is_empty() {
-            writeln!(handle, "### Sequential Scan Hotspots\n").context(OutputSnafu)?;
-            writeln!(
-                handle,
-                "| Table | Seq Scans | Idx Scans | Live Tuples | Size |"
-            )
+        if summary.top_suggestions.is_empty() {
+            return Ok(());
+        }
+        writeln!(handle, "| | Parameter | Current | Suggested | Category |")
             .context(OutputSnafu)?;
+        writeln!(handle, "|---|---|---|---|---|").context(OutputSnafu)?;
+        for ranked in &summary.top_suggestions {
+            let suggestion = &ranked.suggestion;
             writeln!(
                 handle,
-                "|-------|-----------|-----------|-------------|------|"
+                "| {} | `{}` | `{}` | `{}` | {} |",
+                level_emoji(suggestion.level),
+                suggestion.parameter,
+                suggestion.current_value,
+                suggestion.suggested_value,
+                ranked.category.as_str()
             )
             .context(OutputSnafu)?;
+        }
+        writeln!(handle).context(OutputSnafu)?;
 
-            for table in &results.seq_scan_info {
+        if let Some(limit) = limit {
+            if summary.total_suggestions > limit {
                 writeln!(
                     handle,
-                    "| {}.{} | {} | {} | {} | {} |",
-                    table.schema,
-                    table.table_name,
-                    table.seq_scan,
-                    table.idx_scan,
-                    table.live_tuples,
-                    table.table_size_pretty
+                    "_{} more suggestions not shown._\n",
+                    summary.total_suggestions - limit
                 )
                 .context(OutputSnafu)?;
             }
-            writeln!(handle).context(OutputSnafu)?;
+            return Ok(());
         }
 
-        if !results.index_usage_info.is_empty() {
-            writeln!(handle, "### Index Findings\n").context(OutputSnafu)?;
-            for issue in [
-                IndexIssueKind::Unused,
-                IndexIssueKind::LowSelectivity,
-                IndexIssueKind::FailedIndexOnly,
-                IndexIssueKind::MissingPartialIndex,
-                IndexIssueKind::BrinCandidate,
-            ] {
-                let group: Vec<_> = results
-                    .index_usage_info
-                    .iter()
-                    .filter(|idx| idx.issue == issue)
-                    .collect();
-                if group.is_empty() {
-                    continue;
-                }
+        writeln!(
+            handle,
+            "<details>\n<summary>Rationale for {} suggestions</summary>\n",
+            summary.total_suggestions
+        )
+        .context(OutputSnafu)?;
+        for ranked in &summary.top_suggestions {
+            writeln!(
+                handle,
+                "- {} **{}**: {}",
+                level_emoji(ranked.suggestion.level),
+                ranked.suggestion.parameter,
+                ranked.suggestion.rationale
+            )
+            .context(OutputSnafu)?;
+        }
+        writeln!(handle, "\n</details>").context(OutputSnafu)?;
 
-                writeln!(handle, "#### {}\n", self.format_issue_name(&issue))
-                    .context(OutputSnafu)?;
-                writeln!(handle, "| Index | Table | Scans | Size | Notes |")
-                    .context(OutputSnafu)?;
-                writeln!(handle, "|-------|-------|-------|------|-------|")
-                    .context(OutputSnafu)?;
+        Ok(())
+    }
+
+    /// Detailed suggestions, one section per category
+    fn write_suggestions_markdown<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        results: &AnalysisResults,
+    ) -> Result<()> {
+        let mut categories: Vec<ConfigCategory> =
+            results.suggestions_by_category.keys().copied().collect();
+        categories.sort_by_key(|c| c.as_str());
 
-                for idx in group {
-                    let notes = match idx.issue {
-                        IndexIssueKind::Unused => "never scanned".to_string(),
-                        IndexIssueKind::LowSelectivity => {
-                            let percentage = selectivity_ratio(idx) * 100.0;
-                            format!("~{:.1}% of table per scan", percentage.min(100.0))
-                        }
-                        IndexIssueKind::FailedIndexOnly => {
-                            format!("{:.0}% heap fetch ratio", idx.heap_fetch_ratio * 100.0)
-                        }
-                        IndexIssueKind::MissingPartialIndex => {
-                            "missing soft-delete partial index".to_string()
-                        }
-                        IndexIssueKind::BrinCandidate => {
-                            "BRIN candidate for time-series/append-only".to_string()
-                        }
-                    };
+        for category in categories {
+            let suggestions = &results.suggestions_by_category[&category];
 
-                    writeln!(
-                        handle,
-                        "| {}.{} | {}.{} | {} | {} | {} |",
-                        idx.schema,
-                        idx.index_name,
-                        idx.schema,
-                        idx.table_name,
-                        idx.scans,
-                        idx.index_size_pretty,
-                        notes
-                    )
-                    .context(OutputSnafu)?;
-                }
-                writeln!(handle).context(OutputSnafu)?;
+            // Sort by level (Critical first)
+            let mut sorted_suggestions = suggestions.clone();
+            sorted_suggestions.sort_by_key(|suggestion| suggestion.level.rank());
+
+            writeln!(handle, "## {}\n", category.as_str()).context(OutputSnafu)?;
+
+            for suggestion in &sorted_suggestions {
+                self.write_suggestion_markdown(handle, suggestion, results)?;
             }
-        }
 
+            writeln!(handle).context(OutputSnafu)?;
+        }
         Ok(())
     }
 
-    fn format_issue_name(&self, issue: &IndexIssueKind) -> &str {
-        match issue {
-            IndexIssueKind::Unused => "Unused",
-            IndexIssueKind::LowSelectivity => "Low Selectivity",
-            IndexIssueKind::FailedIndexOnly => "Failed Index-Only",
-            IndexIssueKind::MissingPartialIndex => "Missing Partial Index",
-            IndexIssueKind::BrinCandidate => "BRIN Candidate",
+    fn write_suggestion_markdown<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        suggestion: &ConfigSuggestion,
+        results: &AnalysisResults,
+    ) -> Result<()> {
+        let level_badge = self.format_level_badge(&suggestion.level);
+
+        writeln!(handle, "### {} {}\n", suggestion.parameter, level_badge).context(OutputSnafu)?;
+
+        writeln!(handle, "**Current Value**: `{}`", suggestion.current_value)
+            .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "**Suggested Value**: `{}`",
+            suggestion.suggested_value
+        )
+        .context(OutputSnafu)?;
+        if let Some(lifecycle) = lifecycle_of(results, suggestion) {
+            writeln!(handle, "**Status**: {}", lifecycle.status()).context(OutputSnafu)?;
         }
-    }
-}
+        writeln!(handle).context(OutputSnafu)?;
 
-pub struct WorkloadReporter {
-    format: ReportFormat,
-}
+        writeln!(handle, "**Rationale**:\n").context(OutputSnafu)?;
+        writeln!(handle, "{}", suggestion.rationale).context(OutputSnafu)?;
+        writeln!(handle).context(OutputSnafu)?;
 
-impl WorkloadReporter {
-    pub fn new(format: ReportFormat) -> Self {
-        Self { format }
+        Ok(())
     }
 
-    pub fn report(&self, results: &WorkloadResults) -> Result<()> {
-        match self.format {
-            ReportFormat::Markdown => self.report_markdown(results)?,
-            ReportFormat::Json => self.report_json(results)?,
-            ReportFormat::Text => self.report_text(results)?,
-        }
-        Ok(())
+    fn format_level_badge(&self, level: &SuggestionLevel) -> String {
+        let badge = match level {
+            SuggestionLevel::Critical => "![CRITICAL](https://img.shields.io/badge/CRITICAL-red)",
+            SuggestionLevel::Important => {
+                "![IMPORTANT](https://img.shields.io/badge/IMPORTANT-orange)"
+            }
+            SuggestionLevel::Recommended => {
+                "![RECOMMENDED](https://img.shields.io/badge/RECOMMENDED-yellow)"
+            }
+            SuggestionLevel::Info => "![INFO](https://img.shields.io/badge/INFO-blue)",
+        };
+        badge.to_string()
     }
 
-    fn report_markdown(&self, results: &WorkloadResults) -> Result<()> {
-        let stdout = std::io::stdout();
-        let mut handle = stdout.lock();
-        self.write_workload_markdown(&mut handle, results)
+    fn write_json<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        results: &AnalysisResults,
+    ) -> Result<()> {
+        let scored = ScoredResults {
+            health: scoring::score(results),
+            results,
+        };
+        let json = match &self.signing_key {
+            None => serde_json::to_string_pretty(&scored),
+            Some(key) => {
+                let mut report = serde_json::to_value(&scored)
+                    .map_err(std::io::Error::other)
+                    .context(OutputSnafu)?;
+                signing::sign(&mut report, key)
+                    .map_err(std::io::Error::other)
+                    .context(OutputSnafu)?;
+                serde_json::to_string_pretty(&report)
+            }
+        }
+        .map_err(std::io::Error::other)
+        .context(OutputSnafu)?;
+
+        writeln!(handle, "{}", json).context(OutputSnafu)?;
+        Ok(())
     }
 
-    fn write_workload_markdown<W: std::io::Write>(
+    fn write_text<W: std::io::Write>(
         &self,
         handle: &mut W,
-        results: &WorkloadResults,
+        results: &AnalysisResults,
     ) -> Result<()> {
-        writeln!(handle, "# PostgreSQL Workload Analysis Report\n").context(OutputSnafu)?;
-        self.write_workload_summary_markdown(handle, results)?;
+        writeln!(handle, "PostgreSQL Configuration Analysis Report").context(OutputSnafu)?;
+        writeln!(handle, "==========================================\n").context(OutputSnafu)?;
 
-        let show_wal = results
-            .slow_query_groups
-            .iter()
-            .flat_map(|group| group.queries.iter())
-            .any(|query| query.wal_bytes_per_call.is_some());
+        for warning in &results.warnings {
+            writeln!(handle, "WARNING: {}\n", warning).context(OutputSnafu)?;
+        }
 
-        for group in &results.slow_query_groups {
-            writeln!(handle, "## {}\n", format_slow_query_kind(group.kind)).context(OutputSnafu)?;
-            writeln!(handle, "{}\n", describe_slow_query_kind(group.kind)).context(OutputSnafu)?;
-            if group.queries.is_empty() {
-                writeln!(handle, "No queries matched the filters.\n").context(OutputSnafu)?;
-                continue;
-            }
+        // Summary
+        let total_suggestions: usize = results
+            .suggestions_by_category
+            .values()
+            .map(|s| s.len())
+            .sum();
 
-            if show_wal {
-                writeln!(
-                    handle,
-                    "| Query ID | Calls | Total ms | % Total | Mean ms | Max ms | Rows | Shared Read | Temp Written | Cache Hit % | Temp/call | WAL/call | Query |"
-                )
-                .context(OutputSnafu)?;
+        writeln!(handle, "Summary:").context(OutputSnafu)?;
+        writeln!(handle, "  Total Suggestions: {}", total_suggestions).context(OutputSnafu)?;
+
+        for (level, count) in &[
+            (
+                SuggestionLevel::Critical,
+                results
+                    .suggestions_by_category
+                    .values()
+                    .flat_map(|s| s.iter())
+                    .filter(|s| s.level == SuggestionLevel::Critical)
+                    .count(),
+            ),
+            (
+                SuggestionLevel::Important,
+                results
+                    .suggestions_by_category
+                    .values()
+                    .flat_map(|s| s.iter())
+                    .filter(|s| s.level == SuggestionLevel::Important)
+                    .count(),
+            ),
+            (
+                SuggestionLevel::Recommended,
+                results
+                    .suggestions_by_category
+                    .values()
+                    .flat_map(|s| s.iter())
+                    .filter(|s| s.level == SuggestionLevel::Recommended)
+                    .count(),
+            ),
+            (
+                SuggestionLevel::Info,
+                results
+                    .suggestions_by_category
+                    .values()
+                    .flat_map(|s| s.iter())
+                    .filter(|s| s.level == SuggestionLevel::Info)
+                    .count(),
+            ),
+        ] {
+            if *count > 0 {
                 writeln!(
                     handle,
-                    "|---------|-------|----------|---------|---------|--------|------|-------------|--------------|-------------|-----------|----------|-------|"
-                )
-                .context(OutputSnafu)?;
-            } else {
-                writeln!(
-                    handle,
-                    "| Query ID | Calls | Total ms | % Total | Mean ms | Max ms | Rows | Shared Read | Temp Written | Cache Hit % | Temp/call | Query |"
+                    "  {} ({}): {}",
+                    level.as_str(),
+                    self.format_level_text(level),
+                    count
                 )
                 .context(OutputSnafu)?;
+            }
+        }
+
+        writeln!(handle).context(OutputSnafu)?;
+
+        write_health_text(handle, &scoring::score(results))?;
+
+        self.write_parameter_state_text(handle, results)?;
+
+        self.write_suggestions_text(handle, results)?;
+
+        if let Some(saturation) = &results.connection_saturation {
+            self.write_connection_saturation_text(handle, saturation)?;
+        }
+
+        if let Some(memory) = &results.connection_memory {
+            self.write_connection_memory_text(handle, memory)?;
+        }
+
+        if let Some(pitr) = &results.pitr_readiness {
+            self.write_pitr_readiness_text(handle, pitr)?;
+        }
+
+        if let Some(remediation) = &results.managed_remediation {
+            self.write_managed_remediation_text(handle, remediation)?;
+        }
+        if let Some(remediation) = &results.settings_remediation {
+            self.write_settings_remediation_text(handle, remediation)?;
+        }
+
+        if !results.host_commands.is_empty() {
+            writeln!(handle, "Apply on the host:").context(OutputSnafu)?;
+            for command in &results.host_commands {
+                writeln!(handle, "  $ {}", command).context(OutputSnafu)?;
+            }
+            writeln!(handle).context(OutputSnafu)?;
+        }
+
+        if !results.table_health.bloat_info.is_empty() {
+            writeln!(handle, "Table Bloat Watchlist:").context(OutputSnafu)?;
+            for table in &results.table_health.bloat_info {
                 writeln!(
                     handle,
-                    "|---------|-------|----------|---------|---------|--------|------|-------------|--------------|-------------|-----------|-------|"
+                    "  - {}.{}: {:.1}% dead tuples (last autovacuum: {})",
+                    table.schema,
+                    table.table_name,
+                    table.dead_tup_ratio * 100.0,
+                    table.last_autovacuum.as_deref().unwrap_or("never")
                 )
                 .context(OutputSnafu)?;
             }
+            write_not_shown_text(handle, results.table_health.not_shown("bloat"), None)?;
+            writeln!(handle).context(OutputSnafu)?;
+        }
 
-            for query in &group.queries {
-                if show_wal {
-                    writeln!(
-                        handle,
-                        "| {} | {} | {:.2} | {:.1}% | {:.2} | {:.2} | {} | {} | {} | {} | {} | {} | {} |",
-                        query.queryid,
-                        query.calls,
-                        query.total_time_ms,
-                        query.total_time_pct,
-                        query.mean_time_ms,
-                        query.max_time_ms,
-                        query.rows,
-                        query.shared_blks_read,
-                        query.temp_blks_written,
-                        format_optional_pct(query.cache_hit_ratio),
-                        format_optional_f64(query.temp_blks_written_per_call, " blocks"),
-                        format_optional_i64_per_call(query.wal_bytes_per_call, " bytes"),
-                        query.query_text.replace('|', "\\|")
-                    )
-                    .context(OutputSnafu)?;
-                } else {
-                    writeln!(
-                        handle,
-                        "| {} | {} | {:.2} | {:.1}% | {:.2} | {:.2} | {} | {} | {} | {} | {} | {} |",
-                        query.queryid,
-                        query.calls,
-                        query.total_time_ms,
-                        query.total_time_pct,
-                        query.mean_time_ms,
-                        query.max_time_ms,
-                        query.rows,
-                        query.shared_blks_read,
-                        query.temp_blks_written,
-                        format_optional_pct(query.cache_hit_ratio),
-                        format_optional_f64(query.temp_blks_written_per_call, " blocks"),
-                        query.query_text.replace('|', "\\|")
-                    )
-                    .context(OutputSnafu)?;
-                }
+        if !results.table_health.seq_scan_info.is_empty() {
+            writeln!(handle, "Sequential Scan Hotspots:").context(OutputSnafu)?;
+            for table in &results.table_health.seq_scan_info {
+                writeln!(
+                    handle,
+                    "  - {}.{}: {} seq vs {} idx scans ({} rows, {})",
+                    table.schema,
+                    table.table_name,
+                    table.seq_scan,
+                    table.idx_scan,
+                    table.live_tuples,
+                    table.table_size_pretty
+                )
+                .context(OutputSnafu)?;
             }
+            write_not_shown_text(handle, results.table_health.not_shown("seq_scan"), None)?;
             writeln!(handle).context(OutputSnafu)?;
         }
 
-        if !results.query_index_candidates.is_empty() {
-            writeln!(handle, "## Index Candidates (Heuristic)\n").context(OutputSnafu)?;
-            writeln!(
-                handle,
-                "| Table | Columns | Confidence | Calls | Total ms | Mean ms | Query ID | Evidence | Notes | Reason |"
-            )
-            .context(OutputSnafu)?;
-            writeln!(
-                handle,
-                "|-------|---------|------------|-------|----------|---------|----------|----------|-------|--------|"
-            )
-            .context(OutputSnafu)?;
-            for candidate in &results.query_index_candidates {
+        if !results.table_health.index_usage_info.is_empty() {
+            writeln!(handle, "Index Findings:").context(OutputSnafu)?;
+            for index in &results.table_health.index_usage_info {
                 writeln!(
                     handle,
-                    "| {}.{} | {} | {} | {} | {:.2} | {:.2} | {} | {} | {} | {} |",
-                    candidate.schema,
-                    candidate.table,
-                    candidate.columns.join(", "),
-                    candidate.confidence.as_str(),
-                    candidate.calls,
-                    candidate.total_time_ms,
-                    candidate.mean_time_ms,
-                    candidate.queryid,
-                    format_candidate_evidence(&candidate.evidence).replace('|', "\\|"),
-                    format_notes(&candidate.notes).replace('|', "\\|"),
-                    candidate.reason.replace('|', "\\|")
+                    "  - [{}] {}.{} on {}.{} ({})",
+                    format_issue_name(&index.issue),
+                    index.schema,
+                    index.index_name,
+                    index.schema,
+                    index.table_name,
+                    index.index_size_pretty
                 )
                 .context(OutputSnafu)?;
             }
+            for (kind, count) in &results.table_health.not_shown {
+                if let Some(issue) = IndexIssueKind::from_name(kind) {
+                    write_not_shown_text(handle, *count, Some(format_issue_name(&issue)))?;
+                }
+            }
             writeln!(handle).context(OutputSnafu)?;
         }
 
-        if !results.bloat_info.is_empty()
-            || !results.seq_scan_info.is_empty()
-            || !results.index_usage_info.is_empty()
-        {
-            self.write_table_index_markdown(handle, results)?;
+        if !results.schema_usage.is_empty() {
+            write_schema_usage_text(handle, &results.schema_usage)?;
+        }
+
+        if !results.index_removal_plans.is_empty() {
+            self.write_index_removal_text(handle, &results.index_removal_plans)?;
+        }
+
+        if let Some(workload) = &results.workload {
+            writeln!(handle, "Workload Analysis").context(OutputSnafu)?;
+            writeln!(handle, "=================\n").context(OutputSnafu)?;
+            WorkloadReporter::new(self.format).write_workload_sections_text(handle, workload)?;
         }
 
         Ok(())
     }
 
-    fn write_workload_summary_markdown<W: std::io::Write>(
+    /// Suggestions grouped under an underlined category heading
+    fn write_suggestions_text<W: std::io::Write>(
         &self,
         handle: &mut W,
-        results: &WorkloadResults,
+        results: &AnalysisResults,
     ) -> Result<()> {
-        writeln!(handle, "## Summary\n").context(OutputSnafu)?;
-        writeln!(
-            handle,
-            "- **Data source**: `{}`",
-            results.workload_metadata.data_source
-        )
-        .context(OutputSnafu)?;
-        writeln!(handle, "- **Scope**: `{}`", results.workload_metadata.scope)
-            .context(OutputSnafu)?;
-        writeln!(
-            handle,
-            "- **Stats reset at**: {}",
-            results
-                .workload_metadata
-                .stats_reset_at
-                .as_deref()
-                .unwrap_or("unknown")
-        )
-        .context(OutputSnafu)?;
-        writeln!(
-            handle,
-            "- **Entry deallocations**: {}",
-            results
-                .workload_metadata
-                .entry_deallocations
-                .map(|value| value.to_string())
-                .unwrap_or_else(|| "unknown".to_string())
-        )
-        .context(OutputSnafu)?;
-        writeln!(
-            handle,
-            "- **Server version**: {}",
-            results
-                .workload_metadata
-                .server_version
-                .map(|value| value.to_string())
-                .unwrap_or_else(|| "unknown".to_string())
-        )
-        .context(OutputSnafu)?;
-        writeln!(
-            handle,
-            "- **Query text visible**: {}",
-            if results.workload_metadata.query_text_visible {
-                "yes"
-            } else {
-                "no"
-            }
-        )
-        .context(OutputSnafu)?;
-        writeln!(
-            handle,
-            "- **Parsed queries**: {}",
-            results.workload_metadata.parsed_queries
-        )
-        .context(OutputSnafu)?;
-        writeln!(
-            handle,
-            "- **Parse failures**: {}",
-            results.workload_metadata.parse_failures
-        )
-        .context(OutputSnafu)?;
-        writeln!(
-            handle,
-            "- **Suppressed candidates**: {}",
-            results.workload_metadata.suppressed_candidates
-        )
-        .context(OutputSnafu)?;
-        writeln!(
-            handle,
-            "- **Coverage summary**: {} suppressed by existing indexes, {} internal tables skipped, {} unresolved-schema tables skipped, {} unsupported parse shapes, {} parser errors",
-            results.coverage_stats.suppressed_by_existing_index,
-            results.coverage_stats.skipped_internal_tables,
-            results.coverage_stats.skipped_unresolved_schema,
-            results.coverage_stats.skipped_unsupported_parse_shape,
-            results.coverage_stats.parser_errors
-        )
-        .context(OutputSnafu)?;
-        if results.warnings.is_empty() {
-            writeln!(handle, "- **Warnings**: None").context(OutputSnafu)?;
-        } else {
-            for warning in &results.warnings {
-                writeln!(handle, "- **Warning**: {}", warning).context(OutputSnafu)?;
+        for (category, suggestions) in &results.suggestions_by_category {
+            if !suggestions.is_empty() {
+                writeln!(handle, "{}", category.as_str()).context(OutputSnafu)?;
+                writeln!(handle, "{}", "=".repeat(category.as_str().len())).context(OutputSnafu)?;
+                writeln!(handle).context(OutputSnafu)?;
+
+                for suggestion in suggestions {
+                    writeln!(
+                        handle,
+                        "  [{}] {}",
+                        self.format_level_text(&suggestion.level),
+                        suggestion.parameter
+                    )
+                    .context(OutputSnafu)?;
+                    writeln!(handle, "    Current:  {}", suggestion.current_value)
+                        .context(OutputSnafu)?;
+                    writeln!(handle, "    Suggest:  {}", suggestion.suggested_value)
+                        .context(OutputSnafu)?;
+                    if let Some(lifecycle) = lifecycle_of(results, suggestion) {
+                        writeln!(handle, "    Status:   {}", lifecycle.status())
+                            .context(OutputSnafu)?;
+                    }
+                    writeln!(handle, "    Why:      {}", suggestion.rationale)
+                        .context(OutputSnafu)?;
+                    writeln!(handle).context(OutputSnafu)?;
+                }
             }
         }
-        writeln!(handle).context(OutputSnafu)?;
         Ok(())
     }
 
-    fn write_table_index_markdown<W: std::io::Write>(
+    /// Lists parameters waiting for a restart and those changed from their defaults
+    fn write_parameter_state_markdown<W: std::io::Write>(
         &self,
         handle: &mut W,
-        results: &WorkloadResults,
+        results: &AnalysisResults,
     ) -> Result<()> {
-        writeln!(handle, "## Table & Index Health\n").context(OutputSnafu)?;
-
-        if !results.bloat_info.is_empty() {
-            writeln!(handle, "### Table Bloat Watchlist\n").context(OutputSnafu)?;
+        let pending = results.pending_restart_params();
+        if !pending.is_empty() {
             writeln!(
                 handle,
-                "| Table | Dead % | Dead Tuples | Live Tuples | Last Autovacuum | Size |"
+                "## Pending Restart
+"
             )
             .context(OutputSnafu)?;
             writeln!(
                 handle,
-                "|-------|--------|-------------|-------------|-----------------|------|"
+                "These parameters were changed but keep their running value until the server restarts:\n"
             )
             .context(OutputSnafu)?;
-
-            for table in &results.bloat_info {
+            writeln!(handle, "| Parameter | Running Value | Set In |").context(OutputSnafu)?;
+            writeln!(handle, "|-----------|---------------|--------|").context(OutputSnafu)?;
+            for param in pending {
                 writeln!(
                     handle,
-                    "| {}.{} | {:.1}% | {} | {} | {} | {} |",
-                    table.schema,
-                    table.table_name,
-                    table.dead_tup_ratio * 100.0,
-                    table.dead_tuples,
-                    table.live_tuples,
-                    table.last_autovacuum.as_deref().unwrap_or("never"),
-                    table.table_size_pretty
+                    "| {} | {} | {} |",
+                    param.name,
+                    format_setting(&param.current_value, param.unit.as_deref()),
+                    param.set_in()
                 )
                 .context(OutputSnafu)?;
             }
             writeln!(handle).context(OutputSnafu)?;
         }
 
-        if !results.seq_scan_info.is_empty() {
-            writeln!(handle, "### Sequential Scan Hotspots\n").context(OutputSnafu)?;
+        if !results.pending_changes.is_empty() {
+            writeln!(handle, "## Already Configured, Pending Apply\n").context(OutputSnafu)?;
+            writeln!(
+                handle,
+                "These values are staged in a configuration file, e.g. by ALTER SYSTEM, but not in effect yet:\n"
+            )
+            .context(OutputSnafu)?;
             writeln!(
                 handle,
-                "| Table | Seq Scans | Idx Scans | Live Tuples | Size |"
+                "| Parameter | Running Value | Staged Value | Takes Effect On | File | Covers Suggestion |"
             )
             .context(OutputSnafu)?;
             writeln!(
                 handle,
-                "|-------|-----------|-----------|-------------|------|"
+                "|-----------|---------------|--------------|-----------------|------|-------------------|"
             )
             .context(OutputSnafu)?;
+            for change in &results.pending_changes {
+                writeln!(
+                    handle,
+                    "| {} | {} | {} | {} | {} | {} |",
+                    change.name,
+                    format_setting(&change.running_value, change.unit.as_deref()),
+                    change.staged_value,
+                    change.apply.as_str(),
+                    change.sourcefile,
+                    change.covers_suggestion.as_deref().unwrap_or("-")
+                )
+                .context(OutputSnafu)?;
+            }
+            writeln!(handle).context(OutputSnafu)?;
+        }
 
-            for table in &results.seq_scan_info {
+        let changed = results.changed_params();
+        if !changed.is_empty() {
+            writeln!(
+                handle,
+                "## Non-Default Parameters
+"
+            )
+            .context(OutputSnafu)?;
+            writeln!(handle, "| Parameter | Value | Default | Set In |").context(OutputSnafu)?;
+            writeln!(handle, "|-----------|-------|---------|--------|").context(OutputSnafu)?;
+            for param in changed {
                 writeln!(
                     handle,
-                    "| {}.{} | {} | {} | {} | {} |",
-                    table.schema,
-                    table.table_name,
-                    table.seq_scan,
-                    table.idx_scan,
-                    table.live_tuples,
-                    table.table_size_pretty
+                    "| {} | {} | {} | {} |",
+                    param.name,
+                    format_setting(&param.current_value, param.unit.as_deref()),
+                    param
+                        .default_value
+                        .as_deref()
+                        .map(|value| format_setting(value, param.unit.as_deref()))
+                        .unwrap_or_else(|| "-".to_string()),
+                    param.set_in()
                 )
                 .context(OutputSnafu)?;
             }
             writeln!(handle).context(OutputSnafu)?;
         }
 
-        if !results.index_usage_info.is_empty() {
-            writeln!(handle, "### Index Findings\n").context(OutputSnafu)?;
-            for issue in [
-                IndexIssueKind::Unused,
-                IndexIssueKind::LowSelectivity,
-                IndexIssueKind::FailedIndexOnly,
-                IndexIssueKind::MissingPartialIndex,
-                IndexIssueKind::BrinCandidate,
-            ] {
-                let group: Vec<_> = results
-                    .index_usage_info
-                    .iter()
-                    .filter(|idx| idx.issue == issue)
-                    .collect();
-                if group.is_empty() {
-                    continue;
-                }
-
-                writeln!(handle, "#### {}\n", format_issue_name(&issue)).context(OutputSnafu)?;
-                writeln!(handle, "| Index | Table | Scans | Size | Notes |")
-                    .context(OutputSnafu)?;
-                writeln!(handle, "|-------|-------|-------|------|-------|")
-                    .context(OutputSnafu)?;
+        Ok(())
+    }
 
-                for idx in group {
-                    let notes = match idx.issue {
-                        IndexIssueKind::Unused => "never scanned".to_string(),
-                        IndexIssueKind::LowSelectivity => {
-                            let percentage = selectivity_ratio(idx) * 100.0;
-                            format!("~{:.1}% of table per scan", percentage.min(100.0))
-                        }
-                        IndexIssueKind::FailedIndexOnly => {
-                            format!("{:.0}% heap fetch ratio", idx.heap_fetch_ratio * 100.0)
-                        }
-                        IndexIssueKind::MissingPartialIndex => {
-                            "missing soft-delete partial index".to_string()
-                        }
-                        IndexIssueKind::BrinCandidate => {
-                            "BRIN candidate for time-series/append-only".to_string()
-                        }
-                    };
+    fn write_parameter_state_text<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        results: &AnalysisResults,
+    ) -> Result<()> {
+        let pending = results.pending_restart_params();
+        if !pending.is_empty() {
+            writeln!(handle, "Pending Restart:").context(OutputSnafu)?;
+            for param in pending {
+                writeln!(
+                    handle,
+                    "  - {} = {} (set in {})",
+                    param.name,
+                    format_setting(&param.current_value, param.unit.as_deref()),
+                    param.set_in()
+                )
+                .context(OutputSnafu)?;
+            }
+            writeln!(handle).context(OutputSnafu)?;
+        }
 
-                    writeln!(
-                        handle,
-                        "| {}.{} | {}.{} | {} | {} | {} |",
-                        idx.schema,
-                        idx.index_name,
-                        idx.schema,
-                        idx.table_name,
-                        idx.scans,
-                        idx.index_size_pretty,
-                        notes
-                    )
-                    .context(OutputSnafu)?;
+        if !results.pending_changes.is_empty() {
+            writeln!(handle, "Already Configured, Pending Apply:").context(OutputSnafu)?;
+            for change in &results.pending_changes {
+                write!(
+                    handle,
+                    "  - {} = {} staged in {} (running {}; takes effect on {})",
+                    change.name,
+                    change.staged_value,
+                    change.sourcefile,
+                    format_setting(&change.running_value, change.unit.as_deref()),
+                    change.apply.as_str()
+                )
+                .context(OutputSnafu)?;
+                if let Some(suggested) = &change.covers_suggestion {
+                    write!(handle, ", covers the suggested {suggested}").context(OutputSnafu)?;
                 }
                 writeln!(handle).context(OutputSnafu)?;
             }
+            writeln!(handle).context(OutputSnafu)?;
+        }
+
+        let changed = results.changed_params();
+        if !changed.is_empty() {
+            writeln!(handle, "Non-Default Parameters:").context(OutputSnafu)?;
+            for param in changed {
+                writeln!(
+                    handle,
+                    "  - {} = {} (default {}; set in {})",
+                    param.name,
+                    format_setting(&param.current_value, param.unit.as_deref()),
+                    param
+                        .default_value
+                        .as_deref()
+                        .map(|value| format_setting(value, param.unit.as_deref()))
+                        .unwrap_or_else(|| "-".to_string()),
+                    param.set_in()
+                )
+                .context(OutputSnafu)?;
+            }
+            writeln!(handle).context(OutputSnafu)?;
         }
 
         Ok(())
     }
 
-    fn report_json(&self, results: &WorkloadResults) -> Result<()> {
-        let stdout = std::io::stdout();
-        let mut handle = stdout.lock();
-        self.write_workload_json(&mut handle, results)
-    }
+    fn write_connection_saturation_markdown<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        saturation: &ConnectionSaturation,
+    ) -> Result<()> {
+        writeln!(handle, "## Connection Saturation\n").context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "- **Peak**: {} of {} max_connections ({:.0}%) at {}",
+            saturation.peak_connections,
+            saturation.max_connections,
+            saturation.peak_utilization * 100.0,
+            saturation.peak_at
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "- **Samples**: {} from {} to {}\n",
+            saturation.samples, saturation.first_at, saturation.last_at
+        )
+        .context(OutputSnafu)?;
 
-    fn report_text(&self, results: &WorkloadResults) -> Result<()> {
-        let stdout = std::io::stdout();
-        let mut handle = stdout.lock();
-        self.write_workload_text(&mut handle, results)
+        if saturation.hourly_peaks.len() > 1 {
+            writeln!(handle, "| Hour (UTC) | Peak | Utilization | Samples |")
+                .context(OutputSnafu)?;
+            writeln!(handle, "|------------|------|-------------|---------|")
+                .context(OutputSnafu)?;
+            for hour in &saturation.hourly_peaks {
+                writeln!(
+                    handle,
+                    "| {:02}:00 | {} | {:.0}% | {} |",
+                    hour.hour,
+                    hour.peak_connections,
+                    hour.peak_connections as f64 / saturation.max_connections.max(1) as f64 * 100.0,
+                    hour.samples
+                )
+                .context(OutputSnafu)?;
+            }
+            writeln!(handle).context(OutputSnafu)?;
+        }
+
+        Ok(())
     }
 
-    fn write_workload_json<W: std::io::Write>(
+    fn write_connection_saturation_text<W: std::io::Write>(
         &self,
         handle: &mut W,
-        results: &WorkloadResults,
+        saturation: &ConnectionSaturation,
     ) -> Result<()> {
-        let json =
-            serde_json::to_string_pretty(results).map_err(|err| ReporterError::OutputError {
-                source: std::io::Error::other(err),
-            })?;
-        writeln!(handle, "{json}").context(OutputSnafu)?;
+        writeln!(handle, "Connection Saturation:").context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "  Peak: {} of {} max_connections ({:.0}%) at {}, {} samples from {} to {}",
+            saturation.peak_connections,
+            saturation.max_connections,
+            saturation.peak_utilization * 100.0,
+            saturation.peak_at,
+            saturation.samples,
+            saturation.first_at,
+            saturation.last_at
+        )
+        .context(OutputSnafu)?;
+        if saturation.hourly_peaks.len() > 1 {
+            let hours: Vec<_> = saturation
+                .hourly_peaks
+                .iter()
+                .map(|hour| format!("{:02}h {}", hour.hour, hour.peak_connections))
+                .collect();
+            writeln!(handle, "  Peak by hour (UTC): {}", hours.join(", ")).context(OutputSnafu)?;
+        }
+        writeln!(handle).context(OutputSnafu)?;
+
         Ok(())
     }
 
-    fn write_workload_text<W: std::io::Write>(
+    fn write_connection_memory_markdown<W: std::io::Write>(
         &self,
         handle: &mut W,
-        results: &WorkloadResults,
+        memory: &ConnectionMemory,
     ) -> Result<()> {
-        writeln!(handle, "PostgreSQL Workload Analysis Report").context(OutputSnafu)?;
+        writeln!(handle, "## Connection Memory\n").context(OutputSnafu)?;
+        writeln!(handle, "| Component | Per connection |").context(OutputSnafu)?;
+        writeln!(handle, "|-----------|----------------|").context(OutputSnafu)?;
+        writeln!(handle, "| Backend overhead | {} |", memory.backend_overhead)
+            .context(OutputSnafu)?;
         writeln!(
             handle,
-            "Data source: {} ({})",
-            results.workload_metadata.data_source, results.workload_metadata.scope
+            "| work_mem ({} x {}, {:.0}% of connections active) | {} |",
+            memory.work_mem_nodes,
+            memory.work_mem,
+            memory.active_share * 100.0,
+            ByteSize(
+                (memory.work_mem.0 as f64 * memory.work_mem_nodes as f64 * memory.active_share)
+                    as i64
+            )
         )
         .context(OutputSnafu)?;
         writeln!(
             handle,
-            "Stats reset at: {}",
-            results
-                .workload_metadata
-                .stats_reset_at
-                .as_deref()
-                .unwrap_or("unknown")
+            "| temp_buffers (worst case only) | {} |",
+            memory.temp_buffers
         )
         .context(OutputSnafu)?;
         writeln!(
             handle,
-            "Entry deallocations: {}",
-            results
-                .workload_metadata
-                .entry_deallocations
-                .map(|value| value.to_string())
-                .unwrap_or_else(|| "unknown".to_string())
+            "| **Expected / worst case** | **{} / {}** |\n",
+            memory.expected_per_connection, memory.worst_per_connection
         )
         .context(OutputSnafu)?;
+
         writeln!(
             handle,
-            "Query text visible: {}",
-            if results.workload_metadata.query_text_visible {
-                "yes"
-            } else {
-                "no"
-            }
+            "- **Observed peak**: {} connections, ~{} expected",
+            memory.peak_connections, memory.expected_at_peak
         )
         .context(OutputSnafu)?;
         writeln!(
             handle,
-            "Parsed queries: {}, parse failures: {}, suppressed candidates: {}",
-            results.workload_metadata.parsed_queries,
-            results.workload_metadata.parse_failures,
-            results.workload_metadata.suppressed_candidates
+            "- **max_connections**: {}, up to {} if every connection used its full work_mem",
+            memory.max_connections, memory.worst_at_max_connections
         )
         .context(OutputSnafu)?;
+        if let Some(available) = memory.available {
+            writeln!(
+                handle,
+                "- **Available RAM** (total minus shared_buffers): {}",
+                available
+            )
+            .context(OutputSnafu)?;
+        }
         writeln!(
             handle,
-            "Coverage summary: {} suppressed, {} internal, {} unresolved-schema, {} unsupported shapes, {} parser errors",
-            results.coverage_stats.suppressed_by_existing_index,
-            results.coverage_stats.skipped_internal_tables,
-            results.coverage_stats.skipped_unresolved_schema,
-            results.coverage_stats.skipped_unsupported_parse_shape,
-            results.coverage_stats.parser_errors
+            "- **Headroom**: {}: {}\n",
+            memory.headroom.as_str(),
+            memory.headroom.describe()
         )
         .context(OutputSnafu)?;
-        if !results.warnings.is_empty() {
-            for warning in &results.warnings {
-                writeln!(handle, "Warning: {warning}").context(OutputSnafu)?;
-            }
-        }
-        writeln!(handle).context(OutputSnafu)?;
 
-        for group in &results.slow_query_groups {
-            writeln!(handle, "{}:", format_slow_query_kind(group.kind)).context(OutputSnafu)?;
-            writeln!(handle, "  {}", describe_slow_query_kind(group.kind)).context(OutputSnafu)?;
-            for query in &group.queries {
-                writeln!(
-                    handle,
-                    "  - {} calls, total {:.2}ms ({:.1}% of measured total), mean {:.2}ms, cache hit {}, temp/call {}, queryid {}",
-                    query.calls,
-                    query.total_time_ms,
-                    query.total_time_pct,
-                    query.mean_time_ms,
-                    format_optional_pct(query.cache_hit_ratio),
-                    format_optional_f64(query.temp_blks_written_per_call, " blocks"),
-                    query.queryid
-                )
-                .context(OutputSnafu)?;
-                if let Some(wal_bytes_per_call) = query.wal_bytes_per_call {
-                    writeln!(handle, "    WAL/call: {:.1} bytes", wal_bytes_per_call)
-                        .context(OutputSnafu)?;
-                }
-            }
-            writeln!(handle).context(OutputSnafu)?;
-        }
+        Ok(())
+    }
 
-        if !results.query_index_candidates.is_empty() {
-            writeln!(handle, "Index Candidates (Heuristic):").context(OutputSnafu)?;
-            for candidate in &results.query_index_candidates {
-                writeln!(
-                    handle,
-                    "  - {}.{} ({}) [{}]",
-                    candidate.schema,
-                    candidate.table,
-                    candidate.columns.join(", "),
-                    candidate.confidence.as_str()
-                )
-                .context(OutputSnafu)?;
-                writeln!(
-                    handle,
-                    "    evidence: {}",
-                    format_candidate_evidence(&candidate.evidence)
-                )
-                .context(OutputSnafu)?;
-                if !candidate.notes.is_empty() {
-                    writeln!(handle, "    notes: {}", format_notes(&candidate.notes))
-                        .context(OutputSnafu)?;
-                }
-            }
-            writeln!(handle).context(OutputSnafu)?;
+    fn write_connection_memory_text<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        memory: &ConnectionMemory,
+    ) -> Result<()> {
+        writeln!(handle, "Connection Memory:").context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "  Per connection: ~{} expected, {} worst case ({} overhead, {} x {} work_mem at {:.0}% active, {} temp_buffers)",
+            memory.expected_per_connection,
+            memory.worst_per_connection,
+            memory.backend_overhead,
+            memory.work_mem_nodes,
+            memory.work_mem,
+            memory.active_share * 100.0,
+            memory.temp_buffers
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "  Observed peak: {} connections, ~{} expected",
+            memory.peak_connections, memory.expected_at_peak
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "  max_connections: {}, up to {}",
+            memory.max_connections, memory.worst_at_max_connections
+        )
+        .context(OutputSnafu)?;
+        if let Some(available) = memory.available {
+            writeln!(handle, "  Available RAM: {}", available).context(OutputSnafu)?;
         }
+        writeln!(
+            handle,
+            "  Headroom: {} ({})\n",
+            memory.headroom.as_str(),
+            memory.headroom.describe()
+        )
+        .context(OutputSnafu)?;
 
-        if !results.bloat_info.is_empty()
-            || !results.seq_scan_info.is_empty()
-            || !results.index_usage_info.is_empty()
-        {
-            writeln!(handle, "Table & Index Health:").context(OutputSnafu)?;
-            if !results.bloat_info.is_empty() {
-                writeln!(handle, "  - Bloat watchlist: {}", results.bloat_info.len())
-                    .context(OutputSnafu)?;
-            }
-            if !results.seq_scan_info.is_empty() {
-                writeln!(
-                    handle,
-                    "  - Seq scan hotspots: {}",
-                    results.seq_scan_info.len()
-                )
-                .context(OutputSnafu)?;
-            }
-            if !results.index_usage_info.is_empty() {
-                writeln!(
-                    handle,
-                    "  - Index findings: {}",
-                    results.index_usage_info.len()
-                )
+        Ok(())
+    }
+
+    fn write_pitr_readiness_markdown<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        pitr: &PitrReadiness,
+    ) -> Result<()> {
+        writeln!(handle, "## PITR Readiness\n").context(OutputSnafu)?;
+        if pitr.ready {
+            writeln!(handle, "**Ready**: no gaps found.\n").context(OutputSnafu)?;
+        } else {
+            writeln!(handle, "**Not ready**: {} gap(s).\n", pitr.gaps.len())
                 .context(OutputSnafu)?;
-            }
+        }
+        for gap in &pitr.gaps {
+            writeln!(handle, "- {}", gap).context(OutputSnafu)?;
+        }
+        for note in &pitr.notes {
+            writeln!(handle, "- *Note*: {}", note).context(OutputSnafu)?;
+        }
+        if !pitr.gaps.is_empty() || !pitr.notes.is_empty() {
+            writeln!(handle).context(OutputSnafu)?;
         }
 
         Ok(())
     }
-}
 
-fn format_candidate_evidence(evidence: &crate::models::QueryIndexEvidence) -> String {
-    let mut parts = Vec::new();
-    if !evidence.equality_filters.is_empty() {
-        parts.push(format!("WHERE = {}", evidence.equality_filters.join(", ")));
-    }
-    if !evidence.non_equality_filters.is_empty() {
-        parts.push(format!(
-            "WHERE range {}",
-            evidence.non_equality_filters.join(", ")
-        ));
-    }
-    if !evidence.equality_joins.is_empty() {
-        parts.push(format!("JOIN = {}", evidence.equality_joins.join(", ")));
-    }
-    if !evidence.order_by.is_empty() {
-        parts.push(format!("ORDER BY {}", evidence.order_by.join(", ")));
-    }
-    if parts.is_empty() {
-        "none".to_string()
-    } else {
-        parts.join("; ")
-    }
-}
+    fn write_pitr_readiness_text<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        pitr: &PitrReadiness,
+    ) -> Result<()> {
+        if pitr.ready {
+            writeln!(handle, "PITR Readiness: ready").context(OutputSnafu)?;
+        } else {
+            writeln!(
+                handle,
+                "PITR Readiness: not ready ({} gaps)",
+                pitr.gaps.len()
+            )
+            .context(OutputSnafu)?;
+        }
+        for gap in &pitr.gaps {
+            writeln!(handle, "  - {}", gap).context(OutputSnafu)?;
+        }
+        for note in &pitr.notes {
+            writeln!(handle, "  Note: {}", note).context(OutputSnafu)?;
+        }
+        writeln!(handle).context(OutputSnafu)?;
 
-fn format_notes(notes: &[String]) -> String {
-    if notes.is_empty() {
-        "none".to_string()
-    } else {
-        notes.join("; ")
+        Ok(())
     }
-}
 
-fn format_optional_pct(value: Option<f64>) -> String {
+    fn write_managed_remediation_markdown<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        remediation: &ManagedRemediation,
+    ) -> Result<()> {
+        writeln!(handle, "## Apply on {}\n", remediation.platform.as_str()).context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "`ALTER SYSTEM` is not available on this service; apply the suggestions with:\n"
+        )
+        .context(OutputSnafu)?;
+        if !remediation.commands.is_empty() {
+            writeln!(
+                handle,
+                "```bash\n{}\n```\n",
+                remediation.commands.join("\n")
+            )
+            .context(OutputSnafu)?;
+        }
+        if !remediation.rollback.is_empty() {
+            writeln!(
+                handle,
+                "Rollback, restoring the current values:\n\n```bash\n{}\n```\n",
+                remediation.rollback.join("\n")
+            )
+            .context(OutputSnafu)?;
+        }
+        for note in &remediation.notes {
+            writeln!(handle, "- {}", note).context(OutputSnafu)?;
+        }
+        if !remediation.skipped.is_empty() {
+            writeln!(
+                handle,
+                "- Not settable on this service: {}",
+                remediation.skipped.join(", ")
+            )
+            .context(OutputSnafu)?;
+        }
+        writeln!(handle).context(OutputSnafu)?;
+
+        Ok(())
+    }
+
+    fn write_managed_remediation_text<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        remediation: &ManagedRemediation,
+    ) -> Result<()> {
+        writeln!(handle, "Apply on {}:", remediation.platform.as_str()).context(OutputSnafu)?;
+        for command in &remediation.commands {
+            writeln!(handle, "  $ {}", command).context(OutputSnafu)?;
+        }
+        if !remediation.rollback.is_empty() {
+            writeln!(handle, "  Rollback:").context(OutputSnafu)?;
+            for command in &remediation.rollback {
+                writeln!(handle, "  $ {}", command).context(OutputSnafu)?;
+            }
+        }
+        for note in &remediation.notes {
+            writeln!(handle, "  - {}", note).context(OutputSnafu)?;
+        }
+        if !remediation.skipped.is_empty() {
+            writeln!(
+                handle,
+                "  - Not settable on this service: {}",
+                remediation.skipped.join(", ")
+            )
+            .context(OutputSnafu)?;
+        }
+        writeln!(handle).context(OutputSnafu)?;
+
+        Ok(())
+    }
+
+    fn write_settings_remediation_markdown<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        remediation: &SettingsRemediation,
+    ) -> Result<()> {
+        writeln!(handle, "## Apply with ALTER SYSTEM\n").context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "```sql\n{}\n```\n",
+            remediation.statements.join("\n")
+        )
+        .context(OutputSnafu)?;
+        if !remediation.restart_required.is_empty() {
+            writeln!(
+                handle,
+                "Restart the server for {} to take effect.\n",
+                remediation.restart_required.join(", ")
+            )
+            .context(OutputSnafu)?;
+        }
+        writeln!(
+            handle,
+            "Rollback, restoring the current values:\n\n```sql\n{}\n```\n",
+            remediation.rollback.join("\n")
+        )
+        .context(OutputSnafu)?;
+
+        Ok(())
+    }
+
+    fn write_settings_remediation_text<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        remediation: &SettingsRemediation,
+    ) -> Result<()> {
+        writeln!(handle, "Apply with ALTER SYSTEM:").context(OutputSnafu)?;
+        for statement in &remediation.statements {
+            writeln!(handle, "    {}", statement).context(OutputSnafu)?;
+        }
+        if !remediation.restart_required.is_empty() {
+            writeln!(
+                handle,
+                "  - Restart the server for {} to take effect",
+                remediation.restart_required.join(", ")
+            )
+            .context(OutputSnafu)?;
+        }
+        writeln!(handle, "  Rollback:").context(OutputSnafu)?;
+        for statement in &remediation.rollback {
+            writeln!(handle, "    {}", statement).context(OutputSnafu)?;
+        }
+        writeln!(handle).context(OutputSnafu)?;
+
+        Ok(())
+    }
+
+    fn write_index_removal_markdown<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        plans: &[IndexRemovalPlan],
+    ) -> Result<()> {
+        writeln!(handle, "## Index Removal Plan\n").context(OutputSnafu)?;
+
+        for plan in plans {
+            writeln!(
+                handle,
+                "### {}.{} (on {}.{})\n",
+                plan.schema, plan.index_name, plan.schema, plan.table_name
+            )
+            .context(OutputSnafu)?;
+            for note in &plan.notes {
+                writeln!(handle, "- {}", note).context(OutputSnafu)?;
+            }
+            writeln!(handle, "\nCheck usage on every node:\n").context(OutputSnafu)?;
+            writeln!(handle, "```sql\n{}\n```\n", plan.monitor_query).context(OutputSnafu)?;
+
+            if let Some(ddl) = &plan.ddl {
+                writeln!(
+                    handle,
+                    "1. Reset usage counters and start the observation window:\n"
+                )
+                .context(OutputSnafu)?;
+                writeln!(handle, "```sql\n{}\n```\n", ddl.reset_stats).context(OutputSnafu)?;
+                writeln!(handle, "2. Drop once no node reports scans:\n").context(OutputSnafu)?;
+                writeln!(handle, "```sql\n{}\n```\n", ddl.drop_index).context(OutputSnafu)?;
+                if let Some(rollback) = &ddl.rollback {
+                    writeln!(handle, "Rollback:\n").context(OutputSnafu)?;
+                    writeln!(handle, "```sql\n{}\n```\n", rollback).context(OutputSnafu)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_index_removal_text<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        plans: &[IndexRemovalPlan],
+    ) -> Result<()> {
+        writeln!(handle, "Index Removal Plan:").context(OutputSnafu)?;
+
+        for plan in plans {
+            writeln!(handle, "  {}.{}", plan.schema, plan.index_name).context(OutputSnafu)?;
+            for note in &plan.notes {
+                writeln!(handle, "    - {}", note).context(OutputSnafu)?;
+            }
+            writeln!(handle, "    Monitor:  {}", plan.monitor_query).context(OutputSnafu)?;
+            if let Some(ddl) = &plan.ddl {
+                writeln!(handle, "    Reset:    {}", ddl.reset_stats).context(OutputSnafu)?;
+                writeln!(handle, "    Drop:     {}", ddl.drop_index).context(OutputSnafu)?;
+                if let Some(rollback) = &ddl.rollback {
+                    writeln!(handle, "    Rollback: {}", rollback).context(OutputSnafu)?;
+                }
+            }
+        }
+        writeln!(handle).context(OutputSnafu)?;
+
+        Ok(())
+    }
+
+    fn format_level_text(&self, level: &SuggestionLevel) -> &str {
+        match level {
+            SuggestionLevel::Critical => "CRIT",
+            SuggestionLevel::Important => "IMP",
+            SuggestionLevel::Recommended => "REC",
+            SuggestionLevel::Info => "INFO",
+        }
+    }
+}
+
+pub struct WorkloadReporter {
+    format: ReportFormat,
+}
+
+impl WorkloadReporter {
+    pub fn new(format: ReportFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn report(&self, results: &WorkloadResults) -> Result<()> {
+        match self.format {
+            ReportFormat::Markdown | ReportFormat::GithubComment => {
+                self.report_markdown(results)?
+            }
+            ReportFormat::Json => self.report_json(results)?,
+            ReportFormat::Text => self.report_text(results)?,
+            ReportFormat::Html => {
+                let mut markdown = Vec::new();
+                self.write_workload_markdown(&mut markdown, results)?;
+                write_html(
+                    &mut ascii::stdout(),
+                    "PostgreSQL Workload Analysis Report",
+                    &markdown,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn report_markdown(&self, results: &WorkloadResults) -> Result<()> {
+        let mut handle = ascii::stdout();
+        self.write_workload_markdown(&mut handle, results)
+    }
+
+    fn write_workload_markdown<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        results: &WorkloadResults,
+    ) -> Result<()> {
+        writeln!(handle, "# PostgreSQL Workload Analysis Report\n").context(OutputSnafu)?;
+        self.write_workload_sections_markdown(handle, results)
+    }
+
+    /// Everything below the report title, so `analyze --with-workload` can reuse it
+    fn write_workload_sections_markdown<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        results: &WorkloadResults,
+    ) -> Result<()> {
+        self.write_workload_summary_markdown(handle, results)?;
+
+        let show_wal = results
+            .slow_query_groups
+            .iter()
+            .flat_map(|group| group.queries.iter())
+            .any(|query| query.wal_bytes_per_call.is_some());
+
+        if !results.role_summaries.is_empty() {
+            writeln!(handle, "## Time by Role\n").context(OutputSnafu)?;
+            writeln!(handle, "| Role | Statements | Calls | Total ms | % Total |")
+                .context(OutputSnafu)?;
+            writeln!(handle, "|------|------------|-------|----------|---------|")
+                .context(OutputSnafu)?;
+            for summary in &results.role_summaries {
+                writeln!(
+                    handle,
+                    "| {} | {} | {} | {:.2} | {:.1}% |",
+                    summary.role,
+                    summary.statements,
+                    summary.calls,
+                    summary.total_time_ms,
+                    summary.total_time_pct
+                )
+                .context(OutputSnafu)?;
+            }
+            writeln!(handle).context(OutputSnafu)?;
+        }
+
+        for group in &results.slow_query_groups {
+            writeln!(handle, "## {}\n", format_slow_query_kind(group.kind)).context(OutputSnafu)?;
+            writeln!(handle, "{}\n", describe_group(results, group.kind)).context(OutputSnafu)?;
+            if group.queries.is_empty() {
+                writeln!(handle, "No queries matched the filters.\n").context(OutputSnafu)?;
+                continue;
+            }
+
+            if show_wal {
+                writeln!(
+                    handle,
+                    "| Query ID | Calls | Total ms | % Total | Mean ms | Max ms | Rows | Shared Read | Temp Written | Cache Hit % | Temp/call | WAL/call | Roles | Query |"
+                )
+                .context(OutputSnafu)?;
+                writeln!(
+                    handle,
+                    "|---------|-------|----------|---------|---------|--------|------|-------------|--------------|-------------|-----------|----------|-------|-------|"
+                )
+                .context(OutputSnafu)?;
+            } else {
+                writeln!(
+                    handle,
+                    "| Query ID | Calls | Total ms | % Total | Mean ms | Max ms | Rows | Shared Read | Temp Written | Cache Hit % | Temp/call | Roles | Query |"
+                )
+                .context(OutputSnafu)?;
+                writeln!(
+                    handle,
+                    "|---------|-------|----------|---------|---------|--------|------|-------------|--------------|-------------|-----------|-------|-------|"
+                )
+                .context(OutputSnafu)?;
+            }
+
+            for query in &group.queries {
+                if show_wal {
+                    writeln!(
+                        handle,
+                        "| {} | {} | {:.2} | {:.1}% | {:.2} | {:.2} | {} | {} | {} | {} | {} | {} | {} | {} |",
+                        format_slow_query_id(query),
+                        query.calls,
+                        query.total_time_ms,
+                        query.total_time_pct,
+                        query.mean_time_ms,
+                        query.max_time_ms,
+                        query.rows,
+                        query.shared_blks_read,
+                        query.temp_blks_written,
+                        format_optional_pct(query.cache_hit_ratio),
+                        format_optional_f64(query.temp_blks_written_per_call, " blocks"),
+                        format_optional_i64_per_call(query.wal_bytes_per_call, " bytes"),
+                        format_roles(&query.roles),
+                        query.query_text.replace('|', "\\|")
+                    )
+                    .context(OutputSnafu)?;
+                } else {
+                    writeln!(
+                        handle,
+                        "| {} | {} | {:.2} | {:.1}% | {:.2} | {:.2} | {} | {} | {} | {} | {} | {} | {} |",
+                        format_slow_query_id(query),
+                        query.calls,
+                        query.total_time_ms,
+                        query.total_time_pct,
+                        query.mean_time_ms,
+                        query.max_time_ms,
+                        query.rows,
+                        query.shared_blks_read,
+                        query.temp_blks_written,
+                        format_optional_pct(query.cache_hit_ratio),
+                        format_optional_f64(query.temp_blks_written_per_call, " blocks"),
+                        format_roles(&query.roles),
+                        query.query_text.replace('|', "\\|")
+                    )
+                    .context(OutputSnafu)?;
+                }
+            }
+            writeln!(handle).context(OutputSnafu)?;
+        }
+
+        if !results.query_index_candidates.is_empty() {
+            writeln!(handle, "## Index Candidates (Heuristic)\n").context(OutputSnafu)?;
+            writeln!(
+                handle,
+                "| Table | Columns | Confidence | Calls | Total ms | Mean ms | Query ID | Evidence | Notes | Reason | Proposed DDL |"
+            )
+            .context(OutputSnafu)?;
+            writeln!(
+                handle,
+                "|-------|---------|------------|-------|----------|---------|----------|----------|-------|--------|--------------|"
+            )
+            .context(OutputSnafu)?;
+            for candidate in &results.query_index_candidates {
+                writeln!(
+                    handle,
+                    "| {}.{} | {} | {} | {} | {:.2} | {:.2} | {} | {} | {} | {} | `{}` |",
+                    candidate.schema,
+                    candidate.table,
+                    format_candidate_columns(candidate),
+                    candidate.confidence.as_str(),
+                    candidate.calls,
+                    candidate.total_time_ms,
+                    candidate.mean_time_ms,
+                    candidate.queryid,
+                    format_candidate_evidence(&candidate.evidence).replace('|', "\\|"),
+                    format_notes(&candidate.notes).replace('|', "\\|"),
+                    candidate.reason.replace('|', "\\|"),
+                    candidate.ddl.replace('|', "\\|")
+                )
+                .context(OutputSnafu)?;
+            }
+            writeln!(handle).context(OutputSnafu)?;
+        }
+
+        if !results.application_patterns.is_empty() {
+            writeln!(handle, "## Application Patterns\n").context(OutputSnafu)?;
+            writeln!(
+                handle,
+                "| Pattern | Query ID | Calls | Mean ms | Evidence | Suggestion | Query |"
+            )
+            .context(OutputSnafu)?;
+            writeln!(
+                handle,
+                "|---------|----------|-------|---------|----------|------------|-------|"
+            )
+            .context(OutputSnafu)?;
+            for finding in &results.application_patterns {
+                writeln!(
+                    handle,
+                    "| {} | {} | {} | {:.2} | {} | {} | {} |",
+                    format_application_pattern_kind(finding.kind),
+                    finding.queryid,
+                    finding.calls,
+                    finding.mean_time_ms,
+                    finding.evidence.replace('|', "\\|"),
+                    finding.suggestion.replace('|', "\\|"),
+                    finding.query_text.replace('|', "\\|")
+                )
+                .context(OutputSnafu)?;
+            }
+            writeln!(handle).context(OutputSnafu)?;
+        }
+
+        if let Some(findings) = &results.log_findings {
+            write_log_findings_markdown(handle, findings)?;
+        }
+
+        if !results.table_health.is_empty() {
+            write_table_index_markdown(handle, &results.table_health)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_workload_summary_markdown<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        results: &WorkloadResults,
+    ) -> Result<()> {
+        writeln!(handle, "## Summary\n").context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "- **Data source**: `{}`",
+            results.workload_metadata.data_source
+        )
+        .context(OutputSnafu)?;
+        writeln!(handle, "- **Scope**: `{}`", results.workload_metadata.scope)
+            .context(OutputSnafu)?;
+        if !results.workload_metadata.replica_sources.is_empty() {
+            writeln!(
+                handle,
+                "- **Replicas merged**: {}",
+                results.workload_metadata.replica_sources.join(", ")
+            )
+            .context(OutputSnafu)?;
+        }
+        if results.workload_metadata.queries_redacted {
+            writeln!(
+                handle,
+                "- **Query text**: redacted (literals removed, object names hashed)"
+            )
+            .context(OutputSnafu)?;
+        }
+        if let Some(track_io_timing) = results.workload_metadata.track_io_timing {
+            writeln!(
+                handle,
+                "- **I/O timing**: {}",
+                if track_io_timing { "on" } else { "off" }
+            )
+            .context(OutputSnafu)?;
+        }
+        writeln!(
+            handle,
+            "- **Stats reset at**: {}",
+            results
+                .workload_metadata
+                .stats_reset_at
+                .as_deref()
+                .unwrap_or("unknown")
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "- **Entry deallocations**: {}",
+            results
+                .workload_metadata
+                .entry_deallocations
+                .map(|value| value.to_string())
+                .unwrap_or_else(|| "unknown".to_string())
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "- **Server version**: {}",
+            results
+                .workload_metadata
+                .server_version
+                .map(|value| value.to_string())
+                .unwrap_or_else(|| "unknown".to_string())
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "- **Query text visible**: {}",
+            if results.workload_metadata.query_text_visible {
+                "yes"
+            } else {
+                "no"
+            }
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "- **Parsed queries**: {}",
+            results.workload_metadata.parsed_queries
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "- **Parse failures**: {}",
+            results.workload_metadata.parse_failures
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "- **Suppressed candidates**: {}",
+            results.workload_metadata.suppressed_candidates
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "- **Coverage summary**: {} suppressed by existing indexes, {} internal tables skipped, {} unresolved-schema tables skipped, {} unsupported parse shapes, {} parser errors",
+            results.coverage_stats.suppressed_by_existing_index,
+            results.coverage_stats.skipped_internal_tables,
+            results.coverage_stats.skipped_unresolved_schema,
+            results.coverage_stats.skipped_unsupported_parse_shape,
+            results.coverage_stats.parser_errors
+        )
+        .context(OutputSnafu)?;
+        if results.warnings.is_empty() {
+            writeln!(handle, "- **Warnings**: None").context(OutputSnafu)?;
+        } else {
+            for warning in &results.warnings {
+                writeln!(handle, "- **Warning**: {}", warning).context(OutputSnafu)?;
+            }
+        }
+        writeln!(handle).context(OutputSnafu)?;
+        Ok(())
+    }
+
+    fn report_json(&self, results: &WorkloadResults) -> Result<()> {
+        let mut handle = ascii::stdout();
+        self.write_workload_json(&mut handle, results)
+    }
+
+    fn report_text(&self, results: &WorkloadResults) -> Result<()> {
+        let mut handle = ascii::stdout();
+        self.write_workload_text(&mut handle, results)
+    }
+
+    fn write_workload_json<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        results: &WorkloadResults,
+    ) -> Result<()> {
+        let json =
+            serde_json::to_string_pretty(results).map_err(|err| ReporterError::OutputError {
+                source: std::io::Error::other(err),
+            })?;
+        writeln!(handle, "{json}").context(OutputSnafu)?;
+        Ok(())
+    }
+
+    fn write_workload_text<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        results: &WorkloadResults,
+    ) -> Result<()> {
+        writeln!(handle, "PostgreSQL Workload Analysis Report").context(OutputSnafu)?;
+        self.write_workload_sections_text(handle, results)
+    }
+
+    fn write_workload_sections_text<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        results: &WorkloadResults,
+    ) -> Result<()> {
+        writeln!(
+            handle,
+            "Data source: {} ({})",
+            results.workload_metadata.data_source, results.workload_metadata.scope
+        )
+        .context(OutputSnafu)?;
+        if !results.workload_metadata.replica_sources.is_empty() {
+            writeln!(
+                handle,
+                "Replicas merged: {}",
+                results.workload_metadata.replica_sources.join(", ")
+            )
+            .context(OutputSnafu)?;
+        }
+        if results.workload_metadata.queries_redacted {
+            writeln!(
+                handle,
+                "Query text: redacted (literals removed, object names hashed)"
+            )
+            .context(OutputSnafu)?;
+        }
+        writeln!(
+            handle,
+            "Stats reset at: {}",
+            results
+                .workload_metadata
+                .stats_reset_at
+                .as_deref()
+                .unwrap_or("unknown")
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "Entry deallocations: {}",
+            results
+                .workload_metadata
+                .entry_deallocations
+                .map(|value| value.to_string())
+                .unwrap_or_else(|| "unknown".to_string())
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "Query text visible: {}",
+            if results.workload_metadata.query_text_visible {
+                "yes"
+            } else {
+                "no"
+            }
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "Parsed queries: {}, parse failures: {}, suppressed candidates: {}",
+            results.workload_metadata.parsed_queries,
+            results.workload_metadata.parse_failures,
+            results.workload_metadata.suppressed_candidates
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "Coverage summary: {} suppressed, {} internal, {} unresolved-schema, {} unsupported shapes, {} parser errors",
+            results.coverage_stats.suppressed_by_existing_index,
+            results.coverage_stats.skipped_internal_tables,
+            results.coverage_stats.skipped_unresolved_schema,
+            results.coverage_stats.skipped_unsupported_parse_shape,
+            results.coverage_stats.parser_errors
+        )
+        .context(OutputSnafu)?;
+        if !results.warnings.is_empty() {
+            for warning in &results.warnings {
+                writeln!(handle, "Warning: {warning}").context(OutputSnafu)?;
+            }
+        }
+        writeln!(handle).context(OutputSnafu)?;
+
+        if !results.role_summaries.is_empty() {
+            writeln!(handle, "Time by Role:").context(OutputSnafu)?;
+            for summary in &results.role_summaries {
+                writeln!(
+                    handle,
+                    "  - {}: {:.2}ms ({:.1}% of total) over {} calls to {} statements",
+                    summary.role,
+                    summary.total_time_ms,
+                    summary.total_time_pct,
+                    summary.calls,
+                    summary.statements
+                )
+                .context(OutputSnafu)?;
+            }
+            writeln!(handle).context(OutputSnafu)?;
+        }
+
+        for group in &results.slow_query_groups {
+            writeln!(handle, "{}:", format_slow_query_kind(group.kind)).context(OutputSnafu)?;
+            writeln!(handle, "  {}", describe_group(results, group.kind)).context(OutputSnafu)?;
+            for query in &group.queries {
+                writeln!(
+                    handle,
+                    "  - {} calls, total {:.2}ms ({:.1}% of measured total), mean {:.2}ms, cache hit {}, temp/call {}, queryid {}",
+                    query.calls,
+                    query.total_time_ms,
+                    query.total_time_pct,
+                    query.mean_time_ms,
+                    format_optional_pct(query.cache_hit_ratio),
+                    format_optional_f64(query.temp_blks_written_per_call, " blocks"),
+                    query.queryid
+                )
+                .context(OutputSnafu)?;
+                if let Some(wal_bytes_per_call) = query.wal_bytes_per_call {
+                    let fpi = query
+                        .wal_fpi
+                        .map(|fpi| format!(", {fpi} full-page images"))
+                        .unwrap_or_default();
+                    writeln!(handle, "    WAL/call: {wal_bytes_per_call:.1} bytes{fpi}")
+                        .context(OutputSnafu)?;
+                }
+                if let (Some(io_time_ms), Some(share)) = (query.io_time_ms, query.io_time_share) {
+                    writeln!(
+                        handle,
+                        "    I/O time: {io_time_ms:.2}ms ({:.0}% of execution time)",
+                        share * 100.0
+                    )
+                    .context(OutputSnafu)?;
+                }
+                if !query.roles.is_empty() {
+                    writeln!(
+                        handle,
+                        "    roles: {}{}",
+                        format_roles(&query.roles),
+                        query
+                            .database
+                            .as_deref()
+                            .map(|database| format!(" on {database}"))
+                            .unwrap_or_default()
+                    )
+                    .context(OutputSnafu)?;
+                }
+                if !query.merged_queryids.is_empty() {
+                    writeln!(
+                        handle,
+                        "    includes {} variant(s) differing only in literals or list lengths: queryid {}",
+                        query.merged_queryids.len(),
+                        join_queryids(&query.merged_queryids)
+                    )
+                    .context(OutputSnafu)?;
+                }
+            }
+            writeln!(handle).context(OutputSnafu)?;
+        }
+
+        if !results.query_index_candidates.is_empty() {
+            writeln!(handle, "Index Candidates (Heuristic):").context(OutputSnafu)?;
+            for candidate in &results.query_index_candidates {
+                writeln!(
+                    handle,
+                    "  - {}.{} ({}) [{}]",
+                    candidate.schema,
+                    candidate.table,
+                    format_candidate_columns(candidate),
+                    candidate.confidence.as_str()
+                )
+                .context(OutputSnafu)?;
+                writeln!(
+                    handle,
+                    "    reason: {} (queryid {}, {} calls, {:.2} ms mean)",
+                    candidate.reason, candidate.queryid, candidate.calls, candidate.mean_time_ms
+                )
+                .context(OutputSnafu)?;
+                writeln!(
+                    handle,
+                    "    evidence: {}",
+                    format_candidate_evidence(&candidate.evidence)
+                )
+                .context(OutputSnafu)?;
+                if !candidate.ddl.is_empty() {
+                    writeln!(handle, "    ddl: {}", candidate.ddl).context(OutputSnafu)?;
+                }
+                if !candidate.notes.is_empty() {
+                    writeln!(handle, "    notes: {}", format_notes(&candidate.notes))
+                        .context(OutputSnafu)?;
+                }
+            }
+            writeln!(handle).context(OutputSnafu)?;
+        }
+
+        if !results.application_patterns.is_empty() {
+            writeln!(handle, "Application Patterns:").context(OutputSnafu)?;
+            for finding in &results.application_patterns {
+                writeln!(
+                    handle,
+                    "  - [{}] queryid {} ({} calls, {:.2} ms mean): {}",
+                    format_application_pattern_kind(finding.kind),
+                    finding.queryid,
+                    finding.calls,
+                    finding.mean_time_ms,
+                    finding.evidence
+                )
+                .context(OutputSnafu)?;
+                writeln!(handle, "    suggestion: {}", finding.suggestion).context(OutputSnafu)?;
+            }
+            writeln!(handle).context(OutputSnafu)?;
+        }
+
+        if let Some(findings) = &results.log_findings {
+            write_log_findings_text(handle, findings)?;
+        }
+
+        if !results.table_health.is_empty() {
+            writeln!(handle, "Table & Index Health:").context(OutputSnafu)?;
+            if !results.table_health.bloat_info.is_empty() {
+                writeln!(
+                    handle,
+                    "  - Bloat watchlist: {}",
+                    results.table_health.bloat_info.len()
+                )
+                .context(OutputSnafu)?;
+            }
+            if !results.table_health.seq_scan_info.is_empty() {
+                writeln!(
+                    handle,
+                    "  - Seq scan hotspots: {}",
+                    results.table_health.seq_scan_info.len()
+                )
+                .context(OutputSnafu)?;
+            }
+            if !results.table_health.index_usage_info.is_empty() {
+                writeln!(
+                    handle,
+                    "  - Index findings: {}",
+                    results.table_health.index_usage_info.len()
+                )
+                .context(OutputSnafu)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Health score and level counts of every database in a config file, worst first
+pub struct FleetReporter {
+    format: ReportFormat,
+}
+
+impl FleetReporter {
+    pub fn new(format: ReportFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn report(&self, entries: &[FleetEntry]) -> Result<()> {
+        let mut handle = ascii::stdout();
+        if let ReportFormat::Html = self.format {
+            let mut markdown = Vec::new();
+            self.write_fleet(&mut markdown, entries)?;
+            return write_html(&mut handle, "PostGreat Fleet Summary", &markdown);
+        }
+        self.write_fleet(&mut handle, entries)
+    }
+
+    fn write_fleet<W: std::io::Write>(&self, handle: &mut W, entries: &[FleetEntry]) -> Result<()> {
+        let mut entries: Vec<_> = entries.iter().collect();
+        entries.sort_by_key(|entry| (entry.health.score, &entry.database));
+        match self.format {
+            ReportFormat::Json => write_json(handle, &entries),
+            ReportFormat::Text => {
+                writeln!(handle, "Fleet Summary").context(OutputSnafu)?;
+                writeln!(handle, "=============\n").context(OutputSnafu)?;
+                let width = entries
+                    .iter()
+                    .map(|entry| entry.database.len() + entry.host.len() + 1)
+                    .max()
+                    .unwrap_or(0);
+                for entry in entries {
+                    let counts = &entry.level_counts;
+                    writeln!(
+                        handle,
+                        "{:<width$}  {:>3} ({})  {} critical, {} important, {} recommended",
+                        format!("{}@{}", entry.database, entry.host),
+                        entry.health.score,
+                        entry.health.grade,
+                        counts.critical,
+                        counts.important,
+                        counts.recommended
+                    )
+                    .context(OutputSnafu)?;
+                }
+                Ok(())
+            }
+            ReportFormat::Markdown | ReportFormat::Html | ReportFormat::GithubComment => {
+                writeln!(handle, "# Fleet Summary\n").context(OutputSnafu)?;
+                writeln!(
+                    handle,
+                    "| Database | Host | Score | Grade | Critical | Important | Recommended |"
+                )
+                .context(OutputSnafu)?;
+                writeln!(
+                    handle,
+                    "|----------|------|-------|-------|----------|-----------|-------------|"
+                )
+                .context(OutputSnafu)?;
+                for entry in entries {
+                    let counts = &entry.level_counts;
+                    writeln!(
+                        handle,
+                        "| {} | {} | {} | {} | {} | {} | {} |",
+                        entry.database,
+                        entry.host,
+                        entry.health.score,
+                        entry.health.grade,
+                        counts.critical,
+                        counts.important,
+                        counts.recommended
+                    )
+                    .context(OutputSnafu)?;
+                }
+                writeln!(handle).context(OutputSnafu)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+pub struct CheckCatalogReporter {
+    format: ReportFormat,
+}
+
+impl CheckCatalogReporter {
+    pub fn new(format: ReportFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn report(&self, checks: &[CheckInfo]) -> Result<()> {
+        let mut handle = ascii::stdout();
+        if let ReportFormat::Html = self.format {
+            let mut markdown = Vec::new();
+            self.write_checks(&mut markdown, checks)?;
+            return write_html(&mut handle, "PostGreat Checks", &markdown);
+        }
+        self.write_checks(&mut handle, checks)
+    }
+
+    fn write_checks<W: std::io::Write>(&self, handle: &mut W, checks: &[CheckInfo]) -> Result<()> {
+        match self.format {
+            ReportFormat::Markdown | ReportFormat::Html | ReportFormat::GithubComment => {
+                writeln!(handle, "# PostGreat Checks\n").context(OutputSnafu)?;
+                writeln!(
+                    handle,
+                    "| ID | Category | Default Severity | Privileges | Extensions | Description |"
+                )
+                .context(OutputSnafu)?;
+                writeln!(
+                    handle,
+                    "|----|----------|------------------|------------|------------|-------------|"
+                )
+                .context(OutputSnafu)?;
+                for check in checks {
+                    writeln!(
+                        handle,
+                        "| `{}` | {} | {} | {} | {} | {} |",
+                        check.id,
+                        check.category.as_str(),
+                        check.default_level.as_str(),
+                        format_list(check.privileges),
+                        format_list(check.extensions),
+                        check.summary
+                    )
+                    .context(OutputSnafu)?;
+                }
+            }
+            ReportFormat::Json => {
+                let json = serde_json::to_string_pretty(checks).map_err(|err| {
+                    ReporterError::OutputError {
+                        source: std::io::Error::other(err),
+                    }
+                })?;
+                writeln!(handle, "{json}").context(OutputSnafu)?;
+            }
+            ReportFormat::Text => {
+                let id_width = checks.iter().map(|c| c.id.len()).max().unwrap_or(0);
+                for check in checks {
+                    writeln!(
+                        handle,
+                        "{:<id_width$}  {:<11}  {}",
+                        check.id,
+                        check.default_level.as_str(),
+                        check.summary
+                    )
+                    .context(OutputSnafu)?;
+                    writeln!(
+                        handle,
+                        "{:<id_width$}  privileges: {}; extensions: {}",
+                        "",
+                        format_list(check.privileges),
+                        format_list(check.extensions)
+                    )
+                    .context(OutputSnafu)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Renders `postgreat rules`: the check catalog as a document to publish
+pub struct RuleDocsReporter {
+    format: ReportFormat,
+}
+
+impl RuleDocsReporter {
+    pub fn new(format: ReportFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn report(&self, docs: &[RuleDoc]) -> Result<()> {
+        let mut handle = ascii::stdout();
+        if let ReportFormat::Html = self.format {
+            let mut markdown = Vec::new();
+            self.write_rules(&mut markdown, docs)?;
+            return write_html(&mut handle, "PostGreat Rules", &markdown);
+        }
+        self.write_rules(&mut handle, docs)
+    }
+
+    fn write_rules<W: std::io::Write>(&self, handle: &mut W, docs: &[RuleDoc]) -> Result<()> {
+        match self.format {
+            ReportFormat::Markdown | ReportFormat::Html | ReportFormat::GithubComment => {
+                self.write_rules_markdown(handle, docs)
+            }
+            ReportFormat::Json => write_json(handle, &docs),
+            ReportFormat::Text => self.write_rules_text(handle, docs),
+        }
+    }
+
+    fn write_rules_markdown<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        docs: &[RuleDoc],
+    ) -> Result<()> {
+        writeln!(
+            handle,
+            "# PostGreat Rules
+"
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "Generated by `postgreat rules` {} from its check registry.",
+            env!("CARGO_PKG_VERSION")
+        )
+        .context(OutputSnafu)?;
+
+        let mut category = None;
+        for doc in docs {
+            let check = doc.check;
+            if category != Some(check.category) {
+                category = Some(check.category);
+                writeln!(handle, "\n## {}", check.category.as_str()).context(OutputSnafu)?;
+            }
+            writeln!(handle, "\n### `{}`\n", check.id).context(OutputSnafu)?;
+            writeln!(handle, "{}\n", check.summary).context(OutputSnafu)?;
+            writeln!(
+                handle,
+                "- **Default severity**: {}",
+                check.default_level.as_str()
+            )
+            .context(OutputSnafu)?;
+            if let Some(parameter) = doc.parameter {
+                writeln!(handle, "- **Parameter**: `{parameter}`").context(OutputSnafu)?;
+            }
+            if let Some(guide) = doc.guide {
+                writeln!(handle, "- **Takes effect**: {}", guide.apply_mode.as_str())
+                    .context(OutputSnafu)?;
+            }
+            writeln!(
+                handle,
+                "- **Privileges**: {}",
+                format_list(check.privileges)
+            )
+            .context(OutputSnafu)?;
+            writeln!(
+                handle,
+                "- **Extensions**: {}",
+                format_list(check.extensions)
+            )
+            .context(OutputSnafu)?;
+
+            if let Some(guide) = doc.guide {
+                writeln!(handle, "\n**Purpose**: {}\n", guide.purpose).context(OutputSnafu)?;
+                writeln!(handle, "**Recommendation**: {}", guide.recommendation)
+                    .context(OutputSnafu)?;
+            }
+
+            if !doc.thresholds.is_empty() {
+                writeln!(handle, "\n| Profile | Threshold |").context(OutputSnafu)?;
+                writeln!(handle, "|---------|-----------|").context(OutputSnafu)?;
+                for threshold in &doc.thresholds {
+                    writeln!(
+                        handle,
+                        "| {} | {} |",
+                        threshold.profile.as_str(),
+                        threshold.threshold
+                    )
+                    .context(OutputSnafu)?;
+                }
+            }
+
+            if !doc.rationales.is_empty() {
+                writeln!(handle, "\n**Rationale**\n").context(OutputSnafu)?;
+                for rationale in &doc.rationales {
+                    writeln!(handle, "- `{}`: {}", rationale.key, rationale.template)
+                        .context(OutputSnafu)?;
+                }
+            }
+
+            let related = doc.guide.map_or(&[][..], |guide| guide.related);
+            if !doc.references.is_empty() || !related.is_empty() {
+                writeln!(handle, "\n**References**\n").context(OutputSnafu)?;
+                for reference in &doc.references {
+                    writeln!(handle, "- [{}]({})", reference.title, reference.url)
+                        .context(OutputSnafu)?;
+                }
+                if !related.is_empty() {
+                    let related: Vec<_> = related.iter().map(|name| format!("`{name}`")).collect();
+                    writeln!(handle, "- Related parameters: {}", related.join(", "))
+                        .context(OutputSnafu)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_rules_text<W: std::io::Write>(&self, handle: &mut W, docs: &[RuleDoc]) -> Result<()> {
+        for doc in docs {
+            let check = doc.check;
+            writeln!(
+                handle,
+                "{} [{}] {}",
+                check.id,
+                check.default_level.as_str(),
+                check.summary
+            )
+            .context(OutputSnafu)?;
+            for threshold in &doc.thresholds {
+                writeln!(
+                    handle,
+                    "  {}: {}",
+                    threshold.profile.as_str(),
+                    threshold.threshold
+                )
+                .context(OutputSnafu)?;
+            }
+            for rationale in &doc.rationales {
+                writeln!(handle, "  {}: {}", rationale.key, rationale.template)
+                    .context(OutputSnafu)?;
+            }
+            for reference in &doc.references {
+                writeln!(handle, "  see {}", reference.url).context(OutputSnafu)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct QueryCatalogReporter {
+    format: ReportFormat,
+}
+
+impl QueryCatalogReporter {
+    pub fn new(format: ReportFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn report(&self, queries: &[CatalogQuery]) -> Result<()> {
+        let mut handle = ascii::stdout();
+        if let ReportFormat::Html = self.format {
+            let mut markdown = Vec::new();
+            self.write_queries(&mut markdown, queries)?;
+            return write_html(&mut handle, "PostGreat Queries", &markdown);
+        }
+        self.write_queries(&mut handle, queries)
+    }
+
+    fn write_queries<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        queries: &[CatalogQuery],
+    ) -> Result<()> {
+        if let ReportFormat::Json = self.format {
+            return write_json(handle, &queries);
+        }
+        if !matches!(self.format, ReportFormat::Text) {
+            writeln!(handle, "# PostGreat Queries\n").context(OutputSnafu)?;
+        }
+        write_query_list(handle, self.format, queries)
+    }
+}
+
+/// Grants and extensions the checks need, with the number of checks needing each
+#[derive(serde::Serialize)]
+struct Requirement<'a> {
+    name: &'a str,
+    checks: usize,
+}
+
+pub struct PermissionsReporter {
+    format: ReportFormat,
+}
+
+impl PermissionsReporter {
+    pub fn new(format: ReportFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn report(&self, checks: &[CheckInfo], queries: &[CatalogQuery]) -> Result<()> {
+        let mut handle = ascii::stdout();
+        if let ReportFormat::Html = self.format {
+            let mut markdown = Vec::new();
+            self.write_permissions(&mut markdown, checks, queries)?;
+            return write_html(&mut handle, "PostGreat Permissions", &markdown);
+        }
+        self.write_permissions(&mut handle, checks, queries)
+    }
+
+    fn write_permissions<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        checks: &[CheckInfo],
+        queries: &[CatalogQuery],
+    ) -> Result<()> {
+        let privileges = requirements(checks.iter().map(|check| check.privileges));
+        let extensions = requirements(checks.iter().map(|check| check.extensions));
+        match self.format {
+            ReportFormat::Json => {
+                #[derive(serde::Serialize)]
+                struct Permissions<'a> {
+                    privileges: Vec<Requirement<'a>>,
+                    extensions: Vec<Requirement<'a>>,
+                    queries: &'a [CatalogQuery],
+                }
+                write_json(
+                    handle,
+                    &Permissions {
+                        privileges,
+                        extensions,
+                        queries,
+                    },
+                )
+            }
+            ReportFormat::Text => {
+                for (title, requirements) in
+                    [("Privileges", &privileges), ("Extensions", &extensions)]
+                {
+                    writeln!(handle, "{title}:").context(OutputSnafu)?;
+                    for requirement in requirements {
+                        writeln!(
+                            handle,
+                            "  {} ({} checks)",
+                            requirement.name, requirement.checks
+                        )
+                        .context(OutputSnafu)?;
+                    }
+                    writeln!(handle).context(OutputSnafu)?;
+                }
+                writeln!(handle, "Queries:\n").context(OutputSnafu)?;
+                write_query_list(handle, self.format, queries)
+            }
+            ReportFormat::Markdown | ReportFormat::Html | ReportFormat::GithubComment => {
+                writeln!(handle, "# PostGreat Permissions\n").context(OutputSnafu)?;
+                writeln!(
+                    handle,
+                    "Checks whose privileges or extensions are missing are skipped; \
+                     the rest of the report still runs.\n"
+                )
+                .context(OutputSnafu)?;
+                for (title, requirements) in
+                    [("Privileges", &privileges), ("Extensions", &extensions)]
+                {
+                    writeln!(handle, "## {title}\n").context(OutputSnafu)?;
+                    writeln!(handle, "| Name | Checks |").context(OutputSnafu)?;
+                    writeln!(handle, "|------|--------|").context(OutputSnafu)?;
+                    for requirement in requirements {
+                        writeln!(handle, "| {} | {} |", requirement.name, requirement.checks)
+                            .context(OutputSnafu)?;
+                    }
+                    writeln!(handle).context(OutputSnafu)?;
+                }
+                writeln!(handle, "## Queries\n").context(OutputSnafu)?;
+                write_query_list(handle, self.format, queries)
+            }
+        }
+    }
+}
+
+fn requirements<'a>(lists: impl Iterator<Item = &'a [&'a str]>) -> Vec<Requirement<'a>> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for name in lists.flatten() {
+        *counts.entry(name).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .map(|(name, checks)| Requirement { name, checks })
+        .collect()
+}
+
+/// Marks the statements of `apply` in query lists
+const WRITES_NOTE: &str = "Writes: changes server settings";
+
+/// SQL comments and statements in Text, one section with a `sql` fence per
+/// statement otherwise
+fn write_query_list<W: std::io::Write>(
+    handle: &mut W,
+    format: ReportFormat,
+    queries: &[CatalogQuery],
+) -> Result<()> {
+    for query in queries {
+        if let ReportFormat::Text = format {
+            match query.when {
+                Some(when) => writeln!(handle, "-- {} ({when})", query.purpose),
+                None => writeln!(handle, "-- {}", query.purpose),
+            }
+            .context(OutputSnafu)?;
+            if query.writes {
+                writeln!(handle, "-- {WRITES_NOTE}").context(OutputSnafu)?;
+            }
+            writeln!(handle, "{};\n", query.sql).context(OutputSnafu)?;
+            continue;
+        }
+        writeln!(handle, "### {}\n", query.purpose).context(OutputSnafu)?;
+        if let Some(when) = query.when {
+            writeln!(handle, "When: {when}\n").context(OutputSnafu)?;
+        }
+        if query.writes {
+            writeln!(handle, "{WRITES_NOTE}\n").context(OutputSnafu)?;
+        }
+        writeln!(handle, "```sql\n{};\n```\n", query.sql).context(OutputSnafu)?;
+    }
+    Ok(())
+}
+
+pub struct ExplainReporter {
+    format: ReportFormat,
+}
+
+impl ExplainReporter {
+    pub fn new(format: ReportFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn report(&self, explanation: &ParameterExplanation) -> Result<()> {
+        let mut handle = ascii::stdout();
+        if let ReportFormat::Html = self.format {
+            let mut markdown = Vec::new();
+            self.write_explanation(&mut markdown, explanation)?;
+            return write_html(&mut handle, &explanation.name, &markdown);
+        }
+        self.write_explanation(&mut handle, explanation)
+    }
+
+    fn write_explanation<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        explanation: &ParameterExplanation,
+    ) -> Result<()> {
+        if let ReportFormat::Json = self.format {
+            let json = serde_json::to_string_pretty(explanation).map_err(|err| {
+                ReporterError::OutputError {
+                    source: std::io::Error::other(err),
+                }
+            })?;
+            writeln!(handle, "{json}").context(OutputSnafu)?;
+            return Ok(());
+        }
+
+        let markdown = matches!(
+            self.format,
+            ReportFormat::Markdown | ReportFormat::Html | ReportFormat::GithubComment
+        );
+        let label = |name: &str| {
+            if markdown {
+                format!("- **{name}**:")
+            } else {
+                format!("{name}:")
+            }
+        };
+
+        if markdown {
+            writeln!(handle, "# {}\n", explanation.name).context(OutputSnafu)?;
+        } else {
+            writeln!(handle, "{}", explanation.name).context(OutputSnafu)?;
+        }
+
+        match explanation.guide {
+            Some(guide) => {
+                writeln!(handle, "{} {}", label("What it does"), guide.purpose)
+                    .context(OutputSnafu)?;
+                writeln!(
+                    handle,
+                    "{} {}",
+                    label("Recommendation"),
+                    guide.recommendation
+                )
+                .context(OutputSnafu)?;
+                if !guide.related.is_empty() {
+                    writeln!(handle, "{} {}", label("Related"), guide.related.join(", "))
+                        .context(OutputSnafu)?;
+                }
+            }
+            None => {
+                writeln!(
+                    handle,
+                    "{} no stored guidance for this parameter",
+                    label("Note")
+                )
+                .context(OutputSnafu)?;
+            }
+        }
+
+        if let Some(mode) = explanation.apply_mode() {
+            writeln!(handle, "{} {}", label("Takes effect"), mode.as_str()).context(OutputSnafu)?;
+        }
+        if let Some(check_id) = explanation.check_id {
+            writeln!(handle, "{} {}", label("Check"), check_id).context(OutputSnafu)?;
+        }
+
+        if let Some(live) = &explanation.live {
+            let unit = live.unit.as_deref();
+            writeln!(
+                handle,
+                "{} {}",
+                label("Current value"),
+                format_setting(&live.current_value, unit)
+            )
+            .context(OutputSnafu)?;
+            writeln!(
+                handle,
+                "{} {}",
+                label("Default value"),
+                live.default_value
+                    .as_deref()
+                    .map(|value| format_setting(value, unit))
+                    .unwrap_or_else(|| "Unknown".to_string())
+            )
+            .context(OutputSnafu)?;
+            writeln!(handle, "{} {}", label("Context"), live.context).context(OutputSnafu)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct SimulationReporter {
+    format: ReportFormat,
+}
+
+impl SimulationReporter {
+    pub fn new(format: ReportFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn report(&self, simulation: &AutovacuumSimulation) -> Result<()> {
+        let mut handle = ascii::stdout();
+        if let ReportFormat::Html = self.format {
+            let mut markdown = Vec::new();
+            self.write_simulation(&mut markdown, simulation)?;
+            return write_html(&mut handle, "Autovacuum Simulation", &markdown);
+        }
+        self.write_simulation(&mut handle, simulation)
+    }
+
+    fn write_simulation<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        simulation: &AutovacuumSimulation,
+    ) -> Result<()> {
+        if let ReportFormat::Json = self.format {
+            return write_json(handle, simulation);
+        }
+        let markdown = matches!(
+            self.format,
+            ReportFormat::Markdown | ReportFormat::Html | ReportFormat::GithubComment
+        );
+        let (before, after) = (&simulation.before, &simulation.after);
+        let settings = [
+            (
+                "autovacuum_vacuum_cost_limit",
+                before.cost_limit.to_string(),
+                after.cost_limit.to_string(),
+            ),
+            (
+                "autovacuum_vacuum_cost_delay",
+                format!("{}ms", before.cost_delay_ms),
+                format!("{}ms", after.cost_delay_ms),
+            ),
+            (
+                "autovacuum_vacuum_scale_factor",
+                before.scale_factor.to_string(),
+                after.scale_factor.to_string(),
+            ),
+            (
+                "autovacuum_max_workers",
+                before.max_workers.to_string(),
+                after.max_workers.to_string(),
+            ),
+            (
+                "cost budget needed",
+                format!("{:.0}%", simulation.before_load * 100.0),
+                format!("{:.0}%", simulation.after_load * 100.0),
+            ),
+        ];
+
+        if markdown {
+            writeln!(handle, "# Autovacuum Simulation\n").context(OutputSnafu)?;
+        } else {
+            writeln!(handle, "Autovacuum Simulation").context(OutputSnafu)?;
+        }
+        writeln!(
+            handle,
+            "Dead tuples created over {} of statistics, replayed against the current and simulated settings.\n",
+            format_window(simulation.window.0 as u64)
+        )
+        .context(OutputSnafu)?;
+
+        if markdown {
+            writeln!(handle, "| Setting | Current | Simulated |").context(OutputSnafu)?;
+            writeln!(handle, "|---------|---------|-----------|").context(OutputSnafu)?;
+            for (name, current, simulated) in &settings {
+                writeln!(handle, "| {name} | {current} | {simulated} |").context(OutputSnafu)?;
+            }
+        } else {
+            for (name, current, simulated) in &settings {
+                writeln!(handle, "{name:<31} {current:>8} -> {simulated}").context(OutputSnafu)?;
+            }
+        }
+        writeln!(handle).context(OutputSnafu)?;
+
+        if simulation.tables.is_empty() {
+            writeln!(handle, "No table created dead tuples during the window.")
+                .context(OutputSnafu)?;
+            return Ok(());
+        }
+
+        if markdown {
+            writeln!(
+                handle,
+                "| Table | Size | Dead tuples/s | Vacuum due every | Pass takes | Peak dead tuples | Keeps up |"
+            )
+            .context(OutputSnafu)?;
+            writeln!(
+                handle,
+                "|-------|------|---------------|------------------|------------|------------------|----------|"
+            )
+            .context(OutputSnafu)?;
+        }
+        for table in &simulation.tables {
+            let fill = |cycle: &VacuumCycle| {
+                cycle
+                    .fill_time
+                    .map(|fill| format_window(fill.0 as u64))
+                    .unwrap_or_else(|| "never".to_string())
+            };
+            let duration = |cycle: &VacuumCycle| format_window(cycle.vacuum_duration.0 as u64);
+            let keeps_up = |cycle: &VacuumCycle| if cycle.keeps_up { "yes" } else { "no" };
+            let name = format!("{}.{}", table.schema, table.table_name);
+            let columns = [
+                format!("{:.0}", table.dead_tuples_per_sec),
+                format!("{} -> {}", fill(&table.before), fill(&table.after)),
+                format!("{} -> {}", duration(&table.before), duration(&table.after)),
+                format!(
+                    "{:.0} -> {:.0}",
+                    table.before.peak_dead_tuples, table.after.peak_dead_tuples
+                ),
+                format!("{} -> {}", keeps_up(&table.before), keeps_up(&table.after)),
+            ];
+            if markdown {
+                writeln!(
+                    handle,
+                    "| {name} | {} | {} |",
+                    table.table_size_bytes,
+                    columns.join(" | ")
+                )
+                .context(OutputSnafu)?;
+            } else {
+                writeln!(
+                    handle,
+                    "{name} ({}): {} dead tuples/s; due every {}; pass takes {}; peak {} dead tuples; keeps up {}",
+                    table.table_size_bytes,
+                    columns[0],
+                    columns[1],
+                    columns[2],
+                    columns[3],
+                    columns[4]
+                )
+                .context(OutputSnafu)?;
+            }
+        }
+
+        writeln!(
+            handle,
+            "\nPass times assume every heap page is read and one page is dirtied per dead tuple; index cleanup adds to them."
+        )
+        .context(OutputSnafu)?;
+        Ok(())
+    }
+}
+
+/// `postgreat bench --after`: a baseline next to the second capture
+pub struct BenchReporter {
+    format: ReportFormat,
+}
+
+impl BenchReporter {
+    pub fn new(format: ReportFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn report(&self, comparison: &BenchComparison) -> Result<()> {
+        let mut handle = ascii::stdout();
+        if let ReportFormat::Html = self.format {
+            let mut markdown = Vec::new();
+            self.write_comparison(&mut markdown, comparison)?;
+            return write_html(&mut handle, "Benchmark Comparison", &markdown);
+        }
+        self.write_comparison(&mut handle, comparison)
+    }
+
+    fn write_comparison<W: std::io::Write>(
+        &self,
+        handle: &mut W,
+        comparison: &BenchComparison,
+    ) -> Result<()> {
+        if let ReportFormat::Json = self.format {
+            return write_json(handle, comparison);
+        }
+        let markdown = matches!(
+            self.format,
+            ReportFormat::Markdown | ReportFormat::Html | ReportFormat::GithubComment
+        );
+        let (before, after) = (&comparison.before, &comparison.after);
+        let metrics = [
+            (
+                "Cache hit ratio",
+                format_optional_pct(before.cache_hit_ratio),
+                format_optional_pct(after.cache_hit_ratio),
+            ),
+            (
+                "Checkpoints per hour",
+                format_optional_f64(before.checkpoints_per_hour, ""),
+                format_optional_f64(after.checkpoints_per_hour, ""),
+            ),
+            (
+                "Requested checkpoints",
+                format_optional_pct(before.requested_checkpoint_share),
+                format_optional_pct(after.requested_checkpoint_share),
+            ),
+        ];
+
+        if markdown {
+            writeln!(handle, "# Benchmark Comparison\n").context(OutputSnafu)?;
+        } else {
+            writeln!(handle, "Benchmark Comparison").context(OutputSnafu)?;
+        }
+        writeln!(
+            handle,
+            "{}@{}: {} ({}s window) against {} ({}s window).\n",
+            after.database,
+            after.host,
+            after.captured_at,
+            after.window_secs,
+            before.captured_at,
+            before.window_secs
+        )
+        .context(OutputSnafu)?;
+
+        if markdown {
+            writeln!(handle, "| Metric | Before | After |").context(OutputSnafu)?;
+            writeln!(handle, "|--------|--------|-------|").context(OutputSnafu)?;
+            for (name, before, after) in &metrics {
+                writeln!(handle, "| {name} | {before} | {after} |").context(OutputSnafu)?;
+            }
+        } else {
+            for (name, before, after) in &metrics {
+                writeln!(handle, "{name:<22} {before:>8} -> {after}").context(OutputSnafu)?;
+            }
+        }
+        writeln!(handle).context(OutputSnafu)?;
+
+        if comparison.queries.is_empty() {
+            writeln!(
+                handle,
+                "The baseline has no query times; pg_stat_statements was unavailable or idle."
+            )
+            .context(OutputSnafu)?;
+            return Ok(());
+        }
+
+        if markdown {
+            writeln!(handle, "## Top Queries\n").context(OutputSnafu)?;
+            writeln!(handle, "| Query ID | Query | Before | After | Change |")
+                .context(OutputSnafu)?;
+            writeln!(handle, "|----------|-------|--------|-------|--------|")
+                .context(OutputSnafu)?;
+        } else {
+            writeln!(handle, "Top Queries (mean time):").context(OutputSnafu)?;
+        }
+        for query in &comparison.queries {
+            let after = format_optional_f64(query.after_mean_ms, "ms");
+            let change = query
+                .change()
+                .map(|change| format!("{:+.0}%", change * 100.0))
+                .unwrap_or_else(|| "not called".to_string());
+            if markdown {
+                writeln!(
+                    handle,
+                    "| {} | `{}` | {:.1}ms | {} | {} |",
+                    query.queryid,
+                    query.query.replace('|', "\\|").replace('\n', " "),
+                    query.before_mean_ms,
+                    after,
+                    change
+                )
+                .context(OutputSnafu)?;
+            } else {
+                writeln!(
+                    handle,
+                    "  {}: {:.1}ms -> {} ({})",
+                    query.queryid, query.before_mean_ms, after, change
+                )
+                .context(OutputSnafu)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Renders a pg_settings value with its unit; block-sized units read as multipliers
+pub(crate) fn format_setting(value: &str, unit: Option<&str>) -> String {
+    match unit {
+        Some(unit) if unit.starts_with(|c: char| c.is_ascii_digit()) => {
+            format!("{value} x {unit}")
+        }
+        Some(unit) => format!("{value}{unit}"),
+        None => value.to_string(),
+    }
+}
+
+fn format_list(items: &[&str]) -> String {
+    if items.is_empty() {
+        "-".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+fn format_slow_query_id(query: &SlowQueryInfo) -> String {
+    match query.merged_queryids.len() {
+        0 => query.queryid.to_string(),
+        variants => format!("{} (+{variants} variants)", query.queryid),
+    }
+}
+
+fn format_roles(roles: &[String]) -> String {
+    if roles.is_empty() {
+        "-".to_string()
+    } else {
+        roles.join(", ")
+    }
+}
+
+fn join_queryids(queryids: &[i64]) -> String {
+    queryids
+        .iter()
+        .map(i64::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_application_pattern_kind(kind: ApplicationPatternKind) -> &'static str {
+    match kind {
+        ApplicationPatternKind::OffsetPagination => "OFFSET pagination",
+        ApplicationPatternKind::WalHeavyWrites => "WAL-heavy writes",
+        ApplicationPatternKind::AmbiguousSchema => "Ambiguous schema",
+        ApplicationPatternKind::CorrelatedColumns => "Correlated columns",
+    }
+}
+
+/// Wraps a Markdown report in a standalone HTML page.
+///
+/// Only the subset the reporters emit is understood: headings, tables, bullet
+/// lists, block quotes, fenced code, `**bold**`, `` `code` `` and badge images.
+/// Raw `<details>`/`<summary>` lines pass through unchanged.
+/// Severity marker used in `github-comment` output
+fn level_emoji(level: SuggestionLevel) -> &'static str {
+    match level {
+        SuggestionLevel::Critical => ":red_circle:",
+        SuggestionLevel::Important => ":orange_circle:",
+        SuggestionLevel::Recommended => ":yellow_circle:",
+        SuggestionLevel::Info => ":blue_circle:",
+    }
+}
+
+/// JSON report: the results with their health score, which is derived rather
+/// than stored so it follows `--rules` overrides
+#[derive(serde::Serialize)]
+struct ScoredResults<'a> {
+    health: HealthScore,
+    #[serde(flatten)]
+    results: &'a AnalysisResults,
+}
+
+fn write_health_markdown<W: std::io::Write>(handle: &mut W, health: &HealthScore) -> Result<()> {
+    writeln!(handle, "## Health Score\n").context(OutputSnafu)?;
+    writeln!(handle, "**{}**\n", health).context(OutputSnafu)?;
+    if health.categories.is_empty() {
+        return Ok(());
+    }
+    writeln!(handle, "| Category | Score | Suggestions |").context(OutputSnafu)?;
+    writeln!(handle, "|----------|-------|-------------|").context(OutputSnafu)?;
+    for category in &health.categories {
+        writeln!(
+            handle,
+            "| {} | {} | {} |",
+            category.category.as_str(),
+            category.score,
+            category.suggestions
+        )
+        .context(OutputSnafu)?;
+    }
+    writeln!(handle).context(OutputSnafu)?;
+    Ok(())
+}
+
+fn write_health_text<W: std::io::Write>(handle: &mut W, health: &HealthScore) -> Result<()> {
+    writeln!(handle, "Health Score: {}", health).context(OutputSnafu)?;
+    for category in &health.categories {
+        writeln!(
+            handle,
+            "  {:<32} {:>3}",
+            category.category.as_str(),
+            category.score
+        )
+        .context(OutputSnafu)?;
+    }
+    writeln!(handle).context(OutputSnafu)?;
+    Ok(())
+}
+
+/// The stored lifecycle of a suggestion, when the results came from the scheduler
+fn lifecycle_of<'a>(
+    results: &'a AnalysisResults,
+    suggestion: &ConfigSuggestion,
+) -> Option<&'a FindingLifecycle> {
+    results
+        .finding_lifecycle
+        .iter()
+        .find(|finding| finding.fingerprint == suggestion.fingerprint)
+}
+
+fn write_json<W: std::io::Write, T: serde::Serialize>(handle: &mut W, value: &T) -> Result<()> {
+    serde_json::to_writer_pretty(&mut *handle, value)
+        .map_err(std::io::Error::other)
+        .context(OutputSnafu)?;
+    writeln!(handle).context(OutputSnafu)
+}
+
+fn write_html<W: std::io::Write>(handle: &mut W, title: &str, markdown: &[u8]) -> Result<()> {
+    writeln!(
+        handle,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>",
+        escape_html(title)
+    )
+    .context(OutputSnafu)?;
+    handle
+        .write_all(markdown_to_html(&String::from_utf8_lossy(markdown)).as_bytes())
+        .context(OutputSnafu)?;
+    writeln!(handle, "</body>\n</html>").context(OutputSnafu)?;
+    Ok(())
+}
+
+const HTML_STYLE: &str = "body{font-family:sans-serif;margin:2em;max-width:90em}\
+table{border-collapse:collapse;margin-bottom:1em}\
+th,td{border:1px solid #ccc;padding:4px 8px;text-align:left;vertical-align:top}\
+th{background:#f4f4f4}\
+code,pre{background:#f6f8fa}pre{padding:8px;overflow-x:auto}\
+.badge{font-size:75%;font-weight:bold;padding:1px 6px;border-radius:3px;background:#ddd}";
+
+#[derive(PartialEq)]
+enum HtmlBlock {
+    None,
+    Paragraph,
+    List,
+    Quote,
+    Table,
+}
+
+fn markdown_to_html(markdown: &str) -> String {
+    let mut html = String::new();
+    let mut block = HtmlBlock::None;
+    let mut lines = markdown.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_end();
+        if trimmed.starts_with("```") {
+            close_html_block(&mut html, &mut block);
+            html.push_str("<pre><code>");
+            for code in lines.by_ref() {
+                if code.trim_end() == "```" {
+                    break;
+                }
+                html.push_str(&escape_html(code));
+                html.push('\n');
+            }
+            html.push_str("</code></pre>\n");
+            continue;
+        }
+
+        let heading = trimmed.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&heading) && trimmed[heading..].starts_with(' ') {
+            close_html_block(&mut html, &mut block);
+            html.push_str(&format!(
+                "<h{heading}>{}</h{heading}>\n",
+                inline_html(trimmed[heading..].trim())
+            ));
+        } else if trimmed.starts_with('|') {
+            let cells = table_cells(trimmed);
+            let is_separator = lines
+                .peek()
+                .is_some_and(|next| next.starts_with("|-") || next.starts_with("| -"));
+            if block != HtmlBlock::Table {
+                close_html_block(&mut html, &mut block);
+                html.push_str("<table>\n");
+                block = HtmlBlock::Table;
+            }
+            let tag = if is_separator { "th" } else { "td" };
+            html.push_str("<tr>");
+            for cell in cells {
+                html.push_str(&format!("<{tag}>{}</{tag}>", inline_html(&cell)));
+            }
+            html.push_str("</tr>\n");
+            if is_separator {
+                lines.next();
+            }
+        } else if let Some(item) = trimmed.strip_prefix("- ") {
+            if block != HtmlBlock::List {
+                close_html_block(&mut html, &mut block);
+                html.push_str("<ul>\n");
+                block = HtmlBlock::List;
+            }
+            html.push_str(&format!("<li>{}</li>\n", inline_html(item)));
+        } else if let Some(quote) = trimmed.strip_prefix("> ") {
+            if block != HtmlBlock::Quote {
+                close_html_block(&mut html, &mut block);
+                html.push_str("<blockquote>\n");
+                block = HtmlBlock::Quote;
+            }
+            html.push_str(&format!("<p>{}</p>\n", inline_html(quote)));
+        } else if trimmed == "---" {
+            close_html_block(&mut html, &mut block);
+            html.push_str("<hr>\n");
+        } else if trimmed.starts_with("<details>")
+            || trimmed.starts_with("</details>")
+            || trimmed.starts_with("<summary>")
+        {
+            close_html_block(&mut html, &mut block);
+            html.push_str(trimmed);
+            html.push('\n');
+        } else if trimmed.is_empty() {
+            close_html_block(&mut html, &mut block);
+        } else {
+            if block != HtmlBlock::Paragraph {
+                close_html_block(&mut html, &mut block);
+                html.push_str("<p>");
+                block = HtmlBlock::Paragraph;
+            } else {
+                html.push_str("<br>\n");
+            }
+            html.push_str(&inline_html(trimmed));
+        }
+    }
+    close_html_block(&mut html, &mut block);
+
+    html
+}
+
+fn close_html_block(html: &mut String, block: &mut HtmlBlock) {
+    html.push_str(match block {
+        HtmlBlock::None => "",
+        HtmlBlock::Paragraph => "</p>\n",
+        HtmlBlock::List => "</ul>\n",
+        HtmlBlock::Quote => "</blockquote>\n",
+        HtmlBlock::Table => "</table>\n",
+    });
+    *block = HtmlBlock::None;
+}
+
+/// Splits `| a | b \| c |` into cells, honouring the `\|` escape used for query text
+fn table_cells(row: &str) -> Vec<String> {
+    let inner = row.trim().trim_start_matches('|');
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '|' => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+/// Escapes the text and renders `**bold**`, `` `code` `` and `![LABEL](url)` badges
+fn inline_html(text: &str) -> String {
+    let mut html = String::new();
+    let mut rest = text;
+    let mut bold = false;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('`') {
+            if let Some(end) = after.find('`') {
+                html.push_str(&format!("<code>{}</code>", escape_html(&after[..end])));
+                rest = &after[end + 1..];
+                continue;
+            }
+        }
+        if let Some(after) = rest.strip_prefix("**") {
+            html.push_str(if bold { "</strong>" } else { "<strong>" });
+            bold = !bold;
+            rest = after;
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix("![") {
+            if let Some((label, tail)) = after.split_once("](") {
+                if let Some(end) = tail.find(')') {
+                    html.push_str(&format!(
+                        "<span class=\"badge\">{}</span>",
+                        escape_html(label)
+                    ));
+                    rest = &tail[end + 1..];
+                    continue;
+                }
+            }
+        }
+        let next = rest
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| matches!(c, '`' | '*' | '!'))
+            .map(|(index, _)| index)
+            .unwrap_or(rest.len());
+        html.push_str(&escape_html(&rest[..next]));
+        rest = &rest[next..];
+    }
+    if bold {
+        html.push_str("</strong>");
+    }
+
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn format_candidate_columns(candidate: &crate::models::QueryIndexCandidate) -> String {
+    match &candidate.access_method {
+        Some(method) => format!("USING {method} ({})", candidate.columns.join(", ")),
+        None => candidate.columns.join(", "),
+    }
+}
+
+fn format_candidate_evidence(evidence: &crate::models::QueryIndexEvidence) -> String {
+    let mut parts = Vec::new();
+    if !evidence.equality_filters.is_empty() {
+        parts.push(format!("WHERE = {}", evidence.equality_filters.join(", ")));
+    }
+    if !evidence.non_equality_filters.is_empty() {
+        parts.push(format!(
+            "WHERE range {}",
+            evidence.non_equality_filters.join(", ")
+        ));
+    }
+    if !evidence.equality_joins.is_empty() {
+        parts.push(format!("JOIN = {}", evidence.equality_joins.join(", ")));
+    }
+    if !evidence.order_by.is_empty() {
+        parts.push(format!("ORDER BY {}", evidence.order_by.join(", ")));
+    }
+    if parts.is_empty() {
+        "none".to_string()
+    } else {
+        parts.join("; ")
+    }
+}
+
+fn format_notes(notes: &[String]) -> String {
+    if notes.is_empty() {
+        "none".to_string()
+    } else {
+        notes.join("; ")
+    }
+}
+
+fn format_optional_pct(value: Option<f64>) -> String {
     value
         .map(|ratio| format!("{:.1}%", ratio * 100.0))
         .unwrap_or_else(|| "n/a".to_string())
@@ -1178,36 +3508,529 @@ fn format_optional_f64(value: Option<f64>, unit: &str) -> String {
         .unwrap_or_else(|| "n/a".to_string())
 }
 
-fn format_optional_i64_per_call(value: Option<f64>, unit: &str) -> String {
-    value
-        .map(|value| format!("{value:.1}{unit}"))
-        .unwrap_or_else(|| "n/a".to_string())
+fn format_optional_i64_per_call(value: Option<f64>, unit: &str) -> String {
+    value
+        .map(|value| format!("{value:.1}{unit}"))
+        .unwrap_or_else(|| "n/a".to_string())
+}
+
+fn format_slow_query_kind(kind: SlowQueryKind) -> &'static str {
+    match kind {
+        SlowQueryKind::TotalTime => "Slow Queries by Total Time",
+        SlowQueryKind::MeanTime => "Slow Queries by Mean Time",
+        SlowQueryKind::SharedBlksRead => "Slow Queries by Shared Blocks Read",
+        SlowQueryKind::TempBlksWritten => "Slow Queries by Temp Blocks Written",
+        SlowQueryKind::WalBytes => "Statements by WAL Generated",
+        SlowQueryKind::IoBound => "I/O-Bound Statements",
+        SlowQueryKind::CpuBound => "CPU-Bound Statements",
+    }
+}
+
+/// Group description matching where the statements were read from
+fn describe_group(results: &WorkloadResults, kind: SlowQueryKind) -> &'static str {
+    if results.log_findings.is_none() {
+        return describe_slow_query_kind(kind);
+    }
+    match kind {
+        SlowQueryKind::TotalTime => {
+            "Shows which logged statements took the most time in total across the log files; only calls slower than log_min_duration_statement are counted."
+        }
+        SlowQueryKind::MeanTime => {
+            "Shows which logged statements are slowest per call; every call counted was already slower than log_min_duration_statement."
+        }
+        SlowQueryKind::TempBlksWritten => {
+            "Highlights statements whose temporary files (log_temp_files) were largest, in 8kB blocks, pointing at sorts and hashes that outgrew work_mem."
+        }
+        kind => describe_slow_query_kind(kind),
+    }
+}
+
+fn write_log_findings_markdown<W: std::io::Write>(
+    handle: &mut W,
+    findings: &LogFindings,
+) -> Result<()> {
+    writeln!(handle, "## Log Files\n").context(OutputSnafu)?;
+    writeln!(handle, "- **Files**: {}", findings.files.join(", ")).context(OutputSnafu)?;
+    writeln!(
+        handle,
+        "- **Entries**: {} ({} with durations)",
+        findings.entries, findings.duration_entries
+    )
+    .context(OutputSnafu)?;
+    writeln!(
+        handle,
+        "- **Temporary files**: {} ({})\n",
+        findings.temp_files, findings.temp_bytes
+    )
+    .context(OutputSnafu)?;
+
+    if !findings.lock_waits.is_empty() {
+        writeln!(handle, "## Lock Waits\n").context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "Statements that waited longer than deadlock_timeout (log_lock_waits).\n"
+        )
+        .context(OutputSnafu)?;
+        writeln!(handle, "| Lock | Waits | Longest ms | Query |").context(OutputSnafu)?;
+        writeln!(handle, "|------|-------|------------|-------|").context(OutputSnafu)?;
+        for wait in &findings.lock_waits {
+            writeln!(
+                handle,
+                "| {} | {} | {:.2} | {} |",
+                wait.lock_mode,
+                wait.waits,
+                wait.max_wait_ms,
+                wait.query_text.replace('|', "\\|").replace('\n', " ")
+            )
+            .context(OutputSnafu)?;
+        }
+        writeln!(handle).context(OutputSnafu)?;
+    }
+
+    if !findings.plans.is_empty() {
+        writeln!(handle, "## Logged Plans\n").context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "auto_explain plans per statement; rows are estimates unless auto_explain.log_analyze is on.\n"
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "| Query ID | Plans | Total ms | Max ms | Top nodes | Regressions | Query |"
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "|----------|-------|----------|--------|-----------|-------------|-------|"
+        )
+        .context(OutputSnafu)?;
+        for plan in &findings.plans {
+            writeln!(
+                handle,
+                "| {} | {} | {:.2} | {:.2} | {} | {} | {} |",
+                plan.queryid,
+                plan.plans,
+                plan.total_time_ms,
+                plan.max_time_ms,
+                format_plan_nodes(plan),
+                plan.regressions
+                    .iter()
+                    .map(|regression| format!(
+                        "{} ({}/{} plans): {}",
+                        format_plan_regression_kind(regression.kind),
+                        regression.plans,
+                        plan.plans,
+                        regression.detail
+                    ))
+                    .collect::<Vec<_>>()
+                    .join("; ")
+                    .replace('|', "\\|"),
+                plan.query_text.replace('|', "\\|")
+            )
+            .context(OutputSnafu)?;
+        }
+        writeln!(handle).context(OutputSnafu)?;
+    }
+
+    if !findings.autovacuum_runs.is_empty() {
+        writeln!(handle, "## Autovacuum Runs\n").context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "| Table | Vacuums | Analyzes | Total s | Longest s |"
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "|-------|---------|----------|---------|-----------|"
+        )
+        .context(OutputSnafu)?;
+        for run in &findings.autovacuum_runs {
+            writeln!(
+                handle,
+                "| {} | {} | {} | {:.2} | {:.2} |",
+                run.table, run.vacuums, run.analyzes, run.total_elapsed_secs, run.max_elapsed_secs
+            )
+            .context(OutputSnafu)?;
+        }
+        writeln!(handle).context(OutputSnafu)?;
+    }
+
+    Ok(())
+}
+
+fn write_log_findings_text<W: std::io::Write>(
+    handle: &mut W,
+    findings: &LogFindings,
+) -> Result<()> {
+    writeln!(
+        handle,
+        "Log Files: {} entries ({} with durations), {} temporary files ({})",
+        findings.entries, findings.duration_entries, findings.temp_files, findings.temp_bytes
+    )
+    .context(OutputSnafu)?;
+    for file in &findings.files {
+        writeln!(handle, "  - {file}").context(OutputSnafu)?;
+    }
+    writeln!(handle).context(OutputSnafu)?;
+
+    if !findings.lock_waits.is_empty() {
+        writeln!(handle, "Lock Waits:").context(OutputSnafu)?;
+        for wait in &findings.lock_waits {
+            writeln!(
+                handle,
+                "  - {} x{} (longest {:.2}ms): {}",
+                wait.lock_mode,
+                wait.waits,
+                wait.max_wait_ms,
+                wait.query_text.replace('\n', " ")
+            )
+            .context(OutputSnafu)?;
+        }
+        writeln!(handle).context(OutputSnafu)?;
+    }
+
+    if !findings.plans.is_empty() {
+        writeln!(handle, "Logged Plans:").context(OutputSnafu)?;
+        for plan in &findings.plans {
+            writeln!(
+                handle,
+                "  - queryid {} ({} plans, {:.2}ms total, {:.2}ms max): {}",
+                plan.queryid, plan.plans, plan.total_time_ms, plan.max_time_ms, plan.query_text
+            )
+            .context(OutputSnafu)?;
+            writeln!(handle, "    nodes: {}", format_plan_nodes(plan)).context(OutputSnafu)?;
+            for regression in &plan.regressions {
+                writeln!(
+                    handle,
+                    "    [{}] {}/{} plans: {}",
+                    format_plan_regression_kind(regression.kind),
+                    regression.plans,
+                    plan.plans,
+                    regression.detail
+                )
+                .context(OutputSnafu)?;
+                writeln!(handle, "      suggestion: {}", regression.suggestion)
+                    .context(OutputSnafu)?;
+            }
+        }
+        writeln!(handle).context(OutputSnafu)?;
+    }
+
+    if !findings.autovacuum_runs.is_empty() {
+        writeln!(handle, "Autovacuum Runs:").context(OutputSnafu)?;
+        for run in &findings.autovacuum_runs {
+            writeln!(
+                handle,
+                "  - {}: {} vacuums, {} analyzes, {:.2}s total, {:.2}s longest",
+                run.table, run.vacuums, run.analyzes, run.total_elapsed_secs, run.max_elapsed_secs
+            )
+            .context(OutputSnafu)?;
+        }
+        writeln!(handle).context(OutputSnafu)?;
+    }
+
+    Ok(())
+}
+
+/// `Seq Scan on orders x2 (50000 rows), ...`
+fn format_plan_nodes(plan: &LoggedPlanFinding) -> String {
+    let estimated = if plan.analyzed { "" } else { " est." };
+    plan.nodes
+        .iter()
+        .map(|node| {
+            format!(
+                "{} x{} ({:.0} rows{estimated})",
+                node.node, node.count, node.rows
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_plan_regression_kind(kind: PlanRegressionKind) -> &'static str {
+    match kind {
+        PlanRegressionKind::NestedLoopLargeOuter => "Nested loop over many rows",
+        PlanRegressionKind::SortSpill => "Sort spill",
+        PlanRegressionKind::HashSpill => "Hash spill",
+        PlanRegressionKind::WorkMemExposure => "work_mem exposure",
+    }
+}
+
+fn describe_slow_query_kind(kind: SlowQueryKind) -> &'static str {
+    match kind {
+        SlowQueryKind::TotalTime => {
+            "Shows which statements consume the most cumulative execution time since pg_stat_statements was last reset; this is not a time-windowed ranking."
+        }
+        SlowQueryKind::MeanTime => {
+            "Shows which statements are slow per execution within the cumulative pg_stat_statements dataset, useful for reducing end-user latency and fixing expensive query plans."
+        }
+        SlowQueryKind::SharedBlksRead => {
+            "Highlights statements that perform the most disk-backed reads in the cumulative pg_stat_statements dataset, useful for spotting I/O-heavy access patterns and missing indexes."
+        }
+        SlowQueryKind::TempBlksWritten => {
+            "Highlights statements that spill the most temporary blocks in the cumulative pg_stat_statements dataset, useful for identifying costly sort/hash operations and memory pressure."
+        }
+        SlowQueryKind::WalBytes => {
+            "Ranks statements by WAL bytes generated in the cumulative pg_stat_statements dataset, useful for finding the writers behind checkpoint pressure, replication lag and WAL archive volume."
+        }
+        SlowQueryKind::IoBound => {
+            "Statements spending at least half their execution time waiting on block reads and writes (track_io_timing). Many blocks per returned row points to a missing index; a low cache hit ratio across the workload points to more memory; few but slow reads point to faster storage."
+        }
+        SlowQueryKind::CpuBound => {
+            "Statements spending at most a fifth of their execution time on block I/O (track_io_timing), ranked by the remaining time. More cache or faster storage will not help these; look at plans, row counts, functions and sorts instead."
+        }
+    }
+}
+
+/// Schemas listed in the text and markdown reports; JSON carries all of them
+const SCHEMA_USAGE_ROWS: usize = 20;
+
+fn write_schema_usage_markdown<W: std::io::Write>(
+    handle: &mut W,
+    usage: &[SchemaUsage],
+) -> Result<()> {
+    writeln!(handle, "## Usage by Schema\n").context(OutputSnafu)?;
+    writeln!(
+        handle,
+        "Ranked by the mean share of storage, rows written and rows read since the stats reset. \
+         **Noisy** schemas use at least three times an even split.\n"
+    )
+    .context(OutputSnafu)?;
+    writeln!(
+        handle,
+        "| # | Schema | Tables | Table Size | Index Size | Dead Tuples | Rows Written | Rows Read | Share | Findings |"
+    )
+    .context(OutputSnafu)?;
+    writeln!(
+        handle,
+        "|---|--------|--------|------------|------------|-------------|--------------|-----------|-------|----------|"
+    )
+    .context(OutputSnafu)?;
+    for (rank, schema) in usage.iter().take(SCHEMA_USAGE_ROWS).enumerate() {
+        let name = if schema.noisy {
+            format!("**{}** (noisy)", schema.schema)
+        } else {
+            schema.schema.clone()
+        };
+        writeln!(
+            handle,
+            "| {} | {} | {} | {} | {} | {} | {} | {} | {:.1}% | {} |",
+            rank + 1,
+            name,
+            schema.tables,
+            schema.table_bytes,
+            schema.index_bytes,
+            schema.dead_tuples,
+            schema.rows_written,
+            schema.rows_read,
+            schema.share * 100.0,
+            schema.findings
+        )
+        .context(OutputSnafu)?;
+    }
+    if usage.len() > SCHEMA_USAGE_ROWS {
+        writeln!(
+            handle,
+            "\n{} more schemas in the JSON output.",
+            usage.len() - SCHEMA_USAGE_ROWS
+        )
+        .context(OutputSnafu)?;
+    }
+    writeln!(handle).context(OutputSnafu)?;
+
+    Ok(())
+}
+
+fn write_schema_usage_text<W: std::io::Write>(handle: &mut W, usage: &[SchemaUsage]) -> Result<()> {
+    writeln!(handle, "Usage by Schema:").context(OutputSnafu)?;
+    for (rank, schema) in usage.iter().take(SCHEMA_USAGE_ROWS).enumerate() {
+        writeln!(
+            handle,
+            "  {}. {}{}: {:.1}% share, {} tables, {} + {} indexes, {} rows written, {} rows read, {} findings",
+            rank + 1,
+            schema.schema,
+            if schema.noisy { " (noisy)" } else { "" },
+            schema.share * 100.0,
+            schema.tables,
+            schema.table_bytes,
+            schema.index_bytes,
+            schema.rows_written,
+            schema.rows_read,
+            schema.findings
+        )
+        .context(OutputSnafu)?;
+    }
+    if usage.len() > SCHEMA_USAGE_ROWS {
+        writeln!(
+            handle,
+            "  ... {} more schemas in the JSON output",
+            usage.len() - SCHEMA_USAGE_ROWS
+        )
+        .context(OutputSnafu)?;
+    }
+    writeln!(handle).context(OutputSnafu)?;
+
+    Ok(())
+}
+
+fn write_table_index_markdown<W: std::io::Write>(
+    handle: &mut W,
+    health: &TableIndexHealth,
+) -> Result<()> {
+    writeln!(handle, "## Table & Index Health\n").context(OutputSnafu)?;
+
+    if !health.bloat_info.is_empty() {
+        writeln!(handle, "### Table Bloat Watchlist\n").context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "| Table | Dead % | Dead Tuples | Live Tuples | Last Autovacuum | Size |"
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "|-------|--------|-------------|-------------|-----------------|------|"
+        )
+        .context(OutputSnafu)?;
+
+        for table in &health.bloat_info {
+            writeln!(
+                handle,
+                "| {}.{} | {:.1}% | {} | {} | {} | {} |",
+                table.schema,
+                table.table_name,
+                table.dead_tup_ratio * 100.0,
+                table.dead_tuples,
+                table.live_tuples,
+                table.last_autovacuum.as_deref().unwrap_or("never"),
+                table.table_size_pretty
+            )
+            .context(OutputSnafu)?;
+        }
+        writeln!(handle).context(OutputSnafu)?;
+        write_not_shown_markdown(handle, health.not_shown("bloat"))?;
+    }
+
+    if !health.seq_scan_info.is_empty() {
+        writeln!(handle, "### Sequential Scan Hotspots\n").context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "| Table | Seq Scans | Idx Scans | Live Tuples | Size |"
+        )
+        .context(OutputSnafu)?;
+        writeln!(
+            handle,
+            "|-------|-----------|-----------|-------------|------|"
+        )
+        .context(OutputSnafu)?;
+
+        for table in &health.seq_scan_info {
+            writeln!(
+                handle,
+                "| {}.{} | {} | {} | {} | {} |",
+                table.schema,
+                table.table_name,
+                table.seq_scan,
+                table.idx_scan,
+                table.live_tuples,
+                table.table_size_pretty
+            )
+            .context(OutputSnafu)?;
+        }
+        writeln!(handle).context(OutputSnafu)?;
+        write_not_shown_markdown(handle, health.not_shown("seq_scan"))?;
+    }
+
+    if !health.index_usage_info.is_empty() {
+        writeln!(handle, "### Index Findings\n").context(OutputSnafu)?;
+        for issue in [
+            IndexIssueKind::Unused,
+            IndexIssueKind::LowSelectivity,
+            IndexIssueKind::FailedIndexOnly,
+            IndexIssueKind::MissingPartialIndex,
+            IndexIssueKind::BrinCandidate,
+            IndexIssueKind::MissingSpatialIndex,
+        ] {
+            let group: Vec<_> = health
+                .index_usage_info
+                .iter()
+                .filter(|idx| idx.issue == issue)
+                .collect();
+            if group.is_empty() {
+                continue;
+            }
+
+            writeln!(handle, "#### {}\n", format_issue_name(&issue)).context(OutputSnafu)?;
+            writeln!(handle, "| Index | Table | Scans | Size | Notes |").context(OutputSnafu)?;
+            writeln!(handle, "|-------|-------|-------|------|-------|").context(OutputSnafu)?;
+
+            for idx in group {
+                let notes = match idx.issue {
+                    IndexIssueKind::Unused => "never scanned".to_string(),
+                    IndexIssueKind::LowSelectivity => {
+                        let percentage = selectivity_ratio(idx) * 100.0;
+                        format!("~{:.1}% of table per scan", percentage.min(100.0))
+                    }
+                    IndexIssueKind::FailedIndexOnly => {
+                        format!("{:.0}% heap fetch ratio", idx.heap_fetch_ratio * 100.0)
+                    }
+                    IndexIssueKind::MissingPartialIndex => missing_partial_index_note(idx),
+                    IndexIssueKind::BrinCandidate => {
+                        "BRIN candidate for time-series/append-only".to_string()
+                    }
+                    IndexIssueKind::MissingSpatialIndex => format!(
+                        "~{} rows, no GiST/SP-GiST index on {}",
+                        idx.table_live_tup.unwrap_or(0),
+                        idx.key_columns.join(", ")
+                    ),
+                };
+
+                writeln!(
+                    handle,
+                    "| {}.{} | {}.{} | {} | {} | {} |",
+                    idx.schema,
+                    idx.index_name,
+                    idx.schema,
+                    idx.table_name,
+                    idx.scans,
+                    idx.index_size_pretty,
+                    notes
+                )
+                .context(OutputSnafu)?;
+            }
+            writeln!(handle).context(OutputSnafu)?;
+            write_not_shown_markdown(handle, health.not_shown(issue.as_str()))?;
+        }
+    }
+
+    Ok(())
 }
 
-fn format_slow_query_kind(kind: SlowQueryKind) -> &'static str {
-    match kind {
-        SlowQueryKind::TotalTime => "Slow Queries by Total Time",
-        SlowQueryKind::MeanTime => "Slow Queries by Mean Time",
-        SlowQueryKind::SharedBlksRead => "Slow Queries by Shared Blocks Read",
-        SlowQueryKind::TempBlksWritten => "Slow Queries by Temp Blocks Written",
+/// Closes a findings list cut short by `--max-findings-per-kind`
+fn write_not_shown_text<W: std::io::Write>(
+    handle: &mut W,
+    count: usize,
+    label: Option<&str>,
+) -> Result<()> {
+    if count == 0 {
+        return Ok(());
     }
+    let label = label.map(|label| format!(" {label}")).unwrap_or_default();
+    writeln!(
+        handle,
+        "  ... {count} more{label} not shown (raise --max-findings-per-kind to list them)"
+    )
+    .context(OutputSnafu)
 }
 
-fn describe_slow_query_kind(kind: SlowQueryKind) -> &'static str {
-    match kind {
-        SlowQueryKind::TotalTime => {
-            "Shows which statements consume the most cumulative execution time since pg_stat_statements was last reset; this is not a time-windowed ranking."
-        }
-        SlowQueryKind::MeanTime => {
-            "Shows which statements are slow per execution within the cumulative pg_stat_statements dataset, useful for reducing end-user latency and fixing expensive query plans."
-        }
-        SlowQueryKind::SharedBlksRead => {
-            "Highlights statements that perform the most disk-backed reads in the cumulative pg_stat_statements dataset, useful for spotting I/O-heavy access patterns and missing indexes."
-        }
-        SlowQueryKind::TempBlksWritten => {
-            "Highlights statements that spill the most temporary blocks in the cumulative pg_stat_statements dataset, useful for identifying costly sort/hash operations and memory pressure."
-        }
+fn write_not_shown_markdown<W: std::io::Write>(handle: &mut W, count: usize) -> Result<()> {
+    if count == 0 {
+        return Ok(());
     }
+    writeln!(
+        handle,
+        "_{count} more not shown; raise `--max-findings-per-kind` to list them._\n"
+    )
+    .context(OutputSnafu)
 }
 
 fn format_issue_name(issue: &IndexIssueKind) -> &'static str {
@@ -1217,6 +4040,17 @@ fn format_issue_name(issue: &IndexIssueKind) -> &'static str {
         IndexIssueKind::FailedIndexOnly => "Failed Index-Only",
         IndexIssueKind::MissingPartialIndex => "Missing Partial Index",
         IndexIssueKind::BrinCandidate => "BRIN Candidate",
+        IndexIssueKind::MissingSpatialIndex => "Missing Spatial Index",
+    }
+}
+
+fn missing_partial_index_note(idx: &crate::models::IndexUsageInfo) -> String {
+    match (&idx.partial_predicate, idx.estimated_size_bytes) {
+        (Some(predicate), Some(estimate)) => format!(
+            "WHERE {predicate}: ~{estimate} vs {} today",
+            idx.index_size_pretty
+        ),
+        _ => "missing soft-delete partial index".to_string(),
     }
 }
 
@@ -1233,9 +4067,12 @@ fn selectivity_ratio(index: &crate::models::IndexUsageInfo) -> f64 {
 mod tests {
     use super::*;
     use crate::models::{
-        QueryIndexCandidate, QueryIndexEvidence, SlowQueryGroup, SlowQueryInfo,
-        WorkloadCoverageStats, WorkloadFindingConfidence, WorkloadMetadata,
+        ApplicationPatternFinding, AutovacuumLogFinding, LockWaitFinding, PlanNodeStat,
+        PlanRegression, QueryIndexCandidate, QueryIndexEvidence, RoleWorkloadSummary,
+        SlowQueryGroup, SlowQueryInfo, TableSeqScanInfo, WorkloadCoverageStats,
+        WorkloadFindingConfidence, WorkloadMetadata,
     };
+    use rstest::rstest;
 
     fn sample_workload_results() -> WorkloadResults {
         WorkloadResults {
@@ -1274,13 +4111,21 @@ mod tests {
                     temp_blks_written_per_call: Some(2.0),
                     wal_bytes: Some(2_048),
                     wal_bytes_per_call: Some(204.8),
+                    wal_fpi: Some(3),
+                    io_time_ms: Some(400.0),
+                    io_time_share: Some(0.8),
                     query_text: "select * from orders where customer_id = $1".into(),
+                    merged_queryids: vec![7, 9],
+                    roles: vec!["app_rw".into()],
+                    database: Some("shop".into()),
                 }],
             }],
             query_index_candidates: vec![QueryIndexCandidate {
                 schema: "public".into(),
                 table: "orders".into(),
                 columns: vec!["customer_id".into(), "created_at".into()],
+                access_method: None,
+                ddl: "CREATE INDEX CONCURRENTLY ON public.orders (customer_id, created_at);".into(),
                 reason: "heuristic from slow query: WHERE customer_id; ORDER BY created_at".into(),
                 confidence: WorkloadFindingConfidence::Low,
                 evidence: QueryIndexEvidence {
@@ -1305,6 +4150,22 @@ mod tests {
                 "Query text visibility appears limited for the current role; grant pg_read_all_stats to avoid incomplete or anonymized workload findings.".into(),
                 "Only 7 of 10 workload statements were parsed into index evidence; index candidate coverage is partial.".into(),
             ],
+            role_summaries: vec![RoleWorkloadSummary {
+                role: "app_rw".into(),
+                statements: 12,
+                calls: 400,
+                total_time_ms: 800.0,
+                total_time_pct: 80.0,
+            }],
+            application_patterns: vec![ApplicationPatternFinding {
+                kind: ApplicationPatternKind::OffsetPagination,
+                queryid: 43,
+                calls: 200,
+                mean_time_ms: 12.5,
+                evidence: "OFFSET 5000 reads and discards 5000 rows on every call".into(),
+                suggestion: "Use keyset pagination".into(),
+                query_text: "select * from orders order by id limit $1 offset 5000".into(),
+            }],
             ..WorkloadResults::default()
         }
     }
@@ -1329,6 +4190,10 @@ mod tests {
         );
         assert!(rendered.contains("| public.orders | customer_id, created_at | low |"));
         assert!(rendered.contains("table is also a sequential scan hotspot"));
+        assert!(rendered.contains("| OFFSET pagination | 43 | 200 | 12.50 |"));
+        assert!(rendered.contains("| 42 (+2 variants) | 10 |"));
+        assert!(rendered.contains("| app_rw | 12 | 400 | 800.00 | 80.0% |"));
+        assert!(rendered.contains("| app_rw | select * from orders where customer_id = $1 |"));
     }
 
     #[test]
@@ -1358,8 +4223,89 @@ mod tests {
 
         let rendered = String::from_utf8(output).expect("text should be utf8");
         assert!(rendered.contains("Coverage summary: 2 suppressed, 1 internal, 1 unresolved-schema, 2 unsupported shapes, 3 parser errors"));
-        assert!(rendered.contains("WAL/call: 204.8 bytes"));
+        assert!(rendered.contains("WAL/call: 204.8 bytes, 3 full-page images"));
         assert!(rendered.contains("evidence: WHERE = customer_id; ORDER BY created_at"));
+        assert!(rendered.contains(
+            "    reason: heuristic from slow query: WHERE customer_id; ORDER BY created_at (queryid 42, 10 calls, 50.00 ms mean)"
+        ));
+        assert!(rendered.contains(
+            "    ddl: CREATE INDEX CONCURRENTLY ON public.orders (customer_id, created_at);"
+        ));
+        assert!(rendered.contains("[OFFSET pagination] queryid 43 (200 calls, 12.50 ms mean)"));
+        assert!(rendered.contains("    roles: app_rw on shop"));
+        assert!(rendered.contains("    I/O time: 400.00ms (80% of execution time)"));
+        assert!(rendered
+            .contains("  - app_rw: 800.00ms (80.0% of total) over 400 calls to 12 statements"));
+        assert!(rendered.contains(
+            "includes 2 variant(s) differing only in literals or list lengths: queryid 7, 9"
+        ));
+    }
+
+    #[test]
+    fn workload_text_describes_logged_statements_and_log_findings() {
+        let reporter = WorkloadReporter::new(ReportFormat::Text);
+        let mut results = sample_workload_results();
+        results.log_findings = Some(LogFindings {
+            files: vec!["postgresql.csv".into()],
+            entries: 120,
+            duration_entries: 80,
+            temp_files: 3,
+            temp_bytes: ByteSize(48 * 1024 * 1024),
+            lock_waits: vec![LockWaitFinding {
+                lock_mode: "ShareLock".into(),
+                waits: 2,
+                max_wait_ms: 1500.0,
+                query_text: "UPDATE orders SET status = $1 WHERE id = $2".into(),
+            }],
+            autovacuum_runs: vec![AutovacuumLogFinding {
+                table: "shop.public.orders".into(),
+                vacuums: 4,
+                analyzes: 1,
+                total_elapsed_secs: 12.5,
+                max_elapsed_secs: 6.25,
+            }],
+            plan_entries: 2,
+            plans: vec![LoggedPlanFinding {
+                queryid: 42,
+                query_text: "SELECT * FROM orders ORDER BY total".into(),
+                plans: 2,
+                total_time_ms: 1900.0,
+                max_time_ms: 1000.0,
+                analyzed: true,
+                nodes: vec![PlanNodeStat {
+                    node: "Seq Scan on orders".into(),
+                    count: 2,
+                    rows: 180000.0,
+                }],
+                regressions: vec![PlanRegression {
+                    kind: PlanRegressionKind::SortSpill,
+                    plans: 1,
+                    detail: "external merge sort by total wrote 2 MB to disk".into(),
+                    suggestion: "Raise work_mem".into(),
+                }],
+            }],
+        });
+        let mut output = Vec::new();
+
+        reporter
+            .write_workload_text(&mut output, &results)
+            .expect("text workload report should render");
+
+        let rendered = String::from_utf8(output).expect("text should be utf8");
+        assert!(rendered.contains("slower than log_min_duration_statement"));
+        assert!(!rendered.contains("since pg_stat_statements was last reset;"));
+        assert!(rendered
+            .contains("Log Files: 120 entries (80 with durations), 3 temporary files (48 MB)"));
+        assert!(rendered.contains(
+            "  - ShareLock x2 (longest 1500.00ms): UPDATE orders SET status = $1 WHERE id = $2"
+        ));
+        assert!(rendered.contains(
+            "  - shop.public.orders: 4 vacuums, 1 analyzes, 12.50s total, 6.25s longest"
+        ));
+        assert!(rendered.contains("    nodes: Seq Scan on orders x2 (180000 rows)"));
+        assert!(rendered.contains(
+            "    [Sort spill] 1/2 plans: external merge sort by total wrote 2 MB to disk"
+        ));
     }
 
     #[test]
@@ -1380,4 +4326,658 @@ mod tests {
         assert!(rendered.contains("\"equality_filters\": ["));
         assert!(rendered.contains("\"notes\": ["));
     }
+
+    #[test]
+    fn workload_html_renders_tables_and_escapes_query_text() {
+        let reporter = WorkloadReporter::new(ReportFormat::Html);
+        let mut results = sample_workload_results();
+        results.slow_query_groups[0].queries[0].query_text =
+            "select * from orders where note <> 'a|b'".into();
+        let mut markdown = Vec::new();
+        reporter
+            .write_workload_markdown(&mut markdown, &results)
+            .expect("markdown workload report should render");
+        let mut output = Vec::new();
+
+        write_html(&mut output, "Workload", &markdown).expect("html should render");
+
+        let rendered = String::from_utf8(output).expect("html should be utf8");
+        assert!(rendered.starts_with("<!DOCTYPE html>"));
+        assert!(rendered.contains("<h1>PostgreSQL Workload Analysis Report</h1>"));
+        assert!(rendered.contains("<th>Calls</th><th>Total ms</th>"));
+        assert!(rendered.contains("<th>Cache Hit %</th>"));
+        assert!(rendered.contains("<td>select * from orders where note &lt;&gt; 'a|b'</td>"));
+        assert!(rendered.contains(
+            "<td><code>CREATE INDEX CONCURRENTLY ON public.orders (customer_id, created_at);</code></td>"
+        ));
+        assert!(rendered
+            .contains("<li><strong>Data source</strong>: <code>pg_stat_statements</code></li>"));
+        assert!(rendered.trim_end().ends_with("</html>"));
+    }
+
+    #[rstest]
+    #[case::markdown(ReportFormat::Markdown, "# Workload Analysis")]
+    #[case::text(ReportFormat::Text, "Workload Analysis\n=================")]
+    fn analysis_report_includes_workload_sections(
+        #[case] format: ReportFormat,
+        #[case] heading: &str,
+    ) {
+        let reporter = Reporter::new(format);
+        let mut results = AnalysisResults::default();
+        let mut output = Vec::new();
+        match format {
+            ReportFormat::Text => reporter.write_text(&mut output, &results),
+            _ => reporter.write_markdown(&mut output, &results),
+        }
+        .expect("analysis report should render");
+        assert!(!String::from_utf8(output).unwrap().contains(heading));
+
+        results.workload = Some(sample_workload_results());
+        let mut output = Vec::new();
+        match format {
+            ReportFormat::Text => reporter.write_text(&mut output, &results),
+            _ => reporter.write_markdown(&mut output, &results),
+        }
+        .expect("analysis report should render");
+
+        let rendered = String::from_utf8(output).expect("report should be utf8");
+        assert!(rendered.contains(heading));
+        assert!(rendered.contains("CREATE INDEX CONCURRENTLY ON public.orders"));
+        assert!(rendered.contains("42"));
+    }
+
+    #[rstest]
+    #[case::markdown(
+        ReportFormat::Markdown,
+        "## Apply on the Host",
+        "sudo sysctl -w vm.swappiness=1\n"
+    )]
+    #[case::text(
+        ReportFormat::Text,
+        "Apply on the host:",
+        "  $ sudo sysctl -w vm.swappiness=1\n"
+    )]
+    fn analysis_report_lists_host_commands(
+        #[case] format: ReportFormat,
+        #[case] heading: &str,
+        #[case] command: &str,
+    ) {
+        let reporter = Reporter::new(format);
+        let results = AnalysisResults {
+            host_commands: vec!["sudo sysctl -w vm.swappiness=1".to_string()],
+            ..AnalysisResults::default()
+        };
+        let mut output = Vec::new();
+        match format {
+            ReportFormat::Text => reporter.write_text(&mut output, &results),
+            _ => reporter.write_markdown(&mut output, &results),
+        }
+        .expect("analysis report should render");
+
+        let rendered = String::from_utf8(output).expect("report should be utf8");
+        assert!(rendered.contains(heading));
+        assert!(rendered.contains(command));
+    }
+
+    #[rstest]
+    #[case::markdown(ReportFormat::Markdown, "**Status**: acknowledged by alice on 2026-10-02: Batch jobs need it (open since 2026-10-01)")]
+    #[case::text(ReportFormat::Text, "    Status:   acknowledged by alice on 2026-10-02: Batch jobs need it (open since 2026-10-01)")]
+    fn suggestions_show_their_stored_lifecycle(#[case] format: ReportFormat, #[case] status: &str) {
+        let mut results = AnalysisResults::default();
+        results.suggestions_by_category.insert(
+            ConfigCategory::Memory,
+            vec![ConfigSuggestion {
+                parameter: "work_mem".to_string(),
+                current_value: "4MB".to_string(),
+                suggested_value: "64MB".to_string(),
+                level: SuggestionLevel::Important,
+                rationale: String::new(),
+                messages: Vec::new(),
+                fingerprint: "5e1f2c3d4b5a6978".to_string(),
+            }],
+        );
+        results.finding_lifecycle.push(FindingLifecycle {
+            fingerprint: "5e1f2c3d4b5a6978".to_string(),
+            category: ConfigCategory::Memory,
+            parameter: "work_mem".to_string(),
+            level: SuggestionLevel::Important,
+            first_seen: "2026-10-01T03:00:00Z".to_string(),
+            last_seen: "2026-10-03T03:00:00Z".to_string(),
+            acknowledged_by: Some("alice".to_string()),
+            acknowledged_at: Some("2026-10-02T09:30:00Z".to_string()),
+            ack_reason: Some("Batch jobs need it".to_string()),
+            resolved_at: None,
+        });
+
+        let reporter = Reporter::new(format);
+        let mut output = Vec::new();
+        match format {
+            ReportFormat::Text => reporter.write_text(&mut output, &results),
+            _ => reporter.write_markdown(&mut output, &results),
+        }
+        .expect("analysis report should render");
+
+        let rendered = String::from_utf8(output).expect("report should be utf8");
+        assert!(rendered.contains(status), "{rendered}");
+    }
+
+    #[test]
+    fn index_removal_markdown_includes_ddl_only_when_emitted() {
+        let mut plan = IndexRemovalPlan {
+            schema: "public".into(),
+            table_name: "orders".into(),
+            index_name: "orders_legacy_idx".into(),
+            monitor_query: "SELECT idx_scan FROM pg_stat_user_indexes WHERE indexrelname = 'orders_legacy_idx';".into(),
+            notes: vec!["Check replicas too.".into()],
+            ddl: None,
+        };
+        let reporter = Reporter::new(ReportFormat::Markdown);
+
+        let mut output = Vec::new();
+        reporter
+            .write_index_removal_markdown(&mut output, std::slice::from_ref(&plan))
+            .expect("removal plan should render");
+        let rendered = String::from_utf8(output).expect("markdown should be utf8");
+        assert!(rendered.contains("### public.orders_legacy_idx (on public.orders)"));
+        assert!(rendered.contains("- Check replicas too."));
+        assert!(!rendered.contains("DROP INDEX"));
+
+        plan.ddl = Some(crate::models::IndexRemovalDdl {
+            reset_stats:
+                "SELECT pg_stat_reset_single_table_counters('public.orders_legacy_idx'::regclass);"
+                    .into(),
+            drop_index: "DROP INDEX CONCURRENTLY IF EXISTS public.orders_legacy_idx;".into(),
+            rollback: None,
+        });
+        let mut output = Vec::new();
+        reporter
+            .write_index_removal_markdown(&mut output, &[plan])
+            .expect("removal plan should render");
+        let rendered = String::from_utf8(output).expect("markdown should be utf8");
+        assert!(rendered
+            .contains("```sql\nDROP INDEX CONCURRENTLY IF EXISTS public.orders_legacy_idx;\n```"));
+        assert!(!rendered.contains("Rollback"));
+    }
+
+    #[test]
+    fn settings_remediation_markdown_pairs_the_script_with_its_rollback() {
+        let remediation = SettingsRemediation {
+            statements: vec![
+                "ALTER SYSTEM SET shared_buffers = '4GB';".into(),
+                "SELECT pg_reload_conf();".into(),
+            ],
+            rollback: vec![
+                "ALTER SYSTEM SET shared_buffers = '16384';".into(),
+                "SELECT pg_reload_conf();".into(),
+            ],
+            restart_required: vec!["shared_buffers".into()],
+        };
+        let mut output = Vec::new();
+        Reporter::new(ReportFormat::Markdown)
+            .write_settings_remediation_markdown(&mut output, &remediation)
+            .expect("remediation should render");
+        let rendered = String::from_utf8(output).expect("markdown should be utf8");
+        assert!(rendered.starts_with(
+            "## Apply with ALTER SYSTEM\n\n```sql\nALTER SYSTEM SET shared_buffers = '4GB';\n"
+        ));
+        assert!(rendered.contains("Restart the server for shared_buffers to take effect."));
+        assert!(rendered.contains(
+            "Rollback, restoring the current values:\n\n```sql\nALTER SYSTEM SET shared_buffers = '16384';\n"
+        ));
+    }
+
+    #[test]
+    fn parameter_state_lists_pending_restarts_and_non_defaults() {
+        let param = |name: &str, value: &str, source: &str, pending_restart: bool| {
+            (
+                name.to_string(),
+                crate::models::PgConfigParam {
+                    name: name.to_string(),
+                    current_value: value.to_string(),
+                    default_value: Some("1024".to_string()),
+                    unit: Some("8kB".to_string()),
+                    context: "postmaster".to_string(),
+                    value: crate::models::ParamValue::parse(value, "integer", Some("8kB")),
+                    source: Some(source.to_string()),
+                    sourcefile: (source == "configuration file")
+                        .then(|| "/etc/postgresql/postgresql.conf".to_string()),
+                    pending_restart,
+                    min_value: None,
+                    max_value: None,
+                },
+            )
+        };
+        let results = AnalysisResults {
+            params: [
+                param("shared_buffers", "16384", "configuration file", true),
+                param("wal_buffers", "512", "override", false),
+                param("temp_buffers", "1024", "default", false),
+            ]
+            .into_iter()
+            .collect(),
+            pending_changes: vec![crate::models::PendingChange {
+                name: "shared_buffers".to_string(),
+                running_value: "16384".to_string(),
+                unit: Some("8kB".to_string()),
+                staged_value: "8GB".to_string(),
+                sourcefile: "/var/lib/postgresql/data/postgresql.auto.conf".to_string(),
+                apply: crate::models::PendingApply::Restart,
+                covers_suggestion: Some("8GB".to_string()),
+            }],
+            ..AnalysisResults::default()
+        };
+        let reporter = Reporter::new(ReportFormat::Markdown);
+
+        let mut output = Vec::new();
+        reporter
+            .write_parameter_state_markdown(&mut output, &results)
+            .expect("parameter state should render");
+        let rendered = String::from_utf8(output).expect("markdown should be utf8");
+        assert!(rendered.contains("## Pending Restart"));
+        assert!(rendered.contains(
+            "| shared_buffers | 16384 x 8kB | 8GB | restart | /var/lib/postgresql/data/postgresql.auto.conf | 8GB |"
+        ));
+        assert!(rendered.contains(
+            "| shared_buffers | 16384 x 8kB | 1024 x 8kB | configuration file (/etc/postgresql/postgresql.conf) |"
+        ));
+        assert!(!rendered.contains("wal_buffers"));
+        assert!(!rendered.contains("temp_buffers"));
+    }
+
+    #[test]
+    fn connection_memory_markdown_breaks_down_per_connection_usage() {
+        const MB: i64 = 1024 * 1024;
+        let memory = ConnectionMemory {
+            backend_overhead: ByteSize(10 * MB),
+            work_mem: ByteSize(64 * MB),
+            work_mem_nodes: 1,
+            active_share: 0.25,
+            temp_buffers: ByteSize(8 * MB),
+            expected_per_connection: ByteSize(26 * MB),
+            worst_per_connection: ByteSize(82 * MB),
+            peak_connections: 80,
+            max_connections: 200,
+            expected_at_peak: ByteSize(80 * 26 * MB),
+            worst_at_max_connections: ByteSize(200 * 82 * MB),
+            available: Some(ByteSize(6 * 1024 * MB)),
+            headroom: crate::models::MemoryHeadroom::Tight,
+        };
+        let reporter = Reporter::new(ReportFormat::Markdown);
+
+        let mut output = Vec::new();
+        reporter
+            .write_connection_memory_markdown(&mut output, &memory)
+            .expect("connection memory should render");
+        let rendered = String::from_utf8(output).expect("markdown should be utf8");
+        assert!(rendered.contains("| work_mem (1 x 64 MB, 25% of connections active) | 16 MB |"));
+        assert!(rendered.contains("| **Expected / worst case** | **26 MB / 82 MB** |"));
+        assert!(rendered.contains("- **Observed peak**: 80 connections, ~2 GB expected"));
+        assert!(rendered.contains("- **Headroom**: Tight: the observed peak fits"));
+    }
+
+    #[test]
+    fn connection_saturation_markdown_shows_peak_by_hour() {
+        let saturation = ConnectionSaturation {
+            samples: 3,
+            first_at: "2026-10-15T03:00:00Z".to_string(),
+            last_at: "2026-10-16T09:40:00Z".to_string(),
+            peak_connections: 180,
+            peak_at: "2026-10-16T09:40:00Z".to_string(),
+            max_connections: 200,
+            peak_utilization: 0.9,
+            hourly_peaks: vec![
+                crate::models::HourlyPeak {
+                    hour: 3,
+                    peak_connections: 20,
+                    samples: 1,
+                },
+                crate::models::HourlyPeak {
+                    hour: 9,
+                    peak_connections: 180,
+                    samples: 2,
+                },
+            ],
+        };
+
+        let mut output = Vec::new();
+        Reporter::new(ReportFormat::Markdown)
+            .write_connection_saturation_markdown(&mut output, &saturation)
+            .expect("connection saturation should render");
+        let rendered = String::from_utf8(output).expect("markdown should be utf8");
+        assert!(rendered
+            .contains("- **Peak**: 180 of 200 max_connections (90%) at 2026-10-16T09:40:00Z"));
+        assert!(rendered.contains("| 03:00 | 20 | 10% | 1 |"));
+        assert!(rendered.contains("| 09:00 | 180 | 90% | 2 |"));
+    }
+
+    #[test]
+    fn schema_usage_markdown_marks_noisy_schemas_and_caps_rows() {
+        let usage: Vec<SchemaUsage> = (0..25)
+            .map(|i| SchemaUsage {
+                schema: format!("tenant_{i:02}"),
+                tables: 4,
+                table_bytes: ByteSize(1024 * 1024),
+                rows_written: 1000,
+                share: if i == 0 { 0.5 } else { 0.02 },
+                noisy: i == 0,
+                findings: usize::from(i == 0),
+                ..Default::default()
+            })
+            .collect();
+
+        let mut output = Vec::new();
+        write_schema_usage_markdown(&mut output, &usage).expect("schema usage should render");
+        let rendered = String::from_utf8(output).expect("markdown should be utf8");
+        assert!(rendered.contains(
+            "| 1 | **tenant_00** (noisy) | 4 | 1 MB | 0 bytes | 0 | 1000 | 0 | 50.0% | 1 |"
+        ));
+        assert!(rendered.contains("| 20 | tenant_19 |"));
+        assert!(!rendered.contains("tenant_20"));
+        assert!(rendered.contains("5 more schemas in the JSON output."));
+    }
+
+    #[test]
+    fn capped_table_index_findings_report_how_many_are_not_shown() {
+        let mut health = TableIndexHealth {
+            seq_scan_info: vec![TableSeqScanInfo {
+                schema: "public".into(),
+                table_name: "orders".into(),
+                seq_scan: 900,
+                idx_scan: 3,
+                live_tuples: 50_000,
+                table_size_bytes: ByteSize(8 * 1024 * 1024),
+                table_size_pretty: "8192 kB".into(),
+                fingerprint: String::new(),
+            }],
+            ..TableIndexHealth::default()
+        };
+        health.not_shown.insert("seq_scan".into(), 14);
+        health.not_shown.insert("unused".into(), 3);
+
+        let mut output = Vec::new();
+        write_table_index_markdown(&mut output, &health).expect("table health should render");
+        let rendered = String::from_utf8(output).expect("markdown should be utf8");
+        assert!(rendered.contains(
+            "| public.orders | 900 | 3 | 50000 | 8192 kB |\n\n\
+             _14 more not shown; raise `--max-findings-per-kind` to list them._\n"
+        ));
+        // No unused indexes are listed, so there is no section to close
+        assert!(!rendered.contains("3 more"));
+
+        let mut output = Vec::new();
+        write_not_shown_text(&mut output, 3, Some("Unused")).expect("line should render");
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "  ... 3 more Unused not shown (raise --max-findings-per-kind to list them)\n"
+        );
+    }
+
+    #[test]
+    fn summary_lists_top_suggestions_and_quiet_prints_nothing_without_findings() {
+        let suggestion = |parameter: &str, level: SuggestionLevel| ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: "1".to_string(),
+            suggested_value: "2".to_string(),
+            level,
+            rationale: "Because.".to_string(),
+            messages: Vec::new(),
+            fingerprint: String::new(),
+        };
+        let mut results = AnalysisResults::default();
+        results.suggestions_by_category.insert(
+            ConfigCategory::Planner,
+            (0..6)
+                .map(|i| suggestion(&format!("planner_{i}"), SuggestionLevel::Recommended))
+                .collect(),
+        );
+        results.suggestions_by_category.insert(
+            ConfigCategory::Memory,
+            vec![suggestion("shared_buffers", SuggestionLevel::Critical)],
+        );
+        let reporter = Reporter::new(ReportFormat::Text).with_detail(ReportDetail::Summary);
+
+        let mut output = Vec::new();
+        reporter
+            .write_summary(&mut output, &results)
+            .expect("summary should render");
+        let rendered = String::from_utf8(output).expect("text should be utf8");
+        assert!(rendered.contains("Health Score: 70/100 (C)\nTotal Suggestions: 7"));
+        assert!(rendered.contains(
+            "Top Suggestions:\n  [CRIT] shared_buffers: 1 -> 2 (Memory Configuration)\n"
+        ));
+        assert_eq!(rendered.matches("  [").count(), 5);
+        assert!(rendered.contains("0 unused indexes (0 bytes)"));
+        assert!(!rendered.contains("Because."));
+
+        let quiet = Reporter::new(ReportFormat::Markdown).with_detail(ReportDetail::Quiet);
+        let mut output = Vec::new();
+        quiet
+            .write_findings(&mut output, &AnalysisResults::default())
+            .expect("findings should render");
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn analysis_report_scores_health_per_category() {
+        let suggestion = |parameter: &str, level: SuggestionLevel| ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: "1".to_string(),
+            suggested_value: "2".to_string(),
+            level,
+            rationale: String::new(),
+            messages: Vec::new(),
+            fingerprint: String::new(),
+        };
+        let mut results = AnalysisResults::default();
+        results.suggestions_by_category.insert(
+            ConfigCategory::Memory,
+            vec![suggestion("work_mem", SuggestionLevel::Important)],
+        );
+        results.suggestions_by_category.insert(
+            ConfigCategory::Backup,
+            vec![suggestion("backup_age", SuggestionLevel::Critical)],
+        );
+
+        let mut output = Vec::new();
+        Reporter::new(ReportFormat::Markdown)
+            .write_markdown(&mut output, &results)
+            .expect("analysis report should render");
+        let rendered = String::from_utf8(output).expect("markdown should be utf8");
+        assert!(rendered.contains("## Health Score\n\n**68/100 (D)**\n"));
+        assert!(rendered
+            .contains("| Backup and Recovery | 80 | 1 |\n| Memory Configuration | 91 | 1 |\n"));
+
+        let mut output = Vec::new();
+        Reporter::new(ReportFormat::Text)
+            .write_text(&mut output, &results)
+            .expect("analysis report should render");
+        let rendered = String::from_utf8(output).expect("text should be utf8");
+        assert!(rendered.contains("Health Score: 68/100 (D)\n  Backup and Recovery"));
+    }
+
+    #[test]
+    fn fleet_summary_lists_databases_worst_first() {
+        let entry = |database: &str, score: u8| FleetEntry {
+            database: database.to_string(),
+            host: "db1".to_string(),
+            health: HealthScore {
+                score,
+                grade: scoring::Grade::from_score(score),
+                categories: Vec::new(),
+            },
+            level_counts: Default::default(),
+        };
+        let entries = [entry("orders", 92), entry("billing", 61)];
+
+        let mut output = Vec::new();
+        FleetReporter::new(ReportFormat::Markdown)
+            .write_fleet(&mut output, &entries)
+            .expect("fleet summary should render");
+        let rendered = String::from_utf8(output).expect("markdown should be utf8");
+        let billing = rendered.find("| billing | db1 | 61 | D |").unwrap();
+        let orders = rendered.find("| orders | db1 | 92 | A |").unwrap();
+        assert!(billing < orders);
+    }
+
+    #[test]
+    fn github_comment_starts_with_marker_and_folds_rationale() {
+        let mut results = AnalysisResults::default();
+        results.suggestions_by_category.insert(
+            ConfigCategory::Memory,
+            vec![ConfigSuggestion {
+                parameter: "shared_buffers".to_string(),
+                current_value: "128MB".to_string(),
+                suggested_value: "4096MB".to_string(),
+                level: SuggestionLevel::Critical,
+                rationale: "shared_buffers should be ~25% of total RAM.".to_string(),
+                messages: Vec::new(),
+                fingerprint: String::new(),
+            }],
+        );
+        let reporter = Reporter::new(ReportFormat::GithubComment);
+
+        let mut output = Vec::new();
+        reporter
+            .write_github_comment(&mut output, &results, None)
+            .expect("comment should render");
+        let rendered = String::from_utf8(output).expect("markdown should be utf8");
+        assert!(rendered.starts_with(
+            "<!-- postgreat:report -->\n### PostGreat 80/100 (B): :red_circle: 1 critical\n"
+        ));
+        assert!(rendered.contains(
+            "| :red_circle: | `shared_buffers` | `128MB` | `4096MB` | Memory Configuration |"
+        ));
+        assert!(rendered.contains("<details>"));
+
+        let mut output = Vec::new();
+        reporter
+            .write_github_comment(&mut output, &AnalysisResults::default(), None)
+            .expect("comment should render");
+        let rendered = String::from_utf8(output).expect("markdown should be utf8");
+        assert!(rendered.contains("no configuration suggestions"));
+        assert!(!rendered.contains("<details>"));
+    }
+
+    #[test]
+    fn check_catalog_markdown_lists_every_check() {
+        let reporter = CheckCatalogReporter::new(ReportFormat::Markdown);
+        let mut output = Vec::new();
+
+        reporter
+            .write_checks(&mut output, crate::checks::CHECKS)
+            .expect("check catalog should render");
+
+        let rendered = String::from_utf8(output).expect("markdown should be utf8");
+        for check in crate::checks::CHECKS {
+            assert!(rendered.contains(&format!("`{}`", check.id)));
+        }
+        assert!(rendered.contains("| `workload.slow_queries` | Workload Analysis | INFO | pg_read_all_stats | pg_stat_statements |"));
+    }
+
+    #[test]
+    fn permissions_count_checks_per_grant_and_list_queries() {
+        let reporter = PermissionsReporter::new(ReportFormat::Markdown);
+        let mut output = Vec::new();
+
+        reporter
+            .write_permissions(
+                &mut output,
+                crate::checks::CHECKS,
+                &crate::queries::all_queries(),
+            )
+            .expect("permissions should render");
+
+        let rendered = String::from_utf8(output).expect("markdown should be utf8");
+        let stats_checks = crate::checks::CHECKS
+            .iter()
+            .filter(|check| check.privileges.contains(&"pg_read_all_stats"))
+            .count();
+        assert!(rendered.contains(&format!("| pg_read_all_stats | {stats_checks} |")));
+        assert!(rendered.contains("### Server settings\n\n```sql\nSELECT"));
+    }
+
+    #[test]
+    fn simulation_markdown_compares_current_and_simulated_settings() {
+        use crate::analysis::vacuum_simulation::{AutovacuumSettings, TableVacuumSimulation};
+        use crate::models::{ByteSize, Seconds};
+
+        let settings = AutovacuumSettings {
+            cost_limit: 200.0,
+            cost_delay_ms: 2.0,
+            scale_factor: 0.2,
+            threshold: 50.0,
+            max_workers: 3,
+            naptime: Seconds(60.0),
+            page_miss_cost: 2.0,
+            page_dirty_cost: 20.0,
+        };
+        let cycle = |fill: f64, duration: f64, keeps_up: bool| VacuumCycle {
+            trigger_dead_tuples: 1_000.0,
+            fill_time: Some(Seconds(fill)),
+            vacuum_duration: Seconds(duration),
+            peak_dead_tuples: 2_000.0,
+            keeps_up,
+        };
+        let simulation = AutovacuumSimulation {
+            window: Seconds(3_600.0),
+            before: settings,
+            after: AutovacuumSettings {
+                cost_limit: 2000.0,
+                ..settings
+            },
+            tables: vec![TableVacuumSimulation {
+                schema: "public".to_string(),
+                table_name: "orders".to_string(),
+                table_size_bytes: ByteSize(1024 * 1024 * 1024),
+                dead_tuples_per_sec: 10.0,
+                before: cycle(600.0, 1_200.0, false),
+                after: cycle(600.0, 120.0, true),
+            }],
+            before_load: 2.0,
+            after_load: 0.2,
+        };
+        let mut output = Vec::new();
+
+        SimulationReporter::new(ReportFormat::Markdown)
+            .write_simulation(&mut output, &simulation)
+            .expect("simulation should render");
+
+        let rendered = String::from_utf8(output).expect("markdown should be utf8");
+        assert!(rendered.contains("over 1h 0m of statistics"));
+        assert!(rendered.contains("| autovacuum_vacuum_cost_limit | 200 | 2000 |"));
+        assert!(rendered.contains("| cost budget needed | 200% | 20% |"));
+        assert!(rendered.contains(
+            "| public.orders | 1 GB | 10 | 10m -> 10m | 20m -> 2m | 2000 -> 2000 | no -> yes |"
+        ));
+    }
+
+    #[test]
+    fn explain_text_includes_guidance_and_live_values() {
+        let reporter = ExplainReporter::new(ReportFormat::Text);
+        let live = crate::models::PgConfigParam {
+            name: "shared_buffers".to_string(),
+            current_value: "16384".to_string(),
+            default_value: Some("1024".to_string()),
+            unit: Some("8kB".to_string()),
+            context: "postmaster".to_string(),
+            value: crate::models::ParamValue::parse("16384", "integer", Some("8kB")),
+            source: None,
+            sourcefile: None,
+            pending_restart: false,
+            min_value: None,
+            max_value: None,
+        };
+        let explanation = ParameterExplanation::new("shared_buffers", Some(live));
+        let mut output = Vec::new();
+
+        reporter
+            .write_explanation(&mut output, &explanation)
+            .expect("explanation should render");
+
+        let rendered = String::from_utf8(output).expect("text should be utf8");
+        assert!(rendered.contains("Recommendation: ~25% of RAM"));
+        assert!(rendered.contains("Takes effect: server restart required"));
+        assert!(rendered.contains("Check: memory.shared_buffers"));
+        assert!(rendered.contains("Current value: 16384 x 8kB"));
+    }
 }
@@ -0,0 +1,240 @@
+use crate::checks::{CheckInfo, CHECKS};
+use crate::guidance::{self, ParameterGuide};
+use crate::i18n::{self, Lang};
+use crate::models::ConfigCategory;
+use crate::profile::{Profile, ProfileThresholds};
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// Everything `postgreat rules` documents about one check, gathered from the
+/// check registry, the message catalog, stored guidance and the profiles
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleDoc {
+    #[serde(flatten)]
+    pub check: &'static CheckInfo,
+    /// GUC a settings check inspects
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameter: Option<&'static str>,
+    /// Message templates findings are worded with, by catalog key
+    pub rationales: Vec<RationaleTemplate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guide: Option<&'static ParameterGuide>,
+    /// Thresholds that differ per recommendation profile
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub thresholds: Vec<ProfileThreshold>,
+    pub references: Vec<Reference>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RationaleTemplate {
+    pub key: &'static str,
+    pub template: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileThreshold {
+    pub profile: Profile,
+    pub threshold: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Reference {
+    pub title: &'static str,
+    pub url: &'static str,
+}
+
+/// Documentation for every registered check, in report order
+pub fn rule_docs(lang: Lang) -> Vec<RuleDoc> {
+    CHECKS
+        .iter()
+        .map(|check| RuleDoc::new(check, lang))
+        .collect()
+}
+
+impl RuleDoc {
+    pub fn new(check: &'static CheckInfo, lang: Lang) -> Self {
+        let parameter = check.parameter();
+        let mut templates = i18n::check_templates(lang, check.id);
+        if templates.is_empty() {
+            templates = i18n::check_templates(Lang::En, check.id);
+        }
+        Self {
+            check,
+            parameter,
+            rationales: templates
+                .into_iter()
+                .map(|(key, template)| RationaleTemplate { key, template })
+                .collect(),
+            guide: parameter.and_then(guidance::find),
+            thresholds: profile_thresholds(check),
+            references: category_reference(check.category).into_iter().collect(),
+        }
+    }
+}
+
+fn profile_thresholds(check: &CheckInfo) -> Vec<ProfileThreshold> {
+    Profile::value_variants()
+        .iter()
+        .filter_map(|profile| {
+            let enabled = check
+                .parameter()
+                .is_none_or(|parameter| profile.is_check_enabled(parameter));
+            let threshold = if enabled {
+                threshold(check.id, &profile.thresholds())?
+            } else {
+                "not checked".to_string()
+            };
+            Some(ProfileThreshold {
+                profile: *profile,
+                threshold,
+            })
+        })
+        .collect()
+}
+
+/// The profile-dependent threshold a check compares against, worded for the docs
+fn threshold(check_id: &str, thresholds: &ProfileThresholds) -> Option<String> {
+    let tiers = |values: [u64; 3], unit: &str| {
+        format!(
+            "{}{unit} / {}{unit} / {}{unit} with up to 16GB / up to 64GB / more RAM",
+            values[0], values[1], values[2]
+        )
+    };
+    let percent = |ratio: f64| format!("{:.0}%", ratio * 100.0);
+    Some(match check_id {
+        "memory.work_mem" => format!(
+            "{}; flagged above {}MB",
+            tiers(thresholds.work_mem_mb, "MB"),
+            thresholds.work_mem_ceiling_mb
+        ),
+        "concurrency.max_connections" => format!(
+            "{} per vCPU, at least 100",
+            thresholds.max_connections_per_vcpu
+        ),
+        "concurrency.max_parallel_workers_per_gather" => format!(
+            "{} of vCPUs; flagged below {} of that",
+            percent(thresholds.parallel_gather_ratio),
+            percent(thresholds.parallel_underutilized_ratio)
+        ),
+        "concurrency.max_parallel_workers" => format!(
+            "flagged below {} of the vCPU-based target",
+            percent(thresholds.parallel_underutilized_ratio)
+        ),
+        "wal.max_wal_size" => tiers(thresholds.max_wal_size_gb, "GB"),
+        "wal.checkpoint_timeout" => format!(
+            "{}; flagged below {}s",
+            thresholds.checkpoint_timeout_target, thresholds.checkpoint_timeout_min_secs
+        ),
+        "planner.default_statistics_target" => match thresholds.default_statistics_target {
+            Some(target) => format!("at least {target}"),
+            None => "not checked".to_string(),
+        },
+        _ => return None,
+    })
+}
+
+/// PostgreSQL documentation chapter behind each category's checks
+fn category_reference(category: ConfigCategory) -> Option<Reference> {
+    let (title, url) = match category {
+        ConfigCategory::Memory | ConfigCategory::Concurrency => (
+            "Resource Consumption",
+            "https://www.postgresql.org/docs/current/runtime-config-resource.html",
+        ),
+        ConfigCategory::Wal => (
+            "Write Ahead Log",
+            "https://www.postgresql.org/docs/current/runtime-config-wal.html",
+        ),
+        ConfigCategory::Planner => (
+            "Query Planning",
+            "https://www.postgresql.org/docs/current/runtime-config-query.html",
+        ),
+        ConfigCategory::Autovacuum => (
+            "Routine Vacuuming",
+            "https://www.postgresql.org/docs/current/routine-vacuuming.html",
+        ),
+        ConfigCategory::Logging => (
+            "Error Reporting and Logging",
+            "https://www.postgresql.org/docs/current/runtime-config-logging.html",
+        ),
+        ConfigCategory::TableIndex => (
+            "The Cumulative Statistics System",
+            "https://www.postgresql.org/docs/current/monitoring-stats.html",
+        ),
+        ConfigCategory::Workload => (
+            "pg_stat_statements",
+            "https://www.postgresql.org/docs/current/pgstatstatements.html",
+        ),
+        ConfigCategory::Version => (
+            "Versioning Policy",
+            "https://www.postgresql.org/support/versioning/",
+        ),
+        ConfigCategory::Host | ConfigCategory::OperatingSystem => (
+            "Managing Kernel Resources",
+            "https://www.postgresql.org/docs/current/kernel-resources.html",
+        ),
+        ConfigCategory::Backup => (
+            "Backup and Restore",
+            "https://www.postgresql.org/docs/current/backup.html",
+        ),
+        ConfigCategory::Replication => (
+            "Replication",
+            "https://www.postgresql.org/docs/current/runtime-config-replication.html",
+        ),
+        ConfigCategory::Security => (
+            "Row Security Policies",
+            "https://www.postgresql.org/docs/current/ddl-rowsecurity.html",
+        ),
+        ConfigCategory::Schema => (
+            "Data Definition",
+            "https://www.postgresql.org/docs/current/ddl.html",
+        ),
+        ConfigCategory::Extensions => return None,
+    };
+    Some(Reference { title, url })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_check_is_documented_with_its_templates_and_thresholds() {
+        let docs = rule_docs(Lang::En);
+        assert_eq!(docs.len(), CHECKS.len());
+
+        let work_mem = docs
+            .iter()
+            .find(|doc| doc.check.id == "memory.work_mem")
+            .unwrap();
+        let keys: Vec<_> = work_mem.rationales.iter().map(|r| r.key).collect();
+        assert!(keys.contains(&"memory.work_mem.too_high"));
+        assert!(keys.contains(&"memory.work_mem.too_low"));
+        assert_eq!(work_mem.guide.unwrap().name, "work_mem");
+        assert_eq!(work_mem.thresholds.len(), Profile::value_variants().len());
+        assert_eq!(
+            work_mem.thresholds[0].threshold,
+            "32MB / 64MB / 64MB with up to 16GB / up to 64GB / more RAM; flagged above 512MB"
+        );
+
+        let max_connections = docs
+            .iter()
+            .find(|doc| doc.check.id == "concurrency.max_connections")
+            .unwrap();
+        let warehouse = max_connections
+            .thresholds
+            .iter()
+            .find(|t| t.profile == Profile::Warehouse)
+            .unwrap();
+        assert_eq!(warehouse.threshold, "not checked");
+
+        let shared_buffers = docs
+            .iter()
+            .find(|doc| doc.check.id == "memory.shared_buffers")
+            .unwrap();
+        assert!(shared_buffers.thresholds.is_empty());
+        assert_eq!(
+            shared_buffers.references[0].url,
+            "https://www.postgresql.org/docs/current/runtime-config-resource.html"
+        );
+    }
+}
@@ -0,0 +1,459 @@
+use crate::analysis::sampling::format_window;
+use crate::checks::{self, CheckInfo};
+use crate::config::parse_duration;
+use crate::i18n::Message;
+use crate::models::{AnalysisResults, ConfigCategory, IndexIssueKind, SuggestionLevel};
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Debug, Snafu)]
+pub enum RulesError {
+    #[snafu(display("Failed to read rules from {}: {}", path.display(), source))]
+    RulesRead {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Failed to parse rules file {}: {}", path.display(), source))]
+    RulesParse {
+        path: PathBuf,
+        source: serde_yaml::Error,
+    },
+
+    #[snafu(display(
+        "Unknown check '{}' in severity overrides; run `postgreat list-checks` for the list",
+        check
+    ))]
+    UnknownCheck { check: String },
+
+    #[snafu(display(
+        "Check '{}' reports findings rather than suggestions, so its severity cannot be overridden",
+        check
+    ))]
+    NotOverridable { check: String },
+
+    #[snafu(display(
+        "Invalid severity '{}' for '{}'; expected critical, important, recommended or info",
+        level,
+        check
+    ))]
+    InvalidLevel { check: String, level: String },
+
+    #[snafu(display("Invalid evidence.min_stats_window: {}", message))]
+    InvalidWindow { message: String },
+}
+
+type Result<T, E = RulesError> = std::result::Result<T, E>;
+
+/// On-disk layout of a rules file
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    /// Check ID or parameter name to severity
+    #[serde(default)]
+    severity: BTreeMap<String, String>,
+    #[serde(default)]
+    evidence: EvidenceFile,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EvidenceFile {
+    min_stats_window: Option<String>,
+    min_table_scans: Option<i64>,
+}
+
+/// Minimum statistics behind unused-index and sequential-scan findings
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EvidenceThresholds {
+    /// History the cumulative counters must cover since their last reset
+    pub min_stats_window: Option<Duration>,
+    /// Sequential plus index scans the table must have seen
+    pub min_table_scans: Option<i64>,
+}
+
+impl EvidenceThresholds {
+    pub fn is_empty(&self) -> bool {
+        self.min_stats_window.is_none() && self.min_table_scans.is_none()
+    }
+
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(window) = self.min_stats_window {
+            parts.push(format!(
+                "statistics window of at least {}",
+                format_window(window.as_secs())
+            ));
+        }
+        if let Some(scans) = self.min_table_scans {
+            parts.push(format!("at least {scans} table scans"));
+        }
+        parts.join(", ")
+    }
+
+    /// Whether the counters are too young or too sparse to back a finding
+    fn is_weak(&self, stats_age_secs: Option<u64>, table_scans: Option<i64>) -> bool {
+        let short_window = self
+            .min_stats_window
+            .zip(stats_age_secs)
+            .is_some_and(|(min, age)| age < min.as_secs());
+        let few_scans = self
+            .min_table_scans
+            .zip(table_scans)
+            .is_some_and(|(min, scans)| scans < min);
+        short_window || few_scans
+    }
+}
+
+/// Organizational policy applied to results after analysis
+#[derive(Debug, Clone, Default)]
+pub struct Rules {
+    /// Severity per check ID
+    severity: HashMap<&'static str, SuggestionLevel>,
+    evidence: EvidenceThresholds,
+}
+
+impl Rules {
+    /// Loads and validates a YAML rules file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path).context(RulesReadSnafu { path })?;
+        let file: RulesFile = serde_yaml::from_str(&contents).context(RulesParseSnafu { path })?;
+        Self::from_rules_file(file)
+    }
+
+    fn from_rules_file(file: RulesFile) -> Result<Self> {
+        let min_stats_window = file
+            .evidence
+            .min_stats_window
+            .as_deref()
+            .map(parse_duration)
+            .transpose()
+            .map_err(|message| RulesError::InvalidWindow { message })?;
+        Ok(
+            Self::from_overrides(&file.severity)?.with_evidence(EvidenceThresholds {
+                min_stats_window,
+                min_table_scans: file.evidence.min_table_scans,
+            }),
+        )
+    }
+
+    /// Resolves `check -> level` pairs, where a check is its stable ID or, for
+    /// settings checks, the parameter it inspects
+    pub fn from_overrides<K, V>(overrides: &BTreeMap<K, V>) -> Result<Self>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut severity = HashMap::new();
+        for (check, level) in overrides {
+            let (check, level) = (check.as_ref(), level.as_ref());
+            let info = resolve_check(check)?;
+            let level = parse_level(level).ok_or_else(|| RulesError::InvalidLevel {
+                check: check.to_string(),
+                level: level.to_string(),
+            })?;
+            severity.insert(info.id, level);
+        }
+        Ok(Self {
+            severity,
+            evidence: EvidenceThresholds::default(),
+        })
+    }
+
+    pub fn with_evidence(mut self, evidence: EvidenceThresholds) -> Self {
+        self.evidence = evidence;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.severity.is_empty() && self.evidence.is_empty()
+    }
+
+    /// Withholds findings that lack the configured evidence, then re-maps
+    /// suggestion levels to the configured severities
+    pub fn apply(&self, results: &mut AnalysisResults) {
+        self.withhold_weak_findings(results);
+        self.override_severities(results);
+    }
+
+    /// Drops sequential-scan and unused-index findings, with their suggestions
+    /// and removal plans, whose counters fall short of the evidence
+    /// thresholds. Unknown statistics ages and scan counts do not count
+    /// against a finding.
+    fn withhold_weak_findings(&self, results: &mut AnalysisResults) {
+        if self.evidence.is_empty() {
+            return;
+        }
+        let stats_age_secs = results.system_stats.stats_age_secs;
+        let health = &mut results.table_health;
+        let mut withheld = HashSet::new();
+
+        health.seq_scan_info.retain(|table| {
+            let weak = self
+                .evidence
+                .is_weak(stats_age_secs, Some(table.seq_scan + table.idx_scan));
+            if weak {
+                withheld.insert(table.fingerprint.clone());
+            }
+            !weak
+        });
+        let seq_scans = withheld.len();
+
+        let mut unused_indexes = HashSet::new();
+        health.index_usage_info.retain(|index| {
+            let weak = index.issue == IndexIssueKind::Unused
+                && self.evidence.is_weak(stats_age_secs, index.table_scans);
+            if weak {
+                withheld.insert(index.fingerprint.clone());
+                unused_indexes.insert((index.schema.clone(), index.index_name.clone()));
+            }
+            !weak
+        });
+        if withheld.is_empty() {
+            return;
+        }
+
+        if let Some(suggestions) = results
+            .suggestions_by_category
+            .get_mut(&ConfigCategory::TableIndex)
+        {
+            suggestions.retain(|suggestion| !withheld.contains(&suggestion.fingerprint));
+        }
+        results.index_removal_plans.retain(|plan| {
+            !unused_indexes.contains(&(plan.schema.clone(), plan.index_name.clone()))
+        });
+        results.warnings.push(format!(
+            "Withheld {} sequential-scan and {} unused-index finding(s) below the rules file's evidence thresholds ({}).",
+            seq_scans,
+            unused_indexes.len(),
+            self.evidence.describe()
+        ));
+    }
+
+    /// Re-maps suggestion levels to the configured severities. Each changed
+    /// suggestion gets a note naming the level the check reported, so the
+    /// report shows that policy rather than the heuristic set it.
+    fn override_severities(&self, results: &mut AnalysisResults) {
+        if self.severity.is_empty() {
+            return;
+        }
+        for (category, suggestions) in results.suggestions_by_category.iter_mut() {
+            for suggestion in suggestions.iter_mut() {
+                let Some(&level) = checks::for_suggestion(*category, suggestion)
+                    .and_then(|check| self.severity.get(check.id))
+                else {
+                    continue;
+                };
+                if level == suggestion.level {
+                    continue;
+                }
+                suggestion.push_note(
+                    Message::new("note.severity_override")
+                        .arg("level", level.as_str())
+                        .arg("original", suggestion.level.as_str()),
+                );
+                suggestion.level = level;
+            }
+        }
+    }
+}
+
+fn resolve_check(check: &str) -> Result<&'static CheckInfo> {
+    let info = checks::find(check)
+        .or_else(|| checks::for_parameter(check))
+        .ok_or_else(|| RulesError::UnknownCheck {
+            check: check.to_string(),
+        })?;
+    if info.is_finding() {
+        return NotOverridableSnafu { check }.fail();
+    }
+    Ok(info)
+}
+
+fn parse_level(level: &str) -> Option<SuggestionLevel> {
+    [
+        SuggestionLevel::Critical,
+        SuggestionLevel::Important,
+        SuggestionLevel::Recommended,
+        SuggestionLevel::Info,
+    ]
+    .into_iter()
+    .find(|candidate| candidate.as_str().eq_ignore_ascii_case(level))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fingerprint;
+    use crate::models::{ByteSize, ConfigSuggestion, IndexUsageInfo, TableSeqScanInfo};
+    use rstest::rstest;
+
+    fn suggestion(parameter: &str, level: SuggestionLevel) -> ConfigSuggestion {
+        ConfigSuggestion {
+            parameter: parameter.to_string(),
+            current_value: "3".to_string(),
+            suggested_value: "6".to_string(),
+            level,
+            rationale: "rationale".to_string(),
+            messages: Vec::new(),
+            fingerprint: String::new(),
+        }
+    }
+
+    #[rstest]
+    #[case("autovacuum.max_workers", "info")]
+    #[case("autovacuum_max_workers", "INFO")]
+    #[case("Autovacuum_Max_Workers", "Info")]
+    fn overrides_accept_ids_and_parameter_names(#[case] check: &str, #[case] level: &str) {
+        let rules = Rules::from_overrides(&BTreeMap::from([(check, level)])).unwrap();
+        let mut results = AnalysisResults::default();
+        results.suggestions_by_category.insert(
+            ConfigCategory::Autovacuum,
+            vec![
+                suggestion("autovacuum_max_workers", SuggestionLevel::Important),
+                suggestion("autovacuum_naptime", SuggestionLevel::Recommended),
+            ],
+        );
+
+        rules.apply(&mut results);
+
+        let suggestions = &results.suggestions_by_category[&ConfigCategory::Autovacuum];
+        assert_eq!(suggestions[0].level, SuggestionLevel::Info);
+        assert!(suggestions[0]
+            .rationale
+            .contains("Severity set to INFO by the rules file (the check reported IMPORTANT)."));
+        assert_eq!(suggestions[1].level, SuggestionLevel::Recommended);
+        assert_eq!(suggestions[1].rationale, "rationale");
+    }
+
+    #[rstest]
+    #[case("autovacuum.no_such_check", "info", "Unknown check")]
+    #[case("workload.index_candidates", "critical", "reports findings")]
+    #[case("autovacuum.max_workers", "urgent", "Invalid severity 'urgent'")]
+    fn invalid_overrides_are_rejected(
+        #[case] check: &str,
+        #[case] level: &str,
+        #[case] expected: &str,
+    ) {
+        let err = Rules::from_overrides(&BTreeMap::from([(check, level)])).unwrap_err();
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+
+    fn seq_scan_table(table_name: &str, scans: i64) -> TableSeqScanInfo {
+        TableSeqScanInfo {
+            schema: "public".into(),
+            table_name: table_name.into(),
+            seq_scan: scans,
+            idx_scan: 0,
+            live_tuples: 1_000_000,
+            table_size_bytes: ByteSize(1 << 30),
+            table_size_pretty: "1024 MB".into(),
+            fingerprint: String::new(),
+        }
+    }
+
+    fn unused_index(index_name: &str, table_scans: Option<i64>) -> IndexUsageInfo {
+        IndexUsageInfo {
+            issue: IndexIssueKind::Unused,
+            schema: "public".into(),
+            table_name: "orders".into(),
+            index_name: index_name.into(),
+            key_columns: vec!["status".into()],
+            index_size_bytes: ByteSize(1 << 20),
+            index_size_pretty: "1024 kB".into(),
+            scans: 0,
+            tuples_read: 0,
+            tuples_fetched: 0,
+            avg_tuples_per_scan: 0.0,
+            heap_fetch_ratio: 0.0,
+            table_live_tup: None,
+            table_index_writes: None,
+            table_scans,
+            index_definition: None,
+            partial_predicate: None,
+            estimated_size_bytes: None,
+            is_unique: false,
+            enforces_constraint: false,
+            is_expression: false,
+            is_partial: false,
+            fingerprint: String::new(),
+        }
+    }
+
+    fn evidence_results() -> AnalysisResults {
+        let mut results = AnalysisResults::default();
+        results.system_stats.stats_age_secs = Some(10 * 86_400);
+        results.table_health.seq_scan_info = vec![
+            seq_scan_table("orders", 50),
+            seq_scan_table("events", 5_000),
+        ];
+        results.table_health.index_usage_info = vec![
+            unused_index("orders_status_idx", Some(40)),
+            unused_index("orders_region_idx", None),
+        ];
+        results.suggestions_by_category.insert(
+            ConfigCategory::TableIndex,
+            vec![
+                suggestion(
+                    "table public.orders sequential scans",
+                    SuggestionLevel::Important,
+                ),
+                suggestion(
+                    "table public.events sequential scans",
+                    SuggestionLevel::Important,
+                ),
+            ],
+        );
+        fingerprint::assign(&mut results);
+        results
+    }
+
+    #[rstest]
+    #[case("evidence: {min_table_scans: 100}", &["events"], &["orders_region_idx"])]
+    #[case("evidence: {min_stats_window: 14d}", &[], &[])]
+    #[case("evidence: {min_stats_window: 7d}", &["orders", "events"], &["orders_status_idx", "orders_region_idx"])]
+    fn findings_without_enough_evidence_are_withheld(
+        #[case] yaml: &str,
+        #[case] tables: &[&str],
+        #[case] indexes: &[&str],
+    ) {
+        let file: RulesFile = serde_yaml::from_str(yaml).unwrap();
+        let rules = Rules::from_rules_file(file).unwrap();
+        let mut results = evidence_results();
+
+        rules.apply(&mut results);
+
+        let health = &results.table_health;
+        let kept: Vec<_> = health
+            .seq_scan_info
+            .iter()
+            .map(|t| t.table_name.as_str())
+            .collect();
+        assert_eq!(kept, tables);
+        let kept: Vec<_> = health
+            .index_usage_info
+            .iter()
+            .map(|i| i.index_name.as_str())
+            .collect();
+        assert_eq!(kept, indexes);
+        let suggestions = &results.suggestions_by_category[&ConfigCategory::TableIndex];
+        assert_eq!(suggestions.len(), tables.len());
+        assert_eq!(
+            results.warnings.len(),
+            usize::from(tables.len() + indexes.len() < 4)
+        );
+    }
+
+    #[test]
+    fn invalid_stats_window_is_rejected() {
+        let file: RulesFile = serde_yaml::from_str("evidence: {min_stats_window: soon}").unwrap();
+        let err = Rules::from_rules_file(file).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Invalid evidence.min_stats_window"));
+    }
+}
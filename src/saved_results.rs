@@ -0,0 +1,139 @@
+use crate::fingerprint;
+use crate::models::AnalysisResults;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use snafu::{ResultExt, Snafu};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Snafu)]
+pub enum SavedResultsError {
+    #[snafu(display("Failed to write results to {}: {}", path.display(), source))]
+    ResultsWrite {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Failed to read results from {}: {}", path.display(), source))]
+    ResultsRead {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Failed to serialize results: {}", source))]
+    ResultsSerialize { source: serde_json::Error },
+
+    #[snafu(display("{} is not a saved analysis result: {}", path.display(), source))]
+    ResultsParse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+type Result<T, E = SavedResultsError> = std::result::Result<T, E>;
+
+/// First bytes of every gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Writes results as JSON, gzip-compressed when the path ends in `.gz`. The
+/// results are saved before localization so `report --lang` can pick the
+/// language later.
+pub fn save(path: &Path, results: &AnalysisResults) -> Result<()> {
+    let json = serde_json::to_vec(results).context(ResultsSerializeSnafu)?;
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).context(ResultsWriteSnafu { path })?;
+    }
+    let file = File::create(path).context(ResultsWriteSnafu { path })?;
+    if path.extension().is_some_and(|ext| ext == "gz") {
+        let mut encoder = GzEncoder::new(BufWriter::new(file), Compression::default());
+        encoder
+            .write_all(&json)
+            .context(ResultsWriteSnafu { path })?;
+        encoder
+            .finish()
+            .and_then(|mut writer| writer.flush())
+            .context(ResultsWriteSnafu { path })?;
+    } else {
+        BufWriter::new(file)
+            .write_all(&json)
+            .context(ResultsWriteSnafu { path })?;
+    }
+    Ok(())
+}
+
+/// Reads results written by [`save`], `-f json` or the scheduler; gzip is
+/// detected from the content rather than the file name. Files written before
+/// fingerprints existed get them filled in.
+pub fn load(path: &Path) -> Result<AnalysisResults> {
+    let mut bytes = Vec::new();
+    File::open(path)
+        .and_then(|file| BufReader::new(file).read_to_end(&mut bytes))
+        .context(ResultsReadSnafu { path })?;
+    if bytes.starts_with(&GZIP_MAGIC) {
+        let mut json = Vec::new();
+        GzDecoder::new(bytes.as_slice())
+            .read_to_end(&mut json)
+            .context(ResultsReadSnafu { path })?;
+        bytes = json;
+    }
+    let mut results: AnalysisResults =
+        serde_json::from_slice(&bytes).context(ResultsParseSnafu { path })?;
+    fingerprint::assign(&mut results);
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConfigCategory, ConfigSuggestion, SuggestionLevel};
+    use rstest::rstest;
+    use tempfile::tempdir;
+
+    #[rstest]
+    #[case("results.json")]
+    #[case("nested/results.json.gz")]
+    fn saved_results_load_back_unchanged(#[case] name: &str) {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(name);
+        let mut results = AnalysisResults::default();
+        results
+            .suggestions_by_category
+            .entry(ConfigCategory::Memory)
+            .or_default()
+            .push(ConfigSuggestion {
+                parameter: "work_mem".to_string(),
+                current_value: "4MB".to_string(),
+                suggested_value: "64MB".to_string(),
+                level: SuggestionLevel::Important,
+                rationale: String::new(),
+                messages: Vec::new(),
+                fingerprint: String::new(),
+            });
+
+        save(&path, &results).unwrap();
+        let compressed = fs::read(&path).unwrap().starts_with(&GZIP_MAGIC);
+        assert_eq!(compressed, name.ends_with(".gz"));
+
+        let loaded = load(&path).unwrap();
+        let suggestion = &loaded.suggestions_by_category[&ConfigCategory::Memory][0];
+        assert_eq!(suggestion.suggested_value, "64MB");
+        assert_eq!(suggestion.fingerprint.len(), 16);
+    }
+
+    #[test]
+    fn other_json_is_rejected_with_the_file_name() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("lint.json");
+        fs::write(&path, "[1, 2, 3]").unwrap();
+        let err = load(&path).unwrap_err().to_string();
+        assert!(err.starts_with(&format!(
+            "{} is not a saved analysis result",
+            path.display()
+        )));
+    }
+}
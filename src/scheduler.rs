@@ -0,0 +1,696 @@
+use crate::api::{self, ApiError};
+use crate::checker::{AnalyzeOptions, CheckerError, ConfigChecker};
+use crate::config::DbConfig;
+use crate::email::{EmailError, ReportMailer};
+use crate::fingerprint;
+use crate::i18n::Lang;
+use crate::lifecycle::{FindingState, Ledger, LifecycleError};
+use crate::models::{AnalysisResults, ConnectionSample, RankedSuggestion, SuggestionLevel};
+use crate::probes::{self, LastRun, Probes};
+use crate::reporter::{ReportFormat, Reporter};
+use crate::rules::Rules;
+use crate::signing::SigningKey;
+use crate::upload::{ReportUploader, UploadError};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use croner::errors::CronError;
+use croner::Cron;
+use serde::Serialize;
+use snafu::{ensure, ResultExt, Snafu};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::task::JoinSet;
+use tracing::{info, warn};
+
+#[derive(Debug, Snafu)]
+pub enum SchedulerError {
+    #[snafu(display("No config entries have a schedule"))]
+    NoSchedules,
+
+    #[snafu(display("Invalid schedule '{}' for {}: {}", schedule, database, source))]
+    InvalidSchedule {
+        database: String,
+        schedule: String,
+        source: CronError,
+    },
+
+    #[snafu(display("Analysis failed: {}", source))]
+    Analysis { source: CheckerError },
+
+    #[snafu(display("Failed to write snapshot {}: {}", path.display(), source))]
+    SnapshotWrite {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Failed to serialize snapshot: {}", source))]
+    SnapshotSerialize { source: serde_json::Error },
+
+    #[snafu(display("Webhook request failed: {}", source))]
+    Webhook { source: reqwest::Error },
+
+    #[snafu(display("Report email not sent: {}", source))]
+    Email { source: EmailError },
+
+    #[snafu(display("Report not uploaded: {}", source))]
+    Upload { source: UploadError },
+
+    #[snafu(display("Probe endpoints unavailable: {}", source))]
+    ProbeServer { source: ApiError },
+
+    #[snafu(display("Finding lifecycle not updated: {}", source))]
+    Lifecycle { source: LifecycleError },
+}
+
+type Result<T, E = SchedulerError> = std::result::Result<T, E>;
+
+/// Snapshots this recent feed their connection samples into each run
+const CONNECTION_HISTORY_DAYS: i64 = 7;
+
+pub struct SchedulerOptions {
+    /// Each scheduled entry writes its results under a subdirectory of this
+    pub snapshot_dir: PathBuf,
+    /// Receives a JSON POST when a run finds Critical suggestions the previous
+    /// snapshot did not have
+    pub webhook_url: Option<String>,
+    /// Emails the report of every run, Critical findings or not
+    pub mailer: Option<ReportMailer>,
+    /// Archives the HTML and JSON reports of every run
+    pub uploader: Option<ReportUploader>,
+    /// Signs the JSON reports the scheduler hands out
+    pub signing_key: Option<SigningKey>,
+    /// Replace literals and object names in reported query text
+    pub redact_queries: bool,
+    pub lang: Lang,
+    /// Severity overrides, applied before Critical suggestions are compared
+    pub rules: Rules,
+    /// Address to serve `/healthz`, `/readyz` and `/last-run` on
+    pub probe_listen: Option<String>,
+}
+
+/// Payload POSTed to the webhook; `text` makes it usable as a Slack-style
+/// incoming webhook as-is
+#[derive(Debug, Serialize)]
+pub struct CriticalFindingsAlert {
+    pub text: String,
+    pub database: String,
+    pub host: String,
+    pub snapshot: PathBuf,
+    pub findings: Vec<RankedSuggestion>,
+}
+
+struct Job {
+    config: DbConfig,
+    cron: Cron,
+    snapshots: SnapshotStore,
+}
+
+/// Timestamped JSON results of one config entry, oldest first by file name,
+/// next to the [`Ledger`] of the findings they reported
+struct SnapshotStore {
+    dir: PathBuf,
+}
+
+/// Runs every entry that has a `schedule` on its cadence until the process is stopped
+pub async fn run(configs: Vec<DbConfig>, opts: SchedulerOptions) -> Result<()> {
+    let mut jobs = Vec::new();
+    for config in configs {
+        let Some(schedule) = config.schedule.clone() else {
+            continue;
+        };
+        let cron = Cron::new(&schedule).parse().context(InvalidScheduleSnafu {
+            database: config.database.clone(),
+            schedule: schedule.clone(),
+        })?;
+        let snapshots = SnapshotStore::for_config(&opts.snapshot_dir, &config);
+        jobs.push(Job {
+            config,
+            cron,
+            snapshots,
+        });
+    }
+    ensure!(!jobs.is_empty(), NoSchedulesSnafu);
+
+    let probes = Probes::default();
+    let mut tasks = JoinSet::new();
+    if let Some(listen) = &opts.probe_listen {
+        let listener = api::bind(listen).await.context(ProbeServerSnafu)?;
+        let router = probes::router(probes.clone());
+        tasks.spawn(async move {
+            if let Err(err) = api::serve_on(listener, router).await {
+                warn!("Probe endpoints stopped: {}", err);
+            }
+        });
+    }
+
+    let opts = Arc::new(opts);
+    let client = reqwest::Client::new();
+    for job in jobs {
+        tasks.spawn(job.run(opts.clone(), client.clone(), probes.clone()));
+    }
+    probes.set_ready();
+    while tasks.join_next().await.is_some() {}
+    Ok(())
+}
+
+impl Job {
+    async fn run(self, opts: Arc<SchedulerOptions>, client: reqwest::Client, probes: Probes) {
+        loop {
+            let now = Utc::now();
+            let next = match self.cron.find_next_occurrence(&now, false) {
+                Ok(next) => next,
+                Err(err) => {
+                    warn!(
+                        "No next run for {} on '{}': {}",
+                        self.config.database,
+                        self.cron.pattern.to_string(),
+                        err
+                    );
+                    return;
+                }
+            };
+            info!("Next analysis of {} at {}", self.config.database, next);
+            tokio::time::sleep((next - now).to_std().unwrap_or_default()).await;
+
+            let started_at = Utc::now();
+            let outcome = self.run_once(&opts, &client).await;
+            if let Err(err) = &outcome {
+                warn!(
+                    "Scheduled analysis of {} failed: {}",
+                    self.config.database, err
+                );
+            }
+            probes.record(LastRun::finished(
+                &self.config,
+                started_at,
+                outcome.as_ref().map_err(ToString::to_string),
+            ));
+        }
+    }
+
+    async fn run_once(
+        &self,
+        opts: &SchedulerOptions,
+        client: &reqwest::Client,
+    ) -> Result<AnalysisResults> {
+        info!("Running scheduled analysis of {}", self.config.database);
+        let mut checker = ConfigChecker::new(self.config.clone())
+            .await
+            .context(AnalysisSnafu)?;
+        let opts_analyze = AnalyzeOptions {
+            connection_history: self
+                .snapshots
+                .connection_history(Utc::now() - chrono::Duration::days(CONNECTION_HISTORY_DAYS)),
+            redact_queries: opts.redact_queries,
+            ..AnalyzeOptions::default()
+        };
+        let mut results = checker
+            .analyze(&opts_analyze)
+            .await
+            .context(AnalysisSnafu)?;
+        opts.rules.apply(&mut results);
+        results.localize(opts.lang);
+
+        let now = Utc::now();
+        let mut ledger = Ledger::open(&self.snapshots.dir).context(LifecycleSnafu)?;
+        ledger.observe(&results, now);
+        results.finding_lifecycle = ledger.findings();
+
+        let previous = self.snapshots.latest();
+        let snapshot = self.snapshots.write(&results, now)?;
+        ledger.save().context(LifecycleSnafu)?;
+        self.alert(
+            opts.webhook_url.as_deref(),
+            client,
+            previous,
+            &results,
+            snapshot,
+        )
+        .await;
+
+        if let Some(mailer) = &opts.mailer {
+            mailer
+                .send(&self.config, &results, now)
+                .await
+                .context(EmailSnafu)?;
+            info!("Emailed the report of {}", self.config.database);
+        }
+        if let Some(uploader) = &opts.uploader {
+            let keys = uploader
+                .upload(
+                    &self.config,
+                    &results,
+                    &Reporter::new(ReportFormat::Html).with_signing_key(opts.signing_key.clone()),
+                    ReportFormat::Html,
+                    now,
+                )
+                .await
+                .context(UploadSnafu)?;
+            info!(
+                "Uploaded {} objects for {}",
+                keys.len(),
+                self.config.database
+            );
+        }
+        Ok(results)
+    }
+
+    /// Alerts on the Critical findings that are new since `previous` or whose
+    /// alert was not delivered before. A failed delivery is logged and its
+    /// findings kept for the next run, so the rest of the run still happens.
+    async fn alert(
+        &self,
+        webhook_url: Option<&str>,
+        client: &reqwest::Client,
+        previous: Option<AnalysisResults>,
+        results: &AnalysisResults,
+        snapshot: PathBuf,
+    ) {
+        let undelivered = self.snapshots.undelivered();
+        let previous = previous.map(|mut previous| {
+            for suggestions in previous.suggestions_by_category.values_mut() {
+                suggestions.retain(|suggestion| !undelivered.contains(&suggestion.fingerprint));
+            }
+            previous
+        });
+        let findings = new_critical_findings(previous.as_ref(), results);
+        let mut pending = Vec::new();
+        if !findings.is_empty() {
+            let alert = CriticalFindingsAlert::new(&self.config, snapshot, findings);
+            warn!("{}", alert.text);
+            if let Some(url) = webhook_url {
+                let sent = client
+                    .post(url)
+                    .json(&alert)
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .context(WebhookSnafu);
+                if let Err(err) = sent {
+                    warn!("{}; retrying on the next run", err);
+                    pending = alert
+                        .findings
+                        .iter()
+                        .map(|finding| finding.suggestion.fingerprint.clone())
+                        .collect();
+                }
+            }
+        }
+        if let Err(err) = self.snapshots.set_undelivered(&pending) {
+            warn!("{}", err);
+        }
+    }
+}
+
+/// Fingerprints of the Critical findings whose webhook alert failed, kept next
+/// to the snapshots until it is delivered
+const UNDELIVERED_FILE: &str = "undelivered-alerts.json";
+
+/// File stem of a snapshot, the UTC time it was taken
+const SNAPSHOT_STEM: &str = "%Y%m%dT%H%M%SZ";
+
+impl SnapshotStore {
+    fn for_config(root: &Path, config: &DbConfig) -> Self {
+        let name: String = format!("{}@{}", config.database, config.host)
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || "@._-".contains(c) {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        Self {
+            dir: root.join(name),
+        }
+    }
+
+    fn write(&self, results: &AnalysisResults, at: DateTime<Utc>) -> Result<PathBuf> {
+        let path = self.dir.join(format!("{}.json", at.format(SNAPSHOT_STEM)));
+        let json = serde_json::to_vec_pretty(results).context(SnapshotSerializeSnafu)?;
+        fs::create_dir_all(&self.dir).context(SnapshotWriteSnafu { path: &self.dir })?;
+        fs::write(&path, json).context(SnapshotWriteSnafu { path: &path })?;
+        Ok(path)
+    }
+
+    fn undelivered(&self) -> HashSet<String> {
+        fs::read(self.dir.join(UNDELIVERED_FILE))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    /// Replaces the undelivered fingerprints; an empty list removes the file
+    fn set_undelivered(&self, fingerprints: &[String]) -> Result<()> {
+        let path = self.dir.join(UNDELIVERED_FILE);
+        if fingerprints.is_empty() {
+            return match fs::remove_file(&path) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                    Err(err).context(SnapshotWriteSnafu { path })
+                }
+                _ => Ok(()),
+            };
+        }
+        let json = serde_json::to_vec(fingerprints).context(SnapshotSerializeSnafu)?;
+        fs::write(&path, json).context(SnapshotWriteSnafu { path: &path })
+    }
+
+    /// Snapshot files, oldest first. Other files sharing the directory, such
+    /// as the findings ledger, are left out.
+    fn snapshots(&self) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension().is_some_and(|ext| ext == "json")
+                    && path
+                        .file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .is_some_and(|stem| {
+                            NaiveDateTime::parse_from_str(stem, SNAPSHOT_STEM).is_ok()
+                        })
+            })
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    /// Connection samples of the snapshots taken since `since`, for the
+    /// time-of-day saturation pattern
+    fn connection_history(&self, since: DateTime<Utc>) -> Vec<ConnectionSample> {
+        let cutoff = self
+            .dir
+            .join(format!("{}.json", since.format(SNAPSHOT_STEM)));
+        self.snapshots()
+            .into_iter()
+            .filter(|path| *path >= cutoff)
+            .filter_map(|path| fs::read(path).ok())
+            .filter_map(|bytes| serde_json::from_slice::<AnalysisResults>(&bytes).ok())
+            .flat_map(|results| results.system_stats.connection_samples)
+            .collect()
+    }
+
+    /// The most recent snapshot; unreadable ones are treated as missing so a
+    /// corrupt file cannot stop the schedule
+    fn latest(&self) -> Option<AnalysisResults> {
+        let path = self.snapshots().pop()?;
+        let parsed = fs::read(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|bytes| serde_json::from_slice(&bytes).map_err(|err| err.to_string()));
+        match parsed {
+            Ok(mut results) => {
+                fingerprint::assign(&mut results);
+                Some(results)
+            }
+            Err(err) => {
+                warn!("Ignoring unreadable snapshot {}: {}", path.display(), err);
+                None
+            }
+        }
+    }
+}
+
+/// Critical suggestions in `current` whose fingerprint was not already
+/// Critical in `previous`, leaving out acknowledged ones
+pub fn new_critical_findings(
+    previous: Option<&AnalysisResults>,
+    current: &AnalysisResults,
+) -> Vec<RankedSuggestion> {
+    let known: HashSet<_> = previous
+        .map(|previous| {
+            previous
+                .ranked_suggestions()
+                .into_iter()
+                .filter(|(_, suggestion)| suggestion.level == SuggestionLevel::Critical)
+                .map(|(_, suggestion)| suggestion.fingerprint.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+    let acknowledged: HashSet<_> = current
+        .finding_lifecycle
+        .iter()
+        .filter(|finding| finding.state() == FindingState::Acknowledged)
+        .map(|finding| &finding.fingerprint)
+        .collect();
+
+    current
+        .ranked_suggestions()
+        .into_iter()
+        .filter(|(_, suggestion)| {
+            suggestion.level == SuggestionLevel::Critical
+                && !known.contains(&suggestion.fingerprint)
+                && !acknowledged.contains(&suggestion.fingerprint)
+        })
+        .map(|(category, suggestion)| RankedSuggestion {
+            category,
+            suggestion: suggestion.clone(),
+        })
+        .collect()
+}
+
+impl CriticalFindingsAlert {
+    fn new(config: &DbConfig, snapshot: PathBuf, findings: Vec<RankedSuggestion>) -> Self {
+        let parameters: Vec<_> = findings
+            .iter()
+            .map(|finding| finding.suggestion.parameter.as_str())
+            .collect();
+        Self {
+            text: format!(
+                "PostGreat: {} new critical finding{} on {} ({}): {}",
+                findings.len(),
+                if findings.len() == 1 { "" } else { "s" },
+                config.database,
+                config.host,
+                parameters.join(", ")
+            ),
+            database: config.database.clone(),
+            host: config.host.clone(),
+            snapshot,
+            findings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConfigCategory, ConfigSuggestion};
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+
+    fn results(findings: &[(ConfigCategory, &str, SuggestionLevel)]) -> AnalysisResults {
+        let mut results = AnalysisResults::default();
+        for (category, parameter, level) in findings {
+            results
+                .suggestions_by_category
+                .entry(*category)
+                .or_default()
+                .push(ConfigSuggestion {
+                    parameter: parameter.to_string(),
+                    current_value: "1".to_string(),
+                    suggested_value: "2".to_string(),
+                    level: *level,
+                    rationale: String::new(),
+                    messages: Vec::new(),
+                    fingerprint: String::new(),
+                });
+        }
+        fingerprint::assign(&mut results);
+        results
+    }
+
+    #[test]
+    fn only_critical_findings_missing_from_the_previous_run_are_new() {
+        let previous = results(&[
+            (
+                ConfigCategory::Memory,
+                "shared_buffers",
+                SuggestionLevel::Critical,
+            ),
+            (
+                ConfigCategory::Concurrency,
+                "max_connections",
+                SuggestionLevel::Important,
+            ),
+        ]);
+        let current = results(&[
+            (
+                ConfigCategory::Memory,
+                "shared_buffers",
+                SuggestionLevel::Critical,
+            ),
+            (
+                ConfigCategory::Concurrency,
+                "max_connections",
+                SuggestionLevel::Critical,
+            ),
+            (
+                ConfigCategory::Logging,
+                "log_min_duration_statement",
+                SuggestionLevel::Recommended,
+            ),
+        ]);
+
+        let new: Vec<_> = new_critical_findings(Some(&previous), &current)
+            .into_iter()
+            .map(|finding| finding.suggestion.parameter)
+            .collect();
+        assert_eq!(new, ["max_connections"]);
+        assert_eq!(new_critical_findings(None, &current).len(), 2);
+
+        let alert = CriticalFindingsAlert::new(
+            &DbConfig::for_tests("db1.example.com", "production_db"),
+            PathBuf::from("snapshot.json"),
+            new_critical_findings(Some(&previous), &current),
+        );
+        assert_eq!(
+            alert.text,
+            "PostGreat: 1 new critical finding on production_db (db1.example.com): max_connections"
+        );
+    }
+
+    #[test]
+    fn acknowledged_critical_findings_do_not_alert() {
+        let mut current = results(&[
+            (
+                ConfigCategory::Memory,
+                "shared_buffers",
+                SuggestionLevel::Critical,
+            ),
+            (
+                ConfigCategory::Concurrency,
+                "max_connections",
+                SuggestionLevel::Critical,
+            ),
+        ]);
+        let at = Utc.with_ymd_and_hms(2026, 10, 16, 3, 0, 0).unwrap();
+        let mut ledger = Ledger::default();
+        ledger.observe(&current, at);
+        let shared_buffers = current.suggestions_by_category[&ConfigCategory::Memory][0]
+            .fingerprint
+            .clone();
+        ledger
+            .acknowledge(&shared_buffers, "alice", "Sized for the cache", at)
+            .unwrap();
+        current.finding_lifecycle = ledger.findings();
+
+        let new: Vec<_> = new_critical_findings(None, &current)
+            .into_iter()
+            .map(|finding| finding.suggestion.parameter)
+            .collect();
+        assert_eq!(new, ["max_connections"]);
+    }
+
+    #[test]
+    fn latest_snapshot_is_the_most_recent_file() {
+        let root = tempdir().unwrap();
+        let store = SnapshotStore::for_config(
+            root.path(),
+            &DbConfig::for_tests("db1.example.com", "production_db"),
+        );
+        assert!(store.latest().is_none());
+
+        let first = results(&[(
+            ConfigCategory::Memory,
+            "work_mem",
+            SuggestionLevel::Critical,
+        )]);
+        let second = results(&[]);
+        let at = Utc.with_ymd_and_hms(2026, 10, 16, 3, 0, 0).unwrap();
+        store.write(&first, at).unwrap();
+        let path = store
+            .write(&second, at + chrono::Duration::days(1))
+            .unwrap();
+
+        assert!(path.ends_with("production_db@db1.example.com/20261017T030000Z.json"));
+        assert!(store.latest().unwrap().suggestions_by_category.is_empty());
+    }
+
+    #[test]
+    fn the_findings_ledger_is_not_a_snapshot() {
+        let root = tempdir().unwrap();
+        let store = SnapshotStore::for_config(
+            root.path(),
+            &DbConfig::for_tests("db1.example.com", "production_db"),
+        );
+        let at = Utc.with_ymd_and_hms(2026, 10, 16, 3, 0, 0).unwrap();
+        store.write(&results(&[]), at).unwrap();
+        store
+            .write(
+                &results(&[(
+                    ConfigCategory::Memory,
+                    "work_mem",
+                    SuggestionLevel::Critical,
+                )]),
+                at + chrono::Duration::days(1),
+            )
+            .unwrap();
+        fs::write(store.dir.join(crate::lifecycle::LEDGER_FILE), "{}").unwrap();
+
+        assert_eq!(store.snapshots().len(), 2);
+        let latest = store.latest().unwrap();
+        assert!(latest
+            .suggestions_by_category
+            .contains_key(&ConfigCategory::Memory));
+    }
+
+    #[tokio::test]
+    async fn an_undelivered_alert_is_sent_on_the_next_run() {
+        use axum::http::StatusCode;
+        use axum::routing::post;
+        use std::sync::Mutex;
+
+        // The first POST fails, the later ones succeed
+        let received: Arc<Mutex<Vec<String>>> = Arc::default();
+        let router = axum::Router::new().route(
+            "/",
+            post({
+                let received = received.clone();
+                move |body: String| async move {
+                    let mut received = received.lock().unwrap();
+                    received.push(body);
+                    match received.len() {
+                        1 => StatusCode::INTERNAL_SERVER_ERROR,
+                        _ => StatusCode::OK,
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let root = tempdir().unwrap();
+        let config = DbConfig::for_tests("db1.example.com", "production_db");
+        let job = Job {
+            cron: Cron::new("0 3 * * *").parse().unwrap(),
+            snapshots: SnapshotStore::for_config(root.path(), &config),
+            config,
+        };
+        let client = reqwest::Client::new();
+        let critical = results(&[(
+            ConfigCategory::Memory,
+            "work_mem",
+            SuggestionLevel::Critical,
+        )]);
+        let at = Utc.with_ymd_and_hms(2026, 10, 16, 3, 0, 0).unwrap();
+        for day in 0..3 {
+            let previous = job.snapshots.latest();
+            let snapshot = job
+                .snapshots
+                .write(&critical, at + chrono::Duration::days(day))
+                .unwrap();
+            job.alert(Some(&url), &client, previous, &critical, snapshot)
+                .await;
+        }
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 2, "{received:?}");
+        assert!(received[1].contains("work_mem"));
+        assert!(job.snapshots.undelivered().is_empty());
+    }
+}
@@ -0,0 +1,226 @@
+use crate::models::{AnalysisResults, ConfigCategory, SuggestionLevel};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Deduction for one suggestion at `level`, before the category's impact
+fn level_weight(level: SuggestionLevel) -> f64 {
+    match level {
+        SuggestionLevel::Critical => 25.0,
+        SuggestionLevel::Important => 10.0,
+        SuggestionLevel::Recommended => 3.0,
+        SuggestionLevel::Info => 0.0,
+    }
+}
+
+/// How much a category's findings count towards the overall score: data loss
+/// and exposure weigh more than diagnostics and style
+fn category_impact(category: ConfigCategory) -> f64 {
+    match category {
+        ConfigCategory::Backup
+        | ConfigCategory::Security
+        | ConfigCategory::Replication
+        | ConfigCategory::Version => 1.5,
+        ConfigCategory::Memory
+        | ConfigCategory::Concurrency
+        | ConfigCategory::Wal
+        | ConfigCategory::Planner
+        | ConfigCategory::Autovacuum
+        | ConfigCategory::TableIndex
+        | ConfigCategory::Workload
+        | ConfigCategory::OperatingSystem
+        | ConfigCategory::Host => 1.0,
+        ConfigCategory::Logging | ConfigCategory::Extensions | ConfigCategory::Schema => 0.5,
+    }
+}
+
+/// Letter grade for a score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Grade {
+    A,
+    B,
+    C,
+    D,
+    F,
+}
+
+impl Grade {
+    pub fn from_score(score: u8) -> Self {
+        match score {
+            90.. => Grade::A,
+            80..=89 => Grade::B,
+            70..=79 => Grade::C,
+            60..=69 => Grade::D,
+            _ => Grade::F,
+        }
+    }
+}
+
+impl fmt::Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let grade = match self {
+            Grade::A => "A",
+            Grade::B => "B",
+            Grade::C => "C",
+            Grade::D => "D",
+            Grade::F => "F",
+        };
+        f.write_str(grade)
+    }
+}
+
+/// Score of one category's suggestions, without the category's impact
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CategoryScore {
+    pub category: ConfigCategory,
+    pub score: u8,
+    pub suggestions: usize,
+}
+
+/// 0–100 health score of one database, 100 meaning no suggestions above Info
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthScore {
+    pub score: u8,
+    pub grade: Grade,
+    /// Categories with suggestions, lowest score first; the others score 100
+    pub categories: Vec<CategoryScore>,
+}
+
+impl Default for HealthScore {
+    fn default() -> Self {
+        Self {
+            score: 100,
+            grade: Grade::A,
+            categories: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for HealthScore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/100 ({})", self.score, self.grade)
+    }
+}
+
+/// Scores the suggestions after severity overrides. Deductions add up per
+/// suggestion and map to `100 × 100 / (100 + deductions)`, so every finding
+/// lowers the score but no number of them reaches zero.
+pub fn score(results: &AnalysisResults) -> HealthScore {
+    let mut overall = 0.0;
+    let mut by_category: HashMap<ConfigCategory, (f64, usize)> = HashMap::new();
+    for (category, suggestion) in results.ranked_suggestions() {
+        let weight = level_weight(suggestion.level);
+        overall += weight * category_impact(category);
+        let entry = by_category.entry(category).or_default();
+        entry.0 += weight;
+        entry.1 += 1;
+    }
+
+    let mut categories: Vec<_> = by_category
+        .into_iter()
+        .map(|(category, (deductions, suggestions))| CategoryScore {
+            category,
+            score: from_deductions(deductions),
+            suggestions,
+        })
+        .collect();
+    categories.sort_by_key(|category| (category.score, category.category.as_str()));
+
+    let score = from_deductions(overall);
+    HealthScore {
+        score,
+        grade: Grade::from_score(score),
+        categories,
+    }
+}
+
+fn from_deductions(deductions: f64) -> u8 {
+    (100.0 * 100.0 / (100.0 + deductions)).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ConfigSuggestion;
+
+    fn results(suggestions: &[(ConfigCategory, SuggestionLevel)]) -> AnalysisResults {
+        let mut results = AnalysisResults::default();
+        for (index, (category, level)) in suggestions.iter().enumerate() {
+            results
+                .suggestions_by_category
+                .entry(*category)
+                .or_default()
+                .push(ConfigSuggestion {
+                    parameter: format!("param_{index}"),
+                    current_value: "1".to_string(),
+                    suggested_value: "2".to_string(),
+                    level: *level,
+                    rationale: String::new(),
+                    messages: Vec::new(),
+                    fingerprint: String::new(),
+                });
+        }
+        results
+    }
+
+    #[test]
+    fn no_findings_or_only_info_score_full_marks() {
+        let clean = score(&AnalysisResults::default());
+        assert_eq!((clean.score, clean.grade), (100, Grade::A));
+        assert!(clean.categories.is_empty());
+
+        let info = score(&results(&[(
+            ConfigCategory::Logging,
+            SuggestionLevel::Info,
+        )]));
+        assert_eq!(info.score, 100);
+        assert_eq!(info.categories[0].suggestions, 1);
+    }
+
+    #[test]
+    fn severity_and_category_impact_lower_the_score() {
+        let memory = score(&results(&[(
+            ConfigCategory::Memory,
+            SuggestionLevel::Critical,
+        )]));
+        let backup = score(&results(&[(
+            ConfigCategory::Backup,
+            SuggestionLevel::Critical,
+        )]));
+        let logging = score(&results(&[(
+            ConfigCategory::Logging,
+            SuggestionLevel::Critical,
+        )]));
+        assert_eq!(memory.score, 80);
+        assert_eq!(backup.score, 73);
+        assert_eq!(logging.score, 89);
+        // Subscores leave the impact out
+        assert_eq!(backup.categories[0].score, 80);
+    }
+
+    #[test]
+    fn categories_are_listed_worst_first() {
+        let health = score(&results(&[
+            (ConfigCategory::Wal, SuggestionLevel::Recommended),
+            (ConfigCategory::Memory, SuggestionLevel::Important),
+            (ConfigCategory::Memory, SuggestionLevel::Important),
+            (ConfigCategory::Security, SuggestionLevel::Critical),
+        ]));
+        let categories: Vec<_> = health
+            .categories
+            .iter()
+            .map(|category| (category.category, category.score))
+            .collect();
+        assert_eq!(
+            categories,
+            [
+                (ConfigCategory::Security, 80),
+                (ConfigCategory::Memory, 83),
+                (ConfigCategory::Wal, 97),
+            ]
+        );
+        assert_eq!(health.score, 62);
+        assert_eq!(health.grade, Grade::D);
+        assert_eq!(health.to_string(), "62/100 (D)");
+    }
+}
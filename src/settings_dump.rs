@@ -0,0 +1,257 @@
+use crate::models::{unit_bytes, unit_seconds, ParamValue, PgConfigParam};
+use snafu::{ensure, ResultExt, Snafu};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Snafu)]
+pub enum SettingsDumpError {
+    #[snafu(display("Failed to read settings dump {}: {}", path.display(), source))]
+    DumpRead {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Failed to parse settings dump {}: {}", path.display(), source))]
+    DumpParse { path: PathBuf, source: csv::Error },
+
+    #[snafu(display(
+        "No settings found in {}; expected a pg_settings CSV export or SHOW ALL output",
+        path.display()
+    ))]
+    EmptyDump { path: PathBuf },
+}
+
+type Result<T, E = SettingsDumpError> = std::result::Result<T, E>;
+
+/// `SELECT * FROM pg_settings` column order, used when a CSV export has no header
+const PG_SETTINGS_COLUMNS: &[&str] = &[
+    "name",
+    "setting",
+    "unit",
+    "category",
+    "short_desc",
+    "extra_desc",
+    "context",
+    "vartype",
+    "source",
+    "min_val",
+    "max_val",
+    "enumvals",
+    "boot_val",
+    "reset_val",
+    "sourcefile",
+    "sourceline",
+    "pending_restart",
+];
+
+/// `SHOW ALL` column order
+const SHOW_ALL_COLUMNS: &[&str] = &["name", "setting", "description"];
+
+/// Reads settings exported from a server the tool cannot connect to. Accepts
+/// `COPY (SELECT * FROM pg_settings) TO ... CSV`, with or without `HEADER`,
+/// `SHOW ALL` written as CSV, and psql's aligned or unaligned table output of
+/// either query.
+pub fn load_settings_dump(path: &Path) -> Result<HashMap<String, PgConfigParam>> {
+    let content = fs::read_to_string(path).context(DumpReadSnafu { path })?;
+    let rows = if is_psql_table(&content) {
+        psql_rows(&content)
+    } else {
+        csv_rows(&content).context(DumpParseSnafu { path })?
+    };
+    let params = params_from_rows(rows);
+    ensure!(!params.is_empty(), EmptyDumpSnafu { path });
+    Ok(params)
+}
+
+/// psql tables start with a `name | setting | ...` header
+fn is_psql_table(content: &str) -> bool {
+    content
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .and_then(|line| line.split('|').next())
+        .is_some_and(|first| first.trim() == "name" && content.contains('|'))
+}
+
+fn psql_rows(content: &str) -> Vec<Vec<String>> {
+    content
+        .lines()
+        .map(str::trim)
+        // Skip the `-----+-----` rule and the `(353 rows)` footer
+        .filter(|line| !line.is_empty() && !line.starts_with('-') && !line.starts_with('('))
+        .map(|line| {
+            line.split('|')
+                .map(|field| field.trim().to_string())
+                .collect()
+        })
+        .collect()
+}
+
+fn csv_rows(content: &str) -> std::result::Result<Vec<Vec<String>>, csv::Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    reader
+        .records()
+        .map(|record| record.map(|record| record.iter().map(str::to_string).collect()))
+        .collect()
+}
+
+/// Maps rows to parameters, by header when the first row is one and by the
+/// column order of pg_settings or `SHOW ALL` otherwise
+fn params_from_rows(rows: Vec<Vec<String>>) -> HashMap<String, PgConfigParam> {
+    let mut rows = rows.into_iter().peekable();
+    let header: Vec<String> = match rows.peek() {
+        Some(first) if first.first().is_some_and(|name| name == "name") => {
+            rows.next().unwrap_or_default()
+        }
+        Some(first) if first.len() >= PG_SETTINGS_COLUMNS.len() => PG_SETTINGS_COLUMNS
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        _ => SHOW_ALL_COLUMNS
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+    };
+    let column = |name: &str| header.iter().position(|column| column == name);
+    let (Some(name_col), Some(setting_col)) = (column("name"), column("setting")) else {
+        return HashMap::new();
+    };
+    let has_vartype = column("vartype").is_some();
+
+    let mut params = HashMap::new();
+    for row in rows {
+        let field = |index: Option<usize>| {
+            index
+                .and_then(|index| row.get(index))
+                .filter(|value| !value.is_empty())
+                .cloned()
+        };
+        let (Some(name), Some(setting)) = (field(Some(name_col)), field(Some(setting_col))) else {
+            continue;
+        };
+
+        let (current_value, unit, value) = if has_vartype {
+            let unit = field(column("unit"));
+            let vartype = field(column("vartype")).unwrap_or_default();
+            let value = ParamValue::parse(&setting, &vartype, unit.as_deref());
+            (setting, unit, value)
+        } else {
+            parse_show_value(&setting)
+        };
+        let param = PgConfigParam {
+            name: name.clone(),
+            current_value,
+            default_value: field(column("boot_val")),
+            unit,
+            context: field(column("context")).unwrap_or_default(),
+            value,
+            source: field(column("source")),
+            sourcefile: field(column("sourcefile")),
+            pending_restart: field(column("pending_restart"))
+                .is_some_and(|value| matches!(value.as_str(), "t" | "true" | "on")),
+            min_value: field(column("min_val")),
+            max_value: field(column("max_val")),
+        };
+        params.insert(name, param);
+    }
+    params
+}
+
+/// `SHOW ALL` prints values with their unit, e.g. `128MB` or `5min`; the unit
+/// is split off so the value reads like a pg_settings row in that unit
+fn parse_show_value(value: &str) -> (String, Option<String>, ParamValue) {
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .unwrap_or(value.len());
+    let (number, suffix) = value.split_at(split);
+    if !number.is_empty() && (unit_bytes(suffix).is_some() || unit_seconds(suffix).is_some()) {
+        let parsed = ParamValue::parse(number, "", Some(suffix));
+        return (number.to_string(), Some(suffix.to_string()), parsed);
+    }
+    let vartype = if matches!(value, "on" | "off") {
+        "bool"
+    } else {
+        ""
+    };
+    (
+        value.to_string(),
+        None,
+        ParamValue::parse(value, vartype, None),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::time::Duration;
+
+    const MB: u64 = 1024 * 1024;
+
+    #[test]
+    fn reads_pg_settings_csv_by_header() {
+        let csv = "\
+name,setting,unit,category,short_desc,extra_desc,context,vartype,source,min_val,max_val,enumvals,boot_val,reset_val,sourcefile,sourceline,pending_restart
+shared_buffers,16384,8kB,Resource Usage / Memory,\"Sets the number of shared memory buffers used by the server.\",,postmaster,integer,configuration file,16,1073741823,,1024,16384,/etc/postgresql/postgresql.conf,120,t
+jit,on,,Query Tuning / Other Planner Options,\"Allow JIT compilation.\",,user,bool,default,,,,on,on,,,f
+";
+        let params = params_from_rows(csv_rows(csv).unwrap());
+        let shared_buffers = &params["shared_buffers"];
+        assert_eq!(shared_buffers.value.as_bytes(), Some(128 * MB));
+        assert_eq!(shared_buffers.context, "postmaster");
+        assert_eq!(shared_buffers.default_value.as_deref(), Some("1024"));
+        assert_eq!(shared_buffers.max_value.as_deref(), Some("1073741823"));
+        assert!(shared_buffers.pending_restart);
+        assert_eq!(params["jit"].value.as_bool(), Some(true));
+    }
+
+    #[test]
+    fn headerless_export_uses_pg_settings_column_order() {
+        let csv = "work_mem,4096,kB,Resource Usage / Memory,desc,,user,integer,default,64,2147483647,,4096,4096,,,f\n";
+        let params = params_from_rows(csv_rows(csv).unwrap());
+        assert_eq!(params["work_mem"].value.as_bytes(), Some(4 * MB));
+        assert_eq!(params["work_mem"].unit.as_deref(), Some("kB"));
+    }
+
+    #[test]
+    fn reads_show_all_from_psql_output() {
+        let output = "\
+          name           | setting |                 description
+-------------------------+---------+---------------------------------------------
+ checkpoint_timeout      | 5min    | Sets the maximum time between automatic WAL checkpoints.
+ shared_buffers          | 128MB   | Sets the number of shared memory buffers used by the server.
+ random_page_cost        | 4       | Sets the planner's estimate of the cost of a nonsequentially fetched disk page.
+ autovacuum              | on      | Starts the autovacuum subprocess.
+(4 rows)
+";
+        assert!(is_psql_table(output));
+        let params = params_from_rows(psql_rows(output));
+        assert_eq!(params.len(), 4);
+        assert_eq!(params["shared_buffers"].value.as_bytes(), Some(128 * MB));
+        assert_eq!(params["shared_buffers"].current_value, "128");
+        assert_eq!(
+            params["checkpoint_timeout"].value.as_duration(),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(params["autovacuum"].value.as_bool(), Some(true));
+    }
+
+    #[rstest]
+    #[case("8MB", "8", Some("MB"))]
+    #[case("200ms", "200", Some("ms"))]
+    #[case("-1", "-1", None)]
+    #[case("0.9", "0.9", None)]
+    #[case("replica", "replica", None)]
+    fn show_values_split_off_their_unit(
+        #[case] value: &str,
+        #[case] current: &str,
+        #[case] unit: Option<&str>,
+    ) {
+        let (current_value, parsed_unit, _) = parse_show_value(value);
+        assert_eq!(current_value, current);
+        assert_eq!(parsed_unit.as_deref(), unit);
+    }
+}
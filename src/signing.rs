@@ -0,0 +1,242 @@
+use crate::config::DbConfig;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use snafu::{ensure, OptionExt, ResultExt, Snafu};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Snafu)]
+pub enum SigningError {
+    #[snafu(display("Failed to read signing key {}: {}", path.display(), source))]
+    ReadKey {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Signing key {} is empty", path.display()))]
+    EmptyKey { path: PathBuf },
+
+    #[snafu(display("Report is not a JSON object"))]
+    NotAnObject,
+
+    #[snafu(display("Report has no `{}` signature", INTEGRITY_FIELD))]
+    Unsigned,
+
+    #[snafu(display("Unreadable `{}` signature: {}", INTEGRITY_FIELD, source))]
+    InvalidIntegrity { source: serde_json::Error },
+
+    #[snafu(display("Unsupported signature algorithm '{}'", algorithm))]
+    UnsupportedAlgorithm { algorithm: String },
+
+    #[snafu(display(
+        "Signature does not match: the report was changed after signing or signed with another key"
+    ))]
+    Mismatch,
+
+    #[snafu(display("Failed to serialize report: {}", source))]
+    Serialize { source: serde_json::Error },
+}
+
+type Result<T, E = SigningError> = std::result::Result<T, E>;
+
+/// Top-level field of a signed JSON report holding its [`Integrity`]
+pub const INTEGRITY_FIELD: &str = "integrity";
+
+/// The only algorithm reports are signed with
+pub const HMAC_SHA256: &str = "hmac-sha256";
+
+/// Who produced a report, where and about which server, so an archived
+/// report can be traced back to its run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunMetadata {
+    pub tool_version: String,
+    /// `git describe` of the build; absent for builds outside a git checkout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_describe: Option<String>,
+    /// Machine the analysis ran on
+    pub host: String,
+    pub generated_at: DateTime<Utc>,
+    /// SHA-256 of the analyzed server's `host:port/database`, identifying the
+    /// target without revealing its address
+    pub target_fingerprint: String,
+}
+
+impl RunMetadata {
+    pub fn capture(config: &DbConfig) -> Self {
+        let describe = env!("POSTGREAT_GIT_DESCRIBE");
+        Self {
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            git_describe: (!describe.is_empty()).then(|| describe.to_string()),
+            host: hostname::get()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|_| "unknown".to_string()),
+            generated_at: Utc::now(),
+            target_fingerprint: target_fingerprint(config),
+        }
+    }
+}
+
+pub fn target_fingerprint(config: &DbConfig) -> String {
+    let target = format!("{}:{}/{}", config.host, config.port, config.database);
+    format!("{:x}", Sha256::digest(target.as_bytes()))
+}
+
+/// Signature of a JSON report, over every other top-level field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Integrity {
+    pub algorithm: String,
+    /// Lower-case hex
+    pub signature: String,
+}
+
+/// Shared secret reports are signed and verified with
+#[derive(Clone)]
+pub struct SigningKey(Vec<u8>);
+
+impl std::fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SigningKey(..)")
+    }
+}
+
+impl SigningKey {
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Self(secret.into())
+    }
+
+    /// The file's bytes, without a trailing newline an editor may have added
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let mut secret = std::fs::read(path).context(ReadKeySnafu { path })?;
+        while secret
+            .last()
+            .is_some_and(|byte| matches!(byte, b'\n' | b'\r'))
+        {
+            secret.pop();
+        }
+        ensure!(!secret.is_empty(), EmptyKeySnafu { path });
+        Ok(Self(secret))
+    }
+
+    fn mac(&self, report: &Value) -> Result<Hmac<Sha256>> {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.0).expect("HMAC accepts keys of any length");
+        mac.update(&canonical(report)?);
+        Ok(mac)
+    }
+}
+
+/// Adds an HMAC-SHA256 `integrity` field to a JSON report, replacing any
+/// earlier one
+pub fn sign(report: &mut Value, key: &SigningKey) -> Result<()> {
+    let signature = key.mac(report)?.finalize().into_bytes();
+    let integrity = Integrity {
+        algorithm: HMAC_SHA256.to_string(),
+        signature: format!("{signature:x}"),
+    };
+    report.as_object_mut().context(NotAnObjectSnafu)?.insert(
+        INTEGRITY_FIELD.to_string(),
+        serde_json::to_value(integrity).context(SerializeSnafu)?,
+    );
+    Ok(())
+}
+
+/// Checks the `integrity` field of a JSON report against `key`, failing if
+/// any other field was added, removed or changed
+pub fn verify(report: &Value, key: &SigningKey) -> Result<()> {
+    let integrity = report
+        .as_object()
+        .context(NotAnObjectSnafu)?
+        .get(INTEGRITY_FIELD)
+        .context(UnsignedSnafu)?;
+    let integrity: Integrity =
+        serde_json::from_value(integrity.clone()).context(InvalidIntegritySnafu)?;
+    ensure!(
+        integrity.algorithm == HMAC_SHA256,
+        UnsupportedAlgorithmSnafu {
+            algorithm: integrity.algorithm
+        }
+    );
+    let signature = decode_hex(&integrity.signature).context(MismatchSnafu)?;
+    key.mac(report)?
+        .verify_slice(&signature)
+        .ok()
+        .context(MismatchSnafu)
+}
+
+/// Compact JSON of the report without its `integrity` field. Object keys
+/// serialize sorted, so a report re-read from disk gives the same bytes.
+fn canonical(report: &Value) -> Result<Vec<u8>> {
+    let mut unsigned = report.as_object().context(NotAnObjectSnafu)?.clone();
+    unsigned.remove(INTEGRITY_FIELD);
+    serde_json::to_vec(&unsigned).context(SerializeSnafu)
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn report() -> Value {
+        json!({
+            "params": {"shared_buffers": {"setting": "16384"}},
+            "suggestions_by_category": {"Memory": [{"parameter": "work_mem", "level": "Critical"}]},
+            "health": {"score": 72.5},
+        })
+    }
+
+    #[test]
+    fn signed_reports_verify_after_a_round_trip_through_text() {
+        let key = SigningKey::new("audit-secret");
+        let mut signed = report();
+        sign(&mut signed, &key).unwrap();
+        assert_eq!(signed[INTEGRITY_FIELD]["algorithm"], HMAC_SHA256);
+
+        let reread: Value =
+            serde_json::from_str(&serde_json::to_string_pretty(&signed).unwrap()).unwrap();
+        verify(&reread, &key).unwrap();
+    }
+
+    #[test]
+    fn changed_reports_and_other_keys_fail_verification() {
+        let key = SigningKey::new("audit-secret");
+        let mut signed = report();
+        sign(&mut signed, &key).unwrap();
+
+        let mut tampered = signed.clone();
+        tampered["suggestions_by_category"]["Memory"] = json!([]);
+        assert!(matches!(
+            verify(&tampered, &key),
+            Err(SigningError::Mismatch)
+        ));
+        assert!(matches!(
+            verify(&signed, &SigningKey::new("other-secret")),
+            Err(SigningError::Mismatch)
+        ));
+        assert!(matches!(
+            verify(&report(), &key),
+            Err(SigningError::Unsigned)
+        ));
+    }
+
+    #[test]
+    fn target_fingerprint_hides_the_address() {
+        let config = DbConfig::for_tests("db1.example.com", "production_db");
+        let metadata = RunMetadata::capture(&config);
+        assert_eq!(metadata.tool_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(metadata.target_fingerprint.len(), 64);
+        assert!(!metadata.target_fingerprint.contains("example"));
+        assert_eq!(metadata.target_fingerprint, target_fingerprint(&config));
+    }
+}
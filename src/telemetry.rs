@@ -0,0 +1,146 @@
+use crate::models::AnalysisResults;
+use crate::scoring;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::{global, KeyValue};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::trace::{SdkTracer, SdkTracerProvider};
+use opentelemetry_sdk::Resource;
+use snafu::{ResultExt, Snafu};
+use std::time::Duration;
+use tracing::warn;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::registry::LookupSpan;
+
+#[derive(Debug, Snafu)]
+pub enum TelemetryError {
+    #[snafu(display("Failed to build OTLP {} exporter: {}", signal, source))]
+    ExporterError {
+        signal: &'static str,
+        source: opentelemetry_otlp::ExporterBuildError,
+    },
+}
+
+/// OTLP export of the spans and metrics a run produces.
+///
+/// Metrics are recorded through the global meter, so the helpers below are
+/// no-ops until [`Telemetry::init`] has installed a provider.
+pub struct Telemetry {
+    tracer_provider: SdkTracerProvider,
+    meter_provider: SdkMeterProvider,
+}
+
+impl Telemetry {
+    /// Exports over OTLP/HTTP to a collector base URL such as `http://otel:4318`
+    pub fn init(endpoint: &str) -> Result<Self, TelemetryError> {
+        let endpoint = endpoint.trim_end_matches('/');
+        let resource = Resource::builder().with_service_name("postgreat").build();
+
+        let spans = opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .with_endpoint(format!("{endpoint}/v1/traces"))
+            .build()
+            .context(ExporterSnafu { signal: "trace" })?;
+        let tracer_provider = SdkTracerProvider::builder()
+            .with_resource(resource.clone())
+            .with_batch_exporter(spans)
+            .build();
+
+        let metrics = opentelemetry_otlp::MetricExporter::builder()
+            .with_http()
+            .with_endpoint(format!("{endpoint}/v1/metrics"))
+            .build()
+            .context(ExporterSnafu { signal: "metric" })?;
+        let meter_provider = SdkMeterProvider::builder()
+            .with_resource(resource)
+            .with_periodic_exporter(metrics)
+            .build();
+        global::set_meter_provider(meter_provider.clone());
+
+        Ok(Self {
+            tracer_provider,
+            meter_provider,
+        })
+    }
+
+    /// `tracing` layer that exports spans, such as the per-phase spans of
+    /// [`crate::checker::ConfigChecker::analyze`]
+    pub fn layer<S>(&self) -> OpenTelemetryLayer<S, SdkTracer>
+    where
+        S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+    {
+        tracing_opentelemetry::layer().with_tracer(self.tracer_provider.tracer("postgreat"))
+    }
+
+    /// Flushes buffered spans and metrics; call before the process exits
+    pub fn shutdown(self) {
+        if let Err(err) = self.tracer_provider.shutdown() {
+            warn!("Failed to flush OTLP traces: {err}");
+        }
+        if let Err(err) = self.meter_provider.shutdown() {
+            warn!("Failed to flush OTLP metrics: {err}");
+        }
+    }
+}
+
+/// Records how long one analysis phase took, in seconds
+pub(crate) fn record_phase(database: &str, phase: &'static str, elapsed: Duration) {
+    global::meter("postgreat")
+        .f64_histogram("postgreat.phase.duration")
+        .with_unit("s")
+        .with_description("Time spent in each analysis phase")
+        .build()
+        .record(
+            elapsed.as_secs_f64(),
+            &[
+                KeyValue::new("database", database.to_string()),
+                KeyValue::new("phase", phase),
+            ],
+        );
+}
+
+/// Records how long a catalog or statistics query took, in seconds
+pub(crate) fn record_query(database: &str, query: &'static str, elapsed: Duration) {
+    global::meter("postgreat")
+        .f64_histogram("postgreat.query.duration")
+        .with_unit("s")
+        .with_description("Time spent running catalog and statistics queries")
+        .build()
+        .record(
+            elapsed.as_secs_f64(),
+            &[
+                KeyValue::new("database", database.to_string()),
+                KeyValue::new("query", query),
+            ],
+        );
+}
+
+/// Counts the run's suggestions by level and category and records its health
+/// score
+pub(crate) fn record_findings(database: &str, results: &AnalysisResults) {
+    global::meter("postgreat")
+        .u64_gauge("postgreat.health_score")
+        .with_description("Health score of the last analysis run, 0-100")
+        .build()
+        .record(
+            u64::from(scoring::score(results).score),
+            &[KeyValue::new("database", database.to_string())],
+        );
+
+    let findings = global::meter("postgreat")
+        .u64_counter("postgreat.findings")
+        .with_description("Suggestions produced by an analysis run")
+        .build();
+    for (category, suggestions) in &results.suggestions_by_category {
+        for suggestion in suggestions {
+            findings.add(
+                1,
+                &[
+                    KeyValue::new("database", database.to_string()),
+                    KeyValue::new("level", suggestion.level.as_str()),
+                    KeyValue::new("category", category.as_str()),
+                ],
+            );
+        }
+    }
+}
@@ -0,0 +1,487 @@
+use crate::checker::{AnalyzeOptions, ConfigChecker};
+use crate::config::{DbConfig, StorageType, WorkloadType};
+use crate::models::{AnalysisResults, ConfigCategory, ConfigSuggestion};
+use serde_json::{json, Value};
+use sqlx::{
+    postgres::{PgConnectOptions, PgPoolOptions},
+    raw_sql, Pool, Postgres,
+};
+use std::env;
+use std::future::Future;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use testcontainers_modules::{
+    postgres,
+    testcontainers::{runners::SyncRunner, Container, ImageExt},
+};
+
+const TEMPLATE_DB: &str = "postgreat_template";
+const ADMIN_USER: &str = "postgres";
+const ADMIN_PASSWORD: &str = "postgres";
+const APP_USER: &str = "app_user";
+const APP_PASSWORD: &str = "app_password";
+const READER_USER: &str = "reader_user";
+const READER_PASSWORD: &str = "reader_password";
+
+/// Major versions started by [`versions`] unless the environment names others
+pub const DEFAULT_VERSIONS: &[&str] = &["14", "18"];
+/// Compute spec of [`TestPostgres::analyze`], matching the CLI test commands
+pub const TEST_COMPUTE: &str = "8vCPU-64GB";
+
+const FIXTURE_SCHEMA_SQL: &str = include_str!("../tests/_data/0-schema.sql");
+const FIXTURE_DATA_SQL: &str = include_str!("../tests/_data/1-data.sql");
+const FIXTURE_BLOAT_SQL: &str = include_str!("../tests/_data/2-bloat-and-indexes.sql");
+const FIXTURE_ROLES_SQL: &str = include_str!("../tests/_data/00-extensions-and-roles.sql");
+const FIXTURE_WORKLOAD_SQL: &str = include_str!("../tests/_data/3-workload.sql");
+
+#[derive(Debug, Clone, Copy)]
+pub enum ContainerProfile {
+    WorkloadEnabled,
+    WorkloadEnabledLowMax,
+    NoPreload,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum TestRole {
+    Admin,
+    App,
+    Reader,
+}
+
+/// A PostgreSQL container seeded with the rental schema and data from
+/// `tests/_data` as a template database.
+///
+/// Methods block on a runtime of their own, so call them from `#[test]`
+/// functions rather than `#[tokio::test]`.
+pub struct TestPostgres {
+    _container: Container<postgres::Postgres>,
+    runtime: tokio::runtime::Runtime,
+    host: String,
+    port: u16,
+    version_tag: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TestDatabase {
+    host: String,
+    port: u16,
+    name: String,
+}
+
+/// Major versions to test against: the comma-separated
+/// `POSTGREAT_TEST_PG_VERSIONS`, else `POSTGREAT_TEST_PG_VERSION`, else
+/// [`DEFAULT_VERSIONS`]
+pub fn versions() -> Vec<String> {
+    match env::var("POSTGREAT_TEST_PG_VERSIONS") {
+        Ok(list) => list
+            .split(',')
+            .map(str::trim)
+            .filter(|version| !version.is_empty())
+            .map(String::from)
+            .collect(),
+        Err(_) => match env::var("POSTGREAT_TEST_PG_VERSION") {
+            Ok(version) => vec![version],
+            Err(_) => DEFAULT_VERSIONS.iter().map(|v| v.to_string()).collect(),
+        },
+    }
+}
+
+/// Starts a container for each of [`versions`] in turn and runs `test`
+/// against it, stopping each container before the next starts
+pub fn for_each_version(profile: ContainerProfile, mut test: impl FnMut(&TestPostgres)) {
+    for version in versions() {
+        test(&TestPostgres::start_version(&version, profile));
+    }
+}
+
+impl TestPostgres {
+    /// Starts the version in `POSTGREAT_TEST_PG_VERSION`, or 18
+    pub fn start(profile: ContainerProfile) -> Self {
+        let version_tag = env::var("POSTGREAT_TEST_PG_VERSION").unwrap_or_else(|_| "18".into());
+        Self::start_version(&version_tag, profile)
+    }
+
+    /// Starts the `postgres` image tagged `version`, e.g. `16` or `17.2`
+    pub fn start_version(version: &str, profile: ContainerProfile) -> Self {
+        let image = postgres::Postgres::default()
+            .with_init_sql(template_init_sql())
+            .with_tag(version)
+            .with_cmd(container_cmd(profile))
+            .with_startup_timeout(Duration::from_secs(120));
+        let container = image.start().expect("postgres test container should start");
+        let host = container
+            .get_host()
+            .expect("postgres host should resolve")
+            .to_string();
+        let port = container
+            .get_host_port_ipv4(5432)
+            .expect("postgres port should resolve");
+        let runtime = tokio::runtime::Runtime::new().expect("tokio runtime should start");
+
+        let server = Self {
+            _container: container,
+            runtime,
+            host,
+            port,
+            version_tag: version.to_string(),
+        };
+        server.seed_template_database();
+        server
+    }
+
+    pub fn version_tag(&self) -> &str {
+        &self.version_tag
+    }
+
+    /// Clones the seeded template into a database of its own, so tests
+    /// sharing a container do not see each other's changes
+    pub fn create_test_database(&self, suffix: &str) -> TestDatabase {
+        let db_name = format!(
+            "pgtest_{}_{}_{}",
+            sanitize_ident(suffix),
+            sanitize_ident(self.version_tag()),
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("clock should be after unix epoch")
+                .as_millis()
+        );
+        let create_sql = format!(
+            "CREATE DATABASE {} TEMPLATE {}",
+            quote_ident(&db_name),
+            quote_ident(TEMPLATE_DB)
+        );
+        let grant_sql = format!(
+            "GRANT CONNECT ON DATABASE {} TO {}, {}",
+            quote_ident(&db_name),
+            quote_ident(APP_USER),
+            quote_ident(READER_USER)
+        );
+
+        self.runtime.block_on(async {
+            let admin_pool = self.admin_pool("postgres").await;
+            sqlx::query(&create_sql)
+                .execute(&admin_pool)
+                .await
+                .expect("test database should be created from template");
+            sqlx::query(&grant_sql)
+                .execute(&admin_pool)
+                .await
+                .expect("test roles should receive CONNECT on cloned database");
+        });
+
+        TestDatabase {
+            host: self.host.clone(),
+            port: self.port,
+            name: db_name,
+        }
+    }
+
+    /// Bloats `rental`, drops its useful indexes and adds unused ones
+    pub fn apply_table_index_fixture(&self, db: &TestDatabase) {
+        self.execute_sql(db, TestRole::Admin, FIXTURE_BLOAT_SQL);
+        self.execute_sql(db, TestRole::Admin, "ANALYZE;");
+    }
+
+    /// Runs the application queries behind the workload findings
+    pub fn run_workload_fixture_as_app(&self, db: &TestDatabase) {
+        self.execute_sql(db, TestRole::App, FIXTURE_WORKLOAD_SQL);
+    }
+
+    pub fn reset_pg_stat_statements(&self, db: &TestDatabase) {
+        self.execute_sql(db, TestRole::Admin, "SELECT pg_stat_statements_reset();");
+    }
+
+    pub fn drop_pg_stat_statements_extension(&self, db: &TestDatabase) {
+        self.execute_sql(
+            db,
+            TestRole::Admin,
+            "DROP EXTENSION IF EXISTS pg_stat_statements;",
+        );
+    }
+
+    pub fn generate_distinct_queries_as_app(&self, db: &TestDatabase, count: usize) {
+        self.runtime.block_on(async {
+            let pool = self.pool_for_role(db, TestRole::App).await;
+            for query_shape in 1..=count {
+                let mut predicates = Vec::new();
+                for value in 1..=query_shape {
+                    predicates.push(format!("rental_id = {value}"));
+                }
+                let sql = format!(
+                    "SELECT COUNT(*) FROM rental WHERE {};",
+                    predicates.join(" OR ")
+                );
+                sqlx::query(&sql)
+                    .execute(&pool)
+                    .await
+                    .expect("distinct workload query should execute");
+            }
+        });
+    }
+
+    pub fn pg_stat_statements_diagnostics(&self, db: &TestDatabase) -> Value {
+        self.runtime.block_on(async {
+            let pool = self.pool_for_role(db, TestRole::Admin).await;
+            let max_entries: i64 =
+                sqlx::query_scalar("SELECT current_setting('pg_stat_statements.max')::bigint")
+                    .fetch_one(&pool)
+                    .await
+                    .expect("pg_stat_statements.max should be readable");
+            let statement_count: i64 =
+                sqlx::query_scalar("SELECT count(*)::bigint FROM pg_stat_statements")
+                    .fetch_one(&pool)
+                    .await
+                    .expect("pg_stat_statements count should be readable");
+            let deallocations: i64 = sqlx::query_scalar(
+                "SELECT COALESCE(dealloc, 0)::bigint FROM pg_stat_statements_info",
+            )
+            .fetch_one(&pool)
+            .await
+            .expect("pg_stat_statements_info should be readable");
+            json!({
+                "pg_stat_statements_max": max_entries,
+                "statement_count": statement_count,
+                "deallocations": deallocations,
+            })
+        })
+    }
+
+    /// Runs `sql`, which may hold several statements, as `role`; use it to
+    /// load workloads of your own
+    pub fn execute_sql(&self, db: &TestDatabase, role: TestRole, sql: &str) {
+        self.runtime.block_on(async {
+            let pool = self.pool_for_role(db, role).await;
+            raw_sql(sql)
+                .execute(&pool)
+                .await
+                .expect("fixture SQL should execute");
+        });
+    }
+
+    pub fn create_readonly_role(&self, db: &TestDatabase, role_name: &str, password: &str) {
+        let sql = format!(
+            "CREATE ROLE {role} LOGIN PASSWORD {password};
+             GRANT pg_read_all_settings TO {role};
+             GRANT pg_read_all_stats TO {role};
+             GRANT CONNECT ON DATABASE {database} TO {role};
+             GRANT USAGE ON SCHEMA public TO {role};
+             GRANT SELECT ON ALL TABLES IN SCHEMA public TO {role};",
+            role = quote_ident(role_name),
+            password = quote_literal(password),
+            database = quote_ident(db.name()),
+        );
+        self.execute_sql(db, TestRole::Admin, &sql);
+    }
+
+    /// Runs `analyze` in process as `role` and returns the results, before
+    /// `--rules` overrides or localization
+    pub fn analyze(
+        &self,
+        db: &TestDatabase,
+        role: TestRole,
+        options: &AnalyzeOptions,
+    ) -> AnalysisResults {
+        let config = db.config(role);
+        self.runtime.block_on(async {
+            let mut checker = ConfigChecker::new(config)
+                .await
+                .expect("checker should connect to the test database");
+            checker
+                .analyze(options)
+                .await
+                .expect("analysis should succeed")
+        })
+    }
+
+    /// A pool connected as `role`, e.g. to run a custom check with
+    /// [`TestPostgres::block_on`]
+    pub fn pool(&self, db: &TestDatabase, role: TestRole) -> Pool<Postgres> {
+        self.runtime.block_on(self.pool_for_role(db, role))
+    }
+
+    /// Runs a future on the harness's runtime
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    fn seed_template_database(&self) {
+        self.runtime.block_on(async {
+            let template_pool = self.admin_pool(TEMPLATE_DB).await;
+            sqlx::query("SELECT 1")
+                .execute(&template_pool)
+                .await
+                .expect("template database should be ready after init SQL");
+        });
+    }
+
+    async fn admin_pool(&self, database: &str) -> Pool<Postgres> {
+        connect_pool(&self.host, self.port, database, ADMIN_USER, ADMIN_PASSWORD).await
+    }
+
+    async fn pool_for_role(&self, db: &TestDatabase, role: TestRole) -> Pool<Postgres> {
+        let credentials = db.credentials(role);
+        connect_pool(&db.host, db.port, &db.name, credentials.0, credentials.1).await
+    }
+}
+
+impl TestDatabase {
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn credentials(&self, role: TestRole) -> (&'static str, &'static str) {
+        match role {
+            TestRole::Admin => (ADMIN_USER, ADMIN_PASSWORD),
+            TestRole::App => (APP_USER, APP_PASSWORD),
+            TestRole::Reader => (READER_USER, READER_PASSWORD),
+        }
+    }
+
+    /// Connection settings for `role` on SSD storage with [`TEST_COMPUTE`]
+    pub fn config(&self, role: TestRole) -> DbConfig {
+        let (username, password) = self.credentials(role);
+        DbConfig::from_connection_params(
+            self.host.clone(),
+            self.port,
+            self.name.clone(),
+            username.to_string(),
+            password.to_string(),
+            Some(TEST_COMPUTE.to_string()),
+            StorageType::Ssd,
+            WorkloadType::Oltp,
+            None,
+        )
+    }
+}
+
+/// The suggestion for `parameter` in `category`, if any
+pub fn find_suggestion<'a>(
+    results: &'a AnalysisResults,
+    category: ConfigCategory,
+    parameter: &str,
+) -> Option<&'a ConfigSuggestion> {
+    results
+        .suggestions_by_category
+        .get(&category)?
+        .iter()
+        .find(|suggestion| suggestion.parameter == parameter)
+}
+
+/// Panics, listing the category's suggestions, unless one is for `parameter`
+#[track_caller]
+pub fn assert_suggestion<'a>(
+    results: &'a AnalysisResults,
+    category: ConfigCategory,
+    parameter: &str,
+) -> &'a ConfigSuggestion {
+    find_suggestion(results, category, parameter).unwrap_or_else(|| {
+        panic!(
+            "expected a {category:?} suggestion for {parameter}, found: {:?}",
+            parameters(results, category)
+        )
+    })
+}
+
+/// Panics, naming the suggestion, if one is for `parameter`
+#[track_caller]
+pub fn assert_no_suggestion(results: &AnalysisResults, category: ConfigCategory, parameter: &str) {
+    if let Some(suggestion) = find_suggestion(results, category, parameter) {
+        panic!("expected no {category:?} suggestion for {parameter}, found: {suggestion:?}");
+    }
+}
+
+fn parameters(results: &AnalysisResults, category: ConfigCategory) -> Vec<&str> {
+    results
+        .suggestions_by_category
+        .get(&category)
+        .into_iter()
+        .flatten()
+        .map(|suggestion| suggestion.parameter.as_str())
+        .collect()
+}
+
+fn container_cmd(profile: ContainerProfile) -> Vec<String> {
+    let mut args = vec!["-c".into()];
+    match profile {
+        ContainerProfile::WorkloadEnabled => {
+            args.push("shared_preload_libraries=pg_stat_statements".into());
+        }
+        ContainerProfile::WorkloadEnabledLowMax => {
+            args.push("shared_preload_libraries=pg_stat_statements".into());
+            args.extend(["-c".into(), "pg_stat_statements.max=100".into()]);
+        }
+        ContainerProfile::NoPreload => {
+            args.push("log_min_messages=warning".into());
+        }
+    }
+
+    if !matches!(profile, ContainerProfile::NoPreload) {
+        args.extend([
+            "-c".into(),
+            "compute_query_id=on".into(),
+            "-c".into(),
+            "pg_stat_statements.track=all".into(),
+        ]);
+    }
+
+    args
+}
+
+fn template_init_sql() -> Vec<u8> {
+    format!(
+        "CREATE DATABASE {template_db};\n\\connect {template_db}\n{schema}\n{data}\nSET search_path = public;\n{roles}\nANALYZE;\n",
+        template_db = TEMPLATE_DB,
+        schema = FIXTURE_SCHEMA_SQL,
+        data = FIXTURE_DATA_SQL,
+        roles = FIXTURE_ROLES_SQL,
+    )
+    .into_bytes()
+}
+
+fn quote_ident(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn sanitize_ident(value: &str) -> String {
+    value
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() {
+                ch.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+async fn connect_pool(
+    host: &str,
+    port: u16,
+    database: &str,
+    username: &str,
+    password: &str,
+) -> Pool<Postgres> {
+    let options = PgConnectOptions::new()
+        .host(host)
+        .port(port)
+        .database(database)
+        .username(username)
+        .password(password);
+    PgPoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await
+        .expect("pool should connect")
+}
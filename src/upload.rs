@@ -0,0 +1,223 @@
+use crate::config::DbConfig;
+use crate::models::AnalysisResults;
+use crate::reporter::{ReportFormat, Reporter, ReporterError};
+use crate::signing::SigningKey;
+use chrono::{DateTime, Utc};
+use object_store::path::Path;
+use object_store::{ObjectStore, PutPayload};
+use snafu::{ResultExt, Snafu};
+use url::Url;
+
+#[derive(Debug, Snafu)]
+pub enum UploadError {
+    #[snafu(display("Invalid upload URL '{}': {}", url, source))]
+    InvalidUrl {
+        url: String,
+        source: url::ParseError,
+    },
+
+    #[snafu(display("Unsupported upload URL '{}': {}", url, source))]
+    Store {
+        url: String,
+        source: object_store::Error,
+    },
+
+    #[snafu(display("Failed to render the report: {}", source))]
+    Render { source: ReporterError },
+
+    #[snafu(display("Failed to upload {}: {}", key, source))]
+    Put {
+        key: Path,
+        source: object_store::Error,
+    },
+}
+
+type Result<T, E = UploadError> = std::result::Result<T, E>;
+
+/// Environment variables passed on to the object store, e.g. `AWS_REGION`,
+/// `GOOGLE_SERVICE_ACCOUNT` or `AZURE_STORAGE_ACCOUNT_KEY`
+const CREDENTIAL_ENV_PREFIXES: [&str; 3] = ["AWS_", "GOOGLE_", "AZURE_"];
+
+/// Pushes each run's JSON report, and the report rendered in another format,
+/// to S3, GCS or Azure Blob Storage under date-partitioned keys
+pub struct ReportUploader {
+    store: Box<dyn ObjectStore>,
+    prefix: Path,
+    signing_key: Option<SigningKey>,
+}
+
+impl ReportUploader {
+    /// `s3://bucket/prefix/`, `gs://bucket/prefix/` or `az://container/prefix/`,
+    /// with credentials taken from the usual environment variables
+    pub fn new(url: &str) -> Result<Self> {
+        let parsed = Url::parse(url).context(InvalidUrlSnafu { url })?;
+        let options = std::env::vars()
+            .filter(|(key, _)| {
+                CREDENTIAL_ENV_PREFIXES
+                    .iter()
+                    .any(|prefix| key.starts_with(prefix))
+            })
+            .map(|(key, value)| (key.to_ascii_lowercase(), value));
+        let (store, prefix) =
+            object_store::parse_url_opts(&parsed, options).context(StoreSnafu { url })?;
+        Ok(Self {
+            store,
+            prefix,
+            signing_key: None,
+        })
+    }
+
+    /// Signs the uploaded JSON report as `--signing-key-file` does
+    pub fn with_signing_key(mut self, key: Option<SigningKey>) -> Self {
+        self.signing_key = key;
+        self
+    }
+
+    /// Uploads the JSON report, which `postgreat report --from` can read back,
+    /// and the report as `reporter` renders it in `format`; a JSON report is
+    /// not uploaded twice. Returns the keys written
+    pub async fn upload(
+        &self,
+        config: &DbConfig,
+        results: &AnalysisResults,
+        reporter: &Reporter,
+        format: ReportFormat,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<Path>> {
+        let dir = self.run_dir(config, at);
+        let stamp = at.format("%Y%m%dT%H%M%SZ");
+
+        let mut json = Vec::new();
+        Reporter::new(ReportFormat::Json)
+            .with_signing_key(self.signing_key.clone())
+            .write(&mut json, results)
+            .context(RenderSnafu)?;
+        let mut objects = vec![(dir.child(format!("{stamp}.json")), json)];
+        if !matches!(format, ReportFormat::Json) {
+            let mut report = Vec::new();
+            reporter.write(&mut report, results).context(RenderSnafu)?;
+            objects.push((
+                dir.child(format!("{stamp}.report.{}", format.extension())),
+                report,
+            ));
+        }
+
+        let mut keys = Vec::new();
+        for (key, bytes) in objects {
+            self.store
+                .put(&key, PutPayload::from(bytes))
+                .await
+                .context(PutSnafu { key: key.clone() })?;
+            keys.push(key);
+        }
+        Ok(keys)
+    }
+
+    /// `<prefix>/date=2026-10-17/production_db@db1.example.com`, partitioned
+    /// the way Athena and BigQuery external tables expect
+    fn run_dir(&self, config: &DbConfig, at: DateTime<Utc>) -> Path {
+        self.prefix
+            .child(format!("date={}", at.format("%Y-%m-%d")))
+            .child(format!("{}@{}", config.database, config.host))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use object_store::memory::InMemory;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn report_and_results_land_under_date_partitioned_keys() {
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let uploader = ReportUploader {
+            store: Box::new(store.clone()),
+            prefix: Path::from("postgreat/reports"),
+            signing_key: None,
+        };
+        let at = Utc.with_ymd_and_hms(2026, 10, 17, 3, 0, 0).unwrap();
+        let results = AnalysisResults::default();
+
+        let keys = uploader
+            .upload(
+                &DbConfig::for_tests("db1.example.com", "production_db"),
+                &results,
+                &Reporter::new(ReportFormat::Html),
+                ReportFormat::Html,
+                at,
+            )
+            .await
+            .unwrap();
+        let keys: Vec<_> = keys.iter().map(Path::as_ref).collect();
+        assert_eq!(
+            keys,
+            [
+                "postgreat/reports/date=2026-10-17/production_db@db1.example.com/20261017T030000Z.json",
+                "postgreat/reports/date=2026-10-17/production_db@db1.example.com/20261017T030000Z.report.html",
+            ]
+        );
+
+        let raw = store
+            .get(&Path::from(keys[0]))
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+        assert!(serde_json::from_slice::<AnalysisResults>(&raw).is_ok());
+
+        let json_only = uploader
+            .upload(
+                &DbConfig::for_tests("db1.example.com", "production_db"),
+                &results,
+                &Reporter::new(ReportFormat::Json),
+                ReportFormat::Json,
+                at,
+            )
+            .await
+            .unwrap();
+        assert_eq!(json_only.len(), 1);
+    }
+
+    #[test]
+    fn unknown_schemes_are_rejected() {
+        assert!(matches!(
+            ReportUploader::new("ftp://archive.example.com/reports/"),
+            Err(UploadError::Store { .. })
+        ));
+        assert!(matches!(
+            ReportUploader::new("reports/"),
+            Err(UploadError::InvalidUrl { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn uploaded_json_report_is_signed_with_the_key() {
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let key = SigningKey::new("s3cret");
+        let uploader = ReportUploader {
+            store: Box::new(store.clone()),
+            prefix: Path::from("postgreat/reports"),
+            signing_key: None,
+        }
+        .with_signing_key(Some(key.clone()));
+        let at = Utc.with_ymd_and_hms(2026, 10, 17, 3, 0, 0).unwrap();
+
+        let keys = uploader
+            .upload(
+                &DbConfig::for_tests("db1.example.com", "production_db"),
+                &AnalysisResults::default(),
+                &Reporter::new(ReportFormat::Html),
+                ReportFormat::Html,
+                at,
+            )
+            .await
+            .unwrap();
+        let raw = store.get(&keys[0]).await.unwrap().bytes().await.unwrap();
+        let report: serde_json::Value = serde_json::from_slice(&raw).unwrap();
+        crate::signing::verify(&report, &key).unwrap();
+        assert!(serde_json::from_slice::<AnalysisResults>(&raw).is_ok());
+    }
+}
@@ -1,6 +1,8 @@
 mod support;
 
-use support::{analyze_snapshot_view, parse_json_output, ContainerProfile, TestPostgres, TestRole};
+use support::{
+    analyze_snapshot_view, parse_json_output, CliCommands, ContainerProfile, TestPostgres, TestRole,
+};
 
 #[test]
 #[ignore = "requires Docker"]
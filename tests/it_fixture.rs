@@ -0,0 +1,128 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use serde_json::Value;
+
+/// Recorded with `--record-fixture` from PostgreSQL 15 loaded with the SQL in
+/// `tests/_data`, so analyzers and reporters run without Docker
+const FIXTURE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/_data/fixture-pg15");
+
+#[test]
+fn analyze_fixture_json_snapshot() {
+    let assert = cargo_bin_cmd!("postgreat")
+        .arg("--format")
+        .arg("json")
+        .arg("analyze")
+        .arg("--fixture")
+        .arg(FIXTURE_DIR)
+        .arg("--deep")
+        .arg("--schema-lint")
+        .arg("--compute")
+        .arg("8vCPU-64GB")
+        .assert()
+        .success();
+    let mut json: Value = serde_json::from_slice(&assert.get_output().stdout)
+        .expect("CLI output should be valid JSON");
+
+    // The only value read from the clock rather than the fixture
+    json["vacuum_activity"]["captured_at"] = Value::Null;
+    // Machine, build and time of this run
+    let run = json.as_object_mut().unwrap().remove("run");
+    assert!(run.is_some_and(|run| run["target_fingerprint"].is_string()));
+    insta::assert_json_snapshot!("analyze_fixture_pg15", json);
+}
+
+#[test]
+fn analyze_fixture_needs_no_credentials() {
+    cargo_bin_cmd!("postgreat")
+        .arg("analyze")
+        .arg("--fixture")
+        .arg(FIXTURE_DIR)
+        .assert()
+        .success();
+}
+
+#[test]
+fn analyze_fixture_gentle_skips_index_statistics() {
+    let assert = cargo_bin_cmd!("postgreat")
+        .arg("--format")
+        .arg("json")
+        .arg("analyze")
+        .arg("--fixture")
+        .arg(FIXTURE_DIR)
+        .arg("--deep")
+        .arg("--gentle")
+        .assert()
+        .success();
+    let json: Value = serde_json::from_slice(&assert.get_output().stdout)
+        .expect("CLI output should be valid JSON");
+
+    assert!(json["warnings"]
+        .to_string()
+        .contains("--gentle does not read index statistics"));
+    assert!(json["workload"]["warnings"]
+        .to_string()
+        .contains("--gentle does not read the index catalog"));
+    assert!(json["workload"]["query_index_candidates"]
+        .as_array()
+        .is_none_or(|candidates| candidates.is_empty()));
+}
+
+#[test]
+fn analyze_fixture_redacts_workload_queries() {
+    let assert = cargo_bin_cmd!("postgreat")
+        .arg("--format")
+        .arg("json")
+        .arg("analyze")
+        .arg("--fixture")
+        .arg(FIXTURE_DIR)
+        .arg("--deep")
+        .arg("--redact-queries")
+        .assert()
+        .success();
+    let json: Value = serde_json::from_slice(&assert.get_output().stdout)
+        .expect("CLI output should be valid JSON");
+
+    let workload = &json["workload"];
+    assert_eq!(workload["workload_metadata"]["queries_redacted"], true);
+    let query = workload["slow_query_groups"][0]["queries"][0]["query_text"]
+        .as_str()
+        .unwrap();
+    assert!(query.contains("n_"), "{query}");
+}
+
+#[test]
+fn signed_fixture_report_verifies_until_changed() {
+    let dir = tempfile::tempdir().unwrap();
+    let key = dir.path().join("signing.key");
+    let report = dir.path().join("report.json");
+    std::fs::write(&key, "audit-secret\n").unwrap();
+
+    let assert = cargo_bin_cmd!("postgreat")
+        .arg("--format")
+        .arg("json")
+        .arg("--signing-key-file")
+        .arg(&key)
+        .arg("analyze")
+        .arg("--fixture")
+        .arg(FIXTURE_DIR)
+        .assert()
+        .success();
+    std::fs::write(&report, &assert.get_output().stdout).unwrap();
+    cargo_bin_cmd!("postgreat")
+        .arg("--signing-key-file")
+        .arg(&key)
+        .arg("verify")
+        .arg(&report)
+        .assert()
+        .success();
+
+    let mut json: Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    json["warnings"] = Value::Array(Vec::new());
+    std::fs::write(&report, serde_json::to_vec(&json).unwrap()).unwrap();
+    cargo_bin_cmd!("postgreat")
+        .arg("--signing-key-file")
+        .arg(&key)
+        .arg("verify")
+        .arg(&report)
+        .assert()
+        .failure();
+}
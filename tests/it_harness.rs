@@ -0,0 +1,66 @@
+use postgreat::checker::AnalyzeOptions;
+use postgreat::models::ConfigCategory;
+use postgreat::testing::{
+    assert_no_suggestion, assert_suggestion, for_each_version, ContainerProfile, TestRole,
+};
+
+#[test]
+#[ignore = "requires Docker"]
+fn in_process_analysis_across_versions() {
+    for_each_version(ContainerProfile::WorkloadEnabled, |server| {
+        let db = server.create_test_database("harness");
+        server.apply_table_index_fixture(&db);
+
+        let results = server.analyze(&db, TestRole::Admin, &AnalyzeOptions::default());
+
+        assert!(
+            results
+                .table_health
+                .bloat_info
+                .iter()
+                .any(|table| table.table_name == "rental"),
+            "expected seeded rental bloat on PostgreSQL {}",
+            server.version_tag()
+        );
+        // Container defaults assume spinning disks; the test config is SSD
+        assert_suggestion(&results, ConfigCategory::Planner, "random_page_cost");
+        assert_no_suggestion(&results, ConfigCategory::Planner, "no_such_parameter");
+    });
+}
+
+#[test]
+#[ignore = "requires Docker"]
+fn checkpoint_counters_on_every_version() {
+    // Checkpoint counters moved from pg_stat_bgwriter to pg_stat_checkpointer in 17
+    for_each_version(ContainerProfile::WorkloadEnabled, |server| {
+        let db = server.create_test_database("harness_checkpoints");
+
+        let results = server.analyze(&db, TestRole::Admin, &AnalyzeOptions::default());
+
+        let stats = &results.system_stats;
+        assert!(
+            stats.checkpoints_timed.is_some() && stats.checkpoints_req.is_some(),
+            "expected checkpoint counters on PostgreSQL {}",
+            server.version_tag()
+        );
+        assert!(
+            stats.buffer_writes.is_some(),
+            "expected buffer writes on PostgreSQL {}",
+            server.version_tag()
+        );
+    });
+}
+
+#[test]
+#[ignore = "requires Docker"]
+fn custom_checks_run_against_the_pool() {
+    for_each_version(ContainerProfile::WorkloadEnabled, |server| {
+        let db = server.create_test_database("harness_pool");
+        let pool = server.pool(&db, TestRole::Reader);
+
+        let rentals: i64 = server
+            .block_on(sqlx::query_scalar("SELECT count(*) FROM rental").fetch_one(&pool))
+            .expect("reader should see the seeded tables");
+        assert!(rentals > 0);
+    });
+}
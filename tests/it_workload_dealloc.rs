@@ -1,7 +1,8 @@
 mod support;
 
 use support::{
-    parse_json_output, workload_dealloc_snapshot_view, ContainerProfile, TestPostgres, TestRole,
+    parse_json_output, workload_dealloc_snapshot_view, CliCommands, ContainerProfile, TestPostgres,
+    TestRole,
 };
 
 #[test]
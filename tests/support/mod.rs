@@ -2,208 +2,30 @@
 
 use assert_cmd::{cargo::cargo_bin_cmd, Command};
 use serde_json::{json, Value};
-use sqlx::{
-    postgres::{PgConnectOptions, PgPoolOptions},
-    raw_sql, Pool, Postgres,
-};
-use std::env;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use testcontainers_modules::{
-    postgres,
-    testcontainers::{runners::SyncRunner, Container, ImageExt},
-};
 
-const TEMPLATE_DB: &str = "postgreat_template";
-const ADMIN_USER: &str = "postgres";
-const ADMIN_PASSWORD: &str = "postgres";
-const APP_USER: &str = "app_user";
-const APP_PASSWORD: &str = "app_password";
-const READER_USER: &str = "reader_user";
-const READER_PASSWORD: &str = "reader_password";
+pub use postgreat::testing::*;
 
-const FIXTURE_SCHEMA_SQL: &str = include_str!("../_data/0-schema.sql");
-const FIXTURE_DATA_SQL: &str = include_str!("../_data/1-data.sql");
-const FIXTURE_BLOAT_SQL: &str = include_str!("../_data/2-bloat-and-indexes.sql");
-const FIXTURE_ROLES_SQL: &str = include_str!("../_data/00-extensions-and-roles.sql");
-const FIXTURE_WORKLOAD_SQL: &str = include_str!("../_data/3-workload.sql");
+/// `postgreat` commands against a test database
+pub trait CliCommands {
+    fn analyze_command(&self, db: &TestDatabase, role: TestRole) -> Command;
 
-#[derive(Debug, Clone, Copy)]
-pub enum ContainerProfile {
-    WorkloadEnabled,
-    WorkloadEnabledLowMax,
-    NoPreload,
-}
-
-#[derive(Debug, Clone, Copy)]
-pub enum TestRole {
-    Admin,
-    App,
-    Reader,
-}
-
-pub struct TestPostgres {
-    _container: Container<postgres::Postgres>,
-    runtime: tokio::runtime::Runtime,
-    host: String,
-    port: u16,
-    version_tag: String,
-}
+    fn analyze_command_with_credentials(
+        &self,
+        db: &TestDatabase,
+        username: &str,
+        password: &str,
+    ) -> Command;
 
-#[derive(Debug, Clone)]
-pub struct TestDatabase {
-    host: String,
-    port: u16,
-    name: String,
+    fn workload_command(&self, db: &TestDatabase, role: TestRole) -> Command;
 }
 
-impl TestPostgres {
-    pub fn start(profile: ContainerProfile) -> Self {
-        let version_tag = env::var("POSTGREAT_TEST_PG_VERSION").unwrap_or_else(|_| "18".into());
-        let image = postgres::Postgres::default()
-            .with_init_sql(template_init_sql())
-            .with_tag(version_tag.as_str())
-            .with_cmd(container_cmd(profile))
-            .with_startup_timeout(Duration::from_secs(120));
-        let container = image.start().expect("postgres test container should start");
-        let host = container
-            .get_host()
-            .expect("postgres host should resolve")
-            .to_string();
-        let port = container
-            .get_host_port_ipv4(5432)
-            .expect("postgres port should resolve");
-        let runtime = tokio::runtime::Runtime::new().expect("tokio runtime should start");
-
-        let server = Self {
-            _container: container,
-            runtime,
-            host,
-            port,
-            version_tag,
-        };
-        server.seed_template_database();
-        server
-    }
-
-    pub fn version_tag(&self) -> &str {
-        &self.version_tag
-    }
-
-    pub fn create_test_database(&self, suffix: &str) -> TestDatabase {
-        let db_name = format!(
-            "pgtest_{}_{}_{}",
-            sanitize_ident(suffix),
-            sanitize_ident(self.version_tag()),
-            SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("clock should be after unix epoch")
-                .as_millis()
-        );
-        let create_sql = format!(
-            "CREATE DATABASE {} TEMPLATE {}",
-            quote_ident(&db_name),
-            quote_ident(TEMPLATE_DB)
-        );
-        let grant_sql = format!(
-            "GRANT CONNECT ON DATABASE {} TO {}, {}",
-            quote_ident(&db_name),
-            quote_ident(APP_USER),
-            quote_ident(READER_USER)
-        );
-
-        self.runtime.block_on(async {
-            let admin_pool = self.admin_pool("postgres").await;
-            sqlx::query(&create_sql)
-                .execute(&admin_pool)
-                .await
-                .expect("test database should be created from template");
-            sqlx::query(&grant_sql)
-                .execute(&admin_pool)
-                .await
-                .expect("test roles should receive CONNECT on cloned database");
-        });
-
-        TestDatabase {
-            host: self.host.clone(),
-            port: self.port,
-            name: db_name,
-        }
-    }
-
-    pub fn apply_table_index_fixture(&self, db: &TestDatabase) {
-        self.execute_sql(db, TestRole::Admin, FIXTURE_BLOAT_SQL);
-        self.execute_sql(db, TestRole::Admin, "ANALYZE;");
-    }
-
-    pub fn run_workload_fixture_as_app(&self, db: &TestDatabase) {
-        self.execute_sql(db, TestRole::App, FIXTURE_WORKLOAD_SQL);
-    }
-
-    pub fn reset_pg_stat_statements(&self, db: &TestDatabase) {
-        self.execute_sql(db, TestRole::Admin, "SELECT pg_stat_statements_reset();");
-    }
-
-    pub fn drop_pg_stat_statements_extension(&self, db: &TestDatabase) {
-        self.execute_sql(
-            db,
-            TestRole::Admin,
-            "DROP EXTENSION IF EXISTS pg_stat_statements;",
-        );
-    }
-
-    pub fn generate_distinct_queries_as_app(&self, db: &TestDatabase, count: usize) {
-        self.runtime.block_on(async {
-            let pool = self.pool_for_role(db, TestRole::App).await;
-            for query_shape in 1..=count {
-                let mut predicates = Vec::new();
-                for value in 1..=query_shape {
-                    predicates.push(format!("rental_id = {value}"));
-                }
-                let sql = format!(
-                    "SELECT COUNT(*) FROM rental WHERE {};",
-                    predicates.join(" OR ")
-                );
-                sqlx::query(&sql)
-                    .execute(&pool)
-                    .await
-                    .expect("distinct workload query should execute");
-            }
-        });
-    }
-
-    pub fn pg_stat_statements_diagnostics(&self, db: &TestDatabase) -> Value {
-        self.runtime.block_on(async {
-            let pool = self.pool_for_role(db, TestRole::Admin).await;
-            let max_entries: i64 =
-                sqlx::query_scalar("SELECT current_setting('pg_stat_statements.max')::bigint")
-                    .fetch_one(&pool)
-                    .await
-                    .expect("pg_stat_statements.max should be readable");
-            let statement_count: i64 =
-                sqlx::query_scalar("SELECT count(*)::bigint FROM pg_stat_statements")
-                    .fetch_one(&pool)
-                    .await
-                    .expect("pg_stat_statements count should be readable");
-            let deallocations: i64 = sqlx::query_scalar(
-                "SELECT COALESCE(dealloc, 0)::bigint FROM pg_stat_statements_info",
-            )
-            .fetch_one(&pool)
-            .await
-            .expect("pg_stat_statements_info should be readable");
-            json!({
-                "pg_stat_statements_max": max_entries,
-                "statement_count": statement_count,
-                "deallocations": deallocations,
-            })
-        })
-    }
-
-    pub fn analyze_command(&self, db: &TestDatabase, role: TestRole) -> Command {
+impl CliCommands for TestPostgres {
+    fn analyze_command(&self, db: &TestDatabase, role: TestRole) -> Command {
         let credentials = db.credentials(role);
         self.analyze_command_with_credentials(db, credentials.0, credentials.1)
     }
 
-    pub fn analyze_command_with_credentials(
+    fn analyze_command_with_credentials(
         &self,
         db: &TestDatabase,
         username: &str,
@@ -229,7 +51,7 @@ impl TestPostgres {
         command
     }
 
-    pub fn workload_command(&self, db: &TestDatabase, role: TestRole) -> Command {
+    fn workload_command(&self, db: &TestDatabase, role: TestRole) -> Command {
         let mut command = cargo_bin_cmd!("postgreat");
         let credentials = db.credentials(role);
         command.args([
@@ -254,72 +76,6 @@ impl TestPostgres {
         ]);
         command
     }
-
-    fn seed_template_database(&self) {
-        self.runtime.block_on(async {
-            let template_pool = self.admin_pool(TEMPLATE_DB).await;
-            sqlx::query("SELECT 1")
-                .execute(&template_pool)
-                .await
-                .expect("template database should be ready after init SQL");
-        });
-    }
-
-    fn execute_sql(&self, db: &TestDatabase, role: TestRole, sql: &str) {
-        self.runtime.block_on(async {
-            let pool = self.pool_for_role(db, role).await;
-            raw_sql(sql)
-                .execute(&pool)
-                .await
-                .expect("fixture SQL should execute");
-        });
-    }
-
-    pub fn create_readonly_role(&self, db: &TestDatabase, role_name: &str, password: &str) {
-        let sql = format!(
-            "CREATE ROLE {role} LOGIN PASSWORD {password};
-             GRANT pg_read_all_settings TO {role};
-             GRANT pg_read_all_stats TO {role};
-             GRANT CONNECT ON DATABASE {database} TO {role};
-             GRANT USAGE ON SCHEMA public TO {role};
-             GRANT SELECT ON ALL TABLES IN SCHEMA public TO {role};",
-            role = quote_ident(role_name),
-            password = quote_literal(password),
-            database = quote_ident(db.name()),
-        );
-        self.execute_sql(db, TestRole::Admin, &sql);
-    }
-
-    async fn admin_pool(&self, database: &str) -> Pool<Postgres> {
-        connect_pool(&self.host, self.port, database, ADMIN_USER, ADMIN_PASSWORD).await
-    }
-
-    async fn pool_for_role(&self, db: &TestDatabase, role: TestRole) -> Pool<Postgres> {
-        let credentials = db.credentials(role);
-        connect_pool(&db.host, db.port, &db.name, credentials.0, credentials.1).await
-    }
-}
-
-impl TestDatabase {
-    pub fn host(&self) -> &str {
-        &self.host
-    }
-
-    pub fn port(&self) -> u16 {
-        self.port
-    }
-
-    pub fn name(&self) -> &str {
-        &self.name
-    }
-
-    pub fn credentials(&self, role: TestRole) -> (&'static str, &'static str) {
-        match role {
-            TestRole::Admin => (ADMIN_USER, ADMIN_PASSWORD),
-            TestRole::App => (APP_USER, APP_PASSWORD),
-            TestRole::Reader => (READER_USER, READER_PASSWORD),
-        }
-    }
 }
 
 pub fn parse_json_output(output: &[u8]) -> Value {
@@ -379,44 +135,6 @@ pub fn workload_unavailable_snapshot_view(value: &Value) -> Value {
     })
 }
 
-fn container_cmd(profile: ContainerProfile) -> Vec<String> {
-    let mut args = vec!["-c".into()];
-    match profile {
-        ContainerProfile::WorkloadEnabled => {
-            args.push("shared_preload_libraries=pg_stat_statements".into());
-        }
-        ContainerProfile::WorkloadEnabledLowMax => {
-            args.push("shared_preload_libraries=pg_stat_statements".into());
-            args.extend(["-c".into(), "pg_stat_statements.max=100".into()]);
-        }
-        ContainerProfile::NoPreload => {
-            args.push("log_min_messages=warning".into());
-        }
-    }
-
-    if !matches!(profile, ContainerProfile::NoPreload) {
-        args.extend([
-            "-c".into(),
-            "compute_query_id=on".into(),
-            "-c".into(),
-            "pg_stat_statements.track=all".into(),
-        ]);
-    }
-
-    args
-}
-
-fn template_init_sql() -> Vec<u8> {
-    format!(
-        "CREATE DATABASE {template_db};\n\\connect {template_db}\n{schema}\n{data}\nSET search_path = public;\n{roles}\nANALYZE;\n",
-        template_db = TEMPLATE_DB,
-        schema = FIXTURE_SCHEMA_SQL,
-        data = FIXTURE_DATA_SQL,
-        roles = FIXTURE_ROLES_SQL,
-    )
-    .into_bytes()
-}
-
 fn has_table_named(value: &Value, table_name: &str) -> bool {
     value
         .as_array()
@@ -538,44 +256,3 @@ fn has_table_health_note(value: &Value) -> bool {
             })
         })
 }
-
-fn quote_ident(value: &str) -> String {
-    format!("\"{}\"", value.replace('"', "\"\""))
-}
-
-fn quote_literal(value: &str) -> String {
-    format!("'{}'", value.replace('\'', "''"))
-}
-
-fn sanitize_ident(value: &str) -> String {
-    value
-        .chars()
-        .map(|ch| {
-            if ch.is_ascii_alphanumeric() {
-                ch.to_ascii_lowercase()
-            } else {
-                '_'
-            }
-        })
-        .collect()
-}
-
-async fn connect_pool(
-    host: &str,
-    port: u16,
-    database: &str,
-    username: &str,
-    password: &str,
-) -> Pool<Postgres> {
-    let options = PgConnectOptions::new()
-        .host(host)
-        .port(port)
-        .database(database)
-        .username(username)
-        .password(password);
-    PgPoolOptions::new()
-        .max_connections(1)
-        .connect_with(options)
-        .await
-        .expect("pool should connect")
-}